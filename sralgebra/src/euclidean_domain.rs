@@ -0,0 +1,165 @@
+#![doc="Defines the euclidean domain algebraic structure.
+
+A Euclidean domain is an integral domain which can be endowed
+with a euclidean function allowing a suitable generalization
+of the division with remainder of integers.
+
+Every Euclidean domain is a principal ideal domain. Hence
+greatest common divisors exist and can be computed by the
+Euclidean algorithm. They can also be expressed as linear
+combinations of the original elements (Bézout's identity).
+
+These operations are the building blocks of exact elimination
+algorithms like the Hermite and Smith normal forms.
+
+References:
+
+* http://en.wikipedia.org/wiki/Euclidean_domain
+* http://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+
+"]
+
+// external imports
+use num::traits::{Zero, One};
+
+// local imports
+use integral_domain::IntegralDomainPartial;
+
+/// Euclidean domain with partial equivalence
+pub trait EuclideanDomainPartial : IntegralDomainPartial {
+
+    /// Returns the quotient q and remainder r such that
+    /// self = q * other + r and the remainder is smaller
+    /// than other under the euclidean function.
+    ///
+    /// For integers, the remainder is non-negative.
+    fn div_rem_euclid(self, other : Self) -> (Self, Self);
+
+    /// Returns the value of the euclidean function
+    fn euclidean_size(self) -> u64;
+
+    /// Returns a unit u such that u * self is the
+    /// canonical associate of self.
+    ///
+    /// For integers, this is -1 for negative numbers
+    /// and 1 otherwise.
+    fn normalizing_unit(self) -> Self;
+
+    /// Returns the canonical greatest common divisor
+    fn gcd(self, other : Self) -> Self {
+        let (g, _, _) = self.extended_gcd(other);
+        g
+    }
+
+    /// Returns (g, x, y) such that g = x * self + y * other
+    /// is the canonical greatest common divisor.
+    fn extended_gcd(self, other : Self) -> (Self, Self, Self) {
+        let z : Self = Zero::zero();
+        let (mut r0, mut r1) = (self, other);
+        let (mut x0, mut x1) : (Self, Self) = (One::one(), z);
+        let (mut y0, mut y1) : (Self, Self) = (z, One::one());
+        while r1 != z {
+            let (q, r) = r0.div_rem_euclid(r1);
+            r0 = r1;
+            r1 = r;
+            let x = x0 - q * x1;
+            x0 = x1;
+            x1 = x;
+            let y = y0 - q * y1;
+            y0 = y1;
+            y1 = y;
+        }
+        let u = r0.normalizing_unit();
+        (u * r0, u * x0, u * y0)
+    }
+
+    /// Indicates if other divides self
+    fn is_divisible_by(self, other : Self) -> bool {
+        let z : Self = Zero::zero();
+        if other == z {
+            return self == z;
+        }
+        let (_, r) = self.div_rem_euclid(other);
+        r == z
+    }
+}
+
+
+/// Implements the euclidean domain for signed integers
+macro_rules! euclidean_domain_signed {
+    ($t:ty) => (
+        impl EuclideanDomainPartial for $t {
+
+            fn div_rem_euclid(self, other : $t) -> ($t, $t) {
+                let mut q = self / other;
+                let mut r = self % other;
+                if r < 0 {
+                    if other > 0 {
+                        q -= 1;
+                        r += other;
+                    }
+                    else {
+                        q += 1;
+                        r -= other;
+                    }
+                }
+                (q, r)
+            }
+
+            fn euclidean_size(self) -> u64 {
+                (self as i64).wrapping_abs() as u64
+            }
+
+            fn normalizing_unit(self) -> $t {
+                if self < 0 { -1 } else { 1 }
+            }
+        }
+    )
+}
+
+euclidean_domain_signed!(i8);
+euclidean_domain_signed!(i16);
+euclidean_domain_signed!(i32);
+euclidean_domain_signed!(i64);
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_div_rem_euclid() {
+        assert_eq!(7i64.div_rem_euclid(3), (2, 1));
+        assert_eq!((-7i64).div_rem_euclid(3), (-3, 2));
+        assert_eq!(7i64.div_rem_euclid(-3), (-2, 1));
+        assert_eq!((-7i64).div_rem_euclid(-3), (3, 2));
+        assert_eq!((-8i32).div_rem_euclid(4), (-2, 0));
+    }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(12i64.gcd(18), 6);
+        assert_eq!((-12i64).gcd(18), 6);
+        assert_eq!(0i64.gcd(-5), 5);
+        assert_eq!(0i64.gcd(0), 0);
+        for &(a, b) in [(240i64, 46i64), (-35, 15), (17, -5), (0, 7)].iter() {
+            let (g, x, y) = a.extended_gcd(b);
+            assert_eq!(g, a.gcd(b));
+            assert_eq!(x * a + y * b, g);
+        }
+    }
+
+    #[test]
+    fn test_divisibility() {
+        assert!(12i16.is_divisible_by(-4));
+        assert!(!12i16.is_divisible_by(5));
+        assert!(0i16.is_divisible_by(0));
+        assert!(!3i16.is_divisible_by(0));
+    }
+}
@@ -19,6 +19,8 @@ Algebraic structures
 * Commutative group: closure, associativity, identity, inverse, commutativity
 * Ring : commutative group under addition, monoid under multiplication, distributive
 * Commutative Ring: commutative group under addition, commutative monoid under multiplication, distributive (ring + commutative monoid under multiplication)
+* Integral domain: commutative ring without zero divisors
+* Euclidean domain: integral domain with division with remainder
 
 Modules:
 
@@ -29,6 +31,8 @@ Modules:
 * ``monoid``: Monoid and commutative monoid
 * ``group``: Group  and commutative group
 * ``ring``: Ring
+* ``integral_domain``: Integral domain
+* ``euclidean_domain``: Euclidean domain (gcd, extended gcd)
 
 References:
 
@@ -92,6 +96,8 @@ pub use self::commutative_ring::{CommutativeRingPartial, CommutativeRing};
 
 pub use self::integral_domain::{IntegralDomainPartial, IntegralDomain};
 
+pub use self::euclidean_domain::{EuclideanDomainPartial};
+
 pub use self::field::{FieldPartial, Field};
 
 
@@ -106,6 +112,7 @@ pub mod group;
 pub mod ring;
 pub mod commutative_ring;
 pub mod integral_domain;
+pub mod euclidean_domain;
 pub mod field;
 
 pub mod complex;
@@ -162,6 +169,8 @@ pub fn is_commutative_ring<T>(a : &T) where T : CommutativeRing {noop(a)}
 pub fn is_integral_domain_partial<T>(a : &T) where T : IntegralDomainPartial {noop(a)}
 pub fn is_integral_domain<T>(a : &T) where T : IntegralDomain {noop(a)}
 
+pub fn is_euclidean_domain_partial<T>(a : &T) where T : EuclideanDomainPartial {noop(a)}
+
 
 pub fn is_field_partial<T>(a : &T) where T : FieldPartial {noop(a)}
 pub fn is_field<T>(a : &T) where T : Field {noop(a)}
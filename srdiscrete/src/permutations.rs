@@ -11,7 +11,7 @@ use srmatrix::api::Shape;
 
 /// Tells whether a vector is a permutation or not.
 pub fn is_permutation<T:CommutativeMonoidAddPartial+ToPrimitive>(vector : &Matrix<T>)-> bool{
-    assert!(vector.is_vector());
+    assert!(vector.is_vector() || vector.is_scalar());
     let n: usize = vector.num_cells();
     let mut flags : MatrixU8 = Matrix::zeros(n, 1);
     for i in 0..n{
@@ -33,7 +33,7 @@ pub fn is_permutation<T:CommutativeMonoidAddPartial+ToPrimitive>(vector : &Matri
 
 /// Finds the inverse permutation
 pub fn inverse_permutation<T:CommutativeMonoidAddPartial+ToPrimitive+FromPrimitive>(vector : &Matrix<T>)-> Matrix<T>{
-    assert!(vector.is_vector());
+    assert!(vector.is_vector() || vector.is_scalar());
     debug_assert!(is_permutation(vector));
     let n = vector.num_cells();
     let mut result : Matrix<T> = Matrix::zeros(n, 1);
@@ -47,6 +47,33 @@ pub fn inverse_permutation<T:CommutativeMonoidAddPartial+ToPrimitive+FromPrimiti
     result
 }
 
+/// Tells whether a permutation is even (i.e. it can be written
+/// as a product of an even number of transpositions).
+pub fn is_even_permutation<T:CommutativeMonoidAddPartial+ToPrimitive>(vector : &Matrix<T>)-> bool{
+    assert!(vector.is_vector() || vector.is_scalar());
+    debug_assert!(is_permutation(vector));
+    let n = vector.num_cells();
+    let mut visited : MatrixU8 = Matrix::zeros(n, 1);
+    let mut even = true;
+    // Every cycle of length l contributes l - 1 transpositions.
+    for i in 0..n{
+        if visited[i] != 0 {
+            continue;
+        }
+        let mut j = i;
+        let mut length = 0;
+        while visited[j] == 0 {
+            visited.set(j, 0, 1);
+            j = vector[j].to_usize().unwrap();
+            length += 1;
+        }
+        if length % 2 == 0 {
+            even = !even;
+        }
+    }
+    even
+}
+
 
 /******************************************************
  *
@@ -82,5 +109,22 @@ mod test{
         let v2 = inverse_permutation(&v);
         assert_eq!(v2, vector_i64(&[0, 3, 1, 2, 4]));
     }
+
+    #[test]
+    fn test_is_even_permutation(){
+        assert!(is_even_permutation(&vector_i64(&[0, 1, 2])));
+        assert!(!is_even_permutation(&vector_i64(&[1, 0, 2])));
+        assert!(is_even_permutation(&vector_i64(&[2, 0, 1])));
+        assert!(!is_even_permutation(&vector_i64(&[0, 3, 2, 1])));
+        assert!(is_even_permutation(&vector_i64(&[1, 0, 3, 2])));
+    }
+
+    #[test]
+    fn test_single_element_permutation(){
+        let v = vector_i64(&[0]);
+        assert!(is_permutation(&v));
+        assert_eq!(inverse_permutation(&v), v);
+        assert!(is_even_permutation(&v));
+    }
 }
 
@@ -2,7 +2,23 @@
 // Linear algebra library
 pub use linear_system::*;
 pub use lu::*;
+pub use cholesky::*;
+pub use factorization::*;
 pub use singularity::*;
 pub use inverse::*;
 pub use rank::*;
+pub use norm::*;
+pub use eigen::*;
+pub use schur::*;
+pub use matfun::*;
+pub use sylvester::*;
+pub use riccati::*;
+pub use integer::*;
+pub use qr::*;
+pub use svd::*;
 
+pub use iterative::operator::*;
+pub use iterative::preconditioner::*;
+pub use iterative::options::*;
+pub use iterative::krylov::*;
+pub use iterative::stationary::*;
@@ -0,0 +1,236 @@
+#![doc="Cholesky decomposition
+
+A = L L' where A is symmetric positive definite and L is
+lower triangular with positive diagonal entries.
+
+Only the lower triangular part of A is read.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use factorization::Factorization;
+
+
+/// Pivots are replaced by this value in semidefinite mode
+const HUGE_PIVOT : f64 = 1e64;
+
+
+/// Cholesky decomposition of a symmetric matrix
+pub struct CholeskyDecomposition {
+    /// The lower triangular factor
+    l : MatrixF64
+}
+
+
+impl CholeskyDecomposition {
+
+    /// Computes the Cholesky decomposition A = L L' of a
+    /// symmetric positive definite matrix.
+    ///
+    /// Returns ``IsNotPositiveDefinite`` if a pivot is not
+    /// positive.
+    pub fn new(a : &MatrixF64) -> SRResult<CholeskyDecomposition> {
+        CholeskyDecomposition::decompose(a, None)
+    }
+
+    /// Computes the Cholesky decomposition of a symmetric
+    /// positive semidefinite matrix.
+    ///
+    /// Pivots below ``tolerance`` times one plus the largest
+    /// diagonal entry of A are replaced by a huge value instead
+    /// of failing. Solving with the factor then effectively
+    /// removes the corresponding components from the solution,
+    /// which is what interior point methods need when the
+    /// constraint matrix has linearly dependent rows.
+    /// The determinant of such a factorization is meaningless.
+    pub fn new_semidefinite(a : &MatrixF64, tolerance : f64)
+        -> SRResult<CholeskyDecomposition> {
+        CholeskyDecomposition::decompose(a, Some(tolerance))
+    }
+
+    fn decompose(a : &MatrixF64, tolerance : Option<f64>)
+        -> SRResult<CholeskyDecomposition> {
+        if !a.is_square() {
+            return Err(SRError::IsNotSquareMatrix);
+        }
+        let n = a.num_rows();
+        let max_diag = (0..n).fold(0., |s, i| {
+            let v = a.get(i, i).unwrap();
+            if v > s { v } else { s }
+        });
+        let mut l : MatrixF64 = Matrix::zeros(n, n);
+        for j in 0..n {
+            let mut d = a.get(j, j).unwrap();
+            for k in 0..j {
+                let v = l.get(j, k).unwrap();
+                d -= v * v;
+            }
+            let d = match tolerance {
+                Some(tolerance) => {
+                    if d.is_nan() || d <= tolerance * (1. + max_diag) {
+                        HUGE_PIVOT
+                    }
+                    else {
+                        d.sqrt()
+                    }
+                },
+                None => {
+                    if d.is_nan() || d <= 0. || d.is_infinite() {
+                        return Err(SRError::IsNotPositiveDefinite);
+                    }
+                    d.sqrt()
+                }
+            };
+            l.set(j, j, d);
+            for i in (j + 1)..n {
+                let mut v = a.get(i, j).unwrap();
+                for k in 0..j {
+                    v -= l.get(i, k).unwrap() * l.get(j, k).unwrap();
+                }
+                l.set(i, j, v / d);
+            }
+        }
+        Ok(CholeskyDecomposition{l : l})
+    }
+
+    /// Returns the lower triangular factor L
+    pub fn l(&self) -> MatrixF64 {
+        self.l.clone()
+    }
+}
+
+
+/// Reusable solver based on Cholesky decomposition.
+///
+/// A is symmetric, hence solving with A' is the same as
+/// solving with A.
+impl Factorization for CholeskyDecomposition {
+
+    fn size(&self) -> usize {
+        self.l.num_rows()
+    }
+
+    fn solve_in_place(&self, b : &mut MatrixF64) -> SRResult<()> {
+        let n = self.size();
+        if b.num_rows() != n {
+            return Err(SRError::LRDimensionMismatch);
+        }
+        let l = &self.l;
+        for c in 0..b.num_cols() {
+            // Forward substitution with L
+            for r in 0..n {
+                let mut v = b.get(r, c).unwrap();
+                for k in 0..r {
+                    v -= l.get(r, k).unwrap() * b.get(k, c).unwrap();
+                }
+                b.set(r, c, v / l.get(r, r).unwrap());
+            }
+            // Backward substitution with L'
+            for r in (0..n).rev() {
+                let mut v = b.get(r, c).unwrap();
+                for k in (r + 1)..n {
+                    v -= l.get(k, r).unwrap() * b.get(k, c).unwrap();
+                }
+                b.set(r, c, v / l.get(r, r).unwrap());
+            }
+        }
+        Ok(())
+    }
+
+    fn solve_transpose_in_place(&self, b : &mut MatrixF64) -> SRResult<()> {
+        self.solve_in_place(b)
+    }
+
+    fn det(&self) -> f64 {
+        (0..self.size()).fold(1., |p, i| {
+            let v = self.l.get(i, i).unwrap();
+            p * v * v
+        })
+    }
+
+    fn log_det(&self) -> SRResult<(f64, f64)> {
+        let log_abs = (0..self.size())
+            .fold(0., |s, i| s + 2. * self.l.get(i, i).unwrap().ln());
+        Ok((1., log_abs))
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_cholesky(){
+        let a = matrix_rw_f64(3, 3, &[
+            4., 2., -2.,
+            2., 10., 2.,
+            -2., 2., 6.
+            ]);
+        let chol = CholeskyDecomposition::new(&a).unwrap();
+        let l = chol.l();
+        assert_eq!(l, matrix_rw_f64(3, 3, &[
+            2., 0., 0.,
+            1., 3., 0.,
+            -1., 1., 2.
+            ]));
+        assert!((&(&l * &l.transpose()) - &a).max_abs_scalar_value() < 1e-14);
+        let x = matrix_rw_f64(3, 2, &[1., -1., 2., 0., 3., 1.]);
+        let b = &a * &x;
+        let z = chol.solve(&b).unwrap();
+        assert!((&z - &x).max_abs_scalar_value() < 1e-12);
+        assert!((chol.det() - 144.).abs() < 1e-10);
+        let (sign, log_abs) = chol.log_det().unwrap();
+        assert_eq!(sign, 1.);
+        assert!((log_abs - 144f64.ln()).abs() < 1e-12);
+        let inv = chol.inverse().unwrap();
+        let i : MatrixF64 = Matrix::identity(3, 3);
+        assert!((&(&a * &inv) - &i).max_abs_scalar_value() < 1e-12);
+    }
+
+    #[test]
+    fn test_cholesky_not_positive_definite(){
+        let a = matrix_rw_f64(2, 2, &[1., 2., 2., 1.]);
+        match CholeskyDecomposition::new(&a) {
+            Err(SRError::IsNotPositiveDefinite) => {},
+            _ => panic!()
+        }
+        let a = matrix_rw_f64(2, 2, &[1., 1., 1., 1.]);
+        match CholeskyDecomposition::new(&a) {
+            Err(SRError::IsNotPositiveDefinite) => {},
+            _ => panic!()
+        }
+        let a = matrix_rw_f64(2, 3, &[1., 0., 0., 0., 1., 0.]);
+        match CholeskyDecomposition::new(&a) {
+            Err(SRError::IsNotSquareMatrix) => {},
+            _ => panic!()
+        }
+    }
+
+    #[test]
+    fn test_cholesky_semidefinite(){
+        // Rank one: the second pivot vanishes and is replaced
+        let a = matrix_rw_f64(2, 2, &[1., 1., 1., 1.]);
+        let chol = CholeskyDecomposition::new_semidefinite(&a, 1e-12).unwrap();
+        let x = chol.solve(&matrix_rw_f64(2, 1, &[2., 2.])).unwrap();
+        assert!((x[0] - 2.).abs() < 1e-12);
+        assert!(x[1].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cholesky_empty(){
+        let a : MatrixF64 = Matrix::zeros(0, 0);
+        let chol = CholeskyDecomposition::new(&a).unwrap();
+        assert_eq!(chol.size(), 0);
+        assert_eq!(chol.det(), 1.);
+    }
+}
@@ -0,0 +1,162 @@
+#![doc="Eigen value decompositions
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+
+
+#[doc="Computes the eigen value decomposition of a real
+symmetric matrix A = V diag(w) V'.
+
+Returns the eigen values w (in ascending order) as a column
+vector and the orthonormal eigen vectors as columns of V.
+
+# Remarks
+
+Uses the cyclic Jacobi method which is slow for large
+matrices but very accurate.
+"]
+pub fn sym_eigen(a : &MatrixF64) -> SRResult<(MatrixF64, MatrixF64)> {
+    if !a.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    let n = a.num_rows();
+    let scale = a.max_abs_scalar_value();
+    for c in 0..n {
+        for r in (c + 1)..n {
+            let d = (a.get(r, c).unwrap() - a.get(c, r).unwrap()).abs();
+            if d > 1e-12 * scale {
+                return Err(SRError::IsNotSymmetric);
+            }
+        }
+    }
+    let mut a = a.clone();
+    let mut v : MatrixF64 = Matrix::identity(n, n);
+    let mut converged = false;
+    for _ in 0..100 {
+        let mut off = 0.;
+        for c in 0..n {
+            for r in (c + 1)..n {
+                let x = a.get(r, c).unwrap();
+                off += x * x;
+            }
+        }
+        if off.sqrt() <= f64::EPSILON * scale {
+            converged = true;
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let apq = a.get(p, q).unwrap();
+                if apq == 0. {
+                    continue;
+                }
+                let app = a.get(p, p).unwrap();
+                let aqq = a.get(q, q).unwrap();
+                if apq.abs() <= 1e-2 * f64::EPSILON * (app.abs() + aqq.abs()) {
+                    // Negligible compared to the diagonal
+                    a.set(p, q, 0.);
+                    a.set(q, p, 0.);
+                    continue;
+                }
+                let theta = (aqq - app) / (2. * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+                // A = J' A J
+                for k in 0..n {
+                    let akp = a.get(k, p).unwrap();
+                    let akq = a.get(k, q).unwrap();
+                    a.set(k, p, c * akp - s * akq);
+                    a.set(k, q, s * akp + c * akq);
+                }
+                for k in 0..n {
+                    let apk = a.get(p, k).unwrap();
+                    let aqk = a.get(q, k).unwrap();
+                    a.set(p, k, c * apk - s * aqk);
+                    a.set(q, k, s * apk + c * aqk);
+                }
+                // V = V J
+                for k in 0..n {
+                    let vkp = v.get(k, p).unwrap();
+                    let vkq = v.get(k, q).unwrap();
+                    v.set(k, p, c * vkp - s * vkq);
+                    v.set(k, q, s * vkp + c * vkq);
+                }
+            }
+        }
+    }
+    if !converged {
+        return Err(SRError::NotConverged);
+    }
+    // Sort the eigen values in ascending order
+    let mut order : Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a.get(i, i).unwrap().partial_cmp(&a.get(j, j).unwrap()).unwrap());
+    let mut w : MatrixF64 = Matrix::zeros(n, 1);
+    let mut vectors : MatrixF64 = Matrix::zeros(n, n);
+    for (k, &i) in order.iter().enumerate() {
+        w.set(k, 0, a.get(i, i).unwrap());
+        for r in 0..n {
+            vectors.set(r, k, v.get(r, i).unwrap());
+        }
+    }
+    Ok((w, vectors))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_sym_eigen_2x2(){
+        let a = matrix_rw_f64(2, 2, &[
+            2., 1.,
+            1., 2.
+            ]);
+        let (w, v) = sym_eigen(&a).unwrap();
+        assert!((w[0] - 1.).abs() < 1e-14);
+        assert!((w[1] - 3.).abs() < 1e-14);
+        let d = Matrix::diag_from_vec(&w);
+        let b = &(&v * &d) * &v.transpose();
+        assert!((&a - &b).max_abs_scalar_value() < 1e-14);
+    }
+
+    #[test]
+    fn test_sym_eigen_tridiagonal(){
+        // Eigen values of the n x n matrix tridiag(-1, 2, -1)
+        // are 2 - 2 cos(k pi / (n + 1)).
+        let n = 8;
+        let mut a : MatrixF64 = Matrix::zeros(n, n);
+        for i in 0..n {
+            a.set(i, i, 2.);
+            if i + 1 < n {
+                a.set(i, i + 1, -1.);
+                a.set(i + 1, i, -1.);
+            }
+        }
+        let (w, v) = sym_eigen(&a).unwrap();
+        for k in 0..n {
+            let expected = 2. - 2. * ((k + 1) as f64 * f64::consts::PI / (n + 1) as f64).cos();
+            assert!((w[k] - expected).abs() < 1e-12);
+        }
+        let i : MatrixF64 = Matrix::identity(n, n);
+        assert!((&(&v.transpose() * &v) - &i).max_abs_scalar_value() < 1e-12);
+    }
+
+    #[test]
+    fn test_sym_eigen_not_symmetric(){
+        let a = matrix_rw_f64(2, 2, &[1., 2., 3., 4.]);
+        assert!(sym_eigen(&a).is_err());
+    }
+}
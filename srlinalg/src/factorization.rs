@@ -0,0 +1,105 @@
+#![doc="Common interface for matrix factorizations
+
+A factorization of a square matrix A is computed once and can
+then be reused for solving AX = B for as many right hand sides
+as required.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Operations supported by a factorization of a square matrix A
+pub trait Factorization {
+
+    /// Returns the number of rows (and columns) of A
+    fn size(&self) -> usize;
+
+    /// Solves AX = B in place. On return B contains X.
+    fn solve_in_place(&self, b : &mut MatrixF64) -> SRResult<()>;
+
+    /// Solves A' X = B in place. On return B contains X.
+    fn solve_transpose_in_place(&self, b : &mut MatrixF64) -> SRResult<()>;
+
+    /// Returns the determinant of A
+    fn det(&self) -> f64;
+
+    /// Returns the sign and the natural logarithm of the
+    /// absolute value of the determinant of A.
+    ///
+    /// Useful when the determinant overflows or underflows.
+    fn log_det(&self) -> SRResult<(f64, f64)>;
+
+    /// Solves AX = B for every column of B
+    fn solve(&self, b : &MatrixF64) -> SRResult<MatrixF64> {
+        let mut x = b.clone();
+        self.solve_in_place(&mut x)?;
+        Ok(x)
+    }
+
+    /// Solves A' X = B for every column of B
+    fn solve_transpose(&self, b : &MatrixF64) -> SRResult<MatrixF64> {
+        let mut x = b.clone();
+        self.solve_transpose_in_place(&mut x)?;
+        Ok(x)
+    }
+
+    /// Computes the inverse of A
+    fn inverse(&self) -> SRResult<MatrixF64> {
+        let n = self.size();
+        self.solve(&Matrix::identity(n, n))
+    }
+
+    /// Improves a solution of AX = B by iterative refinement.
+    ///
+    /// In each iteration, the residual R = B - AX is computed
+    /// and the correction obtained by solving AE = R is
+    /// added to X.  The iterations stop when the largest
+    /// correction is below ``tolerance`` times the largest
+    /// entry of X or after ``max_iterations`` iterations.
+    ///
+    /// Returns the number of iterations performed.
+    fn refine(&self, a : &MatrixF64, b : &MatrixF64,
+        x : &mut MatrixF64,
+        max_iterations : usize,
+        tolerance : f64) -> SRResult<usize> {
+        if a.num_rows() != self.size() || a.num_cols() != self.size() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if b.num_rows() != self.size() {
+            return Err(SRError::LRDimensionMismatch);
+        }
+        if x.size() != b.size() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        for iteration in 0..max_iterations {
+            let mut e = b - &(a * &*x);
+            self.solve_in_place(&mut e)?;
+            *x = &*x + &e;
+            if x.is_empty() ||
+                e.max_abs_scalar_value() <= tolerance * x.max_abs_scalar_value() {
+                return Ok(iteration + 1);
+            }
+        }
+        Ok(max_iterations)
+    }
+}
+
+
+/// Solves AX = B using a factorization and refines the
+/// solution iteratively.
+///
+/// See ``Factorization::refine`` for the meaning of
+/// ``max_iterations`` and ``tolerance``.
+pub fn solve_refined<F:Factorization>(f : &F,
+    a : &MatrixF64,
+    b : &MatrixF64,
+    max_iterations : usize,
+    tolerance : f64) -> SRResult<MatrixF64> {
+    let mut x = f.solve(b)?;
+    f.refine(a, b, &mut x, max_iterations, tolerance)?;
+    Ok(x)
+}
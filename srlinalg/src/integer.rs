@@ -0,0 +1,534 @@
+#![doc="Exact linear algebra over integers
+
+* ``bareiss``: fraction free row echelon form
+* ``det_bareiss``, ``rank_bareiss``: exact determinant and rank
+* ``det_bigint``, ``rank_bigint``: the same with arbitrary
+  precision arithmetic so that intermediate results never
+  overflow
+* ``hermite_normal_form``: H = U A with U unimodular
+* ``smith_normal_form``: D = U A V with U, V unimodular
+* ``integer_nullspace``: a lattice basis of the integer
+  solutions of A x = 0
+
+Bareiss elimination works in any integral domain. Every
+division performed by the algorithm is exact, hence the
+entries of the matrix never leave the domain.  The normal
+forms require a euclidean domain (division with remainder
+and extended gcd).
+
+Fixed width integers may overflow for large or ill
+conditioned matrices. Use the big integer variants when
+in doubt.
+
+References:
+
+* E. H. Bareiss, Sylvester's identity and multistep
+  integer-preserving Gaussian elimination, Math. Comp. 22, 1968.
+* H. Cohen, A Course in Computational Algebraic Number
+  Theory, Springer, 1993.
+"]
+
+
+// std imports
+
+// external imports
+use num::BigInt;
+use num::traits::{Signed, Zero, One, FromPrimitive};
+
+// local imports
+use sralgebra::{MagmaBase, IntegralDomainPartial, EuclideanDomainPartial};
+use srmatrix::api::*;
+
+
+/// Fraction free elimination on a row major array.
+///
+/// Returns the rank and whether an odd number of row
+/// exchanges was performed.
+fn bareiss_in_place<T>(a : &mut [T], rows : usize, cols : usize) -> (usize, bool)
+    where T : Signed + Clone {
+    let mut prev : T = One::one();
+    let mut r = 0;
+    let mut odd = false;
+    for c in 0..cols {
+        if r == rows {
+            break;
+        }
+        // Find a pivot in column c
+        let p = match (r..rows).find(|&i| !a[i * cols + c].is_zero()) {
+            Some(p) => p,
+            None => continue
+        };
+        if p != r {
+            for j in 0..cols {
+                a.swap(p * cols + j, r * cols + j);
+            }
+            odd = !odd;
+        }
+        let pivot = a[r * cols + c].clone();
+        for i in (r + 1)..rows {
+            let factor = a[i * cols + c].clone();
+            for j in (c + 1)..cols {
+                // Division by the previous pivot is exact
+                let v = (pivot.clone() * a[i * cols + j].clone()
+                    - factor.clone() * a[r * cols + j].clone()) / prev.clone();
+                a[i * cols + j] = v;
+            }
+            a[i * cols + c] = Zero::zero();
+        }
+        prev = pivot;
+        r += 1;
+    }
+    (r, odd)
+}
+
+/// Copies a matrix into a row major array
+fn to_row_major<T:MagmaBase>(m : &Matrix<T>) -> Vec<T> {
+    let mut v = Vec::with_capacity(m.num_cells());
+    for r in 0..m.num_rows() {
+        for c in 0..m.num_cols() {
+            v.push(m.get(r, c).unwrap());
+        }
+    }
+    v
+}
+
+/// Copies a matrix of integers into a row major array of big integers
+fn to_bigint(m : &MatrixI64) -> Vec<BigInt> {
+    to_row_major(m).into_iter().map(|x| BigInt::from_i64(x).unwrap()).collect()
+}
+
+
+#[doc="Computes the fraction free row echelon form of a matrix
+by Bareiss elimination.
+
+Returns the echelon form and the rank of the matrix.
+The k-th pivot of the echelon form is a k x k minor of
+the (row permuted) matrix.
+"]
+pub fn bareiss<T>(m : &Matrix<T>) -> (Matrix<T>, usize)
+    where T : IntegralDomainPartial + Signed {
+    let rows = m.num_rows();
+    let cols = m.num_cols();
+    let mut a = to_row_major(m);
+    let (rank, _) = bareiss_in_place(&mut a, rows, cols);
+    (Matrix::from_slice_rw(rows, cols, &a), rank)
+}
+
+
+/// Computes the determinant of a square matrix exactly
+/// by Bareiss elimination
+pub fn det_bareiss<T>(m : &Matrix<T>) -> SRResult<T>
+    where T : IntegralDomainPartial + Signed {
+    if !m.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    let n = m.num_rows();
+    if n == 0 {
+        return Ok(One::one());
+    }
+    let mut a = to_row_major(m);
+    let (rank, odd) = bareiss_in_place(&mut a, n, n);
+    if rank < n {
+        return Ok(Zero::zero());
+    }
+    let d = a[n * n - 1];
+    Ok(if odd { -d } else { d })
+}
+
+
+/// Computes the rank of a matrix exactly by Bareiss elimination
+pub fn rank_bareiss<T>(m : &Matrix<T>) -> usize
+    where T : IntegralDomainPartial + Signed {
+    bareiss(m).1
+}
+
+
+/// Computes the determinant of an integer matrix using
+/// arbitrary precision arithmetic
+pub fn det_bigint(m : &MatrixI64) -> SRResult<BigInt> {
+    if !m.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    let n = m.num_rows();
+    if n == 0 {
+        return Ok(One::one());
+    }
+    let mut a = to_bigint(m);
+    let (rank, odd) = bareiss_in_place(&mut a, n, n);
+    if rank < n {
+        return Ok(Zero::zero());
+    }
+    let d = a.pop().unwrap();
+    Ok(if odd { -d } else { d })
+}
+
+
+/// Computes the rank of an integer matrix using arbitrary
+/// precision arithmetic
+pub fn rank_bigint(m : &MatrixI64) -> usize {
+    let mut a = to_bigint(m);
+    bareiss_in_place(&mut a, m.num_rows(), m.num_cols()).0
+}
+
+
+/// Replaces rows i and j by (a r_i + b r_j) and (c r_i + d r_j)
+fn combine_rows<T:EuclideanDomainPartial>(m : &mut Matrix<T>,
+    i : usize, j : usize, a : T, b : T, c : T, d : T) {
+    for col in 0..m.num_cols() {
+        let x = m.get(i, col).unwrap();
+        let y = m.get(j, col).unwrap();
+        m.set(i, col, a * x + b * y);
+        m.set(j, col, c * x + d * y);
+    }
+}
+
+/// Replaces columns i and j by (a c_i + b c_j) and (c c_i + d c_j)
+fn combine_cols<T:EuclideanDomainPartial>(m : &mut Matrix<T>,
+    i : usize, j : usize, a : T, b : T, c : T, d : T) {
+    for row in 0..m.num_rows() {
+        let x = m.get(row, i).unwrap();
+        let y = m.get(row, j).unwrap();
+        m.set(row, i, a * x + b * y);
+        m.set(row, j, c * x + d * y);
+    }
+}
+
+
+#[doc="Computes the (row style) Hermite normal form H = U A.
+
+U is unimodular (an integer matrix with determinant
+plus or minus 1). H is in row echelon form, its pivots are
+positive and the entries above each pivot are reduced
+modulo the pivot (0 <= h_ij < h_rj).
+
+Returns (H, U).
+"]
+pub fn hermite_normal_form<T>(m : &Matrix<T>) -> (Matrix<T>, Matrix<T>)
+    where T : EuclideanDomainPartial {
+    let rows = m.num_rows();
+    let cols = m.num_cols();
+    let z : T = Zero::zero();
+    let one : T = One::one();
+    let mut h = m.clone();
+    let mut u : Matrix<T> = Matrix::identity(rows, rows);
+    let mut r = 0;
+    for c in 0..cols {
+        if r == rows {
+            break;
+        }
+        // Accumulate the gcd of column c (rows r..) in row r
+        for i in (r + 1)..rows {
+            let b = h.get(i, c).unwrap();
+            if b == z {
+                continue;
+            }
+            let a = h.get(r, c).unwrap();
+            let (g, x, y) = a.extended_gcd(b);
+            let (a, _) = a.div_rem_euclid(g);
+            let (b, _) = b.div_rem_euclid(g);
+            // [x y; -b a] has determinant x a + y b = 1
+            combine_rows(&mut h, r, i, x, y, -b, a);
+            combine_rows(&mut u, r, i, x, y, -b, a);
+        }
+        let pivot = h.get(r, c).unwrap();
+        if pivot == z {
+            continue;
+        }
+        let unit = pivot.normalizing_unit();
+        if unit != one {
+            combine_rows(&mut h, r, r, unit, z, unit, z);
+            combine_rows(&mut u, r, r, unit, z, unit, z);
+        }
+        let pivot = h.get(r, c).unwrap();
+        // Reduce the entries above the pivot
+        for i in 0..r {
+            let (q, _) = h.get(i, c).unwrap().div_rem_euclid(pivot);
+            if q != z {
+                combine_rows(&mut h, i, r, one, -q, z, one);
+                combine_rows(&mut u, i, r, one, -q, z, one);
+            }
+        }
+        r += 1;
+    }
+    (h, u)
+}
+
+
+#[doc="Computes the Smith normal form D = U A V.
+
+U and V are unimodular and D is diagonal with non-negative
+entries d_1, d_2, ... such that d_i divides d_(i+1).
+
+Returns (D, U, V).
+"]
+pub fn smith_normal_form<T>(m : &Matrix<T>) -> (Matrix<T>, Matrix<T>, Matrix<T>)
+    where T : EuclideanDomainPartial {
+    let rows = m.num_rows();
+    let cols = m.num_cols();
+    let z : T = Zero::zero();
+    let one : T = One::one();
+    let mut d = m.clone();
+    let mut u : Matrix<T> = Matrix::identity(rows, rows);
+    let mut v : Matrix<T> = Matrix::identity(cols, cols);
+    let k = if rows < cols { rows } else { cols };
+    for t in 0..k {
+        loop {
+            // Move the smallest non-zero entry to (t, t)
+            let mut best : Option<(usize, usize, u64)> = None;
+            for c in t..cols {
+                for r in t..rows {
+                    let x = d.get(r, c).unwrap();
+                    if x == z {
+                        continue;
+                    }
+                    let size = x.euclidean_size();
+                    if best.is_none_or(|(_, _, s)| size < s) {
+                        best = Some((r, c, size));
+                    }
+                }
+            }
+            let (pr, pc) = match best {
+                Some((r, c, _)) => (r, c),
+                None => {
+                    return (d, u, v);
+                }
+            };
+            if pr != t {
+                combine_rows(&mut d, t, pr, z, one, one, z);
+                combine_rows(&mut u, t, pr, z, one, one, z);
+            }
+            if pc != t {
+                combine_cols(&mut d, t, pc, z, one, one, z);
+                combine_cols(&mut v, t, pc, z, one, one, z);
+            }
+            let pivot = d.get(t, t).unwrap();
+            let mut done = true;
+            // Clear the column below the pivot
+            for r in (t + 1)..rows {
+                let (q, rem) = d.get(r, t).unwrap().div_rem_euclid(pivot);
+                if q != z {
+                    combine_rows(&mut d, r, t, one, -q, z, one);
+                    combine_rows(&mut u, r, t, one, -q, z, one);
+                }
+                if rem != z {
+                    done = false;
+                }
+            }
+            // Clear the row right of the pivot
+            for c in (t + 1)..cols {
+                let (q, rem) = d.get(t, c).unwrap().div_rem_euclid(pivot);
+                if q != z {
+                    combine_cols(&mut d, c, t, one, -q, z, one);
+                    combine_cols(&mut v, c, t, one, -q, z, one);
+                }
+                if rem != z {
+                    done = false;
+                }
+            }
+            if !done {
+                continue;
+            }
+            // The pivot must divide all remaining entries
+            let mut offending = None;
+            for c in (t + 1)..cols {
+                for r in (t + 1)..rows {
+                    if !d.get(r, c).unwrap().is_divisible_by(pivot) {
+                        offending = Some(r);
+                    }
+                }
+            }
+            match offending {
+                Some(r) => {
+                    // Add row r to row t and start over
+                    combine_rows(&mut d, t, r, one, one, z, one);
+                    combine_rows(&mut u, t, r, one, one, z, one);
+                },
+                None => break
+            }
+        }
+        let unit = d.get(t, t).unwrap().normalizing_unit();
+        if unit != one {
+            combine_rows(&mut d, t, t, unit, z, unit, z);
+            combine_rows(&mut u, t, t, unit, z, unit, z);
+        }
+    }
+    (d, u, v)
+}
+
+
+#[doc="Computes a basis of the integer null space of A.
+
+Returns a matrix whose columns form a basis of the
+lattice {x integer : A x = 0}. Every integer solution
+is an integer combination of the columns.
+
+# Remarks
+
+If U A' = H is the Hermite normal form of A', the rows
+of U corresponding to the zero rows of H span the null
+space lattice.
+"]
+pub fn integer_nullspace<T>(m : &Matrix<T>) -> Matrix<T>
+    where T : EuclideanDomainPartial {
+    let n = m.num_cols();
+    let (h, u) = hermite_normal_form(&m.transpose());
+    let z : T = Zero::zero();
+    let mut rank = 0;
+    for r in 0..n {
+        if (0..h.num_cols()).any(|c| h.get(r, c).unwrap() != z) {
+            rank = r + 1;
+        }
+    }
+    let mut basis : Matrix<T> = Matrix::zeros(n, n - rank);
+    for k in 0..(n - rank) {
+        for i in 0..n {
+            basis.set(i, k, u.get(rank + k, i).unwrap());
+        }
+    }
+    basis
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_det_bareiss(){
+        let a = matrix_rw_i64(3, 3, &[
+            2, -3, 1,
+            2, 0, -1,
+            1, 4, 5
+            ]);
+        assert_eq!(det_bareiss(&a).unwrap(), 49);
+        // Needs a row exchange
+        let a = matrix_rw_i64(2, 2, &[0, 1, 1, 0]);
+        assert_eq!(det_bareiss(&a).unwrap(), -1);
+        let a = matrix_rw_i64(3, 3, &[
+            1, 2, 3,
+            2, 4, 6,
+            1, 0, 1
+            ]);
+        assert_eq!(det_bareiss(&a).unwrap(), 0);
+        assert!(det_bareiss(&matrix_rw_i64(2, 3, &[1, 2, 3, 4, 5, 6])).is_err());
+    }
+
+    #[test]
+    fn test_rank_bareiss(){
+        let a = matrix_rw_i64(3, 4, &[
+            1, 2, 3, 4,
+            2, 4, 6, 8,
+            0, 0, 1, 1
+            ]);
+        assert_eq!(rank_bareiss(&a), 2);
+        assert_eq!(rank_bigint(&a), 2);
+        let (e, rank) = bareiss(&a);
+        assert_eq!(rank, 2);
+        assert_eq!(e.get(2, 3).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_det_bigint(){
+        let t = 10_000_000_000i64;
+        let a = matrix_rw_i64(3, 3, &[
+            t, 1, 2,
+            3, t, 4,
+            5, 6, t
+            ]);
+        let big = |x : i64| BigInt::from_i64(x).unwrap();
+        let bt = big(t);
+        // det = t (t^2 - 24) - (3 t - 20) + 2 (18 - 5 t) = t^3 - 37 t + 56
+        let expected = bt.clone() * bt.clone() * bt.clone()
+            - big(37) * bt.clone() + big(56);
+        assert_eq!(det_bigint(&a).unwrap(), expected);
+        // Agrees with the fixed width version for small entries
+        let a = matrix_rw_i64(3, 3, &[
+            2, -3, 1,
+            2, 0, -1,
+            1, 4, 5
+            ]);
+        assert_eq!(det_bigint(&a).unwrap(), big(49));
+    }
+
+    #[test]
+    fn test_hermite_normal_form(){
+        let a = matrix_rw_i64(3, 4, &[
+            2, 3, 6, 2,
+            5, 6, 1, 6,
+            8, 3, 1, 1
+            ]);
+        let (h, u) = hermite_normal_form(&a);
+        assert_eq!(&u * &a, h);
+        assert_eq!(det_bareiss(&u).unwrap().abs(), 1);
+        // Echelon form with positive pivots and reduced entries above
+        let mut last = None;
+        for r in 0..3 {
+            let c = (0..4).find(|&c| h.get(r, c).unwrap() != 0).unwrap();
+            if let Some(l) = last {
+                assert!(c > l);
+            }
+            last = Some(c);
+            let pivot = h.get(r, c).unwrap();
+            assert!(pivot > 0);
+            for i in 0..r {
+                let x = h.get(i, c).unwrap();
+                assert!(x >= 0 && x < pivot);
+            }
+            for i in (r + 1)..3 {
+                assert_eq!(h.get(i, c).unwrap(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_smith_normal_form(){
+        let a = matrix_rw_i64(3, 3, &[
+            2, 4, 4,
+            -6, 6, 12,
+            10, -4, -16
+            ]);
+        let (d, u, v) = smith_normal_form(&a);
+        assert_eq!(d, matrix_rw_i64(3, 3, &[
+            2, 0, 0,
+            0, 6, 0,
+            0, 0, 12
+            ]));
+        assert_eq!(&(&u * &a) * &v, d);
+        assert_eq!(det_bareiss(&u).unwrap().abs(), 1);
+        assert_eq!(det_bareiss(&v).unwrap().abs(), 1);
+        // Rectangular and rank deficient
+        let a = matrix_rw_i64(2, 3, &[
+            2, 4, 6,
+            1, 2, 3
+            ]);
+        let (d, u, v) = smith_normal_form(&a);
+        assert_eq!(d, matrix_rw_i64(2, 3, &[1, 0, 0, 0, 0, 0]));
+        assert_eq!(&(&u * &a) * &v, d);
+    }
+
+    #[test]
+    fn test_integer_nullspace(){
+        let a = matrix_rw_i64(2, 3, &[
+            1, 2, 3,
+            4, 5, 6
+            ]);
+        let n = integer_nullspace(&a);
+        assert_eq!(n.num_cols(), 1);
+        assert!(n == vector_i64(&[1, -2, 1]) || n == vector_i64(&[-1, 2, -1]));
+        let a = matrix_rw_i64(1, 3, &[2, 4, 6]);
+        let n = integer_nullspace(&a);
+        assert_eq!(n.num_cols(), 2);
+        assert_eq!(&a * &n, Matrix::zeros(1, 2));
+        // The basis generates the lattice: its 2 x 2 minors have gcd 1
+        let m01 = n.get(0, 0).unwrap() * n.get(1, 1).unwrap() - n.get(1, 0).unwrap() * n.get(0, 1).unwrap();
+        let m02 = n.get(0, 0).unwrap() * n.get(2, 1).unwrap() - n.get(2, 0).unwrap() * n.get(0, 1).unwrap();
+        let m12 = n.get(1, 0).unwrap() * n.get(2, 1).unwrap() - n.get(2, 0).unwrap() * n.get(1, 1).unwrap();
+        assert_eq!(m01.gcd(m02).gcd(m12), 1);
+    }
+}
@@ -0,0 +1,481 @@
+#![doc="Krylov subspace methods for solving Ax = b
+
+* ``cg``, ``pcg``: conjugate gradients for symmetric positive definite A
+* ``minres``: minimum residual method for symmetric (possibly indefinite) A
+* ``gmres``: restarted generalized minimum residual method for general A
+* ``bicgstab``: stabilized bi-conjugate gradients for general A
+
+GMRES and BiCGSTAB use right preconditioning so that the
+reported residuals are residuals of the original system.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use iterative::operator::LinearOperator;
+use iterative::preconditioner::{Preconditioner, IdentityPreconditioner};
+use iterative::options::*;
+
+
+/// Conjugate gradient method for symmetric positive definite A
+pub fn cg<A:LinearOperator>(a : &A, b : &MatrixF64,
+    x0 : Option<&MatrixF64>,
+    options : &IterativeOptions) -> SRResult<IterativeResult> {
+    pcg(a, &IdentityPreconditioner, b, x0, options)
+}
+
+
+/// Preconditioned conjugate gradient method.
+///
+/// Both A and the preconditioner M must be symmetric
+/// positive definite.
+pub fn pcg<A:LinearOperator, M:Preconditioner>(a : &A, m : &M,
+    b : &MatrixF64,
+    x0 : Option<&MatrixF64>,
+    options : &IterativeOptions) -> SRResult<IterativeResult> {
+    let mut x = initial_guess(a.dimension(), b, x0)?;
+    let threshold = options.threshold(vec_norm_l2(b));
+    let mut r = b - &a.apply(&x);
+    let mut r_norm = vec_norm_l2(&r);
+    let mut history = vec![r_norm];
+    let mut z = m.precondition(&r);
+    let mut p = z.clone();
+    let mut rz = r.inner_prod(&z);
+    let mut iterations = 0;
+    while r_norm > threshold && iterations < options.max_iterations {
+        let ap = a.apply(&p);
+        let pap = p.inner_prod(&ap);
+        if pap <= 0. {
+            return Err(SRError::IsNotPositiveDefinite);
+        }
+        let alpha = rz / pap;
+        x = &x + &p.copy_mul_scalar(alpha);
+        r = &r - &ap.copy_mul_scalar(alpha);
+        r_norm = vec_norm_l2(&r);
+        history.push(r_norm);
+        iterations += 1;
+        z = m.precondition(&r);
+        let rz_new = r.inner_prod(&z);
+        let beta = rz_new / rz;
+        rz = rz_new;
+        p = &z + &p.copy_mul_scalar(beta);
+    }
+    Ok(IterativeResult {
+        x : x,
+        iterations : iterations,
+        residual_norm : r_norm,
+        converged : r_norm <= threshold,
+        history : history
+    })
+}
+
+
+/// Minimum residual method for symmetric A.
+///
+/// A may be indefinite.
+pub fn minres<A:LinearOperator>(a : &A, b : &MatrixF64,
+    x0 : Option<&MatrixF64>,
+    options : &IterativeOptions) -> SRResult<IterativeResult> {
+    let n = a.dimension();
+    let mut x = initial_guess(n, b, x0)?;
+    let threshold = options.threshold(vec_norm_l2(b));
+    let r = b - &a.apply(&x);
+    let beta1 = vec_norm_l2(&r);
+    let mut history = vec![beta1];
+    let mut eta = beta1;
+    let mut iterations = 0;
+    if beta1 > threshold {
+        // Lanczos vectors
+        let mut v_old : MatrixF64 = Matrix::zeros(n, 1);
+        let mut v = r.copy_mul_scalar(1. / beta1);
+        let mut beta = beta1;
+        // Search directions
+        let mut w_old : MatrixF64 = Matrix::zeros(n, 1);
+        let mut w : MatrixF64 = Matrix::zeros(n, 1);
+        // Givens rotations of the last two steps
+        let (mut c_old, mut c) = (1., 1.);
+        let (mut s_old, mut s) = (0., 0.);
+        while eta.abs() > threshold && iterations < options.max_iterations {
+            // Lanczos step
+            let av = a.apply(&v);
+            let alpha = v.inner_prod(&av);
+            let v_new = &(&av - &v.copy_mul_scalar(alpha)) - &v_old.copy_mul_scalar(beta);
+            let beta_new = vec_norm_l2(&v_new);
+            // QR factorization of the tridiagonal matrix
+            let rho0 = c * alpha - c_old * s * beta;
+            let rho1 = (rho0 * rho0 + beta_new * beta_new).sqrt();
+            let rho2 = s * alpha + c_old * c * beta;
+            let rho3 = s_old * beta;
+            if rho1 == 0. {
+                return Err(SRError::Breakdown);
+            }
+            let c_new = rho0 / rho1;
+            let s_new = beta_new / rho1;
+            // Update of the search direction and the solution
+            let w_new = (&(&v - &w_old.copy_mul_scalar(rho3))
+                - &w.copy_mul_scalar(rho2)).copy_mul_scalar(1. / rho1);
+            x = &x + &w_new.copy_mul_scalar(c_new * eta);
+            eta *= -s_new;
+            history.push(eta.abs());
+            iterations += 1;
+            // Shift
+            w_old = w;
+            w = w_new;
+            c_old = c;
+            c = c_new;
+            s_old = s;
+            s = s_new;
+            v_old = v;
+            beta = beta_new;
+            if beta_new == 0. {
+                // The Krylov subspace is invariant. x is exact.
+                break;
+            }
+            v = v_new.copy_mul_scalar(1. / beta_new);
+        }
+    }
+    let r_norm = vec_norm_l2(&(b - &a.apply(&x)));
+    Ok(IterativeResult {
+        x : x,
+        iterations : iterations,
+        residual_norm : r_norm,
+        converged : eta.abs() <= threshold,
+        history : history
+    })
+}
+
+
+/// Restarted GMRES(m) method with right preconditioning.
+///
+/// ``restart`` is the dimension of the Krylov subspace built
+/// before each restart. Every Arnoldi step counts as one
+/// iteration.
+pub fn gmres<A:LinearOperator, M:Preconditioner>(a : &A, m : &M,
+    b : &MatrixF64,
+    x0 : Option<&MatrixF64>,
+    restart : usize,
+    options : &IterativeOptions) -> SRResult<IterativeResult> {
+    let n = a.dimension();
+    let mut x = initial_guess(n, b, x0)?;
+    let restart = if restart == 0 || restart > n { n } else { restart };
+    let threshold = options.threshold(vec_norm_l2(b));
+    let mut r_norm = vec_norm_l2(&(b - &a.apply(&x)));
+    let mut history = vec![r_norm];
+    let mut iterations = 0;
+    while r_norm > threshold && iterations < options.max_iterations && restart > 0 {
+        let r = b - &a.apply(&x);
+        let beta = vec_norm_l2(&r);
+        // Orthonormal basis of the Krylov subspace
+        let mut vs = vec![r.copy_mul_scalar(1. / beta)];
+        // Preconditioned basis vectors
+        let mut zs : Vec<MatrixF64> = Vec::with_capacity(restart);
+        // Hessenberg matrix reduced to upper triangular form
+        let mut h : MatrixF64 = Matrix::zeros(restart + 1, restart);
+        let mut cs : Vec<f64> = Vec::with_capacity(restart);
+        let mut sn : Vec<f64> = Vec::with_capacity(restart);
+        let mut g : MatrixF64 = Matrix::zeros(restart + 1, 1);
+        g.set(0, 0, beta);
+        let mut k = 0;
+        while k < restart && iterations < options.max_iterations {
+            // Arnoldi step with modified Gram-Schmidt
+            let z = m.precondition(&vs[k]);
+            let mut w = a.apply(&z);
+            zs.push(z);
+            for i in 0..(k + 1) {
+                let hik = w.inner_prod(&vs[i]);
+                h.set(i, k, hik);
+                w = &w - &vs[i].copy_mul_scalar(hik);
+            }
+            let h_next = vec_norm_l2(&w);
+            h.set(k + 1, k, h_next);
+            // Apply previous Givens rotations to the new column
+            for i in 0..k {
+                let h0 = h.get(i, k).unwrap();
+                let h1 = h.get(i + 1, k).unwrap();
+                h.set(i, k, cs[i] * h0 + sn[i] * h1);
+                h.set(i + 1, k, -sn[i] * h0 + cs[i] * h1);
+            }
+            // New rotation eliminating h(k+1, k)
+            let h0 = h.get(k, k).unwrap();
+            let denom = (h0 * h0 + h_next * h_next).sqrt();
+            if denom == 0. {
+                return Err(SRError::Breakdown);
+            }
+            cs.push(h0 / denom);
+            sn.push(h_next / denom);
+            h.set(k, k, denom);
+            h.set(k + 1, k, 0.);
+            let gk = g[k];
+            g.set(k + 1, 0, -sn[k] * gk);
+            g.set(k, 0, cs[k] * gk);
+            k += 1;
+            iterations += 1;
+            r_norm = g[k].abs();
+            history.push(r_norm);
+            if r_norm <= threshold || h_next == 0. {
+                break;
+            }
+            vs.push(w.copy_mul_scalar(1. / h_next));
+        }
+        // Solve the k x k upper triangular system H y = g
+        let mut y : MatrixF64 = Matrix::zeros(k, 1);
+        for i in (0..k).rev() {
+            let mut v = g[i];
+            for j in (i + 1)..k {
+                v -= h.get(i, j).unwrap() * y[j];
+            }
+            y.set(i, 0, v / h.get(i, i).unwrap());
+        }
+        for i in 0..k {
+            x = &x + &zs[i].copy_mul_scalar(y[i]);
+        }
+        // The true residual guards against drift in the
+        // recurrence for the residual norm.
+        r_norm = vec_norm_l2(&(b - &a.apply(&x)));
+    }
+    Ok(IterativeResult {
+        x : x,
+        iterations : iterations,
+        residual_norm : r_norm,
+        converged : r_norm <= threshold,
+        history : history
+    })
+}
+
+
+/// Stabilized bi-conjugate gradient method with right
+/// preconditioning.
+pub fn bicgstab<A:LinearOperator, M:Preconditioner>(a : &A, m : &M,
+    b : &MatrixF64,
+    x0 : Option<&MatrixF64>,
+    options : &IterativeOptions) -> SRResult<IterativeResult> {
+    let n = a.dimension();
+    let mut x = initial_guess(n, b, x0)?;
+    let threshold = options.threshold(vec_norm_l2(b));
+    let mut r = b - &a.apply(&x);
+    let r_hat = r.clone();
+    let mut r_norm = vec_norm_l2(&r);
+    let mut history = vec![r_norm];
+    let (mut rho, mut alpha, mut omega) = (1., 1., 1.);
+    let mut v : MatrixF64 = Matrix::zeros(n, 1);
+    let mut p : MatrixF64 = Matrix::zeros(n, 1);
+    let mut iterations = 0;
+    while r_norm > threshold && iterations < options.max_iterations {
+        let rho_new = r_hat.inner_prod(&r);
+        if rho_new == 0. {
+            return Err(SRError::Breakdown);
+        }
+        let beta = (rho_new / rho) * (alpha / omega);
+        p = &r + &(&p - &v.copy_mul_scalar(omega)).copy_mul_scalar(beta);
+        let p_hat = m.precondition(&p);
+        v = a.apply(&p_hat);
+        let r_hat_v = r_hat.inner_prod(&v);
+        if r_hat_v == 0. {
+            return Err(SRError::Breakdown);
+        }
+        alpha = rho_new / r_hat_v;
+        let s = &r - &v.copy_mul_scalar(alpha);
+        iterations += 1;
+        let s_norm = vec_norm_l2(&s);
+        if s_norm <= threshold {
+            x = &x + &p_hat.copy_mul_scalar(alpha);
+            r_norm = s_norm;
+            history.push(r_norm);
+            break;
+        }
+        let s_hat = m.precondition(&s);
+        let t = a.apply(&s_hat);
+        let tt = t.inner_prod(&t);
+        if tt == 0. {
+            return Err(SRError::Breakdown);
+        }
+        omega = t.inner_prod(&s) / tt;
+        x = &(&x + &p_hat.copy_mul_scalar(alpha)) + &s_hat.copy_mul_scalar(omega);
+        r = &s - &t.copy_mul_scalar(omega);
+        r_norm = vec_norm_l2(&r);
+        history.push(r_norm);
+        rho = rho_new;
+        if omega == 0. {
+            return Err(SRError::Breakdown);
+        }
+    }
+    Ok(IterativeResult {
+        x : x,
+        iterations : iterations,
+        residual_norm : r_norm,
+        converged : r_norm <= threshold,
+        history : history
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use iterative::operator::*;
+    use iterative::preconditioner::*;
+    use linear_system::GaussElimination;
+
+    /// 1-D Poisson matrix with n interior points
+    fn poisson(n : usize) -> TridiagonalOperator {
+        TridiagonalOperator::from_constants(n, -1., 2., -1.)
+    }
+
+    fn non_symmetric() -> MatrixF64 {
+        matrix_rw_f64(4, 4, &[
+            4., 1., 0., 2.,
+            -1., 5., 1., 0.,
+            0., -2., 6., 1.,
+            1., 0., -1., 3.
+            ])
+    }
+
+    fn check(a : &MatrixF64, result : &IterativeResult, b : &MatrixF64){
+        assert!(result.converged);
+        let r = b - &(a * &result.x);
+        assert!(vec_norm_l2(&r) < 1e-8);
+        assert_eq!(result.history.len(), result.iterations + 1);
+    }
+
+    #[test]
+    fn test_cg_poisson(){
+        let t = poisson(20);
+        let b : MatrixF64 = Matrix::ones(20, 1);
+        let options = IterativeOptions::new();
+        let result = cg(&t, &b, None, &options).unwrap();
+        check(&t.to_matrix(), &result, &b);
+        // CG converges in at most n steps in exact arithmetic
+        assert!(result.iterations <= 20);
+    }
+
+    #[test]
+    fn test_pcg_preconditioners(){
+        let a = poisson(16).to_matrix();
+        let b = from_range_cw_f64(16, 1, 1., 100.);
+        let options = IterativeOptions::new();
+        let jacobi = JacobiPreconditioner::new(&a).unwrap();
+        check(&a, &pcg(&a, &jacobi, &b, None, &options).unwrap(), &b);
+        let ic = IncompleteCholeskyPreconditioner::new(&a).unwrap();
+        let result = pcg(&a, &ic, &b, None, &options).unwrap();
+        check(&a, &result, &b);
+        // IC(0) of a tridiagonal matrix is exact
+        assert!(result.iterations <= 1);
+    }
+
+    #[test]
+    fn test_cg_not_positive_definite(){
+        let a = matrix_rw_f64(2, 2, &[1., 0., 0., -1.]);
+        let b = vector_f64(&[1., 1.]);
+        assert!(cg(&a, &b, None, &IterativeOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_minres_indefinite(){
+        let a = matrix_rw_f64(3, 3, &[
+            2., 1., 0.,
+            1., -3., 1.,
+            0., 1., 1.
+            ]);
+        let b = vector_f64(&[1., 2., 3.]);
+        let result = minres(&a, &b, None, &IterativeOptions::new()).unwrap();
+        check(&a, &result, &b);
+        // Residual norms of MINRES never increase
+        for i in 1..result.history.len(){
+            assert!(result.history[i] <= result.history[i - 1] + 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_gmres(){
+        let a = non_symmetric();
+        let b = vector_f64(&[1., 2., 3., 4.]);
+        let options = IterativeOptions::new();
+        let result = gmres(&a, &IdentityPreconditioner, &b, None, 10, &options).unwrap();
+        check(&a, &result, &b);
+        assert!(result.iterations <= 4);
+        // Restarted version
+        let result = gmres(&a, &IdentityPreconditioner, &b, None, 2, &options).unwrap();
+        check(&a, &result, &b);
+        // Preconditioned version
+        let ilu = ILU0Preconditioner::new(&a).unwrap();
+        let result = gmres(&a, &ilu, &b, None, 2, &options).unwrap();
+        check(&a, &result, &b);
+    }
+
+    #[test]
+    fn test_bicgstab(){
+        let a = non_symmetric();
+        let b = vector_f64(&[1., -2., 3., -4.]);
+        let options = IterativeOptions::new();
+        let result = bicgstab(&a, &IdentityPreconditioner, &b, None, &options).unwrap();
+        check(&a, &result, &b);
+        let jacobi = JacobiPreconditioner::new(&a).unwrap();
+        let result = bicgstab(&a, &jacobi, &b, None, &options).unwrap();
+        check(&a, &result, &b);
+    }
+
+    #[test]
+    fn test_one_by_one(){
+        let a = matrix_rw_f64(1, 1, &[4.]);
+        let b = vector_f64(&[2.]);
+        let options = IterativeOptions::new();
+        check(&a, &cg(&a, &b, None, &options).unwrap(), &b);
+        let jacobi = JacobiPreconditioner::new(&a).unwrap();
+        check(&a, &pcg(&a, &jacobi, &b, None, &options).unwrap(), &b);
+        check(&a, &minres(&a, &b, None, &options).unwrap(), &b);
+        check(&a, &gmres(&a, &IdentityPreconditioner, &b, None, 5, &options).unwrap(), &b);
+        let result = bicgstab(&a, &IdentityPreconditioner, &b, None, &options).unwrap();
+        check(&a, &result, &b);
+        assert!((result.x[0] - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_closure_operator(){
+        // A = diag(1, 2, ..., n) applied without forming the matrix
+        let n = 10;
+        let op = FnOperator::new(n, |x : &MatrixF64| {
+            let mut y = x.clone();
+            for i in 0..x.num_rows(){
+                y.set(i, 0, (i + 1) as f64 * x[i]);
+            }
+            y
+        });
+        let b : MatrixF64 = Matrix::ones(n, 1);
+        let result = cg(&op, &b, None, &IterativeOptions::new()).unwrap();
+        assert!(result.converged);
+        for i in 0..n{
+            assert!((result.x[i] - 1. / (i + 1) as f64).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_iteration_limit(){
+        let t = poisson(50);
+        let b : MatrixF64 = Matrix::ones(50, 1);
+        let options = IterativeOptions::new().max_iterations(3);
+        let result = cg(&t, &b, None, &options).unwrap();
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 3);
+        assert_eq!(result.history.len(), 4);
+    }
+
+    #[test]
+    fn test_initial_guess_and_dimensions(){
+        let a = non_symmetric();
+        let b = vector_f64(&[1., 2., 3., 4.]);
+        let x = GaussElimination::new(&a, &b).solve().unwrap();
+        let result = bicgstab(&a, &IdentityPreconditioner, &b, Some(&x),
+            &IterativeOptions::new()).unwrap();
+        assert_eq!(result.iterations, 0);
+        let b = vector_f64(&[1., 2.]);
+        assert!(cg(&a, &b, None, &IterativeOptions::new()).is_err());
+    }
+}
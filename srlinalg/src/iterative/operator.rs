@@ -0,0 +1,191 @@
+#![doc="Linear operators for iterative solvers
+
+Iterative methods only need the product of a matrix with a
+vector.  The ``LinearOperator`` trait abstracts this so that
+dense matrices, structured matrices and user supplied closures
+can be used interchangeably.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// A square linear operator y = A x acting on column vectors
+pub trait LinearOperator {
+
+    /// Returns the number of rows (and columns) of the operator
+    fn dimension(&self) -> usize;
+
+    /// Computes the product y = A x for a column vector x
+    fn apply(&self, x : &MatrixF64) -> MatrixF64;
+}
+
+
+impl LinearOperator for MatrixF64 {
+
+    fn dimension(&self) -> usize {
+        debug_assert!(self.is_square());
+        self.num_rows()
+    }
+
+    fn apply(&self, x : &MatrixF64) -> MatrixF64 {
+        self * x
+    }
+}
+
+
+impl<O:LinearOperator + ?Sized> LinearOperator for &O {
+
+    fn dimension(&self) -> usize {
+        (**self).dimension()
+    }
+
+    fn apply(&self, x : &MatrixF64) -> MatrixF64 {
+        (**self).apply(x)
+    }
+}
+
+
+/// A linear operator defined by a closure
+pub struct FnOperator<F> where F : Fn(&MatrixF64) -> MatrixF64 {
+    /// Size of the operator
+    n : usize,
+    /// The function computing A x
+    f : F
+}
+
+impl<F> FnOperator<F> where F : Fn(&MatrixF64) -> MatrixF64 {
+
+    /// Wraps a closure computing A x for an n x n operator A
+    pub fn new(n : usize, f : F) -> FnOperator<F> {
+        FnOperator{n : n, f : f}
+    }
+}
+
+impl<F> LinearOperator for FnOperator<F> where F : Fn(&MatrixF64) -> MatrixF64 {
+
+    fn dimension(&self) -> usize {
+        self.n
+    }
+
+    fn apply(&self, x : &MatrixF64) -> MatrixF64 {
+        (self.f)(x)
+    }
+}
+
+
+#[doc="A tridiagonal matrix stored by its three diagonals
+
+Typical of finite difference discretizations of one
+dimensional differential equations.
+"]
+pub struct TridiagonalOperator {
+    /// The sub-diagonal (n - 1 entries)
+    pub sub : MatrixF64,
+    /// The main diagonal (n entries)
+    pub diag : MatrixF64,
+    /// The super-diagonal (n - 1 entries)
+    pub sup : MatrixF64
+}
+
+impl TridiagonalOperator {
+
+    /// Constructs a tridiagonal operator from its diagonals
+    pub fn new(sub : MatrixF64, diag : MatrixF64, sup : MatrixF64)
+        -> SRResult<TridiagonalOperator> {
+        let n = diag.num_cells();
+        if n == 0 {
+            return Err(SRError::EmptyMatrix);
+        }
+        if sub.num_cells() != n - 1 || sup.num_cells() != n - 1 {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(TridiagonalOperator{sub : sub, diag : diag, sup : sup})
+    }
+
+    /// Constructs a tridiagonal operator with constant diagonals
+    pub fn from_constants(n : usize, sub : f64, diag : f64, sup : f64)
+        -> TridiagonalOperator {
+        assert!(n > 0);
+        TridiagonalOperator {
+            sub : Matrix::new_with(n - 1, 1, sub),
+            diag : Matrix::new_with(n, 1, diag),
+            sup : Matrix::new_with(n - 1, 1, sup)
+        }
+    }
+
+    /// Returns the equivalent dense matrix
+    pub fn to_matrix(&self) -> MatrixF64 {
+        let n = self.dimension();
+        let mut m : MatrixF64 = Matrix::zeros(n, n);
+        for i in 0..n {
+            m.set(i, i, self.diag[i]);
+            if i + 1 < n {
+                m.set(i + 1, i, self.sub[i]);
+                m.set(i, i + 1, self.sup[i]);
+            }
+        }
+        m
+    }
+}
+
+impl LinearOperator for TridiagonalOperator {
+
+    fn dimension(&self) -> usize {
+        self.diag.num_cells()
+    }
+
+    fn apply(&self, x : &MatrixF64) -> MatrixF64 {
+        let n = self.dimension();
+        debug_assert_eq!(x.num_cells(), n);
+        let mut y : MatrixF64 = Matrix::zeros(n, 1);
+        for i in 0..n {
+            let mut v = self.diag[i] * x[i];
+            if i > 0 {
+                v += self.sub[i - 1] * x[i - 1];
+            }
+            if i + 1 < n {
+                v += self.sup[i] * x[i + 1];
+            }
+            y.set(i, 0, v);
+        }
+        y
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_tridiagonal_apply(){
+        let t = TridiagonalOperator::from_constants(4, -1., 2., -1.);
+        let x = vector_f64(&[1., 2., 3., 4.]);
+        assert_eq!(t.apply(&x), &t.to_matrix() * &x);
+        assert_eq!(t.dimension(), 4);
+    }
+
+    #[test]
+    fn test_fn_operator(){
+        let op = FnOperator::new(3, |x : &MatrixF64| x.copy_mul_scalar(2.));
+        let x = vector_f64(&[1., 2., 3.]);
+        assert_eq!(op.apply(&x), vector_f64(&[2., 4., 6.]));
+    }
+
+    #[test]
+    fn test_tridiagonal_mismatch(){
+        let r = TridiagonalOperator::new(vector_f64(&[1.]),
+            vector_f64(&[1., 2., 3.]), vector_f64(&[1., 2.]));
+        assert!(r.is_err());
+    }
+}
@@ -0,0 +1,94 @@
+#![doc="Options and results shared by the iterative solvers
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Stopping criteria for iterative solvers
+#[derive(Debug, Clone, Copy)]
+pub struct IterativeOptions {
+    /// The iterations stop once ||b - Ax|| <= tolerance * ||b||
+    pub tolerance : f64,
+    /// The iterations also stop once ||b - Ax|| <= absolute_tolerance
+    pub absolute_tolerance : f64,
+    /// Maximum number of iterations
+    pub max_iterations : usize
+}
+
+impl IterativeOptions {
+
+    /// Default options: relative tolerance of 1e-10 and
+    /// at most 1000 iterations
+    pub fn new() -> IterativeOptions {
+        IterativeOptions {
+            tolerance : 1e-10,
+            absolute_tolerance : 0.,
+            max_iterations : 1000
+        }
+    }
+
+    /// Sets the relative tolerance
+    pub fn tolerance(mut self, tolerance : f64) -> IterativeOptions {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of iterations
+    pub fn max_iterations(mut self, max_iterations : usize) -> IterativeOptions {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Returns the residual norm below which the
+    /// iterations are considered converged
+    pub fn threshold(&self, b_norm : f64) -> f64 {
+        let t = self.tolerance * b_norm;
+        if t > self.absolute_tolerance { t } else { self.absolute_tolerance }
+    }
+}
+
+impl Default for IterativeOptions {
+    fn default() -> IterativeOptions {
+        IterativeOptions::new()
+    }
+}
+
+
+/// Outcome of an iterative solver
+#[derive(Debug, Clone)]
+pub struct IterativeResult {
+    /// The computed solution
+    pub x : MatrixF64,
+    /// Number of iterations performed
+    pub iterations : usize,
+    /// Norm of the final residual b - Ax
+    pub residual_norm : f64,
+    /// Indicates if the stopping criterion was met
+    pub converged : bool,
+    /// Residual norms: initial residual followed by one
+    /// entry per iteration
+    pub history : Vec<f64>
+}
+
+
+/// Validates the right hand side and the initial guess
+/// and returns the starting vector
+pub fn initial_guess(n : usize, b : &MatrixF64, x0 : Option<&MatrixF64>)
+    -> SRResult<MatrixF64> {
+    if !b.is_col() || b.num_rows() != n {
+        return Err(SRError::LRDimensionMismatch);
+    }
+    match x0 {
+        Some(x) => {
+            if !x.is_col() || x.num_rows() != n {
+                return Err(SRError::DimensionsMismatch);
+            }
+            Ok(x.clone())
+        },
+        None => Ok(Matrix::zeros(n, 1))
+    }
+}
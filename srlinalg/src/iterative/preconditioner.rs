@@ -0,0 +1,274 @@
+#![doc="Preconditioners for iterative solvers
+
+A preconditioner M approximates A such that systems
+M z = r are cheap to solve.  The incomplete factorizations
+below keep the sparsity pattern of A (no fill-in).
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// A preconditioner M approximating the matrix A
+pub trait Preconditioner {
+
+    /// Solves M z = r for a column vector r
+    fn precondition(&self, r : &MatrixF64) -> MatrixF64;
+}
+
+
+/// The trivial preconditioner M = I
+pub struct IdentityPreconditioner;
+
+impl Preconditioner for IdentityPreconditioner {
+
+    fn precondition(&self, r : &MatrixF64) -> MatrixF64 {
+        r.clone()
+    }
+}
+
+
+/// Jacobi (diagonal) preconditioner M = diag(A)
+pub struct JacobiPreconditioner {
+    /// Reciprocals of the diagonal entries of A
+    inv_diag : MatrixF64
+}
+
+impl JacobiPreconditioner {
+
+    /// Constructs the preconditioner from the diagonal of A
+    pub fn new(a : &MatrixF64) -> SRResult<JacobiPreconditioner> {
+        if !a.is_square() {
+            return Err(SRError::IsNotSquareMatrix);
+        }
+        let n = a.num_rows();
+        let mut inv_diag : MatrixF64 = Matrix::zeros(n, 1);
+        for i in 0..n {
+            let d = a.get(i, i).unwrap();
+            if d == 0. {
+                return Err(SRError::IsSingular);
+            }
+            inv_diag.set(i, 0, 1. / d);
+        }
+        Ok(JacobiPreconditioner{inv_diag : inv_diag})
+    }
+}
+
+impl Preconditioner for JacobiPreconditioner {
+
+    fn precondition(&self, r : &MatrixF64) -> MatrixF64 {
+        r.mul_elt(&self.inv_diag)
+    }
+}
+
+
+#[doc="Incomplete LU factorization with zero fill-in, ILU(0)
+
+L (unit lower triangular) and U (upper triangular) are
+stored together and have nonzero entries only where
+A has nonzero entries.
+"]
+pub struct ILU0Preconditioner {
+    /// Strict lower part holds L, upper part holds U
+    lu : MatrixF64
+}
+
+impl ILU0Preconditioner {
+
+    /// Computes the ILU(0) factorization of A
+    pub fn new(a : &MatrixF64) -> SRResult<ILU0Preconditioner> {
+        if !a.is_square() {
+            return Err(SRError::IsNotSquareMatrix);
+        }
+        let n = a.num_rows();
+        let mut lu = a.clone();
+        for i in 1..n {
+            for k in 0..i {
+                if a.get(i, k).unwrap() == 0. {
+                    continue;
+                }
+                let pivot = lu.get(k, k).unwrap();
+                if pivot == 0. {
+                    return Err(SRError::IsSingular);
+                }
+                let factor = lu.get(i, k).unwrap() / pivot;
+                lu.set(i, k, factor);
+                for j in (k + 1)..n {
+                    if a.get(i, j).unwrap() == 0. {
+                        continue;
+                    }
+                    let v = lu.get(i, j).unwrap() - factor * lu.get(k, j).unwrap();
+                    lu.set(i, j, v);
+                }
+            }
+        }
+        for i in 0..n {
+            if lu.get(i, i).unwrap() == 0. {
+                return Err(SRError::IsSingular);
+            }
+        }
+        Ok(ILU0Preconditioner{lu : lu})
+    }
+}
+
+impl Preconditioner for ILU0Preconditioner {
+
+    fn precondition(&self, r : &MatrixF64) -> MatrixF64 {
+        let lu = &self.lu;
+        let n = lu.num_rows();
+        let mut z = r.clone();
+        // L y = r
+        for i in 0..n {
+            let mut v = z[i];
+            for k in 0..i {
+                v -= lu.get(i, k).unwrap() * z[k];
+            }
+            z.set(i, 0, v);
+        }
+        // U z = y
+        for i in (0..n).rev() {
+            let mut v = z[i];
+            for k in (i + 1)..n {
+                v -= lu.get(i, k).unwrap() * z[k];
+            }
+            z.set(i, 0, v / lu.get(i, i).unwrap());
+        }
+        z
+    }
+}
+
+
+#[doc="Incomplete Cholesky factorization with zero fill-in, IC(0)
+
+Applicable to symmetric positive definite matrices.
+The factor L has nonzero entries only where A has
+nonzero entries.
+"]
+pub struct IncompleteCholeskyPreconditioner {
+    /// The lower triangular factor L with A ~ L L'
+    l : MatrixF64
+}
+
+impl IncompleteCholeskyPreconditioner {
+
+    /// Computes the IC(0) factorization of A
+    pub fn new(a : &MatrixF64) -> SRResult<IncompleteCholeskyPreconditioner> {
+        if !a.is_square() {
+            return Err(SRError::IsNotSquareMatrix);
+        }
+        let n = a.num_rows();
+        let mut l = a.lt();
+        for k in 0..n {
+            let d = l.get(k, k).unwrap();
+            if d <= 0. {
+                return Err(SRError::IsNotPositiveDefinite);
+            }
+            let d = d.sqrt();
+            l.set(k, k, d);
+            for i in (k + 1)..n {
+                if a.get(i, k).unwrap() != 0. {
+                    let v = l.get(i, k).unwrap() / d;
+                    l.set(i, k, v);
+                }
+            }
+            for j in (k + 1)..n {
+                let ljk = l.get(j, k).unwrap();
+                if ljk == 0. {
+                    continue;
+                }
+                for i in j..n {
+                    if a.get(i, j).unwrap() != 0. {
+                        let v = l.get(i, j).unwrap() - l.get(i, k).unwrap() * ljk;
+                        l.set(i, j, v);
+                    }
+                }
+            }
+        }
+        Ok(IncompleteCholeskyPreconditioner{l : l})
+    }
+}
+
+impl Preconditioner for IncompleteCholeskyPreconditioner {
+
+    fn precondition(&self, r : &MatrixF64) -> MatrixF64 {
+        let l = &self.l;
+        let n = l.num_rows();
+        let mut z = r.clone();
+        // L y = r
+        for i in 0..n {
+            let mut v = z[i];
+            for k in 0..i {
+                v -= l.get(i, k).unwrap() * z[k];
+            }
+            z.set(i, 0, v / l.get(i, i).unwrap());
+        }
+        // L' z = y
+        for i in (0..n).rev() {
+            let mut v = z[i];
+            for k in (i + 1)..n {
+                v -= l.get(k, i).unwrap() * z[k];
+            }
+            z.set(i, 0, v / l.get(i, i).unwrap());
+        }
+        z
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn tridiag() -> MatrixF64 {
+        matrix_rw_f64(4, 4, &[
+             4., -1.,  0.,  0.,
+            -1.,  4., -1.,  0.,
+             0., -1.,  4., -1.,
+             0.,  0., -1.,  4.,
+            ])
+    }
+
+    #[test]
+    fn test_jacobi_preconditioner(){
+        let m = JacobiPreconditioner::new(&tridiag()).unwrap();
+        let z = m.precondition(&vector_f64(&[4., 8., 12., 16.]));
+        assert_eq!(z, vector_f64(&[1., 2., 3., 4.]));
+        let a = matrix_rw_f64(2, 2, &[0., 1., 1., 0.]);
+        assert!(JacobiPreconditioner::new(&a).is_err());
+    }
+
+    #[test]
+    fn test_ilu0_exact_for_tridiagonal(){
+        // ILU(0) has no fill-in for tridiagonal matrices
+        // and hence is an exact LU factorization.
+        let a = tridiag();
+        let m = ILU0Preconditioner::new(&a).unwrap();
+        let x = vector_f64(&[1., 2., 3., 4.]);
+        let z = m.precondition(&(&a * &x));
+        assert!((&z - &x).max_abs_scalar_value() < 1e-12);
+    }
+
+    #[test]
+    fn test_ic0_exact_for_tridiagonal(){
+        let a = tridiag();
+        let m = IncompleteCholeskyPreconditioner::new(&a).unwrap();
+        let x = vector_f64(&[1., -2., 3., -4.]);
+        let z = m.precondition(&(&a * &x));
+        assert!((&z - &x).max_abs_scalar_value() < 1e-12);
+    }
+
+    #[test]
+    fn test_ic0_not_positive_definite(){
+        let a = matrix_rw_f64(2, 2, &[1., 2., 2., 1.]);
+        assert!(IncompleteCholeskyPreconditioner::new(&a).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+#![doc="Stationary iterative methods for solving Ax = b
+
+* ``jacobi``: Jacobi iterations
+* ``gauss_seidel``: Gauss-Seidel iterations
+* ``sor``: successive over-relaxation
+
+These methods need access to the individual entries of A
+and hence work on dense matrices. Convergence is guaranteed
+for strictly diagonally dominant matrices (and for symmetric
+positive definite matrices in case of Gauss-Seidel and SOR
+with 0 < omega < 2).
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use iterative::options::*;
+
+
+/// Verifies that A is square with non-zero diagonal
+fn check_matrix(a : &MatrixF64) -> SRResult<()> {
+    if !a.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    for i in 0..a.num_rows() {
+        if a.get(i, i).unwrap() == 0. {
+            return Err(SRError::IsSingular);
+        }
+    }
+    Ok(())
+}
+
+
+/// Jacobi method
+pub fn jacobi(a : &MatrixF64, b : &MatrixF64,
+    x0 : Option<&MatrixF64>,
+    options : &IterativeOptions) -> SRResult<IterativeResult> {
+    check_matrix(a)?;
+    let n = a.num_rows();
+    let mut x = initial_guess(n, b, x0)?;
+    let threshold = options.threshold(vec_norm_l2(b));
+    let mut r_norm = vec_norm_l2(&(b - &(a * &x)));
+    let mut history = vec![r_norm];
+    let mut iterations = 0;
+    while r_norm > threshold && iterations < options.max_iterations {
+        let mut x_new = x.clone();
+        for i in 0..n {
+            let mut v = b[i];
+            for j in 0..n {
+                if j != i {
+                    v -= a.get(i, j).unwrap() * x[j];
+                }
+            }
+            x_new.set(i, 0, v / a.get(i, i).unwrap());
+        }
+        x = x_new;
+        r_norm = vec_norm_l2(&(b - &(a * &x)));
+        history.push(r_norm);
+        iterations += 1;
+    }
+    Ok(IterativeResult {
+        x : x,
+        iterations : iterations,
+        residual_norm : r_norm,
+        converged : r_norm <= threshold,
+        history : history
+    })
+}
+
+
+/// Gauss-Seidel method
+pub fn gauss_seidel(a : &MatrixF64, b : &MatrixF64,
+    x0 : Option<&MatrixF64>,
+    options : &IterativeOptions) -> SRResult<IterativeResult> {
+    sor(a, b, x0, 1., options)
+}
+
+
+/// Successive over-relaxation with relaxation factor omega.
+///
+/// omega = 1 gives the Gauss-Seidel method.
+pub fn sor(a : &MatrixF64, b : &MatrixF64,
+    x0 : Option<&MatrixF64>,
+    omega : f64,
+    options : &IterativeOptions) -> SRResult<IterativeResult> {
+    check_matrix(a)?;
+    let n = a.num_rows();
+    let mut x = initial_guess(n, b, x0)?;
+    let threshold = options.threshold(vec_norm_l2(b));
+    let mut r_norm = vec_norm_l2(&(b - &(a * &x)));
+    let mut history = vec![r_norm];
+    let mut iterations = 0;
+    while r_norm > threshold && iterations < options.max_iterations {
+        for i in 0..n {
+            let mut v = b[i];
+            for j in 0..n {
+                if j != i {
+                    v -= a.get(i, j).unwrap() * x[j];
+                }
+            }
+            let gs = v / a.get(i, i).unwrap();
+            let xi = x[i];
+            x.set(i, 0, (1. - omega) * xi + omega * gs);
+        }
+        r_norm = vec_norm_l2(&(b - &(a * &x)));
+        history.push(r_norm);
+        iterations += 1;
+    }
+    Ok(IterativeResult {
+        x : x,
+        iterations : iterations,
+        residual_norm : r_norm,
+        converged : r_norm <= threshold,
+        history : history
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn diagonally_dominant() -> MatrixF64 {
+        matrix_rw_f64(3, 3, &[
+            10., -1., 2.,
+            -1., 11., -1.,
+            2., -1., 10.
+            ])
+    }
+
+    #[test]
+    fn test_jacobi(){
+        let a = diagonally_dominant();
+        let x = vector_f64(&[1., 2., -1.]);
+        let b = &a * &x;
+        let result = jacobi(&a, &b, None, &IterativeOptions::new()).unwrap();
+        assert!(result.converged);
+        assert!((&result.x - &x).max_abs_scalar_value() < 1e-8);
+    }
+
+    #[test]
+    fn test_gauss_seidel_faster_than_jacobi(){
+        let a = diagonally_dominant();
+        let b = vector_f64(&[6., 25., -11.]);
+        let options = IterativeOptions::new();
+        let j = jacobi(&a, &b, None, &options).unwrap();
+        let gs = gauss_seidel(&a, &b, None, &options).unwrap();
+        assert!(gs.converged);
+        assert!(gs.iterations < j.iterations);
+    }
+
+    #[test]
+    fn test_sor(){
+        let a = matrix_rw_f64(3, 3, &[
+            4., -1., 0.,
+            -1., 4., -1.,
+            0., -1., 4.
+            ]);
+        let b = vector_f64(&[2., 4., 10.]);
+        let result = sor(&a, &b, None, 1.1, &IterativeOptions::new()).unwrap();
+        assert!(result.converged);
+        let r = &b - &(&a * &result.x);
+        assert!(r.max_abs_scalar_value() < 1e-8);
+    }
+
+    #[test]
+    fn test_one_by_one(){
+        let a = matrix_rw_f64(1, 1, &[4.]);
+        let b = vector_f64(&[2.]);
+        let options = IterativeOptions::new();
+        for result in [jacobi(&a, &b, None, &options).unwrap(),
+            gauss_seidel(&a, &b, None, &options).unwrap(),
+            sor(&a, &b, None, 1.2, &options).unwrap()].iter(){
+            assert!(result.converged);
+            assert!((result.x[0] - 0.5).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_zero_diagonal(){
+        let a = matrix_rw_f64(2, 2, &[0., 1., 1., 0.]);
+        let b = vector_f64(&[1., 1.]);
+        assert!(jacobi(&a, &b, None, &IterativeOptions::new()).is_err());
+    }
+}
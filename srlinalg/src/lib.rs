@@ -10,9 +10,28 @@ extern crate srdiscrete;
 pub mod linear_system;
 pub mod det;
 pub mod lu;
+pub mod cholesky;
+pub mod factorization;
 pub mod singularity;
 pub mod inverse;
 pub mod rank;
+pub mod norm;
+pub mod eigen;
+pub mod schur;
+pub mod matfun;
+pub mod sylvester;
+pub mod riccati;
+pub mod integer;
+pub mod qr;
+pub mod svd;
+
+pub mod iterative{
+    pub mod operator;
+    pub mod preconditioner;
+    pub mod options;
+    pub mod krylov;
+    pub mod stationary;
+}
 
 pub mod matrix{
     pub mod mat_impl;
@@ -9,7 +9,9 @@
 // local imports
 //use error::SRError;
 use srmatrix::api::*;
+use srmatrix::vecops::{to_vec, to_col};
 use srdiscrete::api::*;
+use factorization::Factorization;
 
 
 #[doc="LU factorization with partial
@@ -242,7 +244,77 @@ impl LUDecomposition{
 
     /// Computes the determinant
     pub fn det(&self)-> f64{
-        vec_reduce_product(&self.diag_vector)
+        let d = self.diag_vector.cell_iter().fold(1., |p, v| p * v);
+        if is_even_permutation(&self.perm_vector) {
+            d
+        }
+        else {
+            -d
+        }
+    }
+
+    /// Returns true if a zero (or non-finite) pivot
+    /// was encountered during the factorization
+    pub fn is_singular(&self) -> bool {
+        self.diag_vector.cell_iter().any(|v| v == 0. || !v.is_finite())
+    }
+
+    /// Solves L D U X = B in place where L and U are stored in ``a``
+    fn ldu_solve_in_place(&self, b : &mut MatrixF64){
+        let a = &self.a;
+        let n = a.num_rows();
+        for c in 0..b.num_cols(){
+            // Forward substitution with unit lower triangular L
+            for r in 0..n{
+                let mut v = b.get(r, c).unwrap();
+                for k in 0..r{
+                    v -= a.get(r, k).unwrap() * b.get(k, c).unwrap();
+                }
+                b.set(r, c, v);
+            }
+            // Inverse scaling with D
+            for r in 0..n{
+                let v = b.get(r, c).unwrap();
+                b.set(r, c, v / self.diag_vector[r]);
+            }
+            // Backward substitution with unit upper triangular U
+            for r in (0..n).rev(){
+                let mut v = b.get(r, c).unwrap();
+                for k in (r + 1)..n{
+                    v -= a.get(r, k).unwrap() * b.get(k, c).unwrap();
+                }
+                b.set(r, c, v);
+            }
+        }
+    }
+
+    /// Solves U' D L' X = B in place where L and U are stored in ``a``
+    fn ldu_solve_transpose_in_place(&self, b : &mut MatrixF64){
+        let a = &self.a;
+        let n = a.num_rows();
+        for c in 0..b.num_cols(){
+            // Forward substitution with unit lower triangular U'
+            for r in 0..n{
+                let mut v = b.get(r, c).unwrap();
+                for k in 0..r{
+                    v -= a.get(k, r).unwrap() * b.get(k, c).unwrap();
+                }
+                b.set(r, c, v);
+            }
+            // Inverse scaling with D
+            for r in 0..n{
+                let v = b.get(r, c).unwrap();
+                b.set(r, c, v / self.diag_vector[r]);
+            }
+            // Backward substitution with unit upper triangular L'
+            for r in (0..n).rev(){
+                let mut v = b.get(r, c).unwrap();
+                for k in (r + 1)..n{
+                    v -= a.get(k, r).unwrap() * b.get(k, c).unwrap();
+                }
+                b.set(r, c, v);
+            }
+        }
     }
 
     pub fn print(&self){
@@ -257,6 +329,77 @@ impl LUDecomposition{
     }
 }
 
+
+/// Reusable solver based on LU factorization.
+///
+/// If ``pre`` is set, the factorization is PA = LDU, otherwise
+/// it is AP = LDU.
+impl Factorization for LUDecomposition {
+
+    fn size(&self) -> usize {
+        self.a.num_rows()
+    }
+
+    fn solve_in_place(&self, b : &mut MatrixF64) -> SRResult<()> {
+        if b.num_rows() != self.size() {
+            return Err(SRError::LRDimensionMismatch);
+        }
+        if self.is_singular() {
+            return Err(SRError::IsSingular);
+        }
+        if self.pre {
+            // L D U X = P B
+            *b = b.permuted_rows(&self.perm_vector);
+            self.ldu_solve_in_place(b);
+        }
+        else {
+            // L D U Y = B, X = P Y
+            self.ldu_solve_in_place(b);
+            *b = b.permuted_rows(&inverse_permutation(&self.perm_vector));
+        }
+        Ok(())
+    }
+
+    fn solve_transpose_in_place(&self, b : &mut MatrixF64) -> SRResult<()> {
+        if b.num_rows() != self.size() {
+            return Err(SRError::LRDimensionMismatch);
+        }
+        if self.is_singular() {
+            return Err(SRError::IsSingular);
+        }
+        if self.pre {
+            // U' D L' Y = B, X = P' Y
+            self.ldu_solve_transpose_in_place(b);
+            *b = b.permuted_rows(&inverse_permutation(&self.perm_vector));
+        }
+        else {
+            // U' D L' X = P' B
+            *b = b.permuted_rows(&self.perm_vector);
+            self.ldu_solve_transpose_in_place(b);
+        }
+        Ok(())
+    }
+
+    fn det(&self) -> f64 {
+        LUDecomposition::det(self)
+    }
+
+    fn log_det(&self) -> SRResult<(f64, f64)> {
+        if self.is_singular() {
+            return Err(SRError::IsSingular);
+        }
+        let mut sign = if is_even_permutation(&self.perm_vector) {1.} else {-1.};
+        let mut log_abs = 0.;
+        for v in self.diag_vector.cell_iter(){
+            if v < 0. {
+                sign = -sign;
+            }
+            log_abs += v.abs().ln();
+        }
+        Ok((sign, log_abs))
+    }
+}
+
 ///Performs LU factorization  A = LU 
 pub fn lu_ero(a : &MatrixF64) -> (MatrixF64, MatrixF64){
         let mut lu = LUDecomposition::new(a.clone());
@@ -297,6 +440,25 @@ pub fn lup_eco(a : &MatrixF64) -> (MatrixF64, MatrixF64, MatrixF64){
 }
 
 
+/// Computes the LU factorization of a square matrix for
+/// repeated solves.
+///
+/// Returns ``IsSingular`` if a zero pivot is encountered.
+pub fn lu_factor(a : &MatrixF64) -> SRResult<LUDecomposition> {
+    let mut lu = LUDecomposition::new(a.clone());
+    lu.decompose_ero();
+    if lu.is_singular() {
+        return Err(SRError::IsSingular);
+    }
+    Ok(lu)
+}
+
+/// Solves A x = b for a plain vector b with a factorization of A
+pub fn lu_solve(lu : &LUDecomposition, b : &[f64]) -> SRResult<Vec<f64>> {
+    Ok(to_vec(&lu.solve(&to_col(b))?))
+}
+
+
 /******************************************************
  *
  *   Unit tests follow.
@@ -306,6 +468,7 @@ pub fn lup_eco(a : &MatrixF64) -> (MatrixF64, MatrixF64, MatrixF64){
 mod test{
     use super::*;
     use matrix::mat_traits::*;
+    use factorization::*;
 
     #[test]
     fn test_lu_ero_0(){
@@ -517,7 +680,158 @@ mod test{
         assert!(lus.max_abs_diff(&a) < 1e-10);
     }
 
+    #[test]
+    fn test_lu_solve_multiple_rhs(){
+        let a = matrix_rw_f64(3, 3, &[
+            2., 4., -2.,
+            1., -6., 7.,
+            1., 0., 2.
+            ]);
+        let x = matrix_rw_f64(3, 2, &[
+            1., -1.,
+            1., 2.,
+            2., 0.5
+            ]);
+        let b = &a * &x;
+        let mut ero = LUDecomposition::new(a.clone());
+        ero.decompose_ero();
+        let mut eco = LUDecomposition::new(a.clone());
+        eco.decompose_eco();
+        let mut crout = LUDecomposition::new(a.clone());
+        crout.decompose_crout().unwrap();
+        for lu in [ero, eco, crout].iter(){
+            let z = lu.solve(&b).unwrap();
+            assert!((&z - &x).max_abs_scalar_value() < 1e-12);
+            let mut z = b.clone();
+            lu.solve_in_place(&mut z).unwrap();
+            assert!((&z - &x).max_abs_scalar_value() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_lu_solve_transpose(){
+        let a = matrix_rw_f64(3, 3, &[
+            0., 1., 2.,
+            3., -1., 0.,
+            1., -2., 1.
+            ]);
+        let x = matrix_rw_f64(3, 2, &[
+            1., 3.,
+            -2., 1.,
+            4., 0.
+            ]);
+        let b = &a.transpose() * &x;
+        let mut ero = LUDecomposition::new(a.clone());
+        ero.decompose_ero();
+        let mut eco = LUDecomposition::new(a.clone());
+        eco.decompose_eco();
+        for lu in [ero, eco].iter(){
+            let z = lu.solve_transpose(&b).unwrap();
+            assert!((&z - &x).max_abs_scalar_value() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_lu_inverse(){
+        let a = hilbert(4);
+        let mut lu = LUDecomposition::new(a.clone());
+        lu.decompose_ero();
+        let b = lu.inverse().unwrap();
+        let i : MatrixF64 = Matrix::identity(4, 4);
+        assert!((&(&a * &b) - &i).max_abs_scalar_value() < 1e-10);
+    }
+
+    #[test]
+    fn test_lu_det_with_pivoting(){
+        let a = matrix_rw_f64(3,3,&[
+            0. , 1., 2.,
+            3., -1., 0.,
+            1., -2., 1.
+            ]);
+        let mut ero = LUDecomposition::new(a.clone());
+        ero.decompose_ero();
+        assert!((ero.det() + 13.).abs() < 1e-12);
+        let mut eco = LUDecomposition::new(a.clone());
+        eco.decompose_eco();
+        assert!((eco.det() + 13.).abs() < 1e-12);
+        let (sign, log_abs) = ero.log_det().unwrap();
+        assert_eq!(sign, -1.);
+        assert!((log_abs - 13f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lu_one_by_one(){
+        let a = matrix_rw_f64(1, 1, &[-4.]);
+        let mut lu = LUDecomposition::new(a.clone());
+        lu.decompose_ero();
+        assert_eq!(lu.det(), -4.);
+        assert_eq!(Factorization::det(&lu), -4.);
+        assert_eq!(lu.log_det().unwrap(), (-1., 4f64.ln()));
+        let x = lu.solve(&matrix_rw_f64(1, 1, &[2.])).unwrap();
+        assert_eq!(x, matrix_rw_f64(1, 1, &[-0.5]));
+        assert_eq!(lu.inverse().unwrap(), matrix_rw_f64(1, 1, &[-0.25]));
+        let mut eco = LUDecomposition::new(a);
+        eco.decompose_eco();
+        assert_eq!(eco.det(), -4.);
+    }
 
+    #[test]
+    fn test_lu_factor_solve(){
+        let a = matrix_rw_f64(2, 2, &[2., 1., 1., 3.]);
+        let lu = lu_factor(&a).unwrap();
+        let x = lu_solve(&lu, &[3., 5.]).unwrap();
+        assert!((x[0] - 0.8).abs() < 1e-15);
+        assert!((x[1] - 1.4).abs() < 1e-15);
+        assert!(lu_solve(&lu, &[1.]).is_err());
+        assert!(lu_factor(&matrix_rw_f64(2, 2, &[1., 2., 2., 4.])).is_err());
+    }
+
+    #[test]
+    fn test_lu_solve_singular(){
+        let a = matrix_rw_f64(3, 3, &[
+            1., 2., 3.,
+            4., 5., 6.,
+            7., 8., 9.
+            ]);
+        let b = vector_f64(&[1., 2., 3.]);
+        let mut eco = LUDecomposition::new(a.clone());
+        eco.decompose_eco();
+        assert!(eco.solve(&b).is_err());
+        assert!(eco.inverse().is_err());
+        assert!(eco.log_det().is_err());
+        assert_eq!(eco.det(), 0.);
+        let a = matrix_rw_f64(2, 2, &[
+            0., 0.,
+            0., 0.
+            ]);
+        let mut ero = LUDecomposition::new(a);
+        ero.decompose_ero();
+        assert!(ero.solve(&vector_f64(&[1., 1.])).is_err());
+    }
+
+    #[test]
+    fn test_lu_solve_dimension_mismatch(){
+        let a = hilbert(3);
+        let mut lu = LUDecomposition::new(a);
+        lu.decompose_ero();
+        assert!(lu.solve(&vector_f64(&[1., 2.])).is_err());
+    }
+
+    #[test]
+    fn test_lu_refine_hilbert(){
+        let n = 10;
+        let a = hilbert(n);
+        let x : MatrixF64 = Matrix::ones(n, 1);
+        let b = &a * &x;
+        let mut lu = LUDecomposition::new(a.clone());
+        lu.decompose_ero();
+        let mut z = lu.solve(&b).unwrap();
+        let iterations = lu.refine(&a, &b, &mut z, 5, 1e-15).unwrap();
+        assert!((1..=5).contains(&iterations));
+        assert!((&(&a * &z) - &b).max_abs_scalar_value() < 1e-10);
+        let z = solve_refined(&lu, &a, &b, 5, 1e-15).unwrap();
+        assert!((&z - &x).max_abs_scalar_value() < 1e-3);
+    }
 
 }
 /******************************************************
@@ -0,0 +1,467 @@
+#![doc="Functions of matrices
+
+* ``expm``: matrix exponential by scaling and squaring with
+  Padé approximants
+* ``sqrtm``: principal square root by Denman-Beavers iterations
+* ``logm``: principal logarithm by inverse scaling and squaring
+* ``funm_sym``: f(A) for a symmetric matrix A via its eigen
+  value decomposition
+* ``funm``: f(A) for a general matrix A via its complex Schur
+  decomposition and the Parlett recurrence
+
+References:
+
+* N. J. Higham, The scaling and squaring method for the matrix
+  exponential revisited, SIAM J. Matrix Anal. Appl. 26(4), 2005.
+* N. J. Higham, Functions of Matrices: Theory and Computation,
+  SIAM, 2008.
+"]
+
+
+// std imports
+use std::f64;
+
+// external imports
+use num::complex::Complex64;
+
+// local imports
+use srmatrix::api::*;
+use lu::LUDecomposition;
+use factorization::Factorization;
+use inverse::inverse_ero;
+use norm::norm_1;
+use eigen::sym_eigen;
+use schur::schur_complex;
+
+
+/// Verifies that A is square
+fn check_square(a : &MatrixF64) -> SRResult<()> {
+    if !a.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    Ok(())
+}
+
+
+/// Coefficients of the [3/3] Padé approximant to exp
+const PADE_3 : [f64; 4] = [120., 60., 12., 1.];
+
+/// Coefficients of the [5/5] Padé approximant to exp
+const PADE_5 : [f64; 6] = [30240., 15120., 3360., 420., 30., 1.];
+
+/// Coefficients of the [7/7] Padé approximant to exp
+const PADE_7 : [f64; 8] = [17297280., 8648640., 1995840., 277200.,
+    25200., 1512., 56., 1.];
+
+/// Coefficients of the [9/9] Padé approximant to exp
+const PADE_9 : [f64; 10] = [17643225600., 8821612800., 2075673600.,
+    302702400., 30270240., 2162160., 110880., 3960., 90., 1.];
+
+/// Coefficients of the [13/13] Padé approximant to exp
+const PADE_13 : [f64; 14] = [64764752532480000., 32382376266240000.,
+    7771770303897600., 1187353796428800., 129060195264000.,
+    10559470521600., 670442572800., 33522128640., 1323241920.,
+    40840800., 960960., 16380., 182., 1.];
+
+/// Largest 1-norms for which the Padé approximants of
+/// degree 3, 5, 7, 9 and 13 are accurate to double precision
+const THETA : [f64; 5] = [1.495585217958292e-2, 2.53939833006323e-1,
+    9.504178996162932e-1, 2.097847961257068, 5.371920351148152];
+
+
+/// Evaluates the Padé approximant with given coefficients at A
+fn pade_exp(a : &MatrixF64, b : &[f64]) -> SRResult<MatrixF64> {
+    let n = a.num_rows();
+    let a2 = a * a;
+    // Even powers of A: I, A^2, A^4, ...
+    let mut powers : Vec<MatrixF64> = vec![Matrix::identity(n, n)];
+    while 2 * powers.len() < b.len() {
+        let p = &powers[powers.len() - 1] * &a2;
+        powers.push(p);
+    }
+    let mut u : MatrixF64 = Matrix::zeros(n, n);
+    let mut v : MatrixF64 = Matrix::zeros(n, n);
+    for (j, p) in powers.iter().enumerate() {
+        v = &v + &p.copy_mul_scalar(b[2 * j]);
+        if 2 * j + 1 < b.len() {
+            u = &u + &p.copy_mul_scalar(b[2 * j + 1]);
+        }
+    }
+    let u = a * &u;
+    // Solve (V - U) R = (V + U)
+    let mut lu = LUDecomposition::new(&v - &u);
+    lu.decompose_ero();
+    lu.solve(&(&v + &u))
+}
+
+
+#[doc="Computes the matrix exponential e^A.
+
+# Remarks
+
+Uses the scaling and squaring algorithm of Higham (2005).
+The degree of the Padé approximant is chosen based on the
+1-norm of A. If required, A is scaled down by a power of 2
+and the result is squared back.
+"]
+pub fn expm(a : &MatrixF64) -> SRResult<MatrixF64> {
+    check_square(a)?;
+    let norm = norm_1(a);
+    if !norm.is_finite() {
+        return Err(SRError::NotConverged);
+    }
+    let low_degrees : [&[f64]; 4] = [&PADE_3, &PADE_5, &PADE_7, &PADE_9];
+    for (i, b) in low_degrees.iter().enumerate() {
+        if norm <= THETA[i] {
+            return pade_exp(a, b);
+        }
+    }
+    let s = if norm > THETA[4] {
+        (norm / THETA[4]).log2().ceil() as i32
+    } else {
+        0
+    };
+    let scaled = a.copy_mul_scalar(2f64.powi(-s));
+    let mut r = pade_exp(&scaled, &PADE_13)?;
+    for _ in 0..s {
+        r = &r * &r;
+    }
+    Ok(r)
+}
+
+
+#[doc="Computes the principal square root of A.
+
+# Remarks
+
+Uses the Denman-Beavers iterations
+
+Y <- (Y + Z^-1) / 2,  Z <- (Z + Y^-1) / 2
+
+starting with Y = A and Z = I.  Y converges to
+the square root of A and Z to its inverse.
+
+The principal square root exists when A has no eigen
+values on the closed negative real axis. Otherwise
+the iterations fail to converge.
+"]
+pub fn sqrtm(a : &MatrixF64) -> SRResult<MatrixF64> {
+    check_square(a)?;
+    let n = a.num_rows();
+    let mut y = a.clone();
+    let mut z : MatrixF64 = Matrix::identity(n, n);
+    for _ in 0..100 {
+        let y_inv = inverse_ero(&mut y.clone())?;
+        let z_inv = inverse_ero(&mut z.clone())?;
+        let y_next = (&y + &z_inv).copy_mul_scalar(0.5);
+        let z_next = (&z + &y_inv).copy_mul_scalar(0.5);
+        let change = norm_1(&(&y_next - &y));
+        y = y_next;
+        z = z_next;
+        if !change.is_finite() {
+            break;
+        }
+        if change <= 10. * (n as f64) * f64::EPSILON * norm_1(&y) {
+            return Ok(y);
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/// Computes the nodes and weights of the m-point Gauss-Legendre
+/// quadrature rule on [0, 1]
+fn gauss_legendre_01(m : usize) -> (Vec<f64>, Vec<f64>) {
+    let mut nodes = Vec::with_capacity(m);
+    let mut weights = Vec::with_capacity(m);
+    for i in 0..m {
+        // Initial guess for the i-th root of P_m on [-1, 1]
+        let mut x = (f64::consts::PI * (i as f64 + 0.75) / (m as f64 + 0.5)).cos();
+        let mut dp = 1.;
+        for _ in 0..100 {
+            // Evaluate P_m(x) and its derivative by the three term recurrence
+            let mut p0 = 1.;
+            let mut p1 = x;
+            for k in 2..(m + 1) {
+                let k = k as f64;
+                let p2 = ((2. * k - 1.) * x * p1 - (k - 1.) * p0) / k;
+                p0 = p1;
+                p1 = p2;
+            }
+            dp = m as f64 * (x * p1 - p0) / (x * x - 1.);
+            let dx = p1 / dp;
+            x -= dx;
+            if dx.abs() <= f64::EPSILON {
+                break;
+            }
+        }
+        nodes.push((1. - x) * 0.5);
+        weights.push(1. / ((1. - x * x) * dp * dp));
+    }
+    (nodes, weights)
+}
+
+
+#[doc="Computes the principal logarithm of A.
+
+# Remarks
+
+Uses inverse scaling and squaring: square roots of A are
+taken until A is close to the identity. Then log(I + X) is
+evaluated by the Padé approximant in partial fraction form
+
+log(I + X) = sum w_j X (I + x_j X)^-1
+
+where x_j and w_j are the nodes and weights of Gauss-Legendre
+quadrature on [0, 1]. The result is scaled back by 2^k where k
+is the number of square roots taken.
+
+The principal logarithm exists when A has no eigen values on
+the closed negative real axis.
+"]
+pub fn logm(a : &MatrixF64) -> SRResult<MatrixF64> {
+    check_square(a)?;
+    let n = a.num_rows();
+    let identity : MatrixF64 = Matrix::identity(n, n);
+    let mut b = a.clone();
+    let mut k = 0;
+    while norm_1(&(&b - &identity)) > 0.25 {
+        b = sqrtm(&b)?;
+        k += 1;
+        if k > 64 {
+            return Err(SRError::NotConverged);
+        }
+    }
+    let x = &b - &identity;
+    let (nodes, weights) = gauss_legendre_01(8);
+    let mut result : MatrixF64 = Matrix::zeros(n, n);
+    for (node, weight) in nodes.iter().zip(weights.iter()) {
+        let mut lu = LUDecomposition::new(&identity + &x.copy_mul_scalar(*node));
+        lu.decompose_ero();
+        let term = lu.solve(&x)?;
+        result = &result + &term.copy_mul_scalar(*weight);
+    }
+    Ok(result.copy_mul_scalar(2f64.powi(k)))
+}
+
+
+#[doc="Computes f(A) for a real symmetric matrix A.
+
+With A = V diag(w) V', f(A) = V diag(f(w)) V'.
+"]
+pub fn funm_sym<F>(a : &MatrixF64, f : F) -> SRResult<MatrixF64>
+    where F : Fn(f64) -> f64 {
+    let (w, v) = sym_eigen(a)?;
+    let n = w.num_cells();
+    let mut fw : MatrixF64 = Matrix::zeros(n, 1);
+    for i in 0..n {
+        fw.set(i, 0, f(w[i]));
+    }
+    let d = Matrix::diag_from_vec(&fw);
+    Ok(&(&v * &d) * &v.transpose())
+}
+
+
+#[doc="Computes f(A) for a real square matrix A.
+
+Symmetric matrices are handled via their eigen value
+decomposition. For other matrices, the complex Schur
+decomposition A = Q T Q^H is computed and f(T) is obtained
+by the Parlett recurrence. Then f(A) = Q f(T) Q^H.
+
+The real part of the result is returned. This is exact
+when f maps conjugate pairs to conjugate pairs (as is the
+case for functions with real Taylor coefficients).
+
+# Remarks
+
+The Parlett recurrence divides by differences of eigen values.
+An error is returned if A has repeated (or nearly repeated)
+eigen values.
+"]
+pub fn funm<F>(a : &MatrixF64, f : F) -> SRResult<MatrixF64>
+    where F : Fn(Complex64) -> Complex64 {
+    check_square(a)?;
+    if a.is_symmetric() {
+        return funm_sym(a, |x| f(Complex64::new(x, 0.)).re);
+    }
+    let n = a.num_rows();
+    let (q, t) = schur_complex(a)?;
+    let mut scale = 0.;
+    for i in 0..n {
+        let d = t.get(i, i).unwrap().norm();
+        if d > scale {
+            scale = d;
+        }
+    }
+    let tolerance = 1e-8 * if scale == 0. { 1. } else { scale };
+    let mut fm : MatrixC64 = Matrix::zeros(n, n);
+    for i in 0..n {
+        fm.set(i, i, f(t.get(i, i).unwrap()));
+    }
+    // Fill f(T) one super-diagonal at a time
+    for p in 1..n {
+        for i in 0..(n - p) {
+            let j = i + p;
+            let tii = t.get(i, i).unwrap();
+            let tjj = t.get(j, j).unwrap();
+            let delta = tjj - tii;
+            if delta.norm() <= tolerance {
+                return Err(SRError::RepeatedEigenvalues);
+            }
+            let mut s = t.get(i, j).unwrap() * (fm.get(j, j).unwrap() - fm.get(i, i).unwrap());
+            for k in (i + 1)..j {
+                s = s + t.get(i, k).unwrap() * fm.get(k, j).unwrap()
+                    - fm.get(i, k).unwrap() * t.get(k, j).unwrap();
+            }
+            fm.set(i, j, s / delta);
+        }
+    }
+    let result = &(&q * &fm) * &conj_transpose_c64(&q);
+    Ok(real_part_c64(&result))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn max_diff(a : &MatrixF64, b : &MatrixF64) -> f64 {
+        (a - b).max_abs_scalar_value()
+    }
+
+    #[test]
+    fn test_expm_zero(){
+        let z : MatrixF64 = Matrix::zeros(3, 3);
+        let i : MatrixF64 = Matrix::identity(3, 3);
+        assert!(max_diff(&expm(&z).unwrap(), &i) < 1e-15);
+    }
+
+    #[test]
+    fn test_expm_rotation(){
+        // exp([[0, t], [-t, 0]]) = [[cos t, sin t], [-sin t, cos t]]
+        for &t in [0.01, 0.2, 1., 10., 100.].iter() {
+            let a = matrix_rw_f64(2, 2, &[0., t, -t, 0.]);
+            let e = expm(&a).unwrap();
+            let expected = matrix_rw_f64(2, 2, &[
+                t.cos(), t.sin(),
+                -t.sin(), t.cos()
+                ]);
+            assert!(max_diff(&e, &expected) < 1e-12 * (1. + t));
+        }
+    }
+
+    #[test]
+    fn test_expm_nilpotent(){
+        // exp(N) = I + N + N^2/2 for N^3 = 0
+        let a = matrix_rw_f64(3, 3, &[
+            0., 2., 3.,
+            0., 0., 4.,
+            0., 0., 0.
+            ]);
+        let expected = matrix_rw_f64(3, 3, &[
+            1., 2., 7.,
+            0., 1., 4.,
+            0., 0., 1.
+            ]);
+        assert!(max_diff(&expm(&a).unwrap(), &expected) < 1e-13);
+    }
+
+    #[test]
+    fn test_expm_triangular(){
+        // exp([[a, b], [0, c]]) = [[e^a, b (e^a - e^c) / (a - c)], [0, e^c]]
+        let (x, y, z) = (1.5, 3., -0.5);
+        let a = matrix_rw_f64(2, 2, &[x, y, 0., z]);
+        let expected = matrix_rw_f64(2, 2, &[
+            x.exp(), y * (x.exp() - z.exp()) / (x - z),
+            0., z.exp()
+            ]);
+        assert!(max_diff(&expm(&a).unwrap(), &expected) < 1e-13 * x.exp() * y);
+    }
+
+    #[test]
+    fn test_sqrtm(){
+        let a = matrix_rw_f64(2, 2, &[4., 0., 0., 9.]);
+        let expected = matrix_rw_f64(2, 2, &[2., 0., 0., 3.]);
+        assert!(max_diff(&sqrtm(&a).unwrap(), &expected) < 1e-14);
+        let a = matrix_rw_f64(2, 2, &[1., 4., 0., 9.]);
+        let expected = matrix_rw_f64(2, 2, &[1., 1., 0., 3.]);
+        assert!(max_diff(&sqrtm(&a).unwrap(), &expected) < 1e-13);
+        let a = matrix_rw_f64(3, 3, &[
+            4., 1., 0.,
+            1., 5., 2.,
+            0., 2., 6.
+            ]);
+        let s = sqrtm(&a).unwrap();
+        assert!(max_diff(&(&s * &s), &a) < 1e-12);
+    }
+
+    #[test]
+    fn test_sqrtm_singular(){
+        let a = matrix_rw_f64(2, 2, &[1., 1., 1., 1.]);
+        assert!(sqrtm(&a).is_err());
+    }
+
+    #[test]
+    fn test_logm_rotation(){
+        // log([[cos t, -sin t], [sin t, cos t]]) = [[0, -t], [t, 0]]
+        let t = 1.;
+        let a = matrix_rw_f64(2, 2, &[
+            f64::cos(t), -f64::sin(t),
+            f64::sin(t), f64::cos(t)
+            ]);
+        let expected = matrix_rw_f64(2, 2, &[0., -t, t, 0.]);
+        assert!(max_diff(&logm(&a).unwrap(), &expected) < 1e-12);
+    }
+
+    #[test]
+    fn test_logm_expm(){
+        let x = matrix_rw_f64(3, 3, &[
+            0.1, 0.5, -0.2,
+            -0.3, 0.2, 0.4,
+            0.0, 0.7, -0.5
+            ]);
+        let e = expm(&x).unwrap();
+        assert!(max_diff(&logm(&e).unwrap(), &x) < 1e-12);
+        let a = matrix_rw_f64(2, 2, &[f64::consts::E, 0., 0., 1.]);
+        let expected = matrix_rw_f64(2, 2, &[1., 0., 0., 0.]);
+        assert!(max_diff(&logm(&a).unwrap(), &expected) < 1e-13);
+    }
+
+    #[test]
+    fn test_funm_sym(){
+        let a = matrix_rw_f64(2, 2, &[2., 1., 1., 2.]);
+        let s = funm_sym(&a, |x| x.sqrt()).unwrap();
+        assert!(max_diff(&(&s * &s), &a) < 1e-13);
+        let e = funm(&a, |z| z.exp()).unwrap();
+        assert!(max_diff(&e, &expm(&a).unwrap()) < 1e-12);
+    }
+
+    #[test]
+    fn test_funm_general(){
+        let a = matrix_rw_f64(3, 3, &[
+            1., 2., 0.,
+            -1., 0.5, 1.,
+            0.3, 0., -2.
+            ]);
+        let e = funm(&a, |z| z.exp()).unwrap();
+        assert!(max_diff(&e, &expm(&a).unwrap()) < 1e-11);
+        let c = funm(&a, |z| z.cos()).unwrap();
+        let s = funm(&a, |z| z.sin()).unwrap();
+        let i : MatrixF64 = Matrix::identity(3, 3);
+        // cos^2(A) + sin^2(A) = I
+        assert!(max_diff(&(&(&c * &c) + &(&s * &s)), &i) < 1e-11);
+    }
+
+    #[test]
+    fn test_funm_repeated_eigenvalues(){
+        let a = matrix_rw_f64(2, 2, &[1., 1., 0., 1.]);
+        assert!(funm(&a, |z| z.exp()).is_err());
+    }
+}
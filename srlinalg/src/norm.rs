@@ -0,0 +1,71 @@
+#![doc="Matrix norms
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Returns the 1-norm (maximum absolute column sum) of a matrix
+pub fn norm_1(a : &MatrixF64) -> f64 {
+    let mut result = 0.;
+    for c in 0..a.num_cols(){
+        let mut sum = 0.;
+        for r in 0..a.num_rows(){
+            sum += a.get(r, c).unwrap().abs();
+        }
+        if sum > result {
+            result = sum;
+        }
+    }
+    result
+}
+
+/// Returns the infinity norm (maximum absolute row sum) of a matrix
+pub fn norm_inf(a : &MatrixF64) -> f64 {
+    let mut result = 0.;
+    for r in 0..a.num_rows(){
+        let mut sum = 0.;
+        for c in 0..a.num_cols(){
+            sum += a.get(r, c).unwrap().abs();
+        }
+        if sum > result {
+            result = sum;
+        }
+    }
+    result
+}
+
+/// Returns the Frobenius norm of a matrix
+pub fn norm_fro(a : &MatrixF64) -> f64 {
+    let mut sum = 0.;
+    for v in a.cell_iter(){
+        sum += v * v;
+    }
+    sum.sqrt()
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_norms(){
+        let a = matrix_rw_f64(2, 3, &[
+            1., -2., 3.,
+            -4., 5., -6.
+            ]);
+        assert_eq!(norm_1(&a), 9.);
+        assert_eq!(norm_inf(&a), 15.);
+        assert!((norm_fro(&a) - 91f64.sqrt()).abs() < 1e-12);
+    }
+}
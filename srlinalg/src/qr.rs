@@ -0,0 +1,305 @@
+#![doc="QR decomposition
+
+A = Q R where Q is orthogonal and R is upper triangular.
+With column pivoting, A P = Q R where the diagonal entries
+of R are non-increasing in magnitude which reveals the
+numerical rank of A.
+
+Q is stored implicitly as a product of Householder
+reflections H_k = I - beta_k v_k v_k'.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Householder QR decomposition of an m x n matrix
+pub struct QRDecomposition {
+    /// Householder vectors (strictly below the diagonal,
+    /// with implicit unit leading entry) and R (on and above
+    /// the diagonal)
+    qr : MatrixF64,
+    /// Scalars of the Householder reflections
+    betas : Vec<f64>,
+    /// Column k of R corresponds to column perm[k] of A
+    perm : Vec<usize>
+}
+
+
+impl QRDecomposition {
+
+    /// Computes the QR decomposition A = Q R
+    pub fn new(a : &MatrixF64) -> QRDecomposition {
+        QRDecomposition::decompose(a, false)
+    }
+
+    /// Computes the QR decomposition with column pivoting A P = Q R
+    pub fn new_pivoted(a : &MatrixF64) -> QRDecomposition {
+        QRDecomposition::decompose(a, true)
+    }
+
+    fn decompose(a : &MatrixF64, pivoting : bool) -> QRDecomposition {
+        let m = a.num_rows();
+        let n = a.num_cols();
+        let k = if m < n { m } else { n };
+        let mut qr = a.clone();
+        let mut betas = Vec::with_capacity(k);
+        let mut perm : Vec<usize> = (0..n).collect();
+        for j in 0..k {
+            if pivoting {
+                // Bring the column with largest remaining norm forward
+                let mut best = j;
+                let mut best_norm = -1.;
+                for c in j..n {
+                    let mut s = 0.;
+                    for r in j..m {
+                        let x = qr.get(r, c).unwrap();
+                        s += x * x;
+                    }
+                    if s > best_norm {
+                        best_norm = s;
+                        best = c;
+                    }
+                }
+                if best != j {
+                    for r in 0..m {
+                        let x = qr.get(r, j).unwrap();
+                        let y = qr.get(r, best).unwrap();
+                        qr.set(r, j, y);
+                        qr.set(r, best, x);
+                    }
+                    perm.swap(j, best);
+                }
+            }
+            let mut norm = 0.;
+            for r in j..m {
+                let x = qr.get(r, j).unwrap();
+                norm += x * x;
+            }
+            let norm = norm.sqrt();
+            if norm == 0. {
+                betas.push(0.);
+                continue;
+            }
+            let x0 = qr.get(j, j).unwrap();
+            let alpha = if x0 >= 0. { -norm } else { norm };
+            let v0 = x0 - alpha;
+            // v = (x - alpha e_1) / v0 has unit leading entry
+            let mut vtv = 1.;
+            for r in (j + 1)..m {
+                let x = qr.get(r, j).unwrap() / v0;
+                qr.set(r, j, x);
+                vtv += x * x;
+            }
+            let beta = 2. / vtv;
+            qr.set(j, j, alpha);
+            // Apply the reflection to the remaining columns
+            for c in (j + 1)..n {
+                let mut s = qr.get(j, c).unwrap();
+                for r in (j + 1)..m {
+                    s += qr.get(r, j).unwrap() * qr.get(r, c).unwrap();
+                }
+                s *= beta;
+                let x = qr.get(j, c).unwrap() - s;
+                qr.set(j, c, x);
+                for r in (j + 1)..m {
+                    let x = qr.get(r, c).unwrap() - s * qr.get(r, j).unwrap();
+                    qr.set(r, c, x);
+                }
+            }
+            betas.push(beta);
+        }
+        QRDecomposition {qr : qr, betas : betas, perm : perm}
+    }
+
+    /// Applies the k-th reflection to the columns of b
+    fn reflect(&self, j : usize, b : &mut MatrixF64) {
+        let m = self.qr.num_rows();
+        let beta = self.betas[j];
+        if beta == 0. {
+            return;
+        }
+        for c in 0..b.num_cols() {
+            let mut s = b.get(j, c).unwrap();
+            for r in (j + 1)..m {
+                s += self.qr.get(r, j).unwrap() * b.get(r, c).unwrap();
+            }
+            s *= beta;
+            let x = b.get(j, c).unwrap() - s;
+            b.set(j, c, x);
+            for r in (j + 1)..m {
+                let x = b.get(r, c).unwrap() - s * self.qr.get(r, j).unwrap();
+                b.set(r, c, x);
+            }
+        }
+    }
+
+    /// Computes Q' B
+    pub fn apply_qt(&self, b : &MatrixF64) -> SRResult<MatrixF64> {
+        if b.num_rows() != self.qr.num_rows() {
+            return Err(SRError::LRDimensionMismatch);
+        }
+        let mut b = b.clone();
+        for j in 0..self.betas.len() {
+            self.reflect(j, &mut b);
+        }
+        Ok(b)
+    }
+
+    /// Computes Q B
+    pub fn apply_q(&self, b : &MatrixF64) -> SRResult<MatrixF64> {
+        if b.num_rows() != self.qr.num_rows() {
+            return Err(SRError::LRDimensionMismatch);
+        }
+        let mut b = b.clone();
+        for j in (0..self.betas.len()).rev() {
+            self.reflect(j, &mut b);
+        }
+        Ok(b)
+    }
+
+    /// Returns the full m x m orthogonal matrix Q
+    pub fn q(&self) -> MatrixF64 {
+        let m = self.qr.num_rows();
+        self.apply_q(&Matrix::identity(m, m)).unwrap()
+    }
+
+    /// Returns the first min(m, n) columns of Q
+    pub fn thin_q(&self) -> MatrixF64 {
+        let m = self.qr.num_rows();
+        let k = self.betas.len();
+        self.apply_q(&Matrix::identity(m, k)).unwrap()
+    }
+
+    /// Returns the min(m, n) x n upper triangular factor R
+    pub fn r(&self) -> MatrixF64 {
+        let n = self.qr.num_cols();
+        let k = self.betas.len();
+        let mut r : MatrixF64 = Matrix::zeros(k, n);
+        for c in 0..n {
+            for row in 0..(if c < k { c + 1 } else { k }) {
+                r.set(row, c, self.qr.get(row, c).unwrap());
+            }
+        }
+        r
+    }
+
+    /// Returns the column permutation (column k of R
+    /// corresponds to column perm[k] of A)
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Returns the numerical rank: the number of diagonal
+    /// entries of R larger than the tolerance in magnitude.
+    ///
+    /// The default tolerance is max(m, n) * eps * |r_00|.
+    /// This is meaningful only for pivoted decompositions.
+    pub fn rank(&self, tolerance : Option<f64>) -> usize {
+        let k = self.betas.len();
+        if k == 0 {
+            return 0;
+        }
+        let m = self.qr.num_rows();
+        let n = self.qr.num_cols();
+        let tolerance = match tolerance {
+            Some(t) => t,
+            None => (if m > n { m } else { n }) as f64 * f64::EPSILON
+                * self.qr.get(0, 0).unwrap().abs()
+        };
+        (0..k).filter(|&i| self.qr.get(i, i).unwrap().abs() > tolerance).count()
+    }
+
+    /// Solves the least squares problem min ||A X - B||.
+    ///
+    /// For rank deficient A (detected by ``rank``), a basic
+    /// solution is returned in which the entries corresponding
+    /// to dependent columns are zero.
+    pub fn solve_least_squares(&self, b : &MatrixF64) -> SRResult<MatrixF64> {
+        let n = self.qr.num_cols();
+        let qtb = self.apply_qt(b)?;
+        let rank = self.rank(None);
+        let p = b.num_cols();
+        let mut x : MatrixF64 = Matrix::zeros(n, p);
+        for c in 0..p {
+            // Back substitution with the leading rank x rank block of R
+            let mut z = vec![0.; rank];
+            for i in (0..rank).rev() {
+                let mut v = qtb.get(i, c).unwrap();
+                for j in (i + 1)..rank {
+                    v -= self.qr.get(i, j).unwrap() * z[j];
+                }
+                z[i] = v / self.qr.get(i, i).unwrap();
+            }
+            for i in 0..rank {
+                x.set(self.perm[i], c, z[i]);
+            }
+        }
+        Ok(x)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_qr(){
+        let a = matrix_rw_f64(4, 3, &[
+            12., -51., 4.,
+            6., 167., -68.,
+            -4., 24., -41.,
+            1., 1., 1.
+            ]);
+        let qr = QRDecomposition::new(&a);
+        let q = qr.q();
+        let i : MatrixF64 = Matrix::identity(4, 4);
+        assert!((&(&q.transpose() * &q) - &i).max_abs_scalar_value() < 1e-13);
+        let thin = &qr.thin_q() * &qr.r();
+        assert!((&thin - &a).max_abs_scalar_value() < 1e-12);
+        let r = qr.r();
+        assert_eq!(r.get(1, 0).unwrap(), 0.);
+        assert_eq!(qr.rank(None), 3);
+    }
+
+    #[test]
+    fn test_qr_pivoted(){
+        // Third column is the sum of the first two
+        let a = matrix_rw_f64(4, 3, &[
+            1., 2., 3.,
+            4., 5., 9.,
+            7., 8., 15.,
+            1., 0., 1.
+            ]);
+        let qr = QRDecomposition::new_pivoted(&a);
+        assert_eq!(qr.rank(None), 2);
+        let ap = a.permuted_cols(&Matrix::from_iter_cw(3, 1,
+            qr.permutation().iter().map(|&p| p as u16)));
+        assert!((&(&qr.thin_q() * &qr.r()) - &ap).max_abs_scalar_value() < 1e-12);
+    }
+
+    #[test]
+    fn test_qr_least_squares(){
+        // Fit a line through (0, 1), (1, 3), (2, 5), (3, 7)
+        let a = matrix_rw_f64(4, 2, &[
+            1., 0.,
+            1., 1.,
+            1., 2.,
+            1., 3.
+            ]);
+        let b = vector_f64(&[1., 3., 5., 7.]);
+        let x = QRDecomposition::new(&a).solve_least_squares(&b).unwrap();
+        assert!((&x - &vector_f64(&[1., 2.])).max_abs_scalar_value() < 1e-12);
+    }
+}
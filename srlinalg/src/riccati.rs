@@ -0,0 +1,174 @@
+#![doc="Algebraic Riccati equations
+
+* ``solve_discrete_are``: the discrete algebraic Riccati
+  equation (DARE) arising in discrete time LQR control
+
+References:
+
+* A. J. Laub, A Schur method for solving algebraic Riccati
+  equations, IEEE Trans. Automat. Control 24(6), 1979.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use lu::LUDecomposition;
+use cholesky::CholeskyDecomposition;
+use factorization::Factorization;
+use schur::{schur, reorder_schur};
+
+
+#[doc="Solves the discrete algebraic Riccati equation
+
+X = A' X A - A' X B (R + B' X B)^-1 B' X A + Q
+
+for the stabilizing solution X.
+
+A is n x n, B is n x m, Q is n x n symmetric and
+R is m x m symmetric positive definite.
+
+# Remarks
+
+The symplectic matrix
+
+Z = [A + G A^-T Q, -G A^-T; -A^-T Q, A^-T]
+
+with G = B R^-1 B' is reduced to real Schur form and
+the eigen values inside the unit circle are moved to
+the leading positions. If [U1; U2] spans the corresponding
+invariant subspace then X = U2 U1^-1.
+
+Z involves A^-1, hence A must be non-singular and
+``IsSingular`` is returned otherwise. Problems with a
+singular A (e.g. systems with input delays) require the
+generalized eigen value formulation of the symplectic
+pencil, which is not implemented. ``IsNotPositiveDefinite``
+is returned if R is not positive definite and ``NoSolution`` if Z has
+eigen values on the unit circle (no stabilizing solution
+exists).
+"]
+pub fn solve_discrete_are(a : &MatrixF64, b : &MatrixF64,
+    q : &MatrixF64, r : &MatrixF64) -> SRResult<MatrixF64> {
+    if !a.is_square() || !q.is_square() || !r.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    let n = a.num_rows();
+    let m = b.num_cols();
+    if b.num_rows() != n || q.num_rows() != n || r.num_rows() != m {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let mut a_lu = LUDecomposition::new(a.clone());
+    a_lu.decompose_ero();
+    // Fails with IsSingular for a singular A
+    let a_inv_t = a_lu.inverse()?.transpose();
+    let r_inv = CholeskyDecomposition::new(r)?.inverse()?;
+    let g = &(b * &r_inv) * &b.transpose();
+    let ga = &g * &a_inv_t;
+    let aq = &a_inv_t * q;
+    let z11 = a + &(&ga * q);
+    let mut z : MatrixF64 = Matrix::zeros(2 * n, 2 * n);
+    for c in 0..n {
+        for row in 0..n {
+            z.set(row, c, z11.get(row, c).unwrap());
+            z.set(row, n + c, -ga.get(row, c).unwrap());
+            z.set(n + row, c, -aq.get(row, c).unwrap());
+            z.set(n + row, n + c, a_inv_t.get(row, c).unwrap());
+        }
+    }
+    let (u, t) = schur(&z)?;
+    let (u, _, k) = reorder_schur(&u, &t, |w| w.norm() < 1.)?;
+    if k != n {
+        return Err(SRError::NoSolution);
+    }
+    // X U1 = U2, i.e. U1' X' = U2'
+    let u1 = u.view(0, 0, n, n).to_matrix();
+    let u2 = u.view(n, 0, n, n).to_matrix();
+    let mut lu = LUDecomposition::new(u1);
+    lu.decompose_ero();
+    let x = match lu.solve_transpose(&u2.transpose()) {
+        Ok(x) => x.transpose(),
+        Err(SRError::IsSingular) => return Err(SRError::NoSolution),
+        Err(e) => return Err(e)
+    };
+    Ok((&x + &x.transpose()).copy_mul_scalar(0.5))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use schur::eigenvalues;
+    use inverse::inverse_ero;
+
+    fn dare_residual(a : &MatrixF64, b : &MatrixF64,
+        q : &MatrixF64, r : &MatrixF64, x : &MatrixF64) -> f64 {
+        let at = a.transpose();
+        let bt = b.transpose();
+        let s = r + &(&(&bt * x) * b);
+        let k = inverse_ero(&mut s.clone()).unwrap();
+        let rhs = &(&(&at * x) * a) - &(&(&(&(&at * x) * b) * &k) * &(&(&bt * x) * a));
+        (&(&rhs + q) - x).max_abs_scalar_value()
+    }
+
+    #[test]
+    fn test_dare_scalar(){
+        // With a = b = q = r = 1, x^2 - x - 1 = 0
+        let one = matrix_rw_f64(1, 1, &[1.]);
+        let x = solve_discrete_are(&one, &one, &one, &one).unwrap();
+        let golden = (1. + 5f64.sqrt()) / 2.;
+        assert!((x[0] - golden).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dare_singular(){
+        let a = matrix_rw_f64(2, 2, &[1., 0., 0., 0.]);
+        let i : MatrixF64 = Matrix::identity(2, 2);
+        match solve_discrete_are(&a, &i, &i, &i) {
+            Err(SRError::IsSingular) => {},
+            r => panic!("{:?}", r)
+        }
+        match solve_discrete_are(&i, &i, &i, &a) {
+            Err(SRError::IsNotPositiveDefinite) => {},
+            r => panic!("{:?}", r)
+        }
+        // R is invertible but indefinite
+        let r = matrix_rw_f64(2, 2, &[1., 0., 0., -1.]);
+        match solve_discrete_are(&i, &i, &i, &r) {
+            Err(SRError::IsNotPositiveDefinite) => {},
+            r => panic!("{:?}", r)
+        }
+    }
+
+    #[test]
+    fn test_dare(){
+        let a = matrix_rw_f64(3, 3, &[
+            1.1, 0.2, 0.,
+            0., 0.9, 0.3,
+            0.1, 0., 1.2
+            ]);
+        let b = matrix_rw_f64(3, 2, &[
+            1., 0.,
+            0., 0.5,
+            0.2, 1.
+            ]);
+        let q : MatrixF64 = Matrix::identity(3, 3);
+        let r = matrix_rw_f64(2, 2, &[2., 0.1, 0.1, 1.]);
+        let x = solve_discrete_are(&a, &b, &q, &r).unwrap();
+        assert!(dare_residual(&a, &b, &q, &r, &x) < 1e-10);
+        // The closed loop A - B K is stable
+        let bt = b.transpose();
+        let k = &inverse_ero(&mut (&r + &(&(&bt * &x) * &b))).unwrap() * &(&(&bt * &x) * &a);
+        let w = eigenvalues(&(&a - &(&b * &k))).unwrap();
+        for i in 0..3 {
+            assert!(w[i].norm() < 1.);
+        }
+    }
+}
@@ -0,0 +1,1008 @@
+#![doc="Hessenberg and Schur decompositions
+
+The real Schur decomposition of a real square matrix A is
+A = Q T Q' where Q is orthogonal and T is quasi upper
+triangular.  T is block upper triangular with 1x1 blocks
+for real eigen values and 2x2 blocks for pairs of complex
+conjugate eigen values.
+
+The complex Schur decomposition of a square matrix A is
+A = Q T Q^H where Q is unitary and T is upper triangular
+with the eigen values of A on its diagonal.
+
+Both forms can be reordered so that a selected set of
+eigen values appears in the leading diagonal positions.
+The leading columns of Q then span the corresponding
+invariant subspace of A.
+"]
+
+
+// std imports
+use std::f64;
+
+// external imports
+use num::complex::Complex64;
+
+// local imports
+use srmatrix::api::*;
+use lu::LUDecomposition;
+use factorization::Factorization;
+
+
+/// Tells whether a sub-diagonal entry is negligible
+/// compared to its neighbouring diagonal entries
+fn is_negligible_c64(sub : Complex64, d0 : Complex64, d1 : Complex64, scale : f64) -> bool {
+    let s = d0.norm() + d1.norm();
+    let s = if s == 0. { scale } else { s };
+    sub.norm() <= f64::EPSILON * s
+}
+
+
+#[doc="Reduces a complex matrix to upper Hessenberg form
+A = Q H Q^H using Householder reflections.
+
+Returns (Q, H).
+"]
+pub fn hessenberg_c64(a : &MatrixC64) -> SRResult<(MatrixC64, MatrixC64)> {
+    if !a.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    let n = a.num_rows();
+    let zero = Complex64::new(0., 0.);
+    let mut h = a.clone();
+    let mut q : MatrixC64 = Matrix::identity(n, n);
+    for k in 0..n.saturating_sub(2) {
+        // Householder vector for column k below the sub-diagonal
+        let mut norm = 0.;
+        for r in (k + 1)..n {
+            norm += h.get(r, k).unwrap().norm_sqr();
+        }
+        let norm = norm.sqrt();
+        if norm == 0. {
+            continue;
+        }
+        let x0 = h.get(k + 1, k).unwrap();
+        let phase = if x0.norm() == 0. { Complex64::new(1., 0.) } else { x0 / x0.norm() };
+        let alpha = -phase * norm;
+        let mut v : MatrixC64 = Matrix::zeros(n, 1);
+        for r in (k + 1)..n {
+            v.set(r, 0, h.get(r, k).unwrap());
+        }
+        v.set(k + 1, 0, x0 - alpha);
+        let mut v_norm = 0.;
+        for r in (k + 1)..n {
+            v_norm += v[r].norm_sqr();
+        }
+        let v_norm = v_norm.sqrt();
+        for r in (k + 1)..n {
+            let vr = v[r];
+            v.set(r, 0, vr / v_norm);
+        }
+        // H = (I - 2 v v^H) H
+        for c in 0..n {
+            let mut s = zero;
+            for r in (k + 1)..n {
+                s += v[r].conj() * h.get(r, c).unwrap();
+            }
+            for r in (k + 1)..n {
+                let x = h.get(r, c).unwrap() - v[r] * s * 2.;
+                h.set(r, c, x);
+            }
+        }
+        // H = H (I - 2 v v^H), Q = Q (I - 2 v v^H)
+        for m in [&mut h, &mut q].iter_mut() {
+            for r in 0..n {
+                let mut s = zero;
+                for c in (k + 1)..n {
+                    s += m.get(r, c).unwrap() * v[c];
+                }
+                for c in (k + 1)..n {
+                    let x = m.get(r, c).unwrap() - s * v[c].conj() * 2.;
+                    m.set(r, c, x);
+                }
+            }
+        }
+        for r in (k + 2)..n {
+            h.set(r, k, zero);
+        }
+    }
+    Ok((q, h))
+}
+
+
+#[doc="Computes the complex Schur decomposition A = Q T Q^H.
+
+Returns (Q, T) where Q is unitary and T is upper triangular.
+
+# Remarks
+
+The matrix is first reduced to Hessenberg form. Then
+the shifted QR algorithm with Wilkinson shifts is applied.
+"]
+pub fn schur_c64(a : &MatrixC64) -> SRResult<(MatrixC64, MatrixC64)> {
+    let (mut q, mut t) = hessenberg_c64(a)?;
+    let n = t.num_rows();
+    let zero = Complex64::new(0., 0.);
+    let scale = {
+        let mut s = 0.;
+        for v in t.cell_iter() {
+            if v.norm() > s {
+                s = v.norm();
+            }
+        }
+        s
+    };
+    if n < 2 || scale == 0. {
+        return Ok((q, t));
+    }
+    let mut hi = n - 1;
+    let mut iterations = 0;
+    let mut total_iterations = 0;
+    while hi > 0 {
+        // Look for a negligible sub-diagonal entry
+        let mut lo = hi;
+        while lo > 0 {
+            if is_negligible_c64(t.get(lo, lo - 1).unwrap(),
+                t.get(lo - 1, lo - 1).unwrap(), t.get(lo, lo).unwrap(), scale) {
+                t.set(lo, lo - 1, zero);
+                break;
+            }
+            lo -= 1;
+        }
+        if lo == hi {
+            // The eigen value at hi has converged
+            hi -= 1;
+            iterations = 0;
+            continue;
+        }
+        iterations += 1;
+        total_iterations += 1;
+        if total_iterations > 30 * n {
+            return Err(SRError::NotConverged);
+        }
+        // Wilkinson shift from the trailing 2x2 block
+        let a11 = t.get(hi - 1, hi - 1).unwrap();
+        let a12 = t.get(hi - 1, hi).unwrap();
+        let a21 = t.get(hi, hi - 1).unwrap();
+        let a22 = t.get(hi, hi).unwrap();
+        let mu = if iterations % 11 == 10 {
+            // Exceptional shift to break cycles
+            a22 + Complex64::new(a21.norm(), 0.)
+        }
+        else {
+            // Pick the eigen value of the 2x2 block closer to a22
+            let half = (a11 - a22) * 0.5;
+            let disc = (half * half + a12 * a21).sqrt();
+            let m1 = a22 + half + disc;
+            let m2 = a22 + half - disc;
+            if (m1 - a22).norm() <= (m2 - a22).norm() { m1 } else { m2 }
+        };
+        // Shifted QR step on the active block [lo, hi]
+        for k in lo..(hi + 1) {
+            let x = t.get(k, k).unwrap() - mu;
+            t.set(k, k, x);
+        }
+        let mut rotations : Vec<(f64, Complex64)> = Vec::with_capacity(hi - lo);
+        for k in lo..hi {
+            let x = t.get(k, k).unwrap();
+            let y = t.get(k + 1, k).unwrap();
+            let r = (x.norm_sqr() + y.norm_sqr()).sqrt();
+            let (c, s) = if r == 0. {
+                (1., zero)
+            } else if x.norm() == 0. {
+                (0., Complex64::new(1., 0.))
+            } else {
+                (x.norm() / r, (x / x.norm()) * y.conj() / r)
+            };
+            // Rotate rows k and k+1
+            for j in k..n {
+                let u = t.get(k, j).unwrap();
+                let w = t.get(k + 1, j).unwrap();
+                t.set(k, j, u * c + s * w);
+                t.set(k + 1, j, -s.conj() * u + w * c);
+            }
+            rotations.push((c, s));
+        }
+        for k in lo..hi {
+            let (c, s) = rotations[k - lo];
+            // Rotate columns k and k+1 of T and Q
+            let last = if k + 2 <= hi { k + 2 } else { hi };
+            for i in 0..(last + 1) {
+                let u = t.get(i, k).unwrap();
+                let w = t.get(i, k + 1).unwrap();
+                t.set(i, k, u * c + w * s.conj());
+                t.set(i, k + 1, -u * s + w * c);
+            }
+            for i in 0..n {
+                let u = q.get(i, k).unwrap();
+                let w = q.get(i, k + 1).unwrap();
+                q.set(i, k, u * c + w * s.conj());
+                q.set(i, k + 1, -u * s + w * c);
+            }
+        }
+        for k in lo..(hi + 1) {
+            let x = t.get(k, k).unwrap() + mu;
+            t.set(k, k, x);
+        }
+    }
+    // Clean up the strictly lower triangular part
+    for c in 0..n {
+        for r in (c + 1)..n {
+            t.set(r, c, zero);
+        }
+    }
+    Ok((q, t))
+}
+
+
+#[doc="Computes the complex Schur decomposition of a real matrix.
+"]
+pub fn schur_complex(a : &MatrixF64) -> SRResult<(MatrixC64, MatrixC64)> {
+    schur_c64(&to_complex_f64(a))
+}
+
+
+/// Returns the eigen values of a real square matrix
+pub fn eigenvalues(a : &MatrixF64) -> SRResult<MatrixC64> {
+    let (_, t) = schur_complex(a)?;
+    Ok(t.diagonal_vector())
+}
+
+
+#[doc="Reduces a real matrix to upper Hessenberg form
+A = Q H Q' using Householder reflections.
+
+Returns (Q, H).
+"]
+pub fn hessenberg(a : &MatrixF64) -> SRResult<(MatrixF64, MatrixF64)> {
+    if !a.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    let n = a.num_rows();
+    let mut h = a.clone();
+    let mut q : MatrixF64 = Matrix::identity(n, n);
+    for k in 0..n.saturating_sub(2) {
+        let mut v : MatrixF64 = Matrix::zeros(n, 1);
+        for r in (k + 1)..n {
+            v.set(r, 0, h.get(r, k).unwrap());
+        }
+        if !householder_vector(&mut v, k + 1) {
+            continue;
+        }
+        // H = P H P, Q = Q P where P = I - 2 v v'
+        reflect_rows(&mut h, &v, k + 1, 0);
+        reflect_cols(&mut h, &v, k + 1);
+        reflect_cols(&mut q, &v, k + 1);
+        for r in (k + 2)..n {
+            h.set(r, k, 0.);
+        }
+    }
+    Ok((q, h))
+}
+
+
+/// Turns v[start..] into a unit Householder vector which maps
+/// the original v[start..] to a multiple of the unit vector
+/// e_start.  Returns false if v[start..] is zero.
+fn householder_vector(v : &mut MatrixF64, start : usize) -> bool {
+    let n = v.num_rows();
+    let mut norm = 0.;
+    for r in start..n {
+        norm += v[r] * v[r];
+    }
+    let norm = norm.sqrt();
+    if norm == 0. {
+        return false;
+    }
+    let x0 = v[start];
+    let alpha = if x0 >= 0. { -norm } else { norm };
+    v.set(start, 0, x0 - alpha);
+    let mut v_norm = 0.;
+    for r in start..n {
+        v_norm += v[r] * v[r];
+    }
+    let v_norm = v_norm.sqrt();
+    for r in start..n {
+        let vr = v[r];
+        v.set(r, 0, vr / v_norm);
+    }
+    true
+}
+
+/// Computes M = (I - 2 v v') M for columns from first_col onwards
+fn reflect_rows(m : &mut MatrixF64, v : &MatrixF64, start : usize, first_col : usize) {
+    let n = v.num_rows();
+    for c in first_col..m.num_cols() {
+        let mut s = 0.;
+        for r in start..n {
+            s += v[r] * m.get(r, c).unwrap();
+        }
+        for r in start..n {
+            let x = m.get(r, c).unwrap() - 2. * s * v[r];
+            m.set(r, c, x);
+        }
+    }
+}
+
+/// Computes M = M (I - 2 v v')
+fn reflect_cols(m : &mut MatrixF64, v : &MatrixF64, start : usize) {
+    let n = v.num_rows();
+    for r in 0..m.num_rows() {
+        let mut s = 0.;
+        for c in start..n {
+            s += m.get(r, c).unwrap() * v[c];
+        }
+        for c in start..n {
+            let x = m.get(r, c).unwrap() - 2. * s * v[c];
+            m.set(r, c, x);
+        }
+    }
+}
+
+
+#[doc="Computes the real Schur decomposition A = Q T Q'.
+
+Returns (Q, T) where Q is orthogonal and T is quasi upper
+triangular. Complex conjugate pairs of eigen values appear
+as 2x2 blocks on the diagonal of T.
+
+# Remarks
+
+The matrix is first reduced to Hessenberg form. Then
+the Francis double shift QR algorithm is applied
+(following the EISPACK routine hqr2).
+"]
+pub fn schur(a : &MatrixF64) -> SRResult<(MatrixF64, MatrixF64)> {
+    let (mut v, mut h) = hessenberg(a)?;
+    let nn = h.num_rows();
+    if nn < 2 {
+        return Ok((v, h));
+    }
+    let eps = f64::EPSILON;
+    let mut norm = 0.;
+    for i in 0..nn {
+        for j in (if i > 0 { i - 1 } else { 0 })..nn {
+            norm += h.get(i, j).unwrap().abs();
+        }
+    }
+    let mut n = nn as isize - 1;
+    let mut exshift = 0.;
+    let mut iter = 0;
+    let mut total_iter = 0;
+    let (mut p, mut q, mut r, mut s, mut z);
+    let (mut x, mut y, mut w);
+    macro_rules! hh {
+        ($i:expr, $j:expr) => (h.get($i as usize, $j as usize).unwrap())
+    }
+    macro_rules! hs {
+        ($i:expr, $j:expr, $v:expr) => ({let value = $v; h.set($i as usize, $j as usize, value)})
+    }
+    while n >= 0 {
+        // Look for a single small sub-diagonal element
+        let mut l = n;
+        while l > 0 {
+            s = hh!(l - 1, l - 1).abs() + hh!(l, l).abs();
+            if s == 0. {
+                s = norm;
+            }
+            if hh!(l, l - 1).abs() < eps * s {
+                hs!(l, l - 1, 0.);
+                break;
+            }
+            l -= 1;
+        }
+        if l == n {
+            // One root found
+            hs!(n, n, hh!(n, n) + exshift);
+            n -= 1;
+            iter = 0;
+        }
+        else if l == n - 1 {
+            // Two roots found
+            w = hh!(n, n - 1) * hh!(n - 1, n);
+            p = (hh!(n - 1, n - 1) - hh!(n, n)) / 2.;
+            q = p * p + w;
+            z = q.abs().sqrt();
+            hs!(n, n, hh!(n, n) + exshift);
+            hs!(n - 1, n - 1, hh!(n - 1, n - 1) + exshift);
+            if q >= 0. {
+                // Real pair: triangularize the 2x2 block
+                z = if p >= 0. { p + z } else { p - z };
+                x = hh!(n, n - 1);
+                s = x.abs() + z.abs();
+                p = x / s;
+                q = z / s;
+                r = (p * p + q * q).sqrt();
+                p /= r;
+                q /= r;
+                for j in ((n - 1) as usize)..nn {
+                    z = hh!(n - 1, j);
+                    hs!(n - 1, j, q * z + p * hh!(n, j));
+                    hs!(n, j, q * hh!(n, j) - p * z);
+                }
+                for i in 0..((n + 1) as usize) {
+                    z = hh!(i, n - 1);
+                    hs!(i, n - 1, q * z + p * hh!(i, n));
+                    hs!(i, n, q * hh!(i, n) - p * z);
+                }
+                for i in 0..nn {
+                    let n = n as usize;
+                    z = v.get(i, n - 1).unwrap();
+                    let vin = v.get(i, n).unwrap();
+                    v.set(i, n - 1, q * z + p * vin);
+                    v.set(i, n, q * vin - p * z);
+                }
+                hs!(n, n - 1, 0.);
+            }
+            n -= 2;
+            iter = 0;
+        }
+        else {
+            total_iter += 1;
+            if total_iter > 30 * nn {
+                return Err(SRError::NotConverged);
+            }
+            // Form shift
+            x = hh!(n, n);
+            y = hh!(n - 1, n - 1);
+            w = hh!(n, n - 1) * hh!(n - 1, n);
+            if iter == 10 {
+                // Wilkinson's original ad hoc shift
+                exshift += x;
+                for i in 0..((n + 1) as usize) {
+                    hs!(i, i, hh!(i, i) - x);
+                }
+                s = hh!(n, n - 1).abs() + hh!(n - 1, n - 2).abs();
+                x = 0.75 * s;
+                y = x;
+                w = -0.4375 * s * s;
+            }
+            if iter == 30 {
+                // MATLAB's ad hoc shift
+                s = (y - x) / 2.;
+                s = s * s + w;
+                if s > 0. {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / 2. + s);
+                    for i in 0..((n + 1) as usize) {
+                        hs!(i, i, hh!(i, i) - s);
+                    }
+                    exshift += s;
+                    x = 0.964;
+                    y = x;
+                    w = x;
+                }
+            }
+            iter += 1;
+            // Look for two consecutive small sub-diagonal elements
+            let mut m = n - 2;
+            loop {
+                z = hh!(m, m);
+                r = x - z;
+                s = y - z;
+                p = (r * s - w) / hh!(m + 1, m) + hh!(m, m + 1);
+                q = hh!(m + 1, m + 1) - z - r - s;
+                r = hh!(m + 2, m + 1);
+                s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                if hh!(m, m - 1).abs() * (q.abs() + r.abs()) <
+                    eps * (p.abs() * (hh!(m - 1, m - 1).abs() + z.abs() + hh!(m + 1, m + 1).abs())) {
+                    break;
+                }
+                m -= 1;
+            }
+            for i in (m + 2)..(n + 1) {
+                hs!(i, i - 2, 0.);
+                if i > m + 2 {
+                    hs!(i, i - 3, 0.);
+                }
+            }
+            // Double QR step involving rows l..n and columns m..n
+            for k in m..n {
+                let notlast = k != n - 1;
+                if k != m {
+                    p = hh!(k, k - 1);
+                    q = hh!(k + 1, k - 1);
+                    r = if notlast { hh!(k + 2, k - 1) } else { 0. };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x == 0. {
+                        continue;
+                    }
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+                s = (p * p + q * q + r * r).sqrt();
+                if p < 0. {
+                    s = -s;
+                }
+                if s != 0. {
+                    if k != m {
+                        hs!(k, k - 1, -s * x);
+                    }
+                    else if l != m {
+                        hs!(k, k - 1, -hh!(k, k - 1));
+                    }
+                    p += s;
+                    x = p / s;
+                    y = q / s;
+                    z = r / s;
+                    q /= p;
+                    r /= p;
+                    // Row modification
+                    for j in (k as usize)..nn {
+                        p = hh!(k, j) + q * hh!(k + 1, j);
+                        if notlast {
+                            p += r * hh!(k + 2, j);
+                            hs!(k + 2, j, hh!(k + 2, j) - p * z);
+                        }
+                        hs!(k, j, hh!(k, j) - p * x);
+                        hs!(k + 1, j, hh!(k + 1, j) - p * y);
+                    }
+                    // Column modification
+                    let last = if n < k + 3 { n } else { k + 3 };
+                    for i in 0..((last + 1) as usize) {
+                        p = x * hh!(i, k) + y * hh!(i, k + 1);
+                        if notlast {
+                            p += z * hh!(i, k + 2);
+                            hs!(i, k + 2, hh!(i, k + 2) - p * r);
+                        }
+                        hs!(i, k, hh!(i, k) - p);
+                        hs!(i, k + 1, hh!(i, k + 1) - p * q);
+                    }
+                    // Accumulate transformations
+                    let k = k as usize;
+                    for i in 0..nn {
+                        p = x * v.get(i, k).unwrap() + y * v.get(i, k + 1).unwrap();
+                        if notlast {
+                            p += z * v.get(i, k + 2).unwrap();
+                            let vik2 = v.get(i, k + 2).unwrap();
+                            v.set(i, k + 2, vik2 - p * r);
+                        }
+                        let vik = v.get(i, k).unwrap();
+                        let vik1 = v.get(i, k + 1).unwrap();
+                        v.set(i, k, vik - p);
+                        v.set(i, k + 1, vik1 - p * q);
+                    }
+                }
+            }
+        }
+    }
+    // Clean up below the sub-diagonal
+    for c in 0..nn {
+        for r in (c + 2)..nn {
+            h.set(r, c, 0.);
+        }
+    }
+    Ok((v, h))
+}
+
+
+/// Returns the sizes (1 or 2) of the diagonal blocks of
+/// a quasi upper triangular matrix
+fn schur_blocks(t : &MatrixF64) -> Vec<usize> {
+    let n = t.num_rows();
+    let mut blocks = Vec::new();
+    let mut k = 0;
+    while k < n {
+        if k + 1 < n && t.get(k + 1, k).unwrap() != 0. {
+            blocks.push(2);
+            k += 2;
+        }
+        else {
+            blocks.push(1);
+            k += 1;
+        }
+    }
+    blocks
+}
+
+/// Returns one of the eigen values of the diagonal block
+/// of size ``size`` starting at position k
+fn block_eigenvalue(t : &MatrixF64, k : usize, size : usize) -> Complex64 {
+    let a = t.get(k, k).unwrap();
+    if size == 1 {
+        return Complex64::new(a, 0.);
+    }
+    let b = t.get(k, k + 1).unwrap();
+    let c = t.get(k + 1, k).unwrap();
+    let d = t.get(k + 1, k + 1).unwrap();
+    let half = (a - d) / 2.;
+    let disc = half * half + b * c;
+    let mean = (a + d) / 2.;
+    if disc >= 0. {
+        Complex64::new(mean + disc.sqrt(), 0.)
+    }
+    else {
+        Complex64::new(mean, (-disc).sqrt())
+    }
+}
+
+/// Swaps the adjacent diagonal blocks of sizes p and r
+/// starting at position j of a quasi upper triangular T
+fn swap_blocks(t : &mut MatrixF64, q : &mut MatrixF64,
+    j : usize, p : usize, r : usize) -> SRResult<()> {
+    let m = p + r;
+    // Solve A11 X - X A22 = A12 for the p x r matrix X
+    let mut k : MatrixF64 = Matrix::zeros(p * r, p * r);
+    let mut rhs : MatrixF64 = Matrix::zeros(p * r, 1);
+    for c in 0..r {
+        for row in 0..p {
+            let idx = c * p + row;
+            rhs.set(idx, 0, t.get(j + row, j + p + c).unwrap());
+            for l in 0..p {
+                let x = k.get(idx, c * p + l).unwrap() + t.get(j + row, j + l).unwrap();
+                k.set(idx, c * p + l, x);
+            }
+            for l in 0..r {
+                let x = k.get(idx, l * p + row).unwrap() - t.get(j + p + l, j + p + c).unwrap();
+                k.set(idx, l * p + row, x);
+            }
+        }
+    }
+    let mut lu = LUDecomposition::new(k);
+    lu.decompose_ero();
+    let x = match lu.solve(&rhs) {
+        Ok(x) => x,
+        Err(SRError::IsSingular) => return Err(SRError::RepeatedEigenvalues),
+        Err(e) => return Err(e)
+    };
+    // The columns of [-X; I] span the invariant subspace of A22.
+    // Compute its QR decomposition W = U R.
+    let mut w : MatrixF64 = Matrix::zeros(m, r);
+    for c in 0..r {
+        for row in 0..p {
+            w.set(row, c, -x[c * p + row]);
+        }
+        w.set(p + c, c, 1.);
+    }
+    let mut u : MatrixF64 = Matrix::identity(m, m);
+    for c in 0..r {
+        let mut v : MatrixF64 = Matrix::zeros(m, 1);
+        for row in c..m {
+            v.set(row, 0, w.get(row, c).unwrap());
+        }
+        if householder_vector(&mut v, c) {
+            reflect_rows(&mut w, &v, c, c);
+            reflect_cols(&mut u, &v, c);
+        }
+    }
+    // T = U' T U, Q = Q U on the affected rows and columns
+    let n = t.num_rows();
+    let ut = u.transpose();
+    let rows = &ut * &t.view(j, 0, m, n).to_matrix();
+    for row in 0..m {
+        for c in 0..n {
+            t.set(j + row, c, rows.get(row, c).unwrap());
+        }
+    }
+    let cols = &t.view(0, j, n, m).to_matrix() * &u;
+    for row in 0..n {
+        for c in 0..m {
+            t.set(row, j + c, cols.get(row, c).unwrap());
+        }
+    }
+    let cols = &q.view(0, j, n, m).to_matrix() * &u;
+    for row in 0..n {
+        for c in 0..m {
+            q.set(row, j + c, cols.get(row, c).unwrap());
+        }
+    }
+    // The new blocks have sizes r and p.
+    for row in (j + r)..(j + m) {
+        for c in j..(j + r) {
+            t.set(row, c, 0.);
+        }
+    }
+    Ok(())
+}
+
+
+#[doc="Reorders a real Schur decomposition A = Q T Q' so that
+the selected eigen values appear in the leading diagonal
+blocks of T.
+
+A pair of complex conjugate eigen values is selected if
+the function ``select`` returns true for the eigen value
+with positive imaginary part.
+
+Returns the reordered (Q, T) and the number of selected
+eigen values.
+
+# Remarks
+
+Adjacent diagonal blocks are swapped by solving a small
+Sylvester equation. An error is returned if two blocks
+to be swapped share an eigen value.
+"]
+pub fn reorder_schur<F>(q : &MatrixF64, t : &MatrixF64, select : F)
+    -> SRResult<(MatrixF64, MatrixF64, usize)>
+    where F : Fn(Complex64) -> bool {
+    if !t.is_square() || !q.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    if q.num_rows() != t.num_rows() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let mut q = q.clone();
+    let mut t = t.clone();
+    let mut blocks = schur_blocks(&t);
+    // Index of the first block not yet selected
+    let mut head_block = 0;
+    let mut selected = 0;
+    let mut start = 0;
+    for b in 0..blocks.len() {
+        let size = blocks[b];
+        if select(block_eigenvalue(&t, start, size)) {
+            // Move this block up to the head
+            let mut pos = start;
+            let mut idx = b;
+            while idx > head_block {
+                let prev = blocks[idx - 1];
+                swap_blocks(&mut t, &mut q, pos - prev, prev, size)?;
+                blocks.swap(idx - 1, idx);
+                pos -= prev;
+                idx -= 1;
+            }
+            head_block += 1;
+            selected += size;
+        }
+        start += size;
+    }
+    Ok((q, t, selected))
+}
+
+
+#[doc="Reorders a complex Schur decomposition A = Q T Q^H so that
+the selected eigen values appear in the leading diagonal
+positions of T.
+
+Returns the reordered (Q, T) and the number of selected
+eigen values.
+"]
+pub fn reorder_schur_c64<F>(q : &MatrixC64, t : &MatrixC64, select : F)
+    -> SRResult<(MatrixC64, MatrixC64, usize)>
+    where F : Fn(Complex64) -> bool {
+    if !t.is_square() || !q.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    if q.num_rows() != t.num_rows() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let n = t.num_rows();
+    let zero = Complex64::new(0., 0.);
+    let mut q = q.clone();
+    let mut t = t.clone();
+    let mut selected = 0;
+    for k in 0..n {
+        if !select(t.get(k, k).unwrap()) {
+            continue;
+        }
+        // Bubble the eigen value at k up to position selected
+        let mut j = k;
+        while j > selected {
+            let i = j - 1;
+            let a = t.get(i, i).unwrap();
+            let b = t.get(j, j).unwrap();
+            let c = t.get(i, j).unwrap();
+            // [c; b - a] is an eigen vector of the 2x2 block for b
+            let w1 = c;
+            let w2 = b - a;
+            let norm = (w1.norm_sqr() + w2.norm_sqr()).sqrt();
+            if norm == 0. {
+                // Equal eigen values with zero coupling
+                j -= 1;
+                continue;
+            }
+            let g1 = w1 / norm;
+            let g2 = w2 / norm;
+            // G = [g1, -conj(g2); g2, conj(g1)]
+            for col in 0..n {
+                let u = t.get(i, col).unwrap();
+                let v = t.get(j, col).unwrap();
+                t.set(i, col, g1.conj() * u + g2.conj() * v);
+                t.set(j, col, -g2 * u + g1 * v);
+            }
+            for row in 0..n {
+                let u = t.get(row, i).unwrap();
+                let v = t.get(row, j).unwrap();
+                t.set(row, i, u * g1 + v * g2);
+                t.set(row, j, -u * g2.conj() + v * g1.conj());
+                let u = q.get(row, i).unwrap();
+                let v = q.get(row, j).unwrap();
+                q.set(row, i, u * g1 + v * g2);
+                q.set(row, j, -u * g2.conj() + v * g1.conj());
+            }
+            t.set(j, i, zero);
+            j -= 1;
+        }
+        selected += 1;
+    }
+    Ok((q, t, selected))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn max_abs_c64(m : &MatrixC64) -> f64 {
+        let mut s = 0.;
+        for v in m.cell_iter() {
+            if v.norm() > s {
+                s = v.norm();
+            }
+        }
+        s
+    }
+
+    fn check_schur(a : &MatrixF64){
+        let (q, t) = schur_complex(a).unwrap();
+        let n = a.num_rows();
+        let qh = conj_transpose_c64(&q);
+        let i : MatrixC64 = Matrix::identity(n, n);
+        assert!(max_abs_c64(&(&(&qh * &q) - &i)) < 1e-12);
+        let b = &(&q * &t) * &qh;
+        assert!(max_abs_c64(&(&b - &to_complex_f64(a))) < 1e-10);
+        for c in 0..n {
+            for r in (c + 1)..n {
+                assert_eq!(t.get(r, c).unwrap().norm(), 0.);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hessenberg_c64(){
+        let a = to_complex_f64(&from_range_rw_f64(5, 5, 1., 100.));
+        let (q, h) = hessenberg_c64(&a).unwrap();
+        for c in 0..5 {
+            for r in (c + 2)..5 {
+                assert_eq!(h.get(r, c).unwrap().norm(), 0.);
+            }
+        }
+        let b = &(&q * &h) * &conj_transpose_c64(&q);
+        assert!(max_abs_c64(&(&b - &a)) < 1e-10);
+    }
+
+    #[test]
+    fn test_schur_rotation(){
+        // Eigen values are +i and -i
+        let a = matrix_rw_f64(2, 2, &[
+            0., 1.,
+            -1., 0.
+            ]);
+        check_schur(&a);
+        let w = eigenvalues(&a).unwrap();
+        assert!((w[0].re).abs() < 1e-12);
+        assert!((w[0].im.abs() - 1.).abs() < 1e-12);
+        assert!((w[0] + w[1]).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_schur_general(){
+        check_schur(&matrix_rw_f64(4, 4, &[
+            4., 1., 0., 2.,
+            -1., 5., 1., 0.,
+            0., -2., 6., 1.,
+            1., 0., -1., 3.
+            ]));
+        check_schur(&hilbert(6));
+        check_schur(&from_range_rw_f64(5, 5, 1., 100.));
+    }
+
+    #[test]
+    fn test_eigenvalues_triangular(){
+        let a = matrix_rw_f64(3, 3, &[
+            1., 2., 3.,
+            0., 4., 5.,
+            0., 0., 6.
+            ]);
+        let w = eigenvalues(&a).unwrap();
+        let mut re : Vec<f64> = (0..3).map(|i| w[i].re).collect();
+        re.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        assert!((re[0] - 1.).abs() < 1e-12);
+        assert!((re[1] - 4.).abs() < 1e-12);
+        assert!((re[2] - 6.).abs() < 1e-12);
+    }
+    fn check_real_schur(a : &MatrixF64, q : &MatrixF64, t : &MatrixF64){
+        let n = a.num_rows();
+        let i : MatrixF64 = Matrix::identity(n, n);
+        assert!((&(&q.transpose() * q) - &i).max_abs_scalar_value() < 1e-12);
+        let b = &(q * t) * &q.transpose();
+        assert!((&b - a).max_abs_scalar_value() < 1e-10 * (1. + a.max_abs_scalar_value()));
+        for c in 0..n {
+            for r in (c + 2)..n {
+                assert_eq!(t.get(r, c).unwrap(), 0.);
+            }
+            // No two consecutive non-zero sub-diagonal entries
+            if c + 2 < n {
+                assert!(t.get(c + 1, c).unwrap() == 0. || t.get(c + 2, c + 1).unwrap() == 0.);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hessenberg(){
+        let a = from_range_rw_f64(6, 6, 1., 100.);
+        let (q, h) = hessenberg(&a).unwrap();
+        for c in 0..6 {
+            for r in (c + 2)..6 {
+                assert_eq!(h.get(r, c).unwrap(), 0.);
+            }
+        }
+        let b = &(&q * &h) * &q.transpose();
+        assert!((&b - &a).max_abs_scalar_value() < 1e-10);
+    }
+
+    #[test]
+    fn test_real_schur(){
+        let a = matrix_rw_f64(4, 4, &[
+            4., 1., 0., 2.,
+            -1., 5., 1., 0.,
+            0., -2., 6., 1.,
+            1., 0., -1., 3.
+            ]);
+        let (q, t) = schur(&a).unwrap();
+        check_real_schur(&a, &q, &t);
+        let a = hilbert(7);
+        let (q, t) = schur(&a).unwrap();
+        check_real_schur(&a, &q, &t);
+        // Rotation: a single 2x2 block
+        let a = matrix_rw_f64(2, 2, &[0., 1., -1., 0.]);
+        let (q, t) = schur(&a).unwrap();
+        check_real_schur(&a, &q, &t);
+        assert!(t.get(1, 0).unwrap() != 0.);
+    }
+
+    #[test]
+    fn test_reorder_schur(){
+        let a = matrix_rw_f64(5, 5, &[
+            1., 2., 0., 1., 0.,
+            -2., 1., 1., 0., 3.,
+            0., 0., -3., 1., 1.,
+            1., 0., 0., 2., -1.,
+            0., 1., 0., 1., -1.
+            ]);
+        let (q, t) = schur(&a).unwrap();
+        let (q, t, k) = reorder_schur(&q, &t, |z| z.re < 0.).unwrap();
+        check_real_schur(&a, &q, &t);
+        let w = eigenvalues(&a).unwrap();
+        let expected = (0..5).filter(|&i| w[i].re < 0.).count();
+        assert_eq!(k, expected);
+        let blocks = schur_blocks(&t);
+        let mut pos = 0;
+        for size in blocks {
+            let z = block_eigenvalue(&t, pos, size);
+            assert_eq!(z.re < 0., pos < k);
+            pos += size;
+        }
+    }
+
+    #[test]
+    fn test_reorder_schur_c64(){
+        let a = to_complex_f64(&from_range_rw_f64(4, 4, 1., 100.));
+        let a = &a + &to_complex_f64(&hilbert(4));
+        let (q, t) = schur_c64(&a).unwrap();
+        let (q, t, k) = reorder_schur_c64(&q, &t, |z| z.re < 1.).unwrap();
+        let b = &(&q * &t) * &conj_transpose_c64(&q);
+        assert!(max_abs_c64(&(&b - &a)) < 1e-9);
+        for i in 0..4 {
+            assert_eq!(t.get(i, i).unwrap().re < 1., i < k);
+        }
+        assert!(k >= 1);
+    }
+}
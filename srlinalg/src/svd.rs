@@ -0,0 +1,177 @@
+#![doc="Singular value decomposition
+
+A = U diag(s) V' where the columns of U and V are orthonormal
+and the singular values s are non-negative and sorted in
+decreasing order.
+
+The decomposition is computed by the one sided Jacobi method
+(Hestenes) which orthogonalizes the columns of A by plane
+rotations. It is slower than bidiagonalization based methods
+but simple and very accurate.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+
+
+#[doc="Computes the thin singular value decomposition of an
+m x n matrix A.
+
+Returns (U, s, V) with k = min(m, n) where U is m x k, s is
+a column vector of length k and V is n x k.
+
+For m >= n, V is square (n x n).
+"]
+pub fn svd(a : &MatrixF64) -> SRResult<(MatrixF64, MatrixF64, MatrixF64)> {
+    let m = a.num_rows();
+    let n = a.num_cols();
+    if m < n {
+        let (u, s, v) = svd(&a.transpose())?;
+        return Ok((v, s, u));
+    }
+    let mut u = a.clone();
+    let mut v : MatrixF64 = Matrix::identity(n, n);
+    let mut converged = false;
+    for _ in 0..60 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let mut alpha = 0.;
+                let mut beta = 0.;
+                let mut gamma = 0.;
+                for i in 0..m {
+                    let up = u.get(i, p).unwrap();
+                    let uq = u.get(i, q).unwrap();
+                    alpha += up * up;
+                    beta += uq * uq;
+                    gamma += up * uq;
+                }
+                if gamma == 0. || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (2. * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1. + zeta * zeta).sqrt());
+                let c = 1. / (1. + t * t).sqrt();
+                let s = c * t;
+                for i in 0..m {
+                    let up = u.get(i, p).unwrap();
+                    let uq = u.get(i, q).unwrap();
+                    u.set(i, p, c * up - s * uq);
+                    u.set(i, q, s * up + c * uq);
+                }
+                for i in 0..n {
+                    let vp = v.get(i, p).unwrap();
+                    let vq = v.get(i, q).unwrap();
+                    v.set(i, p, c * vp - s * vq);
+                    v.set(i, q, s * vp + c * vq);
+                }
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(SRError::NotConverged);
+    }
+    // Singular values are the norms of the columns
+    let mut norms = vec![0.; n];
+    for j in 0..n {
+        let mut s = 0.;
+        for i in 0..m {
+            let x = u.get(i, j).unwrap();
+            s += x * x;
+        }
+        norms[j] = s.sqrt();
+    }
+    let mut order : Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap());
+    let mut uu : MatrixF64 = Matrix::zeros(m, n);
+    let mut s : MatrixF64 = Matrix::zeros(n, 1);
+    let mut vv : MatrixF64 = Matrix::zeros(n, n);
+    for (k, &j) in order.iter().enumerate() {
+        s.set(k, 0, norms[j]);
+        for i in 0..m {
+            let x = if norms[j] > 0. { u.get(i, j).unwrap() / norms[j] } else { 0. };
+            uu.set(i, k, x);
+        }
+        for i in 0..n {
+            vv.set(i, k, v.get(i, j).unwrap());
+        }
+    }
+    Ok((uu, s, vv))
+}
+
+
+/// Returns the singular values of A in decreasing order
+pub fn singular_values(a : &MatrixF64) -> SRResult<MatrixF64> {
+    let (_, s, _) = svd(a)?;
+    Ok(s)
+}
+
+
+/// Returns the 2-norm condition number s_max / s_min of A
+pub fn cond_2(a : &MatrixF64) -> SRResult<f64> {
+    let s = singular_values(a)?;
+    if s.is_empty() {
+        return Err(SRError::EmptyMatrix);
+    }
+    Ok(s[0] / s[s.num_cells() - 1])
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn check_svd(a : &MatrixF64){
+        let (u, s, v) = svd(a).unwrap();
+        let k = s.num_cells();
+        let b = &(&u * &Matrix::diag_from_vec(&s)) * &v.transpose();
+        assert!((&b - a).max_abs_scalar_value() < 1e-12 * (1. + a.max_abs_scalar_value()));
+        let i : MatrixF64 = Matrix::identity(k, k);
+        assert!((&(&v.transpose() * &v) - &i).max_abs_scalar_value() < 1e-12);
+        for j in 1..k {
+            assert!(s[j - 1] >= s[j]);
+        }
+    }
+
+    #[test]
+    fn test_svd(){
+        check_svd(&matrix_rw_f64(3, 2, &[
+            3., 2.,
+            2., 3.,
+            2., -2.
+            ]));
+        check_svd(&matrix_rw_f64(2, 3, &[
+            3., 2., 2.,
+            2., 3., -2.
+            ]));
+        check_svd(&hilbert(5));
+        // Singular values of [3 2 2; 2 3 -2] are 5 and 3
+        let s = singular_values(&matrix_rw_f64(2, 3, &[
+            3., 2., 2.,
+            2., 3., -2.
+            ])).unwrap();
+        assert!((s[0] - 5.).abs() < 1e-13);
+        assert!((s[1] - 3.).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_cond_2(){
+        let a = matrix_rw_f64(2, 2, &[2., 0., 0., 0.5]);
+        assert!((cond_2(&a).unwrap() - 4.).abs() < 1e-14);
+    }
+}
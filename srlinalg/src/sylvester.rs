@@ -0,0 +1,256 @@
+#![doc="Sylvester and Lyapunov matrix equations
+
+* ``solve_sylvester``: AX + XB = C
+* ``solve_continuous_lyapunov``: AX + XA' + Q = 0
+* ``solve_discrete_lyapunov``: AXA' - X + Q = 0
+
+All solvers follow the Bartels-Stewart approach. The
+coefficient matrices are reduced to (complex) Schur form.
+The transformed equation is then solved column by column
+by back substitution and the solution is transformed back.
+
+References:
+
+* R. H. Bartels and G. W. Stewart, Solution of the matrix
+  equation AX + XB = C, Comm. ACM 15(9), 1972.
+"]
+
+
+// std imports
+use std::f64;
+
+// external imports
+use num::complex::Complex64;
+
+// local imports
+use srmatrix::api::*;
+use schur::schur_complex;
+
+
+/// Solves (alpha R + beta I) y = rhs in place for an upper
+/// triangular R
+fn solve_shifted_triangular(r : &MatrixC64,
+    alpha : Complex64,
+    beta : Complex64,
+    rhs : &mut MatrixC64) -> SRResult<()> {
+    let n = r.num_rows();
+    let mut scale = 0.;
+    for i in 0..n {
+        let d = (alpha * r.get(i, i).unwrap() + beta).norm();
+        if d > scale {
+            scale = d;
+        }
+    }
+    let scale = if scale == 0. { 1. } else { scale };
+    for i in (0..n).rev() {
+        let mut v = rhs[i];
+        for k in (i + 1)..n {
+            v -= alpha * r.get(i, k).unwrap() * rhs[k];
+        }
+        let pivot = alpha * r.get(i, i).unwrap() + beta;
+        if pivot.norm() <= f64::EPSILON * scale {
+            return Err(SRError::IsSingular);
+        }
+        rhs.set(i, 0, v / pivot);
+    }
+    Ok(())
+}
+
+
+#[doc="Solves the Sylvester equation AX + XB = C.
+
+A is m x m, B is n x n and C is m x n.
+
+A unique solution exists when A and -B have no common
+eigen values. Otherwise ``IsSingular`` is returned.
+"]
+pub fn solve_sylvester(a : &MatrixF64, b : &MatrixF64, c : &MatrixF64)
+    -> SRResult<MatrixF64> {
+    if !a.is_square() || !b.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    let m = a.num_rows();
+    let n = b.num_rows();
+    if c.num_rows() != m || c.num_cols() != n {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let (u, r) = schur_complex(a)?;
+    let (v, s) = schur_complex(b)?;
+    // R Y + Y S = F with F = U^H C V and X = U Y V^H
+    let f = &(&conj_transpose_c64(&u) * &to_complex_f64(c)) * &v;
+    let one = Complex64::new(1., 0.);
+    let mut y : MatrixC64 = Matrix::zeros(m, n);
+    for j in 0..n {
+        // (R + s_jj I) y_j = f_j - sum_{k < j} s_kj y_k
+        let mut rhs : MatrixC64 = Matrix::zeros(m, 1);
+        for i in 0..m {
+            let mut x = f.get(i, j).unwrap();
+            for k in 0..j {
+                x -= y.get(i, k).unwrap() * s.get(k, j).unwrap();
+            }
+            rhs.set(i, 0, x);
+        }
+        solve_shifted_triangular(&r, one, s.get(j, j).unwrap(), &mut rhs)?;
+        for i in 0..m {
+            y.set(i, j, rhs[i]);
+        }
+    }
+    let x = &(&u * &y) * &conj_transpose_c64(&v);
+    Ok(real_part_c64(&x))
+}
+
+
+#[doc="Solves the continuous Lyapunov equation AX + XA' + Q = 0.
+
+When A is stable (all eigen values have negative real
+parts) and Q is symmetric positive semi definite, the
+solution X is symmetric positive semi definite.
+"]
+pub fn solve_continuous_lyapunov(a : &MatrixF64, q : &MatrixF64)
+    -> SRResult<MatrixF64> {
+    let x = solve_sylvester(a, &a.transpose(), &q.unary_minus())?;
+    Ok(symmetrize(&x, q))
+}
+
+
+#[doc="Solves the discrete Lyapunov (Stein) equation
+AXA' - X + Q = 0.
+
+A unique solution exists when no product of two eigen
+values of A equals 1. In particular this holds when all
+eigen values of A lie inside the unit circle.
+"]
+pub fn solve_discrete_lyapunov(a : &MatrixF64, q : &MatrixF64)
+    -> SRResult<MatrixF64> {
+    if !a.is_square() {
+        return Err(SRError::IsNotSquareMatrix);
+    }
+    let n = a.num_rows();
+    if q.num_rows() != n || q.num_cols() != n {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let (u, r) = schur_complex(a)?;
+    let uh = conj_transpose_c64(&u);
+    // R Y R^H - Y + F = 0 with F = U^H Q U and X = U Y U^H
+    let f = &(&uh * &to_complex_f64(q)) * &u;
+    let minus_one = Complex64::new(-1., 0.);
+    let mut y : MatrixC64 = Matrix::zeros(n, n);
+    for j in (0..n).rev() {
+        // (conj(r_jj) R - I) y_j = -f_j - R sum_{k > j} conj(r_jk) y_k
+        let mut g : MatrixC64 = Matrix::zeros(n, 1);
+        for k in (j + 1)..n {
+            let rjk = r.get(j, k).unwrap().conj();
+            for i in 0..n {
+                let x = g[i] + rjk * y.get(i, k).unwrap();
+                g.set(i, 0, x);
+            }
+        }
+        let rg = &r * &g;
+        let mut rhs : MatrixC64 = Matrix::zeros(n, 1);
+        for i in 0..n {
+            rhs.set(i, 0, -f.get(i, j).unwrap() - rg[i]);
+        }
+        solve_shifted_triangular(&r, r.get(j, j).unwrap().conj(), minus_one, &mut rhs)?;
+        for i in 0..n {
+            y.set(i, j, rhs[i]);
+        }
+    }
+    let x = &(&u * &y) * &uh;
+    Ok(symmetrize(&real_part_c64(&x), q))
+}
+
+
+/// Returns (X + X') / 2 if Q is symmetric, X otherwise
+fn symmetrize(x : &MatrixF64, q : &MatrixF64) -> MatrixF64 {
+    if q.is_symmetric() {
+        (x + &x.transpose()).copy_mul_scalar(0.5)
+    }
+    else {
+        x.clone()
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_sylvester(){
+        let a = matrix_rw_f64(3, 3, &[
+            1., 2., 0.,
+            -1., 3., 1.,
+            0., 1., 4.
+            ]);
+        let b = matrix_rw_f64(2, 2, &[
+            2., -1.,
+            3., 1.
+            ]);
+        let x = matrix_rw_f64(3, 2, &[
+            1., 2.,
+            -1., 0.5,
+            3., -2.
+            ]);
+        let c = &(&a * &x) + &(&x * &b);
+        let y = solve_sylvester(&a, &b, &c).unwrap();
+        assert!((&y - &x).max_abs_scalar_value() < 1e-12);
+    }
+
+    #[test]
+    fn test_sylvester_singular(){
+        // A and -B share the eigen value 1
+        let a = matrix_rw_f64(2, 2, &[1., 0., 0., 2.]);
+        let b = matrix_rw_f64(2, 2, &[-1., 0., 0., 5.]);
+        let c : MatrixF64 = Matrix::ones(2, 2);
+        assert!(solve_sylvester(&a, &b, &c).is_err());
+    }
+
+    #[test]
+    fn test_continuous_lyapunov(){
+        let a = matrix_rw_f64(3, 3, &[
+            -2., 1., 0.,
+            -1., -3., 1.,
+            0., 2., -1.
+            ]);
+        let q = matrix_rw_f64(3, 3, &[
+            2., 1., 0.,
+            1., 3., 0.,
+            0., 0., 1.
+            ]);
+        let x = solve_continuous_lyapunov(&a, &q).unwrap();
+        let r = &(&(&a * &x) + &(&x * &a.transpose())) + &q;
+        assert!(r.max_abs_scalar_value() < 1e-12);
+        assert!(x.is_symmetric());
+        // Scalar case: 2 a x + q = 0
+        let x = solve_continuous_lyapunov(&matrix_rw_f64(1, 1, &[-2.]),
+            &matrix_rw_f64(1, 1, &[3.])).unwrap();
+        assert!((x[0] - 0.75).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_discrete_lyapunov(){
+        let a = matrix_rw_f64(3, 3, &[
+            0.5, 0.2, 0.,
+            -0.3, 0.4, 0.1,
+            0.1, 0., -0.6
+            ]);
+        let q = matrix_rw_f64(3, 3, &[
+            1., 0.5, 0.,
+            0.5, 2., 0.,
+            0., 0., 1.
+            ]);
+        let x = solve_discrete_lyapunov(&a, &q).unwrap();
+        let r = &(&(&(&a * &x) * &a.transpose()) - &x) + &q;
+        assert!(r.max_abs_scalar_value() < 1e-12);
+        // Scalar case: x = q / (1 - a^2)
+        let x = solve_discrete_lyapunov(&matrix_rw_f64(1, 1, &[0.5]),
+            &matrix_rw_f64(1, 1, &[3.])).unwrap();
+        assert!((x[0] - 4.).abs() < 1e-14);
+    }
+}
@@ -54,6 +54,14 @@ pub enum SRError{
     IsNegativeSemiDefinite,
     /// The matrix is non-definite
     IsNonDefinite,
+    /// The matrix is not positive definite
+    IsNotPositiveDefinite,
+    /// The matrix is not symmetric
+    IsNotSymmetric,
+    /// The matrix has repeated (or nearly repeated) eigenvalues
+    RepeatedEigenvalues,
+    /// An argument is outside of its valid domain
+    InvalidArgument,
 
 
     /******************************************************
@@ -69,6 +77,28 @@ pub enum SRError{
     /// There are infinite solutions to the system of equations.
     InfiniteSolutions,
 
+    /******************************************************
+     *
+     *   Errors related to iterative algorithms
+     *
+     *******************************************************/
+
+    /// The iterative algorithm broke down (division by zero)
+    Breakdown,
+    /// The iterative algorithm did not converge
+    NotConverged,
+
+    /******************************************************
+     *
+     *   Errors related to optimization problems
+     *
+     *******************************************************/
+
+    /// The feasible set of the problem is empty
+    Infeasible,
+    /// The objective is unbounded on the feasible set
+    Unbounded,
+
     /******************************************************
      *
      *   Arithmetic related stuff
@@ -118,10 +148,19 @@ impl SRError{
             SRError::IsNegativeDefinite => format!("Matrix is negative definite"),
             SRError::IsNegativeSemiDefinite => format!("Matrix is negative semi-definite"),
             SRError::IsNonDefinite => format!("Matrix is non-definite"),
+            SRError::IsNotPositiveDefinite => format!("Matrix is not positive definite"),
+            SRError::IsNotSymmetric => format!("Matrix is not symmetric"),
+            SRError::RepeatedEigenvalues => format!("Matrix has repeated eigenvalues"),
+            SRError::InvalidArgument => format!("Argument is outside of its valid domain"),
             // Linear systems
             SRError::LRDimensionMismatch => format!("The dimensions of LHS and RHS don't match"),
             SRError::NoSolution => format!("No solution"),
             SRError::InfiniteSolutions => format!("Infinite solutions"),
+            // Iterative algorithms
+            SRError::Breakdown => format!("Iterative algorithm broke down"),
+            SRError::NotConverged => format!("Iterative algorithm did not converge"),
+            SRError::Infeasible => format!("Problem is infeasible"),
+            SRError::Unbounded => format!("Problem is unbounded"),
             // Arithmetic
             SRError::DivideByZero => format!("Attempt to divide by zero"),
             // Discrete numbers
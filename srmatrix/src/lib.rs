@@ -25,6 +25,7 @@ pub mod matrix;
 pub mod random;
 pub mod traits;
 pub mod vector;
+pub mod vecops;
 
 
 pub mod view;
@@ -908,7 +908,7 @@ impl<T:CommutativeMonoidAddPartial+CommutativeMonoidMulPartial> Matrix<T> {
 impl<T:CommutativeGroupAddPartial> Matrix<T> {
     /// Computes the unary minus of a matrix
     pub fn unary_minus(&self)-> Matrix<T> {
-        let mut result : Matrix<T> = Matrix::new(self.cols, self.rows);
+        let mut result : Matrix<T> = Matrix::new(self.rows, self.cols);
         {
             let ref pa = self.vec;
             let ref mut pc = result.vec;
@@ -1968,6 +1968,10 @@ mod test {
         let m2 = m.unary_minus();
         let m3 = &z - &m;
         assert_eq!(m2, m3);
+        let v : MatrixI64 = Matrix::from_iter_cw(3, 1, 0..3);
+        let v2 = v.unary_minus();
+        assert_eq!(v2.size(), (3, 1));
+        assert_eq!(v2, Matrix::from_iter_cw(3, 1, vec![0, -1, -2].into_iter()));
     }
 
 
@@ -1,5 +1,8 @@
+// external imports
+use num::complex::Complex64;
+
 // local imports
-use matrix::Matrix;
+use matrix::{Matrix, MatrixF64, MatrixC64};
 use traits::*;
 use sralgebra::MagmaBase;
 
@@ -21,3 +24,72 @@ impl <T:MagmaBase> Conversion<T> for Matrix<T> {
 
 
 }
+
+
+/// Converts a real matrix to a complex matrix
+pub fn to_complex_f64(m : &MatrixF64) -> MatrixC64 {
+    let mut result : MatrixC64 = Matrix::zeros(m.num_rows(), m.num_cols());
+    for c in 0..m.num_cols(){
+        for r in 0..m.num_rows(){
+            result.set(r, c, Complex64::new(m.get(r, c).unwrap(), 0.));
+        }
+    }
+    result
+}
+
+/// Returns the real part of a complex matrix
+pub fn real_part_c64(m : &MatrixC64) -> MatrixF64 {
+    let mut result : MatrixF64 = Matrix::zeros(m.num_rows(), m.num_cols());
+    for c in 0..m.num_cols(){
+        for r in 0..m.num_rows(){
+            result.set(r, c, m.get(r, c).unwrap().re);
+        }
+    }
+    result
+}
+
+/// Returns the imaginary part of a complex matrix
+pub fn imag_part_c64(m : &MatrixC64) -> MatrixF64 {
+    let mut result : MatrixF64 = Matrix::zeros(m.num_rows(), m.num_cols());
+    for c in 0..m.num_cols(){
+        for r in 0..m.num_rows(){
+            result.set(r, c, m.get(r, c).unwrap().im);
+        }
+    }
+    result
+}
+
+/// Returns the conjugate transpose of a complex matrix
+pub fn conj_transpose_c64(m : &MatrixC64) -> MatrixC64 {
+    let mut result : MatrixC64 = Matrix::zeros(m.num_cols(), m.num_rows());
+    for c in 0..m.num_cols(){
+        for r in 0..m.num_rows(){
+            result.set(c, r, m.get(r, c).unwrap().conj());
+        }
+    }
+    result
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use constructors::*;
+
+    #[test]
+    fn test_complex_conversions(){
+        let m = matrix_rw_f64(2, 2, &[1., 2., 3., 4.]);
+        let c = to_complex_f64(&m);
+        assert_eq!(real_part_c64(&c), m);
+        assert_eq!(imag_part_c64(&c), Matrix::zeros(2, 2));
+        let c : MatrixC64 = Matrix::from_slice_rw(1, 2, &[Complex64::new(1., 2.), Complex64::new(3., -4.)]);
+        let h = conj_transpose_c64(&c);
+        assert_eq!(h, Matrix::from_slice_rw(2, 1, &[Complex64::new(1., -2.), Complex64::new(3., 4.)]));
+    }
+}
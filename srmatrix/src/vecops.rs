@@ -0,0 +1,104 @@
+#![doc="Dense kernels on plain vectors
+
+Iterative solvers often keep their state in plain vectors
+and only touch matrices for products. These helpers work on
+slices and handle matrices without rows or columns, unlike
+``cell_iter`` and friends.
+
+The names are generic, hence this module is not re-exported
+by ``api``; import the functions explicitly.
+"]
+
+
+// std imports
+
+// local imports
+use matrix::{Matrix, MatrixF64};
+use traits::Shape;
+
+
+/// Returns the inner product u' v
+pub fn dot(u : &[f64], v : &[f64]) -> f64 {
+    u.iter().zip(v.iter()).fold(0., |s, (a, b)| s + a * b)
+}
+
+
+/// Returns the 2-norm of u
+pub fn norm2(u : &[f64]) -> f64 {
+    dot(u, u).sqrt()
+}
+
+
+/// Returns the infinity norm of u
+pub fn norm_inf(u : &[f64]) -> f64 {
+    u.iter().fold(0., |m, v| if v.abs() > m { v.abs() } else { m })
+}
+
+
+/// Computes A v
+pub fn mul(a : &MatrixF64, v : &[f64]) -> Vec<f64> {
+    (0..a.num_rows()).map(|i| (0..a.num_cols())
+        .fold(0., |s, j| s + a.get(i, j).unwrap() * v[j])).collect()
+}
+
+
+/// Computes A' v
+pub fn mul_t(a : &MatrixF64, v : &[f64]) -> Vec<f64> {
+    (0..a.num_cols()).map(|j| (0..a.num_rows())
+        .fold(0., |s, i| s + a.get(i, j).unwrap() * v[i])).collect()
+}
+
+
+/// Converts a column vector to a plain vector
+pub fn to_vec(v : &MatrixF64) -> Vec<f64> {
+    (0..v.num_rows()).map(|i| v.get(i, 0).unwrap()).collect()
+}
+
+
+/// Converts a plain vector to a column vector
+pub fn to_col(v : &[f64]) -> MatrixF64 {
+    Matrix::from_iter_cw(v.len(), 1, v.iter().cloned())
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use constructors::*;
+
+    #[test]
+    fn test_norms(){
+        let u = [3., -4.];
+        assert_eq!(dot(&u, &[1., 2.]), -5.);
+        assert_eq!(norm2(&u), 5.);
+        assert_eq!(norm_inf(&u), 4.);
+        assert_eq!(norm2(&[]), 0.);
+        assert_eq!(norm_inf(&[]), 0.);
+    }
+
+    #[test]
+    fn test_products(){
+        let a = matrix_rw_f64(2, 3, &[1., 2., 3., 4., 5., 6.]);
+        assert_eq!(mul(&a, &[1., 0., -1.]), vec![-2., -2.]);
+        assert_eq!(mul_t(&a, &[1., -1.]), vec![-3., -3., -3.]);
+        let e : MatrixF64 = Matrix::zeros(0, 3);
+        assert!(mul(&e, &[1., 2., 3.]).is_empty());
+        assert_eq!(mul_t(&e, &[]), vec![0., 0., 0.]);
+    }
+
+    #[test]
+    fn test_conversions(){
+        let v = to_col(&[1., 2.]);
+        assert_eq!(v, vector_f64(&[1., 2.]));
+        assert_eq!(to_vec(&v), vec![1., 2.]);
+        let e = to_col(&[]);
+        assert_eq!(e.num_rows(), 0);
+        assert!(to_vec(&e).is_empty());
+    }
+}
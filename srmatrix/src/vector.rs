@@ -9,7 +9,7 @@ or a row vector. The stride is always one in both cases.
 // std imports
 
 // external imports
-use num::traits::{One, Zero};
+use num::traits::{One, Zero, Float};
 
 
 // local imports
@@ -76,6 +76,18 @@ pub fn vec_reduce_product<T:CommutativeRingPartial>(v : &Matrix<T>) -> T{
     result
 }
 
+/// Computes the Euclidean (l2) norm of vector v
+///
+/// A vector with a single entry is accepted as well.
+pub fn vec_norm_l2<T:CommutativeRingPartial+Float>(v : &Matrix<T>) -> T{
+    assert!(v.is_vector() || v.is_scalar());
+    let mut result : T = Zero::zero();
+    for entry in v.cell_iter(){
+        result = result + entry * entry;
+    }
+    result.sqrt()
+}
+
 
 /******************************************************
  *
@@ -131,4 +143,13 @@ mod test{
         assert_eq!(vec_reduce_product(&v), 24);
     }
 
+    #[test]
+    fn test_vec_norm_l2(){
+        let v = vector_f64(&[3., 4.]);
+        assert_eq!(vec_norm_l2(&v), 5.);
+        let v = v.transpose();
+        assert_eq!(vec_norm_l2(&v), 5.);
+        assert_eq!(vec_norm_l2(&vector_f64(&[-2.])), 2.);
+    }
+
 }
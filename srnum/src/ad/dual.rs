@@ -0,0 +1,509 @@
+#![doc="Forward mode automatic differentiation with dual numbers
+
+A dual number a + b e with e^2 = 0 carries a value a together
+with a derivative b. Arithmetic on dual numbers follows the
+rules of differentiation:
+
+(a + b e) (c + d e) = a c + (a d + b c) e
+
+so evaluating f(x + e) yields f(x) + f'(x) e. One evaluation
+gives the derivative of all outputs along one direction of
+the input; a full Jacobian of a function of n inputs needs n
+evaluations.
+
+``Dual<T>`` implements the partial algebraic structure traits
+up to ``FieldPartial``, hence matrices of dual numbers can be
+built and multiplied like ``Matrix<f64>``.
+"]
+
+
+// std imports
+use std::fmt::Debug;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+// external imports
+use num::traits::{Float, Zero, One};
+
+// srmatrix imports
+use srmatrix::api::*;
+use sralgebra::ops::{Recip, Division};
+use sralgebra::{SemiGroupAddPartial, SemiGroupMulPartial,
+    QuasiGroupAddPartial,
+    CommutativeMonoidAddPartial, CommutativeMonoidMulPartial,
+    CommutativeGroupAddPartial,
+    IntegralDomainPartial, FieldPartial};
+
+
+/// A dual number value + deriv e with e^2 = 0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dual<T:Float> {
+    /// The value of the function
+    pub value : T,
+    /// The derivative of the function
+    pub deriv : T
+}
+
+/// A dual number over 64-bit floats
+pub type Dual64 = Dual<f64>;
+
+
+impl<T:Float> Dual<T> {
+
+    /// Constructs a dual number from a value and a derivative
+    #[inline]
+    pub fn new(value : T, deriv : T) -> Dual<T> {
+        Dual {
+            value : value,
+            deriv : deriv
+        }
+    }
+
+    /// A constant (its derivative is zero)
+    #[inline]
+    pub fn constant(value : T) -> Dual<T> {
+        Dual::new(value, T::zero())
+    }
+
+    /// The independent variable (its derivative is one)
+    #[inline]
+    pub fn variable(value : T) -> Dual<T> {
+        Dual::new(value, T::one())
+    }
+
+    /// Applies a function with the given value and derivative at self.value
+    /// using the chain rule
+    #[inline]
+    fn chain(self, value : T, deriv : T) -> Dual<T> {
+        Dual::new(value, deriv * self.deriv)
+    }
+
+    /// Absolute value. The derivative at 0 is taken as 0.
+    pub fn abs(self) -> Dual<T> {
+        let s = if self.value > T::zero() {
+            T::one()
+        } else if self.value < T::zero() {
+            -T::one()
+        } else {
+            T::zero()
+        };
+        self.chain(self.value.abs(), s)
+    }
+
+    /// Square root
+    pub fn sqrt(self) -> Dual<T> {
+        let r = self.value.sqrt();
+        self.chain(r, T::one() / (r + r))
+    }
+
+    /// Exponential function
+    pub fn exp(self) -> Dual<T> {
+        let e = self.value.exp();
+        self.chain(e, e)
+    }
+
+    /// Natural logarithm
+    pub fn ln(self) -> Dual<T> {
+        self.chain(self.value.ln(), self.value.recip())
+    }
+
+    /// Integer power
+    pub fn powi(self, n : i32) -> Dual<T> {
+        if n == 0 {
+            return Dual::constant(T::one());
+        }
+        let d = T::from(n).unwrap() * self.value.powi(n - 1);
+        self.chain(self.value.powi(n), d)
+    }
+
+    /// Real power
+    pub fn powf(self, p : T) -> Dual<T> {
+        if p == T::zero() {
+            return Dual::constant(T::one());
+        }
+        self.chain(self.value.powf(p), p * self.value.powf(p - T::one()))
+    }
+
+    /// Power with a dual exponent, computed as exp(p ln x)
+    pub fn pow(self, p : Dual<T>) -> Dual<T> {
+        (p * self.ln()).exp()
+    }
+
+    /// Sine
+    pub fn sin(self) -> Dual<T> {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+
+    /// Cosine
+    pub fn cos(self) -> Dual<T> {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+
+    /// Tangent
+    pub fn tan(self) -> Dual<T> {
+        let t = self.value.tan();
+        self.chain(t, T::one() + t * t)
+    }
+
+    /// Inverse tangent
+    pub fn atan(self) -> Dual<T> {
+        self.chain(self.value.atan(), (T::one() + self.value * self.value).recip())
+    }
+
+    /// Hyperbolic sine
+    pub fn sinh(self) -> Dual<T> {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+
+    /// Hyperbolic cosine
+    pub fn cosh(self) -> Dual<T> {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+
+    /// Hyperbolic tangent
+    pub fn tanh(self) -> Dual<T> {
+        let t = self.value.tanh();
+        self.chain(t, T::one() - t * t)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Arithmetic
+ *
+ *******************************************************/
+
+impl<T:Float> Add for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn add(self, rhs : Dual<T>) -> Dual<T> {
+        Dual::new(self.value + rhs.value, self.deriv + rhs.deriv)
+    }
+}
+
+impl<T:Float> Sub for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn sub(self, rhs : Dual<T>) -> Dual<T> {
+        Dual::new(self.value - rhs.value, self.deriv - rhs.deriv)
+    }
+}
+
+impl<T:Float> Mul for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn mul(self, rhs : Dual<T>) -> Dual<T> {
+        Dual::new(self.value * rhs.value,
+            self.value * rhs.deriv + self.deriv * rhs.value)
+    }
+}
+
+impl<T:Float> Div for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn div(self, rhs : Dual<T>) -> Dual<T> {
+        let q = self.value / rhs.value;
+        Dual::new(q, (self.deriv - q * rhs.deriv) / rhs.value)
+    }
+}
+
+impl<T:Float> Neg for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn neg(self) -> Dual<T> {
+        Dual::new(-self.value, -self.deriv)
+    }
+}
+
+impl<T:Float> Add<T> for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn add(self, rhs : T) -> Dual<T> {
+        Dual::new(self.value + rhs, self.deriv)
+    }
+}
+
+impl<T:Float> Sub<T> for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn sub(self, rhs : T) -> Dual<T> {
+        Dual::new(self.value - rhs, self.deriv)
+    }
+}
+
+impl<T:Float> Mul<T> for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn mul(self, rhs : T) -> Dual<T> {
+        Dual::new(self.value * rhs, self.deriv * rhs)
+    }
+}
+
+impl<T:Float> Div<T> for Dual<T> {
+    type Output = Dual<T>;
+    #[inline]
+    fn div(self, rhs : T) -> Dual<T> {
+        Dual::new(self.value / rhs, self.deriv / rhs)
+    }
+}
+
+impl<T:Float> Zero for Dual<T> {
+    #[inline]
+    fn zero() -> Dual<T> {
+        Dual::constant(T::zero())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.value.is_zero() && self.deriv.is_zero()
+    }
+}
+
+impl<T:Float> One for Dual<T> {
+    #[inline]
+    fn one() -> Dual<T> {
+        Dual::constant(T::one())
+    }
+}
+
+
+/******************************************************
+ *
+ *   Algebraic structure
+ *
+ *******************************************************/
+
+impl<T:Float> Division for Dual<T> {
+
+}
+
+impl<T:Float> Recip for Dual<T> {
+
+    type Output = Self;
+    #[inline]
+    fn recip(self) -> Dual<T> {
+        let r = self.value.recip();
+        Dual::new(r, -self.deriv * r * r)
+    }
+}
+
+impl<T:Float + Debug> SemiGroupAddPartial for Dual<T> {
+
+}
+
+impl<T:Float + Debug> SemiGroupMulPartial for Dual<T> {
+
+}
+
+impl<T:Float + Debug> CommutativeMonoidAddPartial for Dual<T> {
+
+}
+
+impl<T:Float + Debug> CommutativeMonoidMulPartial for Dual<T> {
+
+}
+
+impl<T:Float + Debug> QuasiGroupAddPartial for Dual<T> {
+
+}
+
+impl<T:Float + Debug> CommutativeGroupAddPartial for Dual<T> {
+
+}
+
+impl<T:Float + Debug> IntegralDomainPartial for Dual<T> {
+
+}
+
+impl<T:Float + Debug> FieldPartial for Dual<T> {
+
+}
+
+
+/******************************************************
+ *
+ *   Derivatives
+ *
+ *******************************************************/
+
+/// Returns the value and the derivative of a scalar function at x
+pub fn derivative<T:Float, F>(f : F, x : T) -> (T, T)
+    where F : Fn(Dual<T>) -> Dual<T> {
+    let y = f(Dual::variable(x));
+    (y.value, y.deriv)
+}
+
+/// Builds a matrix of dual numbers from matrices of values
+/// and derivatives of equal size
+pub fn dual_matrix(values : &MatrixF64, derivs : &MatrixF64) -> SRResult<Matrix<Dual64>> {
+    if values.size() != derivs.size() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let (rows, cols) = values.size();
+    let mut m = Matrix::zeros(rows, cols);
+    for c in 0..cols {
+        for r in 0..rows {
+            m.set(r, c, Dual::new(values.get(r, c).unwrap(), derivs.get(r, c).unwrap()));
+        }
+    }
+    Ok(m)
+}
+
+/// Splits a matrix of dual numbers into its values and derivatives
+pub fn dual_parts(m : &Matrix<Dual64>) -> (MatrixF64, MatrixF64) {
+    let (rows, cols) = m.size();
+    let mut values = Matrix::zeros(rows, cols);
+    let mut derivs = Matrix::zeros(rows, cols);
+    for c in 0..cols {
+        for r in 0..rows {
+            let d = m.get(r, c).unwrap();
+            values.set(r, c, d.value);
+            derivs.set(r, c, d.deriv);
+        }
+    }
+    (values, derivs)
+}
+
+/// Returns the value of f at the vector x and the derivative of f
+/// along the direction v
+pub fn directional_derivative<F>(f : F, x : &MatrixF64, v : &MatrixF64) -> SRResult<(f64, f64)>
+    where F : Fn(&[Dual64]) -> Dual64 {
+    if !x.is_col() || x.size() != v.size() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let n = x.num_rows();
+    let seeds : Vec<Dual64> = (0..n).map(|i| Dual::new(x[i], v[i])).collect();
+    let y = f(&seeds);
+    Ok((y.value, y.deriv))
+}
+
+/// Computes the m x n Jacobian of a function of the vector x
+/// with n evaluations of f, one per input
+pub fn forward_jacobian<F>(f : F, x : &MatrixF64) -> SRResult<MatrixF64>
+    where F : Fn(&[Dual64]) -> Vec<Dual64> {
+    if !x.is_col() {
+        return Err(SRError::IsNotAColVector);
+    }
+    let n = x.num_rows();
+    let mut seeds : Vec<Dual64> = (0..n).map(|i| Dual::constant(x[i])).collect();
+    let mut jacobian = Matrix::zeros(0, 0);
+    for j in 0..n {
+        seeds[j].deriv = 1.;
+        let y = f(&seeds);
+        seeds[j].deriv = 0.;
+        if j == 0 {
+            jacobian = Matrix::zeros(y.len(), n);
+        }
+        else if y.len() != jacobian.num_rows() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        for (i, yi) in y.iter().enumerate() {
+            jacobian.set(i, j, yi.deriv);
+        }
+    }
+    Ok(jacobian)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use sralgebra::*;
+
+    #[test]
+    fn test_dual_traits(){
+        let d = Dual64::variable(1.);
+        is_magma_base(&d);
+        is_magma_add_partial(&d);
+        is_magma_mul_partial(&d);
+        is_quasigroup_add_partial(&d);
+        is_semigroup_add_partial(&d);
+        is_semigroup_mul_partial(&d);
+        is_loop_add_partial(&d);
+        is_monoid_add_partial(&d);
+        is_monoid_mul_partial(&d);
+        is_group_add_partial(&d);
+        is_commutative_group_add_partial(&d);
+        is_ring_partial(&d);
+        is_commutative_ring_partial(&d);
+        is_integral_domain_partial(&d);
+        is_field_partial(&d);
+    }
+
+    #[test]
+    fn test_dual_arithmetic(){
+        let x = Dual::new(2., 1.);
+        let y = Dual::new(3., -1.);
+        assert_eq!(x + y, Dual::new(5., 0.));
+        assert_eq!(x - y, Dual::new(-1., 2.));
+        assert_eq!(x * y, Dual::new(6., 1.));
+        let q = x / y;
+        assert_eq!(q.value, 2. / 3.);
+        assert!((q.deriv - 5. / 9.).abs() < 1e-15);
+        assert_eq!(-x, Dual::new(-2., -1.));
+        assert_eq!(x.recip(), Dual::new(0.5, -0.25));
+        assert_eq!(x * 3. + 1., Dual::new(7., 3.));
+        assert!(Dual64::zero().is_zero());
+        assert_eq!(Dual64::one() * x, x);
+    }
+
+    #[test]
+    fn test_derivatives(){
+        let x = 0.7f64;
+        type Case = (fn(Dual64) -> Dual64, f64);
+        let cases : Vec<Case> = vec![
+            (|x| x.sin() * x.exp(), x.exp() * (x.sin() + x.cos())),
+            (|x| x.ln() / x, (1. - x.ln()) / (x * x)),
+            (|x| x.sqrt().powi(3), 1.5 * x.sqrt()),
+            (|x| x.tanh().atan(), (1. - x.tanh().powi(2)) / (1. + x.tanh().powi(2))),
+            (|x| x.powf(2.5) - x.tan(), 2.5 * x.powf(1.5) - 1. / x.cos().powi(2)),
+            (|x| x.pow(x), x.powf(x) * (x.ln() + 1.)),
+            (|x| (x * x - 1.).abs() + x.cosh() * x.sinh(), -2. * x + (2. * x).cosh()),
+        ];
+        for &(f, expected) in cases.iter() {
+            let (_, d) = derivative(f, x);
+            assert!((d - expected).abs() < 1e-14, "{} != {}", d, expected);
+        }
+        let (v, d) = derivative(|x : Dual<f32>| x * x.cos(), 1f32);
+        assert_eq!(v, 1f32.cos());
+        assert!((d - (1f32.cos() - 1f32.sin())).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dual_matrix(){
+        // d/dt (A(t) x(t)) = A' x + A x'
+        let a = matrix_rw_f64(2, 2, &[1., 2., 3., 4.]);
+        let da = matrix_rw_f64(2, 2, &[0., 1., 1., 0.]);
+        let x = vector_f64(&[1., -1.]);
+        let dx = vector_f64(&[2., 0.]);
+        let ad = dual_matrix(&a, &da).unwrap();
+        let xd = dual_matrix(&x, &dx).unwrap();
+        let (y, dy) = dual_parts(&(&ad * &xd));
+        assert_eq!(y, &a * &x);
+        assert_eq!(dy, &(&da * &x) + &(&a * &dx));
+        let i : Matrix<Dual64> = Matrix::identity(2, 2);
+        assert_eq!(&i * &ad, ad);
+        assert!(dual_matrix(&a, &x).is_err());
+    }
+
+    #[test]
+    fn test_forward_jacobian(){
+        let x = vector_f64(&[1., 2., 3.]);
+        let (v, d) = directional_derivative(|x| x[0] * x[1] * x[2], &x, &vector_f64(&[1., 1., 1.])).unwrap();
+        assert_eq!(v, 6.);
+        assert_eq!(d, 11.);
+        let j = forward_jacobian(|x| vec![x[0] * x[1], x[1].sin() + x[2], x[2].exp()], &x).unwrap();
+        let expected = matrix_rw_f64(3, 3, &[
+            2., 1., 0.,
+            0., 2f64.cos(), 1.,
+            0., 0., 3f64.exp()]);
+        assert_eq!(j, expected);
+        assert!(forward_jacobian(|x| vec![x[0]], &matrix_rw_f64(1, 2, &[1., 2.])).is_err());
+    }
+}
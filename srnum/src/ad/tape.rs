@@ -0,0 +1,471 @@
+#![doc="Reverse mode automatic differentiation on a tape
+
+Every operation on a ``Var`` appends a node to its ``Tape``
+holding the local partial derivatives with respect to (at
+most two) operands. A single backward sweep from an output y
+over the tape accumulates the adjoints dy/dv of all nodes v
+by the chain rule, so the gradient of a scalar function of n
+inputs costs a small multiple of one function evaluation,
+independent of n. A Jacobian of m outputs needs m sweeps over
+the same tape.
+
+```
+extern crate srnum;
+extern crate srmatrix;
+use srnum::api::*;
+use srmatrix::api::*;
+
+let x = vector_f64(&[1., 2.]);
+let g = gradient(|x| x[0] * x[0] * x[1] + x[1].sin(), &x).unwrap();
+assert_eq!(g, vector_f64(&[4., 1. + 2f64.cos()]));
+```
+"]
+
+
+// std imports
+use std::cell::RefCell;
+use std::ptr;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+// srmatrix imports
+use srmatrix::api::*;
+
+
+/// A recorded operation with its local partial derivatives
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    /// Indices of the operands
+    parents : [usize; 2],
+    /// Partial derivatives with respect to the operands
+    weights : [f64; 2]
+}
+
+
+/// Records the operations of a computation for a backward sweep
+#[derive(Debug, Default)]
+pub struct Tape {
+    nodes : RefCell<Vec<Node>>
+}
+
+/// A variable on a tape
+#[derive(Debug, Clone, Copy)]
+pub struct Var<'a> {
+    tape : &'a Tape,
+    index : usize,
+    value : f64
+}
+
+/// The adjoints of all variables on a tape with respect to one output
+#[derive(Debug, Clone)]
+pub struct Adjoints {
+    values : Vec<f64>
+}
+
+
+impl Tape {
+
+    /// Creates an empty tape
+    pub fn new() -> Tape {
+        Tape {
+            nodes : RefCell::new(Vec::new())
+        }
+    }
+
+    /// Number of variables recorded so far
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    /// Returns true if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends a node and returns the variable for it
+    fn push<'a>(&'a self, value : f64, parents : [usize; 2], weights : [f64; 2]) -> Var<'a> {
+        let mut nodes = self.nodes.borrow_mut();
+        let index = nodes.len();
+        nodes.push(Node {
+            parents : parents,
+            weights : weights
+        });
+        Var {
+            tape : self,
+            index : index,
+            value : value
+        }
+    }
+
+    /// Creates an independent variable
+    pub fn var<'a>(&'a self, value : f64) -> Var<'a> {
+        let index = self.len();
+        self.push(value, [index, index], [0., 0.])
+    }
+
+    /// Creates independent variables for the entries of a column vector
+    pub fn vars<'a>(&'a self, x : &MatrixF64) -> SRResult<Vec<Var<'a>>> {
+        if !x.is_col() {
+            return Err(SRError::IsNotAColVector);
+        }
+        Ok((0..x.num_rows()).map(|i| self.var(x[i])).collect())
+    }
+}
+
+
+impl<'a> Var<'a> {
+
+    /// The value of the variable
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// A result of a unary operation with the derivative d
+    fn unary(self, value : f64, d : f64) -> Var<'a> {
+        self.tape.push(value, [self.index, self.index], [d, 0.])
+    }
+
+    /// A result of a binary operation with the partial derivatives da, db
+    fn binary(self, other : Var<'a>, value : f64, da : f64, db : f64) -> Var<'a> {
+        assert!(ptr::eq(self.tape, other.tape),
+            "Variables must belong to the same tape");
+        self.tape.push(value, [self.index, other.index], [da, db])
+    }
+
+    /// Computes the adjoints of all variables recorded before self
+    /// by a backward sweep
+    pub fn backward(&self) -> Adjoints {
+        let nodes = self.tape.nodes.borrow();
+        let mut values = vec![0.; self.index + 1];
+        values[self.index] = 1.;
+        for k in (0..(self.index + 1)).rev() {
+            let a = values[k];
+            if a == 0. {
+                continue;
+            }
+            let node = &nodes[k];
+            for p in 0..2 {
+                values[node.parents[p]] += node.weights[p] * a;
+            }
+        }
+        Adjoints {
+            values : values
+        }
+    }
+
+    /// Absolute value. The derivative at 0 is taken as 0.
+    pub fn abs(self) -> Var<'a> {
+        let s = if self.value > 0. { 1. } else if self.value < 0. { -1. } else { 0. };
+        self.unary(self.value.abs(), s)
+    }
+
+    /// Square root
+    pub fn sqrt(self) -> Var<'a> {
+        let r = self.value.sqrt();
+        self.unary(r, 0.5 / r)
+    }
+
+    /// Exponential function
+    pub fn exp(self) -> Var<'a> {
+        let e = self.value.exp();
+        self.unary(e, e)
+    }
+
+    /// Natural logarithm
+    pub fn ln(self) -> Var<'a> {
+        self.unary(self.value.ln(), 1. / self.value)
+    }
+
+    /// Integer power
+    pub fn powi(self, n : i32) -> Var<'a> {
+        let d = if n == 0 { 0. } else { n as f64 * self.value.powi(n - 1) };
+        self.unary(self.value.powi(n), d)
+    }
+
+    /// Real power
+    pub fn powf(self, p : f64) -> Var<'a> {
+        let d = if p == 0. { 0. } else { p * self.value.powf(p - 1.) };
+        self.unary(self.value.powf(p), d)
+    }
+
+    /// Sine
+    pub fn sin(self) -> Var<'a> {
+        self.unary(self.value.sin(), self.value.cos())
+    }
+
+    /// Cosine
+    pub fn cos(self) -> Var<'a> {
+        self.unary(self.value.cos(), -self.value.sin())
+    }
+
+    /// Tangent
+    pub fn tan(self) -> Var<'a> {
+        let t = self.value.tan();
+        self.unary(t, 1. + t * t)
+    }
+
+    /// Inverse tangent
+    pub fn atan(self) -> Var<'a> {
+        self.unary(self.value.atan(), 1. / (1. + self.value * self.value))
+    }
+
+    /// Hyperbolic sine
+    pub fn sinh(self) -> Var<'a> {
+        self.unary(self.value.sinh(), self.value.cosh())
+    }
+
+    /// Hyperbolic cosine
+    pub fn cosh(self) -> Var<'a> {
+        self.unary(self.value.cosh(), self.value.sinh())
+    }
+
+    /// Hyperbolic tangent
+    pub fn tanh(self) -> Var<'a> {
+        let t = self.value.tanh();
+        self.unary(t, 1. - t * t)
+    }
+}
+
+
+impl Adjoints {
+
+    /// The derivative of the output with respect to v
+    pub fn wrt(&self, v : &Var) -> f64 {
+        self.values.get(v.index).cloned().unwrap_or(0.)
+    }
+
+    /// The derivatives of the output with respect to the given variables
+    /// as a column vector
+    pub fn wrt_all(&self, vars : &[Var]) -> MatrixF64 {
+        Matrix::from_iter_cw(vars.len(), 1, vars.iter().map(|v| self.wrt(v)))
+    }
+}
+
+
+/******************************************************
+ *
+ *   Arithmetic
+ *
+ *******************************************************/
+
+impl<'a> Add for Var<'a> {
+    type Output = Var<'a>;
+    fn add(self, rhs : Var<'a>) -> Var<'a> {
+        self.binary(rhs, self.value + rhs.value, 1., 1.)
+    }
+}
+
+impl<'a> Sub for Var<'a> {
+    type Output = Var<'a>;
+    fn sub(self, rhs : Var<'a>) -> Var<'a> {
+        self.binary(rhs, self.value - rhs.value, 1., -1.)
+    }
+}
+
+impl<'a> Mul for Var<'a> {
+    type Output = Var<'a>;
+    fn mul(self, rhs : Var<'a>) -> Var<'a> {
+        self.binary(rhs, self.value * rhs.value, rhs.value, self.value)
+    }
+}
+
+impl<'a> Div for Var<'a> {
+    type Output = Var<'a>;
+    fn div(self, rhs : Var<'a>) -> Var<'a> {
+        let q = self.value / rhs.value;
+        self.binary(rhs, q, 1. / rhs.value, -q / rhs.value)
+    }
+}
+
+impl<'a> Neg for Var<'a> {
+    type Output = Var<'a>;
+    fn neg(self) -> Var<'a> {
+        self.unary(-self.value, -1.)
+    }
+}
+
+impl<'a> Add<f64> for Var<'a> {
+    type Output = Var<'a>;
+    fn add(self, rhs : f64) -> Var<'a> {
+        self.unary(self.value + rhs, 1.)
+    }
+}
+
+impl<'a> Sub<f64> for Var<'a> {
+    type Output = Var<'a>;
+    fn sub(self, rhs : f64) -> Var<'a> {
+        self.unary(self.value - rhs, 1.)
+    }
+}
+
+impl<'a> Mul<f64> for Var<'a> {
+    type Output = Var<'a>;
+    fn mul(self, rhs : f64) -> Var<'a> {
+        self.unary(self.value * rhs, rhs)
+    }
+}
+
+impl<'a> Div<f64> for Var<'a> {
+    type Output = Var<'a>;
+    fn div(self, rhs : f64) -> Var<'a> {
+        self.unary(self.value / rhs, 1. / rhs)
+    }
+}
+
+impl<'a> Add<Var<'a>> for f64 {
+    type Output = Var<'a>;
+    fn add(self, rhs : Var<'a>) -> Var<'a> {
+        rhs.unary(self + rhs.value, 1.)
+    }
+}
+
+impl<'a> Sub<Var<'a>> for f64 {
+    type Output = Var<'a>;
+    fn sub(self, rhs : Var<'a>) -> Var<'a> {
+        rhs.unary(self - rhs.value, -1.)
+    }
+}
+
+impl<'a> Mul<Var<'a>> for f64 {
+    type Output = Var<'a>;
+    fn mul(self, rhs : Var<'a>) -> Var<'a> {
+        rhs.unary(self * rhs.value, self)
+    }
+}
+
+impl<'a> Div<Var<'a>> for f64 {
+    type Output = Var<'a>;
+    fn div(self, rhs : Var<'a>) -> Var<'a> {
+        let q = self / rhs.value;
+        rhs.unary(q, -q / rhs.value)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Gradients and Jacobians
+ *
+ *******************************************************/
+
+/// Returns the value and the gradient of a scalar function
+/// of the column vector x
+pub fn value_and_gradient<F>(f : F, x : &MatrixF64) -> SRResult<(f64, MatrixF64)>
+    where F : for<'a> Fn(&[Var<'a>]) -> Var<'a> {
+    let tape = Tape::new();
+    let vars = tape.vars(x)?;
+    let y = f(&vars);
+    Ok((y.value, y.backward().wrt_all(&vars)))
+}
+
+/// Returns the gradient of a scalar function of the column vector x
+pub fn gradient<F>(f : F, x : &MatrixF64) -> SRResult<MatrixF64>
+    where F : for<'a> Fn(&[Var<'a>]) -> Var<'a> {
+    value_and_gradient(f, x).map(|(_, g)| g)
+}
+
+/// Returns the m x n Jacobian of a vector function of the column
+/// vector x with one backward sweep per output
+pub fn jacobian<F>(f : F, x : &MatrixF64) -> SRResult<MatrixF64>
+    where F : for<'a> Fn(&[Var<'a>]) -> Vec<Var<'a>> {
+    let tape = Tape::new();
+    let vars = tape.vars(x)?;
+    let y = f(&vars);
+    let n = vars.len();
+    let mut j = Matrix::zeros(y.len(), n);
+    for (i, yi) in y.iter().enumerate() {
+        let adjoints = yi.backward();
+        for (k, v) in vars.iter().enumerate() {
+            j.set(i, k, adjoints.wrt(v));
+        }
+    }
+    Ok(j)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_tape_basic(){
+        let tape = Tape::new();
+        assert!(tape.is_empty());
+        let x = tape.var(3.);
+        let y = tape.var(-2.);
+        let z = x * y + x.sin() - 2. / y;
+        assert_eq!(tape.len(), 7);
+        assert_eq!(z.value(), -6. + 3f64.sin() + 1.);
+        let a = z.backward();
+        assert_eq!(a.wrt(&x), -2. + 3f64.cos());
+        assert_eq!(a.wrt(&y), 3. + 0.5);
+        // Variables created after z do not influence it
+        let w = tape.var(1.);
+        assert_eq!(a.wrt(&w), 0.);
+        // Reused subexpressions accumulate
+        let s = x * x * x;
+        assert_eq!(s.backward().wrt(&x), 27.);
+    }
+
+    fn rosenbrock<'a>(x : &[Var<'a>]) -> Var<'a> {
+        (0..(x.len() - 1)).fold(x[0] * 0., |s, i| {
+            let a = x[i + 1] - x[i] * x[i];
+            let b = 1. - x[i];
+            s + 100. * a * a + b * b
+        })
+    }
+
+    #[test]
+    fn test_rosenbrock_gradient(){
+        let x = vector_f64(&[-1.2, 1., 0.5, 2.]);
+        let (v, g) = value_and_gradient(rosenbrock, &x).unwrap();
+        let n = 4;
+        let mut expected = vec![0.; n];
+        let mut value = 0.;
+        for i in 0..(n - 1) {
+            let a = x[i + 1] - x[i] * x[i];
+            value += 100. * a * a + (1. - x[i]) * (1. - x[i]);
+            expected[i] += -400. * x[i] * a - 2. * (1. - x[i]);
+            expected[i + 1] += 200. * a;
+        }
+        assert!((v - value).abs() < 1e-12);
+        assert!((&g - &vector_f64(&expected)).max_abs_scalar_value() < 1e-12);
+        assert!(gradient(rosenbrock, &matrix_rw_f64(1, 2, &[1., 2.])).is_err());
+    }
+
+    #[test]
+    fn test_elementary_gradients(){
+        let x = vector_f64(&[0.4, 1.3]);
+        let g = gradient(|x| (x[0].exp() * x[1].ln()).sqrt() + x[0].tanh().powi(3), &x).unwrap();
+        let (a, b) = (x[0], x[1]);
+        let r = (a.exp() * b.ln()).sqrt();
+        let expected = vector_f64(&[
+            0.5 * r + 3. * a.tanh().powi(2) * (1. - a.tanh().powi(2)),
+            a.exp() / (2. * r * b)]);
+        assert!((&g - &expected).max_abs_scalar_value() < 1e-14);
+        let g = gradient(|x| x[0].atan() * x[1].cosh() - (x[1] / x[0]).tan() + (-x[0]).abs(), &x).unwrap();
+        let t = (b / a).tan();
+        let expected = vector_f64(&[
+            b.cosh() / (1. + a * a) + (1. + t * t) * b / (a * a) + 1.,
+            a.atan() * b.sinh() - (1. + t * t) / a]);
+        assert!((&g - &expected).max_abs_scalar_value() < 1e-13);
+    }
+
+    #[test]
+    fn test_jacobian(){
+        // Polar to Cartesian coordinates
+        let x = vector_f64(&[2., 0.3]);
+        let j = jacobian(|x| vec![x[0] * x[1].cos(), x[0] * x[1].sin(), x[0].powf(1.5)], &x).unwrap();
+        let expected = matrix_rw_f64(3, 2, &[
+            0.3f64.cos(), -2. * 0.3f64.sin(),
+            0.3f64.sin(), 2. * 0.3f64.cos(),
+            1.5 * 2f64.sqrt(), 0.]);
+        assert!((&j - &expected).max_abs_scalar_value() < 1e-15);
+    }
+}
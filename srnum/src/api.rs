@@ -0,0 +1,39 @@
+// Numerical methods library
+
+pub use ad::dual::*;
+pub use ad::tape::*;
+pub use diff::options::*;
+pub use diff::finite::*;
+pub use diff::richardson::*;
+pub use diff::multivariate::*;
+pub use diff::complex_step::*;
+pub use quad::options::*;
+pub use quad::sampled::*;
+pub use quad::gauss::*;
+pub use quad::kronrod::*;
+pub use quad::tanh_sinh::*;
+pub use quad::nested::*;
+pub use ode::system::*;
+pub use ode::options::*;
+pub use ode::dense::*;
+pub use ode::stepper::*;
+pub use ode::explicit::*;
+pub use ode::rosenbrock::*;
+pub use ode::radau::*;
+pub use ode::bdf::*;
+pub use ode::solve::*;
+pub use interp::traits::*;
+pub use interp::piecewise::*;
+pub use interp::cubic::*;
+pub use interp::bspline::*;
+pub use interp::grid::*;
+pub use interp::rbf::*;
+pub use poly::polynomial::*;
+pub use poly::fit::*;
+pub use poly::orthogonal::*;
+pub use special::gamma::*;
+pub use special::beta::*;
+pub use special::erf::*;
+pub use special::bessel::*;
+pub use special::elliptic::*;
+pub use special::zeta::*;
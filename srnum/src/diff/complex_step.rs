@@ -0,0 +1,184 @@
+#![doc="Complex step differentiation
+
+For a function f that is real on the real axis and analytic near x,
+
+f'(x) = Im f(x + i h) / h + O(h^2)
+
+involves no subtraction, so the step can be taken as small as
+1e-20 and the derivative is accurate to machine precision. The
+function must be written for complex arguments and must not use
+operations like ``abs`` or comparisons that break analyticity.
+
+Second derivatives mix a complex step with a central difference:
+
+d^2 f / dx_i dx_j ~ Im (f(x + i h e_i + d e_j) - f(x + i h e_i - d e_j)) / (2 h d)
+"]
+
+
+// std imports
+
+// external imports
+use num::complex::Complex64;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use diff::finite::step_size;
+
+
+/// The default complex step
+pub const COMPLEX_STEP : f64 = 1e-20;
+
+
+/// Returns a complex vector holding the column vector x
+fn complexify(x : &MatrixF64) -> SRResult<Vec<Complex64>> {
+    if !x.is_col() {
+        return Err(SRError::IsNotAColVector);
+    }
+    if x.is_empty() {
+        return Err(SRError::EmptyMatrix);
+    }
+    Ok((0..x.num_rows()).map(|i| Complex64::new(x[i], 0.)).collect())
+}
+
+
+/// Returns the derivative of f at x with the complex step
+/// ``COMPLEX_STEP`` scaled by max(1, |x|)
+pub fn complex_step_derivative<F>(f : F, x : f64) -> f64
+    where F : Fn(Complex64) -> Complex64 {
+    let h = COMPLEX_STEP * x.abs().max(1.);
+    f(Complex64::new(x, h)).im / h
+}
+
+
+/// Returns the gradient of a scalar function of the column vector
+/// x with one complex evaluation per coordinate
+pub fn complex_step_gradient<F>(f : F, x : &MatrixF64) -> SRResult<MatrixF64>
+    where F : Fn(&[Complex64]) -> Complex64 {
+    let mut z = complexify(x)?;
+    let n = z.len();
+    let mut gradient = Matrix::zeros(n, 1);
+    for j in 0..n {
+        let h = COMPLEX_STEP * x[j].abs().max(1.);
+        z[j].im = h;
+        gradient.set(j, 0, f(&z).im / h);
+        z[j].im = 0.;
+    }
+    Ok(gradient)
+}
+
+
+/// Returns the m x n Jacobian of a vector function of the column
+/// vector x with one complex evaluation per coordinate
+pub fn complex_step_jacobian<F>(f : F, x : &MatrixF64) -> SRResult<MatrixF64>
+    where F : Fn(&[Complex64]) -> Vec<Complex64> {
+    let mut z = complexify(x)?;
+    let n = z.len();
+    let mut jacobian = Matrix::zeros(0, 0);
+    for j in 0..n {
+        let h = COMPLEX_STEP * x[j].abs().max(1.);
+        z[j].im = h;
+        let y = f(&z);
+        z[j].im = 0.;
+        if j == 0 {
+            jacobian = Matrix::zeros(y.len(), n);
+        }
+        else if y.len() != jacobian.num_rows() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        for (i, yi) in y.iter().enumerate() {
+            jacobian.set(i, j, yi.im / h);
+        }
+    }
+    Ok(jacobian)
+}
+
+
+/// Returns the Hessian of a scalar function of the column vector x
+/// by complex steps combined with central differences
+pub fn complex_step_hessian<F>(f : F, x : &MatrixF64) -> SRResult<MatrixF64>
+    where F : Fn(&[Complex64]) -> Complex64 {
+    let mut z = complexify(x)?;
+    let n = z.len();
+    let mut hessian = Matrix::zeros(n, n);
+    for i in 0..n {
+        let h = COMPLEX_STEP * x[i].abs().max(1.);
+        z[i].im = h;
+        for j in 0..=i {
+            let d = step_size(x[j], 1, 2);
+            z[j].re = x[j] + d;
+            let fp = f(&z).im;
+            z[j].re = x[j] - d;
+            let fm = f(&z).im;
+            z[j].re = x[j];
+            let hij = (fp - fm) / (2. * h * d);
+            hessian.set(i, j, hij);
+            hessian.set(j, i, hij);
+        }
+        z[i].im = 0.;
+    }
+    Ok(hessian)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_complex_step_derivative(){
+        // The classic test function of Squire and Trapp
+        let f = |x : Complex64| x.exp() / (x.sin().powf(3.) + x.cos().powf(3.)).sqrt();
+        let d = complex_step_derivative(f, 1.5);
+        assert!((d - 4.053_427_893_898_621).abs() < 1e-14, "{}", d);
+        let d = complex_step_derivative(|x : Complex64| x * x * x, -1e5);
+        assert!((d - 3e10).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_complex_step_gradient_jacobian(){
+        let rosenbrock = |x : &[Complex64]| {
+            let a = x[1] - x[0] * x[0];
+            let b = -x[0] + 1.;
+            a * a * 100. + b * b
+        };
+        let x = Matrix::from_slice_cw(2, 1, &[-1.2, 1.]);
+        let g = complex_step_gradient(rosenbrock, &x).unwrap();
+        assert!((g[0] + 215.6).abs() < 1e-12 && (g[1] + 88.).abs() < 1e-13);
+        let f = |x : &[Complex64]| vec![x[0] * x[1].cos(), x[0] * x[1].sin(), x[0]];
+        let x = Matrix::from_slice_cw(2, 1, &[2., 0.5]);
+        let j = complex_step_jacobian(f, &x).unwrap();
+        assert_eq!(j.size(), (3, 2));
+        let expected = [[0.5f64.cos(), -2. * 0.5f64.sin()], [0.5f64.sin(), 2. * 0.5f64.cos()], [1., 0.]];
+        for r in 0..3 {
+            for c in 0..2 {
+                assert!((j.get(r, c).unwrap() - expected[r][c]).abs() < 1e-15);
+            }
+        }
+        assert!(complex_step_gradient(rosenbrock, &Matrix::zeros(1, 2)).is_err());
+    }
+
+    #[test]
+    fn test_complex_step_hessian(){
+        let rosenbrock = |x : &[Complex64]| {
+            let a = x[1] - x[0] * x[0];
+            let b = -x[0] + 1.;
+            a * a * 100. + b * b
+        };
+        let x = Matrix::from_slice_cw(2, 1, &[-1.2, 1.]);
+        let h = complex_step_hessian(rosenbrock, &x).unwrap();
+        let expected = [[1330., 480.], [480., 200.]];
+        for r in 0..2 {
+            for c in 0..2 {
+                assert!((h.get(r, c).unwrap() - expected[r][c]).abs() < 1e-6, "{} {} {}", r, c, h.get(r, c).unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+#![doc="Finite differences of arbitrary order
+
+The derivative of order d is approximated by
+
+f^(d)(x) ~ h^-d sum_k w_k f(x + o_k h)
+
+on a stencil of offsets o_k. The weights are computed by the
+algorithm of Fornberg (Generation of finite difference formulas
+on arbitrarily spaced grids, Math. Comp. 51, 1988). A stencil of
+n points has truncation error O(h^(n - d)) in general and
+O(h^(n - d + 1)) for symmetric stencils.
+
+The automatic step balances the truncation error O(h^p) against
+the rounding error O(eps / h^d), which gives
+
+h = eps^(1 / (p + d)) max(1, |x|)
+
+for accuracy p. The step is rounded so that x + h is exact.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use diff::options::{DifferenceScheme, DiffOptions};
+
+
+/// Returns the weights of the derivative of the given order at 0
+/// on the stencil of the given offsets
+pub fn difference_weights(offsets : &[f64], order : usize) -> SRResult<Vec<f64>> {
+    let n = offsets.len();
+    if n <= order || offsets.iter().any(|o| !o.is_finite()) {
+        return Err(SRError::InvalidArgument);
+    }
+    // c[j][k] is the weight of node j for the derivative of order k
+    let mut c = vec![vec![0.; order + 1]; n];
+    c[0][0] = 1.;
+    let mut c1 = 1.;
+    let mut c4 = offsets[0];
+    for i in 1..n {
+        let mn = i.min(order);
+        let mut c2 = 1.;
+        let c5 = c4;
+        c4 = offsets[i];
+        for j in 0..i {
+            let c3 = offsets[i] - offsets[j];
+            if c3 == 0. {
+                return Err(SRError::InvalidArgument);
+            }
+            c2 *= c3;
+            if j == i - 1 {
+                for k in (1..=mn).rev() {
+                    c[i][k] = c1 * (k as f64 * c[i - 1][k - 1] - c5 * c[i - 1][k]) / c2;
+                }
+                c[i][0] = -c1 * c5 * c[i - 1][0] / c2;
+            }
+            for k in (1..=mn).rev() {
+                c[j][k] = (c4 * c[j][k] - k as f64 * c[j][k - 1]) / c3;
+            }
+            c[j][0] = c4 * c[j][0] / c3;
+        }
+        c1 = c2;
+    }
+    Ok(c.iter().map(|row| row[order]).collect())
+}
+
+
+/// Returns the offsets of the stencil of a derivative of the
+/// given order with truncation error O(h^accuracy)
+pub fn stencil(scheme : DifferenceScheme, order : usize, accuracy : usize) -> Vec<f64> {
+    match scheme {
+        DifferenceScheme::Forward => (0..order + accuracy).map(|k| k as f64).collect(),
+        DifferenceScheme::Backward => (0..order + accuracy).map(|k| -(k as f64)).collect(),
+        DifferenceScheme::Central => {
+            let m = order.saturating_sub(1) / 2 + accuracy / 2;
+            (0..2 * m + 1).map(|k| k as f64 - m as f64).collect()
+        }
+    }
+}
+
+
+/// Returns the automatic step at x for a derivative of the given
+/// order with truncation error O(h^accuracy)
+pub fn step_size(x : f64, order : usize, accuracy : usize) -> f64 {
+    let h = f64::EPSILON.powf(1. / (order + accuracy) as f64) * x.abs().max(1.);
+    (x + h) - x
+}
+
+
+/// Evaluates the stencil sum with step h
+pub fn apply_stencil<F>(f : &F, x : f64, h : f64, order : usize, offsets : &[f64], weights : &[f64]) -> f64
+    where F : Fn(f64) -> f64 {
+    let sum = offsets.iter().zip(weights.iter())
+        .filter(|&(_, &w)| w != 0.)
+        .fold(0., |s, (&o, &w)| s + w * f(x + o * h));
+    sum / h.powi(order as i32)
+}
+
+
+/// Approximates the derivative of f of the given order at x by
+/// finite differences
+pub fn finite_derivative<F>(f : F, x : f64, order : usize, options : &DiffOptions) -> SRResult<f64>
+    where F : Fn(f64) -> f64 {
+    options.validate()?;
+    if !x.is_finite() {
+        return Err(SRError::InvalidArgument);
+    }
+    let offsets = stencil(options.scheme, order, options.accuracy);
+    let weights = difference_weights(&offsets, order)?;
+    let h = options.step.unwrap_or_else(|| step_size(x, order, options.accuracy));
+    Ok(apply_stencil(&f, x, h, order, &offsets, &weights))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_weights(){
+        let w = difference_weights(&[-1., 0., 1.], 1).unwrap();
+        assert_eq!(w, vec![-0.5, 0., 0.5]);
+        let w = difference_weights(&[-1., 0., 1.], 2).unwrap();
+        assert_eq!(w, vec![1., -2., 1.]);
+        let w = difference_weights(&[-2., -1., 0., 1., 2.], 1).unwrap();
+        let expected = [1. / 12., -2. / 3., 0., 2. / 3., -1. / 12.];
+        for (a, b) in w.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-15);
+        }
+        let w = difference_weights(&[0., 1., 2.], 1).unwrap();
+        assert_eq!(w, vec![-1.5, 2., -0.5]);
+        // Weights of derivatives of order d annihilate polynomials
+        // of degree below d
+        let offsets = [-0.5, 0., 1., 2.5, 3.];
+        let w = difference_weights(&offsets, 3).unwrap();
+        for p in 0..5 {
+            let s : f64 = offsets.iter().zip(w.iter()).map(|(o, w)| w * o.powi(p)).sum();
+            let expected = if p == 3 { 6. } else { 0. };
+            assert!((s - expected).abs() < 1e-12, "{} {}", p, s);
+        }
+        assert!(difference_weights(&[0., 1.], 2).is_err());
+        assert!(difference_weights(&[0., 1., 1.], 1).is_err());
+    }
+
+    #[test]
+    fn test_stencil(){
+        assert_eq!(stencil(DifferenceScheme::Central, 1, 2), vec![-1., 0., 1.]);
+        assert_eq!(stencil(DifferenceScheme::Central, 2, 2), vec![-1., 0., 1.]);
+        assert_eq!(stencil(DifferenceScheme::Central, 3, 2), vec![-2., -1., 0., 1., 2.]);
+        assert_eq!(stencil(DifferenceScheme::Central, 1, 4), vec![-2., -1., 0., 1., 2.]);
+        assert_eq!(stencil(DifferenceScheme::Forward, 1, 1), vec![0., 1.]);
+        assert_eq!(stencil(DifferenceScheme::Backward, 2, 1), vec![0., -1., -2.]);
+    }
+
+    #[test]
+    fn test_finite_derivative(){
+        let f = |x : f64| x.sin();
+        let x = 0.7;
+        let central = DiffOptions::new();
+        assert!((finite_derivative(f, x, 1, &central).unwrap() - x.cos()).abs() < 1e-10);
+        assert!((finite_derivative(f, x, 2, &central).unwrap() + x.sin()).abs() < 1e-6);
+        assert!((finite_derivative(f, x, 3, &central).unwrap() + x.cos()).abs() < 1e-3);
+        let high = central.accuracy(6);
+        assert!((finite_derivative(f, x, 1, &high).unwrap() - x.cos()).abs() < 1e-13);
+        assert!((finite_derivative(f, x, 2, &high).unwrap() + x.sin()).abs() < 1e-9);
+        let forward = DiffOptions::new().scheme(DifferenceScheme::Forward).accuracy(1);
+        assert!((finite_derivative(f, x, 1, &forward).unwrap() - x.cos()).abs() < 1e-7);
+        let backward = DiffOptions::new().scheme(DifferenceScheme::Backward).accuracy(3);
+        assert!((finite_derivative(f, x, 1, &backward).unwrap() - x.cos()).abs() < 1e-11);
+        // A fixed step on a quadratic is exact up to rounding
+        let fixed = DiffOptions::new().step(0.5);
+        assert!((finite_derivative(|x| x * x, 3., 1, &fixed).unwrap() - 6.).abs() < 1e-14);
+        // Large arguments scale the step
+        assert!((finite_derivative(f64::ln, 1e6, 1, &central).unwrap() - 1e-6).abs() < 1e-15);
+        assert!(finite_derivative(f, x, 1, &central.accuracy(3)).is_err());
+        assert!(finite_derivative(f, f64::NAN, 1, &central).is_err());
+    }
+}
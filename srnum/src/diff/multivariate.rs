@@ -0,0 +1,227 @@
+#![doc="Gradients, Jacobians and Hessians by finite differences
+
+The functions take the point x as a column vector. Each partial
+derivative uses the stencil of the options along one coordinate,
+with a step scaled by that coordinate.
+
+The names carry the prefix ``numeric_`` to set them apart from the
+exact derivatives of the ``ad`` module.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use diff::options::DiffOptions;
+use diff::finite::{stencil, difference_weights, step_size};
+
+
+/// Checks that x is a non empty column vector
+fn check_point(x : &MatrixF64) -> SRResult<()> {
+    if !x.is_col() {
+        return Err(SRError::IsNotAColVector);
+    }
+    if x.is_empty() {
+        return Err(SRError::EmptyMatrix);
+    }
+    Ok(())
+}
+
+
+/// Returns the stencil and weights of first derivatives
+fn first_derivative_stencil(options : &DiffOptions) -> SRResult<Vec<(f64, f64)>> {
+    options.validate()?;
+    let offsets = stencil(options.scheme, 1, options.accuracy);
+    let weights = difference_weights(&offsets, 1)?;
+    Ok(offsets.into_iter().zip(weights).filter(|&(_, w)| w != 0.).collect())
+}
+
+
+/// Approximates the gradient of a scalar function at x as a
+/// column vector
+pub fn numeric_gradient<F>(f : F, x : &MatrixF64, options : &DiffOptions) -> SRResult<MatrixF64>
+    where F : Fn(&MatrixF64) -> f64 {
+    check_point(x)?;
+    let points = first_derivative_stencil(options)?;
+    let n = x.num_rows();
+    let mut gradient = Matrix::zeros(n, 1);
+    let mut z = x.clone();
+    for j in 0..n {
+        let xj = x[j];
+        let h = options.step.unwrap_or_else(|| step_size(xj, 1, options.accuracy));
+        let mut sum = 0.;
+        for &(o, w) in points.iter() {
+            z.set(j, 0, xj + o * h);
+            sum += w * f(&z);
+        }
+        z.set(j, 0, xj);
+        gradient.set(j, 0, sum / h);
+    }
+    Ok(gradient)
+}
+
+
+/// Approximates the m x n Jacobian of a function of x whose
+/// values are column vectors of length m
+pub fn numeric_jacobian<F>(f : F, x : &MatrixF64, options : &DiffOptions) -> SRResult<MatrixF64>
+    where F : Fn(&MatrixF64) -> MatrixF64 {
+    check_point(x)?;
+    let points = first_derivative_stencil(options)?;
+    let n = x.num_rows();
+    let mut jacobian = Matrix::zeros(0, 0);
+    let mut z = x.clone();
+    for j in 0..n {
+        let xj = x[j];
+        let h = options.step.unwrap_or_else(|| step_size(xj, 1, options.accuracy));
+        for &(o, w) in points.iter() {
+            z.set(j, 0, xj + o * h);
+            let y = f(&z);
+            if !y.is_col() {
+                return Err(SRError::IsNotAColVector);
+            }
+            if jacobian.is_empty() {
+                jacobian = Matrix::zeros(y.num_rows(), n);
+            }
+            else if y.num_rows() != jacobian.num_rows() {
+                return Err(SRError::DimensionsMismatch);
+            }
+            for i in 0..y.num_rows() {
+                let jij = jacobian.get(i, j).unwrap();
+                jacobian.set(i, j, jij + w * y[i] / h);
+            }
+        }
+        z.set(j, 0, xj);
+    }
+    Ok(jacobian)
+}
+
+
+#[doc="Approximates the Hessian of a scalar function at x.
+
+The second differences are central with truncation error O(h^2):
+
+H_ii ~ (f(x + h_i e_i) - 2 f(x) + f(x - h_i e_i)) / h_i^2
+H_ij ~ (f(x + h_i e_i + h_j e_j) - f(x + h_i e_i - h_j e_j)
+- f(x - h_i e_i + h_j e_j) + f(x - h_i e_i - h_j e_j)) / (4 h_i h_j)
+
+The result is symmetric. Only the step of the options is used.
+"]
+pub fn numeric_hessian<F>(f : F, x : &MatrixF64, options : &DiffOptions) -> SRResult<MatrixF64>
+    where F : Fn(&MatrixF64) -> f64 {
+    check_point(x)?;
+    options.validate()?;
+    let n = x.num_rows();
+    let steps : Vec<f64> = (0..n).map(|i| options.step.unwrap_or_else(|| step_size(x[i], 2, 2))).collect();
+    let f0 = f(x);
+    let mut hessian = Matrix::zeros(n, n);
+    let mut z = x.clone();
+    for i in 0..n {
+        let (xi, hi) = (x[i], steps[i]);
+        z.set(i, 0, xi + hi);
+        let fp = f(&z);
+        z.set(i, 0, xi - hi);
+        let fm = f(&z);
+        z.set(i, 0, xi);
+        hessian.set(i, i, (fp - 2. * f0 + fm) / (hi * hi));
+        for j in 0..i {
+            let (xj, hj) = (x[j], steps[j]);
+            let mut sum = 0.;
+            for &(si, sj) in [(1., 1.), (1., -1.), (-1., 1.), (-1., -1.)].iter() {
+                z.set(i, 0, xi + si * hi);
+                z.set(j, 0, xj + sj * hj);
+                sum += si * sj * f(&z);
+            }
+            z.set(i, 0, xi);
+            z.set(j, 0, xj);
+            let hij = sum / (4. * hi * hj);
+            hessian.set(i, j, hij);
+            hessian.set(j, i, hij);
+        }
+    }
+    Ok(hessian)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use diff::options::DifferenceScheme;
+
+    fn rosenbrock(x : &MatrixF64) -> f64 {
+        let a = x[1] - x[0] * x[0];
+        let b = 1. - x[0];
+        100. * a * a + b * b
+    }
+
+    #[test]
+    fn test_gradient(){
+        let x = Matrix::from_slice_cw(2, 1, &[-1.2, 1.]);
+        let expected = [-215.6, -88.];
+        let g = numeric_gradient(rosenbrock, &x, &DiffOptions::new()).unwrap();
+        assert_eq!(g.size(), (2, 1));
+        for j in 0..2 {
+            assert!((g[j] - expected[j]).abs() < 1e-7, "{}", g[j]);
+        }
+        let g = numeric_gradient(rosenbrock, &x, &DiffOptions::new().accuracy(4)).unwrap();
+        for j in 0..2 {
+            assert!((g[j] - expected[j]).abs() < 1e-9, "{}", g[j]);
+        }
+        let forward = DiffOptions::new().scheme(DifferenceScheme::Forward).accuracy(1);
+        let g = numeric_gradient(rosenbrock, &x, &forward).unwrap();
+        for j in 0..2 {
+            assert!((g[j] - expected[j]).abs() < 1e-4, "{}", g[j]);
+        }
+        assert!(numeric_gradient(rosenbrock, &x.transpose(), &DiffOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_jacobian(){
+        // Polar to Cartesian coordinates
+        let f = |x : &MatrixF64| Matrix::from_slice_cw(3, 1, &[x[0] * x[1].cos(), x[0] * x[1].sin(), x[0]]);
+        let x = Matrix::from_slice_cw(2, 1, &[2., 0.5]);
+        let j = numeric_jacobian(f, &x, &DiffOptions::new()).unwrap();
+        assert_eq!(j.size(), (3, 2));
+        let expected = [[0.5f64.cos(), -2. * 0.5f64.sin()], [0.5f64.sin(), 2. * 0.5f64.cos()], [1., 0.]];
+        for r in 0..3 {
+            for c in 0..2 {
+                assert!((j.get(r, c).unwrap() - expected[r][c]).abs() < 1e-10);
+            }
+        }
+        // Outputs of varying length
+        let g = |x : &MatrixF64| Matrix::zeros(if x[0] > 2. { 2 } else { 3 }, 1);
+        assert!(numeric_jacobian(g, &x, &DiffOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_hessian(){
+        let x = Matrix::from_slice_cw(2, 1, &[-1.2, 1.]);
+        let h = numeric_hessian(rosenbrock, &x, &DiffOptions::new()).unwrap();
+        // 1200 x^2 - 400 y + 2, -400 x and 200
+        let expected = [[1330., 480.], [480., 200.]];
+        for r in 0..2 {
+            for c in 0..2 {
+                assert!((h.get(r, c).unwrap() - expected[r][c]).abs() < 1e-4, "{} {} {}", r, c, h.get(r, c).unwrap());
+            }
+        }
+        assert_eq!(h.get(0, 1).unwrap(), h.get(1, 0).unwrap());
+        // Exact for quadratics up to rounding
+        let q = |x : &MatrixF64| x[0] * x[0] + 3. * x[0] * x[2] - x[1] * x[1] * 0.5;
+        let x = Matrix::from_slice_cw(3, 1, &[1., 2., 3.]);
+        let h = numeric_hessian(q, &x, &DiffOptions::new().step(0.5)).unwrap();
+        let expected = [[2., 0., 3.], [0., -1., 0.], [3., 0., 0.]];
+        for r in 0..3 {
+            for c in 0..3 {
+                assert!((h.get(r, c).unwrap() - expected[r][c]).abs() < 1e-13);
+            }
+        }
+    }
+}
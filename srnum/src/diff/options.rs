@@ -0,0 +1,117 @@
+#![doc="Difference schemes, options and results of numerical
+differentiation
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+
+/// Placement of the stencil points around x
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DifferenceScheme {
+    /// Points x, x + h, x + 2h, ...
+    Forward,
+    /// Points x, x - h, x - 2h, ...
+    Backward,
+    /// Points symmetric about x
+    Central
+}
+
+
+/// Options of the finite difference approximations
+#[derive(Debug, Clone, Copy)]
+pub struct DiffOptions {
+    /// Placement of the stencil
+    pub scheme : DifferenceScheme,
+    /// Order of the truncation error O(h^accuracy)
+    pub accuracy : usize,
+    /// The step h, chosen automatically when None
+    pub step : Option<f64>,
+    /// Bound on the error estimate of Richardson extrapolation
+    /// relative to max(1, |value|)
+    pub tolerance : f64,
+    /// Maximum number of step reductions of Richardson
+    /// extrapolation
+    pub max_levels : usize
+}
+
+impl DiffOptions {
+
+    /// Default options: central differences of accuracy 2 with
+    /// automatic steps, tolerance 1e-12 and at most 10 levels
+    pub fn new() -> DiffOptions {
+        DiffOptions {
+            scheme : DifferenceScheme::Central,
+            accuracy : 2,
+            step : None,
+            tolerance : 1e-12,
+            max_levels : 10
+        }
+    }
+
+    /// Sets the difference scheme
+    pub fn scheme(mut self, scheme : DifferenceScheme) -> DiffOptions {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Sets the order of accuracy
+    pub fn accuracy(mut self, accuracy : usize) -> DiffOptions {
+        self.accuracy = accuracy;
+        self
+    }
+
+    /// Sets a fixed step
+    pub fn step(mut self, step : f64) -> DiffOptions {
+        self.step = Some(step);
+        self
+    }
+
+    /// Sets the tolerance of Richardson extrapolation
+    pub fn tolerance(mut self, tolerance : f64) -> DiffOptions {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of levels
+    pub fn max_levels(mut self, max_levels : usize) -> DiffOptions {
+        self.max_levels = max_levels;
+        self
+    }
+
+    /// Checks the options. Central schemes need an even accuracy.
+    pub fn validate(&self) -> SRResult<()> {
+        if self.accuracy == 0 || (self.scheme == DifferenceScheme::Central && self.accuracy % 2 == 1) {
+            return Err(SRError::InvalidArgument);
+        }
+        if let Some(h) = self.step {
+            if !(h > 0. && h.is_finite()) {
+                return Err(SRError::InvalidArgument);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for DiffOptions {
+    fn default() -> DiffOptions {
+        DiffOptions::new()
+    }
+}
+
+
+/// Result of an extrapolated derivative
+#[derive(Debug, Clone, Copy)]
+pub struct DiffResult {
+    /// The estimate of the derivative
+    pub value : f64,
+    /// The estimate of the absolute error
+    pub error : f64,
+    /// Number of function evaluations
+    pub evaluations : usize,
+    /// Whether the error estimate meets the tolerance
+    pub converged : bool
+}
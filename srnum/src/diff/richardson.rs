@@ -0,0 +1,138 @@
+#![doc="Richardson extrapolation
+
+Given approximations A(h) with the error expansion
+
+A(h) = A + c_1 h^p + c_2 h^(p + q) + c_3 h^(p + 2q) + ...
+
+the steps h, h/t, h/t^2, ... are combined in a Neville tableau
+
+A_i,k = A_i,k-1 + (A_i,k-1 - A_i-1,k-1) / (t^(p + (k-1) q) - 1)
+
+which eliminates one term of the expansion per column. As in the
+method of Ridders, the error estimate of each entry is the larger
+of its differences to its neighbours, the best entry is kept and
+the step reductions stop once the diagonal starts to diverge
+from rounding errors.
+
+For finite differences p is the accuracy of the stencil and q is
+2 for central stencils and 1 otherwise.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use diff::options::{DifferenceScheme, DiffOptions, DiffResult};
+use diff::finite::{stencil, difference_weights, apply_stencil};
+
+
+/// Ratio of successive steps
+const STEP_RATIO : f64 = 2.;
+
+
+/// Extrapolates A(h) to h = 0 from the steps h0, h0 / ratio, ...
+/// given the exponents p and q of the error expansion
+pub fn richardson<A>(a : A, h0 : f64, ratio : f64, p : f64, q : f64, options : &DiffOptions) -> SRResult<DiffResult>
+    where A : Fn(f64) -> f64 {
+    let valid = h0 > 0. && h0.is_finite() && ratio > 1. && p > 0. && q > 0.;
+    if !valid || options.max_levels == 0 {
+        return Err(SRError::InvalidArgument);
+    }
+    let mut h = h0;
+    let mut previous = vec![a(h)];
+    let mut value = previous[0];
+    let mut error = f64::INFINITY;
+    let mut evaluations = 1;
+    for i in 1..options.max_levels {
+        h /= ratio;
+        let mut row = Vec::with_capacity(i + 1);
+        row.push(a(h));
+        evaluations += 1;
+        for k in 1..=i {
+            let factor = ratio.powf(p + (k - 1) as f64 * q);
+            let t = row[k - 1] + (row[k - 1] - previous[k - 1]) / (factor - 1.);
+            let e = (t - row[k - 1]).abs().max((t - previous[k - 1]).abs());
+            if e <= error {
+                error = e;
+                value = t;
+            }
+            row.push(t);
+        }
+        let diverging = (row[i] - previous[i - 1]).abs() >= 2. * error;
+        previous = row;
+        if diverging || error <= options.tolerance * value.abs().max(1.) {
+            break;
+        }
+    }
+    Ok(DiffResult {
+        value : value,
+        error : error,
+        evaluations : evaluations,
+        converged : error <= options.tolerance * value.abs().max(1.)
+    })
+}
+
+
+/// Approximates the derivative of f of the given order at x by
+/// Richardson extrapolation of finite differences. The initial
+/// step is the step of the options or 0.1 max(1, |x|).
+pub fn extrapolated_derivative<F>(f : F, x : f64, order : usize, options : &DiffOptions) -> SRResult<DiffResult>
+    where F : Fn(f64) -> f64 {
+    options.validate()?;
+    if !x.is_finite() {
+        return Err(SRError::InvalidArgument);
+    }
+    let offsets = stencil(options.scheme, order, options.accuracy);
+    let weights = difference_weights(&offsets, order)?;
+    let points = weights.iter().filter(|&&w| w != 0.).count();
+    let h0 = options.step.unwrap_or(0.1 * x.abs().max(1.));
+    let q = if options.scheme == DifferenceScheme::Central { 2. } else { 1. };
+    let mut result = richardson(|h| apply_stencil(&f, x, h, order, &offsets, &weights),
+        h0, STEP_RATIO, options.accuracy as f64, q, options)?;
+    result.evaluations *= points;
+    Ok(result)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_richardson(){
+        // The perimeter of the inscribed n-gon, n sin(pi / n),
+        // tends to pi with an expansion in even powers of 1 / n
+        let options = DiffOptions::new().tolerance(1e-14);
+        let r = richardson(|h| (PI * h).sin() / h, 0.25, 2., 2., 2., &options).unwrap();
+        assert!((r.value - PI).abs() < 1e-13, "{:?}", r);
+        assert!(r.converged);
+        assert!(r.evaluations < 10);
+        assert!(richardson(|h| h, 0., 2., 2., 2., &options).is_err());
+        assert!(richardson(|h| h, 1., 1., 2., 2., &options).is_err());
+    }
+
+    #[test]
+    fn test_extrapolated_derivative(){
+        let options = DiffOptions::new();
+        let r = extrapolated_derivative(f64::exp, 1., 1, &options).unwrap();
+        assert!((r.value - 1f64.exp()).abs() < 1e-13, "{:?}", r);
+        assert!(r.error < 1e-11);
+        let r = extrapolated_derivative(f64::exp, 1., 2, &options).unwrap();
+        assert!((r.value - 1f64.exp()).abs() < 1e-10, "{:?}", r);
+        let r = extrapolated_derivative(|x : f64| x.sin(), 2., 4, &options).unwrap();
+        assert!((r.value - 2f64.sin()).abs() < 1e-6, "{:?}", r);
+        let forward = options.scheme(DifferenceScheme::Forward).accuracy(1);
+        let r = extrapolated_derivative(|x : f64| x.sqrt(), 4., 1, &forward).unwrap();
+        assert!((r.value - 0.25).abs() < 1e-11, "{:?}", r);
+    }
+}
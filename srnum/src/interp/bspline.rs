@@ -0,0 +1,353 @@
+#![doc="B-splines
+
+A spline of degree p with the knots t_0 <= t_1 <= ... <= t_n+p
+and the coefficients c_0, ..., c_n-1 is
+
+s(x) = sum_i c_i B_i,p(x)
+
+where the B_i,p are the B-spline basis functions of the Cox-de
+Boor recursion. The spline is defined on the base interval
+[t_p, t_n]; outside it the first or the last polynomial piece
+is continued. Values are computed by de Boor's algorithm.
+
+Derivatives and antiderivatives are again B-splines of degree
+p - 1 and p + 1 (de Boor, 1978, X).
+
+Splines are constructed
+
+* from given knots and coefficients,
+* by interpolation of data points with knots at the averages
+  of p consecutive abscissas, which satisfy the
+  Schoenberg-Whitney conditions, or
+* by least squares fitting of data points for given interior
+  knots.
+
+The collocation matrix of interpolation and the normal equations
+of fitting are band matrices of band width p, so both are solved
+in O(n p^2).
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use interp::traits::Interpolator;
+use interp::support::*;
+
+
+/// B-spline of a given degree
+#[derive(Debug, Clone)]
+pub struct BSpline {
+    knots : Vec<f64>,
+    coefficients : Vec<f64>,
+    degree : usize
+}
+
+impl BSpline {
+
+    /// Creates the spline with the given knots, coefficients and
+    /// degree. There must be n + degree + 1 non-decreasing knots
+    /// for n > degree coefficients.
+    pub fn new(knots : &MatrixF64, coefficients : &MatrixF64, degree : usize) -> SRResult<BSpline> {
+        let knots = vector_entries(knots)?;
+        let coefficients = vector_entries(coefficients)?;
+        let n = coefficients.len();
+        if n <= degree || knots.len() != n + degree + 1 {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if knots.iter().any(|v| !v.is_finite()) || (1..knots.len()).any(|i| knots[i] < knots[i - 1])
+            || knots[degree] >= knots[n] {
+            return Err(SRError::InvalidArgument);
+        }
+        Ok(BSpline {
+            knots : knots,
+            coefficients : coefficients,
+            degree : degree
+        })
+    }
+
+    /// Interpolates the data points (x_i, y_i) by a spline of the
+    /// given degree (at least 1) with one coefficient per point
+    pub fn interpolate(x : &MatrixF64, y : &MatrixF64, degree : usize) -> SRResult<BSpline> {
+        if degree == 0 {
+            return Err(SRError::InvalidArgument);
+        }
+        let (x, y) = check_points(x, y, degree + 1)?;
+        let n = x.len();
+        let p = degree;
+        let mut knots = vec![x[0]; p + 1];
+        for j in 1..(n - p) {
+            knots.push(x[j..(j + p)].iter().fold(0., |s, v| s + v) / p as f64);
+        }
+        knots.extend(vec![x[n - 1]; p + 1]);
+        let mut a = BandMatrix::new(n, p, p);
+        for (i, xi) in x.iter().enumerate() {
+            let k = find_span(&knots, n, p, *xi);
+            for (r, b) in basis_functions(&knots, k, p, *xi).iter().enumerate() {
+                a.set(i, k - p + r, *b);
+            }
+        }
+        let coefficients = a.solve(&y)?;
+        Ok(BSpline {
+            knots : knots,
+            coefficients : coefficients,
+            degree : p
+        })
+    }
+
+    /// Fits a spline of the given degree to the data points
+    /// (x_i, y_i) in the least squares sense. The knots are the
+    /// interior knots, which must lie strictly inside the range of
+    /// the abscissas, and degree + 1 knots at either end. The
+    /// problem is singular unless every basis function is
+    /// supported by enough data points.
+    pub fn fit(x : &MatrixF64, y : &MatrixF64, interior_knots : &MatrixF64, degree : usize) -> SRResult<BSpline> {
+        let (x, y) = check_points(x, y, 2)?;
+        let p = degree;
+        let interior = if interior_knots.num_cells() == 0 { Vec::new() } else { vector_entries(interior_knots)? };
+        let (first, last) = (x[0], x[x.len() - 1]);
+        if interior.iter().any(|v| v.is_nan() || *v <= first || *v >= last) || (1..interior.len()).any(|i| interior[i] < interior[i - 1]) {
+            return Err(SRError::InvalidArgument);
+        }
+        let mut knots = vec![first; p + 1];
+        knots.extend(interior.iter().cloned());
+        knots.extend(vec![last; p + 1]);
+        let n = knots.len() - p - 1;
+        // Normal equations B^T B c = B^T y
+        let mut a = BandMatrix::new(n, p, p);
+        let mut rhs = vec![0.; n];
+        for (xi, yi) in x.iter().zip(y.iter()) {
+            let k = find_span(&knots, n, p, *xi);
+            let b = basis_functions(&knots, k, p, *xi);
+            for r in 0..=p {
+                rhs[k - p + r] += b[r] * yi;
+                for s in 0..=p {
+                    a.add(k - p + r, k - p + s, b[r] * b[s]);
+                }
+            }
+        }
+        let coefficients = a.solve(&rhs)?;
+        Ok(BSpline {
+            knots : knots,
+            coefficients : coefficients,
+            degree : p
+        })
+    }
+
+    /// Returns the knots
+    pub fn knots(&self) -> &[f64] {
+        &self.knots
+    }
+
+    /// Returns the coefficients
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    /// Returns the degree
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns the base interval [t_p, t_n]
+    pub fn domain(&self) -> (f64, f64) {
+        (self.knots[self.degree], self.knots[self.coefficients.len()])
+    }
+
+    /// Returns the derivative as a spline of one degree less.
+    /// The derivative of a spline of degree 0 is zero.
+    pub fn derivative(&self) -> BSpline {
+        let p = self.degree;
+        let (t, c) = (&self.knots, &self.coefficients);
+        if p == 0 {
+            return BSpline {
+                knots : t.clone(),
+                coefficients : vec![0.; c.len()],
+                degree : 0
+            };
+        }
+        let coefficients = (0..(c.len() - 1)).map(|i| {
+            let dt = t[i + p + 1] - t[i + 1];
+            if dt > 0. { p as f64 * (c[i + 1] - c[i]) / dt } else { 0. }
+        }).collect();
+        BSpline {
+            knots : t[1..(t.len() - 1)].to_vec(),
+            coefficients : coefficients,
+            degree : p - 1
+        }
+    }
+
+    /// Returns an antiderivative as a spline of one degree more.
+    /// It vanishes at t_p if the first p + 1 knots coincide.
+    pub fn antiderivative(&self) -> BSpline {
+        let p = self.degree;
+        let (t, c) = (&self.knots, &self.coefficients);
+        let mut knots = Vec::with_capacity(t.len() + 2);
+        knots.push(t[0]);
+        knots.extend(t.iter().cloned());
+        knots.push(t[t.len() - 1]);
+        let mut total = 0.;
+        let mut coefficients = vec![0.];
+        for i in 0..c.len() {
+            total += c[i] * (t[i + p + 1] - t[i]) / (p as f64 + 1.);
+            coefficients.push(total);
+        }
+        BSpline {
+            knots : knots,
+            coefficients : coefficients,
+            degree : p + 1
+        }
+    }
+
+    /// Returns the integral from a to b inside the base interval
+    pub fn integral(&self, a : f64, b : f64) -> f64 {
+        let anti = self.antiderivative();
+        anti.value(b) - anti.value(a)
+    }
+}
+
+impl Interpolator for BSpline {
+
+    fn value(&self, x : f64) -> f64 {
+        let p = self.degree;
+        let t = &self.knots;
+        let k = find_span(t, self.coefficients.len(), p, x);
+        // de Boor's algorithm
+        let mut d : Vec<f64> = self.coefficients[(k - p)..(k + 1)].to_vec();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let left = t[j + k - p];
+                let alpha = (x - left) / (t[j + 1 + k - r] - left);
+                d[j] = (1. - alpha) * d[j - 1] + alpha * d[j];
+            }
+        }
+        d[p]
+    }
+}
+
+
+/// Returns the index k with t_k <= x < t_k+1 and t_k < t_k+1
+/// inside the base interval [t_p, t_n] of a spline with n
+/// coefficients. Points outside belong to the first or the last
+/// non-empty interval.
+fn find_span(t : &[f64], n : usize, p : usize, x : f64) -> usize {
+    let k = t.partition_point(|v| *v <= x);
+    let mut k = k.max(p + 1).min(n) - 1;
+    while k > p && t[k] == t[k + 1] {
+        k -= 1;
+    }
+    while k + 1 < n && t[k] == t[k + 1] {
+        k += 1;
+    }
+    k
+}
+
+
+/// Returns the values of the p + 1 basis functions B_k-p,p, ...,
+/// B_k,p which may be non-zero in the span k (Piegl and Tiller,
+/// 1997, A2.2)
+fn basis_functions(t : &[f64], k : usize, p : usize, x : f64) -> Vec<f64> {
+    let mut b = vec![0.; p + 1];
+    let mut left = vec![0.; p + 1];
+    let mut right = vec![0.; p + 1];
+    b[0] = 1.;
+    for j in 1..=p {
+        left[j] = x - t[k + 1 - j];
+        right[j] = t[k + j] - x;
+        let mut saved = 0.;
+        for r in 0..j {
+            let temp = b[r] / (right[r + 1] + left[j - r]);
+            b[r] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        b[j] = saved;
+    }
+    b
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_partition_of_unity(){
+        let knots = vector_f64(&[0., 0., 0., 1., 2., 2., 3., 3., 3.]);
+        let s = BSpline::new(&knots, &vector_f64(&[1.; 6]), 2).unwrap();
+        assert_eq!(s.domain(), (0., 3.));
+        for k in 0..=30 {
+            assert!((s.value(0.1 * k as f64) - 1.).abs() < 1e-14);
+        }
+        // A single basis function of degree 1 is a hat
+        let hat = BSpline::new(&vector_f64(&[0., 0., 1., 2., 2.]), &vector_f64(&[0., 1., 0.]), 1).unwrap();
+        assert_eq!(hat.value(0.5), 0.5);
+        assert_eq!(hat.value(1.), 1.);
+        assert_eq!(hat.value(1.5), 0.5);
+        assert!((hat.integral(0., 2.) - 1.).abs() < 1e-15);
+        assert!(BSpline::new(&knots, &vector_f64(&[1.; 5]), 2).is_err());
+        assert!(BSpline::new(&vector_f64(&[0., 0., 2., 1., 2., 2.]), &vector_f64(&[1.; 3]), 2).is_err());
+    }
+
+    #[test]
+    fn test_interpolate(){
+        let f = |x : f64| x * x * x - x + 2.;
+        let xs = [0., 0.4, 1., 1.3, 2., 2.2, 3.];
+        let x = vector_f64(&xs);
+        let y = vector_f64(&xs.iter().map(|v| f(*v)).collect::<Vec<f64>>());
+        // Cubic splines reproduce cubics
+        let s = BSpline::interpolate(&x, &y, 3).unwrap();
+        assert_eq!(s.coefficients().len(), 7);
+        let ds = s.derivative();
+        let dds = ds.derivative();
+        assert_eq!(ds.degree(), 2);
+        for k in 0..=35 {
+            let t = -0.2 + 0.1 * k as f64;
+            assert!((s.value(t) - f(t)).abs() < 1e-12);
+            assert!((ds.value(t) - (3. * t * t - 1.)).abs() < 1e-11);
+            assert!((dds.value(t) - 6. * t).abs() < 1e-10);
+        }
+        let big = |x : f64| x.powi(4) / 4. - x * x / 2. + 2. * x;
+        assert!((s.integral(0.3, 2.9) - (big(2.9) - big(0.3))).abs() < 1e-12);
+        assert!((s.antiderivative().value(3.) - big(3.)).abs() < 1e-12);
+        // Linear splines interpolate linearly
+        let l = BSpline::interpolate(&x, &y, 1).unwrap();
+        assert!((l.value(0.7) - 0.5 * (f(0.4) + f(1.))).abs() < 1e-14);
+        assert!(BSpline::interpolate(&x, &y, 0).is_err());
+        assert!(BSpline::interpolate(&vector_f64(&[0., 1.]), &vector_f64(&[0., 1.]), 2).is_err());
+    }
+
+    #[test]
+    fn test_fit(){
+        // Noise free data of a spline is reproduced
+        let xs : Vec<f64> = (0..50).map(|i| 0.1 * i as f64).collect();
+        let f = |x : f64| if x < 2. { x * x } else { 4. * x - 4. };
+        let x = vector_f64(&xs);
+        let y = vector_f64(&xs.iter().map(|v| f(*v)).collect::<Vec<f64>>());
+        let s = BSpline::fit(&x, &y, &vector_f64(&[1., 2., 3.]), 2).unwrap();
+        for v in xs.iter() {
+            assert!((s.value(*v) - f(*v)).abs() < 1e-12);
+        }
+        // Smooth data is approximated with a few knots
+        let y = vector_f64(&xs.iter().map(|v| v.sin()).collect::<Vec<f64>>());
+        let s = BSpline::fit(&x, &y, &vector_f64(&[0.5, 1., 1.5, 2., 2.5, 3., 3.5, 4., 4.5]), 3).unwrap();
+        for v in xs.iter() {
+            assert!((s.value(*v) - v.sin()).abs() < 1e-3);
+        }
+        // Without interior knots the fit is the least squares polynomial
+        let line = BSpline::fit(&vector_f64(&[0., 1., 2.]), &vector_f64(&[0., 2., 1.]), &Matrix::zeros(0, 0), 1).unwrap();
+        assert!((line.value(0.) - 0.5).abs() < 1e-14);
+        assert!((line.value(2.) - 1.5).abs() < 1e-14);
+        // A knot interval without data makes the fit singular
+        assert!(BSpline::fit(&vector_f64(&[0., 0.1, 5.]), &vector_f64(&[0., 1., 2.]), &vector_f64(&[1., 2.]), 1).is_err());
+        assert!(BSpline::fit(&x, &y, &vector_f64(&[0., 2.]), 3).is_err());
+    }
+}
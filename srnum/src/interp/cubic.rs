@@ -0,0 +1,376 @@
+#![doc="Piecewise cubic interpolation
+
+All interpolants here are piecewise cubic Hermite polynomials,
+determined by the values y_i and the slopes s_i at the data
+points x_i. On [x_i, x_i+1] with u = x - x_i, h = x_i+1 - x_i and
+d = (y_i+1 - y_i) / h:
+
+p(x) = y_i + s_i u + (3 d - 2 s_i - s_i+1) u^2 / h + (s_i + s_i+1 - 2 d) u^3 / h^2
+
+The methods differ in the choice of the slopes:
+
+* ``cubic_spline``: the slopes which make the second derivative
+  continuous, with natural, clamped or not-a-knot end conditions.
+  The tridiagonal system for the slopes is solved in O(n).
+* ``pchip``: the weighted harmonic means of the neighbouring
+  secants by Fritsch and Carlson. The interpolant preserves
+  monotonicity and does not overshoot.
+* ``akima``: Akima's weighted average of the neighbouring
+  secants, which avoids the wiggles of splines near outliers.
+
+Outside [x_0, x_n-1] the first or the last cubic is continued.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use interp::traits::Interpolator;
+use interp::support::*;
+
+
+/// End conditions of cubic splines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplineBoundary {
+    /// Zero second derivative at both ends
+    Natural,
+    /// Given first derivatives at the left and the right end
+    Clamped(f64, f64),
+    /// Continuous third derivative at x_1 and x_n-2
+    NotAKnot
+}
+
+
+/// Piecewise cubic Hermite interpolant
+#[derive(Debug, Clone)]
+pub struct CubicHermite {
+    x : Vec<f64>,
+    y : Vec<f64>,
+    slopes : Vec<f64>,
+    /// Integrals from x_0 to x_i
+    areas : Vec<f64>
+}
+
+impl CubicHermite {
+
+    /// Interpolates at least two points with given slopes
+    pub fn new(x : &MatrixF64, y : &MatrixF64, slopes : &MatrixF64) -> SRResult<CubicHermite> {
+        let (x, y) = check_points(x, y, 2)?;
+        let slopes = vector_entries(slopes)?;
+        if slopes.len() != x.len() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(CubicHermite::from_parts(x, y, slopes))
+    }
+
+    fn from_parts(x : Vec<f64>, y : Vec<f64>, slopes : Vec<f64>) -> CubicHermite {
+        let mut total = 0.;
+        let areas = (0..x.len()).map(|i| {
+            if i > 0 {
+                let h = x[i] - x[i - 1];
+                total += h * (0.5 * (y[i - 1] + y[i]) + h * (slopes[i - 1] - slopes[i]) / 12.);
+            }
+            total
+        }).collect();
+        CubicHermite {
+            x : x,
+            y : y,
+            slopes : slopes,
+            areas : areas
+        }
+    }
+
+    /// Returns the slopes at the data points
+    pub fn slopes(&self) -> &[f64] {
+        &self.slopes
+    }
+
+    /// Returns the index of the piece, the offset u and the
+    /// coefficients of u^2 and u^3
+    fn piece(&self, x : f64) -> (usize, f64, f64, f64) {
+        let i = find_interval(&self.x, x);
+        let h = self.x[i + 1] - self.x[i];
+        let d = (self.y[i + 1] - self.y[i]) / h;
+        let (s0, s1) = (self.slopes[i], self.slopes[i + 1]);
+        let c2 = (3. * d - 2. * s0 - s1) / h;
+        let c3 = (s0 + s1 - 2. * d) / (h * h);
+        (i, x - self.x[i], c2, c3)
+    }
+
+    /// Returns the first derivative at x
+    pub fn derivative(&self, x : f64) -> f64 {
+        let (i, u, c2, c3) = self.piece(x);
+        self.slopes[i] + u * (2. * c2 + 3. * c3 * u)
+    }
+
+    /// Returns the second derivative at x. At the data points
+    /// the value of the piece to the right is returned.
+    pub fn second_derivative(&self, x : f64) -> f64 {
+        let (_, u, c2, c3) = self.piece(x);
+        2. * c2 + 6. * c3 * u
+    }
+
+    /// Returns the integral from a to b
+    pub fn integral(&self, a : f64, b : f64) -> f64 {
+        self.antiderivative(b) - self.antiderivative(a)
+    }
+
+    /// Integral from x_0 to x
+    fn antiderivative(&self, x : f64) -> f64 {
+        let (i, u, c2, c3) = self.piece(x);
+        self.areas[i] + u * (self.y[i] + u * (0.5 * self.slopes[i] + u * (c2 / 3. + 0.25 * c3 * u)))
+    }
+}
+
+impl Interpolator for CubicHermite {
+
+    fn value(&self, x : f64) -> f64 {
+        let (i, u, c2, c3) = self.piece(x);
+        self.y[i] + u * (self.slopes[i] + u * (c2 + c3 * u))
+    }
+}
+
+
+/// Returns the interval lengths and the secant slopes
+fn secants(x : &[f64], y : &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let h : Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+    let d = (0..h.len()).map(|i| (y[i + 1] - y[i]) / h[i]).collect();
+    (h, d)
+}
+
+
+/// Interpolates at least two points by a cubic spline
+pub fn cubic_spline(x : &MatrixF64, y : &MatrixF64, boundary : SplineBoundary) -> SRResult<CubicHermite> {
+    let (x, y) = check_points(x, y, 2)?;
+    let n = x.len();
+    let (h, d) = secants(&x, &y);
+    if boundary == SplineBoundary::NotAKnot && n <= 3 {
+        // The spline is the interpolating polynomial
+        let slopes = if n == 2 {
+            vec![d[0]; 2]
+        }
+        else {
+            let c = (d[1] - d[0]) / (h[0] + h[1]);
+            vec![d[0] - c * h[0], d[0] + c * h[0], d[0] + c * (h[0] + 2. * h[1])]
+        };
+        return Ok(CubicHermite::from_parts(x, y, slopes));
+    }
+    let mut a = BandMatrix::new(n, 1, 1);
+    let mut rhs = vec![0.; n];
+    for i in 1..(n - 1) {
+        a.set(i, i - 1, h[i]);
+        a.set(i, i, 2. * (h[i - 1] + h[i]));
+        a.set(i, i + 1, h[i - 1]);
+        rhs[i] = 3. * (h[i] * d[i - 1] + h[i - 1] * d[i]);
+    }
+    match boundary {
+        SplineBoundary::Natural => {
+            a.set(0, 0, 2.);
+            a.set(0, 1, 1.);
+            rhs[0] = 3. * d[0];
+            a.set(n - 1, n - 2, 1.);
+            a.set(n - 1, n - 1, 2.);
+            rhs[n - 1] = 3. * d[n - 2];
+        },
+        SplineBoundary::Clamped(left, right) => {
+            a.set(0, 0, 1.);
+            rhs[0] = left;
+            a.set(n - 1, n - 1, 1.);
+            rhs[n - 1] = right;
+        },
+        SplineBoundary::NotAKnot => {
+            let w = h[0] + h[1];
+            a.set(0, 0, h[1]);
+            a.set(0, 1, w);
+            rhs[0] = ((h[0] + 2. * w) * h[1] * d[0] + h[0] * h[0] * d[1]) / w;
+            let w = h[n - 2] + h[n - 3];
+            a.set(n - 1, n - 2, w);
+            a.set(n - 1, n - 1, h[n - 3]);
+            rhs[n - 1] = (h[n - 2] * h[n - 2] * d[n - 3] + (2. * w + h[n - 2]) * h[n - 3] * d[n - 2]) / w;
+        }
+    }
+    let slopes = a.solve(&rhs)?;
+    Ok(CubicHermite::from_parts(x, y, slopes))
+}
+
+
+/// Interpolates at least two points by the shape preserving
+/// piecewise cubic Hermite interpolant (PCHIP)
+pub fn pchip(x : &MatrixF64, y : &MatrixF64) -> SRResult<CubicHermite> {
+    let (x, y) = check_points(x, y, 2)?;
+    let n = x.len();
+    let (h, d) = secants(&x, &y);
+    if n == 2 {
+        return Ok(CubicHermite::from_parts(x, y, vec![d[0]; 2]));
+    }
+    let mut slopes = vec![0.; n];
+    for i in 1..(n - 1) {
+        if d[i - 1] * d[i] > 0. {
+            let w1 = 2. * h[i] + h[i - 1];
+            let w2 = h[i] + 2. * h[i - 1];
+            slopes[i] = (w1 + w2) / (w1 / d[i - 1] + w2 / d[i]);
+        }
+    }
+    slopes[0] = pchip_end_slope(h[0], h[1], d[0], d[1]);
+    slopes[n - 1] = pchip_end_slope(h[n - 2], h[n - 3], d[n - 2], d[n - 3]);
+    Ok(CubicHermite::from_parts(x, y, slopes))
+}
+
+/// Returns the shape preserving three point estimate of the slope
+/// at an end point with the adjacent interval h0, secant d0 and
+/// the next interval h1, secant d1
+fn pchip_end_slope(h0 : f64, h1 : f64, d0 : f64, d1 : f64) -> f64 {
+    let s = ((2. * h0 + h1) * d0 - h0 * d1) / (h0 + h1);
+    if s * d0 <= 0. {
+        0.
+    }
+    else if d0 * d1 < 0. && s.abs() > 3. * d0.abs() {
+        3. * d0
+    }
+    else {
+        s
+    }
+}
+
+
+/// Interpolates at least two points by Akima's method
+pub fn akima(x : &MatrixF64, y : &MatrixF64) -> SRResult<CubicHermite> {
+    let (x, y) = check_points(x, y, 2)?;
+    let n = x.len();
+    let (_, d) = secants(&x, &y);
+    if n == 2 {
+        return Ok(CubicHermite::from_parts(x, y, vec![d[0]; 2]));
+    }
+    // Secants extended by two on each side; m[k + 2] = d[k]
+    let mut m = vec![0.; n + 3];
+    m[2..(n + 1)].copy_from_slice(&d);
+    m[1] = 2. * m[2] - m[3];
+    m[0] = 2. * m[1] - m[2];
+    m[n + 1] = 2. * m[n] - m[n - 1];
+    m[n + 2] = 2. * m[n + 1] - m[n];
+    let slopes = (0..n).map(|i| {
+        let w1 = (m[i + 3] - m[i + 2]).abs();
+        let w2 = (m[i + 1] - m[i]).abs();
+        if w1 + w2 > 0. {
+            (w1 * m[i + 1] + w2 * m[i + 2]) / (w1 + w2)
+        }
+        else {
+            0.5 * (m[i + 1] + m[i + 2])
+        }
+    }).collect();
+    Ok(CubicHermite::from_parts(x, y, slopes))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn cubic(x : f64) -> f64 {
+        x * x * x - 2. * x * x + 0.5 * x - 1.
+    }
+
+    fn samples<F:Fn(f64) -> f64>(x : &[f64], f : F) -> (MatrixF64, MatrixF64) {
+        let y : Vec<f64> = x.iter().map(|v| f(*v)).collect();
+        (vector_f64(x), vector_f64(&y))
+    }
+
+    #[test]
+    fn test_spline_reproduces_cubics(){
+        let (x, y) = samples(&[-1., -0.3, 0.5, 1.2, 2., 3.5], cubic);
+        let dcubic = |x : f64| 3. * x * x - 4. * x + 0.5;
+        let splines = [
+            cubic_spline(&x, &y, SplineBoundary::NotAKnot).unwrap(),
+            cubic_spline(&x, &y, SplineBoundary::Clamped(dcubic(-1.), dcubic(3.5))).unwrap()
+        ];
+        for s in splines.iter() {
+            for k in 0..50 {
+                let t = -1.5 + 0.1 * k as f64;
+                assert!((s.value(t) - cubic(t)).abs() < 1e-12);
+                assert!((s.derivative(t) - dcubic(t)).abs() < 1e-11);
+                assert!((s.second_derivative(t) - (6. * t - 4.)).abs() < 1e-10);
+            }
+            // Antiderivative x^4/4 - 2x^3/3 + x^2/4 - x
+            let big = |x : f64| x.powi(4) / 4. - 2. * x.powi(3) / 3. + x * x / 4. - x;
+            assert!((s.integral(-0.7, 3.1) - (big(3.1) - big(-0.7))).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_natural_spline(){
+        let (x, y) = samples(&[0., 1., 2.5, 3., 4.], |t : f64| t.sin());
+        let s = cubic_spline(&x, &y, SplineBoundary::Natural).unwrap();
+        assert!(s.second_derivative(0.).abs() < 1e-12);
+        assert!(s.second_derivative(4. - 1e-12).abs() < 1e-10);
+        // Continuous second derivative at the knots
+        for t in [1., 2.5, 3.].iter() {
+            assert!((s.second_derivative(t - 1e-9) - s.second_derivative(*t)).abs() < 1e-7);
+            assert_eq!(s.value(*t), t.sin());
+        }
+        // Two points give a straight line
+        let (x, y) = samples(&[0., 2.], |t : f64| 3. * t + 1.);
+        let s = cubic_spline(&x, &y, SplineBoundary::Natural).unwrap();
+        assert!((s.value(0.7) - 3.1).abs() < 1e-15);
+        // Three points with not-a-knot give the parabola
+        let (x, y) = samples(&[0., 1., 3.], |t : f64| t * t - t);
+        let s = cubic_spline(&x, &y, SplineBoundary::NotAKnot).unwrap();
+        assert!((s.value(2.) - 2.).abs() < 1e-14);
+        assert!((s.value(-1.) - 2.).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_pchip(){
+        // Monotone data with a flat part
+        let (x, y) = samples(&[0., 1., 2., 3., 4., 5.], |t : f64| if t < 2. { t } else if t < 4. { 2. } else { t - 2. });
+        let p = pchip(&x, &y).unwrap();
+        let mut previous = p.value(0.);
+        for k in 1..=500 {
+            let v = p.value(0.01 * k as f64);
+            assert!(v >= previous - 1e-15);
+            previous = v;
+        }
+        // No overshoot on the flat part
+        assert!((p.value(2.5) - 2.).abs() < 1e-15);
+        assert_eq!(p.slopes()[2], 0.);
+        // A local extremum of the data gets a zero slope
+        let (x, y) = samples(&[0., 1., 2.], |t : f64| 1. - (t - 1.).abs());
+        assert_eq!(pchip(&x, &y).unwrap().slopes()[1], 0.);
+    }
+
+    #[test]
+    fn test_akima(){
+        // Akima reproduces straight lines and is local
+        let (x, y) = samples(&[0., 0.5, 1.5, 2., 3.], |t : f64| 2. * t - 1.);
+        let a = akima(&x, &y).unwrap();
+        for k in 0..30 {
+            let t = 0.1 * k as f64;
+            assert!((a.value(t) - (2. * t - 1.)).abs() < 1e-14);
+        }
+        // An outlier does not disturb the far intervals
+        let (x, y) = samples(&[0., 1., 2., 3., 4., 5., 6., 7.], |t : f64| if t == 6. { 10. } else { 0. });
+        let a = akima(&x, &y).unwrap();
+        assert!(a.value(2.5).abs() < 1e-15);
+        assert!(a.value(3.5).abs() < 1e-15);
+        let s = cubic_spline(&x, &y, SplineBoundary::NotAKnot).unwrap();
+        assert!(s.value(2.5).abs() > 1e-2);
+    }
+
+    #[test]
+    fn test_errors(){
+        let x = vector_f64(&[0., 1., 2.]);
+        assert!(CubicHermite::new(&x, &x, &vector_f64(&[1., 1.])).is_err());
+        assert!(cubic_spline(&vector_f64(&[0.]), &vector_f64(&[1.]), SplineBoundary::Natural).is_err());
+        assert!(pchip(&vector_f64(&[0., 2., 1.]), &x).is_err());
+        let h = CubicHermite::new(&x, &x, &vector_f64(&[1., 1., 1.])).unwrap();
+        assert_eq!(h.value(1.5), 1.5);
+    }
+}
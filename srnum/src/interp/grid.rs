@@ -0,0 +1,310 @@
+#![doc="Interpolation on rectangular grids
+
+The data are the values z_ij = f(x_i, y_j) on a grid with strictly
+increasing x_0 < ... < x_m-1 and y_0 < ... < y_n-1, given as an
+m x n matrix. The methods are
+
+* ``Nearest``: the value at the nearest grid point.
+* ``Bilinear``: the bilinear interpolant on each cell.
+* ``Bicubic``: the bicubic Hermite interpolant on each cell with
+  the partial derivatives f_x, f_y and f_xy at the grid points
+  estimated by three point differences. It is exact for
+  polynomials of degree two in each variable.
+* ``Spline``: the tensor product of not-a-knot cubic splines. It
+  is the bicubic Hermite interpolant with the derivatives taken
+  from the splines through the rows and columns of the grid.
+
+Outside the grid the cells at the boundary are continued and the
+nearest neighbour keeps the boundary values.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use interp::traits::Interpolator;
+use interp::cubic::*;
+use interp::support::*;
+
+
+/// Interpolation methods on grids
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridMethod {
+    Nearest,
+    Bilinear,
+    Bicubic,
+    Spline
+}
+
+
+/// Interpolant of values on a rectangular grid
+#[derive(Debug, Clone)]
+pub struct GridInterpolator {
+    method : GridMethod,
+    x : Vec<f64>,
+    y : Vec<f64>,
+    /// Values in column major order
+    z : Vec<f64>,
+    /// The derivatives f_x, f_y and f_xy for the cubic methods
+    dx : Vec<f64>,
+    dy : Vec<f64>,
+    dxy : Vec<f64>
+}
+
+impl GridInterpolator {
+
+    /// Interpolates the m x n values z at the grid points (x_i, y_j).
+    /// The cubic and bilinear methods need at least two points
+    /// along each axis.
+    pub fn new(x : &MatrixF64, y : &MatrixF64, z : &MatrixF64, method : GridMethod) -> SRResult<GridInterpolator> {
+        let min_points = if method == GridMethod::Nearest { 1 } else { 2 };
+        let x = check_abscissas(x, min_points)?;
+        let y = check_abscissas(y, min_points)?;
+        let (m, n) = (x.len(), y.len());
+        if z.num_rows() != m || z.num_cols() != n {
+            return Err(SRError::DimensionsMismatch);
+        }
+        let values : Vec<f64> = (0..m * n).map(|k| z.get(k % m, k / m).unwrap()).collect();
+        let mut g = GridInterpolator {
+            method : method,
+            x : x,
+            y : y,
+            z : values,
+            dx : Vec::new(),
+            dy : Vec::new(),
+            dxy : Vec::new()
+        };
+        if method == GridMethod::Bicubic || method == GridMethod::Spline {
+            g.dx = g.along_x(&g.z)?;
+            g.dy = g.along_y(&g.z)?;
+            g.dxy = g.along_y(&g.dx)?;
+        }
+        Ok(g)
+    }
+
+    /// Returns the derivatives along x of grid values
+    fn along_x(&self, v : &[f64]) -> SRResult<Vec<f64>> {
+        let m = self.x.len();
+        let mut d = vec![0.; v.len()];
+        for j in 0..self.y.len() {
+            let column = self.slopes(&self.x, &v[(j * m)..((j + 1) * m)])?;
+            d[(j * m)..((j + 1) * m)].copy_from_slice(&column);
+        }
+        Ok(d)
+    }
+
+    /// Returns the derivatives along y of grid values
+    fn along_y(&self, v : &[f64]) -> SRResult<Vec<f64>> {
+        let m = self.x.len();
+        let n = self.y.len();
+        let mut d = vec![0.; v.len()];
+        for i in 0..m {
+            let row : Vec<f64> = (0..n).map(|j| v[i + j * m]).collect();
+            for (j, s) in self.slopes(&self.y, &row)?.iter().enumerate() {
+                d[i + j * m] = *s;
+            }
+        }
+        Ok(d)
+    }
+
+    /// Returns the derivatives at the points t of the data f
+    fn slopes(&self, t : &[f64], f : &[f64]) -> SRResult<Vec<f64>> {
+        if self.method == GridMethod::Spline {
+            let s = cubic_spline(&vector_f64(t), &vector_f64(f), SplineBoundary::NotAKnot)?;
+            return Ok(s.slopes().to_vec());
+        }
+        Ok(difference_slopes(t, f))
+    }
+
+    /// Returns the interpolation method
+    pub fn method(&self) -> GridMethod {
+        self.method
+    }
+
+    /// Returns the value at (x, y)
+    pub fn value(&self, x : f64, y : f64) -> f64 {
+        let m = self.x.len();
+        match self.method {
+            GridMethod::Nearest => {
+                self.z[nearest_index(&self.x, x) + m * nearest_index(&self.y, y)]
+            },
+            GridMethod::Bilinear => {
+                let (i, j) = (find_interval(&self.x, x), find_interval(&self.y, y));
+                let u = (x - self.x[i]) / (self.x[i + 1] - self.x[i]);
+                let v = (y - self.y[j]) / (self.y[j + 1] - self.y[j]);
+                let k = i + m * j;
+                (1. - v) * ((1. - u) * self.z[k] + u * self.z[k + 1])
+                    + v * ((1. - u) * self.z[k + m] + u * self.z[k + m + 1])
+            },
+            GridMethod::Bicubic | GridMethod::Spline => {
+                let (i, j) = (find_interval(&self.x, x), find_interval(&self.y, y));
+                let hx = self.x[i + 1] - self.x[i];
+                let hy = self.y[j + 1] - self.y[j];
+                let (a, b) = hermite_basis((x - self.x[i]) / hx, hx);
+                let (c, d) = hermite_basis((y - self.y[j]) / hy, hy);
+                let mut s = 0.;
+                for p in 0..2 {
+                    for q in 0..2 {
+                        let k = i + p + m * (j + q);
+                        s += a[p] * c[q] * self.z[k] + b[p] * c[q] * self.dx[k]
+                            + a[p] * d[q] * self.dy[k] + b[p] * d[q] * self.dxy[k];
+                    }
+                }
+                s
+            }
+        }
+    }
+
+    /// Returns the values at the points (x_k, y_k) given by the
+    /// cells of two matrices of the same shape
+    pub fn evaluate(&self, x : &MatrixF64, y : &MatrixF64) -> SRResult<MatrixF64> {
+        if x.num_rows() != y.num_rows() || x.num_cols() != y.num_cols() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(Matrix::from_iter_cw(x.num_rows(), x.num_cols(),
+            (0..x.num_cells()).map(|k| self.value(x[k], y[k]))))
+    }
+
+    /// Returns the values on the grid of the points in the vectors
+    /// x and y as a matrix with one row per x and one column per y
+    pub fn resample(&self, x : &MatrixF64, y : &MatrixF64) -> SRResult<MatrixF64> {
+        let x = vector_entries(x)?;
+        let y = vector_entries(y)?;
+        let m = x.len();
+        Ok(Matrix::from_iter_cw(m, y.len(),
+            (0..m * y.len()).map(|k| self.value(x[k % m], y[k / m]))))
+    }
+}
+
+
+/// Returns the index of the point of t nearest to s; ties go to
+/// the right
+fn nearest_index(t : &[f64], s : f64) -> usize {
+    if t.len() == 1 {
+        return 0;
+    }
+    let i = find_interval(t, s);
+    if s - t[i] < t[i + 1] - s { i } else { i + 1 }
+}
+
+
+/// Returns the derivatives of the parabolas through three
+/// neighbouring points, or the secant for two points
+fn difference_slopes(t : &[f64], f : &[f64]) -> Vec<f64> {
+    let n = t.len();
+    let h : Vec<f64> = t.windows(2).map(|w| w[1] - w[0]).collect();
+    let d : Vec<f64> = (0..(n - 1)).map(|i| (f[i + 1] - f[i]) / h[i]).collect();
+    if n == 2 {
+        return vec![d[0]; 2];
+    }
+    let mut s = vec![0.; n];
+    for i in 1..(n - 1) {
+        s[i] = (h[i] * d[i - 1] + h[i - 1] * d[i]) / (h[i - 1] + h[i]);
+    }
+    s[0] = d[0] - h[0] * (d[1] - d[0]) / (h[0] + h[1]);
+    s[n - 1] = d[n - 2] + h[n - 2] * (d[n - 2] - d[n - 3]) / (h[n - 3] + h[n - 2]);
+    s
+}
+
+
+/// Returns the cubic Hermite basis at u for the values at both
+/// ends and for the slopes at both ends of an interval of length h
+fn hermite_basis(u : f64, h : f64) -> ([f64; 2], [f64; 2]) {
+    let u2 = u * u;
+    let u3 = u2 * u;
+    ([2. * u3 - 3. * u2 + 1., 3. * u2 - 2. * u3], [h * (u3 - 2. * u2 + u), h * (u3 - u2)])
+}
+
+
+/// Interpolates values on a grid along one axis: the rows of z
+/// belong to the points x. Returns the values at the points xi for
+/// every column of z.
+pub fn interpolate_rows<I:Interpolator, F:Fn(&MatrixF64, &MatrixF64) -> SRResult<I>>(x : &MatrixF64,
+    z : &MatrixF64, xi : &MatrixF64, build : F) -> SRResult<MatrixF64> {
+    let points = vector_entries(xi)?;
+    let mut columns = Vec::with_capacity(z.num_cols());
+    for j in 0..z.num_cols() {
+        let column = Matrix::from_iter_cw(z.num_rows(), 1, (0..z.num_rows()).map(|i| z.get(i, j).unwrap()));
+        columns.push(build(x, &column)?);
+    }
+    let m = points.len();
+    Ok(Matrix::from_iter_cw(m, columns.len(), (0..m * columns.len()).map(|k| columns[k / m].value(points[k % m]))))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use interp::piecewise::LinearInterpolator;
+
+    fn grid<F:Fn(f64, f64) -> f64>(x : &[f64], y : &[f64], f : F) -> MatrixF64 {
+        let m = x.len();
+        Matrix::from_iter_cw(m, y.len(), (0..m * y.len()).map(|k| f(x[k % m], y[k / m])))
+    }
+
+    #[test]
+    fn test_nearest_and_bilinear(){
+        let (xs, ys) = ([0., 1., 3.], [0., 2.]);
+        let f = |x : f64, y : f64| 1. + 2. * x - y + 0.5 * x * y;
+        let z = grid(&xs, &ys, f);
+        let (x, y) = (vector_f64(&xs), vector_f64(&ys));
+        let nearest = GridInterpolator::new(&x, &y, &z, GridMethod::Nearest).unwrap();
+        assert_eq!(nearest.value(0.4, 1.2), f(0., 2.));
+        assert_eq!(nearest.value(2.5, -3.), f(3., 0.));
+        let bilinear = GridInterpolator::new(&x, &y, &z, GridMethod::Bilinear).unwrap();
+        // Bilinear functions are reproduced, also outside
+        for &(u, v) in [(0.5, 0.5), (2.2, 1.7), (3., 2.), (-1., 4.)].iter() {
+            assert!((bilinear.value(u, v) - f(u, v)).abs() < 1e-14);
+        }
+        let r = bilinear.resample(&vector_f64(&[0.5, 1.5, 2.5]), &vector_f64(&[1., 1.5])).unwrap();
+        assert_eq!((r.num_rows(), r.num_cols()), (3, 2));
+        assert!((r.get(1, 1).unwrap() - f(1.5, 1.5)).abs() < 1e-14);
+        let e = bilinear.evaluate(&vector_f64(&[0.5, 2.]), &vector_f64(&[0.5, 1.])).unwrap();
+        assert!((e[1] - f(2., 1.)).abs() < 1e-14);
+        assert!(bilinear.evaluate(&vector_f64(&[0.5, 2.]), &vector_f64(&[0.5])).is_err());
+    }
+
+    #[test]
+    fn test_bicubic(){
+        let xs = [0., 0.5, 1.2, 2., 2.5];
+        let ys = [-1., 0., 0.4, 1.5];
+        let f = |x : f64, y : f64| x * x * y * y - 3. * x * y + y * y + 2.;
+        let z = grid(&xs, &ys, f);
+        let g = GridInterpolator::new(&vector_f64(&xs), &vector_f64(&ys), &z, GridMethod::Bicubic).unwrap();
+        for k in 0..25 {
+            let (u, v) = (0.1 * k as f64, -1. + 0.1 * k as f64);
+            assert!((g.value(u, v) - f(u, v)).abs() < 1e-12, "{} {}", u, v);
+        }
+        // Splines reproduce bicubic polynomials
+        let f = |x : f64, y : f64| x * x * x * y - y * y * y + x * y * y;
+        let z = grid(&xs, &ys, f);
+        let s = GridInterpolator::new(&vector_f64(&xs), &vector_f64(&ys), &z, GridMethod::Spline).unwrap();
+        for k in 0..25 {
+            let (u, v) = (0.1 * k as f64, 1.5 - 0.1 * k as f64);
+            assert!((s.value(u, v) - f(u, v)).abs() < 1e-12);
+        }
+        assert_eq!(s.method(), GridMethod::Spline);
+        assert!(GridInterpolator::new(&vector_f64(&xs), &vector_f64(&ys), &z.transpose(), GridMethod::Spline).is_err());
+        assert!(GridInterpolator::new(&vector_f64(&[0.]), &vector_f64(&[0.]), &z, GridMethod::Bilinear).is_err());
+    }
+
+    #[test]
+    fn test_interpolate_rows(){
+        let x = vector_f64(&[0., 1., 2., 3.]);
+        let z = Matrix::from_iter_cw(4, 2, (0..8).map(|k| if k < 4 { k as f64 } else { ((k - 4) * (k - 4)) as f64 }));
+        let r = interpolate_rows(&x, &z, &vector_f64(&[0.5, 2.5]), |x, y| cubic_spline(x, y, SplineBoundary::NotAKnot)).unwrap();
+        assert!((r.get(0, 0).unwrap() - 0.5).abs() < 1e-14);
+        assert!((r.get(1, 1).unwrap() - 6.25).abs() < 1e-14);
+        let r = interpolate_rows(&x, &z, &vector_f64(&[0.5]), LinearInterpolator::new).unwrap();
+        assert_eq!(r.get(0, 1).unwrap(), 0.5);
+    }
+}
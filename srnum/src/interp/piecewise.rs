@@ -0,0 +1,158 @@
+#![doc="Piecewise linear and nearest neighbour interpolation
+
+The data points (x_i, y_i) are given as two vectors of equal
+length with strictly increasing x_i. Outside [x_0, x_n-1] the
+linear interpolant continues the first or the last segment and
+the nearest neighbour interpolant keeps the end values.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use interp::traits::Interpolator;
+use interp::support::*;
+
+
+/// Piecewise linear interpolant
+#[derive(Debug, Clone)]
+pub struct LinearInterpolator {
+    x : Vec<f64>,
+    y : Vec<f64>,
+    /// Integrals from x_0 to x_i
+    areas : Vec<f64>
+}
+
+impl LinearInterpolator {
+
+    /// Interpolates at least two points
+    pub fn new(x : &MatrixF64, y : &MatrixF64) -> SRResult<LinearInterpolator> {
+        let (x, y) = check_points(x, y, 2)?;
+        let mut total = 0.;
+        let areas = (0..x.len()).map(|i| {
+            if i > 0 {
+                total += 0.5 * (x[i] - x[i - 1]) * (y[i - 1] + y[i]);
+            }
+            total
+        }).collect();
+        Ok(LinearInterpolator {
+            x : x,
+            y : y,
+            areas : areas
+        })
+    }
+
+    /// Returns the slope at x. At the data points the slope of
+    /// the segment to the right is returned.
+    pub fn derivative(&self, x : f64) -> f64 {
+        let i = find_interval(&self.x, x);
+        (self.y[i + 1] - self.y[i]) / (self.x[i + 1] - self.x[i])
+    }
+
+    /// Returns the integral from a to b
+    pub fn integral(&self, a : f64, b : f64) -> f64 {
+        self.antiderivative(b) - self.antiderivative(a)
+    }
+
+    /// Integral from x_0 to x
+    fn antiderivative(&self, x : f64) -> f64 {
+        let k = find_interval(&self.x, x);
+        let u = x - self.x[k];
+        self.areas[k] + u * (self.y[k] + 0.5 * u * self.derivative(x))
+    }
+}
+
+impl Interpolator for LinearInterpolator {
+
+    fn value(&self, x : f64) -> f64 {
+        let i = find_interval(&self.x, x);
+        let t = (x - self.x[i]) / (self.x[i + 1] - self.x[i]);
+        (1. - t) * self.y[i] + t * self.y[i + 1]
+    }
+}
+
+
+/// Nearest neighbour interpolant. Halfway between two points the
+/// right one is chosen.
+#[derive(Debug, Clone)]
+pub struct NearestInterpolator {
+    x : Vec<f64>,
+    y : Vec<f64>
+}
+
+impl NearestInterpolator {
+
+    /// Interpolates at least one point
+    pub fn new(x : &MatrixF64, y : &MatrixF64) -> SRResult<NearestInterpolator> {
+        let (x, y) = check_points(x, y, 1)?;
+        Ok(NearestInterpolator {
+            x : x,
+            y : y
+        })
+    }
+}
+
+impl Interpolator for NearestInterpolator {
+
+    fn value(&self, x : f64) -> f64 {
+        if self.x.len() == 1 {
+            return self.y[0];
+        }
+        let i = find_interval(&self.x, x);
+        if x - self.x[i] < self.x[i + 1] - x {
+            self.y[i]
+        }
+        else {
+            self.y[i + 1]
+        }
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_linear(){
+        let x = vector_f64(&[0., 1., 3.]);
+        let y = vector_f64(&[1., 3., 2.]);
+        let p = LinearInterpolator::new(&x, &y).unwrap();
+        assert_eq!(p.value(0.5), 2.);
+        assert_eq!(p.value(2.), 2.5);
+        assert_eq!(p.value(-1.), -1.);
+        assert_eq!(p.value(5.), 1.);
+        assert_eq!(p.derivative(2.), -0.5);
+        // Trapezoids 2 + 5
+        assert!((p.integral(0., 3.) - 7.).abs() < 1e-15);
+        assert!((p.integral(0.5, 2.) - (1.25 + 2.75)).abs() < 1e-15);
+        assert!((p.integral(2., 0.5) + 4.).abs() < 1e-15);
+        let v = p.evaluate(&vector_f64(&[0., 1., 2.]).transpose());
+        assert_eq!(v.num_rows(), 1);
+        assert_eq!(v[2], 2.5);
+        assert!(LinearInterpolator::new(&vector_f64(&[0.]), &vector_f64(&[0.])).is_err());
+    }
+
+    #[test]
+    fn test_nearest(){
+        let x = vector_f64(&[0., 1., 3.]);
+        let y = vector_f64(&[1., 3., 2.]);
+        let p = NearestInterpolator::new(&x, &y).unwrap();
+        assert_eq!(p.value(-4.), 1.);
+        assert_eq!(p.value(0.4), 1.);
+        assert_eq!(p.value(0.5), 3.);
+        assert_eq!(p.value(2.2), 2.);
+        assert_eq!(p.value(9.), 2.);
+        let single = NearestInterpolator::new(&vector_f64(&[2.]), &vector_f64(&[5.])).unwrap();
+        assert_eq!(single.value(-3.), 5.);
+    }
+}
@@ -0,0 +1,249 @@
+#![doc="Radial basis function interpolation of scattered data
+
+The data are values f_k at n distinct points p_k in d dimensions,
+given as the rows of an n x d matrix. The interpolant is
+
+s(p) = sum_k w_k phi(|p - p_k|) + c_0 + c_1 p_1 + ... + c_d p_d
+
+where the weights w and the coefficients c of the linear
+polynomial solve
+
+(K + lambda I) w + P c = f
+P' w = 0
+
+with K_kl = phi(|p_k - p_l|) and the rows (1, p_k) of P. A
+smoothing parameter lambda > 0 gives an approximation instead of
+an interpolant. The linear term makes the system uniquely
+solvable for all kernels here if the points do not lie on a
+hyperplane. The dense system is solved by LU decomposition, so
+the construction costs O(n^3).
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// srlinalg imports
+use srlinalg::lu::LUDecomposition;
+use srlinalg::factorization::Factorization;
+
+// local imports
+use interp::support::vector_entries;
+
+
+/// Radial basis functions phi(r)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RbfKernel {
+    /// r
+    Linear,
+    /// r^3
+    Cubic,
+    /// r^2 log r
+    ThinPlate,
+    /// exp(-(e r)^2) with the shape parameter e
+    Gaussian(f64),
+    /// sqrt(1 + (e r)^2) with the shape parameter e
+    Multiquadric(f64),
+    /// 1 / sqrt(1 + (e r)^2) with the shape parameter e
+    InverseMultiquadric(f64)
+}
+
+impl RbfKernel {
+
+    /// Returns phi(r)
+    pub fn value(&self, r : f64) -> f64 {
+        match *self {
+            RbfKernel::Linear => r,
+            RbfKernel::Cubic => r * r * r,
+            RbfKernel::ThinPlate => if r > 0. { r * r * r.ln() } else { 0. },
+            RbfKernel::Gaussian(e) => (-(e * r) * (e * r)).exp(),
+            RbfKernel::Multiquadric(e) => (1. + (e * r) * (e * r)).sqrt(),
+            RbfKernel::InverseMultiquadric(e) => 1. / (1. + (e * r) * (e * r)).sqrt()
+        }
+    }
+}
+
+
+/// Radial basis function interpolant
+#[derive(Debug, Clone)]
+pub struct RbfInterpolator {
+    kernel : RbfKernel,
+    /// The points as rows
+    points : Vec<Vec<f64>>,
+    weights : Vec<f64>,
+    /// Coefficients of 1, p_1, ..., p_d
+    polynomial : Vec<f64>
+}
+
+impl RbfInterpolator {
+
+    /// Interpolates the values at the points given by the rows
+    pub fn new(points : &MatrixF64, values : &MatrixF64, kernel : RbfKernel) -> SRResult<RbfInterpolator> {
+        RbfInterpolator::with_smoothing(points, values, kernel, 0.)
+    }
+
+    /// Approximates the values at the points given by the rows
+    /// with the smoothing parameter lambda >= 0
+    pub fn with_smoothing(points : &MatrixF64, values : &MatrixF64, kernel : RbfKernel,
+        lambda : f64) -> SRResult<RbfInterpolator> {
+        let n = points.num_rows();
+        let d = points.num_cols();
+        if n == 0 || d == 0 {
+            return Err(SRError::EmptyMatrix);
+        }
+        let values = vector_entries(values)?;
+        if values.len() != n {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if lambda.is_nan() || lambda < 0. {
+            return Err(SRError::InvalidArgument);
+        }
+        let rows : Vec<Vec<f64>> = (0..n).map(|k| (0..d).map(|j| points.get(k, j).unwrap()).collect()).collect();
+        if rows.iter().any(|p| p.iter().any(|v| !v.is_finite())) {
+            return Err(SRError::InvalidArgument);
+        }
+        // The saddle point matrix [K + lambda I, P; P', 0]
+        let size = n + d + 1;
+        let a = Matrix::from_iter_cw(size, size, (0..size * size).map(|k| {
+            let (r, c) = (k % size, k / size);
+            if r < n && c < n {
+                let diagonal = if r == c { lambda } else { 0. };
+                kernel.value(distance(&rows[r], &rows[c])) + diagonal
+            }
+            else if r < n {
+                polynomial_term(&rows[r], c - n)
+            }
+            else if c < n {
+                polynomial_term(&rows[c], r - n)
+            }
+            else {
+                0.
+            }
+        }));
+        let mut lu = LUDecomposition::new(a);
+        lu.decompose_ero();
+        if lu.is_singular() {
+            return Err(SRError::IsSingular);
+        }
+        let b = Matrix::from_iter_cw(size, 1, (0..size).map(|k| if k < n { values[k] } else { 0. }));
+        let x = lu.solve(&b)?;
+        Ok(RbfInterpolator {
+            kernel : kernel,
+            points : rows,
+            weights : (0..n).map(|k| x[k]).collect(),
+            polynomial : (n..size).map(|k| x[k]).collect()
+        })
+    }
+
+    /// Returns the number of dimensions
+    pub fn dimension(&self) -> usize {
+        self.polynomial.len() - 1
+    }
+
+    /// Returns the value at a point
+    pub fn value(&self, point : &[f64]) -> SRResult<f64> {
+        if point.len() != self.dimension() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        let s = self.points.iter().zip(self.weights.iter())
+            .fold(0., |s, (p, w)| s + w * self.kernel.value(distance(p, point)));
+        Ok(self.polynomial.iter().enumerate().fold(s, |s, (j, c)| s + c * polynomial_term(point, j)))
+    }
+
+    /// Returns the column vector of the values at the points given
+    /// by the rows
+    pub fn evaluate(&self, points : &MatrixF64) -> SRResult<MatrixF64> {
+        let d = self.dimension();
+        if points.num_cols() != d {
+            return Err(SRError::DimensionsMismatch);
+        }
+        let mut values = Vec::with_capacity(points.num_rows());
+        for k in 0..points.num_rows() {
+            let p : Vec<f64> = (0..d).map(|j| points.get(k, j).unwrap()).collect();
+            values.push(self.value(&p)?);
+        }
+        Ok(Matrix::from_iter_cw(values.len(), 1, values.into_iter()))
+    }
+}
+
+
+/// Returns the Euclidean distance of two points
+fn distance(p : &[f64], q : &[f64]) -> f64 {
+    p.iter().zip(q.iter()).fold(0., |s, (a, b)| s + (a - b) * (a - b)).sqrt()
+}
+
+
+/// Returns the polynomial basis 1, p_1, ..., p_d
+fn polynomial_term(p : &[f64], j : usize) -> f64 {
+    if j == 0 { 1. } else { p[j - 1] }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn scattered() -> (MatrixF64, Vec<[f64; 2]>) {
+        let pts = vec![[0., 0.], [1., 0.], [0., 1.], [1., 1.], [0.5, 0.5], [0.2, 0.7],
+            [0.8, 0.3], [0.4, 0.1], [0.9, 0.8], [0.1, 0.4]];
+        let m = Matrix::from_iter_rw(pts.len(), 2, pts.iter().flat_map(|p| p.iter().cloned()));
+        (m, pts)
+    }
+
+    #[test]
+    fn test_interpolation(){
+        let (m, pts) = scattered();
+        let f = |p : &[f64]| (p[0] + 2. * p[1]).sin();
+        let values = vector_f64(&pts.iter().map(|p| f(p)).collect::<Vec<f64>>());
+        let kernels = [RbfKernel::Linear, RbfKernel::Cubic, RbfKernel::ThinPlate,
+            RbfKernel::Gaussian(2.), RbfKernel::Multiquadric(1.), RbfKernel::InverseMultiquadric(1.)];
+        for kernel in kernels.iter() {
+            let s = RbfInterpolator::new(&m, &values, *kernel).unwrap();
+            assert_eq!(s.dimension(), 2);
+            for p in pts.iter() {
+                assert!((s.value(p).unwrap() - f(p)).abs() < 1e-10, "{:?}", kernel);
+            }
+            // A reasonable approximation between the points
+            assert!((s.value(&[0.6, 0.6]).unwrap() - f(&[0.6, 0.6])).abs() < 0.1, "{:?}", kernel);
+        }
+        let s = RbfInterpolator::new(&m, &values, RbfKernel::ThinPlate).unwrap();
+        let e = s.evaluate(&m).unwrap();
+        assert_eq!((e.num_rows(), e.num_cols()), (10, 1));
+        assert!((e[4] - f(&[0.5, 0.5])).abs() < 1e-10);
+        assert!(s.value(&[0.5]).is_err());
+    }
+
+    #[test]
+    fn test_linear_reproduction_and_smoothing(){
+        let (m, pts) = scattered();
+        let f = |p : &[f64]| 3. - p[0] + 0.5 * p[1];
+        let values = vector_f64(&pts.iter().map(|p| f(p)).collect::<Vec<f64>>());
+        // Linear functions are reproduced exactly, even with smoothing
+        let s = RbfInterpolator::with_smoothing(&m, &values, RbfKernel::ThinPlate, 0.1).unwrap();
+        assert!((s.value(&[2., -1.]).unwrap() - f(&[2., -1.])).abs() < 1e-10);
+        // Smoothing no longer interpolates noisy data
+        let noisy = vector_f64(&pts.iter().enumerate().map(|(k, p)| f(p) + if k % 2 == 0 { 0.1 } else { -0.1 }).collect::<Vec<f64>>());
+        let exact = RbfInterpolator::new(&m, &noisy, RbfKernel::Cubic).unwrap();
+        let smooth = RbfInterpolator::with_smoothing(&m, &noisy, RbfKernel::Cubic, 10.).unwrap();
+        assert!((exact.value(&pts[0]).unwrap() - noisy[0]).abs() < 1e-10);
+        assert!((smooth.value(&pts[0]).unwrap() - f(&pts[0])).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_errors(){
+        let (m, _) = scattered();
+        assert!(RbfInterpolator::new(&m, &vector_f64(&[1., 2.]), RbfKernel::Linear).is_err());
+        assert!(RbfInterpolator::with_smoothing(&m, &vector_f64(&[0.; 10]), RbfKernel::Linear, -1.).is_err());
+        // Repeated points make the system singular
+        let twice = Matrix::from_iter_rw(4, 1, [0., 1., 1., 2.].iter().cloned());
+        assert!(RbfInterpolator::new(&twice, &vector_f64(&[0., 1., 1., 2.]), RbfKernel::Cubic).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+#![doc="Common checks, interval search and band solver for interpolation
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+
+/// Returns the entries of a vector (or of a single sample)
+pub fn vector_entries(v : &MatrixF64) -> SRResult<Vec<f64>> {
+    if v.num_cells() == 0 {
+        return Err(SRError::EmptyMatrix);
+    }
+    if !v.is_row() && !v.is_col() {
+        return Err(SRError::IsNotAVector);
+    }
+    Ok((0..v.num_cells()).map(|i| v[i]).collect())
+}
+
+
+/// Returns strictly increasing abscissas with at least
+/// ``min_points`` entries
+pub fn check_abscissas(x : &MatrixF64, min_points : usize) -> SRResult<Vec<f64>> {
+    let x = vector_entries(x)?;
+    if x.len() < min_points {
+        return Err(SRError::InvalidArgument);
+    }
+    if x.iter().any(|v| !v.is_finite()) || (1..x.len()).any(|i| x[i] <= x[i - 1]) {
+        return Err(SRError::InvalidArgument);
+    }
+    Ok(x)
+}
+
+
+/// Returns the data points (x_i, y_i) with strictly increasing
+/// x_i. At least ``min_points`` points are required.
+pub fn check_points(x : &MatrixF64, y : &MatrixF64, min_points : usize) -> SRResult<(Vec<f64>, Vec<f64>)> {
+    let xs = check_abscissas(x, min_points)?;
+    let ys = vector_entries(y)?;
+    if xs.len() != ys.len() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    Ok((xs, ys))
+}
+
+
+/// Returns the index i of the interval [x_i, x_i+1] containing t.
+/// Points outside belong to the first or the last interval.
+pub fn find_interval(x : &[f64], t : f64) -> usize {
+    let n = x.len();
+    if n < 2 {
+        return 0;
+    }
+    let k = x.partition_point(|v| *v <= t);
+    k.max(1).min(n - 1) - 1
+}
+
+
+/// Square band matrix with ``lower`` sub-diagonals and ``upper``
+/// super-diagonals. The system is solved by Gaussian elimination
+/// without pivoting, which is stable for the diagonally dominant,
+/// totally positive or positive definite matrices of spline
+/// problems.
+pub struct BandMatrix {
+    n : usize,
+    lower : usize,
+    upper : usize,
+    /// Row i holds the columns i - lower ..= i + upper
+    data : Vec<f64>
+}
+
+impl BandMatrix {
+
+    /// Creates a zero band matrix
+    pub fn new(n : usize, lower : usize, upper : usize) -> BandMatrix {
+        BandMatrix {
+            n : n,
+            lower : lower,
+            upper : upper,
+            data : vec![0.; n * (lower + upper + 1)]
+        }
+    }
+
+    fn index(&self, i : usize, j : usize) -> usize {
+        debug_assert!(j + self.lower >= i && j <= i + self.upper);
+        i * (self.lower + self.upper + 1) + j + self.lower - i
+    }
+
+    /// Returns the entry (i, j) inside the band
+    pub fn get(&self, i : usize, j : usize) -> f64 {
+        self.data[self.index(i, j)]
+    }
+
+    /// Sets the entry (i, j) inside the band
+    pub fn set(&mut self, i : usize, j : usize, value : f64) {
+        let k = self.index(i, j);
+        self.data[k] = value;
+    }
+
+    /// Adds to the entry (i, j) inside the band
+    pub fn add(&mut self, i : usize, j : usize, value : f64) {
+        let k = self.index(i, j);
+        self.data[k] += value;
+    }
+
+    /// Solves A x = b, consuming the matrix
+    pub fn solve(mut self, b : &[f64]) -> SRResult<Vec<f64>> {
+        let n = self.n;
+        if b.len() != n {
+            return Err(SRError::DimensionsMismatch);
+        }
+        let mut x = b.to_vec();
+        for k in 0..n {
+            let pivot = self.get(k, k);
+            if pivot == 0. || !pivot.is_finite() {
+                return Err(SRError::IsSingular);
+            }
+            for i in (k + 1)..n.min(k + self.lower + 1) {
+                let factor = self.get(i, k) / pivot;
+                if factor == 0. {
+                    continue;
+                }
+                for j in k..n.min(k + self.upper + 1) {
+                    let v = self.get(k, j);
+                    self.add(i, j, -factor * v);
+                }
+                x[i] -= factor * x[k];
+            }
+        }
+        for k in (0..n).rev() {
+            let mut v = x[k];
+            for j in (k + 1)..n.min(k + self.upper + 1) {
+                v -= self.get(k, j) * x[j];
+            }
+            x[k] = v / self.get(k, k);
+        }
+        Ok(x)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_find_interval(){
+        let x = [0., 1., 2., 3.];
+        assert_eq!(find_interval(&x, -1.), 0);
+        assert_eq!(find_interval(&x, 0.), 0);
+        assert_eq!(find_interval(&x, 1.5), 1);
+        assert_eq!(find_interval(&x, 2.), 2);
+        assert_eq!(find_interval(&x, 3.), 2);
+        assert_eq!(find_interval(&x, 7.), 2);
+    }
+
+    #[test]
+    fn test_band_solve(){
+        // Tridiagonal system with the solution 1, 2, 3, 4
+        let mut a = BandMatrix::new(4, 1, 1);
+        for i in 0..4 {
+            a.set(i, i, 4.);
+            if i > 0 {
+                a.set(i, i - 1, 1.);
+            }
+            if i < 3 {
+                a.set(i, i + 1, 2.);
+            }
+        }
+        let x = a.solve(&[8., 15., 22., 19.]).unwrap();
+        for (i, v) in x.iter().enumerate() {
+            assert!((v - (i as f64 + 1.)).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_checks(){
+        let x = vector_f64(&[0., 1., 2.]);
+        assert!(check_points(&x, &vector_f64(&[1., 2.]), 2).is_err());
+        assert!(check_points(&vector_f64(&[0., 0., 1.]), &x, 2).is_err());
+        assert!(check_points(&x, &x, 4).is_err());
+        assert_eq!(check_points(&x, &x.transpose(), 2).unwrap().1, vec![0., 1., 2.]);
+    }
+}
@@ -0,0 +1,23 @@
+#![doc="Common interface of one dimensional interpolants
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+
+/// A function of one variable built from data
+pub trait Interpolator {
+
+    /// Returns the value at x
+    fn value(&self, x : f64) -> f64;
+
+    /// Returns the values at the cells of x in a matrix of the
+    /// same shape
+    fn evaluate(&self, x : &MatrixF64) -> MatrixF64 {
+        Matrix::from_iter_cw(x.num_rows(), x.num_cols(),
+            (0..x.num_cells()).map(|i| self.value(x[i])))
+    }
+}
@@ -1,3 +1,147 @@
+#![doc="Numerical methods
+
+* ``ad``: automatic differentiation
+* ``diff``: numerical differentiation
+* ``quad``: numerical integration
+* ``ode``: ordinary differential equations
+* ``interp``: interpolation and spline fitting
+* ``poly``: polynomials
+* ``special``: special functions
+"]
+
+extern crate num;
+extern crate sralgebra;
+extern crate srmatrix;
+extern crate srlinalg;
+
+pub mod ad {
+#![doc="Automatic differentiation
+
+* ``dual``: forward mode with dual numbers
+* ``tape``: reverse mode on a tape of recorded operations
+"]
+    pub mod dual;
+    pub mod tape;
+}
+
+pub mod diff {
+#![doc="Numerical differentiation
+
+* ``options``: difference schemes, options and results
+* ``finite``: finite differences of arbitrary order
+* ``richardson``: Richardson extrapolation of finite differences
+* ``multivariate``: gradients, Jacobians and Hessians
+* ``complex_step``: complex step differentiation
+"]
+    pub mod options;
+    pub mod finite;
+    pub mod richardson;
+    pub mod multivariate;
+    pub mod complex_step;
+}
+
+pub mod quad {
+#![doc="Numerical integration
+
+* ``options``: tolerances and results
+* ``sampled``: trapezoid and Simpson rules for sampled data
+* ``gauss``: Gauss-Legendre, Gauss-Laguerre and Gauss-Hermite rules
+* ``kronrod``: adaptive Gauss-Kronrod quadrature
+* ``tanh_sinh``: tanh-sinh quadrature for endpoint singularities
+* ``nested``: iterated integrals over two dimensional regions
+"]
+    pub mod options;
+    pub mod sampled;
+    pub mod gauss;
+    pub mod kronrod;
+    pub mod tanh_sinh;
+    pub mod nested;
+}
+
+pub mod ode {
+#![doc="Initial value problems of ordinary differential equations
+
+* ``system``: systems y' = f(t, y) with Jacobians and events
+* ``options``: options, methods and solutions
+* ``dense``: interpolants of the steps
+* ``stepper``: common interface and step size control
+* ``explicit``: RK4, Dormand-Prince and Tsitouras
+* ``rosenbrock``: Rosenbrock method for stiff problems
+* ``radau``: implicit Runge-Kutta method Radau IIA
+* ``bdf``: backward differentiation formulas
+* ``solve``: the driver with output and event location
+"]
+    mod vecops;
+    pub mod system;
+    pub mod options;
+    pub mod dense;
+    pub mod stepper;
+    pub mod explicit;
+    pub mod rosenbrock;
+    pub mod radau;
+    pub mod bdf;
+    pub mod solve;
+}
+
+pub mod interp {
+#![doc="Interpolation and spline fitting
+
+* ``traits``: common interface of one dimensional interpolants
+* ``piecewise``: linear and nearest neighbour interpolation
+* ``cubic``: cubic splines, PCHIP and Akima interpolation
+* ``bspline``: B-splines with derivatives, integrals and fitting
+* ``grid``: nearest, bilinear, bicubic and spline interpolation on grids
+* ``rbf``: radial basis function interpolation of scattered data
+"]
+    mod support;
+    pub mod traits;
+    pub mod piecewise;
+    pub mod cubic;
+    pub mod bspline;
+    pub mod grid;
+    pub mod rbf;
+}
+
+pub mod poly {
+#![doc="Polynomials
+
+* ``polynomial``: polynomial arithmetic, division and GCD
+* ``roots``: roots from companion matrix eigenvalues
+* ``fit``: least squares polynomial fitting
+* ``orthogonal``: Chebyshev, Legendre, Hermite and Laguerre polynomials
+"]
+
+    pub mod polynomial;
+    pub mod roots;
+    pub mod fit;
+    pub mod orthogonal;
+}
+
+pub mod special {
+#![doc="Special functions
+
+* ``gamma``: gamma, digamma and incomplete gamma functions
+* ``beta``: beta and incomplete beta functions
+* ``erf``: error functions and their inverses
+* ``bessel``: Bessel functions of real order and Airy functions
+* ``elliptic``: Carlson and Legendre elliptic integrals
+* ``zeta``: Riemann and Hurwitz zeta functions
+
+The functions are generic over ``Float`` types. They evaluate in
+double precision and round the result, and return NaN outside of
+their domains.
+"]
+    mod support;
+    pub mod gamma;
+    pub mod beta;
+    pub mod erf;
+    pub mod bessel;
+    pub mod elliptic;
+    pub mod zeta;
+}
+
+pub mod api;
+
 #[cfg(test)]
 mod tests {
     #[test]
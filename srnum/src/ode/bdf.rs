@@ -0,0 +1,366 @@
+#![doc="Backward differentiation formulas for stiff problems
+
+A variable order (1 to 5) quasi-constant step size
+implementation in the style of MATLAB's ode15s and SciPy
+(Shampine and Reichelt, 1997). The method keeps the backward
+differences D_0 = y, D_1, .., D_{k+2} of the solution on an
+equally spaced grid. Every step predicts y from the
+differences and solves the implicit formula
+
+y - c f(t, y) = y_pred - psi
+
+by a simplified Newton iteration with the matrix I - c J,
+c = h / alpha_k. The numerical differentiation formulas
+(NDF) use the coefficients kappa to improve the stability
+of the higher orders. A change of the step size
+interpolates the differences to the new grid. The order is
+changed after k + 1 steps of equal size if the error
+estimates of the neighboring orders are smaller.
+"]
+
+
+// std imports
+use std::f64;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// srlinalg imports
+use srlinalg::lu::{lu_factor, lu_solve};
+
+// local imports
+use ode::system::*;
+use ode::options::*;
+use ode::dense::StepInterpolant;
+use ode::stepper::*;
+use ode::vecops::*;
+
+
+/// Highest order of the formulas
+const MAX_ORDER : usize = 5;
+/// Maximum number of Newton iterations in a step
+const MAX_NEWTON : usize = 4;
+/// The NDF coefficients
+const KAPPA : [f64; MAX_ORDER + 1] = [0., -0.1850, -1. / 9., -0.0823, -0.0415, 0.];
+
+/// Solution of the implicit formula, its correction and the
+/// number of Newton iterations
+type FormulaSolution = (Vec<f64>, Vec<f64>, usize);
+
+
+/// Returns the matrix which maps the differences of step size h
+/// to the differences of step size factor h
+fn change_matrix(order : usize, factor : f64) -> Vec<Vec<f64>> {
+    let mut m = vec![vec![1.; order + 1]; order + 1];
+    for i in 1..(order + 1) {
+        for j in 1..(order + 1) {
+            let (fi, fj) = (i as f64, j as f64);
+            m[i][j] = m[i - 1][j] * (fi - 1. - factor * fj) / fi;
+        }
+    }
+    for row in m.iter_mut().skip(1) {
+        row[0] = 0.;
+    }
+    m
+}
+
+
+/// BDF integrator
+pub struct BdfStepper {
+    control : StepControl,
+    t : f64,
+    /// The backward differences
+    d : Vec<Vec<f64>>,
+    order : usize,
+    equal_steps : usize,
+    gamma : Vec<f64>,
+    alpha : Vec<f64>,
+    error_constants : Vec<f64>,
+    jacobian : MatrixF64,
+    current_jacobian : bool,
+    newton_tolerance : f64,
+    t_old : f64,
+    /// Order, signed step size and differences of the last step
+    last : (usize, f64, Vec<Vec<f64>>),
+    counters : Counters
+}
+
+impl BdfStepper {
+
+    /// Starts the integration at (t0, y0) towards t_end
+    pub fn new<S:OdeSystem + ?Sized>(system : &S, t0 : f64, y0 : &[f64], t_end : f64,
+        options : &OdeOptions) -> SRResult<BdfStepper> {
+        let mut counters = Counters::default();
+        let f = evaluate(system, t0, y0, &mut counters)?;
+        let mut control = StepControl::new(options, t0, t_end);
+        control.select_initial_step(system, t0, y0, &f, 1, &mut counters)?;
+        let n = y0.len();
+        let mut d = vec![vec![0.; n]; MAX_ORDER + 3];
+        d[0] = y0.to_vec();
+        d[1] = f.iter().map(|v| v * control.h_abs * control.direction).collect();
+        let mut gamma = vec![0.; MAX_ORDER + 1];
+        for k in 1..(MAX_ORDER + 1) {
+            gamma[k] = gamma[k - 1] + 1. / k as f64;
+        }
+        let alpha : Vec<f64> = (0..(MAX_ORDER + 1)).map(|k| (1. - KAPPA[k]) * gamma[k]).collect();
+        let error_constants = (0..(MAX_ORDER + 1)).map(|k| KAPPA[k] * gamma[k] + 1. / (k as f64 + 1.)).collect();
+        let jacobian = evaluate_jacobian(system, t0, y0, &mut counters)?;
+        let rtol = options.relative_tolerance;
+        Ok(BdfStepper {
+            control : control,
+            t : t0,
+            d : d.clone(),
+            order : 1,
+            equal_steps : 0,
+            gamma : gamma,
+            alpha : alpha,
+            error_constants : error_constants,
+            jacobian : jacobian,
+            current_jacobian : true,
+            newton_tolerance : (10. * f64::EPSILON / rtol).max(0.03f64.min(rtol.sqrt())),
+            t_old : t0,
+            last : (0, 0., vec![d[0].clone()]),
+            counters : counters
+        })
+    }
+
+    /// Rescales the differences for a step size multiplied by factor
+    fn change_step(&mut self, factor : f64) {
+        let order = self.order;
+        let r = change_matrix(order, factor);
+        let u = change_matrix(order, 1.);
+        // RU = R U, D := (RU)' D
+        let ru : Vec<Vec<f64>> = (0..(order + 1)).map(|i| (0..(order + 1))
+            .map(|j| (0..(order + 1)).fold(0., |s, k| s + r[i][k] * u[k][j])).collect()).collect();
+        let n = self.d[0].len();
+        let old : Vec<Vec<f64>> = self.d[..(order + 1)].to_vec();
+        for j in 0..(order + 1) {
+            for p in 0..n {
+                self.d[j][p] = (0..(order + 1)).fold(0., |s, i| s + ru[i][j] * old[i][p]);
+            }
+        }
+        self.equal_steps = 0;
+    }
+
+    /// Solves the implicit formula by simplified Newton iterations.
+    /// Returns the solution, its difference to the prediction and
+    /// the number of iterations, or None on failure.
+    fn solve_formula<S:OdeSystem + ?Sized>(&mut self, system : &S, t_new : f64, y_predict : &[f64],
+        c : f64, psi : &[f64], scale : &[f64]) -> SRResult<Option<FormulaSolution>> {
+        let n = y_predict.len();
+        self.counters.decompositions += 1;
+        let lu = match lu_factor(&shifted_identity(c, &self.jacobian)) {
+            Ok(lu) => lu,
+            Err(SRError::IsSingular) => return Ok(None),
+            Err(e) => return Err(e)
+        };
+        let mut y = y_predict.to_vec();
+        let mut d = vec![0.; n];
+        let mut previous : Option<f64> = None;
+        for k in 0..MAX_NEWTON {
+            let f = evaluate(system, t_new, &y, &mut self.counters)?;
+            if f.iter().any(|v| !v.is_finite()) {
+                return Ok(None);
+            }
+            let rhs : Vec<f64> = (0..n).map(|i| c * f[i] - psi[i] - d[i]).collect();
+            let dy = lu_solve(&lu, &rhs)?;
+            let norm = rms_norm(&dy, scale);
+            let rate = previous.map(|p| norm / p);
+            if let Some(rate) = rate {
+                if rate >= 1. || rate.powi((MAX_NEWTON - k) as i32) / (1. - rate) * norm > self.newton_tolerance {
+                    return Ok(None);
+                }
+            }
+            for i in 0..n {
+                y[i] += dy[i];
+                d[i] += dy[i];
+            }
+            if norm == 0. || rate.is_some_and(|r| r / (1. - r) * norm < self.newton_tolerance) {
+                return Ok(Some((y, d, k + 1)));
+            }
+            previous = Some(norm);
+        }
+        Ok(None)
+    }
+}
+
+impl Stepper for BdfStepper {
+
+    fn t(&self) -> f64 {
+        self.t
+    }
+
+    fn y(&self) -> &[f64] {
+        &self.d[0]
+    }
+
+    fn step<S:OdeSystem + ?Sized>(&mut self, system : &S, t_end : f64) -> SRResult<()> {
+        let n = self.d[0].len();
+        let direction = self.control.direction;
+        if self.control.h_abs > self.control.max_step {
+            let factor = self.control.max_step / self.control.h_abs;
+            self.change_step(factor);
+            self.control.h_abs = self.control.max_step;
+        }
+        let (d, h, error_norm, safety, t_new) = loop {
+            check_step_size(self.t, self.control.h_abs)?;
+            let mut h = direction * self.control.h_abs;
+            let mut t_new = self.t + h;
+            if direction * (t_new - t_end) > 0. {
+                t_new = t_end;
+                let factor = (t_new - self.t).abs() / self.control.h_abs;
+                self.change_step(factor);
+                self.control.h_abs = (t_new - self.t).abs();
+                h = t_new - self.t;
+            }
+            let order = self.order;
+            let y_predict = add_scaled(&vec![0.; n], 1., &vec![1.; order + 1], &self.d);
+            let scale = self.control.scale(&y_predict, &y_predict);
+            let psi = add_scaled(&vec![0.; n], 1. / self.alpha[order], &self.gamma[1..(order + 1)], &self.d[1..]);
+            let c = h / self.alpha[order];
+            let (y_new, d, iterations) = match self.solve_formula(system, t_new, &y_predict, c, &psi, &scale)? {
+                Some(r) => r,
+                None => {
+                    if self.current_jacobian {
+                        self.control.h_abs *= 0.5;
+                        self.change_step(0.5);
+                    }
+                    else {
+                        self.jacobian = evaluate_jacobian(system, t_new, &y_predict, &mut self.counters)?;
+                        self.current_jacobian = true;
+                    }
+                    self.counters.rejected += 1;
+                    continue;
+                }
+            };
+            let safety = SAFETY * (2. * MAX_NEWTON as f64 + 1.) / (2. * MAX_NEWTON as f64 + iterations as f64);
+            let scale = self.control.scale(&y_new, &y_new);
+            let error : Vec<f64> = d.iter().map(|v| self.error_constants[order] * v).collect();
+            let error_norm = rms_norm(&error, &scale);
+            if error_norm > 1. {
+                let factor = MIN_FACTOR.max(safety * error_norm.powf(-1. / (order as f64 + 1.)));
+                self.control.h_abs *= factor;
+                self.change_step(factor);
+                self.counters.rejected += 1;
+                continue;
+            }
+            break (d, h, error_norm, safety, t_new);
+        };
+        // Accept the step and update the differences
+        self.counters.accepted += 1;
+        self.current_jacobian = false;
+        self.equal_steps += 1;
+        let order = self.order;
+        self.t_old = self.t;
+        self.t = t_new;
+        for p in 0..n {
+            self.d[order + 2][p] = d[p] - self.d[order + 1][p];
+            self.d[order + 1][p] = d[p];
+        }
+        for i in (0..(order + 1)).rev() {
+            for p in 0..n {
+                let v = self.d[i + 1][p];
+                self.d[i][p] += v;
+            }
+        }
+        self.last = (order, h, self.d[..(order + 1)].to_vec());
+        if self.equal_steps < order + 1 {
+            return Ok(());
+        }
+        // Consider a change of the order
+        let scale = self.control.scale(&self.d[0], &self.d[0]);
+        let norm_of = |k : usize, v : &[f64]| {
+            let e : Vec<f64> = v.iter().map(|x| self.error_constants[k] * x).collect();
+            rms_norm(&e, &scale)
+        };
+        let error_m = if order > 1 { norm_of(order - 1, &self.d[order]) } else { f64::INFINITY };
+        let error_p = if order < MAX_ORDER { norm_of(order + 1, &self.d[order + 2]) } else { f64::INFINITY };
+        let norms = [error_m, error_norm, error_p];
+        let factors : Vec<f64> = norms.iter().enumerate().map(|(i, e)| {
+            let exponent = -1. / (order as f64 + i as f64);
+            if *e == 0. { f64::INFINITY } else { e.powf(exponent) }
+        }).collect();
+        let best = (0..3).fold(1, |b, i| if factors[i] > factors[b] { i } else { b });
+        self.order = order + best - 1;
+        let factor = MAX_FACTOR.min(safety * factors[best]);
+        self.control.h_abs *= factor;
+        self.change_step(factor);
+        Ok(())
+    }
+
+    fn interpolant(&self) -> StepInterpolant {
+        // The differences after the last step on the grid t - j h
+        let (order, h, ref d) = self.last;
+        let t_shift = (0..order).map(|j| self.t - h * j as f64).collect();
+        let denominators = (0..order).map(|j| h * (j as f64 + 1.)).collect();
+        StepInterpolant::differences(self.t_old, self.t, t_shift, denominators, d.clone())
+    }
+
+    fn counters(&self) -> Counters {
+        self.counters
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_change_matrix(){
+        // No change for factor 1: U U = I
+        let u = change_matrix(3, 1.);
+        for i in 0..4 {
+            for j in 0..4 {
+                let s = (0..4).fold(0., |s, k| s + u[i][k] * u[k][j]);
+                assert!((s - if i == j { 1. } else { 0. }).abs() < 1e-14);
+            }
+        }
+    }
+
+    #[test]
+    fn test_robertson(){
+        // Robertson's chemical kinetics, a classic stiff problem
+        let system = FnOdeSystem::new(|_, y : &MatrixF64| {
+            vector_f64(&[
+                -0.04 * y[0] + 1e4 * y[1] * y[2],
+                0.04 * y[0] - 1e4 * y[1] * y[2] - 3e7 * y[1] * y[1],
+                3e7 * y[1] * y[1]])
+        });
+        let options = OdeOptions::new().method(OdeMethod::Bdf).tolerances(1e-6, 1e-10);
+        let mut s = BdfStepper::new(&system, 0., &[1., 0., 0.], 40., &options).unwrap();
+        let mut max_order = 1;
+        while s.t() < 40. {
+            s.step(&system, 40.).unwrap();
+            max_order = max_order.max(s.order);
+        }
+        // Reference values at t = 40 (Hairer and Wanner)
+        let y = s.y();
+        assert!((y[0] - 0.715_827_068_7).abs() < 1e-4, "{:?}", y);
+        assert!((y[1] - 9.185_352_2e-6).abs() < 1e-8, "{:?}", y);
+        assert!((y[0] + y[1] + y[2] - 1.).abs() < 1e-8);
+        assert!(max_order > 2);
+        assert!(s.counters().accepted < 500, "{:?}", s.counters());
+    }
+
+    #[test]
+    fn test_accuracy(){
+        // y' = -y + sin t with y(0) = 1
+        let system = FnOdeSystem::new(|t : f64, y : &MatrixF64| vector_f64(&[-y[0] + t.sin()]));
+        let exact = |t : f64| 1.5 * (-t).exp() + 0.5 * (t.sin() - t.cos());
+        let options = OdeOptions::new().method(OdeMethod::Bdf).tolerances(1e-8, 1e-10);
+        let mut s = BdfStepper::new(&system, 0., &[1.], 5., &options).unwrap();
+        while s.t() < 5. {
+            s.step(&system, 5.).unwrap();
+            let tm = 0.5 * (s.t_old + s.t());
+            assert!((s.interpolant().values(tm)[0] - exact(tm)).abs() < 1e-5);
+        }
+        assert!((s.y()[0] - exact(5.)).abs() < 1e-6);
+    }
+}
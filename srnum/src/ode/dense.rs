@@ -0,0 +1,256 @@
+#![doc="Dense output of the ODE solvers
+
+Every step of a solver comes with an interpolant which gives
+the solution anywhere between the two ends of the step:
+
+* Hermite: the cubic through the end values and derivatives
+  (RK4, Tsitouras), of order 3.
+* Dormand-Prince: the continuous extension of order 4 by
+  Hairer, Norsett and Wanner.
+* Rosenbrock: the quadratic continuous extension of the
+  stage vectors (Shampine and Reichelt, 1997).
+* Collocation: the cubic collocation polynomial of the Radau
+  stages.
+* Backward differences: the polynomial through the last
+  order + 1 states of the BDF method.
+
+``DenseOutput`` collects the interpolants of all steps.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+use srmatrix::vecops::to_col;
+
+// local imports
+use ode::vecops::*;
+
+
+/// Interpolation formulas of the solvers
+#[derive(Debug, Clone)]
+enum Interpolant {
+    Hermite {
+        y0 : Vec<f64>,
+        y1 : Vec<f64>,
+        f0 : Vec<f64>,
+        f1 : Vec<f64>
+    },
+    DormandPrince {
+        r : Vec<Vec<f64>>
+    },
+    Rosenbrock {
+        y0 : Vec<f64>,
+        k1 : Vec<f64>,
+        k2 : Vec<f64>,
+        d : f64
+    },
+    Collocation {
+        y0 : Vec<f64>,
+        c : Vec<f64>,
+        z : Vec<Vec<f64>>
+    },
+    Differences {
+        t_shift : Vec<f64>,
+        denominators : Vec<f64>,
+        d : Vec<Vec<f64>>
+    }
+}
+
+
+/// The interpolant of a single step from t0 to t1
+#[derive(Debug, Clone)]
+pub struct StepInterpolant {
+    /// Start of the step
+    pub t0 : f64,
+    /// End of the step
+    pub t1 : f64,
+    kind : Interpolant
+}
+
+impl StepInterpolant {
+
+    /// Cubic Hermite interpolant of the values y0, y1 and the
+    /// derivatives f0, f1 at the ends
+    pub fn hermite(t0 : f64, t1 : f64, y0 : Vec<f64>, y1 : Vec<f64>, f0 : Vec<f64>,
+        f1 : Vec<f64>) -> StepInterpolant {
+        StepInterpolant { t0 : t0, t1 : t1, kind : Interpolant::Hermite { y0 : y0, y1 : y1, f0 : f0, f1 : f1 } }
+    }
+
+    /// Dormand-Prince continuous extension
+    /// y0 + s (r1 + (1 - s) (r2 + s (r3 + (1 - s) r4))) with r = [y0, r1, .., r4]
+    pub fn dormand_prince(t0 : f64, t1 : f64, r : Vec<Vec<f64>>) -> StepInterpolant {
+        StepInterpolant { t0 : t0, t1 : t1, kind : Interpolant::DormandPrince { r : r } }
+    }
+
+    /// Rosenbrock continuous extension
+    /// y0 + h (s (1 - s) k1 + s (s - 2 d) k2) / (1 - 2 d)
+    pub fn rosenbrock(t0 : f64, t1 : f64, y0 : Vec<f64>, k1 : Vec<f64>, k2 : Vec<f64>,
+        d : f64) -> StepInterpolant {
+        StepInterpolant { t0 : t0, t1 : t1, kind : Interpolant::Rosenbrock { y0 : y0, k1 : k1, k2 : k2, d : d } }
+    }
+
+    /// Collocation polynomial u with u(0) = 0 and u(c_i) = z_i added to y0
+    pub fn collocation(t0 : f64, t1 : f64, y0 : Vec<f64>, c : Vec<f64>,
+        z : Vec<Vec<f64>>) -> StepInterpolant {
+        StepInterpolant { t0 : t0, t1 : t1, kind : Interpolant::Collocation { y0 : y0, c : c, z : z } }
+    }
+
+    /// Newton form sum_j d_j prod_{m < j} (t - t_shift_m) / denominators_m
+    pub fn differences(t0 : f64, t1 : f64, t_shift : Vec<f64>, denominators : Vec<f64>,
+        d : Vec<Vec<f64>>) -> StepInterpolant {
+        StepInterpolant { t0 : t0, t1 : t1, kind : Interpolant::Differences {
+            t_shift : t_shift, denominators : denominators, d : d } }
+    }
+
+    /// Evaluates the interpolant at t as a plain vector
+    pub fn values(&self, t : f64) -> Vec<f64> {
+        let h = self.t1 - self.t0;
+        let s = if h == 0. { 0. } else { (t - self.t0) / h };
+        match self.kind {
+            Interpolant::Hermite { ref y0, ref y1, ref f0, ref f1 } => {
+                let u = 1. - s;
+                let c = [(1. + 2. * s) * u * u, h * s * u * u, s * s * (3. - 2. * s), h * s * s * (s - 1.)];
+                (0..y0.len()).map(|i| c[0] * y0[i] + c[1] * f0[i] + c[2] * y1[i] + c[3] * f1[i]).collect()
+            },
+            Interpolant::DormandPrince { ref r } => {
+                let u = 1. - s;
+                (0..r[0].len()).map(|i| {
+                    r[0][i] + s * (r[1][i] + u * (r[2][i] + s * (r[3][i] + u * r[4][i])))
+                }).collect()
+            },
+            Interpolant::Rosenbrock { ref y0, ref k1, ref k2, d } => {
+                let c = [s * (1. - s) / (1. - 2. * d), s * (s - 2. * d) / (1. - 2. * d)];
+                add_scaled(y0, h, &c, &[k1.clone(), k2.clone()])
+            },
+            Interpolant::Collocation { ref y0, ref c, ref z } => {
+                // Lagrange basis on the nodes 0, c_1, .., c_m with u(0) = 0
+                let weights : Vec<f64> = (0..c.len()).map(|i| {
+                    (0..c.len()).filter(|&j| j != i)
+                        .fold(s / c[i], |w, j| w * (s - c[j]) / (c[i] - c[j]))
+                }).collect();
+                add_scaled(y0, 1., &weights, z)
+            },
+            Interpolant::Differences { ref t_shift, ref denominators, ref d } => {
+                let mut p = 1.;
+                let mut weights = vec![1.];
+                for (ts, den) in t_shift.iter().zip(denominators.iter()) {
+                    p *= (t - ts) / den;
+                    weights.push(p);
+                }
+                let zero = vec![0.; d[0].len()];
+                add_scaled(&zero, 1., &weights, d)
+            }
+        }
+    }
+
+    /// Evaluates the interpolant at t as a column vector
+    pub fn evaluate(&self, t : f64) -> MatrixF64 {
+        to_col(&self.values(t))
+    }
+}
+
+
+/// The interpolants of all steps of a solution
+#[derive(Debug, Clone)]
+pub struct DenseOutput {
+    segments : Vec<StepInterpolant>
+}
+
+impl DenseOutput {
+
+    /// An empty dense output
+    pub fn new() -> DenseOutput {
+        DenseOutput { segments : Vec::new() }
+    }
+
+    /// Appends the interpolant of the next step
+    pub fn push(&mut self, step : StepInterpolant) {
+        self.segments.push(step);
+    }
+
+    /// Returns the number of steps
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Indicates if there are no steps
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns the interpolants of the steps
+    pub fn steps(&self) -> &[StepInterpolant] {
+        &self.segments
+    }
+
+    /// Evaluates the solution at t which must lie in the
+    /// integration interval
+    pub fn evaluate(&self, t : f64) -> SRResult<MatrixF64> {
+        if self.segments.is_empty() {
+            return Err(SRError::EmptyMatrix);
+        }
+        let first = &self.segments[0];
+        let last = &self.segments[self.segments.len() - 1];
+        let direction = if last.t1 >= first.t0 { 1. } else { -1. };
+        if !(direction * (t - first.t0) >= 0. && direction * (last.t1 - t) >= 0.) {
+            return Err(SRError::InvalidArgument);
+        }
+        let k = self.segments.partition_point(|s| direction * (s.t1 - t) < 0.);
+        Ok(self.segments[k.min(self.segments.len() - 1)].evaluate(t))
+    }
+}
+
+impl Default for DenseOutput {
+    fn default() -> DenseOutput {
+        DenseOutput::new()
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_hermite(){
+        // y = t^3 on [1, 2] is reproduced exactly
+        let p = StepInterpolant::hermite(1., 2., vec![1.], vec![8.], vec![3.], vec![12.]);
+        for &t in [1., 1.25, 1.5, 2.].iter() {
+            assert!((p.values(t)[0] - t * t * t).abs() < 1e-14);
+        }
+        // Backwards in time
+        let p = StepInterpolant::hermite(2., 1., vec![8.], vec![1.], vec![12.], vec![3.]);
+        assert!((p.values(1.5)[0] - 3.375).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_collocation_and_differences(){
+        // u(s) = s^2 + s through the nodes 0.5 and 1
+        let p = StepInterpolant::collocation(0., 2., vec![1.], vec![0.5, 1.], vec![vec![0.75], vec![2.]]);
+        assert!((p.values(0.5)[0] - (1. + 0.0625 + 0.25)).abs() < 1e-14);
+        // y(t) = 1 + 2 (t - 1) + 3 (t - 1) t / 2 in Newton form
+        let p = StepInterpolant::differences(0., 1., vec![1., 0.], vec![1., 2.], vec![vec![1.], vec![2.], vec![3.]]);
+        assert!((p.values(0.5)[0] - (1. - 1. - 0.375)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_dense_output(){
+        let mut d = DenseOutput::new();
+        assert!(d.evaluate(0.).is_err());
+        d.push(StepInterpolant::hermite(0., 1., vec![0.], vec![1.], vec![1.], vec![1.]));
+        d.push(StepInterpolant::hermite(1., 3., vec![1.], vec![3.], vec![1.], vec![1.]));
+        assert_eq!(d.len(), 2);
+        assert!((d.evaluate(0.5).unwrap()[0] - 0.5).abs() < 1e-15);
+        assert!((d.evaluate(2.5).unwrap()[0] - 2.5).abs() < 1e-15);
+        assert!((d.evaluate(3.).unwrap()[0] - 3.).abs() < 1e-15);
+        assert!(d.evaluate(3.5).is_err());
+    }
+}
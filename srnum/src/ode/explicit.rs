@@ -0,0 +1,366 @@
+#![doc="Explicit Runge-Kutta methods
+
+* ``Rk4Stepper``: the classical method of order 4 with a fixed
+  step size.
+* ``ExplicitStepper``: embedded pairs of order 5(4) with error
+  control, the pair of Dormand and Prince (1980) and the pair
+  of Tsitouras (2011). Both have seven stages and reuse the
+  last stage as the first one of the next step (FSAL).
+
+The step size is controlled by the local error estimate of the
+embedded method of order 4 in the weighted root mean square
+norm with weights atol + rtol |y|, advancing with the solution
+of order 5 (local extrapolation). The explicit methods are
+suited for non-stiff problems only.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use ode::system::*;
+use ode::options::*;
+use ode::dense::StepInterpolant;
+use ode::stepper::*;
+use ode::vecops::*;
+
+
+/// Coefficients of an embedded explicit Runge-Kutta pair
+/// whose last stage is evaluated at the new solution. The last
+/// row of the coefficient matrix holds the weights of the
+/// solution.
+struct ButcherTableau {
+    /// The nodes
+    c : &'static [f64],
+    /// The rows of the coefficient matrix below the diagonal
+    a : &'static [&'static [f64]],
+    /// The weights of the error estimate (b minus the embedded weights)
+    e : &'static [f64]
+}
+
+
+/// Dormand-Prince 5(4)
+static DORMAND_PRINCE : ButcherTableau = ButcherTableau {
+    c : &[0., 1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.],
+    a : &[
+        &[],
+        &[1. / 5.],
+        &[3. / 40., 9. / 40.],
+        &[44. / 45., -56. / 15., 32. / 9.],
+        &[19372. / 6561., -25360. / 2187., 64448. / 6561., -212. / 729.],
+        &[9017. / 3168., -355. / 33., 46732. / 5247., 49. / 176., -5103. / 18656.],
+        &[35. / 384., 0., 500. / 1113., 125. / 192., -2187. / 6784., 11. / 84.]],
+    e : &[71. / 57600., 0., -71. / 16695., 71. / 1920., -17253. / 339200., 22. / 525., -1. / 40.]
+};
+
+/// Coefficients of the continuous extension of Dormand-Prince
+const DORMAND_PRINCE_DENSE : [f64; 7] = [
+    -12715105075. / 11282082432., 0., 87487479700. / 32700410799., -10690763975. / 1880347072.,
+    701980252875. / 199316789632., -1453857185. / 822651844., 69997945. / 29380423.];
+
+/// Tsitouras 5(4)
+static TSITOURAS : ButcherTableau = ButcherTableau {
+    c : &[0., 0.161, 0.327, 0.9, 0.980_025_540_904_509_7, 1., 1.],
+    a : &[
+        &[],
+        &[0.161],
+        &[-0.008_480_655_492_356_989, 0.335_480_655_492_357],
+        &[2.897_153_057_105_493, -6.359_448_489_975_075, 4.362_295_432_869_581_5],
+        &[5.325_864_828_439_257, -11.748_883_564_062_828, 7.495_539_342_889_836_5,
+            -0.092_495_066_361_755_25],
+        &[5.861_455_442_946_42, -12.920_969_317_847_11, 8.159_367_898_576_159,
+            -0.071_584_973_281_401, -0.028_269_050_394_068_383],
+        &[0.096_460_766_818_065_23, 0.01, 0.479_889_650_414_499_6, 1.379_008_574_103_742,
+            -3.290_069_515_436_081, 2.324_710_524_099_774]],
+    e : &[-0.001_780_011_052_225_777, -0.000_816_434_459_656_747, 0.007_880_878_010_261_995,
+        -0.144_711_007_173_262_9, 0.582_357_165_452_555_2, -0.458_082_105_929_187, 1. / 66.]
+};
+
+
+/// Order of the error estimate of the pairs
+const ERROR_ORDER : f64 = 4.;
+
+
+/// Adaptive Dormand-Prince or Tsitouras integrator
+pub struct ExplicitStepper {
+    tableau : &'static ButcherTableau,
+    dormand_prince : bool,
+    control : StepControl,
+    t : f64,
+    y : Vec<f64>,
+    /// f(t, y)
+    f : Vec<f64>,
+    t_old : f64,
+    y_old : Vec<f64>,
+    /// The stages of the last step
+    k : Vec<Vec<f64>>,
+    counters : Counters
+}
+
+impl ExplicitStepper {
+
+    /// Starts the integration at (t0, y0) towards t_end with the
+    /// Dormand-Prince or the Tsitouras pair
+    pub fn new<S:OdeSystem + ?Sized>(system : &S, t0 : f64, y0 : &[f64], t_end : f64,
+        options : &OdeOptions) -> SRResult<ExplicitStepper> {
+        let dormand_prince = match options.method {
+            OdeMethod::DormandPrince => true,
+            OdeMethod::Tsitouras => false,
+            _ => return Err(SRError::InvalidArgument)
+        };
+        let mut counters = Counters::default();
+        let f = evaluate(system, t0, y0, &mut counters)?;
+        let mut control = StepControl::new(options, t0, t_end);
+        control.select_initial_step(system, t0, y0, &f, 5, &mut counters)?;
+        Ok(ExplicitStepper {
+            tableau : if dormand_prince { &DORMAND_PRINCE } else { &TSITOURAS },
+            dormand_prince : dormand_prince,
+            control : control,
+            t : t0,
+            y : y0.to_vec(),
+            f : f.clone(),
+            t_old : t0,
+            y_old : y0.to_vec(),
+            k : vec![f.clone(), f],
+            counters : counters
+        })
+    }
+}
+
+impl Stepper for ExplicitStepper {
+
+    fn t(&self) -> f64 {
+        self.t
+    }
+
+    fn y(&self) -> &[f64] {
+        &self.y
+    }
+
+    fn step<S:OdeSystem + ?Sized>(&mut self, system : &S, t_end : f64) -> SRResult<()> {
+        let tableau = self.tableau;
+        let stages = tableau.c.len();
+        let mut rejected = false;
+        loop {
+            check_step_size(self.t, self.control.h_abs)?;
+            let (t_new, h) = self.control.clip(self.t, t_end);
+            let mut k = Vec::with_capacity(stages);
+            k.push(self.f.clone());
+            for i in 1..stages {
+                let yi = add_scaled(&self.y, h, tableau.a[i], &k);
+                k.push(evaluate(system, self.t + tableau.c[i] * h, &yi, &mut self.counters)?);
+            }
+            // The last stage is evaluated at the new solution
+            let y_new = add_scaled(&self.y, h, tableau.a[stages - 1], &k);
+            let error = add_scaled(&vec![0.; self.y.len()], h, tableau.e, &k);
+            let scale = self.control.scale(&self.y, &y_new);
+            let error_norm = rms_norm(&error, &scale);
+            if error_norm < 1. {
+                let factor = if error_norm == 0. {
+                    MAX_FACTOR
+                }
+                else {
+                    MAX_FACTOR.min(SAFETY * error_norm.powf(-1. / (ERROR_ORDER + 1.)))
+                };
+                let factor = if rejected { factor.min(1.) } else { factor };
+                self.control.h_abs *= factor;
+                self.t_old = self.t;
+                self.y_old = ::std::mem::replace(&mut self.y, y_new);
+                self.t = t_new;
+                self.f = k[stages - 1].clone();
+                self.k = k;
+                self.counters.accepted += 1;
+                return Ok(());
+            }
+            let factor = MIN_FACTOR.max(SAFETY * error_norm.powf(-1. / (ERROR_ORDER + 1.)));
+            self.control.h_abs *= factor;
+            self.counters.rejected += 1;
+            rejected = true;
+        }
+    }
+
+    fn interpolant(&self) -> StepInterpolant {
+        let k = &self.k;
+        let last = k.len() - 1;
+        if !self.dormand_prince {
+            return StepInterpolant::hermite(self.t_old, self.t, self.y_old.clone(), self.y.clone(),
+                k[0].clone(), k[last].clone());
+        }
+        let h = self.t - self.t_old;
+        let n = self.y.len();
+        let diff : Vec<f64> = (0..n).map(|i| self.y[i] - self.y_old[i]).collect();
+        let bspl : Vec<f64> = (0..n).map(|i| h * k[0][i] - diff[i]).collect();
+        let r3 : Vec<f64> = (0..n).map(|i| diff[i] - h * k[last][i] - bspl[i]).collect();
+        let r4 = add_scaled(&vec![0.; n], h, &DORMAND_PRINCE_DENSE, k);
+        StepInterpolant::dormand_prince(self.t_old, self.t, vec![self.y_old.clone(), diff, bspl, r3, r4])
+    }
+
+    fn counters(&self) -> Counters {
+        self.counters
+    }
+}
+
+
+/// Classical Runge-Kutta method with a fixed step size
+pub struct Rk4Stepper {
+    direction : f64,
+    h_abs : f64,
+    t : f64,
+    y : Vec<f64>,
+    f : Vec<f64>,
+    t_old : f64,
+    y_old : Vec<f64>,
+    f_old : Vec<f64>,
+    counters : Counters
+}
+
+impl Rk4Stepper {
+
+    /// Starts the integration at (t0, y0) towards t_end. The step
+    /// size is the initial step of the options, a hundredth of
+    /// the interval by default.
+    pub fn new<S:OdeSystem + ?Sized>(system : &S, t0 : f64, y0 : &[f64], t_end : f64,
+        options : &OdeOptions) -> SRResult<Rk4Stepper> {
+        let h_abs = options.initial_step.map_or((t_end - t0).abs() / 100., |h| h.abs())
+            .min(options.max_step);
+        let mut counters = Counters::default();
+        let f = evaluate(system, t0, y0, &mut counters)?;
+        Ok(Rk4Stepper {
+            direction : if t_end >= t0 { 1. } else { -1. },
+            h_abs : h_abs,
+            t : t0,
+            y : y0.to_vec(),
+            f : f.clone(),
+            t_old : t0,
+            y_old : y0.to_vec(),
+            f_old : f,
+            counters : counters
+        })
+    }
+}
+
+impl Stepper for Rk4Stepper {
+
+    fn t(&self) -> f64 {
+        self.t
+    }
+
+    fn y(&self) -> &[f64] {
+        &self.y
+    }
+
+    fn step<S:OdeSystem + ?Sized>(&mut self, system : &S, t_end : f64) -> SRResult<()> {
+        check_step_size(self.t, self.h_abs)?;
+        let mut t_new = self.t + self.direction * self.h_abs;
+        // Avoid a tiny last step
+        if self.direction * (t_new - t_end) > -1e-10 * self.h_abs {
+            t_new = t_end;
+        }
+        let h = t_new - self.t;
+        let t = self.t;
+        let k1 = self.f.clone();
+        let k2 = evaluate(system, t + 0.5 * h, &axpy(&self.y, 0.5 * h, &k1), &mut self.counters)?;
+        let k3 = evaluate(system, t + 0.5 * h, &axpy(&self.y, 0.5 * h, &k2), &mut self.counters)?;
+        let k4 = evaluate(system, t_new, &axpy(&self.y, h, &k3), &mut self.counters)?;
+        let y_new = add_scaled(&self.y, h, &[1. / 6., 1. / 3., 1. / 3., 1. / 6.], &[k1, k2, k3, k4]);
+        let f_new = evaluate(system, t_new, &y_new, &mut self.counters)?;
+        self.t_old = t;
+        self.y_old = ::std::mem::replace(&mut self.y, y_new);
+        self.f_old = ::std::mem::replace(&mut self.f, f_new);
+        self.t = t_new;
+        self.counters.accepted += 1;
+        Ok(())
+    }
+
+    fn interpolant(&self) -> StepInterpolant {
+        StepInterpolant::hermite(self.t_old, self.t, self.y_old.clone(), self.y.clone(),
+            self.f_old.clone(), self.f.clone())
+    }
+
+    fn counters(&self) -> Counters {
+        self.counters
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_tableaus(){
+        for tableau in [&DORMAND_PRINCE, &TSITOURAS].iter() {
+            // Row sums equal the nodes, the weights sum to one and
+            // the error weights to zero
+            for i in 0..tableau.c.len() {
+                let s = tableau.a[i].iter().fold(0., |s, a| s + a);
+                assert!((s - tableau.c[i]).abs() < 1e-14, "{}", i);
+            }
+            // The weights of the solution are the last row
+            let b = tableau.a[tableau.c.len() - 1];
+            assert!((b.iter().fold(0., |s, b| s + b) - 1.).abs() < 1e-14);
+            assert!(tableau.e.iter().fold(0., |s, e| s + e).abs() < 1e-14);
+            // Order conditions of order 5 for the solution
+            let c = tableau.c;
+            for q in 0..5 {
+                let s = (0..b.len()).fold(0., |s, i| s + b[i] * c[i].powi(q));
+                assert!((s - 1. / (q as f64 + 1.)).abs() < 1e-12, "{}", q);
+            }
+        }
+    }
+
+    fn order_of_convergence(options : &OdeOptions) -> f64 {
+        // y' = -2 t y, y(0) = 1 with solution exp(-t^2)
+        let system = FnOdeSystem::new(|t, y : &MatrixF64| vector_f64(&[-2. * t * y[0]]));
+        let error = |steps : usize| {
+            let options = options.clone().initial_step(1. / steps as f64);
+            let mut s = Rk4Stepper::new(&system, 0., &[1.], 1., &options).unwrap();
+            while s.t() < 1. {
+                s.step(&system, 1.).unwrap();
+            }
+            (s.y()[0] - (-1f64).exp()).abs()
+        };
+        (error(20) / error(40)).log2()
+    }
+
+    #[test]
+    fn test_rk4(){
+        let p = order_of_convergence(&OdeOptions::new().method(OdeMethod::RK4));
+        assert!((p - 4.).abs() < 0.2, "{}", p);
+    }
+
+    #[test]
+    fn test_explicit_pairs(){
+        // Harmonic oscillator over one period
+        let system = FnOdeSystem::new(|_, y : &MatrixF64| vector_f64(&[y[1], -y[0]]));
+        let t_end = 2. * ::std::f64::consts::PI;
+        for &method in [OdeMethod::DormandPrince, OdeMethod::Tsitouras].iter() {
+            let options = OdeOptions::new().method(method).tolerances(1e-10, 1e-12);
+            let mut s = ExplicitStepper::new(&system, 0., &[1., 0.], t_end, &options).unwrap();
+            let mut steps = 0;
+            while s.t() < t_end {
+                let t0 = s.t();
+                s.step(&system, t_end).unwrap();
+                // The dense output is accurate inside the step
+                let tm = 0.5 * (t0 + s.t());
+                let ym = s.interpolant().values(tm);
+                assert!((ym[0] - tm.cos()).abs() < 1e-7, "{:?}", method);
+                steps += 1;
+            }
+            assert_eq!(s.t(), t_end);
+            assert!((s.y()[0] - 1.).abs() < 1e-8 && s.y()[1].abs() < 1e-8);
+            assert!(steps > 20 && steps < 400);
+            assert!(s.counters().evaluations <= 6 * (steps + s.counters().rejected) + 2);
+        }
+        assert!(ExplicitStepper::new(&system, 0., &[1., 0.], 1., &OdeOptions::new().method(OdeMethod::Bdf)).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+#![doc="Options and results shared by the ODE solvers
+"]
+
+
+// std imports
+use std::f64;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use ode::dense::DenseOutput;
+
+
+/// Integration methods
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OdeMethod {
+    /// Classical Runge-Kutta method of order 4 with fixed steps
+    RK4,
+    /// Dormand-Prince 5(4) with adaptive steps
+    DormandPrince,
+    /// Tsitouras 5(4) with adaptive steps
+    Tsitouras,
+    /// Variable order (1 to 5) backward differentiation formulas
+    Bdf,
+    /// Linearly implicit Rosenbrock 2(3) method
+    Rosenbrock,
+    /// Implicit Runge-Kutta method Radau IIA of order 5
+    Radau
+}
+
+
+/// Options of the ODE solvers
+#[derive(Debug, Clone)]
+pub struct OdeOptions {
+    /// The integration method
+    pub method : OdeMethod,
+    /// Bound on the local error relative to the state
+    pub relative_tolerance : f64,
+    /// Bound on the absolute local error
+    pub absolute_tolerance : f64,
+    /// The first step size (the fixed step size for RK4)
+    pub initial_step : Option<f64>,
+    /// Largest allowed step size
+    pub max_step : f64,
+    /// Maximum number of steps
+    pub max_steps : usize,
+    /// Whether to keep the interpolants of all steps
+    pub dense_output : bool,
+    /// Times at which the solution is reported instead of the steps
+    pub t_eval : Option<Vec<f64>>
+}
+
+impl OdeOptions {
+
+    /// Default options: Dormand-Prince with relative tolerance 1e-6,
+    /// absolute tolerance 1e-9 and at most 100000 steps
+    pub fn new() -> OdeOptions {
+        OdeOptions {
+            method : OdeMethod::DormandPrince,
+            relative_tolerance : 1e-6,
+            absolute_tolerance : 1e-9,
+            initial_step : None,
+            max_step : f64::INFINITY,
+            max_steps : 100_000,
+            dense_output : false,
+            t_eval : None
+        }
+    }
+
+    /// Sets the method
+    pub fn method(mut self, method : OdeMethod) -> OdeOptions {
+        self.method = method;
+        self
+    }
+
+    /// Sets the relative and the absolute tolerance
+    pub fn tolerances(mut self, relative : f64, absolute : f64) -> OdeOptions {
+        self.relative_tolerance = relative;
+        self.absolute_tolerance = absolute;
+        self
+    }
+
+    /// Sets the initial step size
+    pub fn initial_step(mut self, step : f64) -> OdeOptions {
+        self.initial_step = Some(step);
+        self
+    }
+
+    /// Sets the largest step size
+    pub fn max_step(mut self, step : f64) -> OdeOptions {
+        self.max_step = step;
+        self
+    }
+
+    /// Sets the maximum number of steps
+    pub fn max_steps(mut self, max_steps : usize) -> OdeOptions {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Keeps the interpolants of all steps
+    pub fn dense_output(mut self, dense_output : bool) -> OdeOptions {
+        self.dense_output = dense_output;
+        self
+    }
+
+    /// Reports the solution at the given times
+    pub fn t_eval(mut self, times : &[f64]) -> OdeOptions {
+        self.t_eval = Some(times.to_vec());
+        self
+    }
+}
+
+impl Default for OdeOptions {
+    fn default() -> OdeOptions {
+        OdeOptions::new()
+    }
+}
+
+
+/// Why the integration stopped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OdeStatus {
+    /// The end of the interval was reached
+    Completed,
+    /// A terminal event occurred
+    TerminalEvent,
+    /// The maximum number of steps was taken
+    MaxSteps
+}
+
+
+/// A located zero of an event function
+#[derive(Debug, Clone)]
+pub struct OdeEvent {
+    /// Index of the event function
+    pub index : usize,
+    /// Time of the event
+    pub t : f64,
+    /// State at the time of the event
+    pub y : MatrixF64
+}
+
+
+/// Trajectory computed by an ODE solver
+#[derive(Debug, Clone)]
+pub struct OdeSolution {
+    /// The times as a column vector
+    pub t : MatrixF64,
+    /// The states at these times as the columns of a matrix
+    pub y : MatrixF64,
+    /// The events in the order of their occurrence
+    pub events : Vec<OdeEvent>,
+    /// Why the integration stopped
+    pub status : OdeStatus,
+    /// The interpolants of all steps if requested
+    pub dense : Option<DenseOutput>,
+    /// Number of evaluations of f
+    pub evaluations : usize,
+    /// Number of evaluations of the Jacobian
+    pub jacobian_evaluations : usize,
+    /// Number of LU factorizations
+    pub decompositions : usize,
+    /// Number of accepted steps
+    pub accepted_steps : usize,
+    /// Number of rejected steps
+    pub rejected_steps : usize
+}
+
+impl OdeSolution {
+
+    /// Returns the number of reported times
+    pub fn len(&self) -> usize {
+        self.t.num_rows()
+    }
+
+    /// Indicates if no time was reported
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the k-th state as a column vector
+    pub fn state(&self, k : usize) -> MatrixF64 {
+        let n = self.y.num_rows();
+        Matrix::from_iter_cw(n, 1, (0..n).map(|i| self.y.get(i, k).unwrap()))
+    }
+
+    /// Returns the last state
+    pub fn final_state(&self) -> MatrixF64 {
+        self.state(self.len() - 1)
+    }
+}
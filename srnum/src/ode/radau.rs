@@ -0,0 +1,336 @@
+#![doc="Implicit Runge-Kutta method Radau IIA for stiff problems
+
+The three stage Radau IIA method has order 5 and is L-stable.
+The stage increments z_i = Y_i - y solve
+
+z = h (A x I) F(y + z)
+
+which is solved by a simplified Newton iteration with the
+matrix I - h (A x J) of size 3n, factorized by LU, starting
+from the extrapolated collocation polynomial of the previous
+step. The Jacobian J is kept over steps while the Newton iteration
+converges fast. The local error is estimated as in RADAU5
+(Hairer and Wanner, 1996):
+
+err = (I - g h J)^-1 (g h f(t, y) + g (e1 z1 + e2 z2 + e3 z3))
+
+with g the real eigenvalue of A. The dense output is the
+collocation polynomial through the stages.
+"]
+
+
+// std imports
+use std::f64;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// srlinalg imports
+use srlinalg::lu::{lu_factor, lu_solve};
+
+// local imports
+use ode::system::*;
+use ode::options::*;
+use ode::dense::StepInterpolant;
+use ode::stepper::*;
+use ode::vecops::*;
+
+
+/// Maximum number of Newton iterations in a step
+const MAX_NEWTON : usize = 7;
+
+
+/// Coefficients of the three stage Radau IIA method
+struct RadauCoefficients {
+    c : [f64; 3],
+    a : [[f64; 3]; 3],
+    /// Weights of the stage increments in the error estimate
+    e : [f64; 3],
+    /// The real eigenvalue 1 / g of A^-1
+    eigenvalue : f64
+}
+
+impl RadauCoefficients {
+
+    fn new() -> RadauCoefficients {
+        let s6 = 6f64.sqrt();
+        RadauCoefficients {
+            c : [(4. - s6) / 10., (4. + s6) / 10., 1.],
+            a : [
+                [(88. - 7. * s6) / 360., (296. - 169. * s6) / 1800., (-2. + 3. * s6) / 225.],
+                [(296. + 169. * s6) / 1800., (88. + 7. * s6) / 360., (-2. - 3. * s6) / 225.],
+                [(16. - s6) / 36., (16. + s6) / 36., 1. / 9.]],
+            e : [-(13. + 7. * s6) / 3., (-13. + 7. * s6) / 3., -1. / 3.],
+            eigenvalue : 30. / (6. + 81f64.cbrt() - 9f64.cbrt())
+        }
+    }
+}
+
+
+/// Radau IIA integrator
+pub struct RadauStepper {
+    coefficients : RadauCoefficients,
+    control : StepControl,
+    t : f64,
+    y : Vec<f64>,
+    f : Vec<f64>,
+    t_old : f64,
+    y_old : Vec<f64>,
+    /// The stage increments of the last step
+    z : Vec<Vec<f64>>,
+    jacobian : Option<MatrixF64>,
+    /// Whether the Jacobian was evaluated at the current point
+    current_jacobian : bool,
+    /// Whether the last Newton iteration converged slowly
+    slow_convergence : bool,
+    newton_tolerance : f64,
+    counters : Counters
+}
+
+impl RadauStepper {
+
+    /// Starts the integration at (t0, y0) towards t_end
+    pub fn new<S:OdeSystem + ?Sized>(system : &S, t0 : f64, y0 : &[f64], t_end : f64,
+        options : &OdeOptions) -> SRResult<RadauStepper> {
+        let mut counters = Counters::default();
+        let f = evaluate(system, t0, y0, &mut counters)?;
+        let mut control = StepControl::new(options, t0, t_end);
+        control.select_initial_step(system, t0, y0, &f, 3, &mut counters)?;
+        let rtol = options.relative_tolerance;
+        Ok(RadauStepper {
+            coefficients : RadauCoefficients::new(),
+            control : control,
+            t : t0,
+            y : y0.to_vec(),
+            f : f,
+            t_old : t0,
+            y_old : y0.to_vec(),
+            z : vec![vec![0.; y0.len()]; 3],
+            jacobian : None,
+            current_jacobian : false,
+            slow_convergence : false,
+            newton_tolerance : (10. * f64::EPSILON / rtol).max(0.03f64.min(rtol.sqrt())),
+            counters : counters
+        })
+    }
+
+    /// Solves the stage equations by simplified Newton iterations
+    /// starting from z. Returns the stage increments, or None if the
+    /// iteration fails to converge.
+    fn solve_stages<S:OdeSystem + ?Sized>(&mut self, system : &S, h : f64, jac : &MatrixF64,
+        mut z : Vec<Vec<f64>>, scale : &[f64]) -> SRResult<Option<Vec<Vec<f64>>>> {
+        let n = self.y.len();
+        let cf = &self.coefficients;
+        // I - h (A x J)
+        let m = Matrix::from_iter_cw(3 * n, 3 * n, (0..9 * n * n).map(|k| {
+            let (r, c) = (k % (3 * n), k / (3 * n));
+            let (i, p, j, q) = (r / n, r % n, c / n, c % n);
+            let d = if r == c { 1. } else { 0. };
+            d - h * cf.a[i][j] * jac.get(p, q).unwrap()
+        }));
+        self.counters.decompositions += 1;
+        let lu = lu_factor(&m)?;
+        let scale3 : Vec<f64> = (0..3 * n).map(|k| scale[k % n]).collect();
+        let mut previous = 0.;
+        for iteration in 0..MAX_NEWTON {
+            let mut fz = Vec::with_capacity(3);
+            for i in 0..3 {
+                let yi = axpy(&self.y, 1., &z[i]);
+                fz.push(evaluate(system, self.t + cf.c[i] * h, &yi, &mut self.counters)?);
+            }
+            if fz.iter().any(|f| f.iter().any(|v| !v.is_finite())) {
+                return Ok(None);
+            }
+            // Residual -z + h (A x I) F
+            let residual : Vec<f64> = (0..3 * n).map(|k| {
+                let (i, p) = (k / n, k % n);
+                -z[i][p] + h * (0..3).fold(0., |s, j| s + cf.a[i][j] * fz[j][p])
+            }).collect();
+            let dz = lu_solve(&lu, &residual)?;
+            let norm = rms_norm(&dz, &scale3);
+            // The contraction rate is known from the second iteration on
+            let rate = if iteration > 0 { Some(norm / previous) } else { None };
+            if let Some(rate) = rate {
+                let remaining = (MAX_NEWTON - iteration) as i32;
+                if rate >= 1. || rate.powi(remaining) / (1. - rate) * norm > self.newton_tolerance {
+                    return Ok(None);
+                }
+            }
+            for k in 0..3 * n {
+                z[k / n][k % n] += dz[k];
+            }
+            let converged = match rate {
+                Some(rate) => rate / (1. - rate) * norm < self.newton_tolerance,
+                None => norm == 0.
+            };
+            if converged {
+                self.slow_convergence = iteration > 1 && rate.is_some_and(|rate| rate > 1e-3);
+                return Ok(Some(z));
+            }
+            previous = norm;
+        }
+        Ok(None)
+    }
+}
+
+
+impl Stepper for RadauStepper {
+
+    fn t(&self) -> f64 {
+        self.t
+    }
+
+    fn y(&self) -> &[f64] {
+        &self.y
+    }
+
+    fn step<S:OdeSystem + ?Sized>(&mut self, system : &S, t_end : f64) -> SRResult<()> {
+        let n = self.y.len();
+        if self.jacobian.is_none() {
+            self.jacobian = Some(evaluate_jacobian(system, self.t, &self.y, &mut self.counters)?);
+            self.current_jacobian = true;
+        }
+        let mut rejected = false;
+        loop {
+            check_step_size(self.t, self.control.h_abs)?;
+            let (t_new, h) = self.control.clip(self.t, t_end);
+            let jac = self.jacobian.clone().unwrap();
+            let scale = self.control.scale(&self.y, &self.y);
+            // Start from the collocation polynomial of the last step
+            let z0 = if self.counters.accepted > 0 {
+                let last = self.interpolant();
+                self.coefficients.c.iter().map(|c| {
+                    last.values(self.t + c * h).iter().zip(self.y.iter()).map(|(a, b)| a - b).collect()
+                }).collect()
+            }
+            else {
+                vec![vec![0.; n]; 3]
+            };
+            let z = match self.solve_stages(system, h, &jac, z0, &scale)? {
+                Some(r) => r,
+                None => {
+                    // Retry with a fresh Jacobian, then with a smaller step
+                    self.counters.rejected += 1;
+                    rejected = true;
+                    if !self.current_jacobian {
+                        self.jacobian = Some(evaluate_jacobian(system, self.t, &self.y, &mut self.counters)?);
+                        self.current_jacobian = true;
+                    }
+                    else {
+                        self.control.h_abs *= 0.5;
+                    }
+                    continue;
+                }
+            };
+            let y_new = axpy(&self.y, 1., &z[2]);
+            // Error estimate
+            let cf = &self.coefficients;
+            let g = 1. / cf.eigenvalue;
+            self.counters.decompositions += 1;
+            let lu = lu_factor(&shifted_identity(g * h, &jac))?;
+            let zsum = add_scaled(&vec![0.; n], g, &cf.e, &z);
+            let rhs = axpy(&zsum, g * h, &self.f);
+            let mut error = lu_solve(&lu, &rhs)?;
+            let scale = self.control.scale(&self.y, &y_new);
+            let mut error_norm = rms_norm(&error, &scale);
+            if error_norm >= 1. && (rejected || self.counters.accepted == 0) {
+                // Refined estimate which suppresses stiff components
+                let ye = axpy(&self.y, 1., &error);
+                let fe = evaluate(system, self.t, &ye, &mut self.counters)?;
+                let rhs = axpy(&zsum, g * h, &fe);
+                error = lu_solve(&lu, &rhs)?;
+                error_norm = rms_norm(&error, &scale);
+            }
+            if error_norm < 1. {
+                let factor = if error_norm == 0. {
+                    MAX_FACTOR
+                }
+                else {
+                    MAX_FACTOR.min(SAFETY * error_norm.powf(-0.25))
+                };
+                let factor = if rejected { factor.min(1.) } else { factor };
+                self.control.h_abs *= factor;
+                self.t_old = self.t;
+                self.y_old = ::std::mem::replace(&mut self.y, y_new);
+                self.t = t_new;
+                self.f = evaluate(system, self.t, &self.y, &mut self.counters)?;
+                self.z = z;
+                self.counters.accepted += 1;
+                // Keep the Jacobian while the Newton iteration converges fast
+                self.current_jacobian = false;
+                if self.slow_convergence {
+                    self.jacobian = None;
+                }
+                return Ok(());
+            }
+            self.control.h_abs *= MIN_FACTOR.max(SAFETY * error_norm.powf(-0.25));
+            self.counters.rejected += 1;
+            rejected = true;
+        }
+    }
+
+    fn interpolant(&self) -> StepInterpolant {
+        StepInterpolant::collocation(self.t_old, self.t, self.y_old.clone(),
+            self.coefficients.c.to_vec(), self.z.clone())
+    }
+
+    fn counters(&self) -> Counters {
+        self.counters
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_coefficients(){
+        let cf = RadauCoefficients::new();
+        for i in 0..3 {
+            let s = cf.a[i].iter().fold(0., |s, a| s + a);
+            assert!((s - cf.c[i]).abs() < 1e-15);
+        }
+        // The real eigenvalue of A^-1
+        assert!((cf.eigenvalue - 3.637_834_252_744_496).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_van_der_pol(){
+        // Stiff Van der Pol oscillator with mu = 1000
+        let mu = 1000.;
+        let system = FnOdeSystem::new(move |_, y : &MatrixF64| {
+            vector_f64(&[y[1], mu * (1. - y[0] * y[0]) * y[1] - y[0]])
+        });
+        let options = OdeOptions::new().method(OdeMethod::Radau).tolerances(1e-6, 1e-6);
+        let mut s = RadauStepper::new(&system, 0., &[2., 0.], 1000., &options).unwrap();
+        while s.t() < 1000. {
+            s.step(&system, 1000.).unwrap();
+        }
+        // The solution stays on the slow branches 1 < |y1| < 2.1
+        assert!(s.y()[0].abs() > 1. && s.y()[0].abs() < 2.1);
+        assert!(s.counters().accepted < 500, "{:?}", s.counters());
+        assert!(s.counters().jacobian_evaluations < s.counters().accepted);
+    }
+
+    #[test]
+    fn test_accuracy(){
+        // y' = -y + sin t with y(0) = 1
+        let system = FnOdeSystem::new(|t : f64, y : &MatrixF64| vector_f64(&[-y[0] + t.sin()]));
+        let exact = |t : f64| 1.5 * (-t).exp() + 0.5 * (t.sin() - t.cos());
+        let options = OdeOptions::new().method(OdeMethod::Radau).tolerances(1e-9, 1e-12);
+        let mut s = RadauStepper::new(&system, 0., &[1.], 5., &options).unwrap();
+        while s.t() < 5. {
+            s.step(&system, 5.).unwrap();
+            let tm = 0.5 * (s.t_old + s.t());
+            assert!((s.interpolant().values(tm)[0] - exact(tm)).abs() < 1e-6);
+        }
+        assert!((s.y()[0] - exact(5.)).abs() < 1e-8);
+    }
+}
@@ -0,0 +1,178 @@
+#![doc="Rosenbrock method for stiff problems
+
+The linearly implicit method of order 2 with an embedded error
+estimate of order 3 by Shampine and Reichelt (1997), known
+from MATLAB's ode23s. With d = 1 / (2 + sqrt 2), J = df/dy and
+T = df/dt at the start of the step and W = I - h d J:
+
+* k1 = W^-1 (f(t, y) + h d T)
+* k2 = W^-1 (f(t + h/2, y + h/2 k1) - k1) + k1
+* y_new = y + h k2
+* k3 = W^-1 (f(t + h, y_new) - (6 + sqrt 2)(k2 - f1) - 2 (k1 - f0) + h d T)
+* error = h/6 (k1 - 2 k2 + k3)
+
+The method is L-stable. Every step evaluates the Jacobian and
+factorizes W once; T is approximated by a forward difference.
+"]
+
+
+// std imports
+use std::f64;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// srlinalg imports
+use srlinalg::lu::{lu_factor, lu_solve};
+
+// local imports
+use ode::system::*;
+use ode::options::*;
+use ode::dense::StepInterpolant;
+use ode::stepper::*;
+use ode::vecops::*;
+
+
+/// Rosenbrock 2(3) integrator
+pub struct RosenbrockStepper {
+    control : StepControl,
+    d : f64,
+    t : f64,
+    y : Vec<f64>,
+    f : Vec<f64>,
+    t_old : f64,
+    y_old : Vec<f64>,
+    /// The stages k1 and k2 of the last step
+    k : (Vec<f64>, Vec<f64>),
+    counters : Counters
+}
+
+impl RosenbrockStepper {
+
+    /// Starts the integration at (t0, y0) towards t_end
+    pub fn new<S:OdeSystem + ?Sized>(system : &S, t0 : f64, y0 : &[f64], t_end : f64,
+        options : &OdeOptions) -> SRResult<RosenbrockStepper> {
+        let mut counters = Counters::default();
+        let f = evaluate(system, t0, y0, &mut counters)?;
+        let mut control = StepControl::new(options, t0, t_end);
+        control.select_initial_step(system, t0, y0, &f, 2, &mut counters)?;
+        Ok(RosenbrockStepper {
+            control : control,
+            d : 1. / (2. + f64::consts::SQRT_2),
+            t : t0,
+            y : y0.to_vec(),
+            f : f.clone(),
+            t_old : t0,
+            y_old : y0.to_vec(),
+            k : (f.clone(), f),
+            counters : counters
+        })
+    }
+}
+
+impl Stepper for RosenbrockStepper {
+
+    fn t(&self) -> f64 {
+        self.t
+    }
+
+    fn y(&self) -> &[f64] {
+        &self.y
+    }
+
+    fn step<S:OdeSystem + ?Sized>(&mut self, system : &S, t_end : f64) -> SRResult<()> {
+        let n = self.y.len();
+        let d = self.d;
+        let e32 = 6. + f64::consts::SQRT_2;
+        let jac = evaluate_jacobian(system, self.t, &self.y, &mut self.counters)?;
+        // T = df/dt by a forward difference
+        let delta = f64::EPSILON.sqrt() * self.t.abs().max(self.control.h_abs);
+        let ft = evaluate(system, self.t + self.control.direction * delta, &self.y, &mut self.counters)?;
+        let dfdt : Vec<f64> = (0..n).map(|i| (ft[i] - self.f[i]) / (self.control.direction * delta)).collect();
+        let mut rejected = false;
+        loop {
+            check_step_size(self.t, self.control.h_abs)?;
+            let (t_new, h) = self.control.clip(self.t, t_end);
+            self.counters.decompositions += 1;
+            let lu = lu_factor(&shifted_identity(h * d, &jac))?;
+            let f0 = &self.f;
+            let rhs : Vec<f64> = (0..n).map(|i| f0[i] + h * d * dfdt[i]).collect();
+            let k1 = lu_solve(&lu, &rhs)?;
+            let f1 = evaluate(system, self.t + 0.5 * h, &axpy(&self.y, 0.5 * h, &k1), &mut self.counters)?;
+            let rhs : Vec<f64> = (0..n).map(|i| f1[i] - k1[i]).collect();
+            let k2 : Vec<f64> = lu_solve(&lu, &rhs)?.iter().zip(k1.iter()).map(|(a, b)| a + b).collect();
+            let y_new = axpy(&self.y, h, &k2);
+            let f2 = evaluate(system, t_new, &y_new, &mut self.counters)?;
+            let rhs : Vec<f64> = (0..n).map(|i| f2[i] - e32 * (k2[i] - f1[i]) - 2. * (k1[i] - f0[i])
+                + h * d * dfdt[i]).collect();
+            let k3 = lu_solve(&lu, &rhs)?;
+            let error : Vec<f64> = (0..n).map(|i| h / 6. * (k1[i] - 2. * k2[i] + k3[i])).collect();
+            let scale = self.control.scale(&self.y, &y_new);
+            let error_norm = rms_norm(&error, &scale);
+            if error_norm < 1. && y_new.iter().all(|v| v.is_finite()) {
+                let factor = if error_norm == 0. {
+                    MAX_FACTOR
+                }
+                else {
+                    MAX_FACTOR.min(SAFETY * error_norm.powf(-1. / 3.))
+                };
+                let factor = if rejected { factor.min(1.) } else { factor };
+                self.control.h_abs *= factor;
+                self.t_old = self.t;
+                self.y_old = ::std::mem::replace(&mut self.y, y_new);
+                self.t = t_new;
+                self.f = f2;
+                self.k = (k1, k2);
+                self.counters.accepted += 1;
+                return Ok(());
+            }
+            let factor = if error_norm.is_finite() {
+                MIN_FACTOR.max(SAFETY * error_norm.powf(-1. / 3.))
+            }
+            else {
+                MIN_FACTOR
+            };
+            self.control.h_abs *= factor;
+            self.counters.rejected += 1;
+            rejected = true;
+        }
+    }
+
+    fn interpolant(&self) -> StepInterpolant {
+        StepInterpolant::rosenbrock(self.t_old, self.t, self.y_old.clone(), self.k.0.clone(),
+            self.k.1.clone(), self.d)
+    }
+
+    fn counters(&self) -> Counters {
+        self.counters
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_stiff_linear(){
+        // y' = -1000 (y - cos t) - sin t with solution cos t
+        let system = FnOdeSystem::new(|t : f64, y : &MatrixF64| vector_f64(&[-1000. * (y[0] - t.cos()) - t.sin()]));
+        let options = OdeOptions::new().method(OdeMethod::Rosenbrock).tolerances(1e-4, 1e-6);
+        let mut s = RosenbrockStepper::new(&system, 0., &[1.], 10., &options).unwrap();
+        while s.t() < 10. {
+            s.step(&system, 10.).unwrap();
+            let tm = 0.5 * (s.t_old + s.t());
+            assert!((s.interpolant().values(tm)[0] - tm.cos()).abs() < 1e-3);
+        }
+        assert!((s.y()[0] - 10f64.cos()).abs() < 1e-3);
+        // Far fewer steps than an explicit method would need
+        assert!(s.counters().accepted < 1000, "{}", s.counters().accepted);
+        assert_eq!(s.counters().jacobian_evaluations, s.counters().accepted);
+    }
+}
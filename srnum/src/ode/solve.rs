@@ -0,0 +1,373 @@
+#![doc="Solving initial value problems
+
+``solve_ivp`` integrates y' = f(t, y), y(t0) = y0 from t0 to
+t1 (t1 < t0 integrates backwards) with the method chosen in
+the options and returns the trajectory:
+
+* By default the solution is reported at the end of every
+  step; with ``t_eval`` at the given times, computed by the
+  interpolants of the steps.
+* After every step the event functions of the system are
+  checked for sign changes. Their zeros are located on the
+  interpolant of the step by the Illinois method. The
+  integration stops at the first terminal event.
+* With ``dense_output`` the solution keeps the interpolants of
+  all steps.
+
+Invalid arguments are errors. So is a step size which became
+too small for the tolerances (``NotConverged``) while
+exhausting the number of steps is reported in the status.
+"]
+
+
+// std imports
+use std::f64;
+
+// srmatrix imports
+use srmatrix::api::*;
+use srmatrix::vecops::{to_vec, to_col};
+
+// local imports
+use ode::system::*;
+use ode::options::*;
+use ode::dense::*;
+use ode::stepper::*;
+use ode::explicit::*;
+use ode::rosenbrock::*;
+use ode::radau::*;
+use ode::bdf::*;
+
+
+/// Maximum number of iterations of the event location
+const MAX_EVENT_ITERATIONS : usize = 100;
+
+
+/// Indicates if the change of g from a to b triggers an event
+fn is_crossing(a : f64, b : f64, direction : EventDirection, time_direction : f64) -> bool {
+    let rising = a < 0. && b >= 0.;
+    let falling = a > 0. && b <= 0.;
+    // Rising and falling refer to increasing time
+    let (rising, falling) = if time_direction > 0. { (rising, falling) } else { (falling, rising) };
+    match direction {
+        EventDirection::Both => rising || falling,
+        EventDirection::Rising => rising,
+        EventDirection::Falling => falling
+    }
+}
+
+
+/// Finds a zero of g between a and b with g(a) = ga and
+/// g(b) = gb of opposite signs by the Illinois method
+fn locate_zero<G>(g : G, a : f64, b : f64, ga : f64, gb : f64) -> f64 where G : Fn(f64) -> f64 {
+    if gb == 0. {
+        return b;
+    }
+    let (mut a, mut b, mut ga, mut gb) = (a, b, ga, gb);
+    let mut side = 0;
+    for _ in 0..MAX_EVENT_ITERATIONS {
+        if (b - a).abs() <= 4. * f64::EPSILON * a.abs().max(b.abs()) {
+            break;
+        }
+        let c = (a * gb - b * ga) / (gb - ga);
+        let c = if c.is_finite() && (c - a) * (c - b) < 0. { c } else { 0.5 * (a + b) };
+        let gc = g(c);
+        if gc == 0. {
+            return c;
+        }
+        if (gc < 0.) == (gb < 0.) {
+            b = c;
+            gb = gc;
+            if side == -1 {
+                ga *= 0.5;
+            }
+            side = -1;
+        }
+        else {
+            a = c;
+            ga = gc;
+            if side == 1 {
+                gb *= 0.5;
+            }
+            side = 1;
+        }
+    }
+    // The end of the bracket past the zero
+    b
+}
+
+
+/// Evaluates the event functions and checks their number
+fn evaluate_events<S:OdeSystem + ?Sized>(system : &S, t : f64, y : &[f64]) -> SRResult<Vec<f64>> {
+    let g = system.events(t, &to_col(y));
+    if g.num_rows() != system.num_events() || (g.num_rows() > 0 && g.num_cols() != 1) {
+        return Err(SRError::DimensionsMismatch);
+    }
+    Ok(to_vec(&g))
+}
+
+
+/// Runs a stepper from t0 to t1 and collects the trajectory
+fn integrate<S:OdeSystem + ?Sized, T:Stepper>(system : &S, mut stepper : T, t1 : f64,
+    options : &OdeOptions) -> SRResult<OdeSolution> {
+    let t0 = stepper.t();
+    let direction = if t1 >= t0 { 1. } else { -1. };
+    let n = stepper.y().len();
+    let mut times = Vec::new();
+    let mut states = Vec::new();
+    let t_eval = options.t_eval.clone();
+    let mut next_eval = 0;
+    match t_eval {
+        Some(ref te) => {
+            while next_eval < te.len() && te[next_eval] == t0 {
+                times.push(t0);
+                states.extend_from_slice(stepper.y());
+                next_eval += 1;
+            }
+        },
+        None => {
+            times.push(t0);
+            states.extend_from_slice(stepper.y());
+        }
+    }
+    let num_events = system.num_events();
+    let mut g_previous = if num_events > 0 { evaluate_events(system, t0, stepper.y())? } else { Vec::new() };
+    let mut events = Vec::new();
+    let mut dense = if options.dense_output { Some(DenseOutput::new()) } else { None };
+    let mut status = OdeStatus::Completed;
+    let mut steps = 0;
+    while direction * (t1 - stepper.t()) > 0. {
+        if steps == options.max_steps {
+            status = OdeStatus::MaxSteps;
+            break;
+        }
+        stepper.step(system, t1)?;
+        steps += 1;
+        let step = stepper.interpolant();
+        let (t_previous, t_new) = (step.t0, step.t1);
+        let mut t_stop = t_new;
+        let mut terminal = false;
+        if num_events > 0 {
+            let g_new = evaluate_events(system, t_new, stepper.y())?;
+            let mut found : Vec<(f64, usize)> = Vec::new();
+            for i in 0..num_events {
+                if is_crossing(g_previous[i], g_new[i], system.event_direction(i), direction) {
+                    let gi = |t : f64| system.events(t, &step.evaluate(t))[i];
+                    found.push((locate_zero(gi, t_previous, t_new, g_previous[i], g_new[i]), i));
+                }
+            }
+            found.sort_by(|p, q| (direction * p.0).partial_cmp(&(direction * q.0))
+                .unwrap_or(::std::cmp::Ordering::Equal));
+            for (te, i) in found {
+                let y = if te == t_new { to_col(stepper.y()) } else { step.evaluate(te) };
+                events.push(OdeEvent { index : i, t : te, y : y });
+                if system.is_terminal(i) {
+                    t_stop = te;
+                    terminal = true;
+                    break;
+                }
+            }
+            g_previous = g_new;
+        }
+        match t_eval {
+            Some(ref te) => {
+                while next_eval < te.len() && direction * (te[next_eval] - t_stop) <= 0. {
+                    let t = te[next_eval];
+                    times.push(t);
+                    if t == t_new {
+                        states.extend_from_slice(stepper.y());
+                    }
+                    else {
+                        states.extend(step.values(t));
+                    }
+                    next_eval += 1;
+                }
+            },
+            None => {
+                times.push(t_stop);
+                if terminal {
+                    states.extend(step.values(t_stop));
+                }
+                else {
+                    states.extend_from_slice(stepper.y());
+                }
+            }
+        }
+        if let Some(ref mut d) = dense {
+            d.push(step);
+        }
+        if terminal {
+            status = OdeStatus::TerminalEvent;
+            break;
+        }
+    }
+    let m = times.len();
+    let counters = stepper.counters();
+    Ok(OdeSolution {
+        t : Matrix::from_iter_cw(m, 1, times.into_iter()),
+        y : Matrix::from_iter_cw(n, m, states.into_iter()),
+        events : events,
+        status : status,
+        dense : dense,
+        evaluations : counters.evaluations,
+        jacobian_evaluations : counters.jacobian_evaluations,
+        decompositions : counters.decompositions,
+        accepted_steps : counters.accepted,
+        rejected_steps : counters.rejected
+    })
+}
+
+
+/// Checks the arguments of ``solve_ivp``
+fn check_arguments(t0 : f64, t1 : f64, y0 : &MatrixF64, options : &OdeOptions) -> SRResult<()> {
+    if y0.num_cells() == 0 {
+        return Err(SRError::EmptyMatrix);
+    }
+    if !y0.is_col() {
+        return Err(SRError::IsNotAColVector);
+    }
+    if !t0.is_finite() || !t1.is_finite() || t0 == t1 {
+        return Err(SRError::InvalidArgument);
+    }
+    if !(options.relative_tolerance > 0. && options.absolute_tolerance > 0. && options.max_step > 0.) {
+        return Err(SRError::InvalidArgument);
+    }
+    if options.initial_step.is_some_and(|h| h == 0. || h.is_nan()) {
+        return Err(SRError::InvalidArgument);
+    }
+    if let Some(ref te) = options.t_eval {
+        let direction = (t1 - t0).signum();
+        let inside = te.iter().all(|t| direction * (t - t0) >= 0. && direction * (t1 - t) >= 0.);
+        let ordered = te.windows(2).all(|w| direction * (w[1] - w[0]) > 0.);
+        if !inside || !ordered {
+            return Err(SRError::InvalidArgument);
+        }
+    }
+    Ok(())
+}
+
+
+#[doc="Solves the initial value problem y' = f(t, y), y(t0) = y0 on [t0, t1].
+
+y0 must be a column vector and t0 != t1. The columns of the
+returned ``y`` are the states at the times in ``t``.
+"]
+pub fn solve_ivp<S:OdeSystem + ?Sized>(system : &S, t0 : f64, t1 : f64, y0 : &MatrixF64,
+    options : &OdeOptions) -> SRResult<OdeSolution> {
+    check_arguments(t0, t1, y0, options)?;
+    let y0 = to_vec(y0);
+    match options.method {
+        OdeMethod::RK4 => integrate(system, Rk4Stepper::new(system, t0, &y0, t1, options)?, t1, options),
+        OdeMethod::DormandPrince | OdeMethod::Tsitouras =>
+            integrate(system, ExplicitStepper::new(system, t0, &y0, t1, options)?, t1, options),
+        OdeMethod::Rosenbrock => integrate(system, RosenbrockStepper::new(system, t0, &y0, t1, options)?, t1, options),
+        OdeMethod::Radau => integrate(system, RadauStepper::new(system, t0, &y0, t1, options)?, t1, options),
+        OdeMethod::Bdf => integrate(system, BdfStepper::new(system, t0, &y0, t1, options)?, t1, options)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn oscillator() -> FnOdeSystem<fn(f64, &MatrixF64) -> MatrixF64> {
+        fn f(_ : f64, y : &MatrixF64) -> MatrixF64 {
+            vector_f64(&[y[1], -y[0]])
+        }
+        FnOdeSystem::new(f)
+    }
+
+    #[test]
+    fn test_all_methods(){
+        let system = oscillator();
+        let y0 = vector_f64(&[1., 0.]);
+        let methods = [OdeMethod::RK4, OdeMethod::DormandPrince, OdeMethod::Tsitouras,
+            OdeMethod::Bdf, OdeMethod::Rosenbrock, OdeMethod::Radau];
+        for &method in methods.iter() {
+            let options = OdeOptions::new().method(method).tolerances(1e-8, 1e-10);
+            let s = solve_ivp(&system, 0., PI, &y0, &options).unwrap();
+            assert_eq!(s.status, OdeStatus::Completed);
+            assert_eq!(s.t[0], 0.);
+            assert_eq!(s.t[s.len() - 1], PI);
+            assert_eq!(s.y.num_rows(), 2);
+            assert_eq!(s.y.num_cols(), s.len());
+            let y = s.final_state();
+            let tolerance = if method == OdeMethod::Rosenbrock { 1e-4 } else { 1e-6 };
+            assert!((y[0] + 1.).abs() < tolerance && y[1].abs() < tolerance, "{:?} {:?}", method, y);
+        }
+    }
+
+    #[test]
+    fn test_t_eval_and_dense_output(){
+        let system = oscillator();
+        let times : Vec<f64> = (0..11).map(|i| 0.5 * i as f64).collect();
+        let options = OdeOptions::new().tolerances(1e-10, 1e-12).t_eval(&times).dense_output(true);
+        let s = solve_ivp(&system, 0., 5., &vector_f64(&[1., 0.]), &options).unwrap();
+        assert_eq!(s.len(), 11);
+        for (k, t) in times.iter().enumerate() {
+            assert_eq!(s.t[k], *t);
+            assert!((s.y.get(0, k).unwrap() - t.cos()).abs() < 1e-8);
+        }
+        let dense = s.dense.unwrap();
+        assert_eq!(dense.len(), s.accepted_steps);
+        assert!((dense.evaluate(3.3).unwrap()[1] + 3.3f64.sin()).abs() < 1e-8);
+        assert!(dense.evaluate(5.5).is_err());
+    }
+
+    #[test]
+    fn test_backwards(){
+        // y' = y from t = 1 back to 0
+        let system = FnOdeSystem::new(|_, y : &MatrixF64| y.clone());
+        for &method in [OdeMethod::DormandPrince, OdeMethod::Radau, OdeMethod::Bdf].iter() {
+            let options = OdeOptions::new().method(method).tolerances(1e-9, 1e-12);
+            let s = solve_ivp(&system, 1., 0., &vector_f64(&[1f64.exp()]), &options).unwrap();
+            assert_eq!(s.t[s.len() - 1], 0.);
+            assert!((s.final_state()[0] - 1.).abs() < 1e-6, "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn test_events(){
+        // A ball thrown upwards: h' = v, v' = -9.81 stops when it hits the ground
+        let ball = FnOdeSystem::new(|_, y : &MatrixF64| vector_f64(&[y[1], -9.81]));
+        let system = EventSystem::new(ball, |_, y : &MatrixF64| vector_f64(&[y[0], y[1]]),
+            &[EventDirection::Falling, EventDirection::Both], &[true, false]).unwrap();
+        let options = OdeOptions::new().tolerances(1e-10, 1e-12);
+        let s = solve_ivp(&system, 0., 10., &vector_f64(&[0., 10.]), &options).unwrap();
+        assert_eq!(s.status, OdeStatus::TerminalEvent);
+        // The apex at t = 10 / 9.81 and the landing at twice that time
+        assert_eq!(s.events.len(), 2);
+        assert_eq!(s.events[0].index, 1);
+        assert!((s.events[0].t - 10. / 9.81).abs() < 1e-10);
+        assert_eq!(s.events[1].index, 0);
+        assert!((s.events[1].t - 20. / 9.81).abs() < 1e-10);
+        assert!(s.events[1].y[0].abs() < 1e-9);
+        assert_eq!(s.t[s.len() - 1], s.events[1].t);
+        // The start on the ground does not trigger the falling event
+        assert!(s.events.iter().all(|e| e.t > 0.));
+    }
+
+    #[test]
+    fn test_errors(){
+        let system = oscillator();
+        let options = OdeOptions::new();
+        let y0 = vector_f64(&[1., 0.]);
+        assert!(solve_ivp(&system, 0., 0., &y0, &options).is_err());
+        assert!(solve_ivp(&system, 0., f64::INFINITY, &y0, &options).is_err());
+        assert!(solve_ivp(&system, 0., 1., &y0.transpose(), &options).is_err());
+        // f returns two entries for three states
+        assert!(solve_ivp(&system, 0., 1., &vector_f64(&[1., 0., 0.]), &options).is_err());
+        assert!(solve_ivp(&system, 0., 1., &y0, &OdeOptions::new().t_eval(&[0.5, 0.2])).is_err());
+        assert!(solve_ivp(&system, 0., 1., &y0, &OdeOptions::new().t_eval(&[2.])).is_err());
+        let s = solve_ivp(&system, 0., 100., &y0, &OdeOptions::new().max_steps(5)).unwrap();
+        assert_eq!(s.status, OdeStatus::MaxSteps);
+        assert_eq!(s.len(), 6);
+    }
+}
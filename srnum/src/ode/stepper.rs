@@ -0,0 +1,178 @@
+#![doc="Common interface of the ODE integrators
+
+A stepper holds the current time and state and advances them
+by one accepted step at a time. The driver in ``solve``
+collects the output, detects events and keeps the dense
+output.
+"]
+
+
+// std imports
+use std::f64;
+
+// srmatrix imports
+use srmatrix::api::*;
+use srmatrix::vecops::{to_vec, to_col};
+
+// local imports
+use ode::system::*;
+use ode::dense::StepInterpolant;
+use ode::vecops::*;
+use ode::options::OdeOptions;
+
+
+/// Smallest factor by which a step size is reduced at once
+pub const MIN_FACTOR : f64 = 0.2;
+/// Largest factor by which a step size is enlarged at once
+pub const MAX_FACTOR : f64 = 10.;
+/// Safety factor of the step size control
+pub const SAFETY : f64 = 0.9;
+
+
+/// Counts of the work done by a stepper
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Counters {
+    /// Evaluations of f
+    pub evaluations : usize,
+    /// Evaluations of the Jacobian
+    pub jacobian_evaluations : usize,
+    /// LU factorizations
+    pub decompositions : usize,
+    /// Accepted steps
+    pub accepted : usize,
+    /// Rejected steps
+    pub rejected : usize
+}
+
+
+/// An integrator advancing the solution step by step
+pub trait Stepper {
+
+    /// Returns the current time
+    fn t(&self) -> f64;
+
+    /// Returns the current state
+    fn y(&self) -> &[f64];
+
+    /// Takes one accepted step which does not pass t_end
+    fn step<S:OdeSystem + ?Sized>(&mut self, system : &S, t_end : f64) -> SRResult<()>;
+
+    /// Returns the interpolant of the last step
+    fn interpolant(&self) -> StepInterpolant;
+
+    /// Returns the work done so far
+    fn counters(&self) -> Counters;
+}
+
+
+/// Evaluates f(t, y) and checks its dimension
+pub fn evaluate<S:OdeSystem + ?Sized>(system : &S, t : f64, y : &[f64],
+    counters : &mut Counters) -> SRResult<Vec<f64>> {
+    counters.evaluations += 1;
+    let f = system.rhs(t, &to_col(y));
+    if f.num_rows() != y.len() || f.num_cols() != 1 {
+        return Err(SRError::DimensionsMismatch);
+    }
+    Ok(to_vec(&f))
+}
+
+
+/// Evaluates the Jacobian at (t, y) and checks its dimensions
+pub fn evaluate_jacobian<S:OdeSystem + ?Sized>(system : &S, t : f64, y : &[f64],
+    counters : &mut Counters) -> SRResult<MatrixF64> {
+    counters.jacobian_evaluations += 1;
+    let jac = system.jacobian(t, &to_col(y));
+    if jac.num_rows() != y.len() || jac.num_cols() != y.len() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    Ok(jac)
+}
+
+
+/// Fails if the step size has become negligible relative to t
+pub fn check_step_size(t : f64, h_abs : f64) -> SRResult<()> {
+    if h_abs.is_nan() || h_abs <= 10. * f64::EPSILON * t.abs().max(f64::MIN_POSITIVE) {
+        return Err(SRError::NotConverged);
+    }
+    Ok(())
+}
+
+
+/// Step size and tolerances of an adaptive stepper
+#[derive(Debug, Clone, Copy)]
+pub struct StepControl {
+    /// 1 for integration forward in time, -1 backward
+    pub direction : f64,
+    /// The current step size
+    pub h_abs : f64,
+    /// Relative tolerance
+    pub rtol : f64,
+    /// Absolute tolerance
+    pub atol : f64,
+    /// Largest allowed step size
+    pub max_step : f64,
+    /// Length of the integration interval
+    pub span : f64
+}
+
+impl StepControl {
+
+    /// Step control for the integration from t0 to t_end
+    pub fn new(options : &OdeOptions, t0 : f64, t_end : f64) -> StepControl {
+        StepControl {
+            direction : if t_end >= t0 { 1. } else { -1. },
+            h_abs : options.initial_step.map_or(0., |h| h.abs().min(options.max_step)),
+            rtol : options.relative_tolerance,
+            atol : options.absolute_tolerance,
+            max_step : options.max_step,
+            span : (t_end - t0).abs()
+        }
+    }
+
+    /// Returns the error weights atol + rtol max(|y_i|, |z_i|)
+    pub fn scale(&self, y : &[f64], z : &[f64]) -> Vec<f64> {
+        error_scale(y, z, self.rtol, self.atol)
+    }
+
+    /// Limits the step size to max_step and the step to t_end.
+    /// Returns the end of the step and the signed step size.
+    pub fn clip(&mut self, t : f64, t_end : f64) -> (f64, f64) {
+        self.h_abs = self.h_abs.min(self.max_step);
+        let t_new = t + self.direction * self.h_abs;
+        if self.direction * (t_new - t_end) > 0. {
+            (t_end, t_end - t)
+        }
+        else {
+            (t_new, t_new - t)
+        }
+    }
+
+    /// Chooses the size of the first step for a method of the
+    /// given order unless an initial step was given (Hairer,
+    /// Norsett and Wanner, 1993, II.4)
+    pub fn select_initial_step<S:OdeSystem + ?Sized>(&mut self, system : &S, t0 : f64,
+        y0 : &[f64], f0 : &[f64], order : usize,
+        counters : &mut Counters) -> SRResult<()> {
+        if self.h_abs > 0. {
+            return Ok(());
+        }
+        let span = self.span;
+        let scale : Vec<f64> = y0.iter().map(|v| self.atol + self.rtol * v.abs()).collect();
+        let d0 = rms_norm(y0, &scale);
+        let d1 = rms_norm(f0, &scale);
+        let h0 = if d0 < 1e-5 || d1 < 1e-5 { 1e-6 } else { 0.01 * d0 / d1 };
+        let h0 = h0.min(span);
+        let y1 = axpy(y0, self.direction * h0, f0);
+        let f1 = evaluate(system, t0 + self.direction * h0, &y1, counters)?;
+        let df : Vec<f64> = f1.iter().zip(f0.iter()).map(|(a, b)| a - b).collect();
+        let d2 = rms_norm(&df, &scale) / h0;
+        let h1 = if d1 <= 1e-15 && d2 <= 1e-15 {
+            (h0 * 1e-3).max(1e-6)
+        }
+        else {
+            (0.01 / d1.max(d2)).powf(1. / (order as f64 + 1.))
+        };
+        self.h_abs = (100. * h0).min(h1).min(span).min(self.max_step);
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+#![doc="Systems of ordinary differential equations
+
+An initial value problem y' = f(t, y), y(t0) = y0 is described
+by an ``OdeSystem``. States are column vectors. The Jacobian
+df/dy needed by the stiff solvers is approximated by forward
+differences unless the system supplies it.
+
+A system may also define event functions g_i(t, y). The
+solvers locate the zeros of the g_i along the trajectory and
+may stop at them.
+"]
+
+
+// std imports
+use std::f64;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+
+/// The sign changes of an event function which trigger the event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventDirection {
+    /// Any zero crossing
+    Both,
+    /// Crossings from negative to positive values
+    Rising,
+    /// Crossings from positive to negative values
+    Falling
+}
+
+
+/// A system y' = f(t, y)
+pub trait OdeSystem {
+
+    /// Evaluates f(t, y) for the column vector y
+    fn rhs(&self, t : f64, y : &MatrixF64) -> MatrixF64;
+
+    /// Evaluates the n x n Jacobian df/dy at (t, y)
+    fn jacobian(&self, t : f64, y : &MatrixF64) -> MatrixF64 {
+        forward_difference_jacobian(self, t, y, &self.rhs(t, y))
+    }
+
+    /// Returns the number of event functions
+    fn num_events(&self) -> usize {
+        0
+    }
+
+    /// Evaluates the event functions g(t, y) as a column vector
+    fn events(&self, _t : f64, _y : &MatrixF64) -> MatrixF64 {
+        Matrix::zeros(self.num_events(), 1)
+    }
+
+    /// Returns the crossings which trigger the i-th event
+    fn event_direction(&self, _i : usize) -> EventDirection {
+        EventDirection::Both
+    }
+
+    /// Indicates if the i-th event stops the integration
+    fn is_terminal(&self, _i : usize) -> bool {
+        false
+    }
+}
+
+
+impl<S:OdeSystem + ?Sized> OdeSystem for &S {
+
+    fn rhs(&self, t : f64, y : &MatrixF64) -> MatrixF64 {
+        (**self).rhs(t, y)
+    }
+
+    fn jacobian(&self, t : f64, y : &MatrixF64) -> MatrixF64 {
+        (**self).jacobian(t, y)
+    }
+
+    fn num_events(&self) -> usize {
+        (**self).num_events()
+    }
+
+    fn events(&self, t : f64, y : &MatrixF64) -> MatrixF64 {
+        (**self).events(t, y)
+    }
+
+    fn event_direction(&self, i : usize) -> EventDirection {
+        (**self).event_direction(i)
+    }
+
+    fn is_terminal(&self, i : usize) -> bool {
+        (**self).is_terminal(i)
+    }
+}
+
+
+/// Approximates df/dy at (t, y) by forward differences given
+/// f0 = f(t, y)
+pub fn forward_difference_jacobian<S:OdeSystem + ?Sized>(system : &S, t : f64, y : &MatrixF64,
+    f0 : &MatrixF64) -> MatrixF64 {
+    let n = y.num_rows();
+    let scale = f64::EPSILON.sqrt();
+    let mut jac = Matrix::zeros(n, n);
+    let mut z = y.clone();
+    for j in 0..n {
+        let yj = y[j];
+        let h = scale * yj.abs().max(1.);
+        z.set(j, 0, yj + h);
+        let fj = system.rhs(t, &z);
+        z.set(j, 0, yj);
+        for i in 0..n {
+            jac.set(i, j, (fj[i] - f0[i]) / h);
+        }
+    }
+    jac
+}
+
+
+/// A system defined by a closure computing f(t, y)
+pub struct FnOdeSystem<F> where F : Fn(f64, &MatrixF64) -> MatrixF64 {
+    f : F
+}
+
+impl<F> FnOdeSystem<F> where F : Fn(f64, &MatrixF64) -> MatrixF64 {
+
+    /// Wraps a closure computing f(t, y)
+    pub fn new(f : F) -> FnOdeSystem<F> {
+        FnOdeSystem{f : f}
+    }
+}
+
+impl<F> OdeSystem for FnOdeSystem<F> where F : Fn(f64, &MatrixF64) -> MatrixF64 {
+
+    fn rhs(&self, t : f64, y : &MatrixF64) -> MatrixF64 {
+        (self.f)(t, y)
+    }
+}
+
+
+/// Adds event functions given by a closure to a system
+pub struct EventSystem<S, G> where S : OdeSystem, G : Fn(f64, &MatrixF64) -> MatrixF64 {
+    system : S,
+    g : G,
+    directions : Vec<EventDirection>,
+    terminal : Vec<bool>
+}
+
+impl<S, G> EventSystem<S, G> where S : OdeSystem, G : Fn(f64, &MatrixF64) -> MatrixF64 {
+
+    /// Wraps a system with event functions g(t, y). The i-th
+    /// entry of g is triggered by crossings in directions[i] and
+    /// stops the integration if terminal[i] is set.
+    pub fn new(system : S, g : G, directions : &[EventDirection],
+        terminal : &[bool]) -> SRResult<EventSystem<S, G>> {
+        if directions.len() != terminal.len() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(EventSystem {
+            system : system,
+            g : g,
+            directions : directions.to_vec(),
+            terminal : terminal.to_vec()
+        })
+    }
+}
+
+impl<S, G> OdeSystem for EventSystem<S, G> where S : OdeSystem, G : Fn(f64, &MatrixF64) -> MatrixF64 {
+
+    fn rhs(&self, t : f64, y : &MatrixF64) -> MatrixF64 {
+        self.system.rhs(t, y)
+    }
+
+    fn jacobian(&self, t : f64, y : &MatrixF64) -> MatrixF64 {
+        self.system.jacobian(t, y)
+    }
+
+    fn num_events(&self) -> usize {
+        self.directions.len()
+    }
+
+    fn events(&self, t : f64, y : &MatrixF64) -> MatrixF64 {
+        (self.g)(t, y)
+    }
+
+    fn event_direction(&self, i : usize) -> EventDirection {
+        self.directions[i]
+    }
+
+    fn is_terminal(&self, i : usize) -> bool {
+        self.terminal[i]
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    struct Pendulum;
+
+    impl OdeSystem for Pendulum {
+        fn rhs(&self, _t : f64, y : &MatrixF64) -> MatrixF64 {
+            vector_f64(&[y[1], -y[0].sin()])
+        }
+    }
+
+    #[test]
+    fn test_jacobian(){
+        let y = vector_f64(&[0.5, 1.]);
+        let j = Pendulum.jacobian(0., &y);
+        assert!(j.get(0, 0).unwrap().abs() < 1e-7);
+        assert!((j.get(0, 1).unwrap() - 1.).abs() < 1e-7);
+        assert!((j.get(1, 0).unwrap() + 0.5f64.cos()).abs() < 1e-7);
+        let s = FnOdeSystem::new(|t, y : &MatrixF64| vector_f64(&[t * y[0]]));
+        assert_eq!(s.rhs(2., &vector_f64(&[3.])), vector_f64(&[6.]));
+        assert_eq!(s.num_events(), 0);
+    }
+
+    #[test]
+    fn test_event_system(){
+        let s = EventSystem::new(Pendulum, |_, y : &MatrixF64| vector_f64(&[y[0], y[1]]),
+            &[EventDirection::Rising, EventDirection::Both], &[true, false]).unwrap();
+        assert_eq!(s.num_events(), 2);
+        assert!(s.is_terminal(0) && !s.is_terminal(1));
+        assert_eq!(s.event_direction(0), EventDirection::Rising);
+        assert_eq!(s.events(0., &vector_f64(&[1., 2.])), vector_f64(&[1., 2.]));
+        assert!(EventSystem::new(Pendulum, |_, y : &MatrixF64| y.clone(),
+            &[EventDirection::Both], &[]).is_err());
+    }
+}
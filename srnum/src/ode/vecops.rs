@@ -0,0 +1,59 @@
+#![doc="Dense vector kernels used by the ODE solvers
+
+The solvers keep states and stage derivatives as plain vectors
+and convert to column vectors only when calling the system.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Returns y + h k
+pub fn axpy(y : &[f64], h : f64, k : &[f64]) -> Vec<f64> {
+    y.iter().zip(k.iter()).map(|(a, b)| a + h * b).collect()
+}
+
+
+/// Returns y + h sum_j c_j k_j skipping the zero coefficients
+pub fn add_scaled(y : &[f64], h : f64, c : &[f64], k : &[Vec<f64>]) -> Vec<f64> {
+    let mut r = y.to_vec();
+    for (cj, kj) in c.iter().zip(k.iter()) {
+        if *cj != 0. {
+            let s = h * cj;
+            for (ri, ki) in r.iter_mut().zip(kj.iter()) {
+                *ri += s * ki;
+            }
+        }
+    }
+    r
+}
+
+
+/// Returns the root mean square of v_i / scale_i
+pub fn rms_norm(v : &[f64], scale : &[f64]) -> f64 {
+    if v.is_empty() {
+        return 0.;
+    }
+    let s = v.iter().zip(scale.iter()).fold(0., |s, (a, b)| s + (a / b) * (a / b));
+    (s / v.len() as f64).sqrt()
+}
+
+
+/// Returns the error weights atol + rtol max(|y_i|, |z_i|)
+pub fn error_scale(y : &[f64], z : &[f64], rtol : f64, atol : f64) -> Vec<f64> {
+    y.iter().zip(z.iter()).map(|(a, b)| atol + rtol * a.abs().max(b.abs())).collect()
+}
+
+
+/// Returns I - c J
+pub fn shifted_identity(c : f64, jac : &MatrixF64) -> MatrixF64 {
+    let n = jac.num_rows();
+    Matrix::from_iter_cw(n, n, (0..n * n).map(|k| {
+        let (i, j) = (k % n, k / n);
+        let d = if i == j { 1. } else { 0. };
+        d - c * jac.get(i, j).unwrap()
+    }))
+}
@@ -0,0 +1,110 @@
+#![doc="Least squares polynomial fitting
+
+``polyfit`` finds the polynomial p of a given degree n
+minimizing
+
+sum_i (p(x_i) - y_i)^2
+
+The abscissas are first mapped affinely onto [-1, 1], where the
+Vandermonde matrix is far better conditioned, and the least
+squares problem is solved by a QR decomposition. The fitted
+polynomial is then transformed back to the original variable.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// srlinalg imports
+use srlinalg::qr::QRDecomposition;
+
+// local imports
+use poly::polynomial::Polynomial;
+
+
+/// Fits a polynomial of the given degree to the points
+/// (x_i, y_i) by least squares. At least degree + 1 points with
+/// distinct abscissas are needed.
+pub fn polyfit(x : &MatrixF64, y : &MatrixF64, degree : usize) -> SRResult<Polynomial<f64>> {
+    if !(x.is_row() || x.is_col()) || !(y.is_row() || y.is_col()) {
+        return Err(SRError::IsNotAVector);
+    }
+    if x.num_cells() != y.num_cells() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let xs : Vec<f64> = (0..x.num_cells()).map(|i| x[i]).collect();
+    let ys : Vec<f64> = (0..y.num_cells()).map(|i| y[i]).collect();
+    if xs.len() <= degree {
+        return Err(SRError::InvalidArgument);
+    }
+    if xs.iter().chain(ys.iter()).any(|v| !v.is_finite()) {
+        return Err(SRError::InvalidArgument);
+    }
+    // t = (x - shift) / width maps the abscissas onto [-1, 1]
+    let lo = xs.iter().fold(f64::INFINITY, |m, v| m.min(*v));
+    let hi = xs.iter().fold(f64::NEG_INFINITY, |m, v| m.max(*v));
+    let shift = 0.5 * (lo + hi);
+    let width = if hi > lo { 0.5 * (hi - lo) } else { 1. };
+    let m = xs.len();
+    let n = degree + 1;
+    let a = Matrix::from_iter_cw(m, n, (0..m * n).map(|k| {
+        let (i, j) = (k % m, k / m);
+        ((xs[i] - shift) / width).powi(j as i32)
+    }));
+    let qr = QRDecomposition::new(&a);
+    if qr.rank(None) < n {
+        return Err(SRError::IsSingular);
+    }
+    let b = Matrix::from_iter_cw(m, 1, ys.into_iter());
+    let c = qr.solve_least_squares(&b)?;
+    let p = Polynomial::new(&(0..n).map(|j| c[j]).collect::<Vec<f64>>());
+    Ok(p.compose(&Polynomial::new(&[-shift / width, 1. / width])))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_exact_fit(){
+        let p = Polynomial::new(&[2., -1., 0.5, 0.25]);
+        let xs = [10., 11., 12.5, 13., 14., 16., 17.];
+        let x = vector_f64(&xs);
+        let y = p.values(&x);
+        let q = polyfit(&x, &y, 3).unwrap();
+        assert_eq!(q.degree(), 3);
+        for k in 0..4 {
+            assert!((q.coefficient(k) - p.coefficient(k)).abs() < 1e-8, "{:?}", q);
+        }
+    }
+
+    #[test]
+    fn test_least_squares(){
+        // The best line through symmetric deviations from y = 1 + 2 x
+        let x = vector_f64(&[0., 1., 2., 3.]);
+        let y = vector_f64(&[1.5, 2.5, 5.5, 6.5]);
+        let q = polyfit(&x, &y, 1).unwrap();
+        assert!((q.coefficient(0) - 1.3).abs() < 1e-12);
+        assert!((q.coefficient(1) - 1.8).abs() < 1e-12);
+        // Degree 0 is the mean
+        let c = polyfit(&x, &y, 0).unwrap();
+        assert!((c.coefficient(0) - 4.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_errors(){
+        let x = vector_f64(&[0., 1., 2.]);
+        assert!(polyfit(&x, &x, 3).is_err());
+        assert!(polyfit(&x, &vector_f64(&[0., 1.]), 1).is_err());
+        assert!(polyfit(&vector_f64(&[1., 1., 1.]), &x, 1).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+#![doc="Classical orthogonal polynomials
+
+Each family p_0, p_1, ... is orthogonal with respect to a weight
+function w on an interval:
+
+| Family      | Interval    | w(x)             |
+|-------------|-------------|------------------|
+| ChebyshevT  | [-1, 1]     | 1 / sqrt(1 - x^2)|
+| ChebyshevU  | [-1, 1]     | sqrt(1 - x^2)    |
+| Legendre    | [-1, 1]     | 1                |
+| Hermite     | (-inf, inf) | exp(-x^2)        |
+| HermiteE    | (-inf, inf) | exp(-x^2 / 2)    |
+| Laguerre    | [0, inf)    | exp(-x)          |
+
+``Hermite`` is the physicists' and ``HermiteE`` the
+probabilists' normalization. All families satisfy a three term
+recurrence
+
+p_k+1(x) = (a_k x + b_k) p_k(x) - c_k p_k-1(x)
+
+with p_0 = 1 and p_-1 = 0, which is used both for the values
+and for the coefficients. Evaluating by the recurrence is stable,
+unlike evaluating the monomial coefficients for large degrees.
+"]
+
+
+// std imports
+use std::f64::consts::PI;
+
+// external imports
+use num::traits::Zero;
+
+// local imports
+use poly::polynomial::Polynomial;
+
+
+/// The classical orthogonal polynomial families
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrthogonalFamily {
+    /// Chebyshev polynomials of the first kind T_n
+    ChebyshevT,
+    /// Chebyshev polynomials of the second kind U_n
+    ChebyshevU,
+    /// Legendre polynomials P_n
+    Legendre,
+    /// Physicists' Hermite polynomials H_n
+    Hermite,
+    /// Probabilists' Hermite polynomials He_n
+    HermiteE,
+    /// Laguerre polynomials L_n
+    Laguerre
+}
+
+impl OrthogonalFamily {
+
+    /// Returns the recurrence coefficients (a_k, b_k, c_k)
+    fn recurrence(&self, k : usize) -> (f64, f64, f64) {
+        let k = k as f64;
+        match *self {
+            OrthogonalFamily::ChebyshevT => if k == 0. { (1., 0., 0.) } else { (2., 0., 1.) },
+            OrthogonalFamily::ChebyshevU => (2., 0., 1.),
+            OrthogonalFamily::Legendre => ((2. * k + 1.) / (k + 1.), 0., k / (k + 1.)),
+            OrthogonalFamily::Hermite => (2., 0., 2. * k),
+            OrthogonalFamily::HermiteE => (1., 0., k),
+            OrthogonalFamily::Laguerre => (-1. / (k + 1.), (2. * k + 1.) / (k + 1.), k / (k + 1.))
+        }
+    }
+
+    /// Returns the polynomial of degree n
+    pub fn polynomial(&self, n : usize) -> Polynomial<f64> {
+        let mut previous = Polynomial::zero();
+        let mut current = Polynomial::constant(1.);
+        for k in 0..n {
+            let (a, b, c) = self.recurrence(k);
+            let next = &(&Polynomial::new(&[b, a]) * &current) - &previous.scale(c);
+            previous = current;
+            current = next;
+        }
+        current
+    }
+
+    /// Returns the polynomials of degrees 0, ..., n
+    pub fn polynomials(&self, n : usize) -> Vec<Polynomial<f64>> {
+        let mut result : Vec<Polynomial<f64>> = Vec::with_capacity(n + 1);
+        result.push(Polynomial::constant(1.));
+        for k in 0..n {
+            let (a, b, c) = self.recurrence(k);
+            let mut next = &Polynomial::new(&[b, a]) * &result[k];
+            if k > 0 {
+                next = &next - &result[k - 1].scale(c);
+            }
+            result.push(next);
+        }
+        result
+    }
+
+    /// Returns p_n(x) by the recurrence
+    pub fn value(&self, n : usize, x : f64) -> f64 {
+        let mut previous = 0.;
+        let mut current = 1.;
+        for k in 0..n {
+            let (a, b, c) = self.recurrence(k);
+            let next = (a * x + b) * current - c * previous;
+            previous = current;
+            current = next;
+        }
+        current
+    }
+
+    /// Returns the interval of orthogonality
+    pub fn domain(&self) -> (f64, f64) {
+        match *self {
+            OrthogonalFamily::ChebyshevT | OrthogonalFamily::ChebyshevU
+                | OrthogonalFamily::Legendre => (-1., 1.),
+            OrthogonalFamily::Hermite | OrthogonalFamily::HermiteE => (f64::NEG_INFINITY, f64::INFINITY),
+            OrthogonalFamily::Laguerre => (0., f64::INFINITY)
+        }
+    }
+
+    /// Returns the weight function w(x), which is zero outside
+    /// the interval of orthogonality
+    pub fn weight(&self, x : f64) -> f64 {
+        let (lo, hi) = self.domain();
+        if x < lo || x > hi {
+            return 0.;
+        }
+        match *self {
+            OrthogonalFamily::ChebyshevT => 1. / (1. - x * x).sqrt(),
+            OrthogonalFamily::ChebyshevU => (1. - x * x).sqrt(),
+            OrthogonalFamily::Legendre => 1.,
+            OrthogonalFamily::Hermite => (-x * x).exp(),
+            OrthogonalFamily::HermiteE => (-0.5 * x * x).exp(),
+            OrthogonalFamily::Laguerre => (-x).exp()
+        }
+    }
+
+    /// Returns the squared norm, the integral of w p_n^2 over
+    /// the interval
+    pub fn norm_squared(&self, n : usize) -> f64 {
+        let factorial = (1..n + 1).fold(1., |f, k| f * k as f64);
+        match *self {
+            OrthogonalFamily::ChebyshevT => if n == 0 { PI } else { 0.5 * PI },
+            OrthogonalFamily::ChebyshevU => 0.5 * PI,
+            OrthogonalFamily::Legendre => 2. / (2 * n + 1) as f64,
+            OrthogonalFamily::Hermite => PI.sqrt() * 2f64.powi(n as i32) * factorial,
+            OrthogonalFamily::HermiteE => (2. * PI).sqrt() * factorial,
+            OrthogonalFamily::Laguerre => 1.
+        }
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use quad::gauss::GaussRule;
+
+    #[test]
+    fn test_coefficients(){
+        let cases : Vec<(OrthogonalFamily, usize, Vec<f64>)> = vec![
+            (OrthogonalFamily::ChebyshevT, 3, vec![0., -3., 0., 4.]),
+            (OrthogonalFamily::ChebyshevU, 2, vec![-1., 0., 4.]),
+            (OrthogonalFamily::Legendre, 2, vec![-0.5, 0., 1.5]),
+            (OrthogonalFamily::Hermite, 3, vec![0., -12., 0., 8.]),
+            (OrthogonalFamily::HermiteE, 3, vec![0., -3., 0., 1.]),
+            (OrthogonalFamily::Laguerre, 2, vec![1., -2., 0.5]),
+        ];
+        for &(family, n, ref c) in cases.iter() {
+            let p = family.polynomial(n);
+            assert_eq!(p.degree(), n);
+            for (k, v) in c.iter().enumerate() {
+                assert!((p.coefficient(k) - v).abs() < 1e-14, "{:?} {:?}", family, p);
+            }
+            assert_eq!(family.polynomials(n)[n], p);
+        }
+        assert_eq!(OrthogonalFamily::Legendre.polynomial(0).coefficients(), &[1.]);
+    }
+
+    #[test]
+    fn test_values(){
+        // T_n(cos t) = cos(n t)
+        let t : f64 = 0.7;
+        assert!((OrthogonalFamily::ChebyshevT.value(7, t.cos()) - (7. * t).cos()).abs() < 1e-13);
+        // U_n(cos t) = sin((n + 1) t) / sin t
+        assert!((OrthogonalFamily::ChebyshevU.value(5, t.cos()) - (6. * t).sin() / t.sin()).abs() < 1e-13);
+        let families = [OrthogonalFamily::Legendre, OrthogonalFamily::Hermite,
+            OrthogonalFamily::HermiteE, OrthogonalFamily::Laguerre];
+        for family in families.iter() {
+            let p = family.polynomial(6);
+            for x in [-0.9, 0.1, 0.5, 1.3].iter() {
+                let v = family.value(6, *x);
+                assert!((p.evaluate(*x) - v).abs() < 1e-10 * (1. + v.abs()), "{:?}", family);
+            }
+        }
+        assert_eq!(OrthogonalFamily::Laguerre.weight(-1.), 0.);
+    }
+
+    #[test]
+    fn test_orthogonality(){
+        let cases = [(OrthogonalFamily::Legendre, GaussRule::legendre(10).unwrap()),
+            (OrthogonalFamily::Hermite, GaussRule::hermite(10).unwrap()),
+            (OrthogonalFamily::Laguerre, GaussRule::laguerre(10, 0.).unwrap())];
+        // The rules include the weight functions and are exact
+        // for products of degree below 20
+        for &(family, ref rule) in cases.iter() {
+            for m in 0..6 {
+                for n in 0..6 {
+                    let s = rule.integrate(|x| family.value(m, x) * family.value(n, x));
+                    let expected = if m == n { family.norm_squared(n) } else { 0. };
+                    assert!((s - expected).abs() < 1e-10 * (1. + expected), "{:?} {} {}", family, m, n);
+                }
+            }
+        }
+    }
+}
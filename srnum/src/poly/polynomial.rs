@@ -0,0 +1,451 @@
+#![doc="Polynomials in one variable
+
+A polynomial
+
+p(x) = c_0 + c_1 x + ... + c_n x^n
+
+is stored by its coefficients in ascending order of the powers.
+Trailing zero coefficients are removed, so the zero polynomial
+has no coefficients and two equal polynomials have equal
+coefficient vectors.
+
+The coefficients may come from any commutative ring, e.g.
+``i64``, ``f64`` or ``Complex64``. Division with remainder, the
+greatest common divisor and integration need a field of
+coefficients. In floating point arithmetic the exact Euclidean
+algorithm rarely finds a non-trivial common divisor, hence
+``approximate_gcd`` drops remainders below a tolerance.
+"]
+
+
+// std imports
+use std::ops::{Add, Sub, Mul, Neg};
+
+// external imports
+use num::traits::{Zero, One};
+
+// srmatrix imports
+use srmatrix::api::*;
+use sralgebra::{CommutativeRingPartial, FieldPartial};
+
+
+/// A polynomial with coefficients in a commutative ring
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T:CommutativeRingPartial> {
+    /// The coefficients c_0, ..., c_n with c_n != 0
+    coefficients : Vec<T>
+}
+
+
+/******************************************************
+ *
+ *   Construction and evaluation
+ *
+ *******************************************************/
+
+impl<T:CommutativeRingPartial> Polynomial<T> {
+
+    /// Creates a polynomial from the coefficients c_0, c_1, ...
+    /// in ascending order of the powers
+    pub fn new(coefficients : &[T]) -> Polynomial<T> {
+        Polynomial::from_vec(coefficients.to_vec())
+    }
+
+    fn from_vec(mut coefficients : Vec<T>) -> Polynomial<T> {
+        while coefficients.last().is_some_and(|c| c.is_zero()) {
+            coefficients.pop();
+        }
+        Polynomial { coefficients : coefficients }
+    }
+
+    /// Creates the constant polynomial c
+    pub fn constant(c : T) -> Polynomial<T> {
+        Polynomial::from_vec(vec![c])
+    }
+
+    /// Creates the monomial c x^k
+    pub fn monomial(c : T, k : usize) -> Polynomial<T> {
+        let mut coefficients = vec![T::zero(); k + 1];
+        coefficients[k] = c;
+        Polynomial::from_vec(coefficients)
+    }
+
+    /// Creates the monic polynomial (x - r_1) ... (x - r_n)
+    pub fn from_roots(roots : &[T]) -> Polynomial<T> {
+        let mut coefficients = vec![T::one()];
+        for r in roots {
+            // Multiply by x - r
+            let mut next = vec![T::zero(); coefficients.len() + 1];
+            for (k, c) in coefficients.iter().enumerate() {
+                next[k + 1] = next[k + 1] + *c;
+                next[k] = next[k] - *r * *c;
+            }
+            coefficients = next;
+        }
+        Polynomial::from_vec(coefficients)
+    }
+
+    /// Returns the coefficients in ascending order of the powers
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    /// Returns the coefficient of x^k
+    pub fn coefficient(&self, k : usize) -> T {
+        self.coefficients.get(k).cloned().unwrap_or_else(T::zero)
+    }
+
+    /// Returns the degree. The zero polynomial has degree 0.
+    pub fn degree(&self) -> usize {
+        if self.coefficients.is_empty() { 0 } else { self.coefficients.len() - 1 }
+    }
+
+    /// Returns the coefficient of the highest power
+    pub fn leading_coefficient(&self) -> T {
+        self.coefficients.last().cloned().unwrap_or_else(T::zero)
+    }
+
+    /// Evaluates the polynomial at x by Horner's scheme
+    pub fn evaluate(&self, x : T) -> T {
+        self.coefficients.iter().rev().fold(T::zero(), |s, c| s * x + *c)
+    }
+
+    /// Evaluates the polynomial at x in a ring containing the
+    /// coefficients, e.g. a real polynomial at a complex point
+    pub fn evaluate_in<U>(&self, x : U) -> U
+    where U : CommutativeRingPartial + From<T> {
+        self.coefficients.iter().rev().fold(U::zero(), |s, c| s * x + U::from(*c))
+    }
+
+    /// Returns the derivative
+    pub fn derivative(&self) -> Polynomial<T> {
+        let mut factor = T::zero();
+        let coefficients = self.coefficients.iter().skip(1).map(|c| {
+            factor = factor + T::one();
+            factor * *c
+        }).collect();
+        Polynomial::from_vec(coefficients)
+    }
+
+    /// Returns the polynomial multiplied by the scalar c
+    pub fn scale(&self, c : T) -> Polynomial<T> {
+        Polynomial::from_vec(self.coefficients.iter().map(|v| *v * c).collect())
+    }
+
+    /// Returns the composition p(q(x))
+    pub fn compose(&self, q : &Polynomial<T>) -> Polynomial<T> {
+        self.coefficients.iter().rev().fold(Polynomial::zero(), |s, c| &(&s * q) + &Polynomial::constant(*c))
+    }
+
+    /// Returns the n-th power
+    pub fn pow(&self, n : usize) -> Polynomial<T> {
+        let mut result = Polynomial::one();
+        let mut base = self.clone();
+        let mut n = n;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = &result * &base;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+}
+
+
+/******************************************************
+ *
+ *   Division, GCD and integration
+ *
+ *******************************************************/
+
+impl<T:FieldPartial> Polynomial<T> {
+
+    /// Divides by a non-zero divisor. Returns the quotient q and
+    /// the remainder r with self = q divisor + r and
+    /// deg r < deg divisor.
+    pub fn div_rem(&self, divisor : &Polynomial<T>) -> SRResult<(Polynomial<T>, Polynomial<T>)> {
+        if divisor.is_zero() {
+            return Err(SRError::DivideByZero);
+        }
+        let n = divisor.coefficients.len();
+        if self.coefficients.len() < n {
+            return Ok((Polynomial::zero(), self.clone()));
+        }
+        let lead = divisor.leading_coefficient();
+        let mut r = self.coefficients.clone();
+        let mut q = vec![T::zero(); r.len() + 1 - n];
+        for k in (0..q.len()).rev() {
+            let f = r[k + n - 1] / lead;
+            q[k] = f;
+            for (j, d) in divisor.coefficients.iter().enumerate() {
+                r[k + j] = r[k + j] - f * *d;
+            }
+            // Exactly zero even if rounding leaves a residue
+            r[k + n - 1] = T::zero();
+        }
+        r.truncate(n - 1);
+        Ok((Polynomial::from_vec(q), Polynomial::from_vec(r)))
+    }
+
+    /// Returns the polynomial divided by its leading coefficient.
+    /// The zero polynomial is returned unchanged.
+    pub fn monic(&self) -> Polynomial<T> {
+        if self.is_zero() {
+            return self.clone();
+        }
+        let lead = self.leading_coefficient();
+        Polynomial::from_vec(self.coefficients.iter().map(|c| *c / lead).collect())
+    }
+
+    /// Returns the monic greatest common divisor by the
+    /// Euclidean algorithm. The divisor of two zero polynomials
+    /// is zero.
+    pub fn gcd(&self, other : &Polynomial<T>) -> Polynomial<T> {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let r = a.div_rem(&b).unwrap().1;
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// Returns the antiderivative with the given value at 0
+    pub fn integral(&self, constant : T) -> Polynomial<T> {
+        let mut coefficients = Vec::with_capacity(self.coefficients.len() + 1);
+        coefficients.push(constant);
+        let mut factor = T::zero();
+        for c in self.coefficients.iter() {
+            factor = factor + T::one();
+            coefficients.push(*c / factor);
+        }
+        Polynomial::from_vec(coefficients)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Real polynomials
+ *
+ *******************************************************/
+
+impl Polynomial<f64> {
+
+    /// Returns the values at the entries of a matrix
+    pub fn values(&self, x : &MatrixF64) -> MatrixF64 {
+        Matrix::from_iter_cw(x.num_rows(), x.num_cols(),
+            (0..x.num_cells()).map(|k| self.evaluate(x[k])))
+    }
+
+    /// Removes the coefficients whose magnitude is at most
+    /// tolerance times the largest magnitude
+    pub fn chop(&self, tolerance : f64) -> Polynomial<f64> {
+        let scale = self.coefficients.iter().fold(0., |m : f64, c| m.max(c.abs()));
+        Polynomial::from_vec(self.coefficients.iter()
+            .map(|c| if c.abs() <= tolerance * scale { 0. } else { *c }).collect())
+    }
+
+    /// Returns the monic greatest common divisor by the
+    /// Euclidean algorithm, treating remainders whose
+    /// coefficients are at most tolerance times those of the
+    /// divisor as zero
+    pub fn approximate_gcd(&self, other : &Polynomial<f64>, tolerance : f64) -> Polynomial<f64> {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let scale = b.coefficients.iter().fold(0., |m : f64, c| m.max(c.abs()));
+            let r = a.div_rem(&b).unwrap().1;
+            a = b;
+            b = Polynomial::from_vec(r.coefficients.iter()
+                .map(|c| if c.abs() <= tolerance * scale { 0. } else { *c }).collect());
+        }
+        a.monic()
+    }
+}
+
+
+/******************************************************
+ *
+ *   Arithmetic
+ *
+ *******************************************************/
+
+impl<T:CommutativeRingPartial> Add<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn add(self, rhs : &Polynomial<T>) -> Polynomial<T> {
+        let n = self.coefficients.len().max(rhs.coefficients.len());
+        Polynomial::from_vec((0..n).map(|k| self.coefficient(k) + rhs.coefficient(k)).collect())
+    }
+}
+
+impl<T:CommutativeRingPartial> Add for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn add(self, rhs : Polynomial<T>) -> Polynomial<T> {
+        &self + &rhs
+    }
+}
+
+impl<T:CommutativeRingPartial> Sub<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn sub(self, rhs : &Polynomial<T>) -> Polynomial<T> {
+        let n = self.coefficients.len().max(rhs.coefficients.len());
+        Polynomial::from_vec((0..n).map(|k| self.coefficient(k) - rhs.coefficient(k)).collect())
+    }
+}
+
+impl<T:CommutativeRingPartial> Sub for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn sub(self, rhs : Polynomial<T>) -> Polynomial<T> {
+        &self - &rhs
+    }
+}
+
+impl<T:CommutativeRingPartial> Mul<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn mul(self, rhs : &Polynomial<T>) -> Polynomial<T> {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::zero();
+        }
+        let mut coefficients = vec![T::zero(); self.coefficients.len() + rhs.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in rhs.coefficients.iter().enumerate() {
+                coefficients[i + j] = coefficients[i + j] + *a * *b;
+            }
+        }
+        Polynomial::from_vec(coefficients)
+    }
+}
+
+impl<T:CommutativeRingPartial> Mul for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn mul(self, rhs : Polynomial<T>) -> Polynomial<T> {
+        &self * &rhs
+    }
+}
+
+impl<T:CommutativeRingPartial> Neg for &Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn neg(self) -> Polynomial<T> {
+        Polynomial::from_vec(self.coefficients.iter().map(|c| T::zero() - *c).collect())
+    }
+}
+
+impl<T:CommutativeRingPartial> Neg for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn neg(self) -> Polynomial<T> {
+        -&self
+    }
+}
+
+impl<T:CommutativeRingPartial> Zero for Polynomial<T> {
+    fn zero() -> Polynomial<T> {
+        Polynomial { coefficients : Vec::new() }
+    }
+    fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+}
+
+impl<T:CommutativeRingPartial> One for Polynomial<T> {
+    fn one() -> Polynomial<T> {
+        Polynomial::constant(T::one())
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use num::complex::Complex64;
+
+    #[test]
+    fn test_construction(){
+        let p = Polynomial::new(&[1i64, 2, 0, 0]);
+        assert_eq!(p.coefficients(), &[1, 2]);
+        assert_eq!(p.degree(), 1);
+        assert_eq!(p.coefficient(5), 0);
+        assert!(Polynomial::new(&[0i64, 0]).is_zero());
+        assert_eq!(Polynomial::monomial(3i64, 2).coefficients(), &[0, 0, 3]);
+        // (x - 1)(x - 2)(x + 3) = x^3 - 7 x + 6
+        let q = Polynomial::from_roots(&[1i64, 2, -3]);
+        assert_eq!(q.coefficients(), &[6, -7, 0, 1]);
+        assert_eq!(q.evaluate(2), 0);
+        assert_eq!(q.evaluate(-1), 12);
+        assert_eq!(q.leading_coefficient(), 1);
+    }
+
+    #[test]
+    fn test_arithmetic(){
+        let p = Polynomial::new(&[1i64, 1]);
+        let q = Polynomial::new(&[-1i64, 0, 2]);
+        assert_eq!((&p + &q).coefficients(), &[0, 1, 2]);
+        assert_eq!((&p - &p), Polynomial::zero());
+        assert_eq!((-&q).coefficients(), &[1, 0, -2]);
+        assert_eq!((p.clone() * q.clone()).coefficients(), &[-1, -1, 2, 2]);
+        assert_eq!(p.pow(3).coefficients(), &[1, 3, 3, 1]);
+        assert_eq!(p.pow(0), Polynomial::one());
+        assert_eq!(q.scale(0), Polynomial::zero());
+        // q(p(x)) = 2 (x + 1)^2 - 1
+        assert_eq!(q.compose(&p).coefficients(), &[1, 4, 2]);
+        assert_eq!(p.pow(4).derivative().coefficients(), &[4, 12, 12, 4]);
+    }
+
+    #[test]
+    fn test_division(){
+        let a = Polynomial::new(&[-4., 0., -2., 1.]);
+        let b = Polynomial::new(&[-3., 1.]);
+        let (q, r) = a.div_rem(&b).unwrap();
+        assert_eq!(q.coefficients(), &[3., 1., 1.]);
+        assert_eq!(r.coefficients(), &[5.]);
+        let (q, r) = b.div_rem(&a).unwrap();
+        assert!(q.is_zero());
+        assert_eq!(r, b);
+        assert!(a.div_rem(&Polynomial::zero()).is_err());
+    }
+
+    #[test]
+    fn test_gcd(){
+        let a = Polynomial::from_roots(&[1., 2., 4.]).scale(3.);
+        let b = Polynomial::from_roots(&[2., 4., -5.]);
+        assert_eq!(a.gcd(&b), Polynomial::from_roots(&[2., 4.]));
+        assert_eq!(a.gcd(&Polynomial::constant(2.)), Polynomial::one());
+        assert_eq!(a.gcd(&Polynomial::zero()), a.monic());
+        // Non-representable roots leave rounding residues
+        let c = Polynomial::from_roots(&[0.1, 0.3, 0.7]);
+        let d = Polynomial::from_roots(&[0.3, 0.7, 1.9]);
+        let g = c.approximate_gcd(&d, 1e-10);
+        assert_eq!(g.degree(), 2);
+        assert!(g.evaluate(0.3).abs() < 1e-12 && g.evaluate(0.7).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_integral(){
+        let p = Polynomial::new(&[1., 2., 3.]);
+        let q = p.integral(5.);
+        assert_eq!(q.coefficients(), &[5., 1., 1., 1.]);
+        assert_eq!(q.derivative(), p);
+        assert_eq!(Polynomial::new(&[0., 0., 0.5, 0.1]).chop(0.5).coefficients(), &[0., 0., 0.5]);
+    }
+
+    #[test]
+    fn test_evaluation(){
+        let p = Polynomial::new(&[1., 0., 1.]);
+        let z = p.evaluate_in(Complex64::new(0., 1.));
+        assert_eq!(z, Complex64::new(0., 0.));
+        let x = vector_f64(&[0., 1., 2.]);
+        let v = p.values(&x);
+        assert_eq!((v[0], v[1], v[2]), (1., 2., 5.));
+    }
+}
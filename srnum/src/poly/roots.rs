@@ -0,0 +1,148 @@
+#![doc="Roots of polynomials
+
+The roots of a polynomial of degree n are the eigenvalues of its
+n x n companion matrix
+
+[0 0 ... 0 -a_0    ]
+[1 0 ... 0 -a_1    ]
+[0 1 ... 0 -a_2    ]
+[    ...           ]
+[0 0 ... 1 -a_n-1  ]
+
+with a_k = c_k / c_n. They are computed from the complex Schur
+form in O(n^3) operations. Roots at zero are split off exactly
+beforehand. Simple roots are accurate to about machine precision
+relative to the coefficients; a root of multiplicity m is only
+accurate to about the m-th root of machine precision.
+"]
+
+
+// std imports
+use std::cmp::Ordering;
+
+// external imports
+use num::complex::Complex64;
+use num::traits::Zero;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// srlinalg imports
+use srlinalg::schur::schur_c64;
+
+// local imports
+use poly::polynomial::Polynomial;
+
+
+impl Polynomial<f64> {
+
+    /// Returns the complex roots as a column vector, sorted by
+    /// their real parts and then by their imaginary parts
+    pub fn roots(&self) -> SRResult<MatrixC64> {
+        let c : Vec<Complex64> = self.coefficients().iter().map(|v| Complex64::new(*v, 0.)).collect();
+        companion_roots(&c)
+    }
+}
+
+
+impl Polynomial<Complex64> {
+
+    /// Returns the roots as a column vector, sorted by their
+    /// real parts and then by their imaginary parts
+    pub fn roots(&self) -> SRResult<MatrixC64> {
+        companion_roots(self.coefficients())
+    }
+}
+
+
+/// Computes the roots from the ascending coefficients with a
+/// non-zero last entry
+fn companion_roots(c : &[Complex64]) -> SRResult<MatrixC64> {
+    if c.is_empty() {
+        // Every number is a root of the zero polynomial
+        return Err(SRError::InvalidArgument);
+    }
+    if c.iter().any(|v| !v.re.is_finite() || !v.im.is_finite()) {
+        return Err(SRError::InvalidArgument);
+    }
+    let zeros = c.iter().take_while(|v| v.is_zero()).count();
+    let c = &c[zeros..];
+    let n = c.len() - 1;
+    let mut roots = vec![Complex64::zero(); zeros];
+    if n > 0 {
+        let lead = c[n];
+        let companion = Matrix::from_iter_cw(n, n, (0..n * n).map(|k| {
+            let (r, col) = (k % n, k / n);
+            if col == n - 1 {
+                -c[r] / lead
+            }
+            else if r == col + 1 {
+                Complex64::new(1., 0.)
+            }
+            else {
+                Complex64::zero()
+            }
+        }));
+        let (_, t) = schur_c64(&companion)?;
+        roots.extend((0..n).map(|k| t.get(k, k).unwrap()));
+    }
+    roots.sort_by(|a, b| a.re.partial_cmp(&b.re).unwrap_or(Ordering::Equal)
+        .then(a.im.partial_cmp(&b.im).unwrap_or(Ordering::Equal)));
+    Ok(Matrix::from_iter_cw(roots.len(), 1, roots.into_iter()))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn close(a : Complex64, re : f64, im : f64, tolerance : f64) -> bool {
+        (a - Complex64::new(re, im)).norm() < tolerance
+    }
+
+    fn contains(r : &MatrixC64, re : f64, im : f64) -> bool {
+        (0..r.num_cells()).any(|k| close(r[k], re, im, 1e-12))
+    }
+
+    #[test]
+    fn test_real_roots(){
+        let p = Polynomial::from_roots(&[3., -1., 0.5, 2.]).scale(-2.);
+        let r = p.roots().unwrap();
+        assert_eq!(r.num_rows(), 4);
+        for (k, v) in [-1., 0.5, 2., 3.].iter().enumerate() {
+            assert!(close(r[k], *v, 0., 1e-12), "{:?}", r[k]);
+        }
+    }
+
+    #[test]
+    fn test_complex_roots(){
+        // x^2 (x^2 + 2 x + 5) has 0, 0 and -1 +- 2i
+        let p = Polynomial::new(&[0., 0., 5., 2., 1.]);
+        let r = p.roots().unwrap();
+        assert_eq!(r.num_rows(), 4);
+        assert!(contains(&r, -1., -2.) && contains(&r, -1., 2.));
+        assert_eq!(r[2], Complex64::zero());
+        assert_eq!(r[3], Complex64::zero());
+        // Complex coefficients: (x - i)(x - 2)
+        let i = Complex64::new(0., 1.);
+        let two = Complex64::new(2., 0.);
+        let q = Polynomial::from_roots(&[i, two]);
+        let r = q.roots().unwrap();
+        assert!(close(r[0], 0., 1., 1e-12));
+        assert!(close(r[1], 2., 0., 1e-12));
+    }
+
+    #[test]
+    fn test_degenerate(){
+        assert!(Polynomial::<f64>::zero().roots().is_err());
+        assert_eq!(Polynomial::constant(3.).roots().unwrap().num_cells(), 0);
+        let r = Polynomial::new(&[1., 2.]).roots().unwrap();
+        assert!(close(r[0], -0.5, 0., 1e-15));
+    }
+}
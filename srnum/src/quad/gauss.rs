@@ -0,0 +1,298 @@
+#![doc="Gaussian quadrature rules
+
+An n point Gauss rule integrates w(x) p(x) exactly for every
+polynomial p of degree at most 2 n - 1. Its nodes are the
+roots of the n-th orthogonal polynomial for the weight w.
+
+* Gauss-Legendre: w(x) = 1 on [-1, 1]
+* Gauss-Laguerre: w(x) = x^alpha exp(-x) on [0, inf)
+* Gauss-Hermite: w(x) = exp(-x^2) on (-inf, inf)
+
+The nodes are found by Newton's method on the three term
+recurrence of the polynomials starting from asymptotic
+approximations of the roots (Press et al., Numerical Recipes,
+section 4.6); the weights follow from the derivative of the
+polynomial at the nodes.
+"]
+
+
+// std imports
+use std::f64::consts::PI;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use special::gamma::ln_gamma;
+
+
+/// Maximum number of Newton steps per node
+const MAX_NEWTON : usize = 100;
+
+
+/// Nodes and weights of a quadrature rule sum_i w_i f(x_i)
+#[derive(Debug, Clone)]
+pub struct GaussRule {
+    /// The nodes in increasing order
+    pub nodes : Vec<f64>,
+    /// The weights
+    pub weights : Vec<f64>
+}
+
+impl GaussRule {
+
+    /// The n point Gauss-Legendre rule on [-1, 1]
+    pub fn legendre(n : usize) -> SRResult<GaussRule> {
+        if n == 0 {
+            return Err(SRError::InvalidArgument);
+        }
+        let mut nodes = vec![0.; n];
+        let mut weights = vec![0.; n];
+        let nf = n as f64;
+        for i in 0..(n / 2 + n % 2) {
+            let mut z = (PI * (i as f64 + 0.75) / (nf + 0.5)).cos();
+            let mut pp = 0.;
+            for _ in 0..MAX_NEWTON {
+                // P_n(z) by the recurrence (j + 1) P_{j+1} = (2 j + 1) z P_j - j P_{j-1}
+                let (mut p1, mut p2) = (1., 0.);
+                for j in 0..n {
+                    let p3 = p2;
+                    p2 = p1;
+                    p1 = ((2. * j as f64 + 1.) * z * p2 - j as f64 * p3) / (j as f64 + 1.);
+                }
+                pp = nf * (z * p1 - p2) / (z * z - 1.);
+                let z_old = z;
+                z = z_old - p1 / pp;
+                if (z - z_old).abs() <= 1e-15 {
+                    break;
+                }
+            }
+            nodes[i] = -z;
+            nodes[n - 1 - i] = z;
+            weights[i] = 2. / ((1. - z * z) * pp * pp);
+            weights[n - 1 - i] = weights[i];
+        }
+        Ok(GaussRule {
+            nodes : nodes,
+            weights : weights
+        })
+    }
+
+    /// The n point generalized Gauss-Laguerre rule for the weight
+    /// x^alpha exp(-x) on [0, inf) with alpha > -1
+    pub fn laguerre(n : usize, alpha : f64) -> SRResult<GaussRule> {
+        if n == 0 || alpha.is_nan() || alpha <= -1. {
+            return Err(SRError::InvalidArgument);
+        }
+        let mut nodes = vec![0.; n];
+        let mut weights = vec![0.; n];
+        let nf = n as f64;
+        let mut z = 0.;
+        for i in 0..n {
+            if i == 0 {
+                z = (1. + alpha) * (3. + 0.92 * alpha) / (1. + 2.4 * nf + 1.8 * alpha);
+            }
+            else if i == 1 {
+                z += (15. + 6.25 * alpha) / (1. + 0.9 * alpha + 2.5 * nf);
+            }
+            else {
+                let ai = (i - 1) as f64;
+                z += ((1. + 2.55 * ai) / (1.9 * ai) + 1.26 * ai * alpha / (1. + 3.5 * ai))
+                    * (z - nodes[i - 2]) / (1. + 0.3 * alpha);
+            }
+            let (mut pp, mut p2) = (0., 0.);
+            for _ in 0..MAX_NEWTON {
+                // (j + 1) L_{j+1} = (2 j + 1 + alpha - z) L_j - (j + alpha) L_{j-1}
+                let mut p1 = 1.;
+                p2 = 0.;
+                for j in 0..n {
+                    let p3 = p2;
+                    p2 = p1;
+                    let jf = j as f64;
+                    p1 = ((2. * jf + 1. + alpha - z) * p2 - (jf + alpha) * p3) / (jf + 1.);
+                }
+                pp = (nf * p1 - (nf + alpha) * p2) / z;
+                let z_old = z;
+                z = z_old - p1 / pp;
+                if (z - z_old).abs() <= 1e-15 * z.abs().max(1.) {
+                    break;
+                }
+            }
+            nodes[i] = z;
+            weights[i] = -(ln_gamma(alpha + nf) - ln_gamma(nf)).exp() / (pp * nf * p2);
+        }
+        Ok(GaussRule {
+            nodes : nodes,
+            weights : weights
+        })
+    }
+
+    /// The n point Gauss-Hermite rule for the weight exp(-x^2)
+    pub fn hermite(n : usize) -> SRResult<GaussRule> {
+        if n == 0 {
+            return Err(SRError::InvalidArgument);
+        }
+        // pi^(-1/4)
+        let pim4 = PI.powf(-0.25);
+        let nf = n as f64;
+        // The non-negative nodes in decreasing order
+        let m = n / 2 + n % 2;
+        let mut roots : Vec<f64> = Vec::with_capacity(m);
+        let mut weights = vec![0.; n];
+        let mut z = 0.;
+        for i in 0..m {
+            z = match i {
+                0 => (2. * nf + 1.).sqrt() - 1.85575 * (2. * nf + 1.).powf(-1. / 6.),
+                1 => z - 1.14 * nf.powf(0.426) / z,
+                2 => 1.86 * z - 0.86 * roots[0],
+                3 => 1.91 * z - 0.91 * roots[1],
+                _ => 2. * z - roots[i - 2]
+            };
+            let mut pp = 0.;
+            for _ in 0..MAX_NEWTON {
+                // Recurrence of the orthonormal Hermite functions
+                let (mut p1, mut p2) = (pim4, 0.);
+                for j in 1..(n + 1) {
+                    let p3 = p2;
+                    p2 = p1;
+                    let jf = j as f64;
+                    p1 = z * (2. / jf).sqrt() * p2 - ((jf - 1.) / jf).sqrt() * p3;
+                }
+                pp = (2. * nf).sqrt() * p2;
+                let z_old = z;
+                z = z_old - p1 / pp;
+                if (z - z_old).abs() <= 1e-15 * z.abs().max(1.) {
+                    break;
+                }
+            }
+            if n % 2 == 1 && i == m - 1 {
+                z = 0.;
+            }
+            roots.push(z);
+            weights[i] = 2. / (pp * pp);
+            weights[n - 1 - i] = weights[i];
+        }
+        let mut nodes = vec![0.; n];
+        for (i, &z) in roots.iter().enumerate() {
+            nodes[i] = -z;
+            nodes[n - 1 - i] = z;
+        }
+        Ok(GaussRule {
+            nodes : nodes,
+            weights : weights
+        })
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if the rule has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Applies the rule: sum_i w_i f(x_i)
+    pub fn integrate<F>(&self, f : F) -> f64 where F : Fn(f64) -> f64 {
+        self.nodes.iter().zip(self.weights.iter()).fold(0., |s, (&x, &w)| s + w * f(x))
+    }
+
+    /// Applies a rule on [-1, 1] to the interval [a, b]
+    pub fn integrate_interval<F>(&self, f : F, a : f64, b : f64) -> f64 where F : Fn(f64) -> f64 {
+        let c = 0.5 * (a + b);
+        let h = 0.5 * (b - a);
+        h * self.integrate(|t| f(c + h * t))
+    }
+
+    /// The nodes as a column vector
+    pub fn nodes_vector(&self) -> MatrixF64 {
+        vector_f64(&self.nodes)
+    }
+
+    /// The weights as a column vector
+    pub fn weights_vector(&self) -> MatrixF64 {
+        vector_f64(&self.weights)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_ln_gamma(){
+        assert!(ln_gamma(1f64).abs() < 1e-15);
+        assert!((ln_gamma(5f64) - 24f64.ln()).abs() < 1e-14);
+        assert!((ln_gamma(0.5f64) - PI.sqrt().ln()).abs() < 1e-14);
+        assert!((ln_gamma(0.1f64) - 2.252_712_651_734_206).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_legendre(){
+        let r = GaussRule::legendre(3).unwrap();
+        let s = (0.6f64).sqrt();
+        assert!((r.nodes[0] + s).abs() < 1e-15 && r.nodes[1].abs() < 1e-15);
+        assert!((r.weights[0] - 5. / 9.).abs() < 1e-14);
+        assert!((r.weights[1] - 8. / 9.).abs() < 1e-15);
+        for &n in [1, 2, 5, 10, 20, 64].iter() {
+            let r = GaussRule::legendre(n).unwrap();
+            assert_eq!(r.len(), n);
+            assert!(r.nodes.windows(2).all(|w| w[0] < w[1]));
+            assert!((r.weights.iter().fold(0., |s, w| s + w) - 2.).abs() < 1e-13);
+            // Exact for x^(2n - 2)
+            let k = 2 * n as i32 - 2;
+            let v = r.integrate(|x| x.powi(k));
+            assert!((v - 2. / (k as f64 + 1.)).abs() < 1e-13, "{} {}", n, v);
+        }
+        let r = GaussRule::legendre(12).unwrap();
+        assert!((r.integrate_interval(f64::exp, 0., 2.) - (2f64.exp() - 1.)).abs() < 1e-13);
+        assert!(GaussRule::legendre(0).is_err());
+    }
+
+    #[test]
+    fn test_laguerre(){
+        // int_0^inf x^k exp(-x) dx = k!
+        for &n in [1, 4, 10, 30].iter() {
+            let r = GaussRule::laguerre(n, 0.).unwrap();
+            assert!(r.nodes.windows(2).all(|w| w[0] < w[1]));
+            assert!(r.nodes[0] > 0.);
+            let mut factorial = 1.;
+            for k in 0..(2 * n) {
+                if k > 0 {
+                    factorial *= k as f64;
+                }
+                let v = r.integrate(|x| x.powi(k as i32));
+                assert!((v - factorial).abs() < 1e-12 * factorial, "{} {} {}", n, k, v);
+            }
+        }
+        // Generalized rule: int_0^inf x^(1/2) x^2 exp(-x) dx = Gamma(3.5) = 15 sqrt(pi) / 8
+        let r = GaussRule::laguerre(5, 0.5).unwrap();
+        assert!((r.integrate(|x| x * x) - 15. * PI.sqrt() / 8.).abs() < 1e-13);
+        assert!(GaussRule::laguerre(3, -1.).is_err());
+    }
+
+    #[test]
+    fn test_hermite(){
+        for &n in [1, 2, 5, 6, 20, 41].iter() {
+            let r = GaussRule::hermite(n).unwrap();
+            assert_eq!(r.len(), n);
+            assert!(r.nodes.windows(2).all(|w| w[0] < w[1]));
+            assert!((r.integrate(|_| 1.) - PI.sqrt()).abs() < 1e-13);
+            if n > 1 {
+                assert!((r.integrate(|x| x * x) - PI.sqrt() / 2.).abs() < 1e-13);
+            }
+        }
+        // int exp(-x^2) cos x dx = sqrt(pi) exp(-1/4)
+        let r = GaussRule::hermite(20).unwrap();
+        assert!((r.integrate(f64::cos) - PI.sqrt() * (-0.25f64).exp()).abs() < 1e-14);
+        assert_eq!(r.nodes_vector().num_rows(), 20);
+    }
+}
@@ -0,0 +1,261 @@
+#![doc="Adaptive Gauss-Kronrod quadrature
+
+The 15 point Kronrod rule extends the 7 point Gauss-Legendre
+rule by 8 nodes; both are evaluated with 15 function values.
+Following QUADPACK (Piessens et al., 1983) the error of the
+Kronrod result K on an interval is estimated from |K - G| as
+
+e = I * min(1, (200 |K - G| / I)^1.5)
+
+where I is the integral of |f - K / (b - a)|, bounded below by
+50 epsilon times the integral of |f|. The adaptive routine
+keeps bisecting the interval with the largest error estimate
+until the sum of the estimates meets the tolerances (as QAG).
+
+Infinite ranges are mapped to finite ones (as QAGI):
+
+* [a, inf): x = a + (1 - t) / t for t in (0, 1]
+* (-inf, b]: x = b - (1 - t) / t for t in (0, 1]
+* (-inf, inf): x = t / (1 - t^2) for t in (-1, 1)
+
+The Kronrod nodes are interior, so f is never evaluated at
+the ends of the interval.
+"]
+
+
+// std imports
+use std::f64;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use quad::options::*;
+
+
+/// Kronrod nodes (the odd ones are the Gauss nodes)
+const XGK : [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_5,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_48,
+    0.];
+
+/// Kronrod weights
+const WGK : [f64; 8] = [
+    0.022_935_322_010_529_224,
+    0.063_092_092_629_978_56,
+    0.104_790_010_322_250_19,
+    0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_42,
+    0.204_432_940_075_298_89,
+    0.209_482_141_084_727_82];
+
+/// Gauss weights of the nodes XGK[1], XGK[3], XGK[5], XGK[7]
+const WG : [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_64,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4];
+
+
+/// An interval with its Kronrod estimate and error
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    a : f64,
+    b : f64,
+    value : f64,
+    error : f64
+}
+
+
+/// Applies the 15 point Kronrod rule on [a, b] and returns the
+/// integral with its error estimate
+pub fn kronrod15<F>(f : &F, a : f64, b : f64) -> (f64, f64) where F : Fn(f64) -> f64 {
+    let center = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+    let fc = f(center);
+    let mut kronrod = fc * WGK[7];
+    let mut gauss = fc * WG[3];
+    let mut absolute = kronrod.abs();
+    let mut values = [(0., 0.); 7];
+    for j in 0..7 {
+        let dx = half * XGK[j];
+        let (f1, f2) = (f(center - dx), f(center + dx));
+        values[j] = (f1, f2);
+        kronrod += WGK[j] * (f1 + f2);
+        absolute += WGK[j] * (f1.abs() + f2.abs());
+        if j % 2 == 1 {
+            gauss += WG[j / 2] * (f1 + f2);
+        }
+    }
+    let mean = 0.5 * kronrod;
+    let asc = values.iter().enumerate().fold(WGK[7] * (fc - mean).abs(), |s, (j, v)| {
+        s + WGK[j] * ((v.0 - mean).abs() + (v.1 - mean).abs())
+    });
+    let value = kronrod * half;
+    let absolute = absolute * half.abs();
+    let asc = asc * half.abs();
+    let mut error = ((kronrod - gauss) * half).abs();
+    if asc != 0. && error != 0. {
+        error = asc * (200. * error / asc).powf(1.5).min(1.);
+    }
+    if absolute > f64::MIN_POSITIVE / (50. * f64::EPSILON) {
+        error = error.max(50. * f64::EPSILON * absolute);
+    }
+    (value, error)
+}
+
+
+/// Adaptive bisection on a finite interval
+fn adapt<F>(f : &F, a : f64, b : f64, options : &QuadOptions) -> QuadResult where F : Fn(f64) -> f64 {
+    let (value, error) = kronrod15(f, a, b);
+    let mut segments = vec![Segment { a : a, b : b, value : value, error : error }];
+    let mut value = value;
+    let mut error = error;
+    let mut evaluations = 15;
+    let mut iterations = 0;
+    while error > options.tolerance(value) && iterations < options.max_subdivisions {
+        // Bisect the segment with the largest error
+        let k = (0..segments.len()).fold(0, |k, i| if segments[i].error > segments[k].error { i } else { k });
+        let s = segments.swap_remove(k);
+        let m = 0.5 * (s.a + s.b);
+        if m <= s.a || m >= s.b {
+            // The interval cannot be split any further
+            segments.push(s);
+            break;
+        }
+        let (v1, e1) = kronrod15(f, s.a, m);
+        let (v2, e2) = kronrod15(f, m, s.b);
+        evaluations += 30;
+        iterations += 1;
+        segments.push(Segment { a : s.a, b : m, value : v1, error : e1 });
+        segments.push(Segment { a : m, b : s.b, value : v2, error : e2 });
+        // Sum afresh to avoid the accumulation of round off
+        value = segments.iter().fold(0., |acc, s| acc + s.value);
+        error = segments.iter().fold(0., |acc, s| acc + s.error);
+    }
+    QuadResult {
+        value : value,
+        error : error,
+        evaluations : evaluations,
+        iterations : iterations,
+        converged : error <= options.tolerance(value)
+    }
+}
+
+
+#[doc="Integrates f over [a, b] by adaptive Gauss-Kronrod quadrature.
+
+Either limit may be infinite. For b < a the integral is the
+negative of the integral over [b, a]. The result is returned
+even if the tolerances could not be met within the allowed
+number of subdivisions; ``converged`` tells whether they were.
+"]
+pub fn gauss_kronrod<F>(f : F, a : f64, b : f64, options : &QuadOptions) -> SRResult<QuadResult>
+    where F : Fn(f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        return Err(SRError::InvalidArgument);
+    }
+    if a == b {
+        return Ok(QuadResult { value : 0., error : 0., evaluations : 0, iterations : 0, converged : true });
+    }
+    if b < a {
+        let mut r = gauss_kronrod(f, b, a, options)?;
+        r.value = -r.value;
+        return Ok(r);
+    }
+    let r = match (a.is_finite(), b.is_finite()) {
+        (true, true) => adapt(&f, a, b, options),
+        (true, false) => adapt(&|t : f64| {
+            let x = a + (1. - t) / t;
+            f(x) / (t * t)
+        }, 0., 1., options),
+        (false, true) => adapt(&|t : f64| {
+            let x = b - (1. - t) / t;
+            f(x) / (t * t)
+        }, 0., 1., options),
+        (false, false) => adapt(&|t : f64| {
+            let d = 1. - t * t;
+            f(t / d) * (1. + t * t) / (d * d)
+        }, -1., 1., options)
+    };
+    Ok(r)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_kronrod_rule(){
+        let s = WGK.iter().fold(0., |s, w| s + w) * 2. - WGK[7];
+        assert!((s - 2.).abs() < 1e-15);
+        let s = WG.iter().fold(0., |s, w| s + w) * 2. - WG[3];
+        assert!((s - 2.).abs() < 1e-15);
+        // Exact for polynomials of degree 22 (the Gauss part up to 13)
+        let (v, _) = kronrod15(&|x : f64| x.powi(22) + x.powi(3), -1., 1.);
+        assert!((v - 2. / 23.).abs() < 1e-15);
+        let (v, e) = kronrod15(&|x : f64| x.powi(12), 0., 1.);
+        assert!((v - 1. / 13.).abs() < 1e-15);
+        assert!(e < 1e-13);
+    }
+
+    #[test]
+    fn test_gauss_kronrod(){
+        let options = QuadOptions::new();
+        let r = gauss_kronrod(|x| x.sin(), 0., PI, &options).unwrap();
+        assert!(r.converged);
+        assert!((r.value - 2.).abs() < 1e-14);
+        assert_eq!(r.evaluations, 15);
+        // A sharp peak needs subdivisions
+        let r = gauss_kronrod(|x| 1. / (1e-4 + x * x), -1., 1., &options).unwrap();
+        let exact = 2. * 100. * (100f64).atan();
+        assert!(r.converged);
+        assert!(r.iterations > 5);
+        assert!((r.value - exact).abs() < 1e-10 * exact);
+        assert!(r.error < 1e-10 * exact);
+        // Integrable endpoint singularity
+        let r = gauss_kronrod(|x : f64| x.ln() / x.sqrt(), 0., 1., &options).unwrap();
+        assert!(r.converged);
+        assert!((r.value + 4.).abs() < 1e-9);
+        // Reversed limits
+        let r = gauss_kronrod(f64::exp, 1., 0., &options).unwrap();
+        assert!((r.value - (1. - 1f64.exp())).abs() < 1e-14);
+        assert!(gauss_kronrod(f64::exp, f64::NAN, 0., &options).is_err());
+    }
+
+    #[test]
+    fn test_infinite_ranges(){
+        let options = QuadOptions::new();
+        let r = gauss_kronrod(|x : f64| (-x * x).exp(), f64::NEG_INFINITY, f64::INFINITY, &options).unwrap();
+        assert!(r.converged);
+        assert!((r.value - PI.sqrt()).abs() < 1e-10);
+        let r = gauss_kronrod(|x : f64| 1. / (1. + x * x), 1., f64::INFINITY, &options).unwrap();
+        assert!((r.value - PI / 4.).abs() < 1e-10);
+        let r = gauss_kronrod(f64::exp, f64::NEG_INFINITY, 0., &options).unwrap();
+        assert!((r.value - 1.).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_not_converged(){
+        // An oscillating integrand with too few subdivisions allowed
+        let options = QuadOptions::new().max_subdivisions(3);
+        let r = gauss_kronrod(|x : f64| (100. * x).sin() * x, 0., 20., &options).unwrap();
+        assert!(!r.converged);
+        assert_eq!(r.iterations, 3);
+        assert_eq!(r.evaluations, 15 + 3 * 30);
+    }
+}
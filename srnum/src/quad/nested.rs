@@ -0,0 +1,106 @@
+#![doc="Nested integration over two dimensional regions
+
+The integral of f(x, y) over the region a <= x <= b,
+g(x) <= y <= h(x) is computed as the iterated integral
+
+int_a^b F(x) dx,  F(x) = int_g(x)^h(x) f(x, y) dy
+
+with adaptive Gauss-Kronrod quadrature for both the outer and
+the inner integrals. Rectangles have constant g and h.
+"]
+
+
+// std imports
+use std::cell::Cell;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use quad::options::*;
+use quad::kronrod::*;
+
+
+#[doc="Integrates f(x, y) over a <= x <= b, lower_y(x) <= y <= upper_y(x).
+
+The inner integrals are computed with the same options as the
+outer one. The error estimate adds |b - a| times the largest
+error of an inner integral to the error of the outer one. The
+result is converged only if the outer and all inner integrals
+are. The evaluations count the calls of f.
+"]
+pub fn integrate_2d<F, G, H>(f : F, a : f64, b : f64, lower_y : G, upper_y : H,
+    options : &QuadOptions) -> SRResult<QuadResult>
+    where F : Fn(f64, f64) -> f64, G : Fn(f64) -> f64, H : Fn(f64) -> f64 {
+    let evaluations = Cell::new(0);
+    let inner_error = Cell::new(0f64);
+    let inner_converged = Cell::new(true);
+    let invalid = Cell::new(false);
+    let outer = gauss_kronrod(|x| {
+        match gauss_kronrod(|y| f(x, y), lower_y(x), upper_y(x), options) {
+            Ok(r) => {
+                evaluations.set(evaluations.get() + r.evaluations);
+                inner_error.set(inner_error.get().max(r.error));
+                inner_converged.set(inner_converged.get() && r.converged);
+                r.value
+            },
+            Err(_) => {
+                invalid.set(true);
+                0.
+            }
+        }
+    }, a, b, options)?;
+    if invalid.get() {
+        return Err(SRError::InvalidArgument);
+    }
+    let error = outer.error + (b - a).abs() * inner_error.get();
+    Ok(QuadResult {
+        value : outer.value,
+        error : error,
+        evaluations : evaluations.get(),
+        iterations : outer.iterations,
+        converged : outer.converged && inner_converged.get()
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_rectangle(){
+        let options = QuadOptions::new();
+        // int_0^1 int_0^2 x y^2 dy dx = 1/2 * 8/3
+        let r = integrate_2d(|x, y| x * y * y, 0., 1., |_| 0., |_| 2., &options).unwrap();
+        assert!(r.converged);
+        assert!((r.value - 4. / 3.).abs() < 1e-13);
+        assert_eq!(r.evaluations, 15 * 15);
+        let r = integrate_2d(|x : f64, y : f64| (x + y).exp(), 0., 1., |_| 0., |_| 1., &options).unwrap();
+        assert!((r.value - (1f64.exp() - 1.).powi(2)).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_regions(){
+        let options = QuadOptions::new();
+        // Area of the unit disk
+        let r = integrate_2d(|_, _| 1., -1., 1.,
+            |x : f64| -(1. - x * x).sqrt(), |x : f64| (1. - x * x).sqrt(), &options).unwrap();
+        assert!((r.value - PI).abs() < 1e-9);
+        // int over the triangle 0 <= y <= x <= 1 of x y = 1/8
+        let r = integrate_2d(|x, y| x * y, 0., 1., |_| 0., |x| x, &options).unwrap();
+        assert!(r.converged);
+        assert!((r.value - 0.125).abs() < 1e-14);
+        // Reversed outer limits change the sign
+        let r = integrate_2d(|x, y| x * y, 1., 0., |_| 0., |x| x, &options).unwrap();
+        assert!((r.value + 0.125).abs() < 1e-14);
+        assert!(integrate_2d(|x, y| x * y, 0., 1., |_| f64::NAN, |x| x, &options).is_err());
+    }
+}
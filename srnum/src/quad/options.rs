@@ -0,0 +1,84 @@
+#![doc="Tolerances and results shared by the quadrature routines
+"]
+
+
+// std imports
+
+
+/// Stopping criteria of the adaptive quadrature routines
+#[derive(Debug, Clone, Copy)]
+pub struct QuadOptions {
+    /// Bound on the absolute error
+    pub absolute_tolerance : f64,
+    /// Bound on the error relative to the integral
+    pub relative_tolerance : f64,
+    /// Maximum number of interval subdivisions (Gauss-Kronrod)
+    pub max_subdivisions : usize,
+    /// Maximum number of step halvings (tanh-sinh)
+    pub max_levels : usize
+}
+
+impl QuadOptions {
+
+    /// Default options: absolute and relative tolerance 1e-10,
+    /// at most 200 subdivisions and 12 levels
+    pub fn new() -> QuadOptions {
+        QuadOptions {
+            absolute_tolerance : 1e-10,
+            relative_tolerance : 1e-10,
+            max_subdivisions : 200,
+            max_levels : 12
+        }
+    }
+
+    /// Sets the absolute tolerance
+    pub fn absolute_tolerance(mut self, tolerance : f64) -> QuadOptions {
+        self.absolute_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the relative tolerance
+    pub fn relative_tolerance(mut self, tolerance : f64) -> QuadOptions {
+        self.relative_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of subdivisions
+    pub fn max_subdivisions(mut self, max_subdivisions : usize) -> QuadOptions {
+        self.max_subdivisions = max_subdivisions;
+        self
+    }
+
+    /// Sets the maximum number of levels
+    pub fn max_levels(mut self, max_levels : usize) -> QuadOptions {
+        self.max_levels = max_levels;
+        self
+    }
+
+    /// The error bound max(absolute, relative |value|)
+    pub fn tolerance(&self, value : f64) -> f64 {
+        self.absolute_tolerance.max(self.relative_tolerance * value.abs())
+    }
+}
+
+impl Default for QuadOptions {
+    fn default() -> QuadOptions {
+        QuadOptions::new()
+    }
+}
+
+
+/// Result of an adaptive quadrature
+#[derive(Debug, Clone, Copy)]
+pub struct QuadResult {
+    /// The estimate of the integral
+    pub value : f64,
+    /// The estimate of the absolute error
+    pub error : f64,
+    /// Number of function evaluations
+    pub evaluations : usize,
+    /// Number of subdivisions or levels used
+    pub iterations : usize,
+    /// Whether the error estimate meets the tolerances
+    pub converged : bool
+}
@@ -0,0 +1,183 @@
+#![doc="Integration of sampled data
+
+The samples y_i = f(x_i) are given as a row or column vector,
+either with a constant spacing dx or with the abscissas x_i in
+a second vector of the same length.
+
+* The trapezoid rule is exact for linear functions.
+* Simpson's rule fits a parabola through each pair of
+  intervals and is exact for cubics on equally spaced points.
+  With an odd number of intervals, the last interval is
+  integrated using the parabola through the last three
+  points.
+"]
+
+
+// std imports
+
+// srmatrix imports
+use srmatrix::api::*;
+
+
+/// Checks that y is a non-empty vector (or a single sample)
+fn check_samples(y : &MatrixF64) -> SRResult<usize> {
+    if y.num_cells() == 0 {
+        return Err(SRError::EmptyMatrix);
+    }
+    if !y.is_row() && !y.is_col() {
+        return Err(SRError::IsNotAVector);
+    }
+    Ok(y.num_cells())
+}
+
+/// Checks that x and y are vectors of equal length with
+/// strictly increasing x
+fn check_abscissas(x : &MatrixF64, y : &MatrixF64) -> SRResult<usize> {
+    let n = check_samples(y)?;
+    if check_samples(x)? != n {
+        return Err(SRError::DimensionsMismatch);
+    }
+    if (0..n).any(|i| x[i].is_nan()) || (1..n).any(|i| x[i] <= x[i - 1]) {
+        return Err(SRError::InvalidArgument);
+    }
+    Ok(n)
+}
+
+
+/// Integrates equally spaced samples by the trapezoid rule
+pub fn trapezoid(y : &MatrixF64, dx : f64) -> SRResult<f64> {
+    let n = check_samples(y)?;
+    let inner = (1..n).fold(0., |s, i| s + y[i - 1] + y[i]);
+    Ok(0.5 * dx * inner)
+}
+
+/// Integrates samples at the abscissas x by the trapezoid rule
+pub fn trapezoid_x(x : &MatrixF64, y : &MatrixF64) -> SRResult<f64> {
+    let n = check_abscissas(x, y)?;
+    Ok((1..n).fold(0., |s, i| s + 0.5 * (x[i] - x[i - 1]) * (y[i - 1] + y[i])))
+}
+
+/// Returns the column vector of the integrals from the first
+/// sample to every sample by the trapezoid rule
+pub fn cumulative_trapezoid(y : &MatrixF64, dx : f64) -> SRResult<MatrixF64> {
+    let n = check_samples(y)?;
+    let mut total = 0.;
+    Ok(Matrix::from_iter_cw(n, 1, (0..n).map(|i| {
+        if i > 0 {
+            total += 0.5 * dx * (y[i - 1] + y[i]);
+        }
+        total
+    })))
+}
+
+/// Integrates equally spaced samples by Simpson's rule.
+/// For an odd number of intervals the last three are integrated
+/// by Simpson's 3/8 rule.
+pub fn simpson(y : &MatrixF64, dx : f64) -> SRResult<f64> {
+    let n = check_samples(y)?;
+    if n == 2 {
+        return trapezoid(y, dx);
+    }
+    // Number of points covered by the 1/3 rule
+    let m = if n % 2 == 1 { n } else { n - 3 };
+    let mut s = 0.;
+    for i in (0..(m - 1)).step_by(2) {
+        s += y[i] + 4. * y[i + 1] + y[i + 2];
+    }
+    s *= dx / 3.;
+    if m < n {
+        let k = n - 4;
+        s += 3. * dx / 8. * (y[k] + 3. * y[k + 1] + 3. * y[k + 2] + y[k + 3]);
+    }
+    Ok(s)
+}
+
+/// Integrates samples at the abscissas x by Simpson's rule for
+/// unequally spaced points
+pub fn simpson_x(x : &MatrixF64, y : &MatrixF64) -> SRResult<f64> {
+    let n = check_abscissas(x, y)?;
+    if n <= 2 {
+        return trapezoid_x(x, y);
+    }
+    let mut s = 0.;
+    let mut i = 0;
+    while i + 2 < n {
+        let h0 = x[i + 1] - x[i];
+        let h1 = x[i + 2] - x[i + 1];
+        let h = h0 + h1;
+        s += h / 6. * ((2. - h1 / h0) * y[i] + h * h / (h0 * h1) * y[i + 1]
+            + (2. - h0 / h1) * y[i + 2]);
+        i += 2;
+    }
+    if i + 1 < n {
+        // The last interval from the parabola through the last three points
+        let h0 = x[n - 2] - x[n - 3];
+        let h1 = x[n - 1] - x[n - 2];
+        let alpha = (2. * h1 * h1 + 3. * h0 * h1) / (6. * (h0 + h1));
+        let beta = (h1 * h1 + 3. * h0 * h1) / (6. * h0);
+        let eta = h1 * h1 * h1 / (6. * h0 * (h0 + h1));
+        s += alpha * y[n - 1] + beta * y[n - 2] - eta * y[n - 3];
+    }
+    Ok(s)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn samples<F : Fn(f64) -> f64>(f : F, x : &[f64]) -> (MatrixF64, MatrixF64) {
+        (vector_f64(x), Matrix::from_iter_cw(x.len(), 1, x.iter().map(|&t| f(t))))
+    }
+
+    #[test]
+    fn test_trapezoid(){
+        let y = vector_f64(&[1., 3., 5., 7.]);
+        // f(x) = 1 + 2 x on [0, 3]
+        assert_eq!(trapezoid(&y, 1.).unwrap(), 12.);
+        assert_eq!(trapezoid(&y.transpose(), 0.5).unwrap(), 6.);
+        assert_eq!(trapezoid(&vector_f64(&[2.]), 1.).unwrap(), 0.);
+        let c = cumulative_trapezoid(&y, 1.).unwrap();
+        assert_eq!(c, vector_f64(&[0., 2., 6., 12.]));
+        let (x, y) = samples(|t| 3. * t - 1., &[0., 0.5, 2., 2.25]);
+        assert!((trapezoid_x(&x, &y).unwrap() - (1.5 * 2.25 * 2.25 - 2.25)).abs() < 1e-14);
+        assert!(trapezoid(&Matrix::zeros(2, 2), 1.).is_err());
+        assert!(trapezoid_x(&vector_f64(&[0., 1.]), &vector_f64(&[0., 1., 2.])).is_err());
+        assert!(trapezoid_x(&vector_f64(&[0., 0.]), &vector_f64(&[0., 1.])).is_err());
+    }
+
+    #[test]
+    fn test_simpson(){
+        let cubic = |t : f64| t * t * t - 2. * t + 1.;
+        let exact = |a : f64, b : f64| {
+            let p = |t : f64| t.powi(4) / 4. - t * t + t;
+            p(b) - p(a)
+        };
+        // Odd and even numbers of equally spaced points
+        for n in 3..9 {
+            let h = 2. / (n - 1) as f64;
+            let x : Vec<f64> = (0..n).map(|i| -1. + h * i as f64).collect();
+            let (_, y) = samples(cubic, &x);
+            assert!((simpson(&y, h).unwrap() - exact(-1., 1.)).abs() < 1e-13, "{}", n);
+        }
+        // Unequal spacing is exact for parabolas
+        let quadratic = |t : f64| 2. * t * t - t + 3.;
+        for x in [vec![0., 0.3, 1.], vec![0., 0.3, 1., 1.2], vec![-1., 0., 0.1, 0.5, 2.]].iter() {
+            let (xv, y) = samples(quadratic, x);
+            let (a, b) = (x[0], x[x.len() - 1]);
+            let expected = 2. / 3. * (b * b * b - a * a * a) - (b * b - a * a) / 2. + 3. * (b - a);
+            assert!((simpson_x(&xv, &y).unwrap() - expected).abs() < 1e-13);
+        }
+        // Convergence for a smooth function
+        let n = 101;
+        let h = 3. / (n - 1) as f64;
+        let y = Matrix::from_iter_cw(n, 1, (0..n).map(|i| (h * i as f64).sin()));
+        assert!((simpson(&y, h).unwrap() - (1. - 3f64.cos())).abs() < 1e-7);
+    }
+}
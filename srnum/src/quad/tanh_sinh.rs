@@ -0,0 +1,170 @@
+#![doc="Tanh-sinh (double exponential) quadrature
+
+The substitution x = c + h tanh(pi/2 sinh t) with c = (a + b)/2,
+h = (b - a)/2 maps [a, b] to the real line and makes the
+integrand decay double exponentially in t. The trapezoid rule
+with step size 2^-k in t then converges very fast, even when f
+has integrable singularities at a or b (Takahasi and Mori,
+1974).
+
+The distances of the nodes to the end points are computed as
+(b - a) / (1 + exp(pi sinh t)) without cancellation, so nodes
+approach a singular end point as closely as the floating point
+numbers near it allow. f is never evaluated at a or b. Each level halves the step size and
+reuses the previous nodes; the difference of the estimates of
+two consecutive levels serves as the error estimate.
+"]
+
+
+// std imports
+use std::f64::consts::PI;
+
+// srmatrix imports
+use srmatrix::api::*;
+
+// local imports
+use quad::options::*;
+
+
+/// Upper limit of the abscissa t
+const MAX_T : f64 = 8.;
+
+
+/// Sum of the contributions of the node pairs at the abscissas
+/// k h for k = first, first + stride, ... until the nodes reach
+/// the end points. Returns the sum and the number of evaluations.
+fn level_sum<F>(f : &F, a : f64, b : f64, h : f64, first : usize, stride : usize) -> (f64, usize)
+    where F : Fn(f64) -> f64 {
+    let width = b - a;
+    let mut sum = 0.;
+    let mut evaluations = 0;
+    let mut k = first;
+    loop {
+        let t = k as f64 * h;
+        if t > MAX_T {
+            break;
+        }
+        let s = 0.5 * PI * t.sinh();
+        // Distance of the nodes to the end points
+        let d = width / (1. + (2. * s).exp());
+        let (left, right) = (a + d, b - d);
+        // A node which rounds to an end point is dropped
+        let (inside_left, inside_right) = (left > a, right < b);
+        if !inside_left && !inside_right {
+            break;
+        }
+        let cosh_s = s.cosh();
+        let w = 0.5 * width * 0.5 * PI * t.cosh() / (cosh_s * cosh_s);
+        let mut values = 0.;
+        if inside_left {
+            values += f(left);
+            evaluations += 1;
+        }
+        if inside_right {
+            values += f(right);
+            evaluations += 1;
+        }
+        let term = w * values;
+        if !term.is_finite() {
+            break;
+        }
+        sum += term;
+        k += stride;
+    }
+    (sum, evaluations)
+}
+
+
+#[doc="Integrates f over the finite interval [a, b] by tanh-sinh quadrature.
+
+Suited for integrands which are analytic inside the interval
+but singular or non-smooth at its ends. For b < a the integral
+is the negative of the integral over [b, a].
+"]
+pub fn tanh_sinh<F>(f : F, a : f64, b : f64, options : &QuadOptions) -> SRResult<QuadResult>
+    where F : Fn(f64) -> f64 {
+    if !a.is_finite() || !b.is_finite() {
+        return Err(SRError::InvalidArgument);
+    }
+    if a == b {
+        return Ok(QuadResult { value : 0., error : 0., evaluations : 0, iterations : 0, converged : true });
+    }
+    if b < a {
+        let mut r = tanh_sinh(f, b, a, options)?;
+        r.value = -r.value;
+        return Ok(r);
+    }
+    // Level 0 with step size 1 including the center t = 0
+    let mut h = 1.;
+    let (sum, mut evaluations) = level_sum(&f, a, b, h, 1, 1);
+    let mut sum = sum + 0.5 * (b - a) * 0.5 * PI * f(0.5 * (a + b));
+    evaluations += 1;
+    let mut value = h * sum;
+    let mut error = f64::INFINITY;
+    let mut level = 0;
+    while level < options.max_levels {
+        level += 1;
+        h *= 0.5;
+        // The new nodes are at the odd multiples of h
+        let (s, e) = level_sum(&f, a, b, h, 1, 2);
+        sum += s;
+        evaluations += e;
+        let previous = value;
+        value = h * sum;
+        error = (value - previous).abs();
+        if error <= options.tolerance(value) {
+            break;
+        }
+    }
+    Ok(QuadResult {
+        value : value,
+        error : error,
+        evaluations : evaluations,
+        iterations : level,
+        converged : error <= options.tolerance(value)
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_smooth(){
+        let options = QuadOptions::new();
+        let r = tanh_sinh(f64::exp, 0., 1., &options).unwrap();
+        assert!(r.converged);
+        assert!((r.value - (1f64.exp() - 1.)).abs() < 1e-14);
+        let r = tanh_sinh(|x : f64| 1. / (1. + x * x), 1., -1., &options).unwrap();
+        assert!((r.value + PI / 2.).abs() < 1e-14);
+        assert!(tanh_sinh(f64::exp, 0., f64::INFINITY, &options).is_err());
+    }
+
+    #[test]
+    fn test_endpoint_singularities(){
+        let options = QuadOptions::new();
+        // int_0^1 1 / sqrt(x) dx = 2
+        let r = tanh_sinh(|x : f64| 1. / x.sqrt(), 0., 1., &options).unwrap();
+        assert!(r.converged);
+        assert!((r.value - 2.).abs() < 1e-12);
+        // int_0^1 ln(x) ln(1 - x) dx = 2 - pi^2 / 6
+        let r = tanh_sinh(|x : f64| x.ln() * (1. - x).ln(), 0., 1., &options).unwrap();
+        assert!((r.value - (2. - PI * PI / 6.)).abs() < 1e-13);
+        // int_{-1}^1 1 / sqrt(1 - x^2) dx = pi, singular at both ends
+        // which are not at zero, so the accuracy is limited as below
+        let r = tanh_sinh(|x : f64| 1. / ((1. - x) * (1. + x)).sqrt(), -1., 1., &options).unwrap();
+        assert!(r.converged);
+        assert!((r.value - PI).abs() < 1e-7);
+        // A shifted singularity loses the part closer to 2 than
+        // the spacing of floats: int_2^3 (x - 2)^(-1/2) dx = 2
+        let r = tanh_sinh(|x : f64| 1. / (x - 2.).sqrt(), 2., 3., &options).unwrap();
+        assert!((r.value - 2.).abs() < 1e-6);
+    }
+}
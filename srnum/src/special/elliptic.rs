@@ -0,0 +1,412 @@
+#![doc="Elliptic integrals
+
+The integrals are reduced to Carlson's symmetric forms
+
+R_F(x, y, z) = 1/2 int_0^inf dt / sqrt((t + x) (t + y) (t + z))
+R_D(x, y, z) = 3/2 int_0^inf dt / (sqrt((t + x) (t + y)) (t + z)^(3/2))
+R_J(x, y, z, p) = 3/2 int_0^inf dt / (sqrt((t + x) (t + y) (t + z)) (t + p))
+R_C(x, y) = R_F(x, y, y)
+
+which are computed by the duplication theorem followed by a
+Taylor expansion (Carlson, Numerical computation of real or
+complex elliptic integrals, Numer. Algorithms 10, 1995). For
+p < 0 and y < 0, R_J and R_C return the Cauchy principal value.
+
+The Legendre forms use the parameter m = k^2:
+
+F(phi | m) = int_0^phi dt / sqrt(1 - m sin^2 t)
+E(phi | m) = int_0^phi sqrt(1 - m sin^2 t) dt
+Pi(n; phi | m) = int_0^phi dt / ((1 - n sin^2 t) sqrt(1 - m sin^2 t))
+
+and K(m) = F(pi/2 | m), E(m) = E(pi/2 | m), Pi(n | m) =
+Pi(n; pi/2 | m). Amplitudes beyond pi/2 use the quasi periodicity
+F(phi + k pi | m) = F(phi | m) + 2 k K(m), and likewise for E and
+Pi.
+"]
+
+
+// std imports
+use std::f64::consts::PI;
+
+// external imports
+use num::traits::Float;
+
+// local imports
+use special::support::{to_f64, from_f64};
+
+
+/// Returns R_F(x, y, z) in double precision
+fn rf(x : f64, y : f64, z : f64) -> f64 {
+    const ERRTOL : f64 = 0.0025;
+    if x.is_nan() || y.is_nan() || z.is_nan() || x.min(y).min(z) < 0.
+        || x + y == 0. || x + z == 0. || y + z == 0. {
+        return f64::NAN;
+    }
+    let (mut xt, mut yt, mut zt) = (x, y, z);
+    loop {
+        let (sx, sy, sz) = (xt.sqrt(), yt.sqrt(), zt.sqrt());
+        let lambda = sx * (sy + sz) + sy * sz;
+        xt = 0.25 * (xt + lambda);
+        yt = 0.25 * (yt + lambda);
+        zt = 0.25 * (zt + lambda);
+        let ave = (xt + yt + zt) / 3.;
+        let dx = (ave - xt) / ave;
+        let dy = (ave - yt) / ave;
+        let dz = (ave - zt) / ave;
+        if dx.abs().max(dy.abs()).max(dz.abs()) <= ERRTOL || !ave.is_finite() {
+            let e2 = dx * dy - dz * dz;
+            let e3 = dx * dy * dz;
+            return (1. + (e2 / 24. - 0.1 - 3. / 44. * e3) * e2 + e3 / 14.) / ave.sqrt();
+        }
+    }
+}
+
+
+/// Returns R_D(x, y, z) in double precision
+fn rd(x : f64, y : f64, z : f64) -> f64 {
+    const ERRTOL : f64 = 0.0015;
+    const C1 : f64 = 3. / 14.;
+    const C2 : f64 = 1. / 6.;
+    const C3 : f64 = 9. / 22.;
+    const C4 : f64 = 3. / 26.;
+    const C5 : f64 = 0.25 * C3;
+    const C6 : f64 = 1.5 * C4;
+    if x.is_nan() || y.is_nan() || z.is_nan() || x.min(y) < 0. || x + y == 0. || z <= 0. {
+        return f64::NAN;
+    }
+    let (mut xt, mut yt, mut zt) = (x, y, z);
+    let mut sum = 0.;
+    let mut fac = 1.;
+    loop {
+        let (sx, sy, sz) = (xt.sqrt(), yt.sqrt(), zt.sqrt());
+        let lambda = sx * (sy + sz) + sy * sz;
+        sum += fac / (sz * (zt + lambda));
+        fac *= 0.25;
+        xt = 0.25 * (xt + lambda);
+        yt = 0.25 * (yt + lambda);
+        zt = 0.25 * (zt + lambda);
+        let ave = 0.2 * (xt + yt + 3. * zt);
+        let dx = (ave - xt) / ave;
+        let dy = (ave - yt) / ave;
+        let dz = (ave - zt) / ave;
+        if dx.abs().max(dy.abs()).max(dz.abs()) <= ERRTOL || !ave.is_finite() {
+            let ea = dx * dy;
+            let eb = dz * dz;
+            let ec = ea - eb;
+            let ed = ea - 6. * eb;
+            let ee = ed + ec + ec;
+            return 3. * sum + fac * (1. + ed * (-C1 + C5 * ed - C6 * dz * ee)
+                + dz * (C2 * ee + dz * (-C3 * ec + dz * C4 * ea))) / (ave * ave.sqrt());
+        }
+    }
+}
+
+
+/// Returns R_C(x, y) in double precision
+fn rc(x : f64, y : f64) -> f64 {
+    const ERRTOL : f64 = 0.0012;
+    if x.is_nan() || y.is_nan() || x < 0. || y == 0. {
+        return f64::NAN;
+    }
+    let (mut xt, mut yt, w) = if y > 0. { (x, y, 1.) } else { (x - y, -y, x.sqrt() / (x - y).sqrt()) };
+    loop {
+        let lambda = 2. * xt.sqrt() * yt.sqrt() + yt;
+        xt = 0.25 * (xt + lambda);
+        yt = 0.25 * (yt + lambda);
+        let ave = (xt + yt + yt) / 3.;
+        let s = (yt - ave) / ave;
+        if s.abs() <= ERRTOL || !ave.is_finite() {
+            return w * (1. + s * s * (0.3 + s * (1. / 7. + s * (0.375 + s * 9. / 22.)))) / ave.sqrt();
+        }
+    }
+}
+
+
+/// Returns R_J(x, y, z, p) in double precision
+fn rj(x : f64, y : f64, z : f64, p : f64) -> f64 {
+    const ERRTOL : f64 = 0.0015;
+    const C1 : f64 = 3. / 14.;
+    const C2 : f64 = 1. / 3.;
+    const C3 : f64 = 3. / 22.;
+    const C4 : f64 = 3. / 26.;
+    const C5 : f64 = 0.75 * C3;
+    const C6 : f64 = 1.5 * C4;
+    const C7 : f64 = 0.5 * C2;
+    const C8 : f64 = C3 + C3;
+    if x.is_nan() || y.is_nan() || z.is_nan() || p.is_nan() || x.min(y).min(z) < 0.
+        || x + y == 0. || x + z == 0. || y + z == 0. || p == 0. {
+        return f64::NAN;
+    }
+    let (mut xt, mut yt, mut zt, mut pt);
+    // Transformation of the principal value for p < 0
+    let (mut a, mut b, mut rcx) = (0., 0., 0.);
+    if p > 0. {
+        xt = x;
+        yt = y;
+        zt = z;
+        pt = p;
+    }
+    else {
+        xt = x.min(y).min(z);
+        zt = x.max(y).max(z);
+        yt = x + y + z - xt - zt;
+        a = 1. / (yt - p);
+        b = a * (zt - yt) * (yt - xt);
+        pt = yt + b;
+        let rho = xt * zt / yt;
+        let tau = p * pt / yt;
+        rcx = rc(rho, tau);
+    }
+    let (x0, y0, z0) = (xt, yt, zt);
+    let mut sum = 0.;
+    let mut fac = 1.;
+    loop {
+        let (sx, sy, sz) = (xt.sqrt(), yt.sqrt(), zt.sqrt());
+        let lambda = sx * (sy + sz) + sy * sz;
+        let alpha = (pt * (sx + sy + sz) + sx * sy * sz).powi(2);
+        let beta = pt * (pt + lambda).powi(2);
+        sum += fac * rc(alpha, beta);
+        fac *= 0.25;
+        xt = 0.25 * (xt + lambda);
+        yt = 0.25 * (yt + lambda);
+        zt = 0.25 * (zt + lambda);
+        pt = 0.25 * (pt + lambda);
+        let ave = 0.2 * (xt + yt + zt + pt + pt);
+        let dx = (ave - xt) / ave;
+        let dy = (ave - yt) / ave;
+        let dz = (ave - zt) / ave;
+        let dp = (ave - pt) / ave;
+        if dx.abs().max(dy.abs()).max(dz.abs()).max(dp.abs()) <= ERRTOL || !ave.is_finite() {
+            let ea = dx * (dy + dz) + dy * dz;
+            let eb = dx * dy * dz;
+            let ec = dp * dp;
+            let ed = ea - 3. * ec;
+            let ee = eb + 2. * dp * (ea - ec);
+            let value = 3. * sum + fac * (1. + ed * (-C1 + C5 * ed - C6 * ee) + eb * (C7 + dp * (-C8 + dp * C4))
+                + dp * ea * (C2 - dp * C3) - C2 * dp * ec) / (ave * ave.sqrt());
+            return if p > 0. { value } else { a * (b * value + 3. * (rcx - rf(x0, y0, z0))) };
+        }
+    }
+}
+
+
+/// Returns Carlson's integral R_F(x, y, z) for x, y, z >= 0 with
+/// at most one of them zero
+pub fn carlson_rf<T:Float>(x : T, y : T, z : T) -> T {
+    from_f64(rf(to_f64(x), to_f64(y), to_f64(z)))
+}
+
+
+/// Returns Carlson's integral R_D(x, y, z) for x, y >= 0 with
+/// at most one of them zero and z > 0
+pub fn carlson_rd<T:Float>(x : T, y : T, z : T) -> T {
+    from_f64(rd(to_f64(x), to_f64(y), to_f64(z)))
+}
+
+
+/// Returns Carlson's integral R_J(x, y, z, p) for x, y, z >= 0
+/// with at most one of them zero and p != 0
+pub fn carlson_rj<T:Float>(x : T, y : T, z : T, p : T) -> T {
+    from_f64(rj(to_f64(x), to_f64(y), to_f64(z), to_f64(p)))
+}
+
+
+/// Returns Carlson's integral R_C(x, y) for x >= 0 and y != 0
+pub fn carlson_rc<T:Float>(x : T, y : T) -> T {
+    from_f64(rc(to_f64(x), to_f64(y)))
+}
+
+
+/// Returns the complete elliptic integral of the first kind K(m)
+/// for m <= 1
+pub fn elliptic_k<T:Float>(m : T) -> T {
+    let m = to_f64(m);
+    let value = if m == 1. { f64::INFINITY } else if m > 1. { f64::NAN } else { rf(0., 1. - m, 1.) };
+    from_f64(value)
+}
+
+
+/// Returns the complete elliptic integral of the second kind
+/// E(m) for m <= 1
+pub fn elliptic_e<T:Float>(m : T) -> T {
+    let m = to_f64(m);
+    let value = if m == 1. {
+        1.
+    }
+    else if m > 1. || m.is_nan() {
+        f64::NAN
+    }
+    else {
+        rf(0., 1. - m, 1.) - m / 3. * rd(0., 1. - m, 1.)
+    };
+    from_f64(value)
+}
+
+
+/// Returns the complete elliptic integral of the third kind
+/// Pi(n | m) for m < 1. For n > 1 this is the Cauchy principal
+/// value.
+pub fn elliptic_pi<T:Float>(n : T, m : T) -> T {
+    let (n, m) = (to_f64(n), to_f64(m));
+    let value = if n.is_nan() || m.is_nan() || m >= 1. {
+        f64::NAN
+    }
+    else if n == 1. {
+        f64::INFINITY
+    }
+    else {
+        rf(0., 1. - m, 1.) + n / 3. * rj(0., 1. - m, 1., 1. - n)
+    };
+    from_f64(value)
+}
+
+
+/// Splits phi = phi_0 + k pi with |phi_0| <= pi/2 and returns
+/// (sin phi_0, cos phi_0, k)
+fn reduce(phi : f64) -> (f64, f64, f64) {
+    let k = (phi / PI).round();
+    let phi0 = phi - k * PI;
+    (phi0.sin(), phi0.cos(), k)
+}
+
+
+/// Returns the incomplete elliptic integral of the first kind
+/// F(phi | m)
+pub fn elliptic_f<T:Float>(phi : T, m : T) -> T {
+    let (phi, m) = (to_f64(phi), to_f64(m));
+    if phi.is_nan() || m.is_nan() || phi.is_infinite() {
+        return T::nan();
+    }
+    let (s, c, k) = reduce(phi);
+    let delta = 1. - m * s * s;
+    let value = if delta < 0. || (k != 0. && m > 1.) {
+        f64::NAN
+    }
+    else {
+        let f = s * rf(c * c, delta, 1.);
+        if k == 0. { f } else { f + 2. * k * to_f64(elliptic_k(m)) }
+    };
+    from_f64(value)
+}
+
+
+/// Returns the incomplete elliptic integral of the second kind
+/// E(phi | m)
+pub fn elliptic_e_incomplete<T:Float>(phi : T, m : T) -> T {
+    let (phi, m) = (to_f64(phi), to_f64(m));
+    if phi.is_nan() || m.is_nan() || phi.is_infinite() {
+        return T::nan();
+    }
+    let (s, c, k) = reduce(phi);
+    let delta = 1. - m * s * s;
+    let value = if delta < 0. || (k != 0. && m > 1.) {
+        f64::NAN
+    }
+    else {
+        let (c2, s3) = (c * c, s * s * s);
+        let e = s * rf(c2, delta, 1.) - m / 3. * s3 * rd(c2, delta, 1.);
+        if k == 0. { e } else { e + 2. * k * to_f64(elliptic_e(m)) }
+    };
+    from_f64(value)
+}
+
+
+/// Returns the incomplete elliptic integral of the third kind
+/// Pi(n; phi | m). Beyond the singularity n sin^2 phi = 1 this is
+/// the Cauchy principal value.
+pub fn elliptic_pi_incomplete<T:Float>(n : T, phi : T, m : T) -> T {
+    let (n, phi, m) = (to_f64(n), to_f64(phi), to_f64(m));
+    if n.is_nan() || phi.is_nan() || m.is_nan() || phi.is_infinite() {
+        return T::nan();
+    }
+    let (s, c, k) = reduce(phi);
+    let delta = 1. - m * s * s;
+    let value = if delta < 0. || (k != 0. && m >= 1.) {
+        f64::NAN
+    }
+    else {
+        let (c2, s3) = (c * c, s * s * s);
+        let p = s * rf(c2, delta, 1.) + n / 3. * s3 * rj(c2, delta, 1., 1. - n * s * s);
+        if k == 0. { p } else { p + 2. * k * to_f64(elliptic_pi(n, m)) }
+    };
+    from_f64(value)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn relative(a : f64, b : f64) -> f64 {
+        ((a - b) / b).abs()
+    }
+
+    #[test]
+    fn test_carlson(){
+        // Reference values by mpmath
+        assert!(relative(carlson_rf(1., 2., 3.), 0.726_945_935_468_908_2) < 1e-14);
+        assert!(relative(carlson_rf(0., 0.5, 4.), 1.236_798_086_875_672) < 1e-14);
+        assert!(relative(carlson_rd(1., 2., 3.), 0.290_460_281_028_990_6) < 1e-14);
+        assert!(relative(carlson_rj(1., 2., 3., 4.), 0.239_848_099_749_567_8) < 1e-14);
+        // Principal value, the real part of the complex integral
+        assert!(relative(carlson_rj(2., 3., 4., -0.5), 0.247_238_197_030_515_6) < 1e-13);
+        assert!(relative(carlson_rc(1., 2.), PI / 4.) < 1e-14);
+        assert!(relative(carlson_rc(2., -1.), 0.661_768_020_759_984_6) < 1e-14);
+        assert!(carlson_rf(0., 0., 1.).is_nan() && carlson_rd(1., 1., 0.).is_nan());
+    }
+
+    #[test]
+    fn test_complete(){
+        // (m, K(m), E(m)) by mpmath
+        let cases = [(0.5, 1.854_074_677_301_372, 1.350_643_881_047_676),
+            (-3., 1.078_257_823_749_822, 2.422_112_055_136_919),
+            (0.99, 3.695_637_362_989_874, 1.015_993_545_025_224),
+            (1e-3, 1.571_189_246_923_344, 1.570_403_554_051_424)];
+        for &(m, k, e) in cases.iter() {
+            assert!(relative(elliptic_k(m), k) < 1e-14, "{} {}", m, elliptic_k(m));
+            assert!(relative(elliptic_e(m), e) < 1e-14, "{} {}", m, elliptic_e(m));
+        }
+        assert!(relative(elliptic_k(0.), PI / 2.) < 1e-15);
+        assert_eq!(elliptic_e(1.), 1.);
+        assert_eq!(elliptic_k(1.), f64::INFINITY);
+        // Legendre's relation
+        let m = 0.3;
+        let (k, e, k1, e1) = (elliptic_k(m), elliptic_e(m), elliptic_k(1. - m), elliptic_e(1. - m));
+        assert!((e * k1 + e1 * k - k * k1 - PI / 2.).abs() < 1e-14);
+        let pi = [(0.3, 0.5, 2.250_376_821_943_947), (-2., 0.8, 1.176_874_417_055_348),
+            (0.9, 0.1, 5.169_473_402_157_697), (3., 0.5, -0.192_988_208_490_139_3)];
+        for &(n, m, v) in pi.iter() {
+            assert!(relative(elliptic_pi(n, m), v) < 1e-13, "{} {} {}", n, m, elliptic_pi(n, m));
+        }
+        assert!(relative(elliptic_pi(0., 0.5), elliptic_k(0.5)) < 1e-15);
+        assert!(relative(elliptic_k(0.5f32) as f64, 1.854_074_677_301_372) < 1e-6);
+    }
+
+    #[test]
+    fn test_incomplete(){
+        // (phi, m, F, E) by mpmath
+        let cases = [(0.7, 0.5, 0.728_770_305_718_190_2, 0.673_189_174_547_128_8),
+            (2.5, 0.3, 2.773_381_177_557_62, 2.261_502_532_164_161),
+            (-4., 0.9, -6.126_351_578_834_841, -2.977_755_245_475_778),
+            (0.4, 3., 0.439_965_510_363_979, 0.366_418_817_217_237_5)];
+        for &(phi, m, f, e) in cases.iter() {
+            assert!(relative(elliptic_f(phi, m), f) < 1e-14, "{} {}", phi, elliptic_f(phi, m));
+            assert!(relative(elliptic_e_incomplete(phi, m), e) < 1e-14, "{} {}", phi, elliptic_e_incomplete(phi, m));
+        }
+        let pi = [(0.3, 0.7, 0.5, 0.764_983_109_583_247_9), (-2., 2.5, 0.8, 1.800_368_471_473_622),
+            (0.5, -1.2, 0.3, -1.619_194_419_388_212), (2., 0.5, 0.4, 0.624_943_727_466_394_2)];
+        for &(n, phi, m, v) in pi.iter() {
+            assert!(relative(elliptic_pi_incomplete(n, phi, m), v) < 1e-13, "{} {} {}", n, phi, m);
+        }
+        assert!(relative(elliptic_f(PI / 2., 0.5), elliptic_k(0.5)) < 1e-15);
+        assert!((elliptic_f(0.6, 0.) - 0.6).abs() < 1e-15);
+        assert!((elliptic_e_incomplete(0.6, 1.) - 0.6f64.sin()).abs() < 1e-15);
+        assert!(elliptic_f(1.2, 2.).is_nan());
+    }
+}
@@ -0,0 +1,219 @@
+#![doc="Error function, complementary error function and inverses
+
+The error functions are incomplete gamma functions of order 1/2:
+
+erf(x) = P(1/2, x^2), erfc(x) = Q(1/2, x^2) for x >= 0
+
+so erfc keeps its relative accuracy far into the tail. The
+inverses start from the approximation of Giles (Approximating
+the erfinv function, GPU Computing Gems, 2011), or from the
+asymptotic expansion of erfc in the far tail, and take Halley
+steps on erf or erfc, whichever is smaller.
+"]
+
+
+// std imports
+use std::f64::consts::PI;
+
+// external imports
+use num::traits::Float;
+
+// local imports
+use special::support::{to_f64, from_f64};
+use special::gamma::{gamma_p, gamma_q};
+
+
+/// Maximum number of Halley steps of the inverses
+const MAX_HALLEY : usize = 50;
+
+
+/// Returns the error function 2 / sqrt(pi) int_0^x exp(-t^2) dt
+pub fn erf<T:Float>(x : T) -> T {
+    let x = to_f64(x);
+    let value = if x.is_nan() {
+        f64::NAN
+    }
+    else if x == 0. {
+        x
+    }
+    else {
+        let v = if x * x < 1.5 { gamma_p(0.5, x * x) } else { 1. - gamma_q(0.5, x * x) };
+        if x < 0. { -v } else { v }
+    };
+    from_f64(value)
+}
+
+
+/// Returns the complementary error function erfc(x) = 1 - erf(x)
+pub fn erfc<T:Float>(x : T) -> T {
+    let x = to_f64(x);
+    let value = if x.is_nan() {
+        f64::NAN
+    }
+    else if x < 0. {
+        1. + erf(-x)
+    }
+    else if x * x < 1.5 {
+        1. - gamma_p(0.5, x * x)
+    }
+    else {
+        gamma_q(0.5, x * x)
+    };
+    from_f64(value)
+}
+
+
+/// Returns the approximation of Giles to the inverse error
+/// function, given w = -ln((1 - y) (1 + y)) > 0
+fn giles(w : f64) -> f64 {
+    if w < 5. {
+        let w = w - 2.5;
+        let mut p = 2.810_226_36e-8;
+        for c in [3.432_739_39e-7, -3.523_387_7e-6, -4.391_506_54e-6, 2.185_808_7e-4,
+            -1.253_725_03e-3, -4.177_681_64e-3, 0.246_640_727, 1.501_409_41].iter() {
+            p = c + p * w;
+        }
+        p
+    }
+    else {
+        let w = w.sqrt() - 3.;
+        let mut p = -2.002_142_57e-4;
+        for c in [1.009_505_58e-4, 1.349_343_22e-3, -3.673_428_44e-3, 5.739_507_73e-3,
+            -7.622_461_3e-3, 9.438_870_47e-3, 1.001_674_06, 2.832_976_82].iter() {
+            p = c + p * w;
+        }
+        p
+    }
+}
+
+
+/// Returns x >= 0 with erf(x) = y and erfc(x) = q, given
+/// 0 <= y < 1 and q = 1 - y
+fn inverse(y : f64, q : f64) -> f64 {
+    let w = -(q * (1. + y)).ln();
+    let mut x = if w < 36. {
+        giles(w) * y
+    }
+    else {
+        // erfc(x) ~ exp(-x^2) / (x sqrt(pi))
+        let t = (-q.ln()).sqrt();
+        (-(q * t * PI.sqrt()).ln()).sqrt()
+    };
+    let c = 2. / PI.sqrt();
+    for _ in 0..MAX_HALLEY {
+        // Halley step on erf or erfc, both with x'' = -2 x x'
+        let derivative = c * (-x * x).exp();
+        if derivative == 0. {
+            break;
+        }
+        let u = if q < 0.5 {
+            -(erfc(x) - q) / derivative
+        }
+        else {
+            (erf(x) - y) / derivative
+        };
+        let step = u / (1. + x * u);
+        x -= step;
+        if step.abs() <= 4. * f64::EPSILON * x.abs() {
+            break;
+        }
+    }
+    x
+}
+
+
+/// Returns the inverse error function, x with erf(x) = y for
+/// -1 <= y <= 1
+pub fn erf_inv<T:Float>(y : T) -> T {
+    let y = to_f64(y);
+    let value = if y.is_nan() || y.abs() > 1. {
+        f64::NAN
+    }
+    else if y == 0. {
+        y
+    }
+    else if y.abs() == 1. {
+        y * f64::INFINITY
+    }
+    else {
+        let x = inverse(y.abs(), 1. - y.abs());
+        if y < 0. { -x } else { x }
+    };
+    from_f64(value)
+}
+
+
+/// Returns the inverse complementary error function, x with
+/// erfc(x) = q for 0 <= q <= 2
+pub fn erfc_inv<T:Float>(q : T) -> T {
+    let q = to_f64(q);
+    let value = if q.is_nan() || !(0. ..=2.).contains(&q) {
+        f64::NAN
+    }
+    else if q == 0. {
+        f64::INFINITY
+    }
+    else if q == 2. {
+        f64::NEG_INFINITY
+    }
+    else if q > 1. {
+        -inverse(q - 1., 2. - q)
+    }
+    else {
+        inverse(1. - q, q)
+    };
+    from_f64(value)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn relative(a : f64, b : f64) -> f64 {
+        ((a - b) / b).abs()
+    }
+
+    #[test]
+    fn test_erf(){
+        // (x, erf(x), erfc(x)) by mpmath
+        let cases = [(0.5, 0.520_499_877_813_046_5, 0.479_500_122_186_953_5),
+            (1., 0.842_700_792_949_714_9, 0.157_299_207_050_285_1),
+            (2., 0.995_322_265_018_952_7, 0.004_677_734_981_047_266),
+            (1e-10, 1.128_379_167_095_513e-10, 0.999_999_999_887_162_1),
+            (5., 0.999_999_999_998_462_5, 1.537_459_794_428_035e-12),
+            (10., 1., 2.088_487_583_762_545e-45)];
+        for &(x, e, c) in cases.iter() {
+            assert!(relative(erf(x), e) < 1e-14, "{} {}", x, erf(x));
+            assert!(relative(erfc(x), c) < 1e-13, "{} {}", x, erfc(x));
+            assert!(relative(erf(-x), -e) < 1e-14);
+            assert!(relative(erfc(-x), 2. - c) < 1e-14);
+        }
+        assert_eq!(erf(0.), 0.);
+        assert_eq!(erfc(f64::INFINITY), 0.);
+        assert!(relative(erf(0.5f32) as f64, 0.520_499_877_813_046_5) < 1e-6);
+    }
+
+    #[test]
+    fn test_inverse(){
+        assert!(relative(erf_inv(0.5), 0.476_936_276_204_469_9) < 1e-14);
+        for &y in [1e-12, 0.01, 0.3, 0.7, 0.95, 0.999_999, 1. - 1e-15].iter() {
+            let x = erf_inv(y);
+            assert!(relative(erf(x), y) < 1e-14, "{} {}", y, x);
+            assert_eq!(erf_inv(-y), -x);
+        }
+        for &q in [1e-300, 1e-100, 1e-20, 1e-5, 0.2, 0.9, 1.3, 1.99].iter() {
+            let x = erfc_inv(q);
+            assert!(relative(erfc(x), q) < 1e-12, "{} {}", q, x);
+        }
+        assert_eq!(erf_inv(1.), f64::INFINITY);
+        assert_eq!(erfc_inv(0.), f64::INFINITY);
+        assert!(erf_inv(1.5).is_nan() && erfc_inv(-0.5).is_nan());
+    }
+}
@@ -0,0 +1,87 @@
+#![doc="Conversions and trigonometric helpers for special functions
+"]
+
+
+// std imports
+use std::f64::consts::PI;
+
+// external imports
+use num::traits::Float;
+
+
+/// Relative accuracy targeted by the series and continued fractions
+pub const EPS : f64 = f64::EPSILON;
+
+/// A number near the smallest normalized number, used to avoid
+/// division by zero in continued fractions
+pub const FPMIN : f64 = 1e-300;
+
+
+/// Converts to double precision
+pub fn to_f64<T:Float>(x : T) -> f64 {
+    x.to_f64().unwrap_or(f64::NAN)
+}
+
+
+/// Rounds a double precision value to the target type, with
+/// overflow to infinity
+pub fn from_f64<T:Float>(x : f64) -> T {
+    T::from(x).unwrap_or_else(|| {
+        if x > 0. { T::infinity() } else if x < 0. { T::neg_infinity() } else { T::nan() }
+    })
+}
+
+
+/// Returns sin(pi x), exactly zero at the integers
+pub fn sin_pi(x : f64) -> f64 {
+    if !x.is_finite() {
+        return f64::NAN;
+    }
+    let r = x % 2.;
+    let r = if r < -1. { r + 2. } else if r > 1. { r - 2. } else { r };
+    if r > 0.5 {
+        (PI * (1. - r)).sin()
+    }
+    else if r < -0.5 {
+        -(PI * (1. + r)).sin()
+    }
+    else {
+        (PI * r).sin()
+    }
+}
+
+
+/// Returns cos(pi x), exactly zero at the half integers
+pub fn cos_pi(x : f64) -> f64 {
+    sin_pi(x + 0.5)
+}
+
+
+/// Returns true for integer values
+pub fn is_integer(x : f64) -> bool {
+    x.is_finite() && x == x.round()
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_sin_pi(){
+        assert_eq!(sin_pi(3.), 0.);
+        assert_eq!(sin_pi(-2.), 0.);
+        assert_eq!(cos_pi(2.5), 0.);
+        assert!((sin_pi(0.25) - 0.5f64.sqrt()).abs() <= f64::EPSILON);
+        assert!((sin_pi(-7.75) - 0.5f64.sqrt()).abs() < 1e-15);
+        assert_eq!(cos_pi(1.), -1.);
+        assert_eq!(from_f64::<f32>(1e300), f32::INFINITY);
+        assert_eq!(to_f64(0.5f32), 0.5);
+    }
+}
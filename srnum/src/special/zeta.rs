@@ -0,0 +1,154 @@
+#![doc="Riemann and Hurwitz zeta functions
+
+The Hurwitz zeta function
+
+zeta(s, a) = sum_k>=0 (k + a)^-s
+
+is summed directly over the first ten terms and the tail is
+approximated by the Euler-Maclaurin formula with twelve
+Bernoulli terms. The Riemann zeta function zeta(s) = zeta(s, 1)
+uses the functional equation
+
+zeta(s) = 2^s pi^(s-1) sin(pi s / 2) Gamma(1 - s) zeta(1 - s)
+
+for s < 0 and a direct sum for large s.
+"]
+
+
+// std imports
+use std::f64::consts::PI;
+
+// external imports
+use num::traits::Float;
+
+// local imports
+use special::support::{to_f64, from_f64, sin_pi, is_integer, EPS};
+use special::gamma::ln_gamma;
+
+
+/// Bernoulli numbers B_2, B_4, ..., B_24
+const BERNOULLI : [f64; 12] = [1. / 6., -1. / 30., 1. / 42., -1. / 30., 5. / 66., -691. / 2_730.,
+    7. / 6., -3_617. / 510., 43_867. / 798., -174_611. / 330., 854_513. / 138.,
+    -236_364_091. / 2_730.];
+
+/// Number of directly summed terms
+const DIRECT : usize = 10;
+
+
+/// Returns zeta(s, a) by the Euler-Maclaurin formula, for
+/// s != 1 and a > 0
+fn euler_maclaurin(s : f64, a : f64) -> f64 {
+    let mut sum = (0..DIRECT).fold(0., |sum, k| sum + (k as f64 + a).powf(-s));
+    let w = DIRECT as f64 + a;
+    sum += w.powf(1. - s) / (s - 1.) + 0.5 * w.powf(-s);
+    // B_2j / (2j)! s (s + 1) ... (s + 2j - 2) w^(-s-2j+1)
+    let mut factor = s * w.powf(-s - 1.);
+    let mut factorial = 2.;
+    for (j, b) in BERNOULLI.iter().enumerate() {
+        let term = b / factorial * factor;
+        sum += term;
+        if term.abs() < EPS * sum.abs() {
+            break;
+        }
+        let k = 2. * (j + 1) as f64;
+        factor *= (s + k - 1.) * (s + k) / (w * w);
+        factorial *= (k + 1.) * (k + 2.);
+    }
+    sum
+}
+
+
+/// Returns the Riemann zeta function zeta(s) for s != 1
+pub fn zeta<T:Float>(s : T) -> T {
+    let s = to_f64(s);
+    let value = if s.is_nan() {
+        f64::NAN
+    }
+    else if s == 1. {
+        f64::INFINITY
+    }
+    else if s >= 20. {
+        // 1 + 2^-s + 3^-s + ... converges fast
+        let mut sum = 1.;
+        let mut k : f64 = 2.;
+        loop {
+            let term = k.powf(-s);
+            sum += term;
+            if term < EPS * sum {
+                break;
+            }
+            k += 1.;
+        }
+        sum
+    }
+    else if s >= 0. {
+        euler_maclaurin(s, 1.)
+    }
+    else if is_integer(0.5 * s) {
+        // The trivial zeros
+        0.
+    }
+    else {
+        let magnitude = (s * 2f64.ln() + (s - 1.) * PI.ln() + ln_gamma(1. - s)).exp();
+        magnitude * sin_pi(0.5 * s) * zeta(1. - s)
+    };
+    from_f64(value)
+}
+
+
+/// Returns the Hurwitz zeta function zeta(s, a) for s > 1 and
+/// a > 0
+pub fn hurwitz_zeta<T:Float>(s : T, a : T) -> T {
+    let (s, a) = (to_f64(s), to_f64(a));
+    let value = if s.is_nan() || a.is_nan() || s <= 1. || a <= 0. {
+        f64::NAN
+    }
+    else {
+        euler_maclaurin(s, a)
+    };
+    from_f64(value)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn relative(a : f64, b : f64) -> f64 {
+        ((a - b) / b).abs()
+    }
+
+    #[test]
+    fn test_zeta(){
+        // Reference values by mpmath
+        let cases = [(2., PI * PI / 6.), (4., PI.powi(4) / 90.), (3., 1.202_056_903_159_594),
+            (0.5, -1.460_354_508_809_587), (1.5, 2.612_375_348_685_488),
+            (-7.5, 0.003_269_039_572_600_22), (30., 1.000_000_000_931_327),
+            (1. + 1e-8, 100_000_001.184_962_8), (-1.5, -0.025_485_201_889_833_04),
+            (0.1, -0.603_037_519_856_241_7), (-30.5, 149_774_871.277_934_8),
+            (4.5, 1.054_707_510_761_454), (0., -0.5), (-1., -1. / 12.), (-3., 1. / 120.)];
+        for &(s, v) in cases.iter() {
+            assert!(relative(zeta(s), v) < 1e-13, "{} {}", s, zeta(s));
+        }
+        assert_eq!(zeta(-2.), 0.);
+        assert_eq!(zeta(-20.), 0.);
+        assert_eq!(zeta(1.), f64::INFINITY);
+        assert!(relative(zeta(2f32) as f64, PI * PI / 6.) < 1e-6);
+    }
+
+    #[test]
+    fn test_hurwitz_zeta(){
+        assert!(relative(hurwitz_zeta(2., 0.5), PI * PI / 2.) < 1e-14);
+        assert!(relative(hurwitz_zeta(3., 1.), zeta(3.)) < 1e-14);
+        assert!(relative(hurwitz_zeta(3., 10.3), 0.005_192_693_408_150_016) < 1e-14);
+        assert!(relative(hurwitz_zeta(1.1, 0.01), 169.056_740_738_629_1) < 1e-13);
+        assert!(relative(hurwitz_zeta(2.5, 1000.), 2.109_766_904_416_677e-5) < 1e-14);
+        assert!(hurwitz_zeta(0.5, 1.).is_nan() && hurwitz_zeta(2., 0.).is_nan());
+    }
+}
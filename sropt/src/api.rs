@@ -0,0 +1,41 @@
+// Optimization library
+
+pub use lp::sparse::*;
+pub use lp::problem::*;
+pub use lp::simplex::*;
+pub use lp::interior::*;
+pub use lp::model::*;
+pub use lp::gomory::*;
+pub use lp::mip::*;
+pub use ls::result::*;
+pub use ls::linear::*;
+pub use ls::ridge::*;
+pub use ls::nnls::*;
+pub use ls::constrained::*;
+pub use ls::tls::*;
+pub use ls::nonlinear::*;
+pub use ls::curve_fit::*;
+pub use cvx::options::*;
+pub use cvx::qp::*;
+pub use cvx::active_set::*;
+pub use cvx::admm::*;
+pub use cvx::socp::*;
+pub use cvx::prox::*;
+pub use cvx::proximal::*;
+pub use unconstrained::objective::*;
+pub use unconstrained::options::*;
+pub use unconstrained::line_search::*;
+pub use unconstrained::gradient_descent::*;
+pub use unconstrained::bfgs::*;
+pub use unconstrained::lbfgs::*;
+pub use unconstrained::newton_cg::*;
+pub use unconstrained::nelder_mead::*;
+pub use roots::scalar::*;
+pub use roots::minimize::*;
+pub use roots::systems::*;
+pub use global::options::*;
+pub use global::annealing::*;
+pub use global::evolution::*;
+pub use global::swarm::*;
+pub use global::cma_es::*;
+pub use global::functions::*;
@@ -0,0 +1,286 @@
+#![doc="Primal active set method for convex quadratic programs
+
+The method keeps a working set W of constraints which are
+treated as equalities. Each iteration solves the equality
+constrained problem
+
+min (1/2) p' P p + g' p subject to a_i' p = 0 for i in W
+
+where g = P x + q is the current gradient.
+
+* If p is non-zero, the method moves along p until the first
+  inequality outside W becomes active and adds it to W.
+* If p vanishes, the multipliers of W are examined. When all
+  inequality multipliers are non-negative the point is
+  optimal; otherwise the inequality with the most negative
+  multiplier is dropped.
+
+A feasible starting point is computed by the simplex method.
+P must be positive definite on the null space of the
+working set constraints, which always holds if P is
+positive definite.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srmatrix::vecops::*;
+use srlinalg::lu::{lu_factor, lu_solve};
+use srlinalg::qr::QRDecomposition;
+use lp::problem::*;
+use lp::simplex::simplex;
+use cvx::options::*;
+use cvx::qp::*;
+
+
+/// A constraint in the working set
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Working {
+    Equality(usize),
+    Inequality(usize)
+}
+
+
+/// Finds a feasible point of the constraints by the simplex method
+fn feasible_point(qp : &QuadraticProgram) -> SRResult<Vec<f64>> {
+    let n = qp.num_variables();
+    let me = qp.num_equalities();
+    let mi = qp.num_inequalities();
+    if me + mi == 0 {
+        return Ok(vec![0.; n]);
+    }
+    let mut a : MatrixF64 = Matrix::zeros(me + mi, n);
+    let mut b : MatrixF64 = Matrix::zeros(me + mi, 1);
+    let mut types = Vec::with_capacity(me + mi);
+    for i in 0..(me + mi) {
+        let (source, rhs, t) = if i < me {
+            (&qp.a_eq, qp.b_eq[i], ConstraintType::Equal)
+        }
+        else {
+            (&qp.a_in, qp.b_in[i - me], ConstraintType::LessEqual)
+        };
+        let r = if i < me { i } else { i - me };
+        for j in 0..n {
+            a.set(i, j, source.get(r, j).unwrap());
+        }
+        b.set(i, 0, rhs);
+        types.push(t);
+    }
+    let mut lp = LinearProgram::new(&Matrix::zeros(n, 1), &a, &types, &b)?;
+    lp.set_bounds(&vec![f64::NEG_INFINITY; n], &vec![f64::INFINITY; n])?;
+    Ok(to_vec(&simplex(&lp)?.x))
+}
+
+
+/// Returns the coefficient row of a working set constraint
+fn constraint_row(qp : &QuadraticProgram, w : Working) -> Vec<f64> {
+    let n = qp.num_variables();
+    match w {
+        Working::Equality(i) => (0..n).map(|j| qp.a_eq.get(i, j).unwrap()).collect(),
+        Working::Inequality(i) => (0..n).map(|j| qp.a_in.get(i, j).unwrap()).collect()
+    }
+}
+
+
+/// Checks whether a row is linearly independent of the working set
+fn is_independent(qp : &QuadraticProgram, working : &[Working], row : &[f64]) -> bool {
+    let n = qp.num_variables();
+    let k = working.len();
+    if k >= n {
+        return false;
+    }
+    let mut m : MatrixF64 = Matrix::zeros(n, k + 1);
+    for (c, &w) in working.iter().enumerate() {
+        for (r, v) in constraint_row(qp, w).into_iter().enumerate() {
+            m.set(r, c, v);
+        }
+    }
+    for r in 0..n {
+        m.set(r, k, row[r]);
+    }
+    QRDecomposition::new_pivoted(&m).rank(None) == k + 1
+}
+
+
+#[doc="Solves a convex quadratic program by the primal active set method.
+
+Returns ``Infeasible`` if the constraints cannot be satisfied,
+``IsNotPositiveDefinite`` if an equality constrained sub-problem
+has no unique solution (P is not positive definite on the null
+space of the working set) and ``NotConverged`` if the iteration
+limit is reached.
+"]
+pub fn active_set(qp : &QuadraticProgram, options : &ConvexOptions) -> SRResult<QPSolution> {
+    let n = qp.num_variables();
+    let me = qp.num_equalities();
+    let mi = qp.num_inequalities();
+    let mut x = feasible_point(qp)?;
+    // Initial working set: independent equalities and active inequalities
+    let mut working : Vec<Working> = Vec::new();
+    for i in 0..me {
+        let row = constraint_row(qp, Working::Equality(i));
+        if is_independent(qp, &working, &row) {
+            working.push(Working::Equality(i));
+        }
+    }
+    let ax = mul(&qp.a_in, &x);
+    for i in 0..mi {
+        let scale = 1. + qp.b_in[i].abs();
+        if (qp.b_in[i] - ax[i]).abs() <= 1e-9 * scale {
+            let row = constraint_row(qp, Working::Inequality(i));
+            if is_independent(qp, &working, &row) {
+                working.push(Working::Inequality(i));
+            }
+        }
+    }
+    let q = to_vec(&qp.q);
+    for iteration in 0..options.max_iterations {
+        let px = mul(&qp.p, &x);
+        let g : Vec<f64> = (0..n).map(|j| px[j] + q[j]).collect();
+        // KKT system of the equality constrained sub-problem
+        let k = working.len();
+        let mut kkt : MatrixF64 = Matrix::zeros(n + k, n + k);
+        for r in 0..n {
+            for c in 0..n {
+                kkt.set(r, c, qp.p.get(r, c).unwrap());
+            }
+        }
+        for (c, &w) in working.iter().enumerate() {
+            for (r, v) in constraint_row(qp, w).into_iter().enumerate() {
+                kkt.set(r, n + c, v);
+                kkt.set(n + c, r, v);
+            }
+        }
+        let mut rhs = vec![0.; n + k];
+        for j in 0..n {
+            rhs[j] = -g[j];
+        }
+        let lu = match lu_factor(&kkt) {
+            Ok(lu) => lu,
+            Err(_) => return Err(SRError::IsNotPositiveDefinite)
+        };
+        let solution = lu_solve(&lu, &rhs)?;
+        let p = &solution[0..n];
+        let lambda = &solution[n..];
+        let x_scale = 1. + norm_inf(&x);
+        if norm_inf(p) <= options.tolerance * x_scale {
+            // Check the signs of the inequality multipliers
+            let mut most_negative = None;
+            let mut min_lambda = -options.threshold(norm_inf(&g));
+            for (c, &w) in working.iter().enumerate() {
+                if let Working::Inequality(_) = w {
+                    if lambda[c] < min_lambda {
+                        min_lambda = lambda[c];
+                        most_negative = Some(c);
+                    }
+                }
+            }
+            match most_negative {
+                Some(c) => {
+                    working.remove(c);
+                    continue;
+                },
+                None => {
+                    let mut y : MatrixF64 = Matrix::zeros(me, 1);
+                    let mut z : MatrixF64 = Matrix::zeros(mi, 1);
+                    for (c, &w) in working.iter().enumerate() {
+                        match w {
+                            Working::Equality(i) => y.set(i, 0, lambda[c]),
+                            Working::Inequality(i) => z.set(i, 0, lambda[c].max(0.))
+                        }
+                    }
+                    return Ok(QPSolution::new(qp, to_col(&x), y, z, iteration, true));
+                }
+            }
+        }
+        // Step along p until a blocking constraint is reached
+        let ax = mul(&qp.a_in, &x);
+        let ap = mul(&qp.a_in, p);
+        let mut alpha = 1.;
+        let mut blocking = None;
+        for i in 0..mi {
+            if working.contains(&Working::Inequality(i)) || ap[i] <= 1e-12 * norm_inf(p) {
+                continue;
+            }
+            let t = ((qp.b_in[i] - ax[i]) / ap[i]).max(0.);
+            if t < alpha {
+                alpha = t;
+                blocking = Some(i);
+            }
+        }
+        for j in 0..n {
+            x[j] += alpha * p[j];
+        }
+        if let Some(i) = blocking {
+            working.push(Working::Inequality(i));
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_active_set_textbook(){
+        // Nocedal and Wright, example 16.3:
+        // min (x1 - 1)^2 + (x2 - 2.5)^2 subject to five inequalities
+        let p = matrix_rw_f64(2, 2, &[2., 0., 0., 2.]);
+        let q = vector_f64(&[-2., -5.]);
+        let a = matrix_rw_f64(5, 2, &[
+            -1., 2.,
+            1., 2.,
+            1., -2.,
+            -1., 0.,
+            0., -1.
+            ]);
+        let b = vector_f64(&[2., 6., 2., 0., 0.]);
+        let qp = QuadraticProgram::new(&p, &q).unwrap().with_inequalities(&a, &b).unwrap();
+        let s = active_set(&qp, &ConvexOptions::new()).unwrap();
+        assert!((s.x[0] - 1.4).abs() < 1e-10);
+        assert!((s.x[1] - 1.7).abs() < 1e-10);
+        assert!(s.converged);
+        assert!(s.kkt.max() < 1e-10);
+        // Only the first constraint is active
+        assert!((s.z[0] - 0.8).abs() < 1e-10);
+        assert!(s.z[1].abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_active_set_equality(){
+        // Projection of (1, 1, 1) onto x1 + 2 x2 + 3 x3 = 0 with x3 >= 0.5
+        let p : MatrixF64 = Matrix::identity(3, 3);
+        let q = vector_f64(&[-1., -1., -1.]);
+        let qp = QuadraticProgram::new(&p, &q).unwrap()
+            .with_equalities(&matrix_rw_f64(1, 3, &[1., 2., 3.]), &vector_f64(&[0.])).unwrap()
+            .with_inequalities(&matrix_rw_f64(1, 3, &[0., 0., -1.]), &vector_f64(&[-0.5])).unwrap();
+        let s = active_set(&qp, &ConvexOptions::new()).unwrap();
+        assert!(s.kkt.max() < 1e-10);
+        assert!((s.x[0] + 2. * s.x[1] + 3. * s.x[2]).abs() < 1e-12);
+        assert!(s.x[2] >= 0.5 - 1e-12);
+    }
+
+    #[test]
+    fn test_active_set_infeasible(){
+        let p : MatrixF64 = Matrix::identity(1, 1);
+        let q = vector_f64(&[0.]);
+        let a = matrix_rw_f64(2, 1, &[1., -1.]);
+        let b = vector_f64(&[-1., -1.]);
+        let qp = QuadraticProgram::new(&p, &q).unwrap().with_inequalities(&a, &b).unwrap();
+        match active_set(&qp, &ConvexOptions::new()) {
+            Err(SRError::Infeasible) => {},
+            r => panic!("{:?}", r)
+        }
+    }
+}
@@ -0,0 +1,219 @@
+#![doc="Alternating direction method of multipliers for quadratic programs
+
+The constraints are written as l <= A x <= u with
+A = [A_eq; A_in], l = [b_eq; -inf] and u = [b_eq; b_in].
+Introducing z = A x, the problem
+
+min (1/2) x' P x + q' x subject to A x = z, l <= z <= u
+
+is solved by the operator splitting iteration of OSQP:
+
+* solve (P + sigma I + A' R A) x~ = sigma x - q + A' (R z - y),
+* z~ = A x~, relaxed to x = a x~ + (1 - a) x,
+* z = projection of a z~ + (1 - a) z + R^-1 y onto [l, u],
+* y = y + R (a z~ + (1 - a) z_old - z),
+
+where R = diag(rho) is a diagonal penalty, a is the
+relaxation parameter and sigma a small regularization.
+Equality rows get a larger penalty than inequality rows.
+
+The penalty is adapted from time to time to balance the
+primal and dual residuals, which requires a new factorization.
+ADMM converges for every convex QP (P positive semidefinite)
+but only to moderate accuracy in reasonable time.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srmatrix::vecops::*;
+use srlinalg::lu::{LUDecomposition, lu_factor, lu_solve};
+use cvx::options::*;
+use cvx::qp::*;
+
+
+/// Regularization of the x update
+const SIGMA : f64 = 1e-6;
+/// Relaxation parameter
+const ALPHA : f64 = 1.6;
+/// Initial penalty parameter of inequality rows
+const RHO : f64 = 0.1;
+/// Ratio of equality row penalties to inequality row penalties
+const EQUALITY_SCALE : f64 = 1e3;
+/// Number of iterations between penalty updates
+const ADAPT_INTERVAL : usize = 25;
+
+
+/// Factorizes P + sigma I + A' R A
+fn factor(qp : &QuadraticProgram, a : &MatrixF64, rho : &[f64])
+    -> SRResult<LUDecomposition> {
+    let n = qp.num_variables();
+    let mut k : MatrixF64 = Matrix::zeros(n, n);
+    for r in 0..n {
+        for c in 0..n {
+            let mut v = qp.p.get(r, c).unwrap();
+            if r == c {
+                v += SIGMA;
+            }
+            for i in 0..a.num_rows() {
+                v += rho[i] * a.get(i, r).unwrap() * a.get(i, c).unwrap();
+            }
+            k.set(r, c, v);
+        }
+    }
+    lu_factor(&k)
+}
+
+
+#[doc="Solves a convex quadratic program by ADMM.
+
+On exit the solution reports whether the residuals met the
+tolerances of ``options`` together with the KKT residuals.
+The iterates are returned even if the iteration limit is
+reached (with ``converged`` set to false).
+"]
+pub fn admm(qp : &QuadraticProgram, options : &ConvexOptions) -> SRResult<QPSolution> {
+    let n = qp.num_variables();
+    let me = qp.num_equalities();
+    let mi = qp.num_inequalities();
+    let m = me + mi;
+    let mut a : MatrixF64 = Matrix::zeros(m, n);
+    let mut lower = vec![f64::NEG_INFINITY; m];
+    let mut upper = vec![0.; m];
+    for i in 0..m {
+        let (source, r) = if i < me { (&qp.a_eq, i) } else { (&qp.a_in, i - me) };
+        for j in 0..n {
+            a.set(i, j, source.get(r, j).unwrap());
+        }
+        if i < me {
+            lower[i] = qp.b_eq[i];
+            upper[i] = qp.b_eq[i];
+        }
+        else {
+            upper[i] = qp.b_in[r];
+        }
+    }
+    let mut base_rho = RHO;
+    let penalties = |base : f64| -> Vec<f64> {
+        (0..m).map(|i| if i < me { base * EQUALITY_SCALE } else { base }).collect()
+    };
+    let mut rho = penalties(base_rho);
+    let mut lu = factor(qp, &a, &rho)?;
+    let q = to_vec(&qp.q);
+    let mut x = vec![0.; n];
+    let mut z = vec![0.; m];
+    let mut y = vec![0.; m];
+    let mut converged = false;
+    let mut iterations = 0;
+    while iterations < options.max_iterations {
+        iterations += 1;
+        // x update
+        let w : Vec<f64> = (0..m).map(|i| rho[i] * z[i] - y[i]).collect();
+        let atw = mul_t(&a, &w);
+        let rhs : Vec<f64> = (0..n).map(|j| SIGMA * x[j] - q[j] + atw[j]).collect();
+        let x_tilde = lu_solve(&lu, &rhs)?;
+        let z_tilde = mul(&a, &x_tilde);
+        for j in 0..n {
+            x[j] = ALPHA * x_tilde[j] + (1. - ALPHA) * x[j];
+        }
+        // z and y updates
+        for i in 0..m {
+            let relaxed = ALPHA * z_tilde[i] + (1. - ALPHA) * z[i];
+            let v = relaxed + y[i] / rho[i];
+            let projected = if v < lower[i] { lower[i] } else if v > upper[i] { upper[i] } else { v };
+            y[i] += rho[i] * (relaxed - projected);
+            z[i] = projected;
+        }
+        // Residuals
+        let ax = mul(&a, &x);
+        let px = mul(&qp.p, &x);
+        let aty = mul_t(&a, &y);
+        let r_prim = norm_inf(&(0..m).map(|i| ax[i] - z[i]).collect::<Vec<f64>>());
+        let r_dual = norm_inf(&(0..n).map(|j| px[j] + q[j] + aty[j]).collect::<Vec<f64>>());
+        let prim_scale = norm_inf(&ax).max(norm_inf(&z));
+        let dual_scale = norm_inf(&px).max(norm_inf(&aty)).max(norm_inf(&q));
+        if r_prim <= options.threshold(prim_scale) && r_dual <= options.threshold(dual_scale) {
+            converged = true;
+            break;
+        }
+        // Balance the residuals by adapting the penalty
+        if iterations % ADAPT_INTERVAL == 0 {
+            let ratio = ((r_prim / (prim_scale + 1e-30)) / (r_dual / (dual_scale + 1e-30) + 1e-30)).sqrt();
+            if !(0.2..=5.).contains(&ratio) {
+                base_rho = (base_rho * ratio).clamp(1e-6, 1e6);
+                rho = penalties(base_rho);
+                lu = factor(qp, &a, &rho)?;
+            }
+        }
+    }
+    let y_eq = to_col(&y[0..me]);
+    let z_in = to_col(&y[me..m]);
+    Ok(QPSolution::new(qp, to_col(&x), y_eq, z_in, iterations, converged))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use cvx::active_set::active_set;
+
+    #[test]
+    fn test_admm_matches_active_set(){
+        let p = matrix_rw_f64(2, 2, &[2., 0., 0., 2.]);
+        let q = vector_f64(&[-2., -5.]);
+        let a = matrix_rw_f64(5, 2, &[
+            -1., 2.,
+            1., 2.,
+            1., -2.,
+            -1., 0.,
+            0., -1.
+            ]);
+        let b = vector_f64(&[2., 6., 2., 0., 0.]);
+        let qp = QuadraticProgram::new(&p, &q).unwrap().with_inequalities(&a, &b).unwrap();
+        let options = ConvexOptions::new().tolerance(1e-10).absolute_tolerance(1e-10);
+        let s = admm(&qp, &options).unwrap();
+        assert!(s.converged);
+        assert!((s.x[0] - 1.4).abs() < 1e-6);
+        assert!((s.x[1] - 1.7).abs() < 1e-6);
+        let t = active_set(&qp, &options).unwrap();
+        assert!((&s.z - &t.z).max_abs_scalar_value() < 1e-6);
+    }
+
+    #[test]
+    fn test_admm_semidefinite(){
+        // A linear program: min -x1 - x2 s.t. x1 + 2 x2 = 2, x >= 0
+        // P = 0 rules out the active set method
+        let p : MatrixF64 = Matrix::zeros(2, 2);
+        let q = vector_f64(&[-1., -1.]);
+        let qp = QuadraticProgram::new(&p, &q).unwrap()
+            .with_equalities(&matrix_rw_f64(1, 2, &[1., 2.]), &vector_f64(&[2.])).unwrap()
+            .with_inequalities(&matrix_rw_f64(2, 2, &[-1., 0., 0., -1.]),
+                &vector_f64(&[0., 0.])).unwrap();
+        let options = ConvexOptions::new().tolerance(1e-9).absolute_tolerance(1e-9);
+        let s = admm(&qp, &options).unwrap();
+        assert!(s.converged);
+        assert!((s.x[0] - 2.).abs() < 1e-6);
+        assert!(s.x[1].abs() < 1e-6);
+        assert!(s.kkt.max() < 1e-6);
+    }
+
+    #[test]
+    fn test_admm_iteration_limit(){
+        let p : MatrixF64 = Matrix::identity(2, 2);
+        let q = vector_f64(&[1., 1.]);
+        let qp = QuadraticProgram::new(&p, &q).unwrap()
+            .with_inequalities(&matrix_rw_f64(1, 2, &[-1., -1.]), &vector_f64(&[-4.])).unwrap();
+        let s = admm(&qp, &ConvexOptions::new().max_iterations(2)).unwrap();
+        assert!(!s.converged);
+        assert_eq!(s.iterations, 2);
+    }
+}
@@ -0,0 +1,91 @@
+#![doc="Options and optimality diagnostics shared by the convex solvers
+"]
+
+
+// std imports
+
+// local imports
+
+
+/// Stopping criteria for convex solvers
+#[derive(Debug, Clone, Copy)]
+pub struct ConvexOptions {
+    /// Relative tolerance on the optimality conditions
+    pub tolerance : f64,
+    /// Absolute tolerance on the optimality conditions
+    pub absolute_tolerance : f64,
+    /// Maximum number of iterations
+    pub max_iterations : usize
+}
+
+impl ConvexOptions {
+
+    /// Default options: tolerances of 1e-8 and
+    /// at most 10000 iterations
+    pub fn new() -> ConvexOptions {
+        ConvexOptions {
+            tolerance : 1e-8,
+            absolute_tolerance : 1e-8,
+            max_iterations : 10000
+        }
+    }
+
+    /// Sets the relative tolerance
+    pub fn tolerance(mut self, tolerance : f64) -> ConvexOptions {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the absolute tolerance
+    pub fn absolute_tolerance(mut self, tolerance : f64) -> ConvexOptions {
+        self.absolute_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of iterations
+    pub fn max_iterations(mut self, max_iterations : usize) -> ConvexOptions {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Returns absolute_tolerance + tolerance * scale
+    pub fn threshold(&self, scale : f64) -> f64 {
+        self.absolute_tolerance + self.tolerance * scale
+    }
+}
+
+impl Default for ConvexOptions {
+    fn default() -> ConvexOptions {
+        ConvexOptions::new()
+    }
+}
+
+
+/// Residuals of the Karush-Kuhn-Tucker optimality conditions
+/// (measured in the infinity norm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KKTResiduals {
+    /// Gradient of the Lagrangian
+    pub stationarity : f64,
+    /// Violation of the constraints
+    pub primal_feasibility : f64,
+    /// Violation of the sign (cone) conditions on the multipliers
+    pub dual_feasibility : f64,
+    /// Violation of complementary slackness
+    pub complementarity : f64
+}
+
+impl KKTResiduals {
+
+    /// Returns the largest of the residuals
+    pub fn max(&self) -> f64 {
+        let mut m = self.stationarity;
+        for &r in [self.primal_feasibility, self.dual_feasibility,
+            self.complementarity].iter() {
+            if r > m {
+                m = r;
+            }
+        }
+        m
+    }
+}
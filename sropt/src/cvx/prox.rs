@@ -0,0 +1,294 @@
+#![doc="Proximal operators
+
+The proximal operator of a closed convex function g with
+step t > 0 is
+
+prox_{t g}(v) = argmin_x g(x) + ||x - v||^2 / (2 t).
+
+For the indicator function of a convex set it reduces to
+the Euclidean projection onto the set.
+
+* ``ZeroFunction``: g = 0, prox is the identity
+* ``L1Norm``: g(x) = lambda ||x||_1, prox is soft thresholding
+* ``BoxIndicator``: projection onto lower <= x <= upper
+* ``SimplexIndicator``: projection onto {x >= 0, sum x = r}
+* ``NuclearNorm``: g(X) = lambda (sum of singular values of X),
+  prox is singular value thresholding
+
+Vectors and matrices are treated alike: the element-wise
+operators act on all cells of their argument.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::svd::svd;
+
+
+/// Tolerance used when deciding membership of a set
+const MEMBERSHIP_TOLERANCE : f64 = 1e-10;
+
+
+/// A closed convex function with an inexpensive proximal operator
+pub trait ProximalOperator {
+
+    /// Evaluates g(x). Indicator functions return infinity
+    /// outside of their set.
+    fn value(&self, x : &MatrixF64) -> f64;
+
+    /// Computes prox_{t g}(v)
+    fn prox(&self, v : &MatrixF64, t : f64) -> MatrixF64;
+}
+
+
+/// The zero function
+#[derive(Debug, Clone, Copy)]
+pub struct ZeroFunction;
+
+impl ProximalOperator for ZeroFunction {
+
+    fn value(&self, _x : &MatrixF64) -> f64 {
+        0.
+    }
+
+    fn prox(&self, v : &MatrixF64, _t : f64) -> MatrixF64 {
+        v.clone()
+    }
+}
+
+
+/// The scaled l1 norm lambda ||x||_1
+#[derive(Debug, Clone, Copy)]
+pub struct L1Norm {
+    pub lambda : f64
+}
+
+impl L1Norm {
+    pub fn new(lambda : f64) -> L1Norm {
+        L1Norm {lambda : lambda}
+    }
+}
+
+impl ProximalOperator for L1Norm {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        self.lambda * x.cell_iter().fold(0., |s, v| s + v.abs())
+    }
+
+    fn prox(&self, v : &MatrixF64, t : f64) -> MatrixF64 {
+        let threshold = self.lambda * t;
+        let mut x = v.clone();
+        for c in 0..v.num_cols() {
+            for r in 0..v.num_rows() {
+                let a = v.get(r, c).unwrap();
+                let s = if a > threshold { a - threshold }
+                    else if a < -threshold { a + threshold }
+                    else { 0. };
+                x.set(r, c, s);
+            }
+        }
+        x
+    }
+}
+
+
+/// Indicator function of the box lower <= x <= upper
+#[derive(Debug, Clone)]
+pub struct BoxIndicator {
+    lower : MatrixF64,
+    upper : MatrixF64
+}
+
+impl BoxIndicator {
+
+    /// Creates a box with element-wise bounds
+    pub fn new(lower : &MatrixF64, upper : &MatrixF64) -> SRResult<BoxIndicator> {
+        if lower.size() != upper.size() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if lower.cell_iter().zip(upper.cell_iter()).any(|(l, u)| l.is_nan() || u.is_nan() || l > u) {
+            return Err(SRError::InvalidArgument);
+        }
+        Ok(BoxIndicator {lower : lower.clone(), upper : upper.clone()})
+    }
+
+    /// Creates a box with the same bounds for every entry
+    /// of a rows x cols argument
+    pub fn uniform(rows : usize, cols : usize, lower : f64, upper : f64)
+        -> SRResult<BoxIndicator> {
+        let l : MatrixF64 = Matrix::ones(rows, cols).copy_mul_scalar(lower);
+        let u : MatrixF64 = Matrix::ones(rows, cols).copy_mul_scalar(upper);
+        BoxIndicator::new(&l, &u)
+    }
+}
+
+impl ProximalOperator for BoxIndicator {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        let inside = x.cell_iter().zip(self.lower.cell_iter().zip(self.upper.cell_iter()))
+            .all(|(v, (l, u))| v >= l - MEMBERSHIP_TOLERANCE * (1. + l.abs())
+                && v <= u + MEMBERSHIP_TOLERANCE * (1. + u.abs()));
+        if inside { 0. } else { f64::INFINITY }
+    }
+
+    fn prox(&self, v : &MatrixF64, _t : f64) -> MatrixF64 {
+        let mut x = v.clone();
+        for c in 0..v.num_cols() {
+            for r in 0..v.num_rows() {
+                let a = v.get(r, c).unwrap();
+                let l = self.lower.get(r, c).unwrap();
+                let u = self.upper.get(r, c).unwrap();
+                x.set(r, c, if a < l { l } else if a > u { u } else { a });
+            }
+        }
+        x
+    }
+}
+
+
+/// Indicator function of the scaled probability simplex
+/// {x >= 0, sum x = radius}
+#[derive(Debug, Clone, Copy)]
+pub struct SimplexIndicator {
+    pub radius : f64
+}
+
+impl SimplexIndicator {
+
+    /// Creates the simplex with a positive radius
+    pub fn new(radius : f64) -> SRResult<SimplexIndicator> {
+        if radius.is_nan() || radius <= 0. {
+            return Err(SRError::InvalidArgument);
+        }
+        Ok(SimplexIndicator {radius : radius})
+    }
+}
+
+impl ProximalOperator for SimplexIndicator {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        let tolerance = MEMBERSHIP_TOLERANCE * (1. + self.radius);
+        let sum = x.cell_iter().fold(0., |s, v| s + v);
+        if x.cell_iter().all(|v| v >= -tolerance) && (sum - self.radius).abs() <= tolerance {
+            0.
+        }
+        else {
+            f64::INFINITY
+        }
+    }
+
+    fn prox(&self, v : &MatrixF64, _t : f64) -> MatrixF64 {
+        // Find the threshold theta with sum max(v - theta, 0) = radius
+        let mut u : Vec<f64> = v.cell_iter().collect();
+        u.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        let mut sum = 0.;
+        let mut theta = 0.;
+        for (j, &uj) in u.iter().enumerate() {
+            sum += uj;
+            let t = (sum - self.radius) / (j + 1) as f64;
+            if uj - t > 0. {
+                theta = t;
+            }
+        }
+        let mut x = v.clone();
+        for c in 0..v.num_cols() {
+            for r in 0..v.num_rows() {
+                let a = v.get(r, c).unwrap() - theta;
+                x.set(r, c, if a > 0. { a } else { 0. });
+            }
+        }
+        x
+    }
+}
+
+
+/// The scaled nuclear norm lambda (sum of singular values)
+#[derive(Debug, Clone, Copy)]
+pub struct NuclearNorm {
+    pub lambda : f64
+}
+
+impl NuclearNorm {
+    pub fn new(lambda : f64) -> NuclearNorm {
+        NuclearNorm {lambda : lambda}
+    }
+}
+
+impl ProximalOperator for NuclearNorm {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        match svd(x) {
+            Ok((_, s, _)) => self.lambda * s.cell_iter().fold(0., |a, v| a + v),
+            Err(_) => f64::NAN
+        }
+    }
+
+    fn prox(&self, v : &MatrixF64, t : f64) -> MatrixF64 {
+        let (u, s, w) = match svd(v) {
+            Ok(d) => d,
+            Err(_) => return v.clone()
+        };
+        let threshold = self.lambda * t;
+        let shrunk : Vec<f64> = s.cell_iter()
+            .map(|x| if x > threshold { x - threshold } else { 0. }).collect();
+        let d = Matrix::diag_from_vec(&Matrix::from_iter_cw(shrunk.len(), 1, shrunk.into_iter()));
+        &(&u * &d) * &w.transpose()
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_l1_prox(){
+        let g = L1Norm::new(2.);
+        let v = vector_f64(&[3., -0.5, -4., 1.]);
+        let x = g.prox(&v, 0.5);
+        assert_eq!(x, vector_f64(&[2., 0., -3., 0.]));
+        assert_eq!(g.value(&v), 17.);
+        assert_eq!(ZeroFunction.prox(&v, 1.), v);
+    }
+
+    #[test]
+    fn test_box_prox(){
+        let g = BoxIndicator::uniform(3, 1, -1., 1.).unwrap();
+        let x = g.prox(&vector_f64(&[-3., 0.5, 2.]), 1.);
+        assert_eq!(x, vector_f64(&[-1., 0.5, 1.]));
+        assert_eq!(g.value(&x), 0.);
+        assert_eq!(g.value(&vector_f64(&[0., 0., 1.5])), f64::INFINITY);
+        assert!(BoxIndicator::uniform(2, 1, 1., 0.).is_err());
+    }
+
+    #[test]
+    fn test_simplex_prox(){
+        let g = SimplexIndicator::new(1.).unwrap();
+        let x = g.prox(&vector_f64(&[0.5, 0.3, -0.2]), 1.);
+        assert!((&x - &vector_f64(&[0.6, 0.4, 0.])).max_abs_scalar_value() < 1e-15);
+        assert_eq!(g.value(&x), 0.);
+        // Points already on the simplex are fixed
+        let p = vector_f64(&[0.2, 0.3, 0.5]);
+        assert!((&g.prox(&p, 1.) - &p).max_abs_scalar_value() < 1e-15);
+        assert_eq!(g.value(&vector_f64(&[0.5, 0.6, 0.])), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_nuclear_prox(){
+        // diag(3, 1) shrinks to diag(2, 0)
+        let g = NuclearNorm::new(1.);
+        let v = matrix_rw_f64(2, 2, &[3., 0., 0., 1.]);
+        let x = g.prox(&v, 1.);
+        assert!((&x - &matrix_rw_f64(2, 2, &[2., 0., 0., 0.])).max_abs_scalar_value() < 1e-14);
+        assert!((g.value(&v) - 4.).abs() < 1e-14);
+    }
+}
@@ -0,0 +1,281 @@
+#![doc="Proximal gradient methods for composite objectives
+
+minimize F(x) = f(x) + g(x)
+
+where f is convex and differentiable and g is convex with an
+inexpensive proximal operator (see ``prox``).
+
+The proximal gradient iteration is
+
+x+ = prox_{t g}(x - t grad f(x)).
+
+FISTA adds Nesterov momentum which improves the convergence
+rate from O(1/k) to O(1/k^2). The momentum is reset whenever
+the objective increases (adaptive restart).
+
+Unless a fixed step is given, the step t is found by
+backtracking until the quadratic upper bound
+
+f(x+) <= f(y) + grad f(y)' (x+ - y) + ||x+ - y||^2 / (2 t)
+
+holds. Close to the solution, where this test is dominated by
+rounding errors, the equivalent condition
+t ||grad f(x+) - grad f(y)|| <= ||x+ - y|| is used instead.
+The iterations stop once the gradient mapping
+G_t(y) = (y - x+) / t is small. The norm of the gradient
+mapping at the final point is reported as the stationarity
+residual of the KKT conditions.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use cvx::options::*;
+use cvx::prox::ProximalOperator;
+
+
+/// Outcome of a proximal gradient method
+#[derive(Debug, Clone)]
+pub struct ProximalResult {
+    /// The computed solution
+    pub x : MatrixF64,
+    /// The objective value f(x) + g(x)
+    pub objective : f64,
+    /// Number of iterations performed
+    pub iterations : usize,
+    /// The step size used in the last iteration
+    pub step : f64,
+    /// Indicates if the stopping criterion was met
+    pub converged : bool,
+    /// KKT residuals. Only stationarity (the norm of the
+    /// gradient mapping) is meaningful for composite problems.
+    pub kkt : KKTResiduals
+}
+
+
+/// Returns the inner product of two matrices of the same shape
+fn inner(a : &MatrixF64, b : &MatrixF64) -> f64 {
+    a.cell_iter().zip(b.cell_iter()).fold(0., |s, (u, v)| s + u * v)
+}
+
+
+/// Returns the Frobenius norm of a matrix
+fn frobenius(a : &MatrixF64) -> f64 {
+    inner(a, a).sqrt()
+}
+
+
+/// Performs one proximal gradient step from y.
+/// Returns the new point and the step size used.
+fn prox_step<F, G, P>(f : &F, grad : &G, g : &P, y : &MatrixF64,
+    step : f64, backtracking : bool) -> (MatrixF64, f64)
+    where F : Fn(&MatrixF64) -> f64, G : Fn(&MatrixF64) -> MatrixF64, P : ProximalOperator {
+    let fy = f(y);
+    let gy = grad(y);
+    let mut t = step;
+    loop {
+        let x = g.prox(&(y - &gy.copy_mul_scalar(t)), t);
+        if !backtracking {
+            return (x, t);
+        }
+        let d = &x - y;
+        let q = inner(&d, &d) / (2. * t);
+        let accepted = if q > 1e-10 * (1. + fy.abs()) {
+            f(&x) <= fy + inner(&gy, &d) + q
+        }
+        else {
+            // The function values are too close to be compared
+            // reliably; check the local Lipschitz constant instead
+            t * frobenius(&(&grad(&x) - &gy)) <= frobenius(&d)
+        };
+        if accepted || t < 1e-20 {
+            return (x, t);
+        }
+        t *= 0.5;
+    }
+}
+
+
+/// Runs the (accelerated) proximal gradient method
+fn run<F, G, P>(f : F, grad : G, g : &P, x0 : &MatrixF64,
+    step : Option<f64>, accelerated : bool,
+    options : &ConvexOptions) -> SRResult<ProximalResult>
+    where F : Fn(&MatrixF64) -> f64, G : Fn(&MatrixF64) -> MatrixF64, P : ProximalOperator {
+    if let Some(t) = step {
+        if t.is_nan() || t <= 0. {
+            return Err(SRError::InvalidArgument);
+        }
+    }
+    if grad(x0).size() != x0.size() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let backtracking = step.is_none();
+    let mut t = step.unwrap_or(1.);
+    let mut x = x0.clone();
+    let mut y = x0.clone();
+    let mut theta : f64 = 1.;
+    let mut objective = f(&x) + g.value(&x);
+    let mut converged = false;
+    let mut iterations = 0;
+    let mut mapping = 0.;
+    while iterations < options.max_iterations {
+        iterations += 1;
+        let (x_new, t_used) = prox_step(&f, &grad, g, &y, t, backtracking);
+        t = t_used;
+        mapping = frobenius(&(&y - &x_new)) / t;
+        let new_objective = f(&x_new) + g.value(&x_new);
+        if accelerated && new_objective > objective {
+            // Adaptive restart: drop the momentum
+            theta = 1.;
+            y = x.clone();
+            continue;
+        }
+        if accelerated {
+            let theta_new = (1. + (1. + 4. * theta * theta).sqrt()) / 2.;
+            let beta = (theta - 1.) / theta_new;
+            y = &x_new + &(&x_new - &x).copy_mul_scalar(beta);
+            theta = theta_new;
+        }
+        else {
+            y = x_new.clone();
+        }
+        x = x_new;
+        objective = new_objective;
+        if mapping <= options.threshold(frobenius(&grad(&x))) {
+            converged = true;
+            break;
+        }
+        if backtracking {
+            // Allow the step to grow again
+            t *= 1.5;
+        }
+    }
+    Ok(ProximalResult {
+        x : x,
+        objective : objective,
+        iterations : iterations,
+        step : t,
+        converged : converged,
+        kkt : KKTResiduals {
+            stationarity : mapping,
+            primal_feasibility : 0.,
+            dual_feasibility : 0.,
+            complementarity : 0.
+        }
+    })
+}
+
+
+#[doc="Minimizes f(x) + g(x) by the proximal gradient method
+(ISTA) starting from x0.
+
+``f`` and ``grad`` evaluate the smooth part and its gradient.
+With ``step`` set to None, the step size is determined by
+backtracking; otherwise the fixed step should not exceed 1/L
+where L is the Lipschitz constant of the gradient of f.
+"]
+pub fn proximal_gradient<F, G, P>(f : F, grad : G, g : &P, x0 : &MatrixF64,
+    step : Option<f64>, options : &ConvexOptions) -> SRResult<ProximalResult>
+    where F : Fn(&MatrixF64) -> f64, G : Fn(&MatrixF64) -> MatrixF64, P : ProximalOperator {
+    run(f, grad, g, x0, step, false, options)
+}
+
+
+/// Minimizes f(x) + g(x) by FISTA with adaptive restart.
+/// The arguments are those of ``proximal_gradient``.
+pub fn fista<F, G, P>(f : F, grad : G, g : &P, x0 : &MatrixF64,
+    step : Option<f64>, options : &ConvexOptions) -> SRResult<ProximalResult>
+    where F : Fn(&MatrixF64) -> f64, G : Fn(&MatrixF64) -> MatrixF64, P : ProximalOperator {
+    run(f, grad, g, x0, step, true, options)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use cvx::prox::*;
+
+    fn lasso() -> (MatrixF64, MatrixF64) {
+        let a = matrix_rw_f64(4, 3, &[
+            1., 0., 0.5,
+            0., 1., 0.5,
+            1., 1., 0.,
+            0.5, 0., 1.
+            ]);
+        let b = vector_f64(&[1., 2., 3., 0.5]);
+        (a, b)
+    }
+
+    #[test]
+    fn test_lasso_fista(){
+        // min (1/2)||A x - b||^2 + lambda ||x||_1
+        let (a, b) = lasso();
+        let at = a.transpose();
+        let f = |x : &MatrixF64| {
+            let r = &(&a * x) - &b;
+            0.5 * r.cell_iter().fold(0., |s, v| s + v * v)
+        };
+        let grad = |x : &MatrixF64| &at * &(&(&a * x) - &b);
+        let g = L1Norm::new(0.5);
+        let x0 : MatrixF64 = Matrix::zeros(3, 1);
+        let options = ConvexOptions::new().tolerance(1e-10).absolute_tolerance(1e-10);
+        let r1 = fista(f, grad, &g, &x0, None, &options).unwrap();
+        let r2 = proximal_gradient(f, grad, &g, &x0, None, &options).unwrap();
+        assert!(r1.converged && r2.converged);
+        assert!((&r1.x - &r2.x).max_abs_scalar_value() < 1e-8);
+        // Optimality: A'(b - A x) is in lambda times the subdifferential of ||x||_1
+        let c = &at * &(&b - &(&a * &r1.x));
+        for j in 0..3 {
+            if r1.x[j].abs() > 1e-9 {
+                assert!((c[j] - 0.5 * r1.x[j].signum()).abs() < 1e-7);
+            }
+            else {
+                assert!(c[j].abs() <= 0.5 + 1e-7);
+            }
+        }
+        assert!(r1.kkt.stationarity < 1e-8);
+    }
+
+    #[test]
+    fn test_projected_gradient(){
+        // Projection of a point onto the simplex by minimizing
+        // (1/2)||x - v||^2 with a fixed step
+        let v = vector_f64(&[0.9, 0.8, -1.]);
+        let f = |x : &MatrixF64| {
+            let d = x - &v;
+            0.5 * d.cell_iter().fold(0., |s, e| s + e * e)
+        };
+        let grad = |x : &MatrixF64| x - &v;
+        let g = SimplexIndicator::new(1.).unwrap();
+        let x0 = vector_f64(&[1., 0., 0.]);
+        let r = fista(f, grad, &g, &x0, Some(1.), &ConvexOptions::new()).unwrap();
+        assert!((&r.x - &vector_f64(&[0.55, 0.45, 0.])).max_abs_scalar_value() < 1e-10);
+        assert_eq!(g.value(&r.x), 0.);
+        assert!(fista(f, grad, &g, &x0, Some(-1.), &ConvexOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_matrix_completion(){
+        // Nuclear norm regularized denoising of a rank one matrix
+        let m = matrix_rw_f64(2, 2, &[2., 4., 1., 2.]);
+        let f = |x : &MatrixF64| {
+            let d = x - &m;
+            0.5 * d.cell_iter().fold(0., |s, e| s + e * e)
+        };
+        let grad = |x : &MatrixF64| x - &m;
+        let g = NuclearNorm::new(0.1);
+        let x0 : MatrixF64 = Matrix::zeros(2, 2);
+        let r = proximal_gradient(f, grad, &g, &x0, Some(1.), &ConvexOptions::new()).unwrap();
+        // The solution is the singular value thresholding of M
+        let expected = g.prox(&m, 1.);
+        assert!((&r.x - &expected).max_abs_scalar_value() < 1e-10);
+    }
+}
@@ -0,0 +1,220 @@
+#![doc="Convex quadratic programs
+
+minimize (1/2) x' P x + q' x
+subject to A_eq x = b_eq and A_in x <= b_in
+
+where P is symmetric positive semidefinite.
+
+The Lagrangian is
+
+L(x, y, z) = (1/2) x' P x + q' x + y' (A_eq x - b_eq) + z' (A_in x - b_in)
+
+and an optimal point satisfies the KKT conditions
+
+P x + q + A_eq' y + A_in' z = 0,
+A_eq x = b_eq, A_in x <= b_in, z >= 0,
+z_i (b_in - A_in x)_i = 0.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use srmatrix::vecops::*;
+use cvx::options::KKTResiduals;
+
+
+/// A convex quadratic program
+#[derive(Debug, Clone)]
+pub struct QuadraticProgram {
+    /// Quadratic term (n x n, symmetric positive semidefinite)
+    pub p : MatrixF64,
+    /// Linear term (n x 1)
+    pub q : MatrixF64,
+    /// Equality constraint matrix (m_eq x n)
+    pub a_eq : MatrixF64,
+    /// Equality right hand side (m_eq x 1)
+    pub b_eq : MatrixF64,
+    /// Inequality constraint matrix (m_in x n)
+    pub a_in : MatrixF64,
+    /// Inequality right hand side (m_in x 1)
+    pub b_in : MatrixF64
+}
+
+
+impl QuadraticProgram {
+
+    /// Creates the unconstrained problem min (1/2) x' P x + q' x
+    pub fn new(p : &MatrixF64, q : &MatrixF64) -> SRResult<QuadraticProgram> {
+        if !p.is_square() {
+            return Err(SRError::IsNotSquareMatrix);
+        }
+        if !p.is_symmetric() {
+            return Err(SRError::IsNotSymmetric);
+        }
+        if q.num_cols() != 1 {
+            return Err(SRError::IsNotAColVector);
+        }
+        let n = p.num_rows();
+        if q.num_rows() != n {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(QuadraticProgram {
+            p : p.clone(),
+            q : q.clone(),
+            a_eq : Matrix::zeros(0, n),
+            b_eq : Matrix::zeros(0, 1),
+            a_in : Matrix::zeros(0, n),
+            b_in : Matrix::zeros(0, 1)
+        })
+    }
+
+    /// Sets the equality constraints A x = b
+    pub fn with_equalities(mut self, a : &MatrixF64, b : &MatrixF64)
+        -> SRResult<QuadraticProgram> {
+        self.check_constraints(a, b)?;
+        self.a_eq = a.clone();
+        self.b_eq = b.clone();
+        Ok(self)
+    }
+
+    /// Sets the inequality constraints A x <= b
+    pub fn with_inequalities(mut self, a : &MatrixF64, b : &MatrixF64)
+        -> SRResult<QuadraticProgram> {
+        self.check_constraints(a, b)?;
+        self.a_in = a.clone();
+        self.b_in = b.clone();
+        Ok(self)
+    }
+
+    fn check_constraints(&self, a : &MatrixF64, b : &MatrixF64) -> SRResult<()> {
+        if b.num_cols() != 1 {
+            return Err(SRError::IsNotAColVector);
+        }
+        if a.num_cols() != self.num_variables() || a.num_rows() != b.num_rows() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of variables
+    pub fn num_variables(&self) -> usize {
+        self.p.num_rows()
+    }
+
+    /// Returns the number of equality constraints
+    pub fn num_equalities(&self) -> usize {
+        self.a_eq.num_rows()
+    }
+
+    /// Returns the number of inequality constraints
+    pub fn num_inequalities(&self) -> usize {
+        self.a_in.num_rows()
+    }
+
+    /// Evaluates the objective (1/2) x' P x + q' x
+    pub fn objective(&self, x : &MatrixF64) -> f64 {
+        let x = to_vec(x);
+        0.5 * dot(&x, &mul(&self.p, &x)) + dot(&x, &to_vec(&self.q))
+    }
+
+    /// Computes the KKT residuals of a primal dual triple
+    pub fn kkt(&self, x : &MatrixF64, y : &MatrixF64, z : &MatrixF64) -> KKTResiduals {
+        let x = to_vec(x);
+        let y = to_vec(y);
+        let z = to_vec(z);
+        let px = mul(&self.p, &x);
+        let ay = mul_t(&self.a_eq, &y);
+        let az = mul_t(&self.a_in, &z);
+        let grad : Vec<f64> = (0..x.len()).map(|j| px[j] + self.q[j] + ay[j] + az[j]).collect();
+        let eq : Vec<f64> = mul(&self.a_eq, &x).iter().enumerate()
+            .map(|(i, v)| v - self.b_eq[i]).collect();
+        let slack : Vec<f64> = mul(&self.a_in, &x).iter().enumerate()
+            .map(|(i, v)| self.b_in[i] - v).collect();
+        let violation = slack.iter().fold(0., |m : f64, &s| m.max(-s));
+        let negative = z.iter().fold(0., |m : f64, &v| m.max(-v));
+        let complementarity = z.iter().zip(slack.iter())
+            .fold(0., |m : f64, (&v, &s)| m.max((v * s).abs()));
+        KKTResiduals {
+            stationarity : norm_inf(&grad),
+            primal_feasibility : norm_inf(&eq).max(violation),
+            dual_feasibility : negative,
+            complementarity : complementarity
+        }
+    }
+}
+
+
+/// Solution of a quadratic program
+#[derive(Debug, Clone)]
+pub struct QPSolution {
+    /// The optimal point
+    pub x : MatrixF64,
+    /// The optimal objective value
+    pub objective : f64,
+    /// Multipliers y of the equality constraints
+    pub y : MatrixF64,
+    /// Multipliers z >= 0 of the inequality constraints
+    pub z : MatrixF64,
+    /// Number of iterations performed
+    pub iterations : usize,
+    /// Indicates if the stopping criterion was met
+    pub converged : bool,
+    /// KKT residuals at the returned point
+    pub kkt : KKTResiduals
+}
+
+
+impl QPSolution {
+
+    /// Packs a primal dual triple and evaluates its residuals
+    pub fn new(qp : &QuadraticProgram, x : MatrixF64, y : MatrixF64, z : MatrixF64,
+        iterations : usize, converged : bool) -> QPSolution {
+        QPSolution {
+            objective : qp.objective(&x),
+            kkt : qp.kkt(&x, &y, &z),
+            x : x,
+            y : y,
+            z : z,
+            iterations : iterations,
+            converged : converged
+        }
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_qp_kkt(){
+        // min (1/2)(x1^2 + x2^2) s.t. x1 + x2 = 2, x1 <= 0.5
+        let p : MatrixF64 = Matrix::identity(2, 2);
+        let q : MatrixF64 = Matrix::zeros(2, 1);
+        let qp = QuadraticProgram::new(&p, &q).unwrap()
+            .with_equalities(&matrix_rw_f64(1, 2, &[1., 1.]), &vector_f64(&[2.])).unwrap()
+            .with_inequalities(&matrix_rw_f64(1, 2, &[1., 0.]), &vector_f64(&[0.5])).unwrap();
+        assert_eq!(qp.num_variables(), 2);
+        assert_eq!(qp.num_equalities(), 1);
+        assert_eq!(qp.num_inequalities(), 1);
+        // Optimum x = (0.5, 1.5), y = -1.5, z = 1
+        let x = vector_f64(&[0.5, 1.5]);
+        let kkt = qp.kkt(&x, &vector_f64(&[-1.5]), &vector_f64(&[1.]));
+        assert!(kkt.max() < 1e-15);
+        assert_eq!(qp.objective(&x), 1.25);
+        // The unconstrained minimizer violates the equality
+        let kkt = qp.kkt(&vector_f64(&[0., 0.]), &vector_f64(&[0.]), &vector_f64(&[0.]));
+        assert_eq!(kkt.primal_feasibility, 2.);
+        assert!(QuadraticProgram::new(&p, &vector_f64(&[1.])).is_err());
+        assert!(QuadraticProgram::new(&p, &q).unwrap()
+            .with_equalities(&p, &vector_f64(&[1.])).is_err());
+    }
+}
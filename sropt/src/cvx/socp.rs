@@ -0,0 +1,614 @@
+#![doc="Second order cone programming
+
+minimize c' x
+subject to G x + s = h, A x = b, s in K
+
+where K is the product of a non-negative orthant R+^l and
+second order cones Q^k = {(u0, u1) : u0 >= ||u1||}. The rows
+of G and h are ordered accordingly: the first l rows are
+linear inequalities, followed by one block per cone.
+
+The dual problem is
+
+maximize -h' z - b' y
+subject to G' z + A' y + c = 0, z in K.
+
+A norm constraint ||F x + g|| <= e' x + d is expressed by the
+cone block G = -[e'; F], h = [d; g].
+
+The problem is solved by a primal-dual path following
+interior point method with Nesterov-Todd scaling and
+Mehrotra's predictor-corrector scheme. The Newton systems
+are reduced to
+
+[G' W^-2 G  A'] [dx]
+[A          0 ] [dy]
+
+and solved by LU factorization. The starting point need not
+be feasible.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srmatrix::vecops::*;
+use srlinalg::lu::{lu_factor, lu_solve};
+use cvx::options::*;
+
+
+/// Fraction of the step to the boundary taken in each iteration
+const STEP_FRACTION : f64 = 0.99;
+
+
+/// Search direction (dx, dy, ds, dz)
+type Direction = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>);
+
+
+/// Structure of the cone K
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConeDimensions {
+    /// Dimension of the non-negative orthant
+    pub linear : usize,
+    /// Dimensions of the second order cones
+    pub second_order : Vec<usize>
+}
+
+impl ConeDimensions {
+
+    /// Creates a cone structure
+    pub fn new(linear : usize, second_order : &[usize]) -> ConeDimensions {
+        ConeDimensions {linear : linear, second_order : second_order.to_vec()}
+    }
+
+    /// Returns the total number of rows
+    pub fn total(&self) -> usize {
+        self.second_order.iter().fold(self.linear, |s, k| s + k)
+    }
+
+    /// Returns the degree of the cone (the number of
+    /// components of the identity element with unit norm)
+    pub fn degree(&self) -> usize {
+        self.linear + self.second_order.len()
+    }
+
+    /// Returns the starting offsets of the second order cones
+    fn offsets(&self) -> Vec<usize> {
+        let mut offsets = Vec::with_capacity(self.second_order.len());
+        let mut start = self.linear;
+        for &k in self.second_order.iter() {
+            offsets.push(start);
+            start += k;
+        }
+        offsets
+    }
+
+    /// Returns the identity element e of the cone
+    fn identity(&self) -> Vec<f64> {
+        let mut e = vec![0.; self.total()];
+        for i in 0..self.linear {
+            e[i] = 1.;
+        }
+        for start in self.offsets() {
+            e[start] = 1.;
+        }
+        e
+    }
+
+    /// Returns how far u is outside the cone (zero inside)
+    fn violation(&self, u : &[f64]) -> f64 {
+        let mut v = 0.;
+        for i in 0..self.linear {
+            if -u[i] > v {
+                v = -u[i];
+            }
+        }
+        for (start, &k) in self.offsets().into_iter().zip(self.second_order.iter()) {
+            let d = norm2(&u[(start + 1)..(start + k)]) - u[start];
+            if d > v {
+                v = d;
+            }
+        }
+        v
+    }
+
+    /// Computes the Jordan product u o v
+    fn product(&self, u : &[f64], v : &[f64]) -> Vec<f64> {
+        let mut w = vec![0.; u.len()];
+        for i in 0..self.linear {
+            w[i] = u[i] * v[i];
+        }
+        for (start, &k) in self.offsets().into_iter().zip(self.second_order.iter()) {
+            let end = start + k;
+            w[start] = dot(&u[start..end], &v[start..end]);
+            for i in (start + 1)..end {
+                w[i] = u[start] * v[i] + v[start] * u[i];
+            }
+        }
+        w
+    }
+
+    /// Solves lambda o u = d for u
+    fn divide(&self, lambda : &[f64], d : &[f64]) -> Vec<f64> {
+        let mut u = vec![0.; d.len()];
+        for i in 0..self.linear {
+            u[i] = d[i] / lambda[i];
+        }
+        for (start, &k) in self.offsets().into_iter().zip(self.second_order.iter()) {
+            let end = start + k;
+            let l0 = lambda[start];
+            let l1 = &lambda[(start + 1)..end];
+            let d1 = &d[(start + 1)..end];
+            let det = l0 * l0 - dot(l1, l1);
+            u[start] = (l0 * d[start] - dot(l1, d1)) / det;
+            for i in (start + 1)..end {
+                u[i] = (d[i] - u[start] * lambda[i]) / l0;
+            }
+        }
+        u
+    }
+
+    /// Returns the largest alpha (possibly infinite) such that
+    /// u + alpha du stays in the cone
+    fn max_step(&self, u : &[f64], du : &[f64]) -> f64 {
+        let mut alpha = f64::INFINITY;
+        for i in 0..self.linear {
+            if du[i] < 0. && -u[i] / du[i] < alpha {
+                alpha = -u[i] / du[i];
+            }
+        }
+        for (start, &k) in self.offsets().into_iter().zip(self.second_order.iter()) {
+            let end = start + k;
+            let (u0, du0) = (u[start], du[start]);
+            let u1 = &u[(start + 1)..end];
+            let du1 = &du[(start + 1)..end];
+            // q(t) = (u0 + t du0)^2 - ||u1 + t du1||^2 with q(0) > 0
+            let a = du0 * du0 - dot(du1, du1);
+            let b = 2. * (u0 * du0 - dot(u1, du1));
+            let c = u0 * u0 - dot(u1, u1);
+            let mut t = f64::INFINITY;
+            if a.abs() <= 1e-14 * (du0 * du0 + dot(du1, du1)) {
+                if b < 0. {
+                    t = -c / b;
+                }
+            }
+            else {
+                let disc = b * b - 4. * a * c;
+                if disc >= 0. {
+                    let sq = disc.sqrt();
+                    let (r1, r2) = if a > 0. {
+                        ((-b - sq) / (2. * a), (-b + sq) / (2. * a))
+                    }
+                    else {
+                        ((-b + sq) / (2. * a), (-b - sq) / (2. * a))
+                    };
+                    if a > 0. {
+                        if r1 > 0. {
+                            t = r1;
+                        }
+                    }
+                    else {
+                        t = r2;
+                    }
+                }
+            }
+            if du0 < 0. && -u0 / du0 < t {
+                t = -u0 / du0;
+            }
+            if t < alpha {
+                alpha = t;
+            }
+        }
+        alpha
+    }
+}
+
+
+/// Nesterov-Todd scaling W with W z = W^-1 s
+struct Scaling {
+    /// sqrt(s / z) for the linear part
+    d : Vec<f64>,
+    /// (eta, w) for each second order cone
+    cones : Vec<(f64, Vec<f64>)>
+}
+
+impl Scaling {
+
+    fn new(k : &ConeDimensions, s : &[f64], z : &[f64]) -> Scaling {
+        let d = (0..k.linear).map(|i| (s[i] / z[i]).sqrt()).collect();
+        let mut cones = Vec::with_capacity(k.second_order.len());
+        for (start, &dim) in k.offsets().into_iter().zip(k.second_order.iter()) {
+            let end = start + dim;
+            let sb = &s[start..end];
+            let zb = &z[start..end];
+            let s_norm = (sb[0] * sb[0] - dot(&sb[1..], &sb[1..])).sqrt();
+            let z_norm = (zb[0] * zb[0] - dot(&zb[1..], &zb[1..])).sqrt();
+            let sbar : Vec<f64> = sb.iter().map(|v| v / s_norm).collect();
+            let zbar : Vec<f64> = zb.iter().map(|v| v / z_norm).collect();
+            let gamma = ((1. + dot(&sbar, &zbar)) / 2.).sqrt();
+            // The scaling point wbar = (sbar + J zbar) / (2 gamma)
+            // satisfies (2 wbar wbar' - J) zbar = sbar; W is its
+            // square root, a hyperbolic Householder reflection
+            let mut w = vec![0.; dim];
+            w[0] = (sbar[0] + zbar[0]) / (2. * gamma);
+            for i in 1..dim {
+                w[i] = (sbar[i] - zbar[i]) / (2. * gamma);
+            }
+            let scale = (2. * (w[0] + 1.)).sqrt();
+            w[0] += 1.;
+            for wi in w.iter_mut() {
+                *wi /= scale;
+            }
+            cones.push(((s_norm / z_norm).sqrt(), w));
+        }
+        Scaling {d : d, cones : cones}
+    }
+
+    /// Computes W v
+    fn apply(&self, k : &ConeDimensions, v : &[f64]) -> Vec<f64> {
+        let mut r = vec![0.; v.len()];
+        for i in 0..k.linear {
+            r[i] = self.d[i] * v[i];
+        }
+        for (start, &(eta, ref w)) in k.offsets().into_iter().zip(self.cones.iter()) {
+            let end = start + w.len();
+            let wv = dot(w, &v[start..end]);
+            // eta (2 w w' v - J v)
+            r[start] = eta * (2. * w[0] * wv - v[start]);
+            for i in (start + 1)..end {
+                r[i] = eta * (2. * w[i - start] * wv + v[i]);
+            }
+        }
+        r
+    }
+
+    /// Computes W^-1 v
+    fn apply_inverse(&self, k : &ConeDimensions, v : &[f64]) -> Vec<f64> {
+        let mut r = vec![0.; v.len()];
+        for i in 0..k.linear {
+            r[i] = v[i] / self.d[i];
+        }
+        for (start, &(eta, ref w)) in k.offsets().into_iter().zip(self.cones.iter()) {
+            let end = start + w.len();
+            // (2 J w w' J v - J v) / eta
+            let mut wjv = w[0] * v[start];
+            for i in (start + 1)..end {
+                wjv -= w[i - start] * v[i];
+            }
+            r[start] = (2. * w[0] * wjv - v[start]) / eta;
+            for i in (start + 1)..end {
+                r[i] = (-2. * w[i - start] * wjv + v[i]) / eta;
+            }
+        }
+        r
+    }
+}
+
+
+/// A second order cone program
+#[derive(Debug, Clone)]
+pub struct SOCProgram {
+    /// Objective (n x 1)
+    pub c : MatrixF64,
+    /// Cone constraint matrix (m x n)
+    pub g : MatrixF64,
+    /// Cone constraint right hand side (m x 1)
+    pub h : MatrixF64,
+    /// Equality constraint matrix (p x n)
+    pub a : MatrixF64,
+    /// Equality right hand side (p x 1)
+    pub b : MatrixF64,
+    /// Structure of the cone
+    pub cones : ConeDimensions
+}
+
+
+impl SOCProgram {
+
+    /// Creates the problem min c' x subject to h - G x in K
+    pub fn new(c : &MatrixF64, g : &MatrixF64, h : &MatrixF64, cones : &ConeDimensions)
+        -> SRResult<SOCProgram> {
+        if c.num_cols() != 1 || h.num_cols() != 1 {
+            return Err(SRError::IsNotAColVector);
+        }
+        let n = c.num_rows();
+        if g.num_cols() != n || g.num_rows() != h.num_rows() || g.num_rows() != cones.total() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if cones.second_order.contains(&0) {
+            return Err(SRError::InvalidArgument);
+        }
+        Ok(SOCProgram {
+            c : c.clone(),
+            g : g.clone(),
+            h : h.clone(),
+            a : Matrix::zeros(0, n),
+            b : Matrix::zeros(0, 1),
+            cones : cones.clone()
+        })
+    }
+
+    /// Sets the equality constraints A x = b
+    pub fn with_equalities(mut self, a : &MatrixF64, b : &MatrixF64) -> SRResult<SOCProgram> {
+        if b.num_cols() != 1 {
+            return Err(SRError::IsNotAColVector);
+        }
+        if a.num_cols() != self.c.num_rows() || a.num_rows() != b.num_rows() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        self.a = a.clone();
+        self.b = b.clone();
+        Ok(self)
+    }
+
+    /// Computes the KKT residuals of a primal dual triple
+    /// where s = h - G x
+    pub fn kkt(&self, x : &MatrixF64, y : &MatrixF64, z : &MatrixF64) -> KKTResiduals {
+        let x = to_vec(x);
+        let y = to_vec(y);
+        let z = to_vec(z);
+        let n = x.len();
+        let aty = mul_t(&self.a, &y);
+        let gtz = mul_t(&self.g, &z);
+        let grad : Vec<f64> = (0..n).map(|j| self.c[j] + aty[j] + gtz[j]).collect();
+        let eq : Vec<f64> = mul(&self.a, &x).iter().enumerate()
+            .map(|(i, v)| v - self.b[i]).collect();
+        let s : Vec<f64> = mul(&self.g, &x).iter().enumerate()
+            .map(|(i, v)| self.h[i] - v).collect();
+        KKTResiduals {
+            stationarity : norm_inf(&grad),
+            primal_feasibility : norm_inf(&eq).max(self.cones.violation(&s)),
+            dual_feasibility : self.cones.violation(&z),
+            complementarity : dot(&s, &z).abs()
+        }
+    }
+}
+
+
+/// Solution of a second order cone program
+#[derive(Debug, Clone)]
+pub struct SOCPSolution {
+    /// The primal solution
+    pub x : MatrixF64,
+    /// The cone slack s = h - G x
+    pub s : MatrixF64,
+    /// Multipliers of the equality constraints
+    pub y : MatrixF64,
+    /// Multipliers of the cone constraints (in K)
+    pub z : MatrixF64,
+    /// The objective value c' x
+    pub objective : f64,
+    /// Number of iterations performed
+    pub iterations : usize,
+    /// Indicates if the stopping criterion was met
+    pub converged : bool,
+    /// KKT residuals at the returned point
+    pub kkt : KKTResiduals
+}
+
+
+#[doc="Solves a second order cone program by the primal-dual
+interior point method.
+
+The iterates are returned with ``converged`` set to false
+if the tolerances are not met within the iteration limit,
+which is also the case for infeasible or unbounded problems.
+"]
+pub fn socp(prog : &SOCProgram, options : &ConvexOptions) -> SRResult<SOCPSolution> {
+    let k = &prog.cones;
+    let n = prog.c.num_rows();
+    let p = prog.a.num_rows();
+    let m = prog.g.num_rows();
+    let c = to_vec(&prog.c);
+    let b = to_vec(&prog.b);
+    let h = to_vec(&prog.h);
+    let e = k.identity();
+    let degree = k.degree() as f64;
+    let mut x = vec![0.; n];
+    let mut y = vec![0.; p];
+    let mut s = e.clone();
+    let mut z = e.clone();
+    let mut converged = false;
+    let mut iterations = 0;
+    while iterations < options.max_iterations {
+        let aty = mul_t(&prog.a, &y);
+        let gtz = mul_t(&prog.g, &z);
+        let rx : Vec<f64> = (0..n).map(|j| c[j] + aty[j] + gtz[j]).collect();
+        let ax = mul(&prog.a, &x);
+        let ry : Vec<f64> = (0..p).map(|i| ax[i] - b[i]).collect();
+        let gx = mul(&prog.g, &x);
+        let rz : Vec<f64> = (0..m).map(|i| gx[i] + s[i] - h[i]).collect();
+        let gap = dot(&s, &z);
+        let mu = if degree > 0. { gap / degree } else { 0. };
+        if norm_inf(&rx) <= options.threshold(norm_inf(&c))
+            && norm_inf(&ry) <= options.threshold(norm_inf(&b))
+            && norm_inf(&rz) <= options.threshold(norm_inf(&h))
+            && gap <= options.threshold(dot(&c, &x).abs()) {
+            converged = true;
+            break;
+        }
+        iterations += 1;
+        let w = Scaling::new(k, &s, &z);
+        let lambda = w.apply(k, &z);
+        // W^-1 G and the reduced KKT matrix
+        let mut wg : Vec<Vec<f64>> = Vec::with_capacity(n);
+        for j in 0..n {
+            let col : Vec<f64> = (0..m).map(|i| prog.g.get(i, j).unwrap()).collect();
+            wg.push(w.apply_inverse(k, &col));
+        }
+        let mut kkt : MatrixF64 = Matrix::zeros(n + p, n + p);
+        for r in 0..n {
+            for cc in 0..n {
+                kkt.set(r, cc, dot(&wg[r], &wg[cc]));
+            }
+            for i in 0..p {
+                let v = prog.a.get(i, r).unwrap();
+                kkt.set(r, n + i, v);
+                kkt.set(n + i, r, v);
+            }
+        }
+        let lu = lu_factor(&kkt)?;
+        // Solves the Newton system for a complementarity target d_s
+        let newton = |ds_target : &[f64]| -> SRResult<Direction> {
+            let wu = w.apply(k, &k.divide(&lambda, ds_target));
+            let t : Vec<f64> = (0..m).map(|i| rz[i] + wu[i]).collect();
+            let tt = w.apply_inverse(k, &w.apply_inverse(k, &t));
+            let gtt = mul_t(&prog.g, &tt);
+            let mut rhs = vec![0.; n + p];
+            for j in 0..n {
+                rhs[j] = -rx[j] - gtt[j];
+            }
+            for i in 0..p {
+                rhs[n + i] = -ry[i];
+            }
+            let sol = lu_solve(&lu, &rhs)?;
+            let dx = sol[0..n].to_vec();
+            let dy = sol[n..].to_vec();
+            let gdx = mul(&prog.g, &dx);
+            let v : Vec<f64> = (0..m).map(|i| gdx[i] + t[i]).collect();
+            let dz = w.apply_inverse(k, &w.apply_inverse(k, &v));
+            let wwdz = w.apply(k, &w.apply(k, &dz));
+            let ds : Vec<f64> = (0..m).map(|i| wu[i] - wwdz[i]).collect();
+            Ok((dx, dy, ds, dz))
+        };
+        // Predictor
+        let target : Vec<f64> = k.product(&lambda, &lambda).iter().map(|v| -v).collect();
+        let (_, _, ds_a, dz_a) = newton(&target)?;
+        let alpha = k.max_step(&s, &ds_a).min(k.max_step(&z, &dz_a)).min(1.);
+        let s_a : Vec<f64> = (0..m).map(|i| s[i] + alpha * ds_a[i]).collect();
+        let z_a : Vec<f64> = (0..m).map(|i| z[i] + alpha * dz_a[i]).collect();
+        let sigma = if gap > 0. { (dot(&s_a, &z_a) / gap).clamp(0., 1.).powi(3) } else { 0. };
+        // Corrector
+        let cross = k.product(&w.apply_inverse(k, &ds_a), &w.apply(k, &dz_a));
+        let ll = k.product(&lambda, &lambda);
+        let target : Vec<f64> = (0..m).map(|i| sigma * mu * e[i] - ll[i] - cross[i]).collect();
+        let (dx, dy, ds, dz) = newton(&target)?;
+        let alpha = (STEP_FRACTION * k.max_step(&s, &ds).min(k.max_step(&z, &dz))).min(1.);
+        for j in 0..n {
+            x[j] += alpha * dx[j];
+        }
+        for i in 0..p {
+            y[i] += alpha * dy[i];
+        }
+        for i in 0..m {
+            s[i] += alpha * ds[i];
+            z[i] += alpha * dz[i];
+        }
+    }
+    let x = to_col(&x);
+    let y = to_col(&y);
+    let z = to_col(&z);
+    Ok(SOCPSolution {
+        objective : dot(&c, &to_vec(&x)),
+        kkt : prog.kkt(&x, &y, &z),
+        x : x,
+        s : to_col(&s),
+        y : y,
+        z : z,
+        iterations : iterations,
+        converged : converged
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use lp::problem::*;
+    use lp::simplex::simplex;
+
+    #[test]
+    fn test_scaling(){
+        let k = ConeDimensions::new(2, &[3, 2]);
+        let s = vec![1., 2., 3., 1., -2., 1.5, 0.3];
+        let z = vec![0.5, 4., 2., 0.5, 1., 2., -1.5];
+        let w = Scaling::new(&k, &s, &z);
+        let wz = w.apply(&k, &z);
+        let ws = w.apply_inverse(&k, &s);
+        for i in 0..7 {
+            assert!((wz[i] - ws[i]).abs() < 1e-12);
+        }
+        let v = vec![0.1, -0.2, 0.3, 0.4, -0.5, 0.6, 0.7];
+        let back = w.apply_inverse(&k, &w.apply(&k, &v));
+        for i in 0..7 {
+            assert!((back[i] - v[i]).abs() < 1e-12);
+        }
+        // lambda o (lambda \ d) = d
+        let d = k.divide(&wz, &v);
+        let p = k.product(&wz, &d);
+        for i in 0..7 {
+            assert!((p[i] - v[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_socp_distance_to_line(){
+        // min t s.t. ||x - (1, 1)|| <= t, x1 + x2 = 1
+        let c = vector_f64(&[1., 0., 0.]);
+        let g = matrix_rw_f64(3, 3, &[
+            -1., 0., 0.,
+            0., -1., 0.,
+            0., 0., -1.
+            ]);
+        let h = vector_f64(&[0., -1., -1.]);
+        let prog = SOCProgram::new(&c, &g, &h, &ConeDimensions::new(0, &[3])).unwrap()
+            .with_equalities(&matrix_rw_f64(1, 3, &[0., 1., 1.]), &vector_f64(&[1.])).unwrap();
+        let s = socp(&prog, &ConvexOptions::new()).unwrap();
+        assert!(s.converged);
+        assert!((s.objective - 0.5f64.sqrt()).abs() < 1e-7);
+        assert!((s.x[1] - 0.5).abs() < 1e-7);
+        assert!((s.x[2] - 0.5).abs() < 1e-7);
+        assert!(s.kkt.max() < 1e-7);
+    }
+
+    #[test]
+    fn test_socp_disc_and_halfplane(){
+        // max x1 + x2 over the unit disc with x1 <= 0.5
+        let c = vector_f64(&[-1., -1.]);
+        let g = matrix_rw_f64(4, 2, &[
+            1., 0.,
+            0., 0.,
+            -1., 0.,
+            0., -1.
+            ]);
+        let h = vector_f64(&[0.5, 1., 0., 0.]);
+        let prog = SOCProgram::new(&c, &g, &h, &ConeDimensions::new(1, &[3])).unwrap();
+        let s = socp(&prog, &ConvexOptions::new()).unwrap();
+        assert!(s.converged);
+        assert!((s.x[0] - 0.5).abs() < 1e-7);
+        assert!((s.x[1] - 0.75f64.sqrt()).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_socp_linear_program(){
+        // With only a linear cone the problem is an LP
+        let c = vector_f64(&[-3., -5.]);
+        let g = matrix_rw_f64(5, 2, &[
+            1., 0.,
+            0., 2.,
+            3., 2.,
+            -1., 0.,
+            0., -1.
+            ]);
+        let h = vector_f64(&[4., 12., 18., 0., 0.]);
+        let prog = SOCProgram::new(&c, &g, &h, &ConeDimensions::new(5, &[])).unwrap();
+        let s = socp(&prog, &ConvexOptions::new()).unwrap();
+        let a = g.view(0, 0, 3, 2).to_matrix();
+        let b = h.view(0, 0, 3, 1).to_matrix();
+        let lp = LinearProgram::new(&c, &a, &[ConstraintType::LessEqual; 3], &b).unwrap();
+        let t = simplex(&lp).unwrap();
+        assert!((s.objective - t.objective).abs() < 1e-6);
+        // Cone multipliers are the negated LP duals
+        for i in 0..3 {
+            assert!((s.z[i] + t.duals[i]).abs() < 1e-6);
+        }
+    }
+}
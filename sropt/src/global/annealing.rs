@@ -0,0 +1,187 @@
+#![doc="Simulated annealing
+
+A random walk which always accepts downhill moves and accepts
+an uphill move by Delta f with probability exp(-Delta f / T)
+(the Metropolis criterion). The temperature T is lowered
+geometrically after each level of ``moves`` trial moves, so
+the walk gradually settles into a deep minimum.
+
+A trial move perturbs one coordinate (cycling through the
+coordinates) by a normally distributed step. The step length
+of every coordinate is adapted after each level to keep the
+acceptance ratio between 0.4 and 0.6 (Corana et al., 1987).
+Each level starts from the best point found so far.
+The method stops once all step lengths are below
+step_tolerance times the width of the box.
+"]
+
+
+// std imports
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use global::options::*;
+use global::search::*;
+
+
+/// Cooling schedule of simulated annealing
+#[derive(Debug, Clone, Copy)]
+pub struct Annealing {
+    /// Initial temperature. By default the standard deviation
+    /// of f over random points of the box.
+    pub initial_temperature : Option<f64>,
+    /// Factor by which the temperature is lowered after each level
+    pub cooling : f64,
+    /// Number of trial moves per temperature level
+    /// (20 n by default)
+    pub moves : Option<usize>
+}
+
+impl Annealing {
+
+    /// Default schedule with a cooling factor of 0.9
+    pub fn new() -> Annealing {
+        Annealing {
+            initial_temperature : None,
+            cooling : 0.9,
+            moves : None
+        }
+    }
+}
+
+impl Default for Annealing {
+    fn default() -> Annealing {
+        Annealing::new()
+    }
+}
+
+
+/// Minimizes f over the box by simulated annealing starting from x0
+pub fn simulated_annealing<O:Objective + ?Sized>(f : &O, x0 : &MatrixF64, bounds : &Bounds,
+    schedule : &Annealing, options : &GlobalOptions) -> SRResult<MinimizeResult> {
+    if !(schedule.cooling > 0. && schedule.cooling < 1.) {
+        return Err(SRError::InvalidArgument);
+    }
+    let n = bounds.dim();
+    let moves = schedule.moves.unwrap_or(20 * n);
+    if moves == 0 {
+        return Err(SRError::InvalidArgument);
+    }
+    let mut rng = options.rng();
+    let mut tracker = Tracker::new(f);
+    let mut x = bounds.start(x0)?;
+    let mut fx = tracker.value(&x);
+    let mut temperature = match schedule.initial_temperature {
+        Some(t) => {
+            if t.is_nan() || t <= 0. {
+                return Err(SRError::InvalidArgument);
+            }
+            t
+        },
+        None => {
+            let values : Vec<f64> = (0..(10 * n).max(20))
+                .map(|_| tracker.value(&bounds.sample(&mut rng)))
+                .filter(|v| v.is_finite()).collect();
+            let k = values.len() as f64;
+            let mean = values.iter().fold(0., |s, v| s + v) / k;
+            let sd = (values.iter().fold(0., |s, v| s + (v - mean) * (v - mean)) / k).sqrt();
+            if sd > 0. && sd.is_finite() { sd } else { 1. }
+        }
+    };
+    let mut steps : Vec<f64> = (0..n).map(|i| 0.25 * bounds.width(i)).collect();
+    tracker.record(0, 0.25);
+    let mut termination = Termination::MaxIterations;
+    let mut iteration = 0;
+    while iteration < options.max_iterations {
+        iteration += 1;
+        let mut trials = vec![0usize; n];
+        let mut accepted = vec![0usize; n];
+        for k in 0..moves {
+            let i = k % n;
+            let mut y = x.clone();
+            y[i] += steps[i] * standard_normal(&mut rng);
+            bounds.clip(&mut y);
+            let fy = tracker.value(&y);
+            trials[i] += 1;
+            let delta = fy - fx;
+            if delta <= 0. || rng.gen::<f64>() < (-delta / temperature).exp() {
+                x = y;
+                fx = fy;
+                accepted[i] += 1;
+            }
+        }
+        // Corana's step adjustment
+        let mut spread : f64 = 0.;
+        for i in 0..n {
+            if trials[i] > 0 {
+                let ratio = accepted[i] as f64 / trials[i] as f64;
+                if ratio > 0.6 {
+                    steps[i] *= 1. + 2. * (ratio - 0.6) / 0.4;
+                }
+                else if ratio < 0.4 {
+                    steps[i] /= 1. + 2. * (0.4 - ratio) / 0.4;
+                }
+                steps[i] = steps[i].min(bounds.width(i));
+            }
+            spread = spread.max(steps[i] / bounds.width(i));
+        }
+        temperature *= schedule.cooling;
+        // Continue from the best point found so far
+        x = tracker.best_x().to_vec();
+        fx = tracker.best_value();
+        tracker.record(iteration, spread);
+        if spread <= options.step_tolerance {
+            termination = Termination::StepTolerance;
+            break;
+        }
+    }
+    Ok(tracker.finish(iteration, termination))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use global::functions::*;
+
+    #[test]
+    fn test_annealing_rastrigin(){
+        let bounds = Bounds::uniform(2, -5.12, 5.12).unwrap();
+        let x0 = vector_f64(&[4., -3.]);
+        let options = GlobalOptions::new().seed(1).step_tolerance(1e-7);
+        let r = simulated_annealing(&Rastrigin, &x0, &bounds, &Annealing::new(), &options).unwrap();
+        assert!(r.converged);
+        assert_eq!(r.termination, Termination::StepTolerance);
+        assert!(r.value < 1e-8);
+        assert!(r.x.max_abs_scalar_value() < 1e-4);
+        assert_eq!(r.log.len(), r.iterations + 1);
+        assert!(r.log.windows(2).all(|w| w[1].value <= w[0].value));
+        // Reproducible with the same seed
+        let s = simulated_annealing(&Rastrigin, &x0, &bounds, &Annealing::new(), &options).unwrap();
+        assert_eq!(r.x, s.x);
+        assert_eq!(r.function_evaluations, s.function_evaluations);
+    }
+
+    #[test]
+    fn test_annealing_errors(){
+        let bounds = Bounds::uniform(2, -1., 1.).unwrap();
+        let x0 = vector_f64(&[0., 0.]);
+        let mut schedule = Annealing::new();
+        schedule.cooling = 1.;
+        assert!(simulated_annealing(&Rastrigin, &x0, &bounds, &schedule,
+            &GlobalOptions::new()).is_err());
+        assert!(simulated_annealing(&Rastrigin, &vector_f64(&[0.]), &bounds,
+            &Annealing::new(), &GlobalOptions::new()).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+#![doc="Covariance matrix adaptation evolution strategy (CMA-ES)
+
+Samples lambda points x_k = m + sigma B D z_k from the normal
+distribution N(m, sigma^2 C) with C = B D^2 B' and moves the
+mean m to a weighted average of the mu best of them. The
+covariance C is adapted by a rank one update along the
+evolution path p_c and a rank mu update from the selected
+steps; the step size sigma follows the length of the
+conjugate evolution path p_sigma. Parameter settings follow
+Hansen's tutorial (The CMA Evolution Strategy, 2016) with
+lambda = 4 + floor(3 ln n) and mu = lambda / 2.
+
+Samples outside the box are projected onto it; the projected
+points are used for the update as well. The method stops
+when sigma sqrt(C_ii) falls below step_tolerance times the
+width of the box for all i, or when the values of the current
+generation and the best values of the last 10 generations
+meet the function tolerance.
+"]
+
+
+// std imports
+use std::collections::VecDeque;
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::eigen::sym_eigen;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use global::options::*;
+use global::search::*;
+
+
+/// Number of generations of best values compared for convergence
+const HISTORY : usize = 10;
+
+
+/// Parameters of CMA-ES
+#[derive(Debug, Clone, Copy)]
+pub struct CmaEs {
+    /// Initial step size (0.3 times the mean width of the box by default)
+    pub sigma : Option<f64>,
+    /// Number of samples per generation (4 + floor(3 ln n) by default)
+    pub population : Option<usize>
+}
+
+impl CmaEs {
+
+    /// Default step size and population
+    pub fn new() -> CmaEs {
+        CmaEs {
+            sigma : None,
+            population : None
+        }
+    }
+}
+
+impl Default for CmaEs {
+    fn default() -> CmaEs {
+        CmaEs::new()
+    }
+}
+
+
+/// Minimizes f over the box by CMA-ES starting from the mean x0
+pub fn cma_es<O:Objective + ?Sized>(f : &O, x0 : &MatrixF64, bounds : &Bounds,
+    parameters : &CmaEs, options : &GlobalOptions) -> SRResult<MinimizeResult> {
+    let n = bounds.dim();
+    let nf = n as f64;
+    let mut mean = bounds.start(x0)?;
+    let mean_width = (0..n).fold(0., |s, i| s + bounds.width(i)) / nf;
+    let mut sigma = parameters.sigma.unwrap_or(0.3 * mean_width);
+    let lambda = parameters.population.unwrap_or(4 + (3. * nf.ln()).floor() as usize);
+    if lambda < 2 || !(sigma > 0. && sigma.is_finite()) {
+        return Err(SRError::InvalidArgument);
+    }
+    // Selection and recombination
+    let mu = lambda / 2;
+    let raw : Vec<f64> = (0..mu).map(|i| (mu as f64 + 0.5).ln() - (i as f64 + 1.).ln()).collect();
+    let total = raw.iter().fold(0., |s, w| s + w);
+    let weights : Vec<f64> = raw.iter().map(|w| w / total).collect();
+    let mueff = 1. / weights.iter().fold(0., |s, w| s + w * w);
+    // Adaptation constants
+    let cc = (4. + mueff / nf) / (nf + 4. + 2. * mueff / nf);
+    let cs = (mueff + 2.) / (nf + mueff + 5.);
+    let c1 = 2. / ((nf + 1.3) * (nf + 1.3) + mueff);
+    let cmu = (1. - c1).min(2. * (mueff - 2. + 1. / mueff) / ((nf + 2.) * (nf + 2.) + mueff));
+    let damps = 1. + 2. * (((mueff - 1.) / (nf + 1.)).sqrt() - 1.).max(0.) + cs;
+    let chi_n = nf.sqrt() * (1. - 1. / (4. * nf) + 1. / (21. * nf * nf));
+
+    let mut rng = options.rng();
+    let mut tracker = Tracker::new(f);
+    tracker.value(&mean);
+    let mut c : MatrixF64 = Matrix::identity(n, n);
+    let mut pc = vec![0.; n];
+    let mut ps = vec![0.; n];
+    let mut history = VecDeque::with_capacity(HISTORY);
+    tracker.record(0, sigma / mean_width);
+    let mut termination = Termination::MaxIterations;
+    let mut iteration = 0;
+    while iteration < options.max_iterations {
+        iteration += 1;
+        // C = B diag(d)^2 B'
+        let (w, b) = sym_eigen(&c)?;
+        let d : Vec<f64> = (0..n).map(|i| w[i].max(0.).sqrt()).collect();
+        let mut samples : Vec<(f64, Vec<f64>)> = (0..lambda).map(|_| {
+            let z : Vec<f64> = (0..n).map(|_| standard_normal(&mut rng)).collect();
+            let mut x : Vec<f64> = (0..n).map(|i| {
+                mean[i] + sigma * (0..n).fold(0., |s, k| s + b.get(i, k).unwrap() * d[k] * z[k])
+            }).collect();
+            bounds.clip(&mut x);
+            (tracker.value(&x), x)
+        }).collect();
+        samples.sort_by(|p, q| p.0.partial_cmp(&q.0).unwrap_or(::std::cmp::Ordering::Equal));
+        // Steps y_k = (x_k - m) / sigma of the selected samples
+        let steps : Vec<Vec<f64>> = samples[..mu].iter()
+            .map(|s| (0..n).map(|i| (s.1[i] - mean[i]) / sigma).collect()).collect();
+        let yw : Vec<f64> = (0..n).map(|i| {
+            (0..mu).fold(0., |s, k| s + weights[k] * steps[k][i])
+        }).collect();
+        for i in 0..n {
+            mean[i] += sigma * yw[i];
+        }
+        // C^{-1/2} yw = B diag(1/d) B' yw
+        let bt_yw : Vec<f64> = (0..n).map(|k| {
+            let v = (0..n).fold(0., |s, i| s + b.get(i, k).unwrap() * yw[i]);
+            if d[k] > 0. { v / d[k] } else { 0. }
+        }).collect();
+        let ps_scale = (cs * (2. - cs) * mueff).sqrt();
+        for i in 0..n {
+            let v = (0..n).fold(0., |s, k| s + b.get(i, k).unwrap() * bt_yw[k]);
+            ps[i] = (1. - cs) * ps[i] + ps_scale * v;
+        }
+        let ps_norm = ps.iter().fold(0., |s, v| s + v * v).sqrt();
+        let hsig = ps_norm / (1. - (1. - cs).powi(2 * iteration as i32)).sqrt() / chi_n
+            < 1.4 + 2. / (nf + 1.);
+        let pc_scale = if hsig { (cc * (2. - cc) * mueff).sqrt() } else { 0. };
+        for i in 0..n {
+            pc[i] = (1. - cc) * pc[i] + pc_scale * yw[i];
+        }
+        // Rank one and rank mu updates
+        let correction = if hsig { 0. } else { c1 * cc * (2. - cc) };
+        for i in 0..n {
+            for j in 0..(i + 1) {
+                let rank_mu = (0..mu).fold(0., |s, k| s + weights[k] * steps[k][i] * steps[k][j]);
+                let v = (1. - c1 - cmu + correction) * c.get(i, j).unwrap()
+                    + c1 * pc[i] * pc[j] + cmu * rank_mu;
+                c.set(i, j, v);
+                c.set(j, i, v);
+            }
+        }
+        sigma *= ((cs / damps) * (ps_norm / chi_n - 1.)).exp();
+        // Convergence
+        let spread = (0..n).fold(0., |s : f64, i| {
+            s.max(sigma * c.get(i, i).unwrap().sqrt() / bounds.width(i))
+        });
+        tracker.record(iteration, spread);
+        if history.len() == HISTORY {
+            history.pop_front();
+        }
+        history.push_back(samples[0].0);
+        let (lo, hi) = value_range(&samples.iter().map(|s| s.0).collect::<Vec<f64>>());
+        let (h_lo, h_hi) = value_range(&history.iter().cloned().collect::<Vec<f64>>());
+        if history.len() == HISTORY && options.values_converged(lo.min(h_lo), hi.max(h_hi)) {
+            termination = Termination::FunctionTolerance;
+            break;
+        }
+        if spread <= options.step_tolerance {
+            termination = Termination::StepTolerance;
+            break;
+        }
+        if !spread.is_finite() {
+            break;
+        }
+    }
+    Ok(tracker.finish(iteration, termination))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use global::functions::*;
+    use unconstrained::objective::*;
+
+    #[test]
+    fn test_cma_es_rosenbrock(){
+        let bounds = Bounds::uniform(4, -5., 5.).unwrap();
+        let x0 = vector_f64(&[-1.2, 1., -1.2, 1.]);
+        let options = GlobalOptions::new().seed(2).function_tolerance(1e-14);
+        let r = cma_es(&Rosenbrock, &x0, &bounds, &CmaEs::new(), &options).unwrap();
+        assert!(r.converged);
+        assert!((&r.x - &Matrix::ones(4, 1)).max_abs_scalar_value() < 1e-5);
+        assert_eq!(r.log.len(), r.iterations + 1);
+        let s = cma_es(&Rosenbrock, &x0, &bounds, &CmaEs::new(), &options).unwrap();
+        assert_eq!(r.x, s.x);
+    }
+
+    #[test]
+    fn test_cma_es_ill_conditioned(){
+        // Rotated ellipsoid with condition number 1e6
+        let f = ValueObjective::new(|x : &MatrixF64| {
+            let u = x[0] + x[1];
+            let v = x[0] - x[1];
+            u * u + 1e6 * v * v
+        });
+        let bounds = Bounds::uniform(2, -3., 3.).unwrap();
+        let options = GlobalOptions::new().seed(9);
+        let r = cma_es(&f, &vector_f64(&[2., 1.]), &bounds, &CmaEs::new(), &options).unwrap();
+        assert!(r.converged);
+        assert!(r.x.max_abs_scalar_value() < 1e-5);
+    }
+
+    #[test]
+    fn test_cma_es_rastrigin(){
+        // A large population helps to escape the local minima
+        let bounds = Bounds::uniform(2, -5.12, 5.12).unwrap();
+        let mut parameters = CmaEs::new();
+        parameters.population = Some(50);
+        parameters.sigma = Some(2.);
+        let options = GlobalOptions::new().seed(4);
+        let r = cma_es(&Rastrigin, &vector_f64(&[3., 3.]), &bounds, &parameters, &options).unwrap();
+        assert!(r.converged);
+        assert!(r.value < 1e-8);
+        assert!(cma_es(&Rastrigin, &vector_f64(&[3., 3.]), &bounds,
+            &CmaEs{sigma : Some(0.), population : None}, &options).is_err());
+    }
+}
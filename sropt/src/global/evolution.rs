@@ -0,0 +1,199 @@
+#![doc="Differential evolution
+
+A population of candidate points is evolved by combining
+differences of its members (Storn and Price, 1997). For each
+member x_i a mutant
+
+v = x_base + F (x_r1 - x_r2)
+
+is formed from distinct random members, where x_base is
+another random member (``Rand1Bin``) or the best member
+(``Best1Bin``). The trial point takes each coordinate from v
+with probability CR (and at least one coordinate) and from
+x_i otherwise. It replaces x_i in the next generation if it is
+not worse.
+
+Mutant coordinates outside the box are moved to a random
+point between x_base and the violated bound. The method stops
+when the values or the positions of the population have
+converged according to ``GlobalOptions``.
+"]
+
+
+// std imports
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use global::options::*;
+use global::search::*;
+
+
+/// Choice of the base vector of the mutation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DEStrategy {
+    /// A random member (DE/rand/1/bin): robust exploration
+    Rand1Bin,
+    /// The best member (DE/best/1/bin): faster but greedier
+    Best1Bin
+}
+
+
+/// Parameters of differential evolution
+#[derive(Debug, Clone, Copy)]
+pub struct DifferentialEvolution {
+    /// The mutation strategy
+    pub strategy : DEStrategy,
+    /// Population size (15 n but at least 10 by default)
+    pub population : Option<usize>,
+    /// The differential weight F in (0, 2]
+    pub weight : f64,
+    /// The crossover probability CR in [0, 1]
+    pub crossover : f64
+}
+
+impl DifferentialEvolution {
+
+    /// DE/rand/1/bin with F = 0.8 and CR = 0.9
+    pub fn new() -> DifferentialEvolution {
+        DifferentialEvolution {
+            strategy : DEStrategy::Rand1Bin,
+            population : None,
+            weight : 0.8,
+            crossover : 0.9
+        }
+    }
+}
+
+impl Default for DifferentialEvolution {
+    fn default() -> DifferentialEvolution {
+        DifferentialEvolution::new()
+    }
+}
+
+
+/// Draws an index in 0..m different from all of the excluded ones
+fn distinct_index<R:Rng>(rng : &mut R, m : usize, excluded : &[usize]) -> usize {
+    loop {
+        let k = rng.gen_range(0, m);
+        if !excluded.contains(&k) {
+            return k;
+        }
+    }
+}
+
+
+/// Minimizes f over the box by differential evolution
+pub fn differential_evolution<O:Objective + ?Sized>(f : &O, bounds : &Bounds,
+    parameters : &DifferentialEvolution, options : &GlobalOptions) -> SRResult<MinimizeResult> {
+    let n = bounds.dim();
+    let m = parameters.population.unwrap_or((15 * n).max(10));
+    if m < 4 || !(parameters.weight > 0. && parameters.weight <= 2.)
+        || !(parameters.crossover >= 0. && parameters.crossover <= 1.) {
+        return Err(SRError::InvalidArgument);
+    }
+    let mut rng = options.rng();
+    let mut tracker = Tracker::new(f);
+    let mut population : Vec<Vec<f64>> = (0..m).map(|_| bounds.sample(&mut rng)).collect();
+    let mut values : Vec<f64> = population.iter().map(|x| tracker.value(x)).collect();
+    tracker.record(0, relative_spread(&population, bounds));
+    let (lower, upper) = (bounds.lower(), bounds.upper());
+    let mut termination = Termination::MaxIterations;
+    let mut iteration = 0;
+    while iteration < options.max_iterations {
+        iteration += 1;
+        let best = (0..m).fold(0, |b, i| if values[i] < values[b] { i } else { b });
+        let mut next = population.clone();
+        for i in 0..m {
+            let base = match parameters.strategy {
+                DEStrategy::Rand1Bin => distinct_index(&mut rng, m, &[i]),
+                DEStrategy::Best1Bin => best
+            };
+            let r1 = distinct_index(&mut rng, m, &[i, base]);
+            let r2 = distinct_index(&mut rng, m, &[i, base, r1]);
+            let forced = rng.gen_range(0, n);
+            let mut trial = population[i].clone();
+            for j in 0..n {
+                if j != forced && rng.gen::<f64>() >= parameters.crossover {
+                    continue;
+                }
+                let b = population[base][j];
+                let mut v = b + parameters.weight * (population[r1][j] - population[r2][j]);
+                if v < lower[j] {
+                    v = lower[j] + rng.gen::<f64>() * (b - lower[j]);
+                }
+                else if v > upper[j] {
+                    v = upper[j] - rng.gen::<f64>() * (upper[j] - b);
+                }
+                trial[j] = v;
+            }
+            let value = tracker.value(&trial);
+            if value <= values[i] {
+                next[i] = trial;
+                values[i] = value;
+            }
+        }
+        population = next;
+        let spread = relative_spread(&population, bounds);
+        tracker.record(iteration, spread);
+        let (lo, hi) = value_range(&values);
+        if options.values_converged(lo, hi) {
+            termination = Termination::FunctionTolerance;
+            break;
+        }
+        if spread <= options.step_tolerance {
+            termination = Termination::StepTolerance;
+            break;
+        }
+    }
+    Ok(tracker.finish(iteration, termination))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use global::functions::*;
+    use unconstrained::objective::ValueObjective;
+
+    #[test]
+    fn test_de_rastrigin(){
+        let bounds = Bounds::uniform(3, -5.12, 5.12).unwrap();
+        let options = GlobalOptions::new().seed(3);
+        for &strategy in [DEStrategy::Rand1Bin, DEStrategy::Best1Bin].iter() {
+            let mut parameters = DifferentialEvolution::new();
+            parameters.strategy = strategy;
+            parameters.crossover = 0.3;
+            let r = differential_evolution(&Rastrigin, &bounds, &parameters, &options).unwrap();
+            assert!(r.converged);
+            assert!(r.value < 1e-8);
+            assert!(r.x.max_abs_scalar_value() < 1e-4);
+            assert!(bounds.contains(&r.x));
+            assert!(r.log.windows(2).all(|w| w[1].value <= w[0].value));
+        }
+    }
+
+    #[test]
+    fn test_de_bounded_minimum(){
+        // The minimum of the sphere shifted outside the box is on its boundary
+        let f = ValueObjective::new(|x : &MatrixF64| (x[0] - 3.) * (x[0] - 3.) + x[1] * x[1]);
+        let bounds = Bounds::uniform(2, -1., 1.).unwrap();
+        let options = GlobalOptions::new().seed(11);
+        let r = differential_evolution(&f, &bounds, &DifferentialEvolution::new(), &options).unwrap();
+        assert!(r.converged);
+        assert!((&r.x - &vector_f64(&[1., 0.])).max_abs_scalar_value() < 1e-4);
+        let mut parameters = DifferentialEvolution::new();
+        parameters.population = Some(3);
+        assert!(differential_evolution(&f, &bounds, &parameters, &options).is_err());
+    }
+}
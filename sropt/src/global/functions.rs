@@ -0,0 +1,76 @@
+#![doc="Multimodal test functions for global optimization
+"]
+
+
+// std imports
+use std::f64::consts::PI;
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+
+
+/// The Rastrigin function
+/// f(x) = 10 n + sum (x[i]^2 - 10 cos(2 pi x[i]))
+/// with a regular grid of local minima and the global
+/// minimum f = 0 at x = 0. Usually searched over [-5.12, 5.12]^n.
+#[derive(Debug, Clone, Copy)]
+pub struct Rastrigin;
+
+impl Objective for Rastrigin {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        let n = x.num_rows();
+        (0..n).fold(10. * n as f64, |s, i| s + x[i] * x[i] - 10. * (2. * PI * x[i]).cos())
+    }
+
+    fn gradient(&self, x : &MatrixF64) -> MatrixF64 {
+        let n = x.num_rows();
+        Matrix::from_iter_cw(n, 1,
+            (0..n).map(|i| 2. * x[i] + 20. * PI * (2. * PI * x[i]).sin()))
+    }
+}
+
+
+/// The Ackley function
+/// f(x) = -20 exp(-0.2 sqrt(mean x[i]^2)) - exp(mean cos(2 pi x[i])) + 20 + e
+/// with a nearly flat outer region, many local minima and the
+/// global minimum f = 0 at x = 0. Usually searched over [-32.768, 32.768]^n.
+#[derive(Debug, Clone, Copy)]
+pub struct Ackley;
+
+impl Objective for Ackley {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        let n = x.num_rows();
+        let k = n as f64;
+        let sqr = (0..n).fold(0., |s, i| s + x[i] * x[i]) / k;
+        let cos = (0..n).fold(0., |s, i| s + (2. * PI * x[i]).cos()) / k;
+        -20. * (-0.2 * sqr.sqrt()).exp() - cos.exp() + 20. + 1f64.exp()
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use unconstrained::objective::central_difference_gradient;
+
+    #[test]
+    fn test_minima(){
+        let zero = Matrix::zeros(3, 1);
+        assert_eq!(Rastrigin.value(&zero), 0.);
+        assert!(Ackley.value(&zero).abs() < 1e-14);
+        // A local minimum of Rastrigin's function near (1, 1, 1)
+        assert!(Rastrigin.value(&Matrix::ones(3, 1)) > 2.9);
+        let x = vector_f64(&[0.3, -1.2, 2.1]);
+        let g = central_difference_gradient(&Rastrigin, &x);
+        assert!((&g - &Rastrigin.gradient(&x)).max_abs_scalar_value() < 1e-6);
+    }
+}
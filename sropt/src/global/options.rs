@@ -0,0 +1,231 @@
+#![doc="Box constraints, options and random numbers shared by
+the global optimizers
+
+The global optimizers report their results as
+``MinimizeResult`` like the local methods in
+``unconstrained``. The log holds one record per iteration
+(generation or temperature level) with the best value found
+so far and a measure of the spread of the search in ``step``.
+"]
+
+
+// std imports
+
+// external imports
+use rand::{Rng, SeedableRng, StdRng};
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Box constraints lower <= x <= upper with finite bounds
+#[derive(Debug, Clone)]
+pub struct Bounds {
+    lower : Vec<f64>,
+    upper : Vec<f64>
+}
+
+impl Bounds {
+
+    /// Creates a box from column vectors of lower and upper bounds.
+    /// The bounds must be finite with lower < upper.
+    pub fn new(lower : &MatrixF64, upper : &MatrixF64) -> SRResult<Bounds> {
+        if !lower.is_col() || !upper.is_col() {
+            return Err(SRError::IsNotAColVector);
+        }
+        let n = lower.num_rows();
+        if n == 0 {
+            return Err(SRError::EmptyMatrix);
+        }
+        if upper.num_rows() != n {
+            return Err(SRError::DimensionsMismatch);
+        }
+        let lower : Vec<f64> = (0..n).map(|i| lower[i]).collect();
+        let upper : Vec<f64> = (0..n).map(|i| upper[i]).collect();
+        for i in 0..n {
+            if !(lower[i].is_finite() && upper[i].is_finite() && lower[i] < upper[i]) {
+                return Err(SRError::InvalidArgument);
+            }
+        }
+        Ok(Bounds {lower : lower, upper : upper})
+    }
+
+    /// Creates the box [lower, upper]^n
+    pub fn uniform(n : usize, lower : f64, upper : f64) -> SRResult<Bounds> {
+        Bounds::new(&Matrix::from_iter_cw(n, 1, (0..n).map(|_| lower)),
+            &Matrix::from_iter_cw(n, 1, (0..n).map(|_| upper)))
+    }
+
+    /// Returns the number of variables
+    pub fn dim(&self) -> usize {
+        self.lower.len()
+    }
+
+    /// Returns the lower bounds
+    pub fn lower(&self) -> &[f64] {
+        &self.lower
+    }
+
+    /// Returns the upper bounds
+    pub fn upper(&self) -> &[f64] {
+        &self.upper
+    }
+
+    /// Returns the width of the box along coordinate i
+    pub fn width(&self, i : usize) -> f64 {
+        self.upper[i] - self.lower[i]
+    }
+
+    /// Returns true if x lies in the box
+    pub fn contains(&self, x : &MatrixF64) -> bool {
+        x.num_rows() == self.dim()
+            && (0..self.dim()).all(|i| self.lower[i] <= x[i] && x[i] <= self.upper[i])
+    }
+
+    /// Projects x onto the box
+    pub fn clip(&self, x : &mut [f64]) {
+        for (i, v) in x.iter_mut().enumerate() {
+            *v = v.max(self.lower[i]).min(self.upper[i]);
+        }
+    }
+
+    /// Returns a uniformly distributed point of the box
+    pub fn sample<R:Rng>(&self, rng : &mut R) -> Vec<f64> {
+        (0..self.dim()).map(|i| self.lower[i] + rng.gen::<f64>() * self.width(i)).collect()
+    }
+
+    /// Verifies a starting point and projects it onto the box
+    pub fn start(&self, x0 : &MatrixF64) -> SRResult<Vec<f64>> {
+        if !x0.is_col() {
+            return Err(SRError::IsNotAColVector);
+        }
+        if x0.num_rows() != self.dim() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        let mut x : Vec<f64> = (0..self.dim()).map(|i| x0[i]).collect();
+        self.clip(&mut x);
+        Ok(x)
+    }
+}
+
+
+/// Options shared by the global optimizers
+#[derive(Debug, Clone, Copy)]
+pub struct GlobalOptions {
+    /// Seed of the random number generator. Without a seed
+    /// the generator is seeded from the thread local generator.
+    pub seed : Option<usize>,
+    /// Stop when the function values of the population (or
+    /// recent iterations) differ by less than
+    /// function_tolerance (1 + |f_best|)
+    pub function_tolerance : f64,
+    /// Stop when the search has contracted to less than
+    /// step_tolerance times the width of the box
+    pub step_tolerance : f64,
+    /// Maximum number of iterations
+    pub max_iterations : usize
+}
+
+impl GlobalOptions {
+
+    /// Default options: no seed, tolerances of 1e-10 and at
+    /// most 1000 iterations
+    pub fn new() -> GlobalOptions {
+        GlobalOptions {
+            seed : None,
+            function_tolerance : 1e-10,
+            step_tolerance : 1e-10,
+            max_iterations : 1000
+        }
+    }
+
+    /// Sets the seed
+    pub fn seed(mut self, seed : usize) -> GlobalOptions {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the function tolerance
+    pub fn function_tolerance(mut self, tolerance : f64) -> GlobalOptions {
+        self.function_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the step tolerance
+    pub fn step_tolerance(mut self, tolerance : f64) -> GlobalOptions {
+        self.step_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of iterations
+    pub fn max_iterations(mut self, max_iterations : usize) -> GlobalOptions {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Returns a generator seeded as configured
+    pub fn rng(&self) -> StdRng {
+        let seed = match self.seed {
+            Some(seed) => seed,
+            None => ::rand::thread_rng().gen()
+        };
+        StdRng::from_seed(&[seed])
+    }
+
+    /// Returns true if the function values in [best, worst]
+    /// meet the function tolerance
+    pub fn values_converged(&self, best : f64, worst : f64) -> bool {
+        worst - best <= self.function_tolerance * (1. + best.abs())
+    }
+}
+
+impl Default for GlobalOptions {
+    fn default() -> GlobalOptions {
+        GlobalOptions::new()
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use global::search::*;
+    use srmatrix::vecops::to_col;
+
+    #[test]
+    fn test_bounds(){
+        let b = Bounds::new(&vector_f64(&[-1., 0.]), &vector_f64(&[1., 2.])).unwrap();
+        assert_eq!(b.dim(), 2);
+        assert_eq!(b.width(1), 2.);
+        let mut x = vec![-3., 1.5];
+        b.clip(&mut x);
+        assert_eq!(x, vec![-1., 1.5]);
+        assert!(b.contains(&to_col(&x)));
+        let mut rng = GlobalOptions::new().seed(7).rng();
+        for _ in 0..100 {
+            assert!(b.contains(&to_col(&b.sample(&mut rng))));
+        }
+        assert!(Bounds::new(&vector_f64(&[1.]), &vector_f64(&[1.])).is_err());
+        assert!(Bounds::uniform(2, 0., f64::INFINITY).is_err());
+        assert!(b.start(&vector_f64(&[0.])).is_err());
+    }
+
+    #[test]
+    fn test_seeded_rng(){
+        let options = GlobalOptions::new().seed(42);
+        let a : Vec<f64> = (0..5).map(|_| standard_normal(&mut options.rng())).collect();
+        let mut rng = options.rng();
+        let b : Vec<f64> = (0..5).map(|_| standard_normal(&mut rng)).collect();
+        let mut rng = options.rng();
+        let c : Vec<f64> = (0..5).map(|_| standard_normal(&mut rng)).collect();
+        assert_eq!(b, c);
+        // A fresh generator repeats the first number
+        assert!(a.iter().all(|&v| v == b[0]));
+    }
+}
@@ -0,0 +1,115 @@
+#![doc="Bookkeeping shared by the global optimizers
+"]
+
+
+// std imports
+use std::f64;
+
+// external imports
+use rand::Rng;
+use rand::distributions::normal::StandardNormal;
+
+// local imports
+use srmatrix::vecops::to_col;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use global::options::Bounds;
+
+
+/// Evaluates the objective and keeps the best point, the
+/// number of evaluations and the iteration log
+pub struct Tracker<'a, O:Objective + ?Sized + 'a> {
+    f : &'a O,
+    evaluations : usize,
+    best_x : Vec<f64>,
+    best_value : f64,
+    log : Vec<IterationRecord>
+}
+
+impl<'a, O:Objective + ?Sized> Tracker<'a, O> {
+
+    /// Starts tracking the evaluations of f
+    pub fn new(f : &'a O) -> Tracker<'a, O> {
+        Tracker {
+            f : f,
+            evaluations : 0,
+            best_x : Vec::new(),
+            best_value : f64::INFINITY,
+            log : Vec::new()
+        }
+    }
+
+    /// Evaluates f at x. NaN values are replaced by infinity
+    /// so that such points are never preferred.
+    pub fn value(&mut self, x : &[f64]) -> f64 {
+        self.evaluations += 1;
+        let v = self.f.value(&to_col(x));
+        let v = if v.is_nan() { f64::INFINITY } else { v };
+        if v < self.best_value || self.best_x.is_empty() {
+            self.best_value = v;
+            self.best_x = x.to_vec();
+        }
+        v
+    }
+
+    /// Returns the smallest value found so far
+    pub fn best_value(&self) -> f64 {
+        self.best_value
+    }
+
+    /// Returns the best point found so far
+    pub fn best_x(&self) -> &[f64] {
+        &self.best_x
+    }
+
+    /// Appends the state after an iteration to the log
+    pub fn record(&mut self, iteration : usize, step : f64) {
+        self.log.push(IterationRecord {
+            iteration : iteration,
+            value : self.best_value,
+            gradient_norm : None,
+            step : step,
+            evaluations : self.evaluations
+        });
+    }
+
+    /// Packs the best point and the log into a result
+    pub fn finish(self, iterations : usize, termination : Termination) -> MinimizeResult {
+        MinimizeResult {
+            x : to_col(&self.best_x),
+            value : self.best_value,
+            gradient_norm : None,
+            iterations : iterations,
+            function_evaluations : self.evaluations,
+            gradient_evaluations : 0,
+            converged : termination.is_converged(),
+            termination : termination,
+            log : self.log
+        }
+    }
+}
+
+
+/// Draws a standard normal random number
+pub fn standard_normal<R:Rng>(rng : &mut R) -> f64 {
+    let StandardNormal(z) = rng.gen::<StandardNormal>();
+    z
+}
+
+
+/// Returns the largest spread of the points along any
+/// coordinate relative to the width of the box
+pub fn relative_spread(points : &[Vec<f64>], bounds : &Bounds) -> f64 {
+    (0..bounds.dim()).fold(0., |s : f64, i| {
+        let (lo, hi) = points.iter().fold((f64::INFINITY, f64::NEG_INFINITY),
+            |(lo, hi), p| (lo.min(p[i]), hi.max(p[i])));
+        s.max((hi - lo) / bounds.width(i))
+    })
+}
+
+
+/// Returns the smallest and largest of the values
+pub fn value_range(values : &[f64]) -> (f64, f64) {
+    values.iter().fold((f64::INFINITY, f64::NEG_INFINITY),
+        |(lo, hi), &v| (lo.min(v), hi.max(v)))
+}
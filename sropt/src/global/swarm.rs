@@ -0,0 +1,168 @@
+#![doc="Particle swarm optimization
+
+Each particle moves with a velocity that is pulled towards
+its own best position p_i and the best position g of the
+swarm (Kennedy and Eberhart, 1995):
+
+v = w v + c1 r1 (p_i - x) + c2 r2 (g - x),  x = x + v
+
+with uniform random numbers r1, r2 drawn per coordinate. The
+default coefficients w = 0.7298 and c1 = c2 = 1.49618 are the
+constriction coefficients of Clerc and Kennedy (2002) which
+make the swarm contract. Velocities are limited to the width
+of the box; a particle leaving the box is stopped at its
+boundary. The method stops when the values or the positions
+of the swarm have converged according to ``GlobalOptions``.
+"]
+
+
+// std imports
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use global::options::*;
+use global::search::*;
+
+
+/// Parameters of particle swarm optimization
+#[derive(Debug, Clone, Copy)]
+pub struct ParticleSwarm {
+    /// Number of particles (10 + 2 sqrt(n) rounded, at least 10, by default)
+    pub particles : Option<usize>,
+    /// The inertia weight w
+    pub inertia : f64,
+    /// The cognitive coefficient c1
+    pub cognitive : f64,
+    /// The social coefficient c2
+    pub social : f64
+}
+
+impl ParticleSwarm {
+
+    /// Constriction coefficients w = 0.7298, c1 = c2 = 1.49618
+    pub fn new() -> ParticleSwarm {
+        ParticleSwarm {
+            particles : None,
+            inertia : 0.7298,
+            cognitive : 1.49618,
+            social : 1.49618
+        }
+    }
+}
+
+impl Default for ParticleSwarm {
+    fn default() -> ParticleSwarm {
+        ParticleSwarm::new()
+    }
+}
+
+
+/// Minimizes f over the box by particle swarm optimization
+pub fn particle_swarm<O:Objective + ?Sized>(f : &O, bounds : &Bounds,
+    parameters : &ParticleSwarm, options : &GlobalOptions) -> SRResult<MinimizeResult> {
+    let n = bounds.dim();
+    let m = parameters.particles.unwrap_or(10 + (2. * (n as f64).sqrt()).round() as usize);
+    if m < 2 || !(parameters.inertia >= 0. && parameters.cognitive >= 0. && parameters.social >= 0.) {
+        return Err(SRError::InvalidArgument);
+    }
+    let mut rng = options.rng();
+    let mut tracker = Tracker::new(f);
+    let (lower, upper) = (bounds.lower(), bounds.upper());
+    let mut positions : Vec<Vec<f64>> = (0..m).map(|_| bounds.sample(&mut rng)).collect();
+    let mut velocities : Vec<Vec<f64>> = positions.iter().map(|x| {
+        let y = bounds.sample(&mut rng);
+        (0..n).map(|j| 0.5 * (y[j] - x[j])).collect()
+    }).collect();
+    let mut values : Vec<f64> = positions.iter().map(|x| tracker.value(x)).collect();
+    let mut best_positions = positions.clone();
+    let mut best_values = values.clone();
+    tracker.record(0, relative_spread(&positions, bounds));
+    let mut termination = Termination::MaxIterations;
+    let mut iteration = 0;
+    while iteration < options.max_iterations {
+        iteration += 1;
+        let g = tracker.best_x().to_vec();
+        for i in 0..m {
+            for j in 0..n {
+                let width = bounds.width(j);
+                let x = positions[i][j];
+                let v = parameters.inertia * velocities[i][j]
+                    + parameters.cognitive * rng.gen::<f64>() * (best_positions[i][j] - x)
+                    + parameters.social * rng.gen::<f64>() * (g[j] - x);
+                let v = v.max(-width).min(width);
+                let y = x + v;
+                if y < lower[j] || y > upper[j] {
+                    positions[i][j] = y.max(lower[j]).min(upper[j]);
+                    velocities[i][j] = 0.;
+                }
+                else {
+                    positions[i][j] = y;
+                    velocities[i][j] = v;
+                }
+            }
+            values[i] = tracker.value(&positions[i]);
+            if values[i] < best_values[i] {
+                best_values[i] = values[i];
+                best_positions[i] = positions[i].clone();
+            }
+        }
+        let spread = relative_spread(&positions, bounds);
+        tracker.record(iteration, spread);
+        let (lo, hi) = value_range(&values);
+        if options.values_converged(lo.min(tracker.best_value()), hi) {
+            termination = Termination::FunctionTolerance;
+            break;
+        }
+        if spread <= options.step_tolerance {
+            termination = Termination::StepTolerance;
+            break;
+        }
+    }
+    Ok(tracker.finish(iteration, termination))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use global::functions::*;
+    use unconstrained::objective::Rosenbrock;
+
+    #[test]
+    fn test_pso_ackley(){
+        let bounds = Bounds::uniform(2, -32.768, 32.768).unwrap();
+        let mut parameters = ParticleSwarm::new();
+        parameters.particles = Some(30);
+        let options = GlobalOptions::new().seed(5);
+        let r = particle_swarm(&Ackley, &bounds, &parameters, &options).unwrap();
+        assert!(r.converged);
+        assert!(r.value < 1e-6);
+        assert!(r.x.max_abs_scalar_value() < 1e-6);
+        assert!(r.log.windows(2).all(|w| w[1].value <= w[0].value));
+        let s = particle_swarm(&Ackley, &bounds, &parameters, &options).unwrap();
+        assert_eq!(r.x, s.x);
+    }
+
+    #[test]
+    fn test_pso_rosenbrock(){
+        let bounds = Bounds::uniform(2, -2., 2.).unwrap();
+        let options = GlobalOptions::new().seed(8);
+        let r = particle_swarm(&Rosenbrock, &bounds, &ParticleSwarm::new(), &options).unwrap();
+        assert!(r.converged);
+        assert!((&r.x - &vector_f64(&[1., 1.])).max_abs_scalar_value() < 1e-3);
+        let mut parameters = ParticleSwarm::new();
+        parameters.inertia = -1.;
+        assert!(particle_swarm(&Rosenbrock, &bounds, &parameters, &options).is_err());
+    }
+}
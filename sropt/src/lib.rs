@@ -1,14 +1,133 @@
 #![doc="Optimization
 "]
+
+extern crate num;
+extern crate rand;
+extern crate sralgebra;
+extern crate srmatrix;
+extern crate srlinalg;
+extern crate srstat;
+
 pub mod lp {
 #![doc="Linear programming
+
+* ``problem``: linear programs with general bounds and
+  their solutions
+* ``sparse``: sparse constraint matrices
+* ``simplex``: revised simplex method with bounded variables
+* ``interior``: primal-dual interior point method
+* ``model``: model building interface
+* ``gomory``: Gomory mixed integer cuts
+* ``mip``: mixed integer programming by branch and bound
 "]
+    pub mod sparse;
+    pub mod problem;
+    pub mod simplex;
+    pub mod interior;
+    pub mod model;
+    pub mod gomory;
+    pub mod mip;
 }
 pub mod ls {
 #![doc="Least squares
+
+* ``linear``: ordinary and weighted least squares via QR
+* ``ridge``: Tikhonov regularization with GCV based
+  selection of the regularization parameter
+* ``nnls``: non-negative least squares (Lawson-Hanson)
+* ``constrained``: equality constrained least squares
+* ``tls``: total least squares via SVD
+* ``nonlinear``: nonlinear least squares by Gauss-Newton and
+  Levenberg-Marquardt with optional bounds
+* ``curve_fit``: model fitting with parameter covariances
 "]
+    pub mod result;
+    pub mod linear;
+    pub mod ridge;
+    pub mod nnls;
+    pub mod constrained;
+    pub mod tls;
+    pub mod nonlinear;
+    pub mod curve_fit;
 }
 pub mod cvx {
 #![doc="Convex optimization
+
+* ``options``: tolerances and KKT residuals
+* ``qp``: convex quadratic programs
+* ``active_set``: primal active set method for QPs
+* ``admm``: operator splitting (ADMM) for QPs
+* ``socp``: interior point method for second order
+  cone programs
+* ``prox``: proximal operators
+* ``proximal``: proximal gradient method and FISTA
 "]
+    pub mod options;
+    pub mod qp;
+    pub mod active_set;
+    pub mod admm;
+    pub mod socp;
+    pub mod prox;
+    pub mod proximal;
 }
+pub mod unconstrained {
+#![doc="Unconstrained nonlinear optimization
+
+* ``objective``: the ``Objective`` trait and objective
+  functions
+* ``options``: stopping criteria, results and iteration logs
+* ``line_search``: Armijo backtracking and strong Wolfe
+  line searches
+* ``gradient_descent``: steepest descent
+* ``bfgs``: the BFGS quasi-Newton method
+* ``lbfgs``: limited memory BFGS
+* ``newton_cg``: truncated Newton method
+* ``nelder_mead``: derivative free simplex method
+"]
+    pub mod objective;
+    pub mod options;
+    pub mod line_search;
+    mod descent;
+    pub mod gradient_descent;
+    pub mod bfgs;
+    pub mod lbfgs;
+    pub mod newton_cg;
+    pub mod nelder_mead;
+}
+pub mod roots {
+#![doc="Roots and one dimensional minimization
+
+* ``scalar``: bisection, Brent, Ridder, Newton and secant
+  methods for f(x) = 0
+* ``minimize``: golden section and Brent minimizers on an
+  interval
+* ``systems``: Newton and Broyden methods for F(x) = 0
+"]
+    pub mod scalar;
+    pub mod minimize;
+    pub mod systems;
+}
+pub mod global {
+#![doc="Global optimization over boxes
+
+* ``options``: box constraints, shared options and seeding
+* ``annealing``: simulated annealing
+* ``evolution``: differential evolution
+* ``swarm``: particle swarm optimization
+* ``cma_es``: covariance matrix adaptation evolution strategy
+* ``functions``: multimodal test functions
+
+All methods take an ``Objective`` and report a
+``MinimizeResult`` like the local minimizers. Runs with the
+same seed are reproducible.
+"]
+    pub mod options;
+    mod search;
+    pub mod annealing;
+    pub mod evolution;
+    pub mod swarm;
+    pub mod cma_es;
+    pub mod functions;
+}
+
+pub mod api;
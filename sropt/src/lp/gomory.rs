@@ -0,0 +1,234 @@
+#![doc="Gomory mixed integer cuts
+
+Let x_k be an integer variable which is basic with a
+fractional value in the optimal simplex tableau. Shifting
+every non-basic variable z_j to its active bound with
+y_j = z_j - l_j (at the lower bound) or y_j = u_j - z_j (at the
+upper bound) turns its tableau row into
+
+x_k + sum_j a_j y_j = v,  y >= 0.
+
+With f_0 = v - floor(v) and f_j = a_j - floor(a_j) every point
+with integral x_k satisfies the Gomory mixed integer cut
+
+sum g_j y_j >= 1
+
+where g_j = f_j / f_0 or (1 - f_j) / (1 - f_0), whichever is
+smaller, for integer y_j and g_j = a_j / f_0 (a_j > 0) or
+-a_j / (1 - f_0) (a_j < 0) for continuous y_j. The current
+vertex has y = 0 and violates the cut. A y_j is integer when
+z_j is an integer variable with an integral bound; the slacks
+are treated as continuous. Substituting y and s = b - A x
+gives a cut pi' x >= pi_0 on the structural variables.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use lp::problem::*;
+use lp::simplex::*;
+
+
+/// Rows whose basic variable is this close to an integer give
+/// numerically unreliable cuts and are skipped
+const MIN_FRACTION : f64 = 1e-3;
+/// Largest accepted ratio of the largest to the smallest
+/// non-zero coefficient of a cut
+const MAX_DYNAMISM : f64 = 1e8;
+
+
+/// A cut pi' x >= pi_0
+#[derive(Debug, Clone)]
+pub struct Cut {
+    /// The coefficients pi
+    pub coefficients : Vec<f64>,
+    /// The right hand side pi_0
+    pub rhs : f64
+}
+
+impl Cut {
+
+    /// Returns pi_0 - pi' x which is positive if x violates the cut
+    pub fn violation(&self, x : &MatrixF64) -> f64 {
+        self.coefficients.iter().enumerate().fold(self.rhs, |s, (j, p)| s - p * x[j])
+    }
+
+    /// Adds the cut to a linear program as a constraint
+    pub fn add_to(&self, lp : &mut LinearProgram) -> SRResult<()> {
+        lp.add_constraint(&self.coefficients, ConstraintType::GreaterEqual, self.rhs)
+    }
+}
+
+
+/// Returns the fractional part v - floor(v)
+fn fraction(v : f64) -> f64 {
+    v - v.floor()
+}
+
+
+/// Derives the Gomory mixed integer cut from a tableau row.
+/// Returns None if the basic variable is (nearly) integral or
+/// a free non-basic variable appears in the row.
+pub fn gomory_cut(lp : &LinearProgram, integer : &[bool], tableau : &Tableau,
+    row : &TableauRow) -> Option<Cut> {
+    let n = lp.num_variables();
+    let f0 = fraction(row.value);
+    if !(MIN_FRACTION..=1. - MIN_FRACTION).contains(&f0) {
+        return None;
+    }
+    let mut pi = vec![0.; n];
+    // The cut is pi' x + constant >= 1
+    let mut constant = 0.;
+    for (j, &a) in row.coefficients.iter().enumerate() {
+        if a == 0. || tableau.status[j] == VariableStatus::Basic
+            || tableau.lower[j] == tableau.upper[j] {
+            continue;
+        }
+        // y_j = sign (z_j - bound)
+        let (sign, bound) = match tableau.status[j] {
+            VariableStatus::AtLower => (1., tableau.lower[j]),
+            VariableStatus::AtUpper => (-1., tableau.upper[j]),
+            _ => return None
+        };
+        let a = sign * a;
+        let is_integer = j < n && integer[j] && bound == bound.round();
+        let g = if is_integer {
+            let f = fraction(a);
+            (f / f0).min((1. - f) / (1. - f0))
+        }
+        else if a > 0. {
+            a / f0
+        }
+        else {
+            -a / (1. - f0)
+        };
+        if g == 0. {
+            continue;
+        }
+        let w = g * sign;
+        constant -= w * bound;
+        if j < n {
+            pi[j] += w;
+        }
+        else {
+            // s_i = b_i - a_i' x
+            let i = j - n;
+            constant += w * lp.b[i];
+            for (k, p) in pi.iter_mut().enumerate() {
+                *p -= w * lp.a.get(i, k).unwrap();
+            }
+        }
+    }
+    let (small, large) = pi.iter().filter(|p| **p != 0.)
+        .fold((f64::INFINITY, 0f64), |(s, l), p| (s.min(p.abs()), l.max(p.abs())));
+    if large == 0. || large > MAX_DYNAMISM * small {
+        return None;
+    }
+    let rhs = 1. - constant;
+    Some(Cut {
+        coefficients : pi,
+        // Slightly relaxed against round off
+        rhs : rhs - 1e-9 * rhs.abs().max(1.)
+    })
+}
+
+
+/// Generates up to max_cuts Gomory mixed integer cuts from the rows
+/// of fractional integer variables, most fractional rows first.
+/// Only cuts violated by x by more than tolerance are returned.
+pub fn gomory_cuts(lp : &LinearProgram, integer : &[bool], tableau : &Tableau,
+    x : &MatrixF64, tolerance : f64, max_cuts : usize) -> Vec<Cut> {
+    let n = lp.num_variables();
+    let mut rows : Vec<&TableauRow> = tableau.rows.iter()
+        .filter(|r| r.basic < n && integer[r.basic]).collect();
+    let distance = |r : &TableauRow| {
+        let f = fraction(r.value);
+        f.min(1. - f)
+    };
+    rows.sort_by(|p, q| distance(q).partial_cmp(&distance(p)).unwrap_or(::std::cmp::Ordering::Equal));
+    rows.into_iter()
+        .filter_map(|r| gomory_cut(lp, integer, tableau, r))
+        .filter(|c| c.violation(x) > tolerance)
+        .take(max_cuts)
+        .collect()
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_gomory_pure_integer(){
+        // max x + y s.t. -x + y <= 1, 3 x + 2 y <= 12, 2 x + 3 y <= 12
+        // The LP optimum (2.4, 2.4) is cut off, the integer points are kept
+        let c = vector_f64(&[-1., -1.]);
+        let a = matrix_rw_f64(3, 2, &[-1., 1., 3., 2., 2., 3.]);
+        let b = vector_f64(&[1., 12., 12.]);
+        let lp = LinearProgram::new(&c, &a, &[ConstraintType::LessEqual; 3], &b).unwrap();
+        let (s, t) = simplex_tableau(&lp).unwrap();
+        let integer = [true, true];
+        let cuts = gomory_cuts(&lp, &integer, &t, &s.x, 1e-6, 10);
+        assert_eq!(cuts.len(), 2);
+        for cut in cuts.iter() {
+            assert!(cut.violation(&s.x) > 0.1);
+            for x in 0..5 {
+                for y in 0..5 {
+                    let p = vector_f64(&[x as f64, y as f64]);
+                    if lp.is_feasible(&p, 0.) {
+                        assert!(cut.violation(&p) <= 1e-9, "{:?} cuts off {:?}", cut, p);
+                    }
+                }
+            }
+        }
+        // Adding the cuts improves the bound
+        let mut tightened = lp.clone();
+        for cut in cuts.iter() {
+            cut.add_to(&mut tightened).unwrap();
+        }
+        let r = simplex(&tightened).unwrap();
+        assert!(r.objective > s.objective + 0.1);
+        assert!(r.objective <= -4. + 1e-9);
+    }
+
+    #[test]
+    fn test_gomory_mixed(){
+        // min -x - 2 z s.t. x - z >= -0.5, x + z <= 2.5, 0 <= x <= 2, z >= 0
+        // with integer x and continuous z
+        let c = vector_f64(&[-1., -2.]);
+        let a = matrix_rw_f64(2, 2, &[1., -1., 1., 1.]);
+        let b = vector_f64(&[-0.5, 2.5]);
+        let types = [ConstraintType::GreaterEqual, ConstraintType::LessEqual];
+        let mut lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        lp.set_bounds(&[0., 0.], &[2., f64::INFINITY]).unwrap();
+        let (s, t) = simplex_tableau(&lp).unwrap();
+        assert!((s.x[0] - 1.).abs() < 1e-9);
+        assert!((s.x[1] - 1.5).abs() < 1e-9);
+        // x is integral here: no cut
+        assert!(gomory_cuts(&lp, &[true, false], &t, &s.x, 1e-6, 10).is_empty());
+        // With the first constraint moved the vertex becomes fractional
+        lp.b = vector_f64(&[-0.8, 2.5]);
+        let (s, t) = simplex_tableau(&lp).unwrap();
+        assert!((s.x[0] - 0.85).abs() < 1e-9);
+        let cuts = gomory_cuts(&lp, &[true, false], &t, &s.x, 1e-6, 10);
+        assert_eq!(cuts.len(), 1);
+        assert!(cuts[0].violation(&s.x) > 1e-3);
+        // Feasible points with integral x satisfy the cut
+        for &x in [0., 1., 2.].iter() {
+            for k in 0..40 {
+                let p = vector_f64(&[x, k as f64 * 0.1]);
+                if lp.is_feasible(&p, 0.) {
+                    assert!(cuts[0].violation(&p) <= 1e-9);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,591 @@
+#![doc="Primal-dual interior point method
+
+The linear program is first converted to the standard form
+
+min c' x subject to A x = b, x >= 0
+
+by shifting, mirroring or splitting variables according to
+their bounds and adding slack variables for inequalities
+and finite upper bounds.
+
+The standard form is solved through its homogeneous self
+dual embedding
+
+A x - b tau = 0,
+A' y + s - c tau = 0,
+b' y - c' x - kappa = 0,
+x, s, tau, kappa >= 0,
+
+following the central path with Mehrotra's predictor-corrector
+scheme. Linearly dependent rows of A are removed beforehand;
+if their right hand sides are inconsistent the program is
+infeasible. At a solution with tau > 0, (x, y, s) / tau is an
+optimal primal dual pair. If tau vanishes while kappa stays
+positive, the iterates converge to a certificate of primal
+infeasibility (b' y > 0, A' y <= 0) or of dual infeasibility
+(c' x < 0, A x = 0, x >= 0) of the problem. The latter only
+shows that the program is unbounded once it is known to be
+feasible, which is checked by solving it with a zero objective.
+
+Each iteration solves two systems with the normal matrix
+A D A' where D = diag(x / s) by a Cholesky factorization.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srmatrix::vecops::{dot, norm2, mul, mul_t, to_vec, to_col};
+use srlinalg::cholesky::CholeskyDecomposition;
+use srlinalg::factorization::Factorization;
+use lp::problem::*;
+
+
+/// Relative tolerance of the optimality conditions
+const TOLERANCE : f64 = 1e-9;
+/// Fraction of the step to the boundary taken in each iteration
+const STEP_FRACTION : f64 = 0.99;
+/// Maximum number of interior point iterations
+const MAX_ITERATIONS : usize = 200;
+/// Relative size below which pivots of the normal matrix are
+/// treated as zero
+const PIVOT_TOLERANCE : f64 = 1e-30;
+
+
+/// How a variable of the original program is recovered from
+/// the standard form variables
+#[derive(Debug, Clone, Copy)]
+enum Recovery {
+    /// x = lower + x'
+    Shift(usize, f64),
+    /// x = upper - x'
+    Mirror(usize, f64),
+    /// x = x+ - x-
+    Split(usize, usize)
+}
+
+
+/// A linear program in standard form
+struct StandardForm {
+    a : MatrixF64,
+    b : Vec<f64>,
+    c : Vec<f64>,
+    recovery : Vec<Recovery>,
+    /// Rows of the standard form kept after removing
+    /// dependent ones
+    rows : Vec<usize>
+}
+
+
+impl StandardForm {
+
+    fn new(lp : &LinearProgram) -> SRResult<StandardForm> {
+        let m = lp.num_constraints();
+        let n = lp.num_variables();
+        // Standard form columns: structural, bound slacks, row slacks
+        let mut columns : Vec<Vec<(usize, f64)>> = Vec::new();
+        let mut c = Vec::new();
+        let mut b : Vec<f64> = (0..m).map(|i| lp.b[i]).collect();
+        let mut recovery = Vec::with_capacity(n);
+        let mut upper_rows : Vec<(usize, f64)> = Vec::new();
+        for j in 0..n {
+            let column : Vec<(usize, f64)> = (0..m)
+                .map(|i| (i, lp.a.get(i, j).unwrap()))
+                .filter(|&(_, v)| v != 0.).collect();
+            let lower = lp.lower[j];
+            let upper = lp.upper[j];
+            if lower > f64::NEG_INFINITY {
+                for &(i, v) in column.iter() {
+                    b[i] -= v * lower;
+                }
+                recovery.push(Recovery::Shift(columns.len(), lower));
+                if upper < f64::INFINITY {
+                    upper_rows.push((columns.len(), upper - lower));
+                }
+                columns.push(column);
+                c.push(lp.c[j]);
+            }
+            else if upper < f64::INFINITY {
+                for &(i, v) in column.iter() {
+                    b[i] -= v * upper;
+                }
+                recovery.push(Recovery::Mirror(columns.len(), upper));
+                columns.push(column.iter().map(|&(i, v)| (i, -v)).collect());
+                c.push(-lp.c[j]);
+            }
+            else {
+                recovery.push(Recovery::Split(columns.len(), columns.len() + 1));
+                columns.push(column.clone());
+                columns.push(column.iter().map(|&(i, v)| (i, -v)).collect());
+                c.push(lp.c[j]);
+                c.push(-lp.c[j]);
+            }
+        }
+        // Rows x' + w = upper - lower for doubly bounded variables
+        for (k, &(col, width)) in upper_rows.iter().enumerate() {
+            let row = m + k;
+            columns[col].push((row, 1.));
+            columns.push(vec![(row, 1.)]);
+            c.push(0.);
+            b.push(width);
+        }
+        for i in 0..m {
+            match lp.constraint_types[i] {
+                ConstraintType::LessEqual => columns.push(vec![(i, 1.)]),
+                ConstraintType::GreaterEqual => columns.push(vec![(i, -1.)]),
+                ConstraintType::Equal => continue
+            }
+            c.push(0.);
+        }
+        let mut dense = vec![vec![0.; columns.len()]; b.len()];
+        for (j, column) in columns.iter().enumerate() {
+            for &(i, v) in column.iter() {
+                dense[i][j] = v;
+            }
+        }
+        let rows = independent_rows(&dense, &b)?;
+        let mut a : MatrixF64 = Matrix::zeros(rows.len(), columns.len());
+        for (k, &i) in rows.iter().enumerate() {
+            for (j, &v) in dense[i].iter().enumerate() {
+                if v != 0. {
+                    a.set(k, j, v);
+                }
+            }
+        }
+        let b = rows.iter().map(|&i| b[i]).collect();
+        Ok(StandardForm {a : a, b : b, c : c, recovery : recovery, rows : rows})
+    }
+
+    /// Maps a standard form point back to the original variables
+    fn recover(&self, x : &[f64]) -> MatrixF64 {
+        let values : Vec<f64> = self.recovery.iter().map(|r| match *r {
+            Recovery::Shift(k, l) => l + x[k],
+            Recovery::Mirror(k, u) => u - x[k],
+            Recovery::Split(p, q) => x[p] - x[q]
+        }).collect();
+        Matrix::from_iter_cw(values.len(), 1, values.into_iter())
+    }
+
+    /// Maps the multipliers of the kept rows to the m constraints
+    /// of the original program. Removed rows get zero multipliers.
+    fn recover_duals(&self, y : &[f64], m : usize) -> MatrixF64 {
+        let mut duals = vec![0.; m];
+        for (k, &i) in self.rows.iter().enumerate() {
+            if i < m {
+                duals[i] = y[k];
+            }
+        }
+        Matrix::from_iter_cw(m, 1, duals.into_iter())
+    }
+}
+
+
+/// Returns the rows of the system A x = b which are linearly
+/// independent of the rows before them.
+///
+/// Returns ``Infeasible`` if a dependent row has an inconsistent
+/// right hand side.
+fn independent_rows(a : &[Vec<f64>], b : &[f64]) -> SRResult<Vec<usize>> {
+    let b_scale = 1. + b.iter().fold(0., |s : f64, v| s.max(v.abs()));
+    // Reduced rows with their pivot columns, normalized to one
+    // at the pivot
+    let mut basis : Vec<(usize, Vec<f64>, f64)> = Vec::new();
+    let mut rows = Vec::new();
+    for i in 0..b.len() {
+        let mut row = a[i].clone();
+        let mut rhs = b[i];
+        let scale = row.iter().fold(0., |s : f64, v| s.max(v.abs()));
+        for &(p, ref pivot_row, pivot_rhs) in basis.iter() {
+            let factor = row[p];
+            if factor != 0. {
+                for (v, w) in row.iter_mut().zip(pivot_row.iter()) {
+                    *v -= factor * w;
+                }
+                rhs -= factor * pivot_rhs;
+            }
+        }
+        let (p, largest) = row.iter().enumerate().fold((0, 0.), |(p, m), (j, v)| {
+            if v.abs() > m { (j, v.abs()) } else { (p, m) }
+        });
+        if largest <= TOLERANCE * scale {
+            if rhs.abs() > TOLERANCE * b_scale {
+                return Err(SRError::Infeasible);
+            }
+            continue;
+        }
+        let pivot = row[p];
+        for v in row.iter_mut() {
+            *v /= pivot;
+        }
+        basis.push((p, row, rhs / pivot));
+        rows.push(i);
+    }
+    Ok(rows)
+}
+
+
+/// Solves M v = rhs with the factorization of the normal matrix
+fn solve_normal(factor : &CholeskyDecomposition, rhs : &[f64]) -> Vec<f64> {
+    // The factorization always matches the size of rhs
+    to_vec(&factor.solve(&to_col(rhs)).unwrap())
+}
+
+
+/// Largest step in [0, 1] keeping v + alpha dv non-negative
+fn max_step(v : &[f64], dv : &[f64]) -> f64 {
+    v.iter().zip(dv.iter()).fold(1., |alpha, (&x, &dx)| {
+        if dx < 0. && -x / dx < alpha { -x / dx } else { alpha }
+    })
+}
+
+
+/// Current point of the homogeneous embedding
+struct Point {
+    x : Vec<f64>,
+    y : Vec<f64>,
+    s : Vec<f64>,
+    tau : f64,
+    kappa : f64
+}
+
+
+/// Residuals of the embedding at the current point
+struct Residuals {
+    /// b tau - A x
+    primal : Vec<f64>,
+    /// c tau - A' y - s
+    dual : Vec<f64>,
+    /// kappa + c' x - b' y
+    gap : f64
+}
+
+
+/// Cholesky factorization of the normal matrix M = A D A'
+/// together with the solution of M p = A D c + b
+struct NormalSystem {
+    factor : CholeskyDecomposition,
+    ad_p : Vec<f64>
+}
+
+
+/// Search direction in the homogeneous embedding
+struct Direction {
+    x : Vec<f64>,
+    y : Vec<f64>,
+    s : Vec<f64>,
+    tau : f64,
+    kappa : f64
+}
+
+
+/// Solves the Newton system of the embedding for the given
+/// right hand sides of the complementarity equations
+///
+/// S dx + X ds = r_xs, kappa dtau + tau dkappa = r_tk
+///
+/// with the residuals scaled by eta.
+fn newton_direction(sf : &StandardForm, p : &Point, system : &NormalSystem,
+    r : &Residuals, eta : f64, r_xs : &[f64], r_tk : f64) -> Direction {
+    let n = p.x.len();
+    let m = p.y.len();
+    let (rp, rd, rg) = (&r.primal, &r.dual, r.gap);
+    let ad_p = &system.ad_p;
+    // q solves M q = eta r_p + A D (eta r_d - X^-1 r_xs)
+    let w : Vec<f64> = (0..n).map(|j| p.x[j] / p.s[j] * (eta * rd[j]
+        - r_xs[j] / p.x[j])).collect();
+    let aw = mul(&sf.a, &w);
+    let rhs : Vec<f64> = (0..m).map(|i| eta * rp[i] + aw[i]).collect();
+    let q = solve_normal(&system.factor, &rhs);
+    // dx = dtau u + v
+    let atp = mul_t(&sf.a, ad_p);
+    let atq = mul_t(&sf.a, &q);
+    let u : Vec<f64> = (0..n).map(|j| p.x[j] / p.s[j] * (atp[j] - sf.c[j])).collect();
+    let v : Vec<f64> = (0..n).map(|j| p.x[j] / p.s[j] * (atq[j] - eta * rd[j]
+        + r_xs[j] / p.x[j])).collect();
+    let numerator = eta * rg + dot(&sf.c, &v) - dot(&sf.b, &q) + r_tk / p.tau;
+    let denominator = -dot(&sf.c, &u) + dot(&sf.b, ad_p) + p.kappa / p.tau;
+    let dtau = numerator / denominator;
+    let dx : Vec<f64> = (0..n).map(|j| dtau * u[j] + v[j]).collect();
+    let dy : Vec<f64> = (0..m).map(|i| dtau * ad_p[i] + q[i]).collect();
+    let ds : Vec<f64> = (0..n).map(|j| (r_xs[j] - p.s[j] * dx[j]) / p.x[j]).collect();
+    let dkappa = (r_tk - p.kappa * dtau) / p.tau;
+    Direction {x : dx, y : dy, s : ds, tau : dtau, kappa : dkappa}
+}
+
+
+/// Largest step keeping the non-negative variables positive
+fn step_length(p : &Point, d : &Direction) -> f64 {
+    let mut alpha = max_step(&p.x, &d.x);
+    let beta = max_step(&p.s, &d.s);
+    if beta < alpha {
+        alpha = beta;
+    }
+    let gamma = max_step(&[p.tau, p.kappa], &[d.tau, d.kappa]);
+    if gamma < alpha { gamma } else { alpha }
+}
+
+
+#[doc="Solves a linear program by the homogeneous primal-dual
+interior point method.
+
+Returns ``Infeasible`` or ``Unbounded`` when the iterates
+approach the corresponding certificate and ``NotConverged``
+if neither an optimal point nor a certificate has been found
+within the iteration limit.
+
+The returned point lies in the interior of the optimal face
+and is generally not a vertex when the optimum is not unique.
+"]
+pub fn interior_point(lp : &LinearProgram) -> SRResult<LPSolution> {
+    let m = lp.num_constraints();
+    let sf = StandardForm::new(lp)?;
+    let ms = sf.b.len();
+    let n = sf.c.len();
+    let mut p = Point {
+        x : vec![1.; n],
+        y : vec![0.; ms],
+        s : vec![1.; n],
+        tau : 1.,
+        kappa : 1.
+    };
+    let norm_b = norm2(&sf.b);
+    let norm_c = norm2(&sf.c);
+    for iteration in 0..MAX_ITERATIONS {
+        // Residuals of the embedding
+        let ax = mul(&sf.a, &p.x);
+        let aty = mul_t(&sf.a, &p.y);
+        let cx = dot(&sf.c, &p.x);
+        let by = dot(&sf.b, &p.y);
+        let r = Residuals {
+            primal : (0..ms).map(|i| sf.b[i] * p.tau - ax[i]).collect(),
+            dual : (0..n).map(|j| sf.c[j] * p.tau - aty[j] - p.s[j]).collect(),
+            gap : p.kappa + cx - by
+        };
+        let mu = (dot(&p.x, &p.s) + p.tau * p.kappa) / (n as f64 + 1.);
+        // Optimality
+        if norm2(&r.primal) <= TOLERANCE * (1. + norm_b) * p.tau
+            && norm2(&r.dual) <= TOLERANCE * (1. + norm_c) * p.tau
+            && (cx - by).abs() <= TOLERANCE * (p.tau + by.abs()) {
+            let xs : Vec<f64> = p.x.iter().map(|v| v / p.tau).collect();
+            let x = sf.recover(&xs);
+            let ys : Vec<f64> = p.y.iter().map(|v| v / p.tau).collect();
+            return Ok(LPSolution::new(lp, x, sf.recover_duals(&ys, m), iteration));
+        }
+        // Infeasibility certificates
+        if p.tau < TOLERANCE * p.kappa.max(1.) || mu < TOLERANCE * TOLERANCE {
+            let aty_s : Vec<f64> = (0..n).map(|j| aty[j] + p.s[j]).collect();
+            if by > 0. && norm2(&aty_s) <= TOLERANCE * by * 1e3 {
+                return Err(SRError::Infeasible);
+            }
+            if cx < 0. && norm2(&ax) <= TOLERANCE * -cx * 1e3 {
+                // The ray only proves unboundedness of a feasible program
+                let mut feasibility = lp.clone();
+                feasibility.c = Matrix::zeros(lp.num_variables(), 1);
+                return match interior_point(&feasibility) {
+                    Ok(_) => Err(SRError::Unbounded),
+                    Err(e) => Err(e)
+                };
+            }
+        }
+        // Normal matrix M = A D A'
+        let mut normal : MatrixF64 = Matrix::zeros(ms, ms);
+        for i in 0..ms {
+            for k in 0..(i + 1) {
+                let mut v = 0.;
+                for j in 0..n {
+                    let aij = sf.a.get(i, j).unwrap();
+                    if aij != 0. {
+                        v += aij * p.x[j] / p.s[j] * sf.a.get(k, j).unwrap();
+                    }
+                }
+                normal.set(i, k, v);
+                normal.set(k, i, v);
+            }
+        }
+        // Tiny pivots arising from linearly dependent rows of A
+        // are replaced by a huge value, which effectively removes
+        // the corresponding component from the solution
+        let factor = CholeskyDecomposition::new_semidefinite(&normal, PIVOT_TOLERANCE)?;
+        // p solves M p = A D c + b
+        let dc : Vec<f64> = (0..n).map(|j| p.x[j] / p.s[j] * sf.c[j]).collect();
+        let adc = mul(&sf.a, &dc);
+        let rhs : Vec<f64> = (0..ms).map(|i| adc[i] + sf.b[i]).collect();
+        let ad_p = solve_normal(&factor, &rhs);
+        let system = NormalSystem {factor : factor, ad_p : ad_p};
+        // Predictor: affine scaling direction
+        let r_xs : Vec<f64> = (0..n).map(|j| -p.x[j] * p.s[j]).collect();
+        let r_tk = -p.tau * p.kappa;
+        let affine = newton_direction(&sf, &p, &system, &r, 1., &r_xs, r_tk);
+        let alpha = step_length(&p, &affine);
+        let sigma = (1. - alpha).powi(3);
+        // Corrector with second order terms
+        let r_xs : Vec<f64> = (0..n).map(|j| -p.x[j] * p.s[j]
+            - affine.x[j] * affine.s[j] + sigma * mu).collect();
+        let r_tk = -p.tau * p.kappa - affine.tau * affine.kappa + sigma * mu;
+        let d = newton_direction(&sf, &p, &system, &r, 1. - sigma, &r_xs, r_tk);
+        let alpha = STEP_FRACTION * step_length(&p, &d);
+        for j in 0..n {
+            p.x[j] += alpha * d.x[j];
+            p.s[j] += alpha * d.s[j];
+        }
+        for i in 0..ms {
+            p.y[i] += alpha * d.y[i];
+        }
+        p.tau += alpha * d.tau;
+        p.kappa += alpha * d.kappa;
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use lp::simplex::simplex;
+
+    #[test]
+    fn test_interior_point_basic(){
+        let c = vector_f64(&[-3., -5.]);
+        let a = matrix_rw_f64(3, 2, &[
+            1., 0.,
+            0., 2.,
+            3., 2.
+            ]);
+        let b = vector_f64(&[4., 12., 18.]);
+        let types = vec![ConstraintType::LessEqual; 3];
+        let lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        let s = interior_point(&lp).unwrap();
+        assert!((s.x[0] - 2.).abs() < 1e-6);
+        assert!((s.x[1] - 6.).abs() < 1e-6);
+        assert!((s.objective + 36.).abs() < 1e-6);
+        assert!((s.duals[1] + 1.5).abs() < 1e-6);
+        assert!((s.duals[2] + 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_interior_point_matches_simplex(){
+        // min x0 - 2 x1 + x2 with mixed bounds and constraints
+        let c = vector_f64(&[1., -2., 1.]);
+        let a = matrix_rw_f64(3, 3, &[
+            1., 1., 1.,
+            1., -1., 0.,
+            0., 1., 2.
+            ]);
+        let b = vector_f64(&[6., 1., 8.]);
+        let types = [ConstraintType::LessEqual, ConstraintType::GreaterEqual,
+            ConstraintType::Equal];
+        let mut lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        lp.set_bounds(&[-2., f64::NEG_INFINITY, 0.], &[3., 4., f64::INFINITY]).unwrap();
+        let s1 = simplex(&lp).unwrap();
+        let s2 = interior_point(&lp).unwrap();
+        assert!((s1.objective - 4.).abs() < 1e-10);
+        assert!((s1.objective - s2.objective).abs() < 1e-6);
+        assert!(lp.is_feasible(&s2.x, 1e-6));
+        assert!((&s1.duals - &s2.duals).max_abs_scalar_value() < 1e-6);
+    }
+
+    #[test]
+    fn test_interior_point_free_variables(){
+        // min x + y s.t. x - y = 0, x + y >= -2 with free x and y
+        let c = vector_f64(&[1., 1.]);
+        let a = matrix_rw_f64(2, 2, &[1., -1., 1., 1.]);
+        let b = vector_f64(&[0., -2.]);
+        let types = [ConstraintType::Equal, ConstraintType::GreaterEqual];
+        let mut lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        lp.set_bounds(&[f64::NEG_INFINITY; 2], &[f64::INFINITY; 2]).unwrap();
+        let s = interior_point(&lp).unwrap();
+        assert!((s.x[0] + 1.).abs() < 1e-6);
+        assert!((s.x[1] + 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_interior_point_infeasible_unbounded(){
+        let c = vector_f64(&[1., 1.]);
+        let a = matrix_rw_f64(2, 2, &[1., 1., 1., 1.]);
+        let b = vector_f64(&[1., 2.]);
+        let types = [ConstraintType::LessEqual, ConstraintType::GreaterEqual];
+        let lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        match interior_point(&lp) {
+            Err(SRError::Infeasible) => {},
+            r => panic!("{:?}", r)
+        }
+        let c = vector_f64(&[-1., 0.]);
+        let a = matrix_rw_f64(1, 2, &[1., -1.]);
+        let b = vector_f64(&[1.]);
+        let lp = LinearProgram::new(&c, &a, &[ConstraintType::LessEqual], &b).unwrap();
+        match interior_point(&lp) {
+            Err(SRError::Unbounded) => {},
+            r => panic!("{:?}", r)
+        }
+    }
+
+    #[test]
+    fn test_interior_point_dependent_rows(){
+        // x0 + x1 = 1 and 2 x0 + 2 x1 = 3 contradict each other
+        let c = vector_f64(&[1., 2.]);
+        let a = matrix_rw_f64(2, 2, &[1., 1., 2., 2.]);
+        let b = vector_f64(&[1., 3.]);
+        let types = [ConstraintType::Equal; 2];
+        let lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        match (simplex(&lp), interior_point(&lp)) {
+            (Err(SRError::Infeasible), Err(SRError::Infeasible)) => {},
+            r => panic!("{:?}", r)
+        }
+        // A zero row with a non-zero right hand side
+        let a = matrix_rw_f64(3, 2, &[0., 0., 3., 1., 3., 2.]);
+        let b = vector_f64(&[-2., -3., 2.]);
+        let types = [ConstraintType::Equal, ConstraintType::GreaterEqual,
+            ConstraintType::LessEqual];
+        let lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        match interior_point(&lp) {
+            Err(SRError::Infeasible) => {},
+            r => panic!("{:?}", r)
+        }
+        // Consistent dependent rows are dropped with zero multipliers
+        let b = vector_f64(&[1., 2.]);
+        let lp = LinearProgram::new(&c, &matrix_rw_f64(2, 2, &[1., 1., 2., 2.]),
+            &[ConstraintType::Equal; 2], &b).unwrap();
+        let s = interior_point(&lp).unwrap();
+        assert!((s.objective - 1.).abs() < 1e-6);
+        assert!((s.x[0] - 1.).abs() < 1e-6);
+        assert!(lp.is_feasible(&s.x, 1e-6));
+    }
+
+    #[test]
+    fn test_interior_point_infeasible_with_ray(){
+        // -x1 = 3 has no non-negative solution while the objective
+        // decreases without bound along x0
+        let c = vector_f64(&[-1., -2.]);
+        let a = matrix_rw_f64(2, 2, &[0., -1., 0., -2.]);
+        let b = vector_f64(&[3., 3.]);
+        let types = [ConstraintType::Equal, ConstraintType::GreaterEqual];
+        let lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        match (simplex(&lp), interior_point(&lp)) {
+            (Err(SRError::Infeasible), Err(SRError::Infeasible)) => {},
+            r => panic!("{:?}", r)
+        }
+    }
+
+    #[test]
+    fn test_interior_point_bounds_only(){
+        // min x0 - x1 s.t. 0 <= x <= 1 without any constraint
+        let c = vector_f64(&[1., -1.]);
+        let a : MatrixF64 = Matrix::zeros(0, 2);
+        let b : MatrixF64 = Matrix::zeros(0, 1);
+        let mut lp = LinearProgram::new(&c, &a, &[], &b).unwrap();
+        lp.set_bounds(&[0., 0.], &[1., 1.]).unwrap();
+        let s = interior_point(&lp).unwrap();
+        assert!(s.x[0].abs() < 1e-6);
+        assert!((s.x[1] - 1.).abs() < 1e-6);
+        assert!((s.objective + 1.).abs() < 1e-6);
+        assert_eq!(s.duals.num_rows(), 0);
+    }
+}
@@ -0,0 +1,703 @@
+#![doc="Mixed integer linear programming by branch and bound
+
+A mixed integer program is a linear program in which some
+variables must take integer values. Branch and bound solves
+the LP relaxation at every node of a search tree. A node whose
+relaxation has a fractional integer variable x_j = v is split
+into two children with x_j <= floor(v) and x_j >= ceil(v); the
+most fractional variable is chosen. A node is pruned when its
+relaxation is infeasible or when its bound is not better than
+the best integer solution found so far (the incumbent) by
+more than the gap tolerances.
+
+Open nodes are processed either in the order of their bounds
+(``BestBound``), which proves optimality with few nodes, or
+last in first out (``DepthFirst``), which finds incumbents
+early and keeps few nodes open.
+
+Before branching, rounds of Gomory mixed integer cuts tighten
+the root relaxation (see ``lp::gomory``). At every node the
+relaxation is rounded to the nearest integers, or else down
+or up; the remaining continuous variables are then optimized
+with the integer ones fixed. The first feasible result is
+offered as a new incumbent.
+
+The search stops once the relative gap between the incumbent
+and the best bound of the open nodes meets the tolerance or
+when the node or time limit is reached.
+"]
+
+
+// std imports
+use std::f64;
+use std::time::{Duration, Instant};
+
+// local imports
+use srmatrix::api::*;
+use lp::problem::*;
+use lp::simplex::*;
+use lp::gomory::*;
+
+
+/// Order in which open nodes are processed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeSelection {
+    /// The node with the smallest bound first
+    BestBound,
+    /// The most recently created node first
+    DepthFirst
+}
+
+
+/// Options of the branch and bound method
+#[derive(Debug, Clone, Copy)]
+pub struct MipOptions {
+    /// Order of the open nodes
+    pub node_selection : NodeSelection,
+    /// A value within this distance of an integer counts as integral
+    pub integrality_tolerance : f64,
+    /// Stop when (incumbent - bound) <= absolute_gap
+    pub absolute_gap : f64,
+    /// Stop when (incumbent - bound) <= relative_gap |incumbent|
+    pub relative_gap : f64,
+    /// Use the rounding heuristic at every node
+    pub rounding : bool,
+    /// Rounds of Gomory cuts at the root node
+    pub cut_rounds : usize,
+    /// Maximum number of cuts added per round
+    pub max_cuts : usize,
+    /// Maximum number of nodes to process
+    pub max_nodes : Option<usize>,
+    /// Maximum running time
+    pub time_limit : Option<Duration>
+}
+
+impl MipOptions {
+
+    /// Default options: best bound selection, integrality tolerance
+    /// 1e-6, gaps 1e-9 and 1e-6, rounding, 5 rounds of up to 10
+    /// cuts and no limits
+    pub fn new() -> MipOptions {
+        MipOptions {
+            node_selection : NodeSelection::BestBound,
+            integrality_tolerance : 1e-6,
+            absolute_gap : 1e-9,
+            relative_gap : 1e-6,
+            rounding : true,
+            cut_rounds : 5,
+            max_cuts : 10,
+            max_nodes : None,
+            time_limit : None
+        }
+    }
+
+    /// Sets the node selection rule
+    pub fn node_selection(mut self, node_selection : NodeSelection) -> MipOptions {
+        self.node_selection = node_selection;
+        self
+    }
+
+    /// Sets the integrality tolerance
+    pub fn integrality_tolerance(mut self, tolerance : f64) -> MipOptions {
+        self.integrality_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the absolute and relative gap tolerances
+    pub fn gap(mut self, absolute_gap : f64, relative_gap : f64) -> MipOptions {
+        self.absolute_gap = absolute_gap;
+        self.relative_gap = relative_gap;
+        self
+    }
+
+    /// Enables or disables the rounding heuristic
+    pub fn rounding(mut self, rounding : bool) -> MipOptions {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Sets the number of rounds of root cuts and the cuts per round
+    pub fn cuts(mut self, cut_rounds : usize, max_cuts : usize) -> MipOptions {
+        self.cut_rounds = cut_rounds;
+        self.max_cuts = max_cuts;
+        self
+    }
+
+    /// Sets the node limit
+    pub fn max_nodes(mut self, max_nodes : usize) -> MipOptions {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Sets the time limit
+    pub fn time_limit(mut self, time_limit : Duration) -> MipOptions {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Checks whether the incumbent value and the bound are close enough
+    fn gap_closed(&self, incumbent : f64, bound : f64) -> bool {
+        incumbent - bound <= self.absolute_gap.max(self.relative_gap * incumbent.abs())
+    }
+}
+
+impl Default for MipOptions {
+    fn default() -> MipOptions {
+        MipOptions::new()
+    }
+}
+
+
+/// A linear program with integer variables
+#[derive(Debug, Clone)]
+pub struct MixedIntegerProgram {
+    /// The linear program (its relaxation)
+    pub lp : LinearProgram,
+    /// Whether each variable must be integral
+    pub integer : Vec<bool>
+}
+
+impl MixedIntegerProgram {
+
+    /// Requires the variables with the given indices to be integral
+    pub fn new(lp : &LinearProgram, integer : &[usize]) -> SRResult<MixedIntegerProgram> {
+        let n = lp.num_variables();
+        let mut flags = vec![false; n];
+        for &j in integer {
+            if j >= n {
+                return Err(SRError::InvalidArgument);
+            }
+            flags[j] = true;
+        }
+        Ok(MixedIntegerProgram {
+            lp : lp.clone(),
+            integer : flags
+        })
+    }
+
+    /// Checks whether x satisfies the constraints and the
+    /// integrality conditions up to the given tolerances
+    pub fn is_feasible(&self, x : &MatrixF64, tolerance : f64, integrality_tolerance : f64) -> bool {
+        self.lp.is_feasible(x, tolerance)
+            && (0..self.integer.len()).all(|j| !self.integer[j]
+                || (x[j] - x[j].round()).abs() <= integrality_tolerance)
+    }
+}
+
+
+/// Reason for the end of the search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MipStatus {
+    /// The incumbent is optimal within the gap tolerances
+    Optimal,
+    /// The node limit was reached
+    NodeLimit,
+    /// The time limit was reached
+    TimeLimit
+}
+
+
+/// Origin of an incumbent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IncumbentSource {
+    /// An integral solution of a relaxation
+    Relaxation,
+    /// The rounding heuristic
+    Rounding
+}
+
+
+/// An improvement of the incumbent during the search
+#[derive(Debug, Clone, Copy)]
+pub struct IncumbentRecord {
+    /// The objective value of the new incumbent
+    pub objective : f64,
+    /// The best bound at the time
+    pub bound : f64,
+    /// Number of nodes processed so far
+    pub node : usize,
+    /// Time since the start of the search
+    pub elapsed : Duration,
+    /// How the incumbent was found
+    pub source : IncumbentSource
+}
+
+
+/// Result of branch and bound
+#[derive(Debug, Clone)]
+pub struct MipSolution {
+    /// The best integer solution found, if any
+    pub x : Option<MatrixF64>,
+    /// Its objective value (infinity if none was found)
+    pub objective : f64,
+    /// Lower bound on the optimal objective value
+    pub bound : f64,
+    /// Reason for the end of the search
+    pub status : MipStatus,
+    /// Number of nodes processed
+    pub nodes : usize,
+    /// Total number of simplex iterations
+    pub lp_iterations : usize,
+    /// Number of cuts added at the root
+    pub cuts : usize,
+    /// The sequence of incumbents
+    pub history : Vec<IncumbentRecord>
+}
+
+impl MipSolution {
+
+    /// Returns the relative gap (objective - bound) / max(|objective|, 1).
+    /// It is infinite without an incumbent.
+    pub fn gap(&self) -> f64 {
+        if self.x.is_none() {
+            return f64::INFINITY;
+        }
+        ((self.objective - self.bound) / self.objective.abs().max(1.)).max(0.)
+    }
+
+    /// Returns true if the solution is proven optimal
+    pub fn is_optimal(&self) -> bool {
+        self.status == MipStatus::Optimal && self.x.is_some()
+    }
+}
+
+
+/// A node of the search tree
+#[derive(Debug, Clone)]
+struct Node {
+    lower : Vec<f64>,
+    upper : Vec<f64>,
+    /// Objective value of the parent relaxation
+    bound : f64
+}
+
+
+/// State of the search
+struct Search<'a> {
+    mip : &'a MixedIntegerProgram,
+    /// The root relaxation with cuts
+    lp : LinearProgram,
+    options : &'a MipOptions,
+    start : Instant,
+    best : Option<(MatrixF64, f64)>,
+    history : Vec<IncumbentRecord>,
+    nodes : usize,
+    lp_iterations : usize
+}
+
+impl<'a> Search<'a> {
+
+    /// Solves the relaxation within the given bounds.
+    /// Returns None if it is infeasible.
+    fn relax(&mut self, lower : &[f64], upper : &[f64]) -> SRResult<Option<LPSolution>> {
+        let mut lp = self.lp.clone();
+        lp.lower = lower.to_vec();
+        lp.upper = upper.to_vec();
+        match simplex(&lp) {
+            Ok(s) => {
+                self.lp_iterations += s.iterations;
+                Ok(Some(s))
+            },
+            Err(SRError::Infeasible) => Ok(None),
+            Err(e) => Err(e)
+        }
+    }
+
+    /// The most fractional integer variable of x
+    fn branching_variable(&self, x : &MatrixF64) -> Option<usize> {
+        let mut best = None;
+        let mut distance = self.options.integrality_tolerance;
+        for j in 0..self.mip.integer.len() {
+            if !self.mip.integer[j] {
+                continue;
+            }
+            let d = (x[j] - x[j].round()).abs();
+            if d > distance {
+                distance = d;
+                best = Some(j);
+            }
+        }
+        best
+    }
+
+    /// Records x as the new incumbent if it is better
+    fn offer(&mut self, x : MatrixF64, source : IncumbentSource, bound : f64) {
+        let objective = self.mip.lp.objective(&x);
+        let better = match self.best {
+            Some((_, v)) => objective < v,
+            None => true
+        };
+        if better {
+            self.history.push(IncumbentRecord {
+                objective : objective,
+                bound : bound,
+                node : self.nodes,
+                elapsed : self.start.elapsed(),
+                source : source
+            });
+            self.best = Some((x, objective));
+        }
+    }
+
+    /// Rounds the integer variables of x to the nearest integers,
+    /// down or up (in this order) and optimizes the continuous
+    /// variables with the integer ones fixed. The first feasible
+    /// point is offered as incumbent.
+    fn round(&mut self, x : &MatrixF64, node : &Node, bound : f64) -> SRResult<()> {
+        let n = x.num_rows();
+        let continuous = self.mip.integer.iter().any(|i| !i);
+        let rules : [fn(f64) -> f64; 3] = [f64::round, f64::floor, f64::ceil];
+        for rule in rules.iter() {
+            let mut lower = node.lower.clone();
+            let mut upper = node.upper.clone();
+            for j in 0..n {
+                if self.mip.integer[j] {
+                    let v = rule(x[j]).max(node.lower[j]).min(node.upper[j]);
+                    lower[j] = v;
+                    upper[j] = v;
+                }
+            }
+            let candidate = if continuous {
+                match self.relax(&lower, &upper)? {
+                    Some(s) => s.x,
+                    None => continue
+                }
+            }
+            else {
+                Matrix::from_iter_cw(n, 1, lower.into_iter())
+            };
+            if self.mip.is_feasible(&candidate, 1e-7, self.options.integrality_tolerance) {
+                self.offer(candidate, IncumbentSource::Rounding, bound);
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if a node with the given bound cannot improve
+    /// the incumbent
+    fn prunable(&self, bound : f64) -> bool {
+        match self.best {
+            Some((_, v)) => self.options.gap_closed(v, bound),
+            None => false
+        }
+    }
+}
+
+
+/// Rounds the bounds of the integer variables inward
+fn integral_bounds(mip : &MixedIntegerProgram, tolerance : f64) -> SRResult<(Vec<f64>, Vec<f64>)> {
+    let mut lower = mip.lp.lower.clone();
+    let mut upper = mip.lp.upper.clone();
+    for j in 0..lower.len() {
+        if mip.integer[j] {
+            lower[j] = (lower[j] - tolerance).ceil();
+            upper[j] = (upper[j] + tolerance).floor();
+            if lower[j] > upper[j] {
+                return Err(SRError::Infeasible);
+            }
+        }
+    }
+    Ok((lower, upper))
+}
+
+
+#[doc="Solves a mixed integer linear program by branch and bound.
+
+Returns ``Infeasible`` if the program has no integer feasible
+point and ``Unbounded`` if the root relaxation is unbounded.
+When a limit stops the search the best solution found so far
+is returned along with the bound and the status.
+"]
+pub fn branch_and_bound(mip : &MixedIntegerProgram, options : &MipOptions) -> SRResult<MipSolution> {
+    let n = mip.lp.num_variables();
+    if mip.integer.len() != n {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let (lower, upper) = integral_bounds(mip, options.integrality_tolerance)?;
+    let mut search = Search {
+        mip : mip,
+        lp : mip.lp.clone(),
+        options : options,
+        start : Instant::now(),
+        best : None,
+        history : Vec::new(),
+        nodes : 0,
+        lp_iterations : 0
+    };
+    search.lp.lower = lower.clone();
+    search.lp.upper = upper.clone();
+    // Root cuts
+    let mut cuts = 0;
+    for _ in 0..options.cut_rounds {
+        let (s, tableau) = match simplex_tableau(&search.lp) {
+            Ok(r) => r,
+            Err(SRError::Infeasible) => break,
+            Err(e) => return Err(e)
+        };
+        search.lp_iterations += s.iterations;
+        let new_cuts = gomory_cuts(&search.lp, &mip.integer, &tableau, &s.x,
+            options.integrality_tolerance, options.max_cuts);
+        if new_cuts.is_empty() {
+            break;
+        }
+        for cut in new_cuts.iter() {
+            cut.add_to(&mut search.lp)?;
+        }
+        cuts += new_cuts.len();
+    }
+    let mut open = vec![Node {
+        lower : lower,
+        upper : upper,
+        bound : f64::NEG_INFINITY
+    }];
+    let mut status = MipStatus::Optimal;
+    let mut bound;
+    loop {
+        // Best bound of the open nodes
+        let open_bound = open.iter().fold(f64::INFINITY, |b, node| b.min(node.bound));
+        bound = match search.best {
+            Some((_, v)) => open_bound.min(v),
+            None => open_bound
+        };
+        if open.is_empty() || search.prunable(open_bound) {
+            break;
+        }
+        if options.max_nodes.is_some_and(|m| search.nodes >= m) {
+            status = MipStatus::NodeLimit;
+            break;
+        }
+        if options.time_limit.is_some_and(|t| search.start.elapsed() >= t) {
+            status = MipStatus::TimeLimit;
+            break;
+        }
+        let node = match options.node_selection {
+            NodeSelection::BestBound => {
+                // Ties go to the most recent node
+                let k = (0..open.len()).fold(0, |k, i| if open[i].bound <= open[k].bound { i } else { k });
+                open.swap_remove(k)
+            },
+            NodeSelection::DepthFirst => open.pop().unwrap()
+        };
+        if search.prunable(node.bound) {
+            continue;
+        }
+        search.nodes += 1;
+        let relaxation = match search.relax(&node.lower, &node.upper) {
+            Ok(Some(s)) => s,
+            Ok(None) => continue,
+            Err(SRError::Unbounded) if search.nodes == 1 => return Err(SRError::Unbounded),
+            Err(e) => return Err(e)
+        };
+        let value = relaxation.objective;
+        if search.prunable(value) {
+            continue;
+        }
+        let j = match search.branching_variable(&relaxation.x) {
+            Some(j) => j,
+            None => {
+                let r = &relaxation.x;
+                let x = Matrix::from_iter_cw(n, 1, (0..n).map(|k| {
+                    if mip.integer[k] { r[k].round() } else { r[k] }
+                }));
+                search.offer(x, IncumbentSource::Relaxation, bound);
+                continue;
+            }
+        };
+        if options.rounding {
+            search.round(&relaxation.x, &node, bound)?;
+        }
+        let v = relaxation.x[j];
+        let mut down = node.clone();
+        down.upper[j] = v.floor();
+        down.bound = value;
+        let mut up = node;
+        up.lower[j] = v.ceil();
+        up.bound = value;
+        // Depth first search explores the nearer child first
+        if v - v.floor() < 0.5 {
+            open.push(up);
+            open.push(down);
+        }
+        else {
+            open.push(down);
+            open.push(up);
+        }
+    }
+    match search.best {
+        Some((x, objective)) => Ok(MipSolution {
+            x : Some(x),
+            objective : objective,
+            bound : if status == MipStatus::Optimal { bound.min(objective) } else { bound },
+            status : status,
+            nodes : search.nodes,
+            lp_iterations : search.lp_iterations,
+            cuts : cuts,
+            history : search.history
+        }),
+        None => {
+            if status == MipStatus::Optimal {
+                return Err(SRError::Infeasible);
+            }
+            Ok(MipSolution {
+                x : None,
+                objective : f64::INFINITY,
+                bound : bound,
+                status : status,
+                nodes : search.nodes,
+                lp_iterations : search.lp_iterations,
+                cuts : cuts,
+                history : search.history
+            })
+        }
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    /// Brute force optimum of a small pure integer program
+    fn enumerate(mip : &MixedIntegerProgram, range : i32) -> f64 {
+        let n = mip.lp.num_variables();
+        let mut best = f64::INFINITY;
+        let count = (2 * range + 1).pow(n as u32);
+        for k in 0..count {
+            let mut r = k;
+            let x = Matrix::from_iter_cw(n, 1, (0..n).map(|_| {
+                let v = (r % (2 * range + 1)) - range;
+                r /= 2 * range + 1;
+                v as f64
+            }));
+            if mip.lp.is_feasible(&x, 1e-9) {
+                best = best.min(mip.lp.objective(&x));
+            }
+        }
+        best
+    }
+
+    fn knapsack() -> MixedIntegerProgram {
+        // max 8 x0 + 11 x1 + 6 x2 + 4 x3 s.t. 5 x0 + 7 x1 + 4 x2 + 3 x3 <= 14, x binary
+        let c = vector_f64(&[-8., -11., -6., -4.]);
+        let a = matrix_rw_f64(1, 4, &[5., 7., 4., 3.]);
+        let b = vector_f64(&[14.]);
+        let mut lp = LinearProgram::new(&c, &a, &[ConstraintType::LessEqual], &b).unwrap();
+        lp.set_bounds(&[0.; 4], &[1.; 4]).unwrap();
+        MixedIntegerProgram::new(&lp, &[0, 1, 2, 3]).unwrap()
+    }
+
+    #[test]
+    fn test_knapsack(){
+        let mip = knapsack();
+        for &selection in [NodeSelection::BestBound, NodeSelection::DepthFirst].iter() {
+            for &(rounds, rounding) in [(0, false), (0, true), (5, true)].iter() {
+                let options = MipOptions::new().node_selection(selection)
+                    .cuts(rounds, 10).rounding(rounding);
+                let s = branch_and_bound(&mip, &options).unwrap();
+                assert!(s.is_optimal());
+                assert!((s.objective + 21.).abs() < 1e-9);
+                assert_eq!(s.x.as_ref().unwrap(), &vector_f64(&[0., 1., 1., 1.]));
+                assert!(s.gap() < 1e-6);
+                assert!(!s.history.is_empty());
+                assert!(s.history.windows(2).all(|w| w[1].objective < w[0].objective));
+                assert_eq!(s.history.last().unwrap().objective, s.objective);
+            }
+        }
+    }
+
+    #[test]
+    fn test_general_integers(){
+        // max x + y s.t. -x + y <= 1, 3 x + 2 y <= 12, 2 x + 3 y <= 12
+        let c = vector_f64(&[-1., -1.]);
+        let a = matrix_rw_f64(3, 2, &[-1., 1., 3., 2., 2., 3.]);
+        let b = vector_f64(&[1., 12., 12.]);
+        let lp = LinearProgram::new(&c, &a, &[ConstraintType::LessEqual; 3], &b).unwrap();
+        let mip = MixedIntegerProgram::new(&lp, &[0, 1]).unwrap();
+        let s = branch_and_bound(&mip, &MipOptions::new()).unwrap();
+        assert!(s.cuts > 0);
+        assert!((s.objective - enumerate(&mip, 6)).abs() < 1e-9);
+        assert!(mip.is_feasible(s.x.as_ref().unwrap(), 1e-9, 0.));
+        // A randomly generated family checked against enumeration
+        let data : [[f64; 9]; 4] = [
+            [3., -2., 7., 1., 4., 2., 5., 3., -1.],
+            [-5., -4., 3., 6., -2., 7., 1., -3., 2.],
+            [2., 3., 2., 4., 1., 9., -3., 5., 4.],
+            [-1., -6., 6., 2., 5., 11., 4., -2., 3.]];
+        for d in data.iter() {
+            // min c' x s.t. A x <= b, x in [-3, 3]^2 integer
+            let c = vector_f64(&[d[0], d[1]]);
+            let a = matrix_rw_f64(3, 2, &[d[2], d[3], d[4], d[5], d[6], d[7]]);
+            let b = vector_f64(&[d[8] + 7.5, 10.3, 8.7]);
+            let mut lp = LinearProgram::new(&c, &a, &[ConstraintType::LessEqual; 3], &b).unwrap();
+            lp.set_bounds(&[-3.; 2], &[3.; 2]).unwrap();
+            let mip = MixedIntegerProgram::new(&lp, &[0, 1]).unwrap();
+            let expected = enumerate(&mip, 3);
+            for &selection in [NodeSelection::BestBound, NodeSelection::DepthFirst].iter() {
+                let s = branch_and_bound(&mip, &MipOptions::new().node_selection(selection)).unwrap();
+                assert!((s.objective - expected).abs() < 1e-9, "{} != {}", s.objective, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixed_program(){
+        // min -x - 2 z s.t. x - z >= -0.8, x + z <= 2.5, 0 <= x <= 2 integer, z >= 0
+        // x = 0 gives z = 0.8, x = 1 gives z = 1.5 (objective -4)
+        let c = vector_f64(&[-1., -2.]);
+        let a = matrix_rw_f64(2, 2, &[1., -1., 1., 1.]);
+        let b = vector_f64(&[-0.8, 2.5]);
+        let types = [ConstraintType::GreaterEqual, ConstraintType::LessEqual];
+        let mut lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        lp.set_bounds(&[0., 0.], &[2., f64::INFINITY]).unwrap();
+        let mip = MixedIntegerProgram::new(&lp, &[0]).unwrap();
+        let s = branch_and_bound(&mip, &MipOptions::new().cuts(0, 0)).unwrap();
+        let x = s.x.unwrap();
+        assert!((x[0] - 1.).abs() < 1e-9);
+        assert!((x[1] - 1.5).abs() < 1e-9);
+        assert!((s.objective + 4.).abs() < 1e-9);
+        assert!(s.bound <= s.objective);
+    }
+
+    #[test]
+    fn test_infeasible_and_limits(){
+        // 2 x = 1 has no integer solution
+        let c = vector_f64(&[1.]);
+        let a = matrix_rw_f64(1, 1, &[2.]);
+        let b = vector_f64(&[1.]);
+        let lp = LinearProgram::new(&c, &a, &[ConstraintType::Equal], &b).unwrap();
+        let mip = MixedIntegerProgram::new(&lp, &[0]).unwrap();
+        match branch_and_bound(&mip, &MipOptions::new()) {
+            Err(SRError::Infeasible) => {},
+            r => panic!("{:?}", r)
+        }
+        assert!(MixedIntegerProgram::new(&lp, &[1]).is_err());
+        // Integer bounds with no integer in between
+        let mut lp = lp.clone();
+        lp.set_bounds(&[0.2], &[0.8]).unwrap();
+        assert!(branch_and_bound(&MixedIntegerProgram::new(&lp, &[0]).unwrap(),
+            &MipOptions::new()).is_err());
+        // A node limit of one stops after the root
+        let mip = knapsack();
+        let options = MipOptions::new().cuts(0, 0).rounding(false).max_nodes(1);
+        let s = branch_and_bound(&mip, &options).unwrap();
+        assert_eq!(s.status, MipStatus::NodeLimit);
+        assert_eq!(s.nodes, 1);
+        assert!(s.x.is_none());
+        assert_eq!(s.gap(), f64::INFINITY);
+        assert!(s.bound <= -21.);
+        // The rounding heuristic finds an incumbent at the root
+        let s = branch_and_bound(&mip, &options.rounding(true)).unwrap();
+        assert!(s.x.is_some());
+        assert_eq!(s.history[0].source, IncumbentSource::Rounding);
+        assert!(s.gap() > 0.);
+        let s = branch_and_bound(&mip, &MipOptions::new().time_limit(Duration::from_secs(0))).unwrap();
+        assert_eq!(s.status, MipStatus::TimeLimit);
+    }
+}
@@ -0,0 +1,319 @@
+#![doc="Model building interface for linear programs
+
+A ``Model`` collects named variables with bounds, linear
+constraints and a linear objective to be minimized or
+maximized. It is converted to a ``LinearProgram`` (with a
+sparse constraint matrix) and solved by either the simplex
+or the interior point method.
+
+```
+use sropt::lp::model::*;
+use sropt::lp::problem::ConstraintType;
+
+let mut model = Model::new(Sense::Maximize);
+let x = model.add_variable(\"x\", 0., 4.);
+let y = model.add_variable(\"y\", 0., 6.);
+model.set_objective(&[(x, 3.), (y, 5.)]);
+let capacity = model.add_constraint(&[(x, 3.), (y, 2.)],
+    ConstraintType::LessEqual, 18.);
+let solution = model.solve().unwrap();
+assert!((solution.objective - 36.).abs() < 1e-9);
+assert!((solution.value(x) - 2.).abs() < 1e-9);
+assert!((solution.dual(capacity) - 1.).abs() < 1e-9);
+```
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use lp::sparse::SparseMatrix;
+use lp::problem::*;
+use lp::simplex::simplex;
+use lp::interior::interior_point;
+
+
+/// Direction of optimization
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sense {
+    Minimize,
+    Maximize
+}
+
+
+/// Handle of a variable in a model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Variable(usize);
+
+impl Variable {
+    /// Returns the column of the variable in the linear program
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+
+/// Handle of a constraint in a model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Constraint(usize);
+
+impl Constraint {
+    /// Returns the row of the constraint in the linear program
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+
+/// A constraint of a model: its sparse terms, type and right hand side
+type Row = (Vec<(usize, f64)>, ConstraintType, f64);
+
+
+/// A linear programming model
+#[derive(Debug, Clone)]
+pub struct Model {
+    sense : Sense,
+    names : Vec<String>,
+    lower : Vec<f64>,
+    upper : Vec<f64>,
+    objective : Vec<f64>,
+    rows : Vec<Row>
+}
+
+
+impl Model {
+
+    /// Creates an empty model
+    pub fn new(sense : Sense) -> Model {
+        Model {
+            sense : sense,
+            names : Vec::new(),
+            lower : Vec::new(),
+            upper : Vec::new(),
+            objective : Vec::new(),
+            rows : Vec::new()
+        }
+    }
+
+    /// Adds a variable with bounds lower <= x <= upper.
+    /// Infinite bounds are allowed.
+    pub fn add_variable(&mut self, name : &str, lower : f64, upper : f64) -> Variable {
+        self.names.push(name.to_string());
+        self.lower.push(lower);
+        self.upper.push(upper);
+        self.objective.push(0.);
+        Variable(self.names.len() - 1)
+    }
+
+    /// Returns the number of variables
+    pub fn num_variables(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns the number of constraints
+    pub fn num_constraints(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Looks up a variable by name
+    pub fn variable(&self, name : &str) -> Option<Variable> {
+        self.names.iter().position(|n| n == name).map(Variable)
+    }
+
+    /// Returns the name of a variable
+    pub fn name(&self, v : Variable) -> &str {
+        &self.names[v.0]
+    }
+
+    /// Sets the objective to sum coefficient * variable.
+    /// Variables not listed get a zero coefficient.
+    pub fn set_objective(&mut self, terms : &[(Variable, f64)]) {
+        for c in self.objective.iter_mut() {
+            *c = 0.;
+        }
+        for &(v, c) in terms.iter() {
+            self.objective[v.0] += c;
+        }
+    }
+
+    /// Sets the objective coefficient of a single variable
+    pub fn set_objective_coefficient(&mut self, v : Variable, c : f64) {
+        self.objective[v.0] = c;
+    }
+
+    /// Adds the constraint sum coefficient * variable ? rhs
+    pub fn add_constraint(&mut self, terms : &[(Variable, f64)],
+        constraint_type : ConstraintType, rhs : f64) -> Constraint {
+        let terms = terms.iter().map(|&(v, c)| (v.0, c)).collect();
+        self.rows.push((terms, constraint_type, rhs));
+        Constraint(self.rows.len() - 1)
+    }
+
+    /// Converts the model to a linear program in minimization form.
+    /// For maximization models the objective is negated.
+    pub fn to_linear_program(&self) -> SRResult<LinearProgram> {
+        let m = self.rows.len();
+        let n = self.names.len();
+        let mut a = SparseMatrix::new(m, n);
+        let mut b : MatrixF64 = Matrix::zeros(m, 1);
+        let mut types = Vec::with_capacity(m);
+        for (i, &(ref terms, constraint_type, rhs)) in self.rows.iter().enumerate() {
+            for &(j, v) in terms.iter() {
+                a.push(i, j, v)?;
+            }
+            b.set(i, 0, rhs);
+            types.push(constraint_type);
+        }
+        let sign = match self.sense {
+            Sense::Minimize => 1.,
+            Sense::Maximize => -1.
+        };
+        let c = Matrix::from_iter_cw(n, 1, self.objective.iter().map(|v| sign * v));
+        let mut lp = LinearProgram::from_sparse(&c, &a, &types, &b)?;
+        lp.set_bounds(&self.lower, &self.upper)?;
+        Ok(lp)
+    }
+
+    /// Solves the model by the simplex method
+    pub fn solve(&self) -> SRResult<ModelSolution> {
+        let lp = self.to_linear_program()?;
+        Ok(self.wrap(simplex(&lp)?))
+    }
+
+    /// Solves the model by the interior point method
+    pub fn solve_interior_point(&self) -> SRResult<ModelSolution> {
+        let lp = self.to_linear_program()?;
+        Ok(self.wrap(interior_point(&lp)?))
+    }
+
+    /// Restores the signs of a maximization model
+    fn wrap(&self, mut solution : LPSolution) -> ModelSolution {
+        if self.sense == Sense::Maximize {
+            solution.objective = -solution.objective;
+            solution.duals = solution.duals.unary_minus();
+            solution.reduced_costs = solution.reduced_costs.unary_minus();
+        }
+        ModelSolution {
+            objective : solution.objective,
+            names : self.names.clone(),
+            solution : solution
+        }
+    }
+}
+
+
+/// Solution of a model. Objective, duals and reduced costs
+/// refer to the objective as stated in the model (maximized
+/// or minimized).
+#[derive(Debug, Clone)]
+pub struct ModelSolution {
+    /// The optimal objective value
+    pub objective : f64,
+    /// The underlying solution
+    pub solution : LPSolution,
+    names : Vec<String>
+}
+
+
+impl ModelSolution {
+
+    /// Returns the optimal value of a variable
+    pub fn value(&self, v : Variable) -> f64 {
+        self.solution.x[v.0]
+    }
+
+    /// Returns the optimal value of a variable given its name
+    pub fn value_by_name(&self, name : &str) -> Option<f64> {
+        self.names.iter().position(|n| n == name).map(|j| self.solution.x[j])
+    }
+
+    /// Returns the dual value (shadow price) of a constraint:
+    /// the rate of change of the optimal objective with respect
+    /// to its right hand side
+    pub fn dual(&self, c : Constraint) -> f64 {
+        self.solution.duals[c.0]
+    }
+
+    /// Returns the reduced cost of a variable
+    pub fn reduced_cost(&self, v : Variable) -> f64 {
+        self.solution.reduced_costs[v.0]
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_model_diet(){
+        // Minimum cost diet with two foods and two nutrients
+        let mut model = Model::new(Sense::Minimize);
+        let bread = model.add_variable("bread", 0., f64::INFINITY);
+        let milk = model.add_variable("milk", 0., f64::INFINITY);
+        model.set_objective(&[(bread, 2.), (milk, 3.)]);
+        let protein = model.add_constraint(&[(bread, 1.), (milk, 2.)],
+            ConstraintType::GreaterEqual, 8.);
+        let energy = model.add_constraint(&[(bread, 3.), (milk, 2.)],
+            ConstraintType::GreaterEqual, 12.);
+        assert_eq!(model.num_variables(), 2);
+        assert_eq!(model.num_constraints(), 2);
+        for solution in [model.solve().unwrap(), model.solve_interior_point().unwrap()].iter() {
+            assert!((solution.value(bread) - 2.).abs() < 1e-6);
+            assert!((solution.value_by_name("milk").unwrap() - 3.).abs() < 1e-6);
+            assert!((solution.objective - 13.).abs() < 1e-6);
+            // y solves [1 3; 2 2] y = [2; 3]
+            assert!((solution.dual(protein) - 1.25).abs() < 1e-6);
+            assert!((solution.dual(energy) - 0.25).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_model_maximize(){
+        let mut model = Model::new(Sense::Maximize);
+        let x = model.add_variable("x", 0., 4.);
+        let y = model.add_variable("y", 0., 6.);
+        model.set_objective(&[(x, 3.), (y, 5.)]);
+        let capacity = model.add_constraint(&[(x, 3.), (y, 2.)],
+            ConstraintType::LessEqual, 18.);
+        let solution = model.solve().unwrap();
+        assert!((solution.objective - 36.).abs() < 1e-9);
+        assert!((solution.value(x) - 2.).abs() < 1e-9);
+        assert!((solution.dual(capacity) - 1.).abs() < 1e-9);
+        // y is at its upper bound with a positive reduced profit
+        assert!((solution.reduced_cost(y) - 3.).abs() < 1e-9);
+        assert_eq!(model.variable("y"), Some(y));
+        assert_eq!(model.name(x), "x");
+        assert_eq!(model.variable("z"), None);
+    }
+
+    #[test]
+    fn test_model_infeasible(){
+        let mut model = Model::new(Sense::Minimize);
+        let x = model.add_variable("x", 0., 1.);
+        model.add_constraint(&[(x, 1.)], ConstraintType::GreaterEqual, 2.);
+        assert!(model.solve().is_err());
+    }
+
+    #[test]
+    fn test_model_bounds_only(){
+        let mut model = Model::new(Sense::Maximize);
+        let x = model.add_variable("x", 0., 1.);
+        let y = model.add_variable("y", 0., 1.);
+        model.set_objective(&[(x, -1.), (y, 1.)]);
+        for solution in [model.solve(), model.solve_interior_point()].iter() {
+            let solution = solution.as_ref().unwrap();
+            assert!((solution.objective - 1.).abs() < 1e-6);
+            assert!(solution.value(x).abs() < 1e-6);
+            assert!((solution.value(y) - 1.).abs() < 1e-6);
+        }
+    }
+}
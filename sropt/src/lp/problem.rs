@@ -0,0 +1,238 @@
+#![doc="Linear programs and their solutions
+
+A linear program is stored in the form
+
+minimize c' x
+subject to a_i' x (<=, >=, =) b_i for every row i of A
+and lower <= x <= upper.
+
+Infinite bounds are represented by ``f64::INFINITY`` and
+``f64::NEG_INFINITY``. By default all variables are
+non-negative.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use lp::sparse::SparseMatrix;
+
+
+/// Type of a linear constraint a' x ? b
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstraintType {
+    /// a' x <= b
+    LessEqual,
+    /// a' x >= b
+    GreaterEqual,
+    /// a' x = b
+    Equal
+}
+
+
+/// A linear program in minimization form
+#[derive(Debug, Clone)]
+pub struct LinearProgram {
+    /// Objective coefficients (n x 1)
+    pub c : MatrixF64,
+    /// Constraint matrix (m x n)
+    pub a : MatrixF64,
+    /// Right hand side (m x 1)
+    pub b : MatrixF64,
+    /// Type of each constraint
+    pub constraint_types : Vec<ConstraintType>,
+    /// Lower bounds of the variables
+    pub lower : Vec<f64>,
+    /// Upper bounds of the variables
+    pub upper : Vec<f64>
+}
+
+
+impl LinearProgram {
+
+    /// Creates the program min c' x subject to the given
+    /// constraints and x >= 0
+    pub fn new(c : &MatrixF64, a : &MatrixF64,
+        constraint_types : &[ConstraintType], b : &MatrixF64) -> SRResult<LinearProgram> {
+        let m = a.num_rows();
+        let n = a.num_cols();
+        if c.num_cols() != 1 || b.num_cols() != 1 {
+            return Err(SRError::IsNotAColVector);
+        }
+        if c.num_rows() != n || b.num_rows() != m || constraint_types.len() != m {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(LinearProgram {
+            c : c.clone(),
+            a : a.clone(),
+            b : b.clone(),
+            constraint_types : constraint_types.to_vec(),
+            lower : vec![0.; n],
+            upper : vec![f64::INFINITY; n]
+        })
+    }
+
+    /// Creates a program with a sparse constraint matrix
+    pub fn from_sparse(c : &MatrixF64, a : &SparseMatrix,
+        constraint_types : &[ConstraintType], b : &MatrixF64) -> SRResult<LinearProgram> {
+        LinearProgram::new(c, &a.to_dense(), constraint_types, b)
+    }
+
+    /// Sets the bounds lower <= x <= upper
+    pub fn set_bounds(&mut self, lower : &[f64], upper : &[f64]) -> SRResult<()> {
+        let n = self.num_variables();
+        if lower.len() != n || upper.len() != n {
+            return Err(SRError::DimensionsMismatch);
+        }
+        for j in 0..n {
+            if lower[j].is_nan() || upper[j].is_nan() || lower[j] > upper[j]
+                || lower[j] == f64::INFINITY || upper[j] == f64::NEG_INFINITY {
+                return Err(SRError::InvalidArgument);
+            }
+        }
+        self.lower = lower.to_vec();
+        self.upper = upper.to_vec();
+        Ok(())
+    }
+
+    /// Appends the constraint a' x ? b
+    pub fn add_constraint(&mut self, a : &[f64], constraint_type : ConstraintType,
+        b : f64) -> SRResult<()> {
+        let n = self.num_variables();
+        if a.len() != n {
+            return Err(SRError::DimensionsMismatch);
+        }
+        let row = Matrix::from_iter_cw(1, n, a.iter().cloned());
+        if self.num_constraints() == 0 {
+            self.a = row;
+            self.b = vector_f64(&[b]);
+        }
+        else {
+            self.a.append_rows(&row);
+            self.b.append_rows(&vector_f64(&[b]));
+        }
+        self.constraint_types.push(constraint_type);
+        Ok(())
+    }
+
+    /// Returns the number of variables
+    pub fn num_variables(&self) -> usize {
+        self.a.num_cols()
+    }
+
+    /// Returns the number of constraints
+    pub fn num_constraints(&self) -> usize {
+        self.a.num_rows()
+    }
+
+    /// Evaluates the objective c' x
+    pub fn objective(&self, x : &MatrixF64) -> f64 {
+        (0..self.num_variables()).fold(0., |s, j| s + self.c[j] * x[j])
+    }
+
+    /// Checks whether x satisfies all constraints and bounds
+    /// up to an absolute tolerance
+    pub fn is_feasible(&self, x : &MatrixF64, tolerance : f64) -> bool {
+        let n = self.num_variables();
+        if x.num_cells() != n {
+            return false;
+        }
+        for j in 0..n {
+            if x[j] < self.lower[j] - tolerance || x[j] > self.upper[j] + tolerance {
+                return false;
+            }
+        }
+        for i in 0..self.num_constraints() {
+            let ax = (0..n).fold(0., |s, j| s + self.a.get(i, j).unwrap() * x[j]);
+            let ok = match self.constraint_types[i] {
+                ConstraintType::LessEqual => ax <= self.b[i] + tolerance,
+                ConstraintType::GreaterEqual => ax >= self.b[i] - tolerance,
+                ConstraintType::Equal => (ax - self.b[i]).abs() <= tolerance
+            };
+            if !ok {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+
+/// Optimal solution of a linear program
+#[derive(Debug, Clone)]
+pub struct LPSolution {
+    /// The optimal point
+    pub x : MatrixF64,
+    /// The optimal objective value c' x
+    pub objective : f64,
+    /// Dual values y, one per constraint. y_i is the rate of
+    /// change of the optimal objective with respect to b_i.
+    pub duals : MatrixF64,
+    /// Reduced costs c - A' y of the variables
+    pub reduced_costs : MatrixF64,
+    /// Number of iterations performed
+    pub iterations : usize
+}
+
+
+impl LPSolution {
+
+    /// Packs a primal dual pair of a program
+    pub fn new(lp : &LinearProgram, x : MatrixF64, duals : MatrixF64,
+        iterations : usize) -> LPSolution {
+        // c - A' y computed entrywise so that programs without
+        // constraints are handled as well
+        let m = lp.num_constraints();
+        let reduced_costs = Matrix::from_iter_cw(lp.num_variables(), 1,
+            (0..lp.num_variables()).map(|j| (0..m)
+                .fold(lp.c[j], |s, i| s - lp.a.get(i, j).unwrap() * duals[i])));
+        LPSolution {
+            objective : lp.objective(&x),
+            x : x,
+            duals : duals,
+            reduced_costs : reduced_costs,
+            iterations : iterations
+        }
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_linear_program(){
+        let c = vector_f64(&[1., 1.]);
+        let a = matrix_rw_f64(2, 2, &[1., 2., 3., 1.]);
+        let b = vector_f64(&[4., 6.]);
+        let types = [ConstraintType::LessEqual, ConstraintType::GreaterEqual];
+        let mut lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        assert_eq!(lp.num_variables(), 2);
+        assert_eq!(lp.num_constraints(), 2);
+        assert!(lp.is_feasible(&vector_f64(&[2., 0.]), 1e-12));
+        assert!(!lp.is_feasible(&vector_f64(&[1., 0.]), 1e-12));
+        assert!(!lp.is_feasible(&vector_f64(&[-1., 10.]), 1e-12));
+        assert_eq!(lp.objective(&vector_f64(&[2., 1.])), 3.);
+        assert!(lp.set_bounds(&[0., 1.], &[1., 0.]).is_err());
+        assert!(lp.set_bounds(&[0., f64::NAN], &[1., 1.]).is_err());
+        lp.set_bounds(&[f64::NEG_INFINITY, 0.], &[2., 2.]).unwrap();
+        assert!(lp.is_feasible(&vector_f64(&[2., 1.]), 1e-12));
+        assert!(!lp.is_feasible(&vector_f64(&[2.5, 0.]), 1e-12));
+        assert!(LinearProgram::new(&c, &a, &types[0..1], &b).is_err());
+        lp.add_constraint(&[1., 1.], ConstraintType::Equal, 2.).unwrap();
+        assert_eq!(lp.num_constraints(), 3);
+        assert_eq!(lp.a.get(2, 1).unwrap(), 1.);
+        assert_eq!(lp.b[2], 2.);
+        assert!(!lp.is_feasible(&vector_f64(&[2., 1.]), 1e-12));
+        assert!(lp.add_constraint(&[1.], ConstraintType::Equal, 2.).is_err());
+    }
+}
@@ -0,0 +1,661 @@
+#![doc="Revised simplex method with bounded variables
+
+Every constraint a_i' x ? b_i receives a slack variable s_i
+so that the constraints become the equalities A x + s = b with
+
+* s_i >= 0 for a' x <= b,
+* s_i <= 0 for a' x >= b,
+* s_i = 0 for a' x = b.
+
+Variables with finite bounds are handled directly: a non-basic
+variable sits at one of its bounds (or at zero if it is free)
+and may jump to the opposite bound without a basis change.
+
+Phase one starts from an artificial basis and minimizes the
+sum of the artificial variables. A positive minimum proves
+infeasibility. Phase two minimizes the original objective
+from the feasible basis found.
+
+Bland's rule (smallest index entering and leaving variable)
+is used throughout which guarantees termination on
+degenerate problems.
+
+The inverse of the basis matrix is kept explicitly and
+updated by elementary row operations. It is recomputed by
+LU factorization periodically to limit round off errors.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::lu::LUDecomposition;
+use srlinalg::factorization::Factorization;
+use lp::problem::*;
+
+
+/// Tolerance on reduced costs and on primal feasibility
+const TOLERANCE : f64 = 1e-9;
+/// Entries of the pivot column below this are treated as zero
+const PIVOT_TOLERANCE : f64 = 1e-11;
+/// Number of basis updates between refactorizations
+const REFACTOR_INTERVAL : usize = 50;
+
+
+/// Position of a variable with respect to the basis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VariableStatus {
+    /// A basic variable
+    Basic,
+    /// A non-basic variable at its lower bound
+    AtLower,
+    /// A non-basic variable at its upper bound
+    AtUpper,
+    /// A free non-basic variable held at zero
+    Free
+}
+
+
+/// Working state of the bounded simplex method
+struct Simplex {
+    /// [A I D] where D holds the artificial columns
+    a : MatrixF64,
+    b : Vec<f64>,
+    lower : Vec<f64>,
+    upper : Vec<f64>,
+    /// Variables which may enter the basis
+    enterable : Vec<bool>,
+    basis : Vec<usize>,
+    status : Vec<VariableStatus>,
+    x : Vec<f64>,
+    binv : MatrixF64,
+    updates : usize,
+    iterations : usize,
+    max_iterations : usize
+}
+
+
+impl Simplex {
+
+    /// Sets up phase one: structural variables at a bound,
+    /// slacks at zero and artificials in the basis
+    fn new(lp : &LinearProgram) -> Simplex {
+        let m = lp.num_constraints();
+        let n = lp.num_variables();
+        let total = n + 2 * m;
+        let mut a : MatrixF64 = Matrix::zeros(m, total);
+        let mut lower = vec![0.; total];
+        let mut upper = vec![f64::INFINITY; total];
+        let mut status = vec![VariableStatus::AtLower; total];
+        let mut x = vec![0.; total];
+        for j in 0..n {
+            for i in 0..m {
+                a.set(i, j, lp.a.get(i, j).unwrap());
+            }
+            lower[j] = lp.lower[j];
+            upper[j] = lp.upper[j];
+            if lower[j] > f64::NEG_INFINITY {
+                x[j] = lower[j];
+            }
+            else if upper[j] < f64::INFINITY {
+                status[j] = VariableStatus::AtUpper;
+                x[j] = upper[j];
+            }
+            else {
+                status[j] = VariableStatus::Free;
+            }
+        }
+        for i in 0..m {
+            a.set(i, n + i, 1.);
+            match lp.constraint_types[i] {
+                ConstraintType::LessEqual => {},
+                ConstraintType::GreaterEqual => {
+                    lower[n + i] = f64::NEG_INFINITY;
+                    upper[n + i] = 0.;
+                    status[n + i] = VariableStatus::AtUpper;
+                },
+                ConstraintType::Equal => {
+                    upper[n + i] = 0.;
+                }
+            }
+        }
+        let mut basis = Vec::with_capacity(m);
+        let mut binv : MatrixF64 = Matrix::zeros(m, m);
+        for i in 0..m {
+            let r = (0..n).fold(lp.b[i], |s, j| s - lp.a.get(i, j).unwrap() * x[j]);
+            let sign = if r >= 0. { 1. } else { -1. };
+            let k = n + m + i;
+            a.set(i, k, sign);
+            binv.set(i, i, sign);
+            basis.push(k);
+            status[k] = VariableStatus::Basic;
+            x[k] = r.abs();
+        }
+        Simplex {
+            a : a,
+            b : (0..m).map(|i| lp.b[i]).collect(),
+            lower : lower,
+            upper : upper,
+            enterable : vec![true; total],
+            basis : basis,
+            status : status,
+            x : x,
+            binv : binv,
+            updates : 0,
+            iterations : 0,
+            max_iterations : 100 * (total + 10)
+        }
+    }
+
+    fn num_rows(&self) -> usize {
+        self.basis.len()
+    }
+
+    /// Recomputes the basis inverse and the basic variables
+    fn refactor(&mut self) -> SRResult<()> {
+        let m = self.num_rows();
+        let total = self.x.len();
+        if m == 0 {
+            return Ok(());
+        }
+        let mut basis_matrix : MatrixF64 = Matrix::zeros(m, m);
+        for (k, &j) in self.basis.iter().enumerate() {
+            for i in 0..m {
+                basis_matrix.set(i, k, self.a.get(i, j).unwrap());
+            }
+        }
+        let mut lu = LUDecomposition::new(basis_matrix);
+        lu.decompose_ero();
+        self.binv = lu.inverse()?;
+        // x_B = B^-1 (b - N x_N)
+        let mut r : MatrixF64 = Matrix::zeros(m, 1);
+        for i in 0..m {
+            let mut v = self.b[i];
+            for j in 0..total {
+                if self.status[j] != VariableStatus::Basic && self.x[j] != 0. {
+                    v -= self.a.get(i, j).unwrap() * self.x[j];
+                }
+            }
+            r.set(i, 0, v);
+        }
+        let xb = &self.binv * &r;
+        for k in 0..m {
+            self.x[self.basis[k]] = xb[k];
+        }
+        self.updates = 0;
+        Ok(())
+    }
+
+    /// Computes the simplex multipliers y' = c_B' B^-1
+    fn multipliers(&self, cost : &[f64]) -> Vec<f64> {
+        let m = self.num_rows();
+        (0..m).map(|i| (0..m).fold(0., |s, k| s + cost[self.basis[k]]
+            * self.binv.get(k, i).unwrap())).collect()
+    }
+
+    /// Computes the reduced cost of variable j
+    fn reduced_cost(&self, cost : &[f64], y : &[f64], j : usize) -> f64 {
+        (0..self.num_rows()).fold(cost[j], |s, i| s - y[i] * self.a.get(i, j).unwrap())
+    }
+
+    /// Computes B^-1 a_j
+    fn column(&self, j : usize) -> Vec<f64> {
+        let m = self.num_rows();
+        (0..m).map(|i| (0..m).fold(0., |s, k| s + self.binv.get(i, k).unwrap()
+            * self.a.get(k, j).unwrap())).collect()
+    }
+
+    /// Exchanges the basic variable of row r with variable j
+    /// whose transformed column is w
+    fn pivot(&mut self, r : usize, j : usize, w : &[f64]) {
+        let m = self.num_rows();
+        let p = w[r];
+        for c in 0..m {
+            let v = self.binv.get(r, c).unwrap() / p;
+            self.binv.set(r, c, v);
+        }
+        for i in 0..m {
+            if i == r || w[i] == 0. {
+                continue;
+            }
+            for c in 0..m {
+                let v = self.binv.get(i, c).unwrap() - w[i] * self.binv.get(r, c).unwrap();
+                self.binv.set(i, c, v);
+            }
+        }
+        self.basis[r] = j;
+        self.status[j] = VariableStatus::Basic;
+        self.updates += 1;
+    }
+
+    /// Runs simplex iterations until the cost can no longer
+    /// be reduced
+    fn optimize(&mut self, cost : &[f64]) -> SRResult<()> {
+        let m = self.num_rows();
+        let total = self.x.len();
+        loop {
+            if self.updates >= REFACTOR_INTERVAL {
+                self.refactor()?;
+            }
+            let y = self.multipliers(cost);
+            // Bland's rule: the first improving variable enters
+            let mut entering = None;
+            for j in 0..total {
+                if !self.enterable[j] {
+                    continue;
+                }
+                let direction = match self.status[j] {
+                    VariableStatus::Basic => continue,
+                    VariableStatus::AtLower => {
+                        let d = self.reduced_cost(cost, &y, j);
+                        if d < -TOLERANCE && self.upper[j] > self.lower[j] { 1. } else { continue }
+                    },
+                    VariableStatus::AtUpper => {
+                        let d = self.reduced_cost(cost, &y, j);
+                        if d > TOLERANCE && self.upper[j] > self.lower[j] { -1. } else { continue }
+                    },
+                    VariableStatus::Free => {
+                        let d = self.reduced_cost(cost, &y, j);
+                        if d < -TOLERANCE { 1. } else if d > TOLERANCE { -1. } else { continue }
+                    }
+                };
+                entering = Some((j, direction));
+                break;
+            }
+            let (j, direction) = match entering {
+                Some(e) => e,
+                None => return Ok(())
+            };
+            if self.iterations == self.max_iterations {
+                return Err(SRError::NotConverged);
+            }
+            self.iterations += 1;
+            let w = self.column(j);
+            // Ratio test. A bound flip of the entering variable
+            // is preferred over a basis change on ties.
+            let mut step = self.upper[j] - self.lower[j];
+            let mut leaving : Option<(usize, bool)> = None;
+            for i in 0..m {
+                let rate = -direction * w[i];
+                let k = self.basis[i];
+                let (t, to_upper) = if rate < -PIVOT_TOLERANCE && self.lower[k] > f64::NEG_INFINITY {
+                    ((self.x[k] - self.lower[k]) / -rate, false)
+                }
+                else if rate > PIVOT_TOLERANCE && self.upper[k] < f64::INFINITY {
+                    ((self.upper[k] - self.x[k]) / rate, true)
+                }
+                else {
+                    continue;
+                };
+                let t = if t < 0. { 0. } else { t };
+                let better = match leaving {
+                    None => t < step,
+                    Some((l, _)) => t < step || (t == step && k < self.basis[l])
+                };
+                if better {
+                    step = t;
+                    leaving = Some((i, to_upper));
+                }
+            }
+            if step == f64::INFINITY {
+                return Err(SRError::Unbounded);
+            }
+            for i in 0..m {
+                let k = self.basis[i];
+                self.x[k] -= direction * w[i] * step;
+            }
+            self.x[j] += direction * step;
+            match leaving {
+                None => {
+                    // Bound flip
+                    if direction > 0. {
+                        self.status[j] = VariableStatus::AtUpper;
+                        self.x[j] = self.upper[j];
+                    }
+                    else {
+                        self.status[j] = VariableStatus::AtLower;
+                        self.x[j] = self.lower[j];
+                    }
+                },
+                Some((r, to_upper)) => {
+                    let k = self.basis[r];
+                    if to_upper {
+                        self.status[k] = VariableStatus::AtUpper;
+                        self.x[k] = self.upper[k];
+                    }
+                    else {
+                        self.status[k] = VariableStatus::AtLower;
+                        self.x[k] = self.lower[k];
+                    }
+                    self.pivot(r, j, &w);
+                }
+            }
+        }
+    }
+
+    /// Removes artificial variables from the basis after phase one.
+    /// Rows in which no other variable can replace the artificial
+    /// one are redundant; their artificial stays basic at zero.
+    fn drive_out_artificials(&mut self, first_artificial : usize) -> SRResult<()> {
+        let m = self.num_rows();
+        for r in 0..m {
+            if self.basis[r] < first_artificial {
+                continue;
+            }
+            for j in 0..first_artificial {
+                if self.status[j] == VariableStatus::Basic {
+                    continue;
+                }
+                let w = self.column(j);
+                if w[r].abs() > 1e-7 {
+                    let k = self.basis[r];
+                    self.status[k] = VariableStatus::AtLower;
+                    self.x[k] = 0.;
+                    self.pivot(r, j, &w);
+                    break;
+                }
+            }
+        }
+        self.refactor()
+    }
+}
+
+
+/// A row x_k + sum_j a_j z_j = value of the final simplex tableau
+/// for the basic variable z_k
+#[derive(Debug, Clone)]
+pub struct TableauRow {
+    /// Index of the basic variable
+    pub basic : usize,
+    /// Value of the basic variable
+    pub value : f64,
+    /// The row of B^-1 [A I] over all variables
+    pub coefficients : Vec<f64>
+}
+
+
+/// The final simplex tableau over the variables z = (x, s)
+/// where s = b - A x are the slacks of the constraints
+#[derive(Debug, Clone)]
+pub struct Tableau {
+    /// Lower bounds of x and s
+    pub lower : Vec<f64>,
+    /// Upper bounds of x and s
+    pub upper : Vec<f64>,
+    /// Status of the variables x and s
+    pub status : Vec<VariableStatus>,
+    /// One row for each basic variable x_k or s_k
+    pub rows : Vec<TableauRow>
+}
+
+
+impl Simplex {
+
+    /// Extracts the tableau over the structural and slack variables.
+    /// Rows of artificial variables left in the basis are skipped.
+    fn tableau(&self, first_artificial : usize) -> Tableau {
+        let m = self.num_rows();
+        let rows = (0..m).filter(|&r| self.basis[r] < first_artificial).map(|r| {
+            let coefficients = (0..first_artificial).map(|j| {
+                (0..m).fold(0., |s, k| s + self.binv.get(r, k).unwrap() * self.a.get(k, j).unwrap())
+            }).collect();
+            TableauRow {
+                basic : self.basis[r],
+                value : self.x[self.basis[r]],
+                coefficients : coefficients
+            }
+        }).collect();
+        Tableau {
+            lower : self.lower[..first_artificial].to_vec(),
+            upper : self.upper[..first_artificial].to_vec(),
+            status : self.status[..first_artificial].to_vec(),
+            rows : rows
+        }
+    }
+}
+
+
+#[doc="Solves a linear program by the two phase revised simplex
+method with Bland's rule.
+
+Returns ``Infeasible`` if the constraints cannot be satisfied
+and ``Unbounded`` if the objective can be decreased without
+limit.
+"]
+pub fn simplex(lp : &LinearProgram) -> SRResult<LPSolution> {
+    solve(lp).map(|(_, solution)| solution)
+}
+
+
+/// Solves a linear program by the simplex method and returns
+/// the final tableau along with the solution
+pub fn simplex_tableau(lp : &LinearProgram) -> SRResult<(LPSolution, Tableau)> {
+    let (s, solution) = solve(lp)?;
+    let tableau = s.tableau(lp.num_variables() + lp.num_constraints());
+    Ok((solution, tableau))
+}
+
+
+/// Runs both phases and returns the final state with the solution
+fn solve(lp : &LinearProgram) -> SRResult<(Simplex, LPSolution)> {
+    let m = lp.num_constraints();
+    let n = lp.num_variables();
+    let mut s = Simplex::new(lp);
+    let first_artificial = n + m;
+    let total = n + 2 * m;
+    // Phase one: minimize the sum of artificial variables
+    let mut cost = vec![0.; total];
+    for k in first_artificial..total {
+        cost[k] = 1.;
+    }
+    s.optimize(&cost)?;
+    let infeasibility = (first_artificial..total).fold(0., |acc, k| acc + s.x[k]);
+    let scale = (0..m).fold(1., |acc, i| if lp.b[i].abs() > acc { lp.b[i].abs() } else { acc });
+    if infeasibility > TOLERANCE * scale * (m as f64 + 1.) {
+        return Err(SRError::Infeasible);
+    }
+    s.drive_out_artificials(first_artificial)?;
+    // Phase two: artificial variables are fixed at zero
+    for k in first_artificial..total {
+        s.upper[k] = 0.;
+        s.enterable[k] = false;
+        if s.status[k] != VariableStatus::Basic {
+            s.x[k] = 0.;
+        }
+    }
+    let mut cost = vec![0.; total];
+    for j in 0..n {
+        cost[j] = lp.c[j];
+    }
+    s.optimize(&cost)?;
+    s.refactor()?;
+    let y = s.multipliers(&cost);
+    let x = Matrix::from_iter_cw(n, 1, s.x.iter().take(n).cloned());
+    let duals = Matrix::from_iter_cw(m, 1, y.into_iter());
+    let solution = LPSolution::new(lp, x, duals, s.iterations);
+    Ok((s, solution))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn le(m : usize) -> Vec<ConstraintType> {
+        vec![ConstraintType::LessEqual; m]
+    }
+
+    #[test]
+    fn test_simplex_basic(){
+        // max 3 x + 5 y s.t. x <= 4, 2 y <= 12, 3 x + 2 y <= 18
+        let c = vector_f64(&[-3., -5.]);
+        let a = matrix_rw_f64(3, 2, &[
+            1., 0.,
+            0., 2.,
+            3., 2.
+            ]);
+        let b = vector_f64(&[4., 12., 18.]);
+        let lp = LinearProgram::new(&c, &a, &le(3), &b).unwrap();
+        let s = simplex(&lp).unwrap();
+        assert!((s.x[0] - 2.).abs() < 1e-10);
+        assert!((s.x[1] - 6.).abs() < 1e-10);
+        assert!((s.objective + 36.).abs() < 1e-10);
+        // Shadow prices of the textbook example are (0, 3/2, 1)
+        assert!(s.duals[0].abs() < 1e-10);
+        assert!((s.duals[1] + 1.5).abs() < 1e-10);
+        assert!((s.duals[2] + 1.).abs() < 1e-10);
+        // Strong duality b' y = c' x
+        let by = (0..3).fold(0., |acc, i| acc + b[i] * s.duals[i]);
+        assert!((by - s.objective).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_simplex_mixed_constraints(){
+        // min 2 x + 3 y s.t. x + y >= 4, x - y = 1, x <= 10
+        let c = vector_f64(&[2., 3.]);
+        let a = matrix_rw_f64(3, 2, &[
+            1., 1.,
+            1., -1.,
+            1., 0.
+            ]);
+        let b = vector_f64(&[4., 1., 10.]);
+        let types = [ConstraintType::GreaterEqual, ConstraintType::Equal,
+            ConstraintType::LessEqual];
+        let lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        let s = simplex(&lp).unwrap();
+        assert!((s.x[0] - 2.5).abs() < 1e-10);
+        assert!((s.x[1] - 1.5).abs() < 1e-10);
+        assert!((s.objective - 9.5).abs() < 1e-10);
+        assert!(lp.is_feasible(&s.x, 1e-10));
+        // Reduced costs of basic variables vanish
+        assert!(s.reduced_costs.max_abs_scalar_value() < 1e-10);
+    }
+
+    #[test]
+    fn test_simplex_bounds(){
+        // min -x - 2 y s.t. x + y <= 3, 0 <= x <= 2, -1 <= y <= 1
+        let c = vector_f64(&[-1., -2.]);
+        let a = matrix_rw_f64(1, 2, &[1., 1.]);
+        let b = vector_f64(&[3.]);
+        let mut lp = LinearProgram::new(&c, &a, &le(1), &b).unwrap();
+        lp.set_bounds(&[0., -1.], &[2., 1.]).unwrap();
+        let s = simplex(&lp).unwrap();
+        assert!((s.x[0] - 2.).abs() < 1e-10);
+        assert!((s.x[1] - 1.).abs() < 1e-10);
+        // Free variable with a negative optimum
+        let c = vector_f64(&[1.]);
+        let a = matrix_rw_f64(1, 1, &[1.]);
+        let b = vector_f64(&[-5.]);
+        let mut lp = LinearProgram::new(&c, &a, &[ConstraintType::GreaterEqual], &b).unwrap();
+        lp.set_bounds(&[f64::NEG_INFINITY], &[f64::INFINITY]).unwrap();
+        let s = simplex(&lp).unwrap();
+        assert!((s.x[0] + 5.).abs() < 1e-10);
+        assert!((s.duals[0] - 1.).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_simplex_infeasible_unbounded(){
+        // x + y <= 1 and x + y >= 2
+        let c = vector_f64(&[1., 1.]);
+        let a = matrix_rw_f64(2, 2, &[1., 1., 1., 1.]);
+        let b = vector_f64(&[1., 2.]);
+        let types = [ConstraintType::LessEqual, ConstraintType::GreaterEqual];
+        let lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        match simplex(&lp) {
+            Err(SRError::Infeasible) => {},
+            r => panic!("{:?}", r)
+        }
+        // min -x s.t. x - y <= 1
+        let c = vector_f64(&[-1., 0.]);
+        let a = matrix_rw_f64(1, 2, &[1., -1.]);
+        let b = vector_f64(&[1.]);
+        let lp = LinearProgram::new(&c, &a, &le(1), &b).unwrap();
+        match simplex(&lp) {
+            Err(SRError::Unbounded) => {},
+            r => panic!("{:?}", r)
+        }
+    }
+
+    #[test]
+    fn test_simplex_degenerate(){
+        // Beale's example cycles under the textbook largest
+        // coefficient rule but not under Bland's rule
+        let c = vector_f64(&[-0.75, 150., -0.02, 6.]);
+        let a = matrix_rw_f64(3, 4, &[
+            0.25, -60., -0.04, 9.,
+            0.5, -90., -0.02, 3.,
+            0., 0., 1., 0.
+            ]);
+        let b = vector_f64(&[0., 0., 1.]);
+        let lp = LinearProgram::new(&c, &a, &le(3), &b).unwrap();
+        let s = simplex(&lp).unwrap();
+        assert!((s.objective + 0.05).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_simplex_tableau(){
+        // max x + y s.t. 2 x + 3 y <= 12, 2 x - 3 y >= -2, y <= 3 (optimum at (6, 0))
+        let c = vector_f64(&[-1., -1.]);
+        let a = matrix_rw_f64(2, 2, &[2., 3., 2., -3.]);
+        let b = vector_f64(&[12., -2.]);
+        let types = [ConstraintType::LessEqual, ConstraintType::GreaterEqual];
+        let mut lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        lp.set_bounds(&[0., 0.], &[f64::INFINITY, 3.]).unwrap();
+        let (s, t) = simplex_tableau(&lp).unwrap();
+        assert_eq!(t.rows.len(), 2);
+        assert_eq!(t.status.len(), 4);
+        assert_eq!(t.upper[1], 3.);
+        assert_eq!(t.lower[3], f64::NEG_INFINITY);
+        // Each row expresses its basic variable in the non-basic ones:
+        // z_k = value - sum_j a_j (z_j - z_j*) holds at any z with A x + s = b
+        let z = [1., 2., 12. - 8., -2. - (2. - 6.)];
+        let (x, y) = (s.x[0], s.x[1]);
+        let z_opt = [x, y, 12. - 2. * x - 3. * y, -2. - 2. * x + 3. * y];
+        for row in t.rows.iter() {
+            assert_eq!(t.status[row.basic], VariableStatus::Basic);
+            assert!((row.coefficients[row.basic] - 1.).abs() < 1e-12);
+            assert!((row.value - z_opt[row.basic]).abs() < 1e-12);
+            let rhs = (0..4).filter(|&j| t.status[j] != VariableStatus::Basic)
+                .fold(row.value, |acc, j| acc - row.coefficients[j] * (z[j] - z_opt[j]));
+            assert!((z[row.basic] - rhs).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_simplex_redundant_equalities(){
+        // The second equality is twice the first
+        let c = vector_f64(&[1., 2.]);
+        let a = matrix_rw_f64(2, 2, &[1., 1., 2., 2.]);
+        let b = vector_f64(&[1., 2.]);
+        let types = [ConstraintType::Equal, ConstraintType::Equal];
+        let lp = LinearProgram::new(&c, &a, &types, &b).unwrap();
+        let s = simplex(&lp).unwrap();
+        assert!((s.x[0] - 1.).abs() < 1e-10);
+        assert!((s.objective - 1.).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_simplex_bounds_only(){
+        // min x0 - x1 s.t. 0 <= x <= 1 without any constraint
+        let c = vector_f64(&[1., -1.]);
+        let a : MatrixF64 = Matrix::zeros(0, 2);
+        let b : MatrixF64 = Matrix::zeros(0, 1);
+        let mut lp = LinearProgram::new(&c, &a, &[], &b).unwrap();
+        lp.set_bounds(&[0., 0.], &[1., 1.]).unwrap();
+        let s = simplex(&lp).unwrap();
+        assert!(s.x[0].abs() < 1e-10);
+        assert!((s.x[1] - 1.).abs() < 1e-10);
+        assert!((s.objective + 1.).abs() < 1e-10);
+        assert_eq!(s.duals.num_rows(), 0);
+        assert!((s.reduced_costs[0] - 1.).abs() < 1e-10);
+        assert!((s.reduced_costs[1] + 1.).abs() < 1e-10);
+    }
+}
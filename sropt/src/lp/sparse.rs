@@ -0,0 +1,129 @@
+#![doc="Sparse constraint matrices
+
+Constraint matrices of practical linear programs are mostly
+zero. ``SparseMatrix`` stores the non-zero entries as
+(row, column, value) triplets. Duplicate entries are summed
+when the matrix is converted to dense form.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// A sparse matrix in coordinate (triplet) format
+#[derive(Debug, Clone)]
+pub struct SparseMatrix {
+    rows : usize,
+    cols : usize,
+    entries : Vec<(usize, usize, f64)>
+}
+
+
+impl SparseMatrix {
+
+    /// Creates an empty rows x cols sparse matrix
+    pub fn new(rows : usize, cols : usize) -> SparseMatrix {
+        SparseMatrix {rows : rows, cols : cols, entries : Vec::new()}
+    }
+
+    /// Creates a sparse matrix from the non-zero entries of a dense matrix
+    pub fn from_dense(a : &MatrixF64) -> SparseMatrix {
+        let mut result = SparseMatrix::new(a.num_rows(), a.num_cols());
+        for c in 0..a.num_cols() {
+            for r in 0..a.num_rows() {
+                let v = a.get(r, c).unwrap();
+                if v != 0. {
+                    result.entries.push((r, c, v));
+                }
+            }
+        }
+        result
+    }
+
+    /// Adds an entry. Entries at the same position are summed.
+    pub fn push(&mut self, r : usize, c : usize, v : f64) -> SRResult<()> {
+        if r >= self.rows || c >= self.cols {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if v != 0. {
+            self.entries.push((r, c, v));
+        }
+        Ok(())
+    }
+
+    /// Returns the number of rows
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the stored (row, column, value) triplets
+    pub fn entries(&self) -> &[(usize, usize, f64)] {
+        &self.entries
+    }
+
+    /// Converts to a dense matrix
+    pub fn to_dense(&self) -> MatrixF64 {
+        let mut a : MatrixF64 = Matrix::zeros(self.rows, self.cols);
+        for &(r, c, v) in self.entries.iter() {
+            let x = a.get(r, c).unwrap() + v;
+            a.set(r, c, x);
+        }
+        a
+    }
+
+    /// Computes the product A x for a column vector x
+    pub fn mul_vec(&self, x : &MatrixF64) -> SRResult<MatrixF64> {
+        if x.num_rows() != self.cols || x.num_cols() != 1 {
+            return Err(SRError::DimensionsMismatch);
+        }
+        let mut y : MatrixF64 = Matrix::zeros(self.rows, 1);
+        for &(r, c, v) in self.entries.iter() {
+            let s = y[r] + v * x[c];
+            y.set(r, 0, s);
+        }
+        Ok(y)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_sparse_matrix(){
+        let mut s = SparseMatrix::new(2, 3);
+        s.push(0, 0, 1.).unwrap();
+        s.push(1, 2, 2.).unwrap();
+        s.push(1, 2, 3.).unwrap();
+        s.push(0, 1, 0.).unwrap();
+        assert_eq!(s.nnz(), 3);
+        assert!(s.push(2, 0, 1.).is_err());
+        let a = s.to_dense();
+        assert_eq!(a, matrix_rw_f64(2, 3, &[1., 0., 0., 0., 0., 5.]));
+        let y = s.mul_vec(&vector_f64(&[1., 2., 3.])).unwrap();
+        assert_eq!(y, vector_f64(&[1., 15.]));
+        let t = SparseMatrix::from_dense(&a);
+        assert_eq!(t.nnz(), 2);
+        assert_eq!(t.to_dense(), a);
+    }
+}
@@ -0,0 +1,144 @@
+#![doc="Equality constrained least squares
+
+Solves
+
+min ||A x - b||  subject to  C x = d
+
+where A is m x n and C is p x n with p <= n and full row rank.
+
+The null space method is used. With the QR decomposition
+C' = Q R and Q = [Q1 Q2], every feasible point has the form
+x = Q1 y1 + Q2 y2 where R' y1 = d. The remaining free
+coordinates y2 solve the unconstrained problem
+
+min ||A Q2 y2 - (b - A Q1 y1)||.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::qr::QRDecomposition;
+use ls::result::*;
+use ls::linear::lstsq;
+
+
+#[doc="Solves min ||A x - b|| subject to C x = d.
+
+The reported rank is the rank of A restricted to the null
+space of C, i.e. the number of parameters left free by the
+constraints.
+
+Returns ``IsNotFullRankMatrix`` if the constraints are linearly
+dependent.
+"]
+pub fn lse(a : &MatrixF64, b : &MatrixF64, c : &MatrixF64, d : &MatrixF64)
+    -> SRResult<LeastSquaresResult> {
+    check_ls_dimensions(a, b)?;
+    check_ls_dimensions(c, d)?;
+    let n = a.num_cols();
+    let p = c.num_rows();
+    if c.num_cols() != n {
+        return Err(SRError::DimensionsMismatch);
+    }
+    if p > n {
+        return Err(SRError::IsNotFullRankMatrix);
+    }
+    let ct = c.transpose();
+    if p > 0 && QRDecomposition::new_pivoted(&ct).rank(None) < p {
+        return Err(SRError::IsNotFullRankMatrix);
+    }
+    let qr = QRDecomposition::new(&ct);
+    let q = qr.q();
+    let r = qr.r();
+    // Forward substitution for R' y1 = d
+    let mut y1 : MatrixF64 = Matrix::zeros(p, 1);
+    for i in 0..p {
+        let mut v = d[i];
+        for j in 0..i {
+            v -= r.get(j, i).unwrap() * y1[j];
+        }
+        y1.set(i, 0, v / r.get(i, i).unwrap());
+    }
+    let q1 = q.view(0, 0, n, p).to_matrix();
+    let x1 = &q1 * &y1;
+    if p == n {
+        // The constraints determine x completely
+        return Ok(LeastSquaresResult::new(a, b, x1, 0, 0));
+    }
+    let q2 = q.view(0, p, n, n - p).to_matrix();
+    let rhs = b - &(a * &x1);
+    let reduced = lstsq(&(a * &q2), &rhs)?;
+    let x = &x1 + &(&q2 * &reduced.x);
+    Ok(LeastSquaresResult::new(a, b, x, reduced.rank, 0))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_lse_sum_constraint(){
+        // Closest point to b whose coordinates sum to one
+        let a : MatrixF64 = Matrix::identity(3, 3);
+        let b = vector_f64(&[1., 2., 3.]);
+        let c = matrix_rw_f64(1, 3, &[1., 1., 1.]);
+        let d = vector_f64(&[1.]);
+        let result = lse(&a, &b, &c, &d).unwrap();
+        let expected = vector_f64(&[-2. / 3., 1. / 3., 4. / 3.]);
+        assert!((&result.x - &expected).max_abs_scalar_value() < 1e-12);
+        assert_eq!(result.rank, 2);
+    }
+
+    #[test]
+    fn test_lse_line_through_point(){
+        // Fit y = c0 + c1 t through the origin
+        let a = matrix_rw_f64(4, 2, &[
+            1., 1.,
+            1., 2.,
+            1., 3.,
+            1., 4.
+            ]);
+        let b = vector_f64(&[2.1, 3.9, 6.2, 7.8]);
+        let c = matrix_rw_f64(1, 2, &[1., 0.]);
+        let d = vector_f64(&[0.]);
+        let result = lse(&a, &b, &c, &d).unwrap();
+        assert!(result.x[0].abs() < 1e-14);
+        // Slope is sum(t y) / sum(t^2)
+        let slope = (2.1 + 2. * 3.9 + 3. * 6.2 + 4. * 7.8) / 30.;
+        assert!((result.x[1] - slope).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lse_single_observation(){
+        // x0 + x1 = 3 fitted exactly subject to x0 - x1 = 1
+        let a = matrix_rw_f64(1, 2, &[1., 1.]);
+        let b = vector_f64(&[3.]);
+        let c = matrix_rw_f64(1, 2, &[1., -1.]);
+        let d = vector_f64(&[1.]);
+        let result = lse(&a, &b, &c, &d).unwrap();
+        let expected = vector_f64(&[2., 1.]);
+        assert!((&result.x - &expected).max_abs_scalar_value() < 1e-12);
+        assert!(result.residual_norm < 1e-12);
+    }
+
+    #[test]
+    fn test_lse_dependent_constraints(){
+        let a : MatrixF64 = Matrix::identity(3, 3);
+        let b = vector_f64(&[1., 2., 3.]);
+        let c = matrix_rw_f64(2, 3, &[
+            1., 1., 1.,
+            2., 2., 2.
+            ]);
+        let d = vector_f64(&[1., 2.]);
+        assert!(lse(&a, &b, &c, &d).is_err());
+    }
+}
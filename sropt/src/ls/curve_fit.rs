@@ -0,0 +1,337 @@
+#![doc="Curve fitting
+
+Fits the parameters p of a model y = f(x, p) to observations
+(x_i, y_i) by minimizing the (weighted) sum of squares
+
+sum_i ((f(x_i, p) - y_i) / sigma_i)^2
+
+with the methods of ``nonlinear``. The model maps the
+independent data and the parameters to the vector of
+predictions, so x may hold one observation per row with any
+number of columns.
+
+The covariance of the estimates is approximated by
+
+C = s^2 (J'J)^{-1},  s^2 = ||r||^2 / (m - n)
+
+where J is the Jacobian of the weighted residuals at the
+solution. It is computed from a QR decomposition with column
+pivoting of J so that J'J is never formed.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::qr::QRDecomposition;
+use srlinalg::linear_system::ut_solve;
+use srstat::moments::traits::Sums;
+use ls::nonlinear::*;
+
+
+/// Signature of a model Jacobian dJ(x, p) with respect to p
+type JacobianFn = fn(&MatrixF64, &MatrixF64) -> MatrixF64;
+
+
+/// Weighted residuals (f(x, p) - y) / sigma of a model
+struct ModelResiduals<'a, F, J> {
+    model : F,
+    jacobian : Option<J>,
+    xdata : &'a MatrixF64,
+    ydata : &'a MatrixF64,
+    sigma : Option<&'a MatrixF64>
+}
+
+impl<'a, F, J> Residuals for ModelResiduals<'a, F, J>
+    where F : Fn(&MatrixF64, &MatrixF64) -> MatrixF64,
+    J : Fn(&MatrixF64, &MatrixF64) -> MatrixF64 {
+
+    fn residuals(&self, p : &MatrixF64) -> MatrixF64 {
+        let mut r = &(self.model)(self.xdata, p) - self.ydata;
+        if let Some(sigma) = self.sigma {
+            for i in 0..r.num_rows() {
+                let v = r[i] / sigma[i];
+                r.set(i, 0, v);
+            }
+        }
+        r
+    }
+
+    fn jacobian(&self, p : &MatrixF64) -> MatrixF64 {
+        let mut jac = match self.jacobian {
+            Some(ref jacobian) => jacobian(self.xdata, p),
+            None => return forward_difference_jacobian(self, p, &self.residuals(p))
+        };
+        if let Some(sigma) = self.sigma {
+            for i in 0..jac.num_rows() {
+                jac.ero_scale(i, 1. / sigma[i]);
+            }
+        }
+        jac
+    }
+}
+
+
+/// Result of a curve fit
+#[derive(Debug, Clone)]
+pub struct CurveFitResult {
+    /// Solution of the underlying least squares problem
+    /// (residuals and Jacobian are weighted)
+    pub result : NonlinearResult,
+    /// The covariance of the parameters, if the Jacobian
+    /// has full column rank and there are more observations
+    /// than parameters
+    pub covariance : Option<MatrixF64>,
+    /// The estimate ||r||^2 / dof of the residual variance
+    pub residual_variance : f64,
+    /// The residual degrees of freedom m - n
+    pub dof : usize
+}
+
+impl CurveFitResult {
+
+    /// Returns the fitted parameters
+    pub fn parameters(&self) -> &MatrixF64 {
+        &self.result.p
+    }
+
+    /// Returns the standard errors of the parameters
+    /// (square roots of the diagonal of the covariance)
+    pub fn standard_errors(&self) -> Option<MatrixF64> {
+        self.covariance.as_ref().map(|c| {
+            Matrix::from_iter_cw(c.num_rows(), 1,
+                (0..c.num_rows()).map(|i| c.get(i, i).unwrap().sqrt()))
+        })
+    }
+}
+
+
+#[doc="Computes the covariance s2 (J'J)^{-1} of least squares
+estimates from the Jacobian J of the residuals.
+
+Returns ``None`` if J does not have full column rank.
+"]
+pub fn parameter_covariance(jacobian : &MatrixF64, s2 : f64) -> Option<MatrixF64> {
+    let n = jacobian.num_cols();
+    if n == 0 || jacobian.num_rows() < n {
+        return None;
+    }
+    let qr = QRDecomposition::new_pivoted(jacobian);
+    if qr.rank(None) < n {
+        return None;
+    }
+    // J P = Q R gives (J'J)^{-1} = P R^{-1} R^{-T} P'
+    let r = qr.r();
+    let r_inv = match ut_solve(&r, &Matrix::identity(n, n)) {
+        Ok(r_inv) => r_inv,
+        Err(_) => return None
+    };
+    let c = &r_inv * &r_inv.transpose();
+    let perm = qr.permutation();
+    let mut covariance = Matrix::zeros(n, n);
+    for j in 0..n {
+        for i in 0..n {
+            covariance.set(perm[i], perm[j], s2 * c.get(i, j).unwrap());
+        }
+    }
+    Some(covariance)
+}
+
+
+/// Checks the data and completes the fit
+fn fit<F, J>(model : F, jacobian : Option<J>, xdata : &MatrixF64, ydata : &MatrixF64,
+    p0 : &MatrixF64, sigma : Option<&MatrixF64>, options : &NonlinearOptions)
+    -> SRResult<CurveFitResult>
+    where F : Fn(&MatrixF64, &MatrixF64) -> MatrixF64,
+    J : Fn(&MatrixF64, &MatrixF64) -> MatrixF64 {
+    if !ydata.is_col() {
+        return Err(SRError::IsNotAColVector);
+    }
+    let m = ydata.num_rows();
+    if xdata.num_rows() != m {
+        return Err(SRError::DimensionsMismatch);
+    }
+    if let Some(sigma) = sigma {
+        if !sigma.is_col() || sigma.num_rows() != m {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if (0..m).any(|i| sigma[i].is_nan() || sigma[i] <= 0.) {
+            return Err(SRError::InvalidArgument);
+        }
+    }
+    let residuals = ModelResiduals {
+        model : model,
+        jacobian : jacobian,
+        xdata : xdata,
+        ydata : ydata,
+        sigma : sigma
+    };
+    let result = nonlinear_lstsq(&residuals, p0, options)?;
+    let n = p0.num_rows();
+    let dof = m.saturating_sub(n);
+    let residual_variance = if dof > 0 {
+        result.residual.sum_sqr_cw()[0] / dof as f64
+    }
+    else {
+        0.
+    };
+    let covariance = if dof > 0 {
+        parameter_covariance(&result.jacobian, residual_variance)
+    }
+    else {
+        None
+    };
+    Ok(CurveFitResult {
+        result : result,
+        covariance : covariance,
+        residual_variance : residual_variance,
+        dof : dof
+    })
+}
+
+
+#[doc="Fits model(x, p) to the observations y starting from p0.
+
+The Jacobian is approximated by forward differences.
+``sigma`` optionally holds the standard deviations of the
+observations; each residual is divided by its sigma.
+"]
+pub fn curve_fit<F>(model : F, xdata : &MatrixF64, ydata : &MatrixF64,
+    p0 : &MatrixF64, sigma : Option<&MatrixF64>, options : &NonlinearOptions)
+    -> SRResult<CurveFitResult>
+    where F : Fn(&MatrixF64, &MatrixF64) -> MatrixF64 {
+    fit(model, None::<JacobianFn>, xdata, ydata, p0, sigma, options)
+}
+
+
+#[doc="Fits model(x, p) to the observations y starting from p0
+with the Jacobian of the model with respect to p supplied by
+``jacobian(x, p)`` (one row per observation, one column per
+parameter).
+"]
+pub fn curve_fit_with_jacobian<F, J>(model : F, jacobian : J, xdata : &MatrixF64,
+    ydata : &MatrixF64, p0 : &MatrixF64, sigma : Option<&MatrixF64>,
+    options : &NonlinearOptions) -> SRResult<CurveFitResult>
+    where F : Fn(&MatrixF64, &MatrixF64) -> MatrixF64,
+    J : Fn(&MatrixF64, &MatrixF64) -> MatrixF64 {
+    fit(model, Some(jacobian), xdata, ydata, p0, sigma, options)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use ls::linear::lstsq;
+
+    fn decay(x : &MatrixF64, p : &MatrixF64) -> MatrixF64 {
+        Matrix::from_iter_cw(x.num_rows(), 1,
+            (0..x.num_rows()).map(|i| p[0] * (-p[1] * x[i]).exp()))
+    }
+
+    fn decay_jacobian(x : &MatrixF64, p : &MatrixF64) -> MatrixF64 {
+        let m = x.num_rows();
+        let mut jac = Matrix::zeros(m, 2);
+        for i in 0..m {
+            let e = (-p[1] * x[i]).exp();
+            jac.set(i, 0, e);
+            jac.set(i, 1, -p[0] * x[i] * e);
+        }
+        jac
+    }
+
+    fn decay_data() -> (MatrixF64, MatrixF64) {
+        // 2.5 exp(-1.3 x) with a small deterministic perturbation
+        let x = Matrix::from_iter_cw(20, 1, (0..20).map(|i| i as f64 * 0.2));
+        let y = Matrix::from_iter_cw(20, 1, (0..20).map(|i| {
+            let xi = i as f64 * 0.2;
+            2.5 * (-1.3 * xi).exp() + 0.01 * ((i * 7 % 5) as f64 - 2.)
+        }));
+        (x, y)
+    }
+
+    #[test]
+    fn test_exponential_decay(){
+        let (x, y) = decay_data();
+        let p0 = vector_f64(&[1., 1.]);
+        let mut fits = Vec::new();
+        for &method in [NonlinearMethod::GaussNewton, NonlinearMethod::LevenbergMarquardt].iter() {
+            let options = NonlinearOptions::new().method(method);
+            let fd = curve_fit(decay, &x, &y, &p0, None, &options).unwrap();
+            let exact = curve_fit_with_jacobian(decay, decay_jacobian, &x, &y, &p0,
+                None, &options).unwrap();
+            assert!(fd.result.converged);
+            assert!(exact.result.converged);
+            assert!((fd.parameters() - exact.parameters()).max_abs_scalar_value() < 1e-6);
+            assert!((exact.result.p[0] - 2.5).abs() < 0.05);
+            assert!((exact.result.p[1] - 1.3).abs() < 0.05);
+            assert_eq!(exact.dof, 18);
+            let se = exact.standard_errors().unwrap();
+            assert!(se[0] > 0. && se[0] < 0.05);
+            fits.push(exact);
+        }
+        assert!((fits[0].parameters() - fits[1].parameters()).max_abs_scalar_value() < 1e-8);
+    }
+
+    #[test]
+    fn test_linear_model_covariance(){
+        // For a linear model the covariance is exactly s^2 (X'X)^{-1}
+        let x = vector_f64(&[0., 1., 2., 3., 4., 5.]);
+        let y = vector_f64(&[1.1, 2.9, 5.2, 7.1, 8.8, 11.2]);
+        let model = |x : &MatrixF64, p : &MatrixF64| Matrix::from_iter_cw(x.num_rows(), 1,
+            (0..x.num_rows()).map(|i| p[0] + p[1] * x[i]));
+        let fit = curve_fit(model, &x, &y, &vector_f64(&[0., 0.]), None,
+            &NonlinearOptions::new()).unwrap();
+        let design = Matrix::from_iter_cw(6, 2, (0..12).map(|k| if k < 6 { 1. } else { x[k - 6] }));
+        let ls = lstsq(&design, &y).unwrap();
+        assert!((fit.parameters() - &ls.x).max_abs_scalar_value() < 1e-8);
+        assert!((fit.residual_variance - ls.residual_variance()).abs() < 1e-10);
+        let xtx = &design.transpose() * &design;
+        let (a, b, d) = (xtx.get(0, 0).unwrap(), xtx.get(0, 1).unwrap(), xtx.get(1, 1).unwrap());
+        let expected = matrix_rw_f64(2, 2, &[d, -b, -b, a])
+            .copy_mul_scalar(ls.residual_variance() / (a * d - b * b));
+        let covariance = fit.covariance.unwrap();
+        // Up to the accuracy of the finite difference Jacobian
+        assert!((&covariance - &expected).max_abs_scalar_value() < 1e-8);
+        assert!(covariance.is_symmetric());
+    }
+
+    #[test]
+    fn test_weights_and_bounds(){
+        let (x, y) = decay_data();
+        // Uniform weights scale the cost but not the parameters
+        let sigma = Matrix::from_iter_cw(20, 1, (0..20).map(|_| 0.5));
+        let options = NonlinearOptions::new();
+        let p0 = vector_f64(&[1., 1.]);
+        let plain = curve_fit(decay, &x, &y, &p0, None, &options).unwrap();
+        let weighted = curve_fit(decay, &x, &y, &p0, Some(&sigma), &options).unwrap();
+        assert!((plain.parameters() - weighted.parameters()).max_abs_scalar_value() < 1e-7);
+        assert!((weighted.result.cost - 4. * plain.result.cost).abs() < 1e-12);
+        // The rate is pinned at its upper bound
+        let options = NonlinearOptions::new()
+            .bounds(&vector_f64(&[0., 0.]), &vector_f64(&[10., 1.]));
+        let bounded = curve_fit(decay, &x, &y, &p0, None, &options).unwrap();
+        assert!(bounded.result.converged);
+        assert_eq!(bounded.result.p[1], 1.);
+        assert!(bounded.result.cost > plain.result.cost);
+        // Invalid weights
+        let zero = Matrix::zeros(20, 1);
+        assert!(curve_fit(decay, &x, &y, &p0, Some(&zero), &options).is_err());
+        assert!(curve_fit(decay, &x, &vector_f64(&[1.]), &p0, None, &options).is_err());
+    }
+
+    #[test]
+    fn test_rank_deficient_covariance(){
+        let jac = matrix_rw_f64(3, 2, &[1., 2., 2., 4., 3., 6.]);
+        assert!(parameter_covariance(&jac, 1.).is_none());
+        let jac = matrix_rw_f64(3, 2, &[1., 0., 0., 2., 0., 0.]);
+        let c = parameter_covariance(&jac, 2.).unwrap();
+        assert!((&c - &matrix_rw_f64(2, 2, &[2., 0., 0., 0.5])).max_abs_scalar_value() < 1e-14);
+    }
+}
@@ -0,0 +1,154 @@
+#![doc="Ordinary and weighted linear least squares
+
+Both solvers use the QR decomposition with column pivoting.
+Rank deficient problems are handled by returning a basic
+solution in which the coefficients of dependent columns
+are zero.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::qr::QRDecomposition;
+use ls::result::*;
+
+
+/// Solves min ||A x - b|| using QR decomposition with column pivoting
+pub fn lstsq(a : &MatrixF64, b : &MatrixF64) -> SRResult<LeastSquaresResult> {
+    check_ls_dimensions(a, b)?;
+    let qr = QRDecomposition::new_pivoted(a);
+    let x = qr.solve_least_squares(b)?;
+    Ok(LeastSquaresResult::new(a, b, x, qr.rank(None), 0))
+}
+
+
+#[doc="Solves the weighted least squares problem
+
+min sum_i w_i (a_i x - b_i)^2
+
+where a_i is the i-th row of A and the weights w_i are
+non-negative.
+
+The reported residual is the unweighted b - A x while
+the residual norm is the weighted norm which is minimized.
+"]
+pub fn weighted_lstsq(a : &MatrixF64, b : &MatrixF64, w : &MatrixF64)
+    -> SRResult<LeastSquaresResult> {
+    check_ls_dimensions(a, b)?;
+    if w.num_cells() != a.num_rows() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let m = a.num_rows();
+    let mut wa = a.clone();
+    let mut wb = b.clone();
+    for i in 0..m {
+        if w[i] < 0. {
+            return Err(SRError::InvalidArgument);
+        }
+        let s = w[i].sqrt();
+        for j in 0..a.num_cols() {
+            let x = wa.get(i, j).unwrap() * s;
+            wa.set(i, j, x);
+        }
+        wb.set(i, 0, b[i] * s);
+    }
+    let weighted = lstsq(&wa, &wb)?;
+    let mut result = LeastSquaresResult::new(a, b, weighted.x, weighted.rank, 0);
+    result.residual_norm = weighted.residual_norm;
+    let positive = w.cell_iter().filter(|&v| v > 0.).count();
+    result.dof = positive as f64 - weighted.rank as f64;
+    Ok(result)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_lstsq_line(){
+        // y = 1 + 2 t with noise free data
+        let a = matrix_rw_f64(4, 2, &[
+            1., 0.,
+            1., 1.,
+            1., 2.,
+            1., 3.
+            ]);
+        let b = vector_f64(&[1., 3., 5., 7.]);
+        let result = lstsq(&a, &b).unwrap();
+        assert!((&result.x - &vector_f64(&[1., 2.])).max_abs_scalar_value() < 1e-12);
+        assert!(result.residual_norm < 1e-12);
+        assert_eq!(result.rank, 2);
+        assert_eq!(result.dof, 2.);
+        assert!((result.r_squared(&b) - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lstsq_overdetermined(){
+        // Mean of observations is the least squares constant fit
+        let a : MatrixF64 = Matrix::ones(4, 1);
+        let b = vector_f64(&[1., 2., 3., 6.]);
+        let result = lstsq(&a, &b).unwrap();
+        assert!((result.x[0] - 3.).abs() < 1e-14);
+        assert!((result.rss() - 14.).abs() < 1e-12);
+        assert!((result.residual_variance() - 14. / 3.).abs() < 1e-12);
+        // Residual is orthogonal to the columns of A
+        assert!((&a.transpose() * &result.residual)[0].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lstsq_rank_deficient(){
+        let a = matrix_rw_f64(3, 3, &[
+            1., 2., 3.,
+            2., 4., 6.,
+            1., 0., 1.
+            ]);
+        let b = vector_f64(&[6., 12., 2.]);
+        let result = lstsq(&a, &b).unwrap();
+        assert_eq!(result.rank, 2);
+        assert!(result.residual_norm < 1e-12);
+    }
+
+    #[test]
+    fn test_weighted_lstsq(){
+        let a : MatrixF64 = Matrix::ones(3, 1);
+        let b = vector_f64(&[1., 2., 4.]);
+        let w = vector_f64(&[1., 1., 2.]);
+        let result = weighted_lstsq(&a, &b, &w).unwrap();
+        // Weighted mean
+        assert!((result.x[0] - 11. / 4.).abs() < 1e-14);
+        // Zero weight removes an observation
+        let w = vector_f64(&[1., 1., 0.]);
+        let result = weighted_lstsq(&a, &b, &w).unwrap();
+        assert!((result.x[0] - 1.5).abs() < 1e-14);
+        assert_eq!(result.dof, 1.);
+    }
+
+    #[test]
+    fn test_lstsq_single_observation(){
+        let a = matrix_rw_f64(1, 1, &[2.]);
+        let b = vector_f64(&[3.]);
+        let result = lstsq(&a, &b).unwrap();
+        assert!((result.x[0] - 1.5).abs() < 1e-14);
+        assert!(result.residual_norm < 1e-14);
+        assert_eq!(result.dof, 0.);
+        let result = weighted_lstsq(&a, &b, &vector_f64(&[4.])).unwrap();
+        assert!((result.x[0] - 1.5).abs() < 1e-14);
+        assert_eq!(result.dof, 0.);
+    }
+
+    #[test]
+    fn test_lstsq_mismatch(){
+        let a : MatrixF64 = Matrix::ones(3, 2);
+        let b = vector_f64(&[1., 2.]);
+        assert!(lstsq(&a, &b).is_err());
+    }
+}
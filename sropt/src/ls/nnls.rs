@@ -0,0 +1,254 @@
+#![doc="Non-negative least squares
+
+Solves
+
+min ||A x - b||  subject to  x >= 0
+
+by the active set method of Lawson and Hanson.
+
+Variables are split into a passive set P (free to vary)
+and an active set Z (held at zero). In each outer iteration
+the active variable with the largest positive gradient
+component w = A' (b - A x) is released. If rounding leaves
+it non-positive in the least squares solution on P, it goes
+back to Z and the next candidate is tried. The unconstrained
+least squares problem on P is then solved; if that pushes
+some variables negative, the algorithm moves along the
+segment towards the new solution until the first variable
+hits zero and returns it to Z.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::norm::norm_1;
+use srlinalg::qr::QRDecomposition;
+use ls::result::*;
+
+
+/// Solves the least squares problem restricted to the passive columns.
+/// Entries outside the passive set are zero.
+fn solve_passive(a : &MatrixF64, b : &MatrixF64, passive : &[bool]) -> SRResult<Vec<f64>> {
+    let m = a.num_rows();
+    let n = a.num_cols();
+    let columns : Vec<usize> = (0..n).filter(|&j| passive[j]).collect();
+    let mut z = vec![0.; n];
+    if columns.is_empty() {
+        return Ok(z);
+    }
+    let mut ap : MatrixF64 = Matrix::zeros(m, columns.len());
+    for (c, &j) in columns.iter().enumerate() {
+        for i in 0..m {
+            ap.set(i, c, a.get(i, j).unwrap());
+        }
+    }
+    let zp = QRDecomposition::new_pivoted(&ap).solve_least_squares(b)?;
+    for (c, &j) in columns.iter().enumerate() {
+        z[j] = zp[c];
+    }
+    Ok(z)
+}
+
+
+/// Computes the gradient A' (b - A x) of the negative half squared residual
+fn gradient(a : &MatrixF64, b : &MatrixF64, x : &[f64]) -> MatrixF64 {
+    let x = Matrix::from_slice_cw(x.len(), 1, x);
+    let r = b - &(a * &x);
+    &a.transpose() * &r
+}
+
+
+#[doc="Solves min ||A x - b|| subject to x >= 0 by the
+Lawson-Hanson active set method.
+
+Returns ``NotConverged`` if the solution has not been found
+within 3 n outer iterations.
+"]
+pub fn nnls(a : &MatrixF64, b : &MatrixF64) -> SRResult<LeastSquaresResult> {
+    check_ls_dimensions(a, b)?;
+    let m = a.num_rows();
+    let n = a.num_cols();
+    let mut x = vec![0.; n];
+    let mut passive = vec![false; n];
+    let scale = norm_1(a) * vec_norm_l2(b);
+    let tolerance = (if m > n { m } else { n }) as f64 * f64::EPSILON * scale;
+    let max_iterations = 3 * n;
+    let mut iterations = 0;
+    'outer: loop {
+        let mut w = gradient(a, b, &x);
+        let mut z = loop {
+            // Pick the most promising variable of the active set
+            let mut best = None;
+            let mut best_w = tolerance;
+            for j in 0..n {
+                if !passive[j] && w[j] > best_w {
+                    best_w = w[j];
+                    best = Some(j);
+                }
+            }
+            let j = match best {
+                Some(j) => j,
+                None => break 'outer
+            };
+            if iterations == max_iterations {
+                return Err(SRError::NotConverged);
+            }
+            iterations += 1;
+            passive[j] = true;
+            let z = solve_passive(a, b, &passive)?;
+            if z[j] > 0. {
+                break z;
+            }
+            // Releasing this variable does not help (degenerate step):
+            // return it to the active set and try the next candidate
+            passive[j] = false;
+            w.set(j, 0, 0.);
+        };
+        // Inner loop: restore feasibility
+        loop {
+            let mut alpha = f64::INFINITY;
+            for i in 0..n {
+                if passive[i] && z[i] <= 0. {
+                    let t = x[i] / (x[i] - z[i]);
+                    if t < alpha {
+                        alpha = t;
+                    }
+                }
+            }
+            if alpha == f64::INFINITY {
+                break;
+            }
+            for i in 0..n {
+                x[i] += alpha * (z[i] - x[i]);
+                if passive[i] && x[i] <= tolerance {
+                    passive[i] = false;
+                    x[i] = 0.;
+                }
+            }
+            z = solve_passive(a, b, &passive)?;
+        }
+        x = z;
+    }
+    let rank = passive.iter().filter(|&&p| p).count();
+    let x = Matrix::from_slice_cw(n, 1, &x);
+    Ok(LeastSquaresResult::new(a, b, x, rank, iterations))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use ls::linear::lstsq;
+
+    #[test]
+    fn test_nnls_unconstrained_optimum(){
+        // The unconstrained solution is already non-negative
+        let a = matrix_rw_f64(4, 2, &[
+            1., 0.,
+            1., 1.,
+            1., 2.,
+            1., 3.
+            ]);
+        let b = vector_f64(&[1., 3., 5., 7.]);
+        let result = nnls(&a, &b).unwrap();
+        let ls = lstsq(&a, &b).unwrap();
+        assert!((&result.x - &ls.x).max_abs_scalar_value() < 1e-12);
+    }
+
+    #[test]
+    fn test_nnls_active_constraint(){
+        // Unconstrained fit has a negative slope
+        let a = matrix_rw_f64(3, 2, &[
+            1., 0.,
+            1., 1.,
+            1., 2.
+            ]);
+        let b = vector_f64(&[3., 2., 1.]);
+        let result = nnls(&a, &b).unwrap();
+        assert!((result.x[0] - 2.).abs() < 1e-12);
+        assert_eq!(result.x[1], 0.);
+        assert_eq!(result.rank, 1);
+        assert!(result.iterations >= 1);
+        // KKT: gradient is zero on the passive set and
+        // non-positive on the active set
+        let w = &a.transpose() * &result.residual;
+        assert!(w[0].abs() < 1e-12);
+        assert!(w[1] <= 1e-12);
+    }
+
+    #[test]
+    fn test_nnls_kkt(){
+        let a = matrix_rw_f64(5, 4, &[
+            0.8, 0.1, 0.6, 0.2,
+            0.3, 0.9, 0.1, 0.7,
+            0.5, 0.4, 0.9, 0.1,
+            0.2, 0.6, 0.3, 0.8,
+            0.9, 0.2, 0.4, 0.5
+            ]);
+        let b = vector_f64(&[1., -2., 0.5, -1., 2.]);
+        let result = nnls(&a, &b).unwrap();
+        let w = &a.transpose() * &result.residual;
+        for j in 0..4 {
+            assert!(result.x[j] >= 0.);
+            if result.x[j] > 0. {
+                assert!(w[j].abs() < 1e-12);
+            }
+            else {
+                assert!(w[j] <= 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nnls_degenerate_release(){
+        // A variable released with a positive gradient ends up
+        // non-positive in the rank deficient least squares solution
+        let a = matrix_rw_f64(4, 7, &[
+            -2., -1., 0., 2., 1., 2., -1.,
+            -1., 0., -1., 0., 1., 1., 0.,
+            -1., -2., 2., -2., 1., 1., 2.,
+            -2., 0., 1., 1., 1., 2., 0.
+            ]);
+        let b = vector_f64(&[0., -1., 1., -1.]);
+        let result = nnls(&a, &b).unwrap();
+        let w = &a.transpose() * &result.residual;
+        for j in 0..7 {
+            assert!(result.x[j] >= 0.);
+            if result.x[j] > 0. {
+                assert!(w[j].abs() < 1e-12);
+            }
+            else {
+                assert!(w[j] <= 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nnls_single_observation(){
+        let a = matrix_rw_f64(1, 1, &[2.]);
+        let result = nnls(&a, &vector_f64(&[4.])).unwrap();
+        assert!((result.x[0] - 2.).abs() < 1e-14);
+        assert!(result.residual_norm < 1e-14);
+        let result = nnls(&a, &vector_f64(&[-4.])).unwrap();
+        assert_eq!(result.x[0], 0.);
+        assert_eq!(result.residual_norm, 4.);
+    }
+
+    #[test]
+    fn test_nnls_all_zero(){
+        let a : MatrixF64 = Matrix::identity(3, 3);
+        let b = vector_f64(&[-1., -2., -3.]);
+        let result = nnls(&a, &b).unwrap();
+        assert_eq!(result.x.max_abs_scalar_value(), 0.);
+        assert_eq!(result.iterations, 0);
+    }
+}
@@ -0,0 +1,508 @@
+#![doc="Nonlinear least squares
+
+minimize (1/2) ||r(p)||^2 subject to lower <= p <= upper
+
+where r maps the n parameters p to m residuals. Both methods
+linearize r around the current parameters using the Jacobian
+J = dr/dp.
+
+* Gauss-Newton: the step solves min ||J d + r|| (by QR with
+  column pivoting) and is damped by a backtracking line
+  search on the cost.
+* Levenberg-Marquardt: the step solves
+  (J'J + mu D) d = -J'r where D holds the largest diagonal
+  entries of J'J seen so far (Marquardt scaling). The damping
+  mu is adapted from the ratio of the actual to the predicted
+  reduction of the cost (Nielsen's strategy).
+
+Bounds are handled by projection. Parameters at a bound whose
+gradient component points out of the box are held fixed for
+the step; the remaining step is projected back onto the box.
+
+The Jacobian is approximated by forward differences unless
+the residual function supplies one.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::qr::QRDecomposition;
+use unconstrained::options::Termination;
+
+
+/// Maximum number of step halvings in the Gauss-Newton method
+const MAX_BACKTRACKS : usize = 30;
+
+
+/// A vector valued residual function r(p)
+pub trait Residuals {
+
+    /// Evaluates the residual vector at p
+    fn residuals(&self, p : &MatrixF64) -> MatrixF64;
+
+    /// Evaluates the m x n Jacobian of the residuals at p
+    fn jacobian(&self, p : &MatrixF64) -> MatrixF64 {
+        forward_difference_jacobian(self, p, &self.residuals(p))
+    }
+}
+
+
+impl<R:Residuals + ?Sized> Residuals for &R {
+
+    fn residuals(&self, p : &MatrixF64) -> MatrixF64 {
+        (**self).residuals(p)
+    }
+
+    fn jacobian(&self, p : &MatrixF64) -> MatrixF64 {
+        (**self).jacobian(p)
+    }
+}
+
+
+/// Approximates the Jacobian of r at p by forward differences
+/// given the residuals r0 = r(p)
+pub fn forward_difference_jacobian<R:Residuals + ?Sized>(r : &R, p : &MatrixF64,
+    r0 : &MatrixF64) -> MatrixF64 {
+    let m = r0.num_rows();
+    let n = p.num_rows();
+    let scale = f64::EPSILON.sqrt();
+    let mut jac = Matrix::zeros(m, n);
+    let mut q = p.clone();
+    for j in 0..n {
+        let pj = p[j];
+        let h = scale * pj.abs().max(1.);
+        q.set(j, 0, pj + h);
+        let rj = r.residuals(&q);
+        q.set(j, 0, pj);
+        for i in 0..m {
+            jac.set(i, j, (rj[i] - r0[i]) / h);
+        }
+    }
+    jac
+}
+
+
+/// Residuals defined by a closure
+pub struct FnResiduals<F> where F : Fn(&MatrixF64) -> MatrixF64 {
+    f : F
+}
+
+impl<F> FnResiduals<F> where F : Fn(&MatrixF64) -> MatrixF64 {
+
+    /// Wraps a closure computing r(p)
+    pub fn new(f : F) -> FnResiduals<F> {
+        FnResiduals{f : f}
+    }
+}
+
+impl<F> Residuals for FnResiduals<F> where F : Fn(&MatrixF64) -> MatrixF64 {
+
+    fn residuals(&self, p : &MatrixF64) -> MatrixF64 {
+        (self.f)(p)
+    }
+}
+
+
+/// Algorithms for nonlinear least squares
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NonlinearMethod {
+    GaussNewton,
+    LevenbergMarquardt
+}
+
+
+/// Options of the nonlinear least squares solvers
+#[derive(Debug, Clone)]
+pub struct NonlinearOptions {
+    /// The algorithm
+    pub method : NonlinearMethod,
+    /// Stop when the relative reduction of the cost is below
+    pub function_tolerance : f64,
+    /// Stop when ||d|| <= parameter_tolerance (||p|| + parameter_tolerance)
+    pub parameter_tolerance : f64,
+    /// Stop when the (projected) gradient J'r is below in the infinity norm
+    pub gradient_tolerance : f64,
+    /// Maximum number of iterations
+    pub max_iterations : usize,
+    /// Optional lower bounds of the parameters
+    pub lower : Option<MatrixF64>,
+    /// Optional upper bounds of the parameters
+    pub upper : Option<MatrixF64>
+}
+
+impl NonlinearOptions {
+
+    /// Default options: Levenberg-Marquardt with tolerances of
+    /// 1e-10, at most 200 iterations and no bounds
+    pub fn new() -> NonlinearOptions {
+        NonlinearOptions {
+            method : NonlinearMethod::LevenbergMarquardt,
+            function_tolerance : 1e-10,
+            parameter_tolerance : 1e-10,
+            gradient_tolerance : 1e-10,
+            max_iterations : 200,
+            lower : None,
+            upper : None
+        }
+    }
+
+    /// Sets the algorithm
+    pub fn method(mut self, method : NonlinearMethod) -> NonlinearOptions {
+        self.method = method;
+        self
+    }
+
+    /// Sets the function tolerance
+    pub fn function_tolerance(mut self, tolerance : f64) -> NonlinearOptions {
+        self.function_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the parameter tolerance
+    pub fn parameter_tolerance(mut self, tolerance : f64) -> NonlinearOptions {
+        self.parameter_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the gradient tolerance
+    pub fn gradient_tolerance(mut self, tolerance : f64) -> NonlinearOptions {
+        self.gradient_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of iterations
+    pub fn max_iterations(mut self, max_iterations : usize) -> NonlinearOptions {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets bounds on the parameters. Infinite entries
+    /// leave a parameter unbounded on that side.
+    pub fn bounds(mut self, lower : &MatrixF64, upper : &MatrixF64) -> NonlinearOptions {
+        self.lower = Some(lower.clone());
+        self.upper = Some(upper.clone());
+        self
+    }
+
+    /// Returns the bounds as vectors of length n
+    fn box_bounds(&self, n : usize) -> SRResult<(Vec<f64>, Vec<f64>)> {
+        let expand = |b : &Option<MatrixF64>, default : f64| -> SRResult<Vec<f64>> {
+            match *b {
+                Some(ref v) => {
+                    if !v.is_col() || v.num_rows() != n {
+                        return Err(SRError::DimensionsMismatch);
+                    }
+                    Ok((0..n).map(|i| v[i]).collect())
+                },
+                None => Ok(vec![default; n])
+            }
+        };
+        let lower = expand(&self.lower, f64::NEG_INFINITY)?;
+        let upper = expand(&self.upper, f64::INFINITY)?;
+        if lower.iter().zip(upper.iter()).any(|(l, u)| l.is_nan() || u.is_nan() || l > u) {
+            return Err(SRError::InvalidArgument);
+        }
+        Ok((lower, upper))
+    }
+}
+
+impl Default for NonlinearOptions {
+    fn default() -> NonlinearOptions {
+        NonlinearOptions::new()
+    }
+}
+
+
+/// Solution of a nonlinear least squares problem
+#[derive(Debug, Clone)]
+pub struct NonlinearResult {
+    /// The estimated parameters
+    pub p : MatrixF64,
+    /// The residual vector r(p)
+    pub residual : MatrixF64,
+    /// The cost (1/2) ||r(p)||^2
+    pub cost : f64,
+    /// The Jacobian of the residuals at p
+    pub jacobian : MatrixF64,
+    /// Number of iterations performed
+    pub iterations : usize,
+    /// Number of residual evaluations (excluding those
+    /// made by finite difference Jacobians)
+    pub function_evaluations : usize,
+    /// Number of Jacobian evaluations
+    pub jacobian_evaluations : usize,
+    /// Indicates if a convergence criterion was met
+    pub converged : bool,
+    /// Reason for the termination
+    pub termination : Termination
+}
+
+
+/// Projects p onto the box
+fn project(p : &MatrixF64, lower : &[f64], upper : &[f64]) -> MatrixF64 {
+    let mut q = p.clone();
+    for i in 0..p.num_rows() {
+        q.set(i, 0, p[i].max(lower[i]).min(upper[i]));
+    }
+    q
+}
+
+
+/// Returns half the squared norm of r
+fn half_norm_sqr(r : &MatrixF64) -> f64 {
+    0.5 * r.inner_prod(r)
+}
+
+
+/// Computes the step on the free parameters and expands it
+/// to all parameters. For Levenberg-Marquardt ``damping``
+/// holds mu D.
+fn step(jac : &MatrixF64, r : &MatrixF64, free : &[usize], damping : Option<&[f64]>)
+    -> SRResult<MatrixF64> {
+    let m = jac.num_rows();
+    let n = jac.num_cols();
+    let k = free.len();
+    let extra = if damping.is_some() { k } else { 0 };
+    let mut a : MatrixF64 = Matrix::zeros(m + extra, k);
+    let mut b : MatrixF64 = Matrix::zeros(m + extra, 1);
+    for (c, &j) in free.iter().enumerate() {
+        for i in 0..m {
+            a.set(i, c, jac.get(i, j).unwrap());
+        }
+    }
+    for i in 0..m {
+        b.set(i, 0, -r[i]);
+    }
+    if let Some(md) = damping {
+        // Stacked system [J; sqrt(mu D)] d = [-r; 0]
+        for (c, &j) in free.iter().enumerate() {
+            a.set(m + c, c, md[j].sqrt());
+        }
+    }
+    let d = QRDecomposition::new_pivoted(&a).solve_least_squares(&b)?;
+    let mut full = Matrix::zeros(n, 1);
+    for (c, &j) in free.iter().enumerate() {
+        full.set(j, 0, d[c]);
+    }
+    Ok(full)
+}
+
+
+#[doc="Minimizes (1/2) ||r(p)||^2 starting from p0.
+
+The starting point is projected onto the bounds. Returns
+``InvalidArgument`` for inconsistent bounds and
+``DimensionsMismatch`` if the Jacobian does not have one row
+per residual and one column per parameter. Failure to meet
+the tolerances is reported through ``converged``.
+"]
+pub fn nonlinear_lstsq<R:Residuals + ?Sized>(f : &R, p0 : &MatrixF64,
+    options : &NonlinearOptions) -> SRResult<NonlinearResult> {
+    if !p0.is_col() {
+        return Err(SRError::IsNotAColVector);
+    }
+    let n = p0.num_rows();
+    if n == 0 {
+        return Err(SRError::EmptyMatrix);
+    }
+    let (lower, upper) = options.box_bounds(n)?;
+    let mut p = project(p0, &lower, &upper);
+    let mut r = f.residuals(&p);
+    let mut function_evaluations = 1;
+    let mut cost = half_norm_sqr(&r);
+    let mut jac = f.jacobian(&p);
+    let mut jacobian_evaluations = 1;
+    let m = r.num_rows();
+    if jac.num_rows() != m || jac.num_cols() != n {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let lm = options.method == NonlinearMethod::LevenbergMarquardt;
+    let mut scale = vec![0f64; n];
+    let mut mu = 1e-3;
+    let mut nu = 2.;
+    let mut termination = Termination::MaxIterations;
+    let mut iterations = 0;
+    while iterations < options.max_iterations {
+        let g = &jac.transpose() * &r;
+        // Free parameters and the projected gradient
+        let free : Vec<usize> = (0..n).filter(|&j| {
+            !((p[j] <= lower[j] && g[j] > 0.) || (p[j] >= upper[j] && g[j] < 0.))
+        }).collect();
+        let g_norm = free.iter().fold(0., |s : f64, &j| s.max(g[j].abs()));
+        if g_norm <= options.gradient_tolerance {
+            termination = Termination::GradientTolerance;
+            break;
+        }
+        iterations += 1;
+        let p_norm = p.inner_prod(&p).sqrt();
+        if lm {
+            for j in 0..n {
+                let c = (0..m).fold(0., |s, i| {
+                    let v = jac.get(i, j).unwrap();
+                    s + v * v
+                });
+                scale[j] = scale[j].max(c).max(1e-30);
+            }
+            let damping : Vec<f64> = scale.iter().map(|d| mu * d).collect();
+            let d = step(&jac, &r, &free, Some(&damping))?;
+            let trial = project(&(&p + &d), &lower, &upper);
+            let s = &trial - &p;
+            let s_norm = s.inner_prod(&s).sqrt();
+            let r_trial = f.residuals(&trial);
+            function_evaluations += 1;
+            let cost_trial = half_norm_sqr(&r_trial);
+            let predicted = cost - half_norm_sqr(&(&r + &(&jac * &s)));
+            let rho = if predicted > 0. { (cost - cost_trial) / predicted } else { -1. };
+            if rho > 0. {
+                let reduction = cost - cost_trial;
+                p = trial;
+                r = r_trial;
+                cost = cost_trial;
+                jac = f.jacobian(&p);
+                jacobian_evaluations += 1;
+                let t = 2. * rho - 1.;
+                mu *= (1. - t * t * t).max(1. / 3.);
+                nu = 2.;
+                if reduction <= options.function_tolerance * (cost + reduction) {
+                    termination = Termination::FunctionTolerance;
+                    break;
+                }
+            }
+            else {
+                mu *= nu;
+                nu *= 2.;
+            }
+            if s_norm <= options.parameter_tolerance * (p_norm + options.parameter_tolerance) {
+                termination = Termination::StepTolerance;
+                break;
+            }
+        }
+        else {
+            let d = step(&jac, &r, &free, None)?;
+            let mut t = 1.;
+            let mut accepted = None;
+            for _ in 0..MAX_BACKTRACKS {
+                let trial = project(&(&p + &d.copy_mul_scalar(t)), &lower, &upper);
+                let s = &trial - &p;
+                let r_trial = f.residuals(&trial);
+                function_evaluations += 1;
+                let cost_trial = half_norm_sqr(&r_trial);
+                if cost_trial <= cost + 1e-4 * g.inner_prod(&s) {
+                    accepted = Some((trial, s, r_trial, cost_trial));
+                    break;
+                }
+                t *= 0.5;
+            }
+            let (trial, s, r_trial, cost_trial) = match accepted {
+                Some(a) => a,
+                None => {
+                    termination = Termination::LineSearchFailure;
+                    break;
+                }
+            };
+            let reduction = cost - cost_trial;
+            let s_norm = s.inner_prod(&s).sqrt();
+            p = trial;
+            r = r_trial;
+            cost = cost_trial;
+            jac = f.jacobian(&p);
+            jacobian_evaluations += 1;
+            if s_norm <= options.parameter_tolerance * (p_norm + options.parameter_tolerance) {
+                termination = Termination::StepTolerance;
+                break;
+            }
+            if reduction <= options.function_tolerance * (cost + reduction) {
+                termination = Termination::FunctionTolerance;
+                break;
+            }
+        }
+    }
+    Ok(NonlinearResult {
+        p : p,
+        residual : r,
+        cost : cost,
+        jacobian : jac,
+        iterations : iterations,
+        function_evaluations : function_evaluations,
+        jacobian_evaluations : jacobian_evaluations,
+        converged : termination.is_converged(),
+        termination : termination
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    /// Rosenbrock's function as the residuals (10 (p2 - p1^2), 1 - p1)
+    struct RosenbrockResiduals;
+
+    impl Residuals for RosenbrockResiduals {
+        fn residuals(&self, p : &MatrixF64) -> MatrixF64 {
+            vector_f64(&[10. * (p[1] - p[0] * p[0]), 1. - p[0]])
+        }
+
+        fn jacobian(&self, p : &MatrixF64) -> MatrixF64 {
+            matrix_rw_f64(2, 2, &[-20. * p[0], 10., -1., 0.])
+        }
+    }
+
+    #[test]
+    fn test_rosenbrock_residuals(){
+        let p0 = vector_f64(&[-1.2, 1.]);
+        for &method in [NonlinearMethod::GaussNewton, NonlinearMethod::LevenbergMarquardt].iter() {
+            let r = nonlinear_lstsq(&RosenbrockResiduals, &p0,
+                &NonlinearOptions::new().method(method)).unwrap();
+            assert!(r.converged);
+            assert!((&r.p - &vector_f64(&[1., 1.])).max_abs_scalar_value() < 1e-9);
+            assert!(r.cost < 1e-20);
+        }
+    }
+
+    #[test]
+    fn test_forward_differences(){
+        let p = vector_f64(&[0.7, -0.3]);
+        let f = FnResiduals::new(|p : &MatrixF64| RosenbrockResiduals.residuals(p));
+        let fd = f.jacobian(&p);
+        let exact = RosenbrockResiduals.jacobian(&p);
+        assert!((&fd - &exact).max_abs_scalar_value() < 1e-6);
+    }
+
+    #[test]
+    fn test_bounds(){
+        // With p1 <= 0.5 the minimum is on the bound:
+        // p2 = p1^2 makes the first residual vanish
+        let p0 = vector_f64(&[0., 0.]);
+        let inf = f64::INFINITY;
+        let options = NonlinearOptions::new()
+            .bounds(&vector_f64(&[-inf, -inf]), &vector_f64(&[0.5, inf]));
+        for &method in [NonlinearMethod::GaussNewton, NonlinearMethod::LevenbergMarquardt].iter() {
+            let r = nonlinear_lstsq(&RosenbrockResiduals, &p0, &options.clone().method(method)).unwrap();
+            assert!(r.converged);
+            assert_eq!(r.p[0], 0.5);
+            assert!((r.p[1] - 0.25).abs() < 1e-9);
+        }
+        let bad = NonlinearOptions::new().bounds(&vector_f64(&[1., 0.]), &vector_f64(&[0., 1.]));
+        assert!(nonlinear_lstsq(&RosenbrockResiduals, &p0, &bad).is_err());
+    }
+
+    #[test]
+    fn test_iteration_limit(){
+        let p0 = vector_f64(&[-1.2, 1.]);
+        let r = nonlinear_lstsq(&RosenbrockResiduals, &p0,
+            &NonlinearOptions::new().max_iterations(1)).unwrap();
+        assert!(!r.converged);
+        assert_eq!(r.termination, Termination::MaxIterations);
+        assert_eq!(r.iterations, 1);
+    }
+}
@@ -0,0 +1,80 @@
+#![doc="Results and diagnostics of least squares solvers
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Solution of a least squares problem min ||A x - b||
+/// together with diagnostics
+#[derive(Debug, Clone)]
+pub struct LeastSquaresResult {
+    /// The solution vector
+    pub x : MatrixF64,
+    /// The residual vector b - A x
+    pub residual : MatrixF64,
+    /// The 2-norm of the residual (weighted for weighted problems)
+    pub residual_norm : f64,
+    /// The numerical rank of the coefficient matrix
+    pub rank : usize,
+    /// The residual degrees of freedom (number of observations
+    /// minus the number of effective parameters)
+    pub dof : f64,
+    /// Number of iterations (zero for direct methods)
+    pub iterations : usize
+}
+
+impl LeastSquaresResult {
+
+    /// Computes the residual of a solution and packs the diagnostics
+    pub fn new(a : &MatrixF64, b : &MatrixF64, x : MatrixF64,
+        rank : usize, iterations : usize) -> LeastSquaresResult {
+        let residual = b - &(a * &x);
+        let residual_norm = vec_norm_l2(&residual);
+        LeastSquaresResult {
+            x : x,
+            residual : residual,
+            residual_norm : residual_norm,
+            rank : rank,
+            dof : a.num_rows() as f64 - rank as f64,
+            iterations : iterations
+        }
+    }
+
+    /// Returns the residual sum of squares
+    pub fn rss(&self) -> f64 {
+        self.residual_norm * self.residual_norm
+    }
+
+    /// Returns the estimate rss / dof of the noise variance
+    pub fn residual_variance(&self) -> f64 {
+        if self.dof > 0. { self.rss() / self.dof } else { 0. }
+    }
+
+    /// Returns the coefficient of determination
+    /// R^2 = 1 - rss / ||b - mean(b)||^2
+    pub fn r_squared(&self, b : &MatrixF64) -> f64 {
+        let n = b.num_cells();
+        if n == 0 {
+            return 0.;
+        }
+        let mean = b.cell_iter().fold(0., |s, v| s + v) / n as f64;
+        let tss = b.cell_iter().fold(0., |s, v| s + (v - mean) * (v - mean));
+        if tss == 0. { 0. } else { 1. - self.rss() / tss }
+    }
+}
+
+
+/// Verifies that b is a column vector matching the rows of A
+pub fn check_ls_dimensions(a : &MatrixF64, b : &MatrixF64) -> SRResult<()> {
+    if b.num_cols() != 1 {
+        return Err(SRError::IsNotAColVector);
+    }
+    if b.num_rows() != a.num_rows() {
+        return Err(SRError::LRDimensionMismatch);
+    }
+    Ok(())
+}
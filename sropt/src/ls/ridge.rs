@@ -0,0 +1,330 @@
+#![doc="Tikhonov regularized least squares
+
+Ridge regression solves
+
+min ||A x - b||^2 + lambda ||x||^2
+
+through the singular value decomposition A = U diag(s) V'.
+The solution is x = V diag(s / (s^2 + lambda)) U' b.
+
+The regularization parameter can be selected automatically
+by minimizing the generalized cross validation (GCV) score
+
+GCV(lambda) = m ||A x - b||^2 / (m - dof(lambda))^2
+
+where dof(lambda) = sum s^2 / (s^2 + lambda) is the effective
+number of parameters.
+
+The general form min ||A x - b||^2 + lambda ||L x||^2 is
+solved by QR decomposition of the stacked matrix [A; sqrt(lambda) L].
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::svd::svd;
+use ls::result::*;
+use ls::linear::lstsq;
+
+
+/// Solution of a ridge regression problem
+#[derive(Debug, Clone)]
+pub struct RidgeResult {
+    /// The solution and its diagnostics. The degrees of freedom
+    /// are m minus the effective number of parameters.
+    pub result : LeastSquaresResult,
+    /// The regularization parameter
+    pub lambda : f64,
+    /// The effective number of parameters sum s^2 / (s^2 + lambda)
+    pub effective_dof : f64,
+    /// The generalized cross validation score
+    pub gcv : f64
+}
+
+
+/// Singular value decomposition of A along with U' b
+struct RidgeSystem {
+    v : MatrixF64,
+    s : Vec<f64>,
+    beta : Vec<f64>,
+    /// Squared norm of the component of b outside the range of U
+    outside : f64,
+    rows : usize
+}
+
+impl RidgeSystem {
+
+    fn new(a : &MatrixF64, b : &MatrixF64) -> SRResult<RidgeSystem> {
+        check_ls_dimensions(a, b)?;
+        let (u, s, v) = svd(a)?;
+        let beta = &u.transpose() * b;
+        let bb = b.cell_iter().fold(0., |acc, x| acc + x * x);
+        let btb = beta.cell_iter().fold(0., |acc, x| acc + x * x);
+        let outside = if bb > btb { bb - btb } else { 0. };
+        Ok(RidgeSystem {
+            v : v,
+            s : s.cell_iter().collect(),
+            beta : beta.cell_iter().collect(),
+            outside : outside,
+            rows : a.num_rows()
+        })
+    }
+
+    /// Returns (rss, effective dof, gcv) for a given lambda
+    fn score(&self, lambda : f64) -> (f64, f64, f64) {
+        let mut rss = self.outside;
+        let mut dof = 0.;
+        for i in 0..self.s.len() {
+            let s2 = self.s[i] * self.s[i];
+            let d = s2 + lambda;
+            if d == 0. {
+                continue;
+            }
+            let r = lambda / d * self.beta[i];
+            rss += r * r;
+            dof += s2 / d;
+        }
+        let m = self.rows as f64;
+        let gcv = if m > dof { m * rss / ((m - dof) * (m - dof)) } else { f64::INFINITY };
+        (rss, dof, gcv)
+    }
+
+    fn solve(&self, a : &MatrixF64, b : &MatrixF64, lambda : f64) -> RidgeResult {
+        let k = self.s.len();
+        let mut z : MatrixF64 = Matrix::zeros(k, 1);
+        let mut rank = 0;
+        let tolerance = self.tolerance();
+        for i in 0..k {
+            let s = self.s[i];
+            let d = s * s + lambda;
+            if s > tolerance {
+                rank += 1;
+            }
+            if d > 0. && s > tolerance {
+                z.set(i, 0, s / d * self.beta[i]);
+            }
+        }
+        let x = &self.v * &z;
+        let (_, dof, gcv) = self.score(lambda);
+        let mut result = LeastSquaresResult::new(a, b, x, rank, 0);
+        result.dof = self.rows as f64 - dof;
+        RidgeResult {
+            result : result,
+            lambda : lambda,
+            effective_dof : dof,
+            gcv : gcv
+        }
+    }
+
+    fn tolerance(&self) -> f64 {
+        let k = self.s.len();
+        if k == 0 {
+            return 0.;
+        }
+        let n = if self.rows > self.v.num_rows() { self.rows } else { self.v.num_rows() };
+        n as f64 * f64::EPSILON * self.s[0]
+    }
+}
+
+
+/// Solves min ||A x - b||^2 + lambda ||x||^2 for lambda >= 0
+pub fn ridge(a : &MatrixF64, b : &MatrixF64, lambda : f64) -> SRResult<RidgeResult> {
+    if lambda.is_nan() || lambda < 0. {
+        return Err(SRError::InvalidArgument);
+    }
+    let system = RidgeSystem::new(a, b)?;
+    Ok(system.solve(a, b, lambda))
+}
+
+
+/// Returns the generalized cross validation score of
+/// ridge regression with a given lambda
+pub fn gcv_score(a : &MatrixF64, b : &MatrixF64, lambda : f64) -> SRResult<f64> {
+    if lambda.is_nan() || lambda < 0. {
+        return Err(SRError::InvalidArgument);
+    }
+    let system = RidgeSystem::new(a, b)?;
+    Ok(system.score(lambda).2)
+}
+
+
+#[doc="Solves ridge regression with lambda chosen to minimize
+the GCV score.
+
+The score is evaluated on a logarithmic grid spanning
+the squared singular values of A and the best grid point
+is refined by golden section search in log(lambda).
+"]
+pub fn ridge_gcv(a : &MatrixF64, b : &MatrixF64) -> SRResult<RidgeResult> {
+    let system = RidgeSystem::new(a, b)?;
+    let s_max = if system.s.is_empty() { 0. } else { system.s[0] };
+    if s_max == 0. {
+        return Ok(system.solve(a, b, 0.));
+    }
+    let lo = (s_max * 1e-8).powi(2).ln();
+    let hi = (s_max * 1e2).powi(2).ln();
+    let points = 200;
+    let step = (hi - lo) / (points - 1) as f64;
+    let gcv = |t : f64| system.score(t.exp()).2;
+    let mut best = 0;
+    let mut best_score = f64::INFINITY;
+    for i in 0..points {
+        let score = gcv(lo + step * i as f64);
+        if score < best_score {
+            best_score = score;
+            best = i;
+        }
+    }
+    // Golden section search between the neighbours of the best point
+    let mut left = lo + step * (if best > 0 { best - 1 } else { 0 }) as f64;
+    let mut right = lo + step * (if best + 1 < points { best + 1 } else { best }) as f64;
+    let ratio = (5f64.sqrt() - 1.) / 2.;
+    let mut c = right - ratio * (right - left);
+    let mut d = left + ratio * (right - left);
+    let mut fc = gcv(c);
+    let mut fd = gcv(d);
+    for _ in 0..60 {
+        if fc < fd {
+            right = d;
+            d = c;
+            fd = fc;
+            c = right - ratio * (right - left);
+            fc = gcv(c);
+        }
+        else {
+            left = c;
+            c = d;
+            fc = fd;
+            d = left + ratio * (right - left);
+            fd = gcv(d);
+        }
+    }
+    let t = (left + right) / 2.;
+    let grid_t = lo + step * best as f64;
+    let t = if gcv(t) <= best_score { t } else { grid_t };
+    Ok(system.solve(a, b, t.exp()))
+}
+
+
+#[doc="Solves the general form Tikhonov problem
+
+min ||A x - b||^2 + lambda ||L x||^2
+
+where L is a p x n regularization matrix (e.g. a finite
+difference operator) and lambda >= 0.
+"]
+pub fn tikhonov(a : &MatrixF64, b : &MatrixF64, l : &MatrixF64, lambda : f64)
+    -> SRResult<LeastSquaresResult> {
+    check_ls_dimensions(a, b)?;
+    if l.num_cols() != a.num_cols() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    if lambda.is_nan() || lambda < 0. {
+        return Err(SRError::InvalidArgument);
+    }
+    let mut stacked = a.clone();
+    stacked.append_rows(&l.copy_mul_scalar(lambda.sqrt()));
+    let mut rhs = b.clone();
+    rhs.append_rows(&Matrix::zeros(l.num_rows(), 1));
+    let solution = lstsq(&stacked, &rhs)?;
+    Ok(LeastSquaresResult::new(a, b, solution.x, solution.rank, 0))
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn problem() -> (MatrixF64, MatrixF64) {
+        let a = matrix_rw_f64(5, 3, &[
+            1., 0., 1.,
+            1., 1., 0.,
+            1., 2., 1.,
+            1., 3., 0.,
+            1., 4., 1.
+            ]);
+        let b = vector_f64(&[1.1, 2.9, 5.2, 6.8, 9.1]);
+        (a, b)
+    }
+
+    #[test]
+    fn test_ridge_normal_equations(){
+        let (a, b) = problem();
+        let lambda = 0.7;
+        let result = ridge(&a, &b, lambda).unwrap();
+        // (A'A + lambda I) x = A'b
+        let at = a.transpose();
+        let mut m = &at * &a;
+        for i in 0..3 {
+            let x = m.get(i, i).unwrap() + lambda;
+            m.set(i, i, x);
+        }
+        let lhs = &m * &result.result.x;
+        let rhs = &at * &b;
+        assert!((&lhs - &rhs).max_abs_scalar_value() < 1e-12);
+        assert!(result.effective_dof < 3.);
+        assert!((result.result.dof - (5. - result.effective_dof)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_ridge_zero_lambda(){
+        let (a, b) = problem();
+        let result = ridge(&a, &b, 0.).unwrap();
+        let ls = lstsq(&a, &b).unwrap();
+        assert!((&result.result.x - &ls.x).max_abs_scalar_value() < 1e-12);
+        assert!((result.effective_dof - 3.).abs() < 1e-12);
+        assert!(ridge(&a, &b, -1.).is_err());
+        assert!(ridge(&a, &b, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_ridge_gcv(){
+        let (a, b) = problem();
+        let result = ridge_gcv(&a, &b).unwrap();
+        assert!(result.lambda > 0.);
+        // The selected lambda is a local minimum of the score
+        let score = gcv_score(&a, &b, result.lambda).unwrap();
+        assert!((score - result.gcv).abs() < 1e-12);
+        assert!(score <= gcv_score(&a, &b, result.lambda * 1.1).unwrap() + 1e-15);
+        assert!(score <= gcv_score(&a, &b, result.lambda / 1.1).unwrap() + 1e-15);
+    }
+
+    #[test]
+    fn test_ridge_single_observation(){
+        let a = matrix_rw_f64(1, 1, &[2.]);
+        let b = vector_f64(&[4.]);
+        // x = s b / (s^2 + lambda)
+        let result = ridge(&a, &b, 1.).unwrap();
+        assert!((result.result.x[0] - 1.6).abs() < 1e-14);
+        assert!((result.result.residual_norm - 0.8).abs() < 1e-14);
+        let result = ridge_gcv(&a, &b).unwrap();
+        assert!(result.result.x[0].is_finite());
+    }
+
+    #[test]
+    fn test_tikhonov(){
+        let (a, b) = problem();
+        // With L = I, Tikhonov reduces to ridge
+        let l : MatrixF64 = Matrix::identity(3, 3);
+        let x1 = tikhonov(&a, &b, &l, 0.7).unwrap().x;
+        let x2 = ridge(&a, &b, 0.7).unwrap().result.x;
+        assert!((&x1 - &x2).max_abs_scalar_value() < 1e-12);
+        // A first difference penalty pulls the coefficients together
+        let d = matrix_rw_f64(2, 3, &[
+            -1., 1., 0.,
+            0., -1., 1.
+            ]);
+        let x = tikhonov(&a, &b, &d, 1e8).unwrap().x;
+        assert!((x[0] - x[1]).abs() < 1e-5);
+        assert!((x[1] - x[2]).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,120 @@
+#![doc="Total least squares
+
+Ordinary least squares assumes that only the observations
+b carry errors. Total least squares allows errors in A as
+well and solves
+
+min ||[E f]||_F  subject to  (A + E) x = b + f.
+
+The solution follows from the singular value decomposition
+of the augmented matrix [A b]. If v is the right singular
+vector belonging to the smallest singular value, then
+x = -v[0..n] / v[n] and the norm of the smallest correction
+[E f] equals that singular value.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::svd::svd;
+use ls::result::*;
+
+
+/// Solution of a total least squares problem
+#[derive(Debug, Clone)]
+pub struct TotalLeastSquaresResult {
+    /// The solution and the ordinary residual b - A x
+    pub result : LeastSquaresResult,
+    /// Frobenius norm of the smallest correction [E f]
+    /// (the smallest singular value of [A b])
+    pub correction_norm : f64
+}
+
+
+#[doc="Solves the total least squares problem for A x ~ b.
+
+A must have at least n + 1 rows. Returns ``NoSolution`` if
+the smallest right singular vector of [A b] has a (nearly)
+zero last entry, in which case the problem is not solvable.
+"]
+pub fn tls(a : &MatrixF64, b : &MatrixF64) -> SRResult<TotalLeastSquaresResult> {
+    check_ls_dimensions(a, b)?;
+    let m = a.num_rows();
+    let n = a.num_cols();
+    if m < n + 1 {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let mut z = a.clone();
+    z.append_columns(b);
+    let (_, s, v) = svd(&z)?;
+    let last = v.get(n, n).unwrap();
+    if last.abs() <= (n + 1) as f64 * f64::EPSILON {
+        return Err(SRError::NoSolution);
+    }
+    let mut x : MatrixF64 = Matrix::zeros(n, 1);
+    for i in 0..n {
+        x.set(i, 0, -v.get(i, n).unwrap() / last);
+    }
+    // The rank of A is the number of significant singular values
+    // of [A b] less one when b is consistent
+    let tolerance = m as f64 * f64::EPSILON * s[0];
+    let rank = (0..n).filter(|&i| s[i] > tolerance).count();
+    Ok(TotalLeastSquaresResult {
+        result : LeastSquaresResult::new(a, b, x, rank, 0),
+        correction_norm : s[n]
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use ls::linear::lstsq;
+
+    #[test]
+    fn test_tls_consistent(){
+        // Exact data: TLS and LS agree
+        let a = matrix_rw_f64(4, 2, &[
+            1., 0.,
+            1., 1.,
+            1., 2.,
+            1., 3.
+            ]);
+        let b = vector_f64(&[1., 3., 5., 7.]);
+        let result = tls(&a, &b).unwrap();
+        assert!((&result.result.x - &vector_f64(&[1., 2.])).max_abs_scalar_value() < 1e-10);
+        assert!(result.correction_norm < 1e-12);
+    }
+
+    #[test]
+    fn test_tls_line_through_origin(){
+        // Fitting y = k t with errors in both t and y gives the
+        // slope of the principal axis of the points
+        let t = vector_f64(&[1., 2., 3.]);
+        let y = vector_f64(&[2., 1., 3.]);
+        let result = tls(&t, &y).unwrap();
+        // Principal axis of [t y] has direction of the dominant
+        // eigenvector of [[14, 13], [13, 14]], i.e. slope one
+        assert!((result.result.x[0] - 1.).abs() < 1e-12);
+        assert!((result.correction_norm - 1.).abs() < 1e-12);
+        // TLS slope differs from the LS slope 13 / 14
+        let ls = lstsq(&t, &y).unwrap();
+        assert!((ls.x[0] - 13. / 14.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_tls_dimensions(){
+        let a : MatrixF64 = Matrix::ones(2, 2);
+        let b = vector_f64(&[1., 2.]);
+        assert!(tls(&a, &b).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+#![doc="Minimization of scalar functions on an interval
+
+* ``golden_section``: shrinks the interval by the golden ratio
+  in each iteration, reusing one interior point.
+* ``brent_minimize``: parabolic interpolation through the
+  three best points found so far, safeguarded by golden
+  section steps (Brent, 1973).
+
+The function is assumed to be unimodal on [a, b]; otherwise
+a local minimizer is returned. Near a minimizer f changes
+only quadratically with x, so x can be located with a
+relative accuracy of about sqrt(eps). The methods stop once
+the minimizer is known to within sqrt(eps) |x| + x_tolerance.
+The f tolerance of ``ScalarOptions`` is not used.
+"]
+
+
+// std imports
+
+// external imports
+use num::traits::Float;
+
+// local imports
+use srmatrix::api::{SRError, SRResult};
+use roots::scalar::{ScalarOptions, ScalarResult};
+
+
+/// Converts an f64 constant to T
+fn constant<T:Float>(v : f64) -> T {
+    T::from(v).unwrap()
+}
+
+
+/// Verifies the search interval
+fn check_interval<T:Float>(a : T, b : T) -> SRResult<(T, T)> {
+    if !(a.is_finite() && b.is_finite()) || a == b {
+        return Err(SRError::InvalidArgument);
+    }
+    Ok(if a < b { (a, b) } else { (b, a) })
+}
+
+
+/// Minimizes f on [a, b] by golden section search
+pub fn golden_section<T:Float, F:Fn(T) -> T>(f : F, a : T, b : T,
+    options : &ScalarOptions<T>) -> SRResult<ScalarResult<T>> {
+    let (mut a, mut b) = check_interval(a, b)?;
+    let sqrt_eps = T::epsilon().sqrt();
+    let half = constant::<T>(0.5);
+    // 1 / phi
+    let r = constant::<T>(0.5 * (5f64.sqrt() - 1.));
+    let mut c = b - r * (b - a);
+    let mut d = a + r * (b - a);
+    let mut fc = f(c);
+    let mut fd = f(d);
+    for iteration in 1..(options.max_iterations + 1) {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - r * (b - a);
+            fc = f(c);
+        }
+        else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + r * (b - a);
+            fd = f(d);
+        }
+        let (x, fx) = if fc < fd { (c, fc) } else { (d, fd) };
+        if half * (b - a) <= sqrt_eps * x.abs() + options.x_tolerance {
+            return Ok(ScalarResult::new(x, fx, iteration, iteration + 2));
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/// Minimizes f on [a, b] by Brent's method
+pub fn brent_minimize<T:Float, F:Fn(T) -> T>(f : F, a : T, b : T,
+    options : &ScalarOptions<T>) -> SRResult<ScalarResult<T>> {
+    let (mut a, mut b) = check_interval(a, b)?;
+    let zero = T::zero();
+    let half = constant::<T>(0.5);
+    let two = constant::<T>(2.);
+    let sqrt_eps = T::epsilon().sqrt();
+    // 1 - 1 / phi
+    let golden = constant::<T>(0.5 * (3. - 5f64.sqrt()));
+    let third = constant::<T>(1. / 3.);
+    let mut x = a + golden * (b - a);
+    let (mut v, mut w) = (x, x);
+    let mut fx = f(x);
+    let (mut fv, mut fw) = (fx, fx);
+    let mut d = zero;
+    let mut e = zero;
+    for iteration in 0..(options.max_iterations + 1) {
+        let xm = half * (a + b);
+        let tol1 = sqrt_eps * x.abs() + third * options.x_tolerance;
+        let tol2 = two * tol1;
+        if (x - xm).abs() <= tol2 - half * (b - a) {
+            return Ok(ScalarResult::new(x, fx, iteration, iteration + 1));
+        }
+        if iteration == options.max_iterations {
+            break;
+        }
+        let mut golden_step = true;
+        if e.abs() > tol1 {
+            // Parabola through x, v and w
+            let r = (x - w) * (fx - fv);
+            let mut q = (x - v) * (fx - fw);
+            let mut p = (x - v) * q - (x - w) * r;
+            q = two * (q - r);
+            if q > zero {
+                p = -p;
+            }
+            else {
+                q = -q;
+            }
+            let e_old = e;
+            e = d;
+            if p.abs() < (half * q * e_old).abs() && p > q * (a - x) && p < q * (b - x) {
+                d = p / q;
+                let u = x + d;
+                if u - a < tol2 || b - u < tol2 {
+                    d = if xm > x { tol1 } else { -tol1 };
+                }
+                golden_step = false;
+            }
+        }
+        if golden_step {
+            e = if x >= xm { a - x } else { b - x };
+            d = golden * e;
+        }
+        let u = x + if d.abs() >= tol1 { d } else if d > zero { tol1 } else { -tol1 };
+        let fu = f(u);
+        if fu <= fx {
+            if u >= x {
+                a = x;
+            }
+            else {
+                b = x;
+            }
+            v = w;
+            fv = fw;
+            w = x;
+            fw = fx;
+            x = u;
+            fx = fu;
+        }
+        else {
+            if u < x {
+                a = u;
+            }
+            else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                v = w;
+                fv = fw;
+                w = u;
+                fw = fu;
+            }
+            else if fu <= fv || v == x || v == w {
+                v = u;
+                fv = fu;
+            }
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn quartic(x : f64) -> f64 {
+        // Minimum at x = 1.5 with value -1
+        (x - 1.5) * (x - 1.5) * (1. + (x - 1.5) * (x - 1.5)) - 1.
+    }
+
+    #[test]
+    fn test_golden_section(){
+        let options = ScalarOptions::new();
+        let r = golden_section(quartic, 0., 4., &options).unwrap();
+        assert!((r.x - 1.5).abs() < 1e-7);
+        assert!((r.value + 1.).abs() < 1e-14);
+        // The interval may be given in either order
+        let s = golden_section(quartic, 4., 0., &options).unwrap();
+        assert_eq!(r, s);
+        assert!(golden_section(quartic, 1., 1., &options).is_err());
+    }
+
+    #[test]
+    fn test_brent_minimize(){
+        let options = ScalarOptions::new();
+        let r = brent_minimize(quartic, 0., 4., &options).unwrap();
+        assert!((r.x - 1.5).abs() < 1e-7);
+        let g = golden_section(quartic, 0., 4., &options).unwrap();
+        assert!(r.function_evaluations < g.function_evaluations);
+        // Minimum at an end of the interval
+        let r = brent_minimize(|x : f64| x, 2., 3., &options).unwrap();
+        assert!((r.x - 2.).abs() < 1e-7);
+        match brent_minimize(quartic, 0., 4., &options.max_iterations(3)) {
+            Err(SRError::NotConverged) => {},
+            _ => panic!("expected NotConverged")
+        }
+    }
+
+    #[test]
+    fn test_single_precision(){
+        let options = ScalarOptions::<f32>::new().x_tolerance(1e-5);
+        let r = brent_minimize(|x : f32| x.sin(), 3., 6., &options).unwrap();
+        assert!((r.x - 4.712389).abs() < 1e-3);
+        let g = golden_section(|x : f32| x.sin(), 3., 6., &options).unwrap();
+        assert!((g.x - 4.712389).abs() < 1e-3);
+    }
+}
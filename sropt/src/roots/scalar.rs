@@ -0,0 +1,462 @@
+#![doc="Roots of scalar functions
+
+Bracketing methods start from an interval [a, b] with
+f(a) f(b) <= 0 and keep a sign change in every iteration:
+
+* ``bisection``: halves the interval; linear convergence
+  with rate 1/2 but guaranteed.
+* ``brent``: inverse quadratic interpolation and secant
+  steps safeguarded by bisection (Brent, 1973).
+* ``ridder``: exponential fit through the end points and
+  the midpoint (Ridders, 1979); quadratic convergence.
+
+Open methods start from one or two points and converge
+faster when close to a simple root but may diverge:
+
+* ``newton``: x+ = x - f(x) / f'(x).
+* ``secant``: Newton's method with the derivative replaced
+  by a difference quotient of the last two iterates.
+
+All methods stop once |f(x)| <= f_tolerance or the
+uncertainty in x is below x_tolerance + 2 eps |x|.
+Failures are reported as errors: ``InvalidArgument`` if the
+interval does not bracket a root, ``IsSingular`` for a zero
+derivative (or difference quotient) and ``NotConverged`` if
+the iteration limit is reached or the iterates are not
+finite.
+"]
+
+
+// std imports
+
+// external imports
+use num::traits::Float;
+
+// local imports
+use srmatrix::api::{SRError, SRResult};
+
+
+/// Converts an f64 constant to T
+fn constant<T:Float>(v : f64) -> T {
+    T::from(v).unwrap()
+}
+
+
+/// Options of the scalar root finders and minimizers
+#[derive(Debug, Clone, Copy)]
+pub struct ScalarOptions<T:Float> {
+    /// Absolute tolerance on x
+    pub x_tolerance : T,
+    /// Tolerance on |f(x)| (root finders only)
+    pub f_tolerance : T,
+    /// Maximum number of iterations
+    pub max_iterations : usize
+}
+
+impl<T:Float> ScalarOptions<T> {
+
+    /// Default options: x tolerance of 2 eps, f tolerance
+    /// of zero and at most 200 iterations
+    pub fn new() -> ScalarOptions<T> {
+        ScalarOptions {
+            x_tolerance : T::epsilon() + T::epsilon(),
+            f_tolerance : T::zero(),
+            max_iterations : 200
+        }
+    }
+
+    /// Sets the x tolerance
+    pub fn x_tolerance(mut self, tolerance : T) -> ScalarOptions<T> {
+        self.x_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the f tolerance
+    pub fn f_tolerance(mut self, tolerance : T) -> ScalarOptions<T> {
+        self.f_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of iterations
+    pub fn max_iterations(mut self, max_iterations : usize) -> ScalarOptions<T> {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Returns the tolerance on x at x
+    fn tolerance_at(&self, x : T) -> T {
+        self.x_tolerance + constant::<T>(2.) * T::epsilon() * x.abs()
+    }
+}
+
+impl<T:Float> Default for ScalarOptions<T> {
+    fn default() -> ScalarOptions<T> {
+        ScalarOptions::new()
+    }
+}
+
+
+/// A root or minimizer of a scalar function
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScalarResult<T:Float> {
+    /// The root or minimizer
+    pub x : T,
+    /// The function value at x
+    pub value : T,
+    /// Number of iterations performed
+    pub iterations : usize,
+    /// Number of function evaluations
+    pub function_evaluations : usize
+}
+
+impl<T:Float> ScalarResult<T> {
+
+    /// Packs a result
+    pub fn new(x : T, value : T, iterations : usize,
+        function_evaluations : usize) -> ScalarResult<T> {
+        ScalarResult {
+            x : x,
+            value : value,
+            iterations : iterations,
+            function_evaluations : function_evaluations
+        }
+    }
+}
+
+
+/// Evaluates f at the ends of a bracket and verifies the sign change
+fn check_bracket<T:Float, F:Fn(T) -> T>(f : &F, a : T, b : T) -> SRResult<(T, T)> {
+    if !(a.is_finite() && b.is_finite()) {
+        return Err(SRError::InvalidArgument);
+    }
+    let fa = f(a);
+    let fb = f(b);
+    if fa.is_nan() || fb.is_nan() || fa * fb > T::zero() {
+        return Err(SRError::InvalidArgument);
+    }
+    Ok((fa, fb))
+}
+
+
+/// Finds a root of f in [a, b] by bisection
+pub fn bisection<T:Float, F:Fn(T) -> T>(f : F, a : T, b : T,
+    options : &ScalarOptions<T>) -> SRResult<ScalarResult<T>> {
+    let (mut fa, fb) = check_bracket(&f, a, b)?;
+    if fa == T::zero() {
+        return Ok(ScalarResult::new(a, fa, 0, 2));
+    }
+    if fb == T::zero() {
+        return Ok(ScalarResult::new(b, fb, 0, 2));
+    }
+    let half = constant::<T>(0.5);
+    let (mut a, mut b) = (a, b);
+    for iteration in 1..(options.max_iterations + 1) {
+        let x = a + half * (b - a);
+        let fx = f(x);
+        if fx.abs() <= options.f_tolerance || half * (b - a).abs() <= options.tolerance_at(x) {
+            return Ok(ScalarResult::new(x, fx, iteration, iteration + 2));
+        }
+        if (fx < T::zero()) == (fa < T::zero()) {
+            a = x;
+            fa = fx;
+        }
+        else {
+            b = x;
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/// Finds a root of f in [a, b] by Brent's method
+pub fn brent<T:Float, F:Fn(T) -> T>(f : F, a : T, b : T,
+    options : &ScalarOptions<T>) -> SRResult<ScalarResult<T>> {
+    let (mut fa, mut fb) = check_bracket(&f, a, b)?;
+    let zero = T::zero();
+    let half = constant::<T>(0.5);
+    let two = constant::<T>(2.);
+    let three = constant::<T>(3.);
+    let (mut a, mut b) = (a, b);
+    let (mut c, mut fc) = (b, fb);
+    let mut d = b - a;
+    let mut e = d;
+    for iteration in 0..(options.max_iterations + 1) {
+        if (fb > zero) == (fc > zero) {
+            // Keep the sign change between b and c
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol = half * options.tolerance_at(b);
+        let m = half * (c - b);
+        if fb.abs() <= options.f_tolerance || m.abs() <= tol {
+            return Ok(ScalarResult::new(b, fb, iteration, iteration + 2));
+        }
+        if iteration == options.max_iterations {
+            break;
+        }
+        if e.abs() < tol || fa.abs() <= fb.abs() {
+            d = m;
+            e = m;
+        }
+        else {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // Secant step
+                (two * m * s, T::one() - s)
+            }
+            else {
+                // Inverse quadratic interpolation
+                let q = fa / fc;
+                let r = fb / fc;
+                (s * (two * m * q * (q - r) - (b - a) * (r - T::one())),
+                    (q - T::one()) * (r - T::one()) * (s - T::one()))
+            };
+            if p > zero {
+                q = -q;
+            }
+            else {
+                p = -p;
+            }
+            if two * p < (three * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            }
+            else {
+                d = m;
+                e = m;
+            }
+        }
+        a = b;
+        fa = fb;
+        b = b + if d.abs() > tol { d } else if m > zero { tol } else { -tol };
+        fb = f(b);
+        if !fb.is_finite() {
+            break;
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/// Finds a root of f in [a, b] by Ridders' method
+pub fn ridder<T:Float, F:Fn(T) -> T>(f : F, a : T, b : T,
+    options : &ScalarOptions<T>) -> SRResult<ScalarResult<T>> {
+    let (mut fa, mut fb) = check_bracket(&f, a, b)?;
+    if fa == T::zero() {
+        return Ok(ScalarResult::new(a, fa, 0, 2));
+    }
+    if fb == T::zero() {
+        return Ok(ScalarResult::new(b, fb, 0, 2));
+    }
+    let zero = T::zero();
+    let half = constant::<T>(0.5);
+    let (mut a, mut b) = (a, b);
+    let mut evaluations = 2;
+    let mut x_old = T::nan();
+    for iteration in 1..(options.max_iterations + 1) {
+        let xm = a + half * (b - a);
+        let fm = f(xm);
+        let s = (fm * fm - fa * fb).sqrt();
+        evaluations += 1;
+        if s == zero {
+            return Ok(ScalarResult::new(xm, fm, iteration, evaluations));
+        }
+        let sign = if fa > fb { T::one() } else { -T::one() };
+        let x = xm + (xm - a) * sign * fm / s;
+        let fx = f(x);
+        evaluations += 1;
+        if !fx.is_finite() {
+            break;
+        }
+        // Smallest bracket among a, xm, x, b
+        if (fm < zero) != (fx < zero) {
+            a = xm;
+            fa = fm;
+            b = x;
+            fb = fx;
+        }
+        else if (fa < zero) != (fx < zero) {
+            b = x;
+            fb = fx;
+        }
+        else {
+            a = x;
+            fa = fx;
+        }
+        if fx.abs() <= options.f_tolerance || half * (b - a).abs() <= options.tolerance_at(x)
+            || (x - x_old).abs() <= options.tolerance_at(x) {
+            return Ok(ScalarResult::new(x, fx, iteration, evaluations));
+        }
+        x_old = x;
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/// Finds a root of f by Newton's method starting from x0
+/// given the derivative df
+pub fn newton<T:Float, F:Fn(T) -> T, D:Fn(T) -> T>(f : F, df : D, x0 : T,
+    options : &ScalarOptions<T>) -> SRResult<ScalarResult<T>> {
+    let mut x = x0;
+    let mut fx = f(x);
+    let mut evaluations = 1;
+    for iteration in 0..(options.max_iterations + 1) {
+        if !fx.is_finite() {
+            break;
+        }
+        if fx.abs() <= options.f_tolerance {
+            return Ok(ScalarResult::new(x, fx, iteration, evaluations));
+        }
+        if iteration == options.max_iterations {
+            break;
+        }
+        let d = df(x);
+        if d == T::zero() {
+            return Err(SRError::IsSingular);
+        }
+        let step = fx / d;
+        x = x - step;
+        fx = f(x);
+        evaluations += 1;
+        if step.abs() <= options.tolerance_at(x) && fx.is_finite() {
+            return Ok(ScalarResult::new(x, fx, iteration + 1, evaluations));
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/// Finds a root of f by the secant method starting from x0 and x1
+pub fn secant<T:Float, F:Fn(T) -> T>(f : F, x0 : T, x1 : T,
+    options : &ScalarOptions<T>) -> SRResult<ScalarResult<T>> {
+    let (mut x_old, mut x) = (x0, x1);
+    let mut f_old = f(x_old);
+    let mut fx = f(x);
+    let mut evaluations = 2;
+    for iteration in 0..(options.max_iterations + 1) {
+        if !fx.is_finite() {
+            break;
+        }
+        if fx.abs() <= options.f_tolerance {
+            return Ok(ScalarResult::new(x, fx, iteration, evaluations));
+        }
+        if iteration == options.max_iterations {
+            break;
+        }
+        if fx == f_old {
+            return Err(SRError::IsSingular);
+        }
+        let step = fx * (x - x_old) / (fx - f_old);
+        x_old = x;
+        f_old = fx;
+        x = x - step;
+        fx = f(x);
+        evaluations += 1;
+        if step.abs() <= options.tolerance_at(x) && fx.is_finite() {
+            return Ok(ScalarResult::new(x, fx, iteration + 1, evaluations));
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn cubic(x : f64) -> f64 {
+        // Single real root at 2.0945514815423265
+        x * x * x - 2. * x - 5.
+    }
+
+    const CUBIC_ROOT : f64 = 2.0945514815423265;
+
+    #[test]
+    fn test_bracketing(){
+        let options = ScalarOptions::new();
+        let b = bisection(cubic, 2., 3., &options).unwrap();
+        let r = brent(cubic, 2., 3., &options).unwrap();
+        let s = ridder(cubic, 2., 3., &options).unwrap();
+        for result in [b, r, s].iter() {
+            assert!((result.x - CUBIC_ROOT).abs() < 1e-14);
+            assert_eq!(result.value, cubic(result.x));
+        }
+        // Interpolation needs far fewer evaluations than bisection
+        assert!(b.iterations > 40);
+        assert!(r.function_evaluations < 15);
+        assert!(s.function_evaluations < 15);
+    }
+
+    #[test]
+    fn test_bracket_errors(){
+        let options = ScalarOptions::new();
+        assert!(bisection(cubic, 3., 4., &options).is_err());
+        assert!(brent(cubic, 0., 1., &options).is_err());
+        assert!(ridder(cubic, f64::NAN, 3., &options).is_err());
+        // Too few iterations for the tolerance
+        match bisection(cubic, 2., 3., &options.max_iterations(5)) {
+            Err(SRError::NotConverged) => {},
+            _ => panic!("expected NotConverged")
+        }
+        // A root at an end point
+        let r = brent(|x : f64| x - 1., 1., 2., &options).unwrap();
+        assert_eq!(r.x, 1.);
+    }
+
+    #[test]
+    fn test_tolerances(){
+        let options = ScalarOptions::new().x_tolerance(1e-6);
+        let r = bisection(cubic, 2., 3., &options).unwrap();
+        assert!((r.x - CUBIC_ROOT).abs() < 1e-6);
+        assert!(r.iterations < 21);
+        let options = ScalarOptions::new().f_tolerance(1e-3);
+        let r = brent(cubic, 2., 3., &options).unwrap();
+        assert!(r.value.abs() <= 1e-3);
+    }
+
+    #[test]
+    fn test_open_methods(){
+        let options = ScalarOptions::new();
+        let n = newton(cubic, |x : f64| 3. * x * x - 2., 2., &options).unwrap();
+        assert!((n.x - CUBIC_ROOT).abs() < 1e-14);
+        assert!(n.iterations < 8);
+        let s = secant(cubic, 2., 3., &options).unwrap();
+        assert!((s.x - CUBIC_ROOT).abs() < 1e-14);
+        assert!(s.iterations < 12);
+        // Zero derivative at the start
+        match newton(|x : f64| x * x - 1., |x : f64| 2. * x, 0., &options) {
+            Err(SRError::IsSingular) => {},
+            _ => panic!("expected IsSingular")
+        }
+        // No real root
+        match newton(|x : f64| x * x + 1., |x : f64| 2. * x, 0.5, &options.max_iterations(50)) {
+            Err(SRError::NotConverged) | Err(SRError::IsSingular) => {},
+            _ => panic!("expected a convergence error")
+        }
+    }
+
+    #[test]
+    fn test_single_precision(){
+        let options = ScalarOptions::<f32>::new();
+        let r = brent(|x : f32| x.cos() - x, 0., 1., &options).unwrap();
+        assert!((r.x - 0.73908513).abs() < 1e-6);
+        let n = newton(|x : f32| x.cos() - x, |x : f32| -x.sin() - 1., 1., &options).unwrap();
+        assert!((n.x - r.x).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,340 @@
+#![doc="Systems of nonlinear equations F(x) = 0
+
+F maps R^n to R^n and is given as ``Residuals`` (see
+``ls::nonlinear``), so the Jacobian is approximated by
+forward differences unless F supplies one.
+
+* ``newton_system``: solves J(x) s = -F(x) by LU in every
+  iteration.
+* ``broyden``: Broyden's (good) method evaluates the Jacobian
+  once and then applies the rank one secant updates
+  B+ = B + (y - B s) s' / s's. The Jacobian is evaluated again
+  when an updated matrix fails to give a decrease.
+
+Both methods halve the step until ||F|| decreases. They stop
+with success once ||F(x)|| <= tolerance in the infinity norm.
+Failures are errors: ``IsSingular`` for a singular Jacobian,
+``NotConverged`` if the iteration limit is reached or no step
+decreases ||F||, and ``DimensionsMismatch`` if F is not
+square.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::lu::LUDecomposition;
+use srlinalg::factorization::Factorization;
+use ls::nonlinear::Residuals;
+
+
+/// Maximum number of step halvings
+const MAX_BACKTRACKS : usize = 30;
+
+
+/// Options of the solvers for nonlinear systems
+#[derive(Debug, Clone, Copy)]
+pub struct SystemOptions {
+    /// Stop when ||F(x)|| is below in the infinity norm
+    pub tolerance : f64,
+    /// Maximum number of iterations
+    pub max_iterations : usize
+}
+
+impl SystemOptions {
+
+    /// Default options: tolerance 1e-10 and at most 100 iterations
+    pub fn new() -> SystemOptions {
+        SystemOptions {
+            tolerance : 1e-10,
+            max_iterations : 100
+        }
+    }
+
+    /// Sets the tolerance
+    pub fn tolerance(mut self, tolerance : f64) -> SystemOptions {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of iterations
+    pub fn max_iterations(mut self, max_iterations : usize) -> SystemOptions {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
+impl Default for SystemOptions {
+    fn default() -> SystemOptions {
+        SystemOptions::new()
+    }
+}
+
+
+/// Solution of a nonlinear system
+#[derive(Debug, Clone)]
+pub struct SystemResult {
+    /// The solution
+    pub x : MatrixF64,
+    /// F(x)
+    pub residual : MatrixF64,
+    /// ||F(x)|| in the infinity norm
+    pub residual_norm : f64,
+    /// Number of iterations performed
+    pub iterations : usize,
+    /// Number of evaluations of F (excluding those made by
+    /// finite difference Jacobians)
+    pub function_evaluations : usize,
+    /// Number of Jacobian evaluations
+    pub jacobian_evaluations : usize
+}
+
+
+/// Evaluation counts and convergence test shared by the solvers
+struct State {
+    x : MatrixF64,
+    fx : MatrixF64,
+    /// (1/2) ||F(x)||^2
+    merit : f64,
+    function_evaluations : usize,
+    jacobian_evaluations : usize
+}
+
+impl State {
+
+    fn new<R:Residuals + ?Sized>(f : &R, x0 : &MatrixF64) -> SRResult<State> {
+        if !x0.is_col() {
+            return Err(SRError::IsNotAColVector);
+        }
+        if x0.num_rows() == 0 {
+            return Err(SRError::EmptyMatrix);
+        }
+        let fx = f.residuals(x0);
+        if fx.num_rows() != x0.num_rows() || !fx.is_col() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(State {
+            x : x0.clone(),
+            merit : 0.5 * fx.inner_prod(&fx),
+            fx : fx,
+            function_evaluations : 1,
+            jacobian_evaluations : 0
+        })
+    }
+
+    fn jacobian<R:Residuals + ?Sized>(&mut self, f : &R) -> SRResult<MatrixF64> {
+        let jac = f.jacobian(&self.x);
+        self.jacobian_evaluations += 1;
+        let n = self.x.num_rows();
+        if jac.num_rows() != n || jac.num_cols() != n {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(jac)
+    }
+
+    fn residual_norm(&self) -> f64 {
+        self.fx.max_abs_scalar_value()
+    }
+
+    /// Returns true if F(x) is finite and meets the tolerance
+    fn converged(&self, options : &SystemOptions) -> bool {
+        self.fx.cell_iter().all(|v| v.is_finite())
+            && self.residual_norm() <= options.tolerance
+    }
+
+    /// Tries x + t d for t = 1, 1/2, ... until ||F|| decreases.
+    /// Returns the step taken.
+    fn backtrack<R:Residuals + ?Sized>(&mut self, f : &R, d : &MatrixF64)
+        -> Option<MatrixF64> {
+        let mut t = 1.;
+        for _ in 0..MAX_BACKTRACKS {
+            let s = d.copy_mul_scalar(t);
+            let trial = &self.x + &s;
+            let ft = f.residuals(&trial);
+            self.function_evaluations += 1;
+            let merit = 0.5 * ft.inner_prod(&ft);
+            // Sufficient decrease of (1/2) ||F||^2 along a Newton
+            // direction, whose slope is -||F||^2
+            if merit <= (1. - 2e-4 * t) * self.merit {
+                self.x = trial;
+                self.fx = ft;
+                self.merit = merit;
+                return Some(s);
+            }
+            t *= 0.5;
+        }
+        None
+    }
+
+    fn result(self, iterations : usize) -> SystemResult {
+        SystemResult {
+            residual_norm : self.residual_norm(),
+            x : self.x,
+            residual : self.fx,
+            iterations : iterations,
+            function_evaluations : self.function_evaluations,
+            jacobian_evaluations : self.jacobian_evaluations
+        }
+    }
+}
+
+
+/// Solves B d = -F with an LU factorization of B
+fn newton_step(b : &MatrixF64, fx : &MatrixF64) -> SRResult<MatrixF64> {
+    let mut lu = LUDecomposition::new(b.clone());
+    lu.decompose_ero();
+    if lu.is_singular() {
+        return Err(SRError::IsSingular);
+    }
+    let d = lu.solve(&fx.unary_minus())?;
+    if d.cell_iter().any(|v| !v.is_finite()) {
+        return Err(SRError::IsSingular);
+    }
+    Ok(d)
+}
+
+
+/// Solves F(x) = 0 by Newton's method starting from x0
+pub fn newton_system<R:Residuals + ?Sized>(f : &R, x0 : &MatrixF64,
+    options : &SystemOptions) -> SRResult<SystemResult> {
+    let mut state = State::new(f, x0)?;
+    for iteration in 0..(options.max_iterations + 1) {
+        if state.converged(options) {
+            return Ok(state.result(iteration));
+        }
+        if iteration == options.max_iterations {
+            break;
+        }
+        let jac = state.jacobian(f)?;
+        let d = newton_step(&jac, &state.fx)?;
+        if state.backtrack(f, &d).is_none() {
+            break;
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/// Solves F(x) = 0 by Broyden's method starting from x0
+pub fn broyden<R:Residuals + ?Sized>(f : &R, x0 : &MatrixF64,
+    options : &SystemOptions) -> SRResult<SystemResult> {
+    let mut state = State::new(f, x0)?;
+    let mut b = state.jacobian(f)?;
+    let mut fresh = true;
+    for iteration in 0..(options.max_iterations + 1) {
+        if state.converged(options) {
+            return Ok(state.result(iteration));
+        }
+        if iteration == options.max_iterations {
+            break;
+        }
+        let fx = state.fx.clone();
+        let step = match newton_step(&b, &fx) {
+            Ok(d) => state.backtrack(f, &d),
+            Err(err) => {
+                if fresh {
+                    return Err(err);
+                }
+                None
+            }
+        };
+        match step {
+            Some(s) => {
+                // B+ = B + (y - B s) s' / s's
+                let y = &state.fx - &fx;
+                let u = &y - &(&b * &s);
+                let ss = s.inner_prod(&s);
+                b = &b + &(&u * &s.transpose()).copy_mul_scalar(1. / ss);
+                fresh = false;
+            },
+            None => {
+                if fresh {
+                    break;
+                }
+                // Restart from the true Jacobian
+                b = state.jacobian(f)?;
+                fresh = true;
+            }
+        }
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use ls::nonlinear::FnResiduals;
+
+    /// x^2 + y^2 = 4, e^x + y = 1
+    struct Circle;
+
+    impl Residuals for Circle {
+        fn residuals(&self, p : &MatrixF64) -> MatrixF64 {
+            vector_f64(&[p[0] * p[0] + p[1] * p[1] - 4., p[0].exp() + p[1] - 1.])
+        }
+
+        fn jacobian(&self, p : &MatrixF64) -> MatrixF64 {
+            matrix_rw_f64(2, 2, &[2. * p[0], 2. * p[1], p[0].exp(), 1.])
+        }
+    }
+
+    #[test]
+    fn test_newton_system(){
+        let x0 = vector_f64(&[1., -1.]);
+        let r = newton_system(&Circle, &x0, &SystemOptions::new()).unwrap();
+        assert!(r.residual_norm <= 1e-10);
+        assert!((r.x[0] - 1.0041687384746592).abs() < 1e-9);
+        assert!((r.x[1] + 1.72963728702587).abs() < 1e-9);
+        assert_eq!(r.jacobian_evaluations, r.iterations);
+        assert!(r.iterations < 8);
+        // Finite difference Jacobian
+        let f = FnResiduals::new(|p : &MatrixF64| Circle.residuals(p));
+        let s = newton_system(&f, &x0, &SystemOptions::new()).unwrap();
+        assert!((&s.x - &r.x).max_abs_scalar_value() < 1e-9);
+    }
+
+    #[test]
+    fn test_broyden(){
+        let x0 = vector_f64(&[1., -1.]);
+        let r = broyden(&Circle, &x0, &SystemOptions::new()).unwrap();
+        assert!(r.residual_norm <= 1e-10);
+        assert!((r.x[0] - 1.0041687384746592).abs() < 1e-9);
+        // Far fewer Jacobians than iterations
+        assert!(r.jacobian_evaluations < r.iterations);
+    }
+
+    #[test]
+    fn test_errors(){
+        // Singular Jacobian: F does not depend on the second variable
+        let f = FnResiduals::new(|p : &MatrixF64| vector_f64(&[p[0] - 1., p[0] * p[0] - 1.]));
+        let origin = vector_f64(&[0., 0.]);
+        match newton_system(&f, &origin, &SystemOptions::new()) {
+            Err(SRError::IsSingular) => {},
+            _ => panic!("expected IsSingular")
+        }
+        // No solution: x^2 + 1 = 0
+        let g = FnResiduals::new(|p : &MatrixF64| vector_f64(&[p[0] * p[0] + 1.]));
+        match newton_system(&g, &vector_f64(&[1.]), &SystemOptions::new()) {
+            Err(SRError::NotConverged) => {},
+            _ => panic!("expected NotConverged")
+        }
+        match broyden(&g, &vector_f64(&[1.]), &SystemOptions::new()) {
+            Err(SRError::NotConverged) => {},
+            _ => panic!("expected NotConverged")
+        }
+        // Not square
+        let h = FnResiduals::new(|p : &MatrixF64| vector_f64(&[p[0], p[1], 1.]));
+        assert!(broyden(&h, &origin, &SystemOptions::new()).is_err());
+        // Too few iterations
+        let x0 = vector_f64(&[1., -1.]);
+        assert!(newton_system(&Circle, &x0, &SystemOptions::new().max_iterations(1)).is_err());
+    }
+}
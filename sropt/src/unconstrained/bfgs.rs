@@ -0,0 +1,125 @@
+#![doc="The BFGS quasi-Newton method
+
+BFGS maintains an approximation H of the inverse Hessian and
+searches along p = -H g. After a step s with gradient change
+y the approximation is updated by
+
+H+ = (I - rho s y') H (I - rho y s') + rho s s',  rho = 1 / (y' s).
+
+The strong Wolfe line search guarantees y' s > 0 so that H
+stays positive definite. Before the first update H is scaled
+by y' s / y' y. Updates with too little curvature are
+skipped. Convergence is superlinear near a minimizer with a
+positive definite Hessian.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use unconstrained::line_search::LineSearch;
+use unconstrained::descent::*;
+
+
+/// Inverse Hessian approximation
+struct InverseHessian {
+    /// None until the first update (H = I)
+    h : Option<MatrixF64>
+}
+
+impl SearchDirection for InverseHessian {
+
+    fn direction<O:Objective + ?Sized>(&mut self, _f : &O, _x : &MatrixF64,
+        g : &MatrixF64) -> MatrixF64 {
+        match self.h {
+            Some(ref h) => (h * g).unary_minus(),
+            None => g.unary_minus()
+        }
+    }
+
+    fn update(&mut self, s : &MatrixF64, y : &MatrixF64) {
+        let sy = s.inner_prod(y);
+        if sy.is_nan() || sy <= 1e-10 * vec_norm_l2(s) * vec_norm_l2(y) {
+            return;
+        }
+        let n = s.num_rows();
+        let mut h = match self.h.take() {
+            Some(h) => h,
+            None => Matrix::identity(n, n).copy_mul_scalar(sy / y.inner_prod(y))
+        };
+        let rho = 1. / sy;
+        let hy = &h * y;
+        let c = rho * rho * y.inner_prod(&hy) + rho;
+        for r in 0..n {
+            for k in 0..n {
+                let v = h.get(r, k).unwrap()
+                    - rho * (s[r] * hy[k] + hy[r] * s[k])
+                    + c * s[r] * s[k];
+                h.set(r, k, v);
+            }
+        }
+        self.h = Some(h);
+    }
+
+    fn reset(&mut self) {
+        self.h = None;
+    }
+
+    fn initial_step(&self, g : &MatrixF64, _p : &MatrixF64,
+        _previous : Option<(f64, f64)>) -> f64 {
+        match self.h {
+            Some(_) => 1.,
+            None => 1. / g.max_abs_scalar_value().max(1.)
+        }
+    }
+}
+
+
+/// Minimizes f by the BFGS method starting from x0
+pub fn bfgs<O:Objective + ?Sized>(f : &O, x0 : &MatrixF64,
+    options : &MinimizerOptions) -> SRResult<MinimizeResult> {
+    descent(f, x0, &mut InverseHessian {h : None}, LineSearch::wolfe(), options)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use unconstrained::objective::*;
+
+    #[test]
+    fn test_bfgs_rosenbrock(){
+        let x0 = vector_f64(&[-1.2, 1.]);
+        let r = bfgs(&Rosenbrock, &x0, &MinimizerOptions::new()).unwrap();
+        assert!(r.converged);
+        assert!((&r.x - &vector_f64(&[1., 1.])).max_abs_scalar_value() < 1e-6);
+        assert!(r.iterations < 100);
+        assert_eq!(r.log.last().unwrap().value, r.value);
+    }
+
+    #[test]
+    fn test_bfgs_quadratic(){
+        // On a quadratic with exact line searches BFGS terminates
+        // in at most n steps; inexact searches need a few more
+        let a = matrix_rw_f64(3, 3, &[
+            4., 1., 0.,
+            1., 3., 1.,
+            0., 1., 2.
+            ]);
+        let b = vector_f64(&[1., 2., 3.]);
+        let q = QuadraticObjective::new(&a, &b).unwrap();
+        let r = bfgs(&q, &vector_f64(&[0., 0., 0.]), &MinimizerOptions::new()).unwrap();
+        assert!(r.converged);
+        assert!((&(&a * &r.x) - &b).max_abs_scalar_value() < 1e-8);
+        assert!(r.iterations <= 15);
+    }
+}
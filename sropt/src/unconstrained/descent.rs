@@ -0,0 +1,126 @@
+#![doc="Generic line search descent loop
+
+The gradient based minimizers differ only in how they choose
+the search direction and the initial trial step. This module
+runs the common loop: compute a direction, search along it,
+update the state and check the stopping criteria.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::*;
+use unconstrained::options::*;
+use unconstrained::line_search::LineSearch;
+
+
+/// Strategy for the search directions of a descent method
+pub trait SearchDirection {
+
+    /// Computes a search direction at x with gradient g
+    fn direction<O:Objective + ?Sized>(&mut self, f : &O, x : &MatrixF64,
+        g : &MatrixF64) -> MatrixF64;
+
+    /// Incorporates the step s and the gradient change y
+    fn update(&mut self, _s : &MatrixF64, _y : &MatrixF64) {
+    }
+
+    /// Discards accumulated curvature information
+    fn reset(&mut self) {
+    }
+
+    /// Initial trial step along p. ``previous`` holds the step
+    /// length and the directional derivative of the previous
+    /// iteration.
+    fn initial_step(&self, _g : &MatrixF64, _p : &MatrixF64,
+        _previous : Option<(f64, f64)>) -> f64 {
+        1.
+    }
+}
+
+
+/// Runs a descent method from x0
+pub fn descent<O, D>(f : &O, x0 : &MatrixF64, rule : &mut D, line_search : LineSearch,
+    options : &MinimizerOptions) -> SRResult<MinimizeResult>
+    where O : Objective + ?Sized, D : SearchDirection {
+    check_start(x0)?;
+    let counted = CountedObjective::new(f);
+    let mut x = x0.clone();
+    let mut fx = counted.value(&x);
+    let mut g = counted.gradient(&x);
+    if g.size() != x.size() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let mut g_norm = g.max_abs_scalar_value();
+    let mut log = vec![IterationRecord {
+        iteration : 0,
+        value : fx,
+        gradient_norm : Some(g_norm),
+        step : 0.,
+        evaluations : counted.value_count()
+    }];
+    let mut termination = Termination::MaxIterations;
+    let mut previous = None;
+    if g_norm <= options.gradient_tolerance {
+        termination = Termination::GradientTolerance;
+    }
+    else {
+        for iteration in 1..(options.max_iterations + 1) {
+            let mut p = rule.direction(&counted, &x, &g);
+            let mut slope = g.inner_prod(&p);
+            if slope.is_nan() || slope >= 0. {
+                // Not a descent direction: restart with steepest descent
+                rule.reset();
+                p = g.unary_minus();
+                slope = -g.inner_prod(&g);
+                previous = None;
+            }
+            let alpha0 = rule.initial_step(&g, &p, previous);
+            let accepted = match line_search.search(&counted, &x, fx, &g, &p, alpha0) {
+                Ok(r) => r,
+                Err(_) => {
+                    termination = Termination::LineSearchFailure;
+                    break;
+                }
+            };
+            let g_new = match accepted.gradient {
+                Some(gn) => gn,
+                None => counted.gradient(&accepted.x)
+            };
+            let s = &accepted.x - &x;
+            let y = &g_new - &g;
+            rule.update(&s, &y);
+            previous = Some((accepted.alpha, slope));
+            let f_old = fx;
+            x = accepted.x;
+            fx = accepted.value;
+            g = g_new;
+            g_norm = g.max_abs_scalar_value();
+            let step = vec_norm_l2(&s);
+            log.push(IterationRecord {
+                iteration : iteration,
+                value : fx,
+                gradient_norm : Some(g_norm),
+                step : step,
+                evaluations : counted.value_count()
+            });
+            if let Some(t) = options.check(f_old, fx, step, vec_norm_l2(&x), Some(g_norm)) {
+                termination = t;
+                break;
+            }
+        }
+    }
+    Ok(MinimizeResult {
+        x : x,
+        value : fx,
+        gradient_norm : Some(g_norm),
+        iterations : log.len() - 1,
+        function_evaluations : counted.value_count(),
+        gradient_evaluations : counted.gradient_count(),
+        converged : termination.is_converged(),
+        termination : termination,
+        log : log
+    })
+}
@@ -0,0 +1,98 @@
+#![doc="Gradient descent
+
+The iteration x+ = x - alpha g(x) moves along the negative
+gradient with a step length found by a line search. The
+initial trial step of each search reuses the decrease
+achieved in the previous iteration:
+
+alpha0 = alpha_prev (g_prev' p_prev) / (g' p).
+
+Gradient descent converges linearly at a rate governed by
+the condition number of the Hessian and is mostly useful for
+well conditioned or very large problems.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use unconstrained::line_search::LineSearch;
+use unconstrained::descent::*;
+
+
+/// Steepest descent directions
+struct SteepestDescent;
+
+impl SearchDirection for SteepestDescent {
+
+    fn direction<O:Objective + ?Sized>(&mut self, _f : &O, _x : &MatrixF64,
+        g : &MatrixF64) -> MatrixF64 {
+        g.unary_minus()
+    }
+
+    fn initial_step(&self, g : &MatrixF64, p : &MatrixF64,
+        previous : Option<(f64, f64)>) -> f64 {
+        match previous {
+            Some((alpha, slope)) => alpha * slope / g.inner_prod(p),
+            None => 1. / g.max_abs_scalar_value().max(1.)
+        }
+    }
+}
+
+
+/// Minimizes f by gradient descent starting from x0
+/// using the given line search
+pub fn gradient_descent<O:Objective + ?Sized>(f : &O, x0 : &MatrixF64,
+    line_search : LineSearch, options : &MinimizerOptions) -> SRResult<MinimizeResult> {
+    descent(f, x0, &mut SteepestDescent, line_search, options)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use unconstrained::objective::*;
+
+    #[test]
+    fn test_gradient_descent_quadratic(){
+        let a = matrix_rw_f64(2, 2, &[3., 1., 1., 2.]);
+        let b = vector_f64(&[1., -1.]);
+        let q = QuadraticObjective::new(&a, &b).unwrap();
+        let x0 = vector_f64(&[5., 5.]);
+        let options = MinimizerOptions::new().gradient_tolerance(1e-7);
+        // Solution of A x = b
+        let expected = vector_f64(&[0.6, -0.8]);
+        for &ls in [LineSearch::armijo(), LineSearch::wolfe()].iter() {
+            let r = gradient_descent(&q, &x0, ls, &options).unwrap();
+            assert!(r.converged);
+            assert_eq!(r.termination, Termination::GradientTolerance);
+            assert!((&r.x - &expected).max_abs_scalar_value() < 1e-7);
+            // The log holds the starting point and every iteration
+            assert_eq!(r.log.len(), r.iterations + 1);
+            assert_eq!(r.log[0].value, q.value(&x0));
+            assert!(r.log.windows(2).all(|w| w[1].value <= w[0].value));
+        }
+    }
+
+    #[test]
+    fn test_gradient_descent_iteration_limit(){
+        let x0 = vector_f64(&[-1.2, 1.]);
+        let options = MinimizerOptions::new().max_iterations(10);
+        let r = gradient_descent(&Rosenbrock, &x0, LineSearch::armijo(), &options).unwrap();
+        assert!(!r.converged);
+        assert_eq!(r.termination, Termination::MaxIterations);
+        assert_eq!(r.iterations, 10);
+        assert!(r.value < Rosenbrock.value(&x0));
+        assert!(gradient_descent(&Rosenbrock, &matrix_rw_f64(1, 2, &[0., 0.]),
+            LineSearch::armijo(), &options).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+#![doc="The limited memory BFGS method
+
+L-BFGS keeps only the m most recent pairs (s, y) instead of
+a dense inverse Hessian approximation. The search direction
+p = -H g is computed by the two-loop recursion in O(m n)
+operations with the initial matrix H0 = (s' y / y' y) I taken
+from the latest pair. This makes the method suitable for
+problems with many variables. Typical memory sizes are 3 to 20.
+"]
+
+
+// std imports
+use std::collections::VecDeque;
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use unconstrained::line_search::LineSearch;
+use unconstrained::descent::*;
+
+
+/// Limited memory of curvature pairs
+struct Memory {
+    m : usize,
+    /// Pairs (s, y, 1 / y's), oldest first
+    pairs : VecDeque<(MatrixF64, MatrixF64, f64)>
+}
+
+impl SearchDirection for Memory {
+
+    fn direction<O:Objective + ?Sized>(&mut self, _f : &O, _x : &MatrixF64,
+        g : &MatrixF64) -> MatrixF64 {
+        let mut q = g.clone();
+        let mut alphas = Vec::with_capacity(self.pairs.len());
+        for (s, y, rho) in self.pairs.iter().rev() {
+            let a = rho * s.inner_prod(&q);
+            q = &q - &y.copy_mul_scalar(a);
+            alphas.push(a);
+        }
+        if let Some((s, y, _)) = self.pairs.back() {
+            q = q.copy_mul_scalar(s.inner_prod(y) / y.inner_prod(y));
+        }
+        for ((s, y, rho), a) in self.pairs.iter().zip(alphas.into_iter().rev()) {
+            let b = rho * y.inner_prod(&q);
+            q = &q + &s.copy_mul_scalar(a - b);
+        }
+        q.unary_minus()
+    }
+
+    fn update(&mut self, s : &MatrixF64, y : &MatrixF64) {
+        let sy = s.inner_prod(y);
+        if sy.is_nan() || sy <= 1e-10 * vec_norm_l2(s) * vec_norm_l2(y) {
+            return;
+        }
+        if self.pairs.len() == self.m {
+            self.pairs.pop_front();
+        }
+        self.pairs.push_back((s.clone(), y.clone(), 1. / sy));
+    }
+
+    fn reset(&mut self) {
+        self.pairs.clear();
+    }
+
+    fn initial_step(&self, g : &MatrixF64, _p : &MatrixF64,
+        _previous : Option<(f64, f64)>) -> f64 {
+        if self.pairs.is_empty() { 1. / g.max_abs_scalar_value().max(1.) } else { 1. }
+    }
+}
+
+
+/// Minimizes f by L-BFGS with m stored pairs starting from x0.
+/// Returns ``InvalidArgument`` if m is zero.
+pub fn lbfgs<O:Objective + ?Sized>(f : &O, x0 : &MatrixF64, m : usize,
+    options : &MinimizerOptions) -> SRResult<MinimizeResult> {
+    if m == 0 {
+        return Err(SRError::InvalidArgument);
+    }
+    let mut memory = Memory {m : m, pairs : VecDeque::with_capacity(m)};
+    descent(f, x0, &mut memory, LineSearch::wolfe(), options)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use unconstrained::objective::*;
+    use unconstrained::bfgs::bfgs;
+
+    #[test]
+    fn test_lbfgs_rosenbrock(){
+        // Extended Rosenbrock function in 10 dimensions
+        let x0 = Matrix::from_iter_cw(10, 1, (0..10).map(|i| if i % 2 == 0 { -1.2 } else { 1. }));
+        let r = lbfgs(&Rosenbrock, &x0, 5, &MinimizerOptions::new()).unwrap();
+        assert!(r.converged);
+        assert!((&r.x - &Matrix::ones(10, 1)).max_abs_scalar_value() < 1e-6);
+        assert!(lbfgs(&Rosenbrock, &x0, 0, &MinimizerOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_lbfgs_full_memory(){
+        // With a memory longer than the run L-BFGS differs from
+        // BFGS only in the scaling of the initial matrix
+        let a = matrix_rw_f64(2, 2, &[2., 0.5, 0.5, 1.]);
+        let q = QuadraticObjective::new(&a, &vector_f64(&[1., 1.])).unwrap();
+        let x0 = vector_f64(&[3., -2.]);
+        let r = lbfgs(&q, &x0, 10, &MinimizerOptions::new()).unwrap();
+        let s = bfgs(&q, &x0, &MinimizerOptions::new()).unwrap();
+        assert!((&r.x - &s.x).max_abs_scalar_value() < 1e-8);
+    }
+}
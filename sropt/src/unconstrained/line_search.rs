@@ -0,0 +1,312 @@
+#![doc="Line searches along a descent direction
+
+Given a point x, a descent direction p (with g' p < 0 for the
+gradient g at x) and an initial trial step, a line search
+finds a step length alpha for which x + alpha p is acceptable.
+
+* Backtracking (Armijo): alpha is reduced geometrically until
+  the sufficient decrease condition
+  f(x + alpha p) <= f(x) + c1 alpha g' p holds.
+* Strong Wolfe: additionally requires the curvature condition
+  |g(x + alpha p)' p| <= c2 |g' p|. The search brackets an
+  acceptable interval and refines it by safeguarded cubic
+  interpolation (Nocedal and Wright, algorithms 3.5 and 3.6).
+
+Quasi-Newton methods need the curvature condition to keep
+their Hessian approximations positive definite.
+"]
+
+
+// std imports
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+
+
+/// Maximum number of step reductions in backtracking
+const MAX_BACKTRACKS : usize = 60;
+/// Maximum number of bracketing steps in the Wolfe search
+const MAX_BRACKETS : usize = 30;
+/// Maximum number of interval refinements in the Wolfe search
+const MAX_ZOOMS : usize = 40;
+
+
+/// Line search strategies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineSearch {
+    /// Backtracking with sufficient decrease parameter c1
+    /// and reduction factor shrink
+    Armijo {c1 : f64, shrink : f64},
+    /// Strong Wolfe conditions with parameters 0 < c1 < c2 < 1
+    Wolfe {c1 : f64, c2 : f64}
+}
+
+
+/// An accepted step
+#[derive(Debug, Clone)]
+pub struct LineSearchResult {
+    /// The step length
+    pub alpha : f64,
+    /// The new point x + alpha p
+    pub x : MatrixF64,
+    /// The objective value at the new point
+    pub value : f64,
+    /// The gradient at the new point if it was evaluated
+    pub gradient : Option<MatrixF64>
+}
+
+
+impl LineSearch {
+
+    /// Backtracking with c1 = 1e-4 and halving of the step
+    pub fn armijo() -> LineSearch {
+        LineSearch::Armijo {c1 : 1e-4, shrink : 0.5}
+    }
+
+    /// Strong Wolfe conditions with c1 = 1e-4 and c2 = 0.9,
+    /// suitable for (quasi) Newton methods
+    pub fn wolfe() -> LineSearch {
+        LineSearch::Wolfe {c1 : 1e-4, c2 : 0.9}
+    }
+
+    /// Searches along p from x where f(x) = fx and the
+    /// gradient is gx, starting with the step alpha0
+    pub fn search<O:Objective + ?Sized>(&self, f : &O, x : &MatrixF64, fx : f64,
+        gx : &MatrixF64, p : &MatrixF64, alpha0 : f64) -> SRResult<LineSearchResult> {
+        match *self {
+            LineSearch::Armijo {c1, shrink} => backtracking(f, x, fx, gx, p, alpha0, (c1, shrink)),
+            LineSearch::Wolfe {c1, c2} => strong_wolfe(f, x, fx, gx, p, alpha0, (c1, c2))
+        }
+    }
+}
+
+
+/// Validates the parameters shared by the line searches
+/// and returns the directional derivative g' p
+fn directional_derivative(gx : &MatrixF64, p : &MatrixF64, alpha0 : f64) -> SRResult<f64> {
+    if gx.size() != p.size() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    if alpha0.is_nan() || alpha0 <= 0. {
+        return Err(SRError::InvalidArgument);
+    }
+    let slope = gx.inner_prod(p);
+    if slope.is_nan() || slope >= 0. {
+        // Not a descent direction
+        return Err(SRError::InvalidArgument);
+    }
+    Ok(slope)
+}
+
+
+/// Backtracking line search with the Armijo condition.
+/// Returns ``InvalidArgument`` if p is not a descent direction
+/// and ``NotConverged`` if no acceptable step is found.
+fn backtracking<O:Objective + ?Sized>(f : &O, x : &MatrixF64, fx : f64,
+    gx : &MatrixF64, p : &MatrixF64, alpha0 : f64, (c1, shrink) : (f64, f64))
+    -> SRResult<LineSearchResult> {
+    if !(c1 > 0. && c1 < 1. && shrink > 0. && shrink < 1.) {
+        return Err(SRError::InvalidArgument);
+    }
+    let slope = directional_derivative(gx, p, alpha0)?;
+    let mut alpha = alpha0;
+    for _ in 0..MAX_BACKTRACKS {
+        let y = x + &p.copy_mul_scalar(alpha);
+        let fy = f.value(&y);
+        if fy <= fx + c1 * alpha * slope {
+            return Ok(LineSearchResult {alpha : alpha, x : y, value : fy, gradient : None});
+        }
+        alpha *= shrink;
+    }
+    Err(SRError::NotConverged)
+}
+
+
+/// A trial point of the Wolfe search
+struct Trial {
+    alpha : f64,
+    x : MatrixF64,
+    value : f64,
+    gradient : MatrixF64,
+    slope : f64
+}
+
+impl Trial {
+    fn new<O:Objective + ?Sized>(f : &O, x : &MatrixF64, p : &MatrixF64, alpha : f64) -> Trial {
+        let y = x + &p.copy_mul_scalar(alpha);
+        let value = f.value(&y);
+        let gradient = f.gradient(&y);
+        let slope = gradient.inner_prod(p);
+        Trial {alpha : alpha, x : y, value : value, gradient : gradient, slope : slope}
+    }
+
+    fn accept(self) -> LineSearchResult {
+        LineSearchResult {
+            alpha : self.alpha,
+            x : self.x,
+            value : self.value,
+            gradient : Some(self.gradient)
+        }
+    }
+}
+
+
+/// Minimizer of the cubic interpolating the values and slopes
+/// at a and b, safeguarded to stay well inside the interval
+fn cubic_step(a : (f64, f64, f64), b : (f64, f64, f64)) -> f64 {
+    let (a0, f0, d0) = a;
+    let (a1, f1, d1) = b;
+    let lo = a0.min(a1);
+    let hi = a0.max(a1);
+    let margin = 0.1 * (hi - lo);
+    let t1 = d0 + d1 - 3. * (f0 - f1) / (a0 - a1);
+    let disc = t1 * t1 - d0 * d1;
+    if disc >= 0. {
+        let t2 = (a1 - a0).signum() * disc.sqrt();
+        let c = a1 - (a1 - a0) * (d1 + t2 - t1) / (d1 - d0 + 2. * t2);
+        if c.is_finite() && c >= lo + margin && c <= hi - margin {
+            return c;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+
+/// Line search satisfying the strong Wolfe conditions.
+///
+/// The trial step grows by a factor of two until an interval
+/// containing acceptable steps is bracketed. Returns
+/// ``InvalidArgument`` if p is not a descent direction and
+/// ``NotConverged`` if no acceptable step is found. If the
+/// interval collapses, the best step satisfying the sufficient
+/// decrease condition is returned.
+fn strong_wolfe<O:Objective + ?Sized>(f : &O, x : &MatrixF64, fx : f64,
+    gx : &MatrixF64, p : &MatrixF64, alpha0 : f64, (c1, c2) : (f64, f64))
+    -> SRResult<LineSearchResult> {
+    if !(c1 > 0. && c1 < c2 && c2 < 1.) {
+        return Err(SRError::InvalidArgument);
+    }
+    let slope0 = directional_derivative(gx, p, alpha0)?;
+    let armijo = |t : &Trial| t.value <= fx + c1 * t.alpha * slope0;
+    let curvature = |t : &Trial| t.slope.abs() <= -c2 * slope0;
+    // Bracketing phase
+    let mut prev : Option<Trial> = None;
+    let mut alpha = alpha0;
+    let mut bracket : Option<(Trial, Trial)> = None;
+    for _ in 0..MAX_BRACKETS {
+        let t = Trial::new(f, x, p, alpha);
+        if !t.value.is_finite() {
+            // Step into a region where f is undefined: shrink
+            alpha = 0.5 * (alpha + prev.as_ref().map_or(0., |q| q.alpha));
+            continue;
+        }
+        let worse = prev.as_ref().is_some_and(|q| t.value >= q.value);
+        if !armijo(&t) || worse {
+            bracket = Some((prev.take().unwrap_or_else(|| origin(x, fx, gx, slope0)), t));
+            break;
+        }
+        if curvature(&t) {
+            return Ok(t.accept());
+        }
+        if t.slope >= 0. {
+            bracket = Some((t, prev.take().unwrap_or_else(|| origin(x, fx, gx, slope0))));
+            break;
+        }
+        alpha = 2. * t.alpha;
+        prev = Some(t);
+    }
+    let (mut lo, mut hi) = match bracket {
+        Some(b) => b,
+        None => return match prev {
+            Some(t) => Ok(t.accept()),
+            None => Err(SRError::NotConverged)
+        }
+    };
+    // Zoom phase: lo satisfies sufficient decrease and has the
+    // lowest value so far, hi bounds the interval
+    for _ in 0..MAX_ZOOMS {
+        if (hi.alpha - lo.alpha).abs() <= f64::EPSILON * lo.alpha.max(hi.alpha) {
+            break;
+        }
+        let a = cubic_step((lo.alpha, lo.value, lo.slope), (hi.alpha, hi.value, hi.slope));
+        let t = Trial::new(f, x, p, a);
+        if !armijo(&t) || t.value >= lo.value {
+            hi = t;
+        }
+        else {
+            if curvature(&t) {
+                return Ok(t.accept());
+            }
+            if t.slope * (hi.alpha - lo.alpha) >= 0. {
+                hi = lo;
+            }
+            lo = t;
+        }
+    }
+    if lo.alpha > 0. {
+        Ok(lo.accept())
+    }
+    else {
+        Err(SRError::NotConverged)
+    }
+}
+
+
+/// The trial at alpha = 0
+fn origin(x : &MatrixF64, fx : f64, gx : &MatrixF64, slope : f64) -> Trial {
+    Trial {alpha : 0., x : x.clone(), value : fx, gradient : gx.clone(), slope : slope}
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use unconstrained::objective::*;
+
+    fn quadratic() -> QuadraticObjective {
+        let a = matrix_rw_f64(2, 2, &[4., 0., 0., 1.]);
+        QuadraticObjective::new(&a, &vector_f64(&[0., 0.])).unwrap()
+    }
+
+    #[test]
+    fn test_backtracking(){
+        let q = quadratic();
+        let x = vector_f64(&[1., 1.]);
+        let g = q.gradient(&x);
+        let p = g.unary_minus();
+        let r = LineSearch::armijo().search(&q, &x, q.value(&x), &g, &p, 1.).unwrap();
+        // f(x + p) = 18 is rejected, f(x + p / 2) = 2.125 accepted
+        assert_eq!(r.alpha, 0.5);
+        assert!(r.value < q.value(&x));
+        assert!(r.gradient.is_none());
+        // Ascent directions are rejected
+        assert!(LineSearch::armijo().search(&q, &x, q.value(&x), &g, &g, 1.).is_err());
+    }
+
+    #[test]
+    fn test_strong_wolfe(){
+        let q = quadratic();
+        let x = vector_f64(&[1., 1.]);
+        let g = q.gradient(&x);
+        let p = g.unary_minus();
+        let fx = q.value(&x);
+        let slope = g.inner_prod(&p);
+        for &alpha0 in [1e-3, 1., 100.].iter() {
+            let r = LineSearch::Wolfe {c1 : 1e-4, c2 : 0.1}.search(&q, &x, fx, &g, &p, alpha0).unwrap();
+            let gn = r.gradient.unwrap();
+            assert!(r.value <= fx + 1e-4 * r.alpha * slope);
+            assert!(gn.inner_prod(&p).abs() <= 0.1 * slope.abs());
+        }
+        // Exact minimizer along p is 17 / 65
+        let r = LineSearch::Wolfe {c1 : 1e-8, c2 : 1e-6}.search(&q, &x, fx, &g, &p, 1.).unwrap();
+        assert!((r.alpha - 17. / 65.).abs() < 1e-6);
+    }
+}
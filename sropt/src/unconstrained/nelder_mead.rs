@@ -0,0 +1,182 @@
+#![doc="The Nelder-Mead simplex method
+
+A derivative free method which maintains a simplex of n + 1
+points. In each iteration the worst vertex is replaced by its
+reflection through the centroid of the others, possibly
+expanded or contracted; if none of these improves on the
+worst vertex the simplex shrinks towards the best one.
+
+The coefficients depend on the dimension (Gao and Han, 2012):
+reflection 1, expansion 1 + 2/n, contraction 3/4 - 1/(2n)
+and shrinkage 1 - 1/n. For n = 2 these are the classical
+values 1, 2, 1/2 and 1/2.
+
+The initial simplex perturbs each coordinate of x0 by 5%
+(or by 0.00025 for zero coordinates). The method stops when
+the simplex diameter or the spread of the function values
+meets the tolerances of ``MinimizerOptions``; the gradient
+tolerance is not used.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::*;
+use unconstrained::options::*;
+
+
+/// Minimizes f by the Nelder-Mead method starting from x0
+pub fn nelder_mead<O:Objective + ?Sized>(f : &O, x0 : &MatrixF64,
+    options : &MinimizerOptions) -> SRResult<MinimizeResult> {
+    check_start(x0)?;
+    let counted = CountedObjective::new(f);
+    let n = x0.num_rows();
+    let nf = n as f64;
+    let (rho, chi, gamma, sigma) = (1., 1. + 2. / nf, 0.75 - 0.5 / nf, 1. - 1. / nf);
+    // A shrink factor of zero (n = 1) would collapse the simplex
+    let sigma = if sigma > 0. { sigma } else { 0.5 };
+    let mut points = vec![x0.clone()];
+    for i in 0..n {
+        let mut p = x0.clone();
+        let xi = x0[i];
+        p.set(i, 0, if xi != 0. { 1.05 * xi } else { 0.00025 });
+        points.push(p);
+    }
+    let mut values : Vec<f64> = points.iter().map(|p| counted.value(p)).collect();
+    let mut log = Vec::new();
+    let mut termination = Termination::MaxIterations;
+    let mut iteration = 0;
+    loop {
+        // Sort the vertices by their values
+        let mut order : Vec<usize> = (0..(n + 1)).collect();
+        order.sort_by(|&a, &b| values[a].partial_cmp(&values[b])
+            .unwrap_or(::std::cmp::Ordering::Equal));
+        points = order.iter().map(|&i| points[i].clone()).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+        let diameter = points[1..].iter()
+            .fold(0., |d : f64, p| d.max((p - &points[0]).max_abs_scalar_value()));
+        log.push(IterationRecord {
+            iteration : iteration,
+            value : values[0],
+            gradient_norm : None,
+            step : diameter,
+            evaluations : counted.value_count()
+        });
+        let x_norm = points[0].inner_prod(&points[0]).sqrt();
+        if iteration > 0 {
+            if diameter <= options.step_tolerance * (1. + x_norm) {
+                termination = Termination::StepTolerance;
+                break;
+            }
+            if values[n] - values[0] <= options.function_tolerance * (1. + values[0].abs()) {
+                termination = Termination::FunctionTolerance;
+                break;
+            }
+        }
+        if iteration == options.max_iterations {
+            break;
+        }
+        iteration += 1;
+        // Centroid of all but the worst vertex
+        let mut centroid : MatrixF64 = Matrix::zeros(n, 1);
+        for p in points[0..n].iter() {
+            centroid = &centroid + p;
+        }
+        let centroid = centroid.copy_mul_scalar(1. / nf);
+        let along = |t : f64| &centroid + &(&points[n] - &centroid).copy_mul_scalar(t);
+        let reflected = along(-rho);
+        let f_reflected = counted.value(&reflected);
+        if f_reflected < values[0] {
+            let expanded = along(-rho * chi);
+            let f_expanded = counted.value(&expanded);
+            if f_expanded < f_reflected {
+                points[n] = expanded;
+                values[n] = f_expanded;
+            }
+            else {
+                points[n] = reflected;
+                values[n] = f_reflected;
+            }
+            continue;
+        }
+        if f_reflected < values[n - 1] {
+            points[n] = reflected;
+            values[n] = f_reflected;
+            continue;
+        }
+        // Contraction outside or inside of the simplex
+        let (contracted, bound) = if f_reflected < values[n] {
+            (along(-rho * gamma), f_reflected)
+        }
+        else {
+            (along(gamma), values[n])
+        };
+        let f_contracted = counted.value(&contracted);
+        if f_contracted <= bound {
+            points[n] = contracted;
+            values[n] = f_contracted;
+            continue;
+        }
+        // Shrink towards the best vertex
+        for i in 1..(n + 1) {
+            points[i] = &points[0] + &(&points[i] - &points[0]).copy_mul_scalar(sigma);
+            values[i] = counted.value(&points[i]);
+        }
+    }
+    Ok(MinimizeResult {
+        x : points[0].clone(),
+        value : values[0],
+        gradient_norm : None,
+        iterations : iteration,
+        function_evaluations : counted.value_count(),
+        gradient_evaluations : 0,
+        converged : termination.is_converged(),
+        termination : termination,
+        log : log
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_nelder_mead_rosenbrock(){
+        let x0 = vector_f64(&[-1.2, 1.]);
+        let options = MinimizerOptions::new().step_tolerance(1e-10).function_tolerance(0.);
+        let r = nelder_mead(&Rosenbrock, &x0, &options).unwrap();
+        assert!(r.converged);
+        assert_eq!(r.termination, Termination::StepTolerance);
+        assert!((&r.x - &vector_f64(&[1., 1.])).max_abs_scalar_value() < 1e-8);
+        assert_eq!(r.gradient_evaluations, 0);
+        assert_eq!(r.log.len(), r.iterations + 1);
+        assert!(r.log.windows(2).all(|w| w[1].value <= w[0].value));
+    }
+
+    #[test]
+    fn test_nelder_mead_nonsmooth(){
+        // |x1 - 1| + 2 |x2 + 0.5| has no gradient at its minimizer
+        let f = ValueObjective::new(|x : &MatrixF64| (x[0] - 1.).abs() + 2. * (x[1] + 0.5).abs());
+        let r = nelder_mead(&f, &vector_f64(&[0., 0.]), &MinimizerOptions::new()).unwrap();
+        assert!(r.converged);
+        assert!((&r.x - &vector_f64(&[1., -0.5])).max_abs_scalar_value() < 1e-6);
+    }
+
+    #[test]
+    fn test_nelder_mead_one_dimension(){
+        let f = ValueObjective::new(|x : &MatrixF64| (x[0] - 3.) * (x[0] - 3.));
+        let options = MinimizerOptions::new().max_iterations(500);
+        let r = nelder_mead(&f, &vector_f64(&[0.]), &options).unwrap();
+        assert!(r.converged);
+        assert!((r.x[0] - 3.).abs() < 1e-6);
+    }
+}
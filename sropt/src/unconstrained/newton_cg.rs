@@ -0,0 +1,117 @@
+#![doc="Line search Newton-CG (truncated Newton) method
+
+The Newton system H p = -g is solved approximately by the
+conjugate gradient method, which only needs products of the
+Hessian with vectors (see ``Objective::hessian_vector``). The
+inner iterations stop once
+
+||H p + g|| <= min(0.5, sqrt(||g||)) ||g||
+
+which gives superlinear convergence, or when a direction of
+non-positive curvature is met. In the latter case the current
+iterate (or -g in the first inner iteration) is returned so
+that p is always a descent direction. A backtracking line
+search starting from the full Newton step follows
+(Nocedal and Wright, algorithm 7.1).
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use unconstrained::objective::Objective;
+use unconstrained::options::*;
+use unconstrained::line_search::LineSearch;
+use unconstrained::descent::*;
+
+
+/// Truncated conjugate gradient directions
+struct TruncatedNewton;
+
+impl SearchDirection for TruncatedNewton {
+
+    fn direction<O:Objective + ?Sized>(&mut self, f : &O, x : &MatrixF64,
+        g : &MatrixF64) -> MatrixF64 {
+        let n = x.num_rows();
+        let g_norm = vec_norm_l2(g);
+        let tolerance = g_norm.sqrt().min(0.5) * g_norm;
+        let mut z : MatrixF64 = Matrix::zeros(n, 1);
+        let mut r = g.clone();
+        let mut d = g.unary_minus();
+        let mut rr = r.inner_prod(&r);
+        for j in 0..(2 * n) {
+            let hd = f.hessian_vector(x, &d);
+            let curvature = d.inner_prod(&hd);
+            if curvature <= 0. {
+                return if j == 0 { d } else { z };
+            }
+            let alpha = rr / curvature;
+            z = &z + &d.copy_mul_scalar(alpha);
+            r = &r + &hd.copy_mul_scalar(alpha);
+            let rr_new = r.inner_prod(&r);
+            if rr_new.sqrt() <= tolerance {
+                break;
+            }
+            d = &r.unary_minus() + &d.copy_mul_scalar(rr_new / rr);
+            rr = rr_new;
+        }
+        z
+    }
+}
+
+
+/// Minimizes f by the Newton-CG method starting from x0
+pub fn newton_cg<O:Objective + ?Sized>(f : &O, x0 : &MatrixF64,
+    options : &MinimizerOptions) -> SRResult<MinimizeResult> {
+    descent(f, x0, &mut TruncatedNewton, LineSearch::armijo(), options)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use unconstrained::objective::*;
+
+    #[test]
+    fn test_newton_cg_rosenbrock(){
+        let x0 = vector_f64(&[-1.2, 1.]);
+        let r = newton_cg(&Rosenbrock, &x0, &MinimizerOptions::new()).unwrap();
+        assert!(r.converged);
+        assert!((&r.x - &vector_f64(&[1., 1.])).max_abs_scalar_value() < 1e-8);
+        // Steepest descent like steps where the Hessian is
+        // indefinite, then quadratic convergence
+        assert!(r.iterations < 100);
+        let last = &r.log[(r.iterations - 2)..];
+        assert!(last[2].gradient_norm.unwrap() < last[1].gradient_norm.unwrap().powi(2) * 10.);
+    }
+
+    #[test]
+    fn test_newton_cg_without_hessian(){
+        // Hessian-vector products by differences of the gradient
+        let f = FnObjective::new(|x : &MatrixF64| Rosenbrock.value(x),
+            |x : &MatrixF64| Rosenbrock.gradient(x));
+        let x0 = vector_f64(&[-1.2, 1., -0.5, 0.8]);
+        let r = newton_cg(&f, &x0, &MinimizerOptions::new().gradient_tolerance(1e-6)).unwrap();
+        assert!(r.converged);
+        assert!((&r.x - &Matrix::ones(4, 1)).max_abs_scalar_value() < 1e-5);
+    }
+
+    #[test]
+    fn test_newton_cg_quadratic(){
+        // One Newton step solves a quadratic
+        let a = matrix_rw_f64(2, 2, &[2., 1., 1., 2.]);
+        let b = vector_f64(&[1., 0.]);
+        let q = QuadraticObjective::new(&a, &b).unwrap();
+        let r = newton_cg(&q, &vector_f64(&[10., -3.]), &MinimizerOptions::new()).unwrap();
+        assert_eq!(r.termination, Termination::GradientTolerance);
+        assert!(r.iterations <= 2);
+        assert!((&r.x - &vector_f64(&[2. / 3., -1. / 3.])).max_abs_scalar_value() < 1e-10);
+    }
+}
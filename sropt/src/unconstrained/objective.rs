@@ -0,0 +1,337 @@
+#![doc="Objective functions for nonlinear optimization
+
+An objective maps a column vector x to a scalar f(x). The
+minimizers need its value and, depending on the method, the
+gradient and products of the Hessian with vectors.
+
+Only ``value`` has to be implemented. The gradient defaults
+to central differences and Hessian-vector products default to
+a forward difference of the gradient. Implementations which
+know their derivatives should override these.
+"]
+
+
+// std imports
+use std::cell::Cell;
+use std::f64;
+
+// local imports
+use srmatrix::api::*;
+
+
+/// A scalar function of a column vector
+pub trait Objective {
+
+    /// Evaluates f(x)
+    fn value(&self, x : &MatrixF64) -> f64;
+
+    /// Evaluates the gradient of f at x as a column vector
+    fn gradient(&self, x : &MatrixF64) -> MatrixF64 {
+        central_difference_gradient(self, x)
+    }
+
+    /// Evaluates the Hessian of f at x if it is available
+    fn hessian(&self, _x : &MatrixF64) -> Option<MatrixF64> {
+        None
+    }
+
+    /// Computes the product of the Hessian at x with v
+    fn hessian_vector(&self, x : &MatrixF64, v : &MatrixF64) -> MatrixF64 {
+        if let Some(h) = self.hessian(x) {
+            return &h * v;
+        }
+        let v_norm = v.inner_prod(v).sqrt();
+        if v_norm == 0. {
+            return Matrix::zeros(x.num_rows(), 1);
+        }
+        let x_norm = x.inner_prod(x).sqrt();
+        let h = f64::EPSILON.sqrt() * (1. + x_norm) / v_norm;
+        let g0 = self.gradient(x);
+        let g1 = self.gradient(&(x + &v.copy_mul_scalar(h)));
+        (&g1 - &g0).copy_mul_scalar(1. / h)
+    }
+}
+
+
+impl<O:Objective + ?Sized> Objective for &O {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        (**self).value(x)
+    }
+
+    fn gradient(&self, x : &MatrixF64) -> MatrixF64 {
+        (**self).gradient(x)
+    }
+
+    fn hessian(&self, x : &MatrixF64) -> Option<MatrixF64> {
+        (**self).hessian(x)
+    }
+
+    fn hessian_vector(&self, x : &MatrixF64, v : &MatrixF64) -> MatrixF64 {
+        (**self).hessian_vector(x, v)
+    }
+}
+
+
+/// Approximates the gradient of f at x by central differences
+pub fn central_difference_gradient<O:Objective + ?Sized>(f : &O, x : &MatrixF64) -> MatrixF64 {
+    let n = x.num_rows();
+    let scale = f64::EPSILON.cbrt();
+    let mut g = Matrix::zeros(n, 1);
+    let mut y = x.clone();
+    for i in 0..n {
+        let xi = x[i];
+        let h = scale * (1. + xi.abs());
+        y.set(i, 0, xi + h);
+        let fp = f.value(&y);
+        y.set(i, 0, xi - h);
+        let fm = f.value(&y);
+        y.set(i, 0, xi);
+        g.set(i, 0, (fp - fm) / (2. * h));
+    }
+    g
+}
+
+
+/// An objective defined by closures for its value and gradient
+pub struct FnObjective<F, G>
+    where F : Fn(&MatrixF64) -> f64, G : Fn(&MatrixF64) -> MatrixF64 {
+    f : F,
+    g : G
+}
+
+impl<F, G> FnObjective<F, G>
+    where F : Fn(&MatrixF64) -> f64, G : Fn(&MatrixF64) -> MatrixF64 {
+
+    /// Wraps closures computing f(x) and its gradient
+    pub fn new(f : F, g : G) -> FnObjective<F, G> {
+        FnObjective{f : f, g : g}
+    }
+}
+
+impl<F, G> Objective for FnObjective<F, G>
+    where F : Fn(&MatrixF64) -> f64, G : Fn(&MatrixF64) -> MatrixF64 {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        (self.f)(x)
+    }
+
+    fn gradient(&self, x : &MatrixF64) -> MatrixF64 {
+        (self.g)(x)
+    }
+}
+
+
+/// An objective defined by a closure for its value only.
+/// Derivatives are approximated by finite differences.
+pub struct ValueObjective<F> where F : Fn(&MatrixF64) -> f64 {
+    f : F
+}
+
+impl<F> ValueObjective<F> where F : Fn(&MatrixF64) -> f64 {
+
+    /// Wraps a closure computing f(x)
+    pub fn new(f : F) -> ValueObjective<F> {
+        ValueObjective{f : f}
+    }
+}
+
+impl<F> Objective for ValueObjective<F> where F : Fn(&MatrixF64) -> f64 {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        (self.f)(x)
+    }
+}
+
+
+/// The quadratic f(x) = (1/2) x' A x - b' x with symmetric A
+#[derive(Debug, Clone)]
+pub struct QuadraticObjective {
+    a : MatrixF64,
+    b : MatrixF64
+}
+
+impl QuadraticObjective {
+
+    /// Creates the quadratic from a symmetric matrix A and a vector b
+    pub fn new(a : &MatrixF64, b : &MatrixF64) -> SRResult<QuadraticObjective> {
+        if !a.is_square() {
+            return Err(SRError::IsNotSquareMatrix);
+        }
+        if !a.is_symmetric() {
+            return Err(SRError::IsNotSymmetric);
+        }
+        if !b.is_col() {
+            return Err(SRError::IsNotAColVector);
+        }
+        if b.num_rows() != a.num_rows() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        Ok(QuadraticObjective{a : a.clone(), b : b.clone()})
+    }
+}
+
+impl Objective for QuadraticObjective {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        0.5 * x.inner_prod(&(&self.a * x)) - self.b.inner_prod(x)
+    }
+
+    fn gradient(&self, x : &MatrixF64) -> MatrixF64 {
+        &(&self.a * x) - &self.b
+    }
+
+    fn hessian(&self, _x : &MatrixF64) -> Option<MatrixF64> {
+        Some(self.a.clone())
+    }
+}
+
+
+/// The Rosenbrock function
+/// f(x) = sum (100 (x[i+1] - x[i]^2)^2 + (1 - x[i])^2)
+/// with its minimum f = 0 at x = (1, ..., 1)
+#[derive(Debug, Clone, Copy)]
+pub struct Rosenbrock;
+
+impl Objective for Rosenbrock {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        let n = x.num_rows();
+        (0..n.saturating_sub(1)).fold(0., |s, i| {
+            let a = x[i + 1] - x[i] * x[i];
+            let b = 1. - x[i];
+            s + 100. * a * a + b * b
+        })
+    }
+
+    fn gradient(&self, x : &MatrixF64) -> MatrixF64 {
+        let n = x.num_rows();
+        let mut g = Matrix::zeros(n, 1);
+        for i in 0..n.saturating_sub(1) {
+            let a = x[i + 1] - x[i] * x[i];
+            g.set(i, 0, g[i] - 400. * x[i] * a - 2. * (1. - x[i]));
+            g.set(i + 1, 0, g[i + 1] + 200. * a);
+        }
+        g
+    }
+
+    fn hessian(&self, x : &MatrixF64) -> Option<MatrixF64> {
+        let n = x.num_rows();
+        let mut h = Matrix::zeros(n, n);
+        for i in 0..n.saturating_sub(1) {
+            let d = h.get(i, i).unwrap();
+            h.set(i, i, d + 1200. * x[i] * x[i] - 400. * x[i + 1] + 2.);
+            let d = h.get(i + 1, i + 1).unwrap();
+            h.set(i + 1, i + 1, d + 200.);
+            h.set(i, i + 1, -400. * x[i]);
+            h.set(i + 1, i, -400. * x[i]);
+        }
+        Some(h)
+    }
+}
+
+
+/// Wraps an objective and counts its evaluations
+pub struct CountedObjective<'a, O:Objective + ?Sized + 'a> {
+    inner : &'a O,
+    values : Cell<usize>,
+    gradients : Cell<usize>
+}
+
+impl<'a, O:Objective + ?Sized> CountedObjective<'a, O> {
+
+    /// Starts counting the evaluations of an objective
+    pub fn new(inner : &'a O) -> CountedObjective<'a, O> {
+        CountedObjective {inner : inner, values : Cell::new(0), gradients : Cell::new(0)}
+    }
+
+    /// Returns the number of function evaluations
+    pub fn value_count(&self) -> usize {
+        self.values.get()
+    }
+
+    /// Returns the number of gradient evaluations
+    /// (including Hessian-vector products)
+    pub fn gradient_count(&self) -> usize {
+        self.gradients.get()
+    }
+}
+
+impl<'a, O:Objective + ?Sized> Objective for CountedObjective<'a, O> {
+
+    fn value(&self, x : &MatrixF64) -> f64 {
+        self.values.set(self.values.get() + 1);
+        self.inner.value(x)
+    }
+
+    fn gradient(&self, x : &MatrixF64) -> MatrixF64 {
+        self.gradients.set(self.gradients.get() + 1);
+        self.inner.gradient(x)
+    }
+
+    fn hessian(&self, x : &MatrixF64) -> Option<MatrixF64> {
+        self.inner.hessian(x)
+    }
+
+    fn hessian_vector(&self, x : &MatrixF64, v : &MatrixF64) -> MatrixF64 {
+        self.gradients.set(self.gradients.get() + 1);
+        self.inner.hessian_vector(x, v)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_finite_differences(){
+        let f = ValueObjective::new(|x : &MatrixF64| x[0] * x[0] * x[1] + x[1].sin());
+        let x = vector_f64(&[1.5, 0.5]);
+        let g = f.gradient(&x);
+        assert!((g[0] - 1.5).abs() < 1e-9);
+        assert!((g[1] - (2.25 + 0.5f64.cos())).abs() < 1e-9);
+        // Hessian [[2 x2, 2 x1], [2 x1, -sin x2]] applied to (1, 0)
+        let hv = f.hessian_vector(&x, &vector_f64(&[1., 0.]));
+        assert!((hv[0] - 1.).abs() < 1e-4);
+        assert!((hv[1] - 3.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_quadratic_objective(){
+        let a = matrix_rw_f64(2, 2, &[2., 1., 1., 3.]);
+        let b = vector_f64(&[1., 2.]);
+        let q = QuadraticObjective::new(&a, &b).unwrap();
+        let x = vector_f64(&[1., -1.]);
+        assert_eq!(q.value(&x), 0.5 * 3. + 1.);
+        assert_eq!(q.gradient(&x), vector_f64(&[0., -4.]));
+        assert_eq!(q.hessian_vector(&x, &vector_f64(&[1., 0.])), vector_f64(&[2., 1.]));
+        let counted = CountedObjective::new(&q);
+        counted.value(&x);
+        counted.gradient(&x);
+        counted.value(&x);
+        assert_eq!(counted.value_count(), 2);
+        assert_eq!(counted.gradient_count(), 1);
+        assert!(QuadraticObjective::new(&matrix_rw_f64(2, 2, &[1., 2., 0., 1.]), &b).is_err());
+    }
+
+    #[test]
+    fn test_rosenbrock(){
+        let x = vector_f64(&[-1.2, 1., 0.5]);
+        let g = Rosenbrock.gradient(&x);
+        let fd = central_difference_gradient(&ValueObjective::new(|y : &MatrixF64| Rosenbrock.value(y)), &x);
+        assert!((&g - &fd).max_abs_scalar_value() < 1e-5);
+        let v = vector_f64(&[0.3, -0.7, 1.1]);
+        let hv = Rosenbrock.hessian_vector(&x, &v);
+        let fd = FnObjective::new(|y : &MatrixF64| Rosenbrock.value(y),
+            |y : &MatrixF64| Rosenbrock.gradient(y)).hessian_vector(&x, &v);
+        assert!((&hv - &fd).max_abs_scalar_value() < 1e-4 * hv.max_abs_scalar_value());
+        assert_eq!(Rosenbrock.value(&vector_f64(&[1., 1., 1.])), 0.);
+    }
+}
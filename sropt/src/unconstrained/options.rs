@@ -0,0 +1,205 @@
+#![doc="Stopping criteria and results shared by the minimizers
+
+A minimizer stops as soon as one of the following holds:
+
+* the gradient is small: ||g||_inf <= gradient_tolerance,
+* the step is small: ||x+ - x|| <= step_tolerance (1 + ||x+||),
+* the decrease is small: f(x) - f(x+) <= function_tolerance (1 + |f(x+)|),
+* the iteration limit is reached.
+
+The first three count as convergence. For Nelder-Mead the
+step is the diameter of the simplex and the decrease is the
+spread of the function values over its vertices.
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Stopping criteria for the minimizers
+#[derive(Debug, Clone, Copy)]
+pub struct MinimizerOptions {
+    /// Bound on the infinity norm of the gradient
+    pub gradient_tolerance : f64,
+    /// Relative bound on the length of a step
+    pub step_tolerance : f64,
+    /// Relative bound on the decrease of the objective
+    pub function_tolerance : f64,
+    /// Maximum number of iterations
+    pub max_iterations : usize
+}
+
+impl MinimizerOptions {
+
+    /// Default options: gradient tolerance 1e-8, step
+    /// tolerance 1e-12, function tolerance 1e-15 and at most
+    /// 1000 iterations
+    pub fn new() -> MinimizerOptions {
+        MinimizerOptions {
+            gradient_tolerance : 1e-8,
+            step_tolerance : 1e-12,
+            function_tolerance : 1e-15,
+            max_iterations : 1000
+        }
+    }
+
+    /// Sets the gradient tolerance
+    pub fn gradient_tolerance(mut self, tolerance : f64) -> MinimizerOptions {
+        self.gradient_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the step tolerance
+    pub fn step_tolerance(mut self, tolerance : f64) -> MinimizerOptions {
+        self.step_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the function tolerance
+    pub fn function_tolerance(mut self, tolerance : f64) -> MinimizerOptions {
+        self.function_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of iterations
+    pub fn max_iterations(mut self, max_iterations : usize) -> MinimizerOptions {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Checks the stopping criteria after a step of length
+    /// ``step`` which changed the objective from ``f_old`` to
+    /// ``f_new``. ``gradient_norm`` is the infinity norm of the
+    /// new gradient if it is known.
+    pub fn check(&self, f_old : f64, f_new : f64, step : f64, x_norm : f64,
+        gradient_norm : Option<f64>) -> Option<Termination> {
+        if let Some(g) = gradient_norm {
+            if g <= self.gradient_tolerance {
+                return Some(Termination::GradientTolerance);
+            }
+        }
+        if step <= self.step_tolerance * (1. + x_norm) {
+            return Some(Termination::StepTolerance);
+        }
+        if f_old - f_new <= self.function_tolerance * (1. + f_new.abs()) {
+            return Some(Termination::FunctionTolerance);
+        }
+        None
+    }
+}
+
+impl Default for MinimizerOptions {
+    fn default() -> MinimizerOptions {
+        MinimizerOptions::new()
+    }
+}
+
+
+/// Reason for the termination of a minimizer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Termination {
+    /// The gradient became small enough
+    GradientTolerance,
+    /// The step became small enough
+    StepTolerance,
+    /// The decrease of the objective became small enough
+    FunctionTolerance,
+    /// The iteration limit was reached
+    MaxIterations,
+    /// No acceptable step was found along the search direction
+    LineSearchFailure
+}
+
+impl Termination {
+
+    /// Indicates if the termination reason counts as convergence
+    pub fn is_converged(&self) -> bool {
+        matches!(*self, Termination::GradientTolerance
+            | Termination::StepTolerance
+            | Termination::FunctionTolerance)
+    }
+}
+
+
+/// State of a minimizer after one iteration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IterationRecord {
+    /// The iteration number (0 for the starting point)
+    pub iteration : usize,
+    /// The objective value
+    pub value : f64,
+    /// Infinity norm of the gradient (None for derivative
+    /// free methods)
+    pub gradient_norm : Option<f64>,
+    /// Length of the step taken in this iteration (the
+    /// diameter of the simplex for Nelder-Mead)
+    pub step : f64,
+    /// Number of function evaluations so far
+    pub evaluations : usize
+}
+
+
+/// Outcome of a minimizer
+#[derive(Debug, Clone)]
+pub struct MinimizeResult {
+    /// The best point found
+    pub x : MatrixF64,
+    /// The objective value at x
+    pub value : f64,
+    /// Infinity norm of the gradient at x (None for
+    /// derivative free methods)
+    pub gradient_norm : Option<f64>,
+    /// Number of iterations performed
+    pub iterations : usize,
+    /// Number of function evaluations
+    pub function_evaluations : usize,
+    /// Number of gradient evaluations
+    pub gradient_evaluations : usize,
+    /// Indicates if a convergence criterion was met
+    pub converged : bool,
+    /// Reason for the termination
+    pub termination : Termination,
+    /// One record for the starting point followed by one
+    /// record per iteration
+    pub log : Vec<IterationRecord>
+}
+
+
+/// Validates a starting point
+pub fn check_start(x0 : &MatrixF64) -> SRResult<()> {
+    if !x0.is_col() {
+        return Err(SRError::IsNotAColVector);
+    }
+    if x0.num_rows() == 0 {
+        return Err(SRError::EmptyMatrix);
+    }
+    Ok(())
+}
+
+
+/******************************************************
+ *
+ *   Unit tests follow.
+ *
+ *******************************************************/
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_stopping_criteria(){
+        let o = MinimizerOptions::new().gradient_tolerance(1e-6).step_tolerance(1e-8)
+            .function_tolerance(0.);
+        assert_eq!(o.check(2., 1., 1., 1., Some(1e-7)), Some(Termination::GradientTolerance));
+        assert_eq!(o.check(2., 1., 1e-9, 1., Some(1.)), Some(Termination::StepTolerance));
+        assert_eq!(o.check(1., 1., 1., 1., None), Some(Termination::FunctionTolerance));
+        assert_eq!(o.check(2., 1., 1., 1., Some(1.)), None);
+        assert!(Termination::StepTolerance.is_converged());
+        assert!(!Termination::MaxIterations.is_converged());
+        assert!(check_start(&matrix_rw_f64(1, 2, &[1., 2.])).is_err());
+    }
+}
@@ -1,3 +1,26 @@
 // Statistics library
 pub use moments::matrix_moments::*;
 
+pub use descriptive::sample::QuantileMethod;
+pub use descriptive::traits::*;
+pub use correlation::missing::*;
+pub use correlation::pearson::*;
+pub use correlation::rank::*;
+pub use correlation::partial::*;
+pub use streaming::univariate::*;
+pub use streaming::multivariate::*;
+pub use distributions::traits::*;
+pub use distributions::normal::*;
+pub use distributions::elementary::*;
+pub use distributions::gamma::*;
+pub use distributions::sampling::*;
+pub use distributions::discrete::*;
+pub use distributions::multivariate::*;
+pub use hypothesis::options::*;
+pub use hypothesis::t_test::*;
+pub use hypothesis::anova::*;
+pub use hypothesis::chi_square::*;
+pub use hypothesis::ks::*;
+pub use hypothesis::rank_tests::*;
+pub use hypothesis::normality::*;
+pub use hypothesis::correction::*;
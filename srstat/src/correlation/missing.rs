@@ -0,0 +1,148 @@
+#![doc="Handling of missing values in data matrices
+
+Observations are the rows and variables the columns of a data
+matrix. A missing value is a NaN entry. Under listwise deletion
+every row with a missing value is dropped before any statistic is
+computed, so all entries of the result use the same observations.
+Under pairwise deletion the statistic of two variables uses every
+row in which both of them are present; the result may then fail to
+be positive semidefinite.
+"]
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+
+
+/// Treatment of NaN entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingValues {
+    /// Use all rows; a NaN makes the affected entries NaN
+    Propagate,
+    /// Drop each row containing a NaN in any variable
+    Listwise,
+    /// Drop the rows containing a NaN in either variable of a pair
+    Pairwise
+}
+
+
+/// Returns the rows of x to keep, which under listwise deletion
+/// excludes those containing NaN
+fn kept_rows(x : &MatrixF64, missing : MissingValues) -> Vec<usize> {
+    let (rows, cols) = x.size();
+    (0..rows)
+        .filter(|&r| missing != MissingValues::Listwise || (0..cols).all(|c| !x.get(r, c).unwrap().is_nan()))
+        .collect()
+}
+
+
+/// Returns the given rows of each column of x
+fn select_rows(x : &MatrixF64, keep : &[usize]) -> Vec<Vec<f64>> {
+    (0..x.num_cols()).map(|c| keep.iter().map(|&r| x.get(r, c).unwrap()).collect()).collect()
+}
+
+
+/// Returns the columns of the data matrix, without the rows
+/// containing NaN under listwise deletion
+pub fn data_columns(x : &MatrixF64, missing : MissingValues) -> Vec<Vec<f64>> {
+    select_rows(x, &kept_rows(x, missing))
+}
+
+
+/// Returns the rows of a and b with both present under pairwise
+/// deletion, and a and b unchanged otherwise
+pub fn complete_pairs(a : &[f64], b : &[f64], missing : MissingValues) -> (Vec<f64>, Vec<f64>) {
+    if missing != MissingValues::Pairwise {
+        return (a.to_vec(), b.to_vec());
+    }
+    a.iter().zip(b.iter()).filter(|&(u, v)| !u.is_nan() && !v.is_nan()).map(|(&u, &v)| (u, v)).unzip()
+}
+
+
+/// Checks that x is a non empty data matrix
+fn check_data(x : &MatrixF64) -> SRResult<()> {
+    if x.is_empty() {
+        return Err(SRError::EmptyMatrix);
+    }
+    Ok(())
+}
+
+
+/// Applies a symmetric statistic of two variables to every pair of
+/// columns of x and returns the p x p matrix of results
+pub fn pairwise_matrix<F>(x : &MatrixF64, missing : MissingValues, f : F) -> SRResult<MatrixF64>
+    where F : Fn(&[f64], &[f64]) -> f64 {
+    check_data(x)?;
+    let columns = data_columns(x, missing);
+    let p = columns.len();
+    let mut result = Matrix::zeros(p, p);
+    for i in 0..p {
+        for j in 0..=i {
+            let (a, b) = complete_pairs(&columns[i], &columns[j], missing);
+            let value = f(&a, &b);
+            result.set(i, j, value);
+            result.set(j, i, value);
+        }
+    }
+    Ok(result)
+}
+
+
+/// Applies a statistic of two variables to every column of x paired
+/// with every column of y and returns the p x q matrix of results.
+/// Listwise deletion drops rows with a NaN in either matrix.
+pub fn cross_matrix<F>(x : &MatrixF64, y : &MatrixF64, missing : MissingValues, f : F) -> SRResult<MatrixF64>
+    where F : Fn(&[f64], &[f64]) -> f64 {
+    check_data(x)?;
+    check_data(y)?;
+    if x.num_rows() != y.num_rows() {
+        return Err(SRError::RowsMismatch);
+    }
+    let y_rows = kept_rows(y, missing);
+    let keep : Vec<usize> = kept_rows(x, missing).into_iter().filter(|r| y_rows.contains(r)).collect();
+    let x_columns = select_rows(x, &keep);
+    let y_columns = select_rows(y, &keep);
+    let mut result = Matrix::zeros(x.num_cols(), y.num_cols());
+    for (i, a) in x_columns.iter().enumerate() {
+        for (j, b) in y_columns.iter().enumerate() {
+            let (a, b) = complete_pairs(a, b, missing);
+            result.set(i, j, f(&a, &b));
+        }
+    }
+    Ok(result)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+
+    #[test]
+    fn test_deletion(){
+        let nan = f64::NAN;
+        let x = matrix_rw_f64(4, 2, &[
+            1., 2.,
+            nan, 3.,
+            4., nan,
+            5., 6.]);
+        let c = data_columns(&x, MissingValues::Listwise);
+        assert_eq!(c, vec![vec![1., 5.], vec![2., 6.]]);
+        let c = data_columns(&x, MissingValues::Pairwise);
+        assert_eq!(c[1].len(), 4);
+        let (a, b) = complete_pairs(&c[0], &c[1], MissingValues::Pairwise);
+        assert_eq!((a, b), (vec![1., 5.], vec![2., 6.]));
+        let (a, _) = complete_pairs(&c[0], &c[1], MissingValues::Propagate);
+        assert_eq!(a.len(), 4);
+        let n = pairwise_matrix(&x, MissingValues::Pairwise, |a, _| a.len() as f64).unwrap();
+        assert_eq!(n, matrix_rw_f64(2, 2, &[3., 2., 2., 3.]));
+        let y = matrix_rw_f64(3, 1, &[1., 2., 3.]);
+        assert!(cross_matrix(&x, &y, MissingValues::Pairwise, |_, _| 0.).is_err());
+    }
+}
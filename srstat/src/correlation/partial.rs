@@ -0,0 +1,98 @@
+#![doc="Partial correlation
+
+The partial correlation of variables i and j is their correlation
+after removing the linear effect of all the other variables. With
+P the inverse of the Pearson correlation matrix (the precision
+matrix)
+
+pcor(i, j) = -P_ij / sqrt(P_ii P_jj)
+
+The correlation matrix must be non-singular, so there must be more
+complete observations than variables and no variable may be a
+linear combination of the others.
+"]
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::inverse::inverse_ero;
+use correlation::missing::MissingValues;
+use correlation::pearson::corrcoef;
+
+
+/// Returns the p x p matrix of partial correlations of the columns
+/// of x given all the other columns
+pub fn partial_corr(x : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    let r = corrcoef(x, missing)?;
+    let p = r.num_rows();
+    for i in 0..p {
+        for j in 0..p {
+            if r.get(i, j).unwrap().is_nan() {
+                return Err(SRError::InvalidArgument);
+            }
+        }
+    }
+    let precision = inverse_ero(&mut r.clone())?;
+    let diagonal : Vec<f64> = (0..p).map(|i| precision.get(i, i).unwrap()).collect();
+    // A nearly singular correlation matrix may survive elimination
+    // with a meaningless inverse
+    if diagonal.iter().any(|&d| !d.is_finite() || d <= 0.) {
+        return Err(SRError::IsSingular);
+    }
+    let mut result = Matrix::identity(p, p);
+    for i in 0..p {
+        for j in 0..i {
+            let value = -precision.get(i, j).unwrap() / (diagonal[i] * diagonal[j]).sqrt();
+            let value = value.clamp(-1., 1.);
+            result.set(i, j, value);
+            result.set(j, i, value);
+        }
+    }
+    Ok(result)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+
+    #[test]
+    fn test_partial_corr(){
+        let x = matrix_rw_f64(6, 3, &[
+            1., 2., 5.,
+            2., 4., 3.,
+            3., 5., 4.,
+            4., 4., 1.,
+            5., 5., 2.,
+            6., 7., 2.]);
+        let r = corrcoef(&x, MissingValues::Propagate).unwrap();
+        let pc = partial_corr(&x, MissingValues::Propagate).unwrap();
+        // First order partial correlation of two variables given a third
+        let partial = |i : usize, j : usize, k : usize| {
+            let (rij, rik, rjk) = (r.get(i, j).unwrap(), r.get(i, k).unwrap(), r.get(j, k).unwrap());
+            (rij - rik * rjk) / ((1. - rik * rik) * (1. - rjk * rjk)).sqrt()
+        };
+        assert!((pc.get(0, 1).unwrap() - partial(0, 1, 2)).abs() < 1e-14);
+        assert!((pc.get(0, 2).unwrap() - partial(0, 2, 1)).abs() < 1e-14);
+        assert!((pc.get(2, 1).unwrap() - partial(1, 2, 0)).abs() < 1e-14);
+        assert_eq!(pc.get(1, 1).unwrap(), 1.);
+        assert_eq!(pc.get(1, 0).unwrap(), pc.get(0, 1).unwrap());
+    }
+
+    #[test]
+    fn test_partial_corr_invalid(){
+        // The second column is twice the first
+        let x = matrix_rw_f64(4, 2, &[1., 2., 2., 4., 3., 6., 4., 8.]);
+        assert!(partial_corr(&x, MissingValues::Propagate).is_err());
+        let x = matrix_rw_f64(3, 2, &[1., 2., f64::NAN, 1., 3., 7.]);
+        assert!(partial_corr(&x, MissingValues::Propagate).is_err());
+        assert!(partial_corr(&x, MissingValues::Listwise).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+#![doc="Pearson correlation and cross-covariance
+
+For variables a and b observed n times
+
+cov(a, b) = sum (a_k - mean(a)) (b_k - mean(b)) / (n - 1)
+r(a, b) = cov(a, b) / sqrt(cov(a, a) cov(b, b))
+
+Statistics of fewer than two observations, and correlations of
+constant variables, are NaN.
+"]
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use correlation::missing::{MissingValues, pairwise_matrix, cross_matrix};
+
+
+/// Returns the means of a and b and the sums of squares and cross
+/// products of their deviations
+fn centered_sums(a : &[f64], b : &[f64]) -> (f64, f64, f64) {
+    let n = a.len() as f64;
+    let ma = a.iter().sum::<f64>() / n;
+    let mb = b.iter().sum::<f64>() / n;
+    a.iter().zip(b.iter()).fold((0., 0., 0.), |(saa, sbb, sab), (&u, &v)| {
+        let (du, dv) = (u - ma, v - mb);
+        (saa + du * du, sbb + dv * dv, sab + du * dv)
+    })
+}
+
+
+/// Returns the sample covariance of two variables
+pub fn covariance(a : &[f64], b : &[f64]) -> f64 {
+    if a.len() != b.len() || a.len() < 2 {
+        return f64::NAN;
+    }
+    let (_, _, sab) = centered_sums(a, b);
+    sab / (a.len() - 1) as f64
+}
+
+
+/// Returns the Pearson correlation coefficient of two variables
+pub fn pearson_r(a : &[f64], b : &[f64]) -> f64 {
+    if a.len() != b.len() || a.len() < 2 {
+        return f64::NAN;
+    }
+    let (saa, sbb, sab) = centered_sums(a, b);
+    let r = sab / (saa * sbb).sqrt();
+    // Rounding may push |r| slightly beyond 1
+    r.clamp(-1., 1.)
+}
+
+
+/// Returns the p x p sample covariance matrix of the columns of x
+pub fn covariance_matrix(x : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    pairwise_matrix(x, missing, covariance)
+}
+
+
+/// Returns the p x p Pearson correlation matrix of the columns of x
+pub fn corrcoef(x : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    pairwise_matrix(x, missing, pearson_r)
+}
+
+
+/// Returns the p x q sample cross-covariance matrix of the columns
+/// of x and the columns of y, observed in the same rows
+pub fn cross_cov(x : &MatrixF64, y : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    cross_matrix(x, y, missing, covariance)
+}
+
+
+/// Returns the p x q Pearson cross-correlation matrix of the
+/// columns of x and the columns of y, observed in the same rows
+pub fn cross_corrcoef(x : &MatrixF64, y : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    cross_matrix(x, y, missing, pearson_r)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use moments::traits::Moments;
+    use test_support::assert_close;
+
+    #[test]
+    fn test_pair(){
+        let a = [1., 2., 3., 4., 5.];
+        let b = [2., 4., 5., 4., 5.];
+        assert!((covariance(&a, &b) - 1.5).abs() < 1e-15);
+        // 6 / sqrt(10 * 6)
+        assert!((pearson_r(&a, &b) - 0.774_596_669_241_483_4).abs() < 1e-15);
+        assert_eq!(pearson_r(&a, &a), 1.);
+        assert!(pearson_r(&a, &[1., 1., 1., 1., 1.]).is_nan());
+        assert!(pearson_r(&[1.], &[2.]).is_nan());
+    }
+
+    #[test]
+    fn test_corrcoef(){
+        let x = matrix_rw_f64(5, 3, &[
+            1., 2., 5.,
+            2., 4., 3.,
+            3., 5., 4.,
+            4., 4., 1.,
+            5., 5., 2.]);
+        let c = covariance_matrix(&x, MissingValues::Propagate).unwrap();
+        assert_close(&c, &x.cov(), 1e-14);
+        let r = corrcoef(&x, MissingValues::Propagate).unwrap();
+        assert_close(&r, &matrix_rw_f64(3, 3, &[
+            1., 0.774_596_669_241_483_4, -0.8,
+            0.774_596_669_241_483_4, 1., -0.516_397_779_494_322_2,
+            -0.8, -0.516_397_779_494_322_2, 1.]), 1e-15);
+        // Cross products of the data with itself
+        let xy = cross_cov(&x, &x, MissingValues::Propagate).unwrap();
+        assert_close(&xy, &c, 1e-15);
+        let y = matrix_rw_f64(5, 1, &[5., 3., 4., 1., 2.]);
+        let rxy = cross_corrcoef(&x, &y, MissingValues::Propagate).unwrap();
+        assert_eq!(rxy.size(), (3, 1));
+        assert_eq!(rxy.get(2, 0).unwrap(), 1.);
+        assert!(corrcoef(&Matrix::zeros(0, 0), MissingValues::Listwise).is_err());
+    }
+
+    #[test]
+    fn test_missing(){
+        let nan = f64::NAN;
+        let x = matrix_rw_f64(6, 3, &[
+            1., 2., 5.,
+            2., 4., 3.,
+            nan, 7., 0.,
+            3., 5., 4.,
+            4., 4., nan,
+            5., 5., 2.]);
+        let r = corrcoef(&x, MissingValues::Propagate).unwrap();
+        assert!(r.get(0, 1).unwrap().is_nan() && r.get(1, 2).unwrap().is_nan());
+        // Listwise keeps rows 1, 2, 4 and 6
+        let r = corrcoef(&x, MissingValues::Listwise).unwrap();
+        let kept = matrix_rw_f64(4, 3, &[1., 2., 5., 2., 4., 3., 3., 5., 4., 5., 5., 2.]);
+        assert_close(&r, &corrcoef(&kept, MissingValues::Propagate).unwrap(), 1e-15);
+        // Pairwise uses the five complete rows of columns 1 and 2
+        let r = corrcoef(&x, MissingValues::Pairwise).unwrap();
+        assert!((r.get(0, 1).unwrap() - 0.774_596_669_241_483_4).abs() < 1e-15);
+        assert!((r.get(1, 0).unwrap() - r.get(0, 1).unwrap()).abs() == 0.);
+        assert!((r.get(1, 2).unwrap() - pearson_r(&[2., 4., 7., 5., 5.], &[5., 3., 0., 4., 2.])).abs() < 1e-15);
+        let y = matrix_rw_f64(6, 1, &[1., nan, 3., 4., 5., 6.]);
+        let c = cross_cov(&x, &y, MissingValues::Listwise).unwrap();
+        assert!((c.get(0, 0).unwrap() - covariance(&[1., 3., 5.], &[1., 4., 6.])).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,187 @@
+#![doc="Rank correlations
+
+Spearman's rho is the Pearson correlation of the ranks, with tied
+values given the average of their ranks. Kendall's tau-b counts
+the concordant pairs C and discordant pairs D of observations and
+the pairs T_a and T_b tied only in a or only in b:
+
+tau_b = (C - D) / sqrt((C + D + T_a) (C + D + T_b))
+
+Pairs tied in both variables do not count. Samples containing NaN
+give NaN.
+"]
+
+// std imports
+use std::cmp::Ordering;
+
+// local imports
+use srmatrix::api::*;
+use correlation::missing::{MissingValues, pairwise_matrix, cross_matrix};
+use correlation::pearson::pearson_r;
+
+
+/// Returns the ranks 1, ..., n of the values, with ties given the
+/// average of their ranks
+pub fn ranks(values : &[f64]) -> Vec<f64> {
+    let n = values.len();
+    let mut order : Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| values[i].partial_cmp(&values[j]).unwrap_or(Ordering::Equal));
+    let mut result = vec![0.; n];
+    let mut start = 0;
+    while start < n {
+        let mut end = start + 1;
+        while end < n && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // Positions start..end hold ranks start + 1..=end
+        let rank = (start + end + 1) as f64 / 2.;
+        for &k in order[start..end].iter() {
+            result[k] = rank;
+        }
+        start = end;
+    }
+    result
+}
+
+
+/// Returns Spearman's rank correlation coefficient of two variables
+pub fn spearman_rho(a : &[f64], b : &[f64]) -> f64 {
+    if a.iter().chain(b.iter()).any(|v| v.is_nan()) {
+        return f64::NAN;
+    }
+    pearson_r(&ranks(a), &ranks(b))
+}
+
+
+/// Returns Kendall's rank correlation coefficient tau-b of two
+/// variables
+pub fn kendall_tau(a : &[f64], b : &[f64]) -> f64 {
+    let n = a.len();
+    if n != b.len() || n < 2 || a.iter().chain(b.iter()).any(|v| v.is_nan()) {
+        return f64::NAN;
+    }
+    let (mut concordant, mut discordant, mut tied_a, mut tied_b) = (0usize, 0usize, 0usize, 0usize);
+    for i in 1..n {
+        for j in 0..i {
+            let da = a[i] - a[j];
+            let db = b[i] - b[j];
+            if da == 0. && db == 0. {
+                continue;
+            }
+            if da == 0. {
+                tied_a += 1;
+            }
+            else if db == 0. {
+                tied_b += 1;
+            }
+            else if (da > 0.) == (db > 0.) {
+                concordant += 1;
+            }
+            else {
+                discordant += 1;
+            }
+        }
+    }
+    let untied = (concordant + discordant) as f64;
+    (concordant as f64 - discordant as f64) / ((untied + tied_a as f64) * (untied + tied_b as f64)).sqrt()
+}
+
+
+/// Returns the p x p Spearman correlation matrix of the columns of x
+pub fn spearman_corr(x : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    pairwise_matrix(x, missing, spearman_rho)
+}
+
+
+/// Returns the p x p Kendall tau-b correlation matrix of the
+/// columns of x
+pub fn kendall_corr(x : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    pairwise_matrix(x, missing, kendall_tau)
+}
+
+
+/// Returns the p x q Spearman correlations of the columns of x with
+/// the columns of y
+pub fn cross_spearman_corr(x : &MatrixF64, y : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    cross_matrix(x, y, missing, spearman_rho)
+}
+
+
+/// Returns the p x q Kendall tau-b correlations of the columns of x
+/// with the columns of y
+pub fn cross_kendall_corr(x : &MatrixF64, y : &MatrixF64, missing : MissingValues) -> SRResult<MatrixF64> {
+    cross_matrix(x, y, missing, kendall_tau)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+
+    #[test]
+    fn test_ranks(){
+        assert_eq!(ranks(&[3., 1., 4., 1., 5.]), vec![3., 1.5, 4., 1.5, 5.]);
+        assert_eq!(ranks(&[2., 2., 2.]), vec![2., 2., 2.]);
+        assert!(ranks(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_spearman(){
+        // Monotone but not linear
+        let a = [1., 2., 3., 4., 5., 6.];
+        let b = [1., 8., 27., 64., 125., 216.];
+        assert_eq!(spearman_rho(&a, &b), 1.);
+        assert_eq!(spearman_rho(&a, &[6., 5., 4., 3., 2., 1.]), -1.);
+        let c = [2., 1., 4., 3., 6., 5.];
+        assert!((spearman_rho(&a, &c) - 0.828_571_428_571_428_6).abs() < 1e-15);
+        // Ranks of d are 1, 3.5, 3.5, 2, 5.5, 5.5
+        let d = [1., 3., 3., 2., 5., 5.];
+        assert!((spearman_rho(&c, &d) - 0.735_612_357_920_624_5).abs() < 1e-15);
+        assert!(spearman_rho(&a, &[1., 2., 3., 4., 5., f64::NAN]).is_nan());
+    }
+
+    #[test]
+    fn test_kendall(){
+        let a = [1., 2., 3., 4., 5., 6.];
+        let c = [2., 1., 4., 3., 6., 5.];
+        // 15 pairs, 3 discordant
+        assert!((kendall_tau(&a, &c) - 0.6).abs() < 1e-15);
+        let d = [1., 3., 3., 2., 5., 5.];
+        // 11 concordant, 2 discordant, 2 tied in d only
+        assert!((kendall_tau(&c, &d) - 0.644_503_386_635_489_6).abs() < 1e-15);
+        assert_eq!(kendall_tau(&a, &a), 1.);
+        assert!(kendall_tau(&a, &[1., 1., 1., 1., 1., 1.]).is_nan());
+    }
+
+    #[test]
+    fn test_rank_matrices(){
+        let nan = f64::NAN;
+        let x = matrix_rw_f64(7, 2, &[
+            1., 2.,
+            2., 1.,
+            3., 4.,
+            nan, 9.,
+            4., 3.,
+            5., 6.,
+            6., 5.]);
+        let s = spearman_corr(&x, MissingValues::Pairwise).unwrap();
+        assert!((s.get(0, 1).unwrap() - 0.828_571_428_571_428_6).abs() < 1e-15);
+        assert_eq!(s.get(0, 0).unwrap(), 1.);
+        let k = kendall_corr(&x, MissingValues::Listwise).unwrap();
+        assert!((k.get(1, 0).unwrap() - 0.6).abs() < 1e-15);
+        let k = kendall_corr(&x, MissingValues::Propagate).unwrap();
+        assert!(k.get(0, 1).unwrap().is_nan());
+        assert_eq!(k.get(1, 1).unwrap(), 1.);
+        let y = matrix_rw_f64(7, 1, &[6., 5., 4., 3., 2., 1., 0.]);
+        let c = cross_kendall_corr(&x, &y, MissingValues::Pairwise).unwrap();
+        assert_eq!(c.get(0, 0).unwrap(), -1.);
+        let c = cross_spearman_corr(&x, &y, MissingValues::Listwise).unwrap();
+        assert_eq!(c.get(0, 0).unwrap(), -1.);
+    }
+}
@@ -0,0 +1,122 @@
+#![doc="Implementation of descriptive statistics for matrices and
+matrix views
+"]
+
+// std imports
+
+// external imports
+use num::{Float, FromPrimitive};
+
+// local imports
+use sralgebra::FieldPartial;
+use srmatrix::api::{Matrix, MatrixView};
+use descriptive::traits::{OrderStatistics, HigherMoments, Means};
+
+
+impl <T:FieldPartial + Float + FromPrimitive> OrderStatistics<T> for Matrix<T> {
+}
+
+impl <T:FieldPartial + Float + FromPrimitive> HigherMoments<T> for Matrix<T> {
+}
+
+impl <T:FieldPartial + Float + FromPrimitive> Means<T> for Matrix<T> {
+}
+
+impl <'a, T:FieldPartial + Float + FromPrimitive> OrderStatistics<T> for MatrixView<'a, T> {
+}
+
+impl <'a, T:FieldPartial + Float + FromPrimitive> HigherMoments<T> for MatrixView<'a, T> {
+}
+
+impl <'a, T:FieldPartial + Float + FromPrimitive> Means<T> for MatrixView<'a, T> {
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use srmatrix::api::*;
+    use descriptive::sample::QuantileMethod;
+    use descriptive::traits::{OrderStatistics, HigherMoments, Means};
+
+
+    #[test]
+    fn test_order_statistics_cw(){
+        let m = matrix_rw_f64(4, 3, &[
+            3., 2., 7.,
+            1., 8., 7.,
+            4., 5., 1.,
+            2., 9., 7.]);
+        assert_eq!(m.min_cw(), matrix_cw_f64(1, 3, &[1., 2., 1.]));
+        assert_eq!(m.max_cw(), matrix_cw_f64(1, 3, &[4., 9., 7.]));
+        assert_eq!(m.range_cw(), matrix_cw_f64(1, 3, &[3., 7., 6.]));
+        assert_eq!(m.median_cw(), matrix_cw_f64(1, 3, &[2.5, 6.5, 7.]));
+        assert_eq!(m.mode_cw(), matrix_cw_f64(1, 3, &[1., 2., 7.]));
+        assert_eq!(m.mad_cw(), matrix_cw_f64(1, 3, &[1., 2., 0.]));
+        assert_eq!(m.quantile_cw(0.25, QuantileMethod::Linear), matrix_cw_f64(1, 3, &[1.75, 4.25, 5.5]));
+        assert_eq!(m.iqr_cw(QuantileMethod::Linear), matrix_cw_f64(1, 3, &[1.5, 4., 1.5]));
+        let q = m.quantiles_cw(&[0., 0.5, 1.], QuantileMethod::InverseCdf);
+        assert_eq!(q, matrix_rw_f64(3, 3, &[
+            1., 2., 1.,
+            2., 5., 7.,
+            4., 9., 7.]));
+    }
+
+    #[test]
+    fn test_order_statistics_rw(){
+        let m = matrix_rw_f64(2, 5, &[
+            5., 1., 4., 2., 3.,
+            1., 1., 6., 2., 10.]);
+        assert_eq!(m.median_rw(), matrix_cw_f64(2, 1, &[3., 2.]));
+        assert_eq!(m.mode_rw(), matrix_cw_f64(2, 1, &[1., 1.]));
+        assert_eq!(m.range_rw(), matrix_cw_f64(2, 1, &[4., 9.]));
+        assert_eq!(m.quantile_rw(0.5, QuantileMethod::Hazen), matrix_cw_f64(2, 1, &[3., 2.]));
+        let q = m.quantiles_rw(&[0.25, 0.75], QuantileMethod::Linear);
+        assert_eq!(q, matrix_rw_f64(2, 2, &[2., 4., 1., 6.]));
+    }
+
+    #[test]
+    fn test_view_statistics(){
+        let m = matrix_rw_f64(3, 4, &[
+            9., 9., 9., 9.,
+            9., 1., 2., 9.,
+            9., 4., 8., 9.]);
+        let v = m.view(1, 1, 2, 2);
+        assert_eq!(v.median_cw(), matrix_cw_f64(1, 2, &[2.5, 5.]));
+        assert_eq!(v.max_rw(), matrix_cw_f64(2, 1, &[2., 8.]));
+        let g = v.geometric_mean_rw();
+        assert!((g.get(0, 0).unwrap() - 2f64.sqrt()).abs() < 1e-15);
+        assert!((g.get(1, 0).unwrap() - 32f64.sqrt()).abs() < 1e-14);
+        assert_eq!(v.trimmed_mean_cw(0.), matrix_cw_f64(1, 2, &[2.5, 5.]));
+        let s = v.skewness_cw();
+        assert!(s.get(0, 0).unwrap().abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_shape_and_means(){
+        let m = matrix_cw_f64(8, 2, &[
+            2., 8., 0., 4., 1., 9., 9., 0.,
+            1., 2., 4., 8., 1., 2., 4., 8.]);
+        let s = m.skewness_cw();
+        assert!((s.get(0, 0).unwrap() - 0.265_055_412_269_857_3).abs() < 1e-14);
+        let k = m.kurtosis_cw();
+        assert!((k.get(0, 0).unwrap() + 1.666_001_075_283_850_8).abs() < 1e-14);
+        let g = m.geometric_mean_cw();
+        assert_eq!(g.get(0, 0).unwrap(), 0.);
+        assert!((g.get(0, 1).unwrap() - 8f64.sqrt()).abs() < 1e-15);
+        let h = m.harmonic_mean_cw();
+        assert!((h.get(0, 1).unwrap() - 32. / 15.).abs() < 1e-15);
+        let t = m.trimmed_mean_cw(0.25);
+        assert_eq!(t, matrix_cw_f64(1, 2, &[3.75, 3.]));
+        let m = matrix_rw_f32(2, 4, &[3., 6., 2., 3., 1., 3., 1., 3.]);
+        let h = m.harmonic_mean_rw();
+        assert!((h.get(0, 0).unwrap() - 3.).abs() < 1e-6);
+        assert!((h.get(1, 0).unwrap() - 1.5).abs() < 1e-6);
+        assert_eq!(m.kurtosis_rw().get(1, 0).unwrap(), -2.);
+    }
+}
@@ -0,0 +1,390 @@
+#![doc="Descriptive statistics of a sample held in a slice
+
+The functions return NaN for empty samples and for samples
+containing NaN.
+"]
+
+// std imports
+use std::cmp::Ordering;
+
+// external imports
+use num::{Float, FromPrimitive};
+
+
+#[doc="Sample quantile definitions of Hyndman and Fan (Sample
+quantiles in statistical packages, The American Statistician 50,
+1996).
+
+For a sorted sample x_1 <= ... <= x_n and a probability p, let
+j = floor(n p + m) and g = n p + m - j. The quantile is
+
+Q(p) = (1 - gamma) x_j + gamma x_j+1
+
+with x_0 = x_1 and x_n+1 = x_n. The methods differ in m and gamma.
+"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuantileMethod {
+    /// Type 1, inverse of the empirical distribution function:
+    /// m = 0, gamma = 1 if g > 0 and 0 otherwise
+    InverseCdf,
+    /// Type 2, as type 1 but averaging at discontinuities:
+    /// m = 0, gamma = 1/2 if g = 0
+    AveragedInverseCdf,
+    /// Type 3, nearest even order statistic (SAS): m = -1/2,
+    /// gamma = 0 if g = 0 and j is even and 1 otherwise
+    ClosestObservation,
+    /// Type 4, linear interpolation of the empirical distribution
+    /// function: m = 0
+    InterpolatedInverseCdf,
+    /// Type 5, piecewise linear with knots at the midpoints of the
+    /// steps: m = 1/2
+    Hazen,
+    /// Type 6, p_k = k / (n + 1) (SPSS, Minitab): m = p
+    Weibull,
+    /// Type 7, p_k = (k - 1) / (n - 1) (R, NumPy and spreadsheet
+    /// default): m = 1 - p
+    #[default]
+    Linear,
+    /// Type 8, approximately median unbiased: m = (p + 1) / 3
+    MedianUnbiased,
+    /// Type 9, approximately unbiased for normal samples:
+    /// m = p / 4 + 3 / 8
+    NormalUnbiased
+}
+
+impl QuantileMethod {
+
+    /// Returns the method of the given Hyndman-Fan type 1 to 9
+    pub fn from_type(k : usize) -> Option<QuantileMethod> {
+        use self::QuantileMethod::*;
+        match k {
+            1 => Some(InverseCdf),
+            2 => Some(AveragedInverseCdf),
+            3 => Some(ClosestObservation),
+            4 => Some(InterpolatedInverseCdf),
+            5 => Some(Hazen),
+            6 => Some(Weibull),
+            7 => Some(Linear),
+            8 => Some(MedianUnbiased),
+            9 => Some(NormalUnbiased),
+            _ => None
+        }
+    }
+}
+
+
+/// Converts a count to T
+fn count<T:Float+FromPrimitive>(n : usize) -> T {
+    T::from_usize(n).unwrap()
+}
+
+
+/// Returns the values sorted in increasing order, or None if the
+/// sample is empty or contains NaN
+pub fn sorted<T:Float>(values : &[T]) -> Option<Vec<T>> {
+    if values.is_empty() || values.iter().any(|v| v.is_nan()) {
+        return None;
+    }
+    let mut v = values.to_vec();
+    v.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    Some(v)
+}
+
+
+/// Returns the quantile of probability p in [0, 1] of a sorted
+/// sample
+pub fn quantile_sorted<T:Float+FromPrimitive>(x : &[T], p : T, method : QuantileMethod) -> T {
+    use self::QuantileMethod::*;
+    let n = x.len();
+    if n == 0 || !(p >= T::zero() && p <= T::one()) {
+        return T::nan();
+    }
+    let nf : T = count(n);
+    let third : T = count::<T>(1) / count(3);
+    let half : T = count::<T>(1) / count(2);
+    let m = match method {
+        InverseCdf | AveragedInverseCdf | InterpolatedInverseCdf => T::zero(),
+        ClosestObservation => -half,
+        Hazen => half,
+        Weibull => p,
+        Linear => T::one() - p,
+        MedianUnbiased => (p + T::one()) * third,
+        NormalUnbiased => p / count(4) + count::<T>(3) / count(8)
+    };
+    let npm = nf * p + m;
+    // Absorb rounding errors in n p + m as R does
+    let fuzz = count::<T>(4) * T::epsilon() * npm.abs().max(T::one());
+    let j = (npm + fuzz).floor();
+    let mut g = npm - j;
+    if g.abs() < fuzz {
+        g = T::zero();
+    }
+    let gamma = match method {
+        InverseCdf => if g > T::zero() { T::one() } else { T::zero() },
+        AveragedInverseCdf => if g > T::zero() { T::one() } else { half },
+        ClosestObservation => {
+            let even = (j * half).floor() * count(2) == j;
+            if g == T::zero() && even { T::zero() } else { T::one() }
+        },
+        _ => g
+    };
+    // 1-based order statistics x_j and x_j+1 clamped to the sample
+    let at = |k : T| {
+        let k = k.max(T::one()).min(nf).to_usize().unwrap();
+        x[k - 1]
+    };
+    let (lo, hi) = (at(j), at(j + T::one()));
+    if gamma == T::zero() {
+        lo
+    }
+    else if gamma == T::one() {
+        hi
+    }
+    else {
+        lo + gamma * (hi - lo)
+    }
+}
+
+
+/// Returns the quantile of probability p in [0, 1]
+pub fn quantile<T:Float+FromPrimitive>(values : &[T], p : T, method : QuantileMethod) -> T {
+    match sorted(values) {
+        Some(x) => quantile_sorted(&x, p, method),
+        None => T::nan()
+    }
+}
+
+
+/// Returns the median
+pub fn median<T:Float+FromPrimitive>(values : &[T]) -> T {
+    quantile(values, count::<T>(1) / count(2), QuantileMethod::Linear)
+}
+
+
+/// Returns the interquartile range Q(3/4) - Q(1/4)
+pub fn iqr<T:Float+FromPrimitive>(values : &[T], method : QuantileMethod) -> T {
+    match sorted(values) {
+        Some(x) => {
+            let quarter = count::<T>(1) / count(4);
+            quantile_sorted(&x, count::<T>(3) * quarter, method) - quantile_sorted(&x, quarter, method)
+        },
+        None => T::nan()
+    }
+}
+
+
+/// Returns the median absolute deviation from the median,
+/// median(|x - median(x)|), without a consistency factor
+pub fn mad<T:Float+FromPrimitive>(values : &[T]) -> T {
+    let center = median(values);
+    if center.is_nan() {
+        return center;
+    }
+    let deviations : Vec<T> = values.iter().map(|&v| (v - center).abs()).collect();
+    median(&deviations)
+}
+
+
+/// Returns the most frequent value, the smallest one in case of
+/// ties
+pub fn mode<T:Float>(values : &[T]) -> T {
+    let x = match sorted(values) {
+        Some(x) => x,
+        None => return T::nan()
+    };
+    let (mut best, mut best_count) = (x[0], 0);
+    let mut start = 0;
+    for i in 1..=x.len() {
+        if i == x.len() || x[i] != x[start] {
+            if i - start > best_count {
+                best = x[start];
+                best_count = i - start;
+            }
+            start = i;
+        }
+    }
+    best
+}
+
+
+/// Returns the smallest value
+pub fn min<T:Float>(values : &[T]) -> T {
+    if values.is_empty() || values.iter().any(|v| v.is_nan()) {
+        return T::nan();
+    }
+    values.iter().fold(T::infinity(), |m, &v| m.min(v))
+}
+
+
+/// Returns the largest value
+pub fn max<T:Float>(values : &[T]) -> T {
+    if values.is_empty() || values.iter().any(|v| v.is_nan()) {
+        return T::nan();
+    }
+    values.iter().fold(T::neg_infinity(), |m, &v| m.max(v))
+}
+
+
+/// Returns max - min
+pub fn range<T:Float>(values : &[T]) -> T {
+    max(values) - min(values)
+}
+
+
+/// Returns the arithmetic mean
+pub fn mean<T:Float+FromPrimitive>(values : &[T]) -> T {
+    if values.is_empty() {
+        return T::nan();
+    }
+    values.iter().fold(T::zero(), |s, &v| s + v) / count(values.len())
+}
+
+
+/// Returns the central moments of orders 2, 3 and 4 divided by n
+fn central_moments<T:Float+FromPrimitive>(values : &[T]) -> (T, T, T) {
+    let mu = mean(values);
+    let n : T = count(values.len());
+    let (m2, m3, m4) = values.iter().fold((T::zero(), T::zero(), T::zero()), |(a, b, c), &v| {
+        let d = v - mu;
+        let d2 = d * d;
+        (a + d2, b + d2 * d, c + d2 * d2)
+    });
+    (m2 / n, m3 / n, m4 / n)
+}
+
+
+/// Returns the skewness m_3 / m_2^(3/2) with the biased central
+/// moments m_k = sum (x - mean)^k / n
+pub fn skewness<T:Float+FromPrimitive>(values : &[T]) -> T {
+    if values.is_empty() {
+        return T::nan();
+    }
+    let (m2, m3, _) = central_moments(values);
+    m3 / (m2 * m2.sqrt())
+}
+
+
+/// Returns the excess kurtosis m_4 / m_2^2 - 3 with the biased
+/// central moments
+pub fn kurtosis<T:Float+FromPrimitive>(values : &[T]) -> T {
+    if values.is_empty() {
+        return T::nan();
+    }
+    let (m2, _, m4) = central_moments(values);
+    m4 / (m2 * m2) - count(3)
+}
+
+
+/// Returns the geometric mean exp(mean(ln x)) of non-negative
+/// values
+pub fn geometric_mean<T:Float+FromPrimitive>(values : &[T]) -> T {
+    if values.is_empty() || values.iter().any(|&v| v < T::zero()) {
+        return T::nan();
+    }
+    let logs : Vec<T> = values.iter().map(|v| v.ln()).collect();
+    mean(&logs).exp()
+}
+
+
+/// Returns the harmonic mean n / sum(1 / x) of non-negative values
+pub fn harmonic_mean<T:Float+FromPrimitive>(values : &[T]) -> T {
+    if values.is_empty() || values.iter().any(|&v| v.is_nan() || v < T::zero()) {
+        return T::nan();
+    }
+    if values.iter().any(|&v| v == T::zero()) {
+        return T::zero();
+    }
+    count::<T>(values.len()) / values.iter().fold(T::zero(), |s, &v| s + v.recip())
+}
+
+
+/// Returns the mean after removing floor(proportion n) values
+/// from each end of the sorted sample, for proportion in [0, 1/2)
+pub fn trimmed_mean<T:Float+FromPrimitive>(values : &[T], proportion : T) -> T {
+    if !(proportion >= T::zero() && proportion < count::<T>(1) / count(2)) {
+        return T::nan();
+    }
+    let x = match sorted(values) {
+        Some(x) => x,
+        None => return T::nan()
+    };
+    let k = (proportion * count(x.len())).floor().to_usize().unwrap();
+    mean(&x[k..x.len() - k])
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+
+    #[test]
+    fn test_quantile_types(){
+        // Reference values of R, quantile(x, p, type = k)
+        let x = [2., 9., 4., 7., 5., 1., 8., 3.];
+        let p = [0.1, 0.25, 0.5, 0.9];
+        let expected = [
+            [1., 2., 4., 9.],
+            [1., 2.5, 4.5, 9.],
+            [1., 2., 4., 8.],
+            [1., 2., 4., 8.2],
+            [1.3, 2.5, 4.5, 8.7],
+            [1., 2.25, 4.5, 9.],
+            [1.7, 2.75, 4.5, 8.3],
+            [1.166_666_666_666_666_7, 2.416_666_666_666_666_5, 4.5, 8.833_333_333_333_334],
+            [1.2, 2.4375, 4.5, 8.8]];
+        for (k, row) in expected.iter().enumerate() {
+            let method = QuantileMethod::from_type(k + 1).unwrap();
+            for (&pi, &e) in p.iter().zip(row.iter()) {
+                let q = quantile(&x, pi, method);
+                assert!((q - e).abs() < 1e-12, "type {} p {} {} {}", k + 1, pi, q, e);
+            }
+        }
+        assert!(QuantileMethod::from_type(10).is_none());
+        assert_eq!(quantile(&x, 0., QuantileMethod::Linear), 1.);
+        assert_eq!(quantile(&x, 1., QuantileMethod::Weibull), 9.);
+        assert!(quantile(&x, 1.5, QuantileMethod::Linear).is_nan());
+        assert!(quantile::<f64>(&[], 0.5, QuantileMethod::Linear).is_nan());
+    }
+
+    #[test]
+    fn test_order_statistics(){
+        let x = [3., 1., 4., 1., 5., 9., 2., 6., 5., 3., 5.];
+        assert_eq!(median(&x), 4.);
+        assert_eq!(median(&[4., 1., 3., 2.]), 2.5);
+        assert_eq!(mode(&x), 5.);
+        assert_eq!(mode(&[2., 1., 2., 1.]), 1.);
+        assert_eq!(min(&x), 1.);
+        assert_eq!(max(&x), 9.);
+        assert_eq!(range(&x), 8.);
+        assert_eq!(iqr(&x, QuantileMethod::Linear), 2.5);
+        // |x - 4| = 1 3 0 3 1 5 2 2 1 1 1
+        assert_eq!(mad(&x), 1.);
+        assert!(median(&[1., f64::NAN]).is_nan());
+        assert!(mode::<f32>(&[]).is_nan());
+    }
+
+    #[test]
+    fn test_shape_and_means(){
+        // Reference values of scipy.stats.skew and kurtosis
+        let x = [2., 8., 0., 4., 1., 9., 9., 0.];
+        assert!((skewness(&x) - 0.265_055_412_269_857_3).abs() < 1e-14);
+        assert!((kurtosis(&x) + 1.666_001_075_283_850_8).abs() < 1e-14);
+        assert!(skewness(&[1., 2., 3.]).abs() < 1e-15);
+        let y = [1., 2., 4., 8.];
+        assert!((geometric_mean(&y) - 8f64.sqrt()).abs() < 1e-15);
+        assert!((harmonic_mean(&y) - 32. / 15.).abs() < 1e-15);
+        assert_eq!(harmonic_mean(&[0., 1.]), 0.);
+        assert!(geometric_mean(&[-1., 1.]).is_nan());
+        let z = [1., 2., 3., 4., 5., 6., 7., 8., 9., 100.];
+        assert_eq!(trimmed_mean(&z, 0.1), 5.5);
+        assert_eq!(trimmed_mean(&z, 0.), 14.5);
+        assert!(trimmed_mean(&z, 0.5).is_nan());
+        assert!((skewness(&[2f32, 8., 0., 4., 1., 9., 9., 0.]) - 0.265_055_4).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,236 @@
+#![doc="Traits describing descriptive statistics of data beyond the
+moments
+
+As with ``Moments``, the ``_cw`` methods compute a statistic over
+each column and return a row vector, and the ``_rw`` methods
+compute it over each row and return a column vector. The methods
+are provided in terms of ``Shape``, so any matrix type gets them
+with an empty implementation.
+"]
+
+// std imports
+use num::{Float, FromPrimitive};
+
+// local imports
+use srmatrix::api::{Matrix, Shape};
+use sralgebra::FieldPartial;
+use descriptive::sample::{self, QuantileMethod};
+
+
+/// Returns the entries of column c
+fn column<T:FieldPartial, M:Shape<T>+?Sized>(m : &M, c : usize) -> Vec<T> {
+    (0..m.num_rows()).map(|r| m.get(r, c).unwrap()).collect()
+}
+
+/// Returns the entries of row r
+fn row<T:FieldPartial, M:Shape<T>+?Sized>(m : &M, r : usize) -> Vec<T> {
+    (0..m.num_cols()).map(|c| m.get(r, c).unwrap()).collect()
+}
+
+/// Applies a statistic to each column and returns a row vector
+fn apply_cw<T:FieldPartial, M:Shape<T>+?Sized, F:Fn(&[T]) -> T>(m : &M, f : F) -> Matrix<T> {
+    let cols = m.num_cols();
+    let mut result = Matrix::new_uninitialized(1, cols);
+    for c in 0..cols {
+        result.set(0, c, f(&column(m, c)));
+    }
+    result
+}
+
+/// Applies a statistic to each row and returns a column vector
+fn apply_rw<T:FieldPartial, M:Shape<T>+?Sized, F:Fn(&[T]) -> T>(m : &M, f : F) -> Matrix<T> {
+    let rows = m.num_rows();
+    let mut result = Matrix::new_uninitialized(rows, 1);
+    for r in 0..rows {
+        result.set(r, 0, f(&row(m, r)));
+    }
+    result
+}
+
+
+pub trait OrderStatistics <T: FieldPartial + Float + FromPrimitive> : Shape<T> {
+
+    /// Computes minimum over columns and returns a row vector
+    fn min_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::min)
+    }
+
+    /// Computes minimum over rows and returns a column vector
+    fn min_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::min)
+    }
+
+    /// Computes maximum over columns and returns a row vector
+    fn max_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::max)
+    }
+
+    /// Computes maximum over rows and returns a column vector
+    fn max_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::max)
+    }
+
+    /// Computes max - min over columns and returns a row vector
+    fn range_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::range)
+    }
+
+    /// Computes max - min over rows and returns a column vector
+    fn range_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::range)
+    }
+
+    /// Computes median over columns and returns a row vector
+    fn median_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::median)
+    }
+
+    /// Computes median over rows and returns a column vector
+    fn median_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::median)
+    }
+
+    /// Computes the quantile of probability p over columns and
+    /// returns a row vector
+    fn quantile_cw(&self, p : T, method : QuantileMethod) -> Matrix<T> {
+        apply_cw(self, |x| sample::quantile(x, p, method))
+    }
+
+    /// Computes the quantile of probability p over rows and
+    /// returns a column vector
+    fn quantile_rw(&self, p : T, method : QuantileMethod) -> Matrix<T> {
+        apply_rw(self, |x| sample::quantile(x, p, method))
+    }
+
+    /// Computes several quantiles over columns. Row i of the
+    /// result holds the quantiles of probability p[i].
+    fn quantiles_cw(&self, p : &[T], method : QuantileMethod) -> Matrix<T> {
+        let cols = self.num_cols();
+        let mut result = Matrix::new_uninitialized(p.len(), cols);
+        for c in 0..cols {
+            let x = sample::sorted(&column(self, c));
+            for (i, &pi) in p.iter().enumerate() {
+                let q = x.as_ref().map_or(T::nan(), |x| sample::quantile_sorted(x, pi, method));
+                result.set(i, c, q);
+            }
+        }
+        result
+    }
+
+    /// Computes several quantiles over rows. Column i of the
+    /// result holds the quantiles of probability p[i].
+    fn quantiles_rw(&self, p : &[T], method : QuantileMethod) -> Matrix<T> {
+        let rows = self.num_rows();
+        let mut result = Matrix::new_uninitialized(rows, p.len());
+        for r in 0..rows {
+            let x = sample::sorted(&row(self, r));
+            for (i, &pi) in p.iter().enumerate() {
+                let q = x.as_ref().map_or(T::nan(), |x| sample::quantile_sorted(x, pi, method));
+                result.set(r, i, q);
+            }
+        }
+        result
+    }
+
+    /// Computes interquartile range over columns and returns a
+    /// row vector
+    fn iqr_cw(&self, method : QuantileMethod) -> Matrix<T> {
+        apply_cw(self, |x| sample::iqr(x, method))
+    }
+
+    /// Computes interquartile range over rows and returns a
+    /// column vector
+    fn iqr_rw(&self, method : QuantileMethod) -> Matrix<T> {
+        apply_rw(self, |x| sample::iqr(x, method))
+    }
+
+    /// Computes median absolute deviation over columns and
+    /// returns a row vector
+    fn mad_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::mad)
+    }
+
+    /// Computes median absolute deviation over rows and returns
+    /// a column vector
+    fn mad_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::mad)
+    }
+
+    /// Computes the most frequent value over columns and returns
+    /// a row vector
+    fn mode_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::mode)
+    }
+
+    /// Computes the most frequent value over rows and returns a
+    /// column vector
+    fn mode_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::mode)
+    }
+}
+
+
+pub trait HigherMoments <T: FieldPartial + Float + FromPrimitive> : Shape<T> {
+
+    /// Computes skewness over columns and returns a row vector
+    fn skewness_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::skewness)
+    }
+
+    /// Computes skewness over rows and returns a column vector
+    fn skewness_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::skewness)
+    }
+
+    /// Computes excess kurtosis over columns and returns a row
+    /// vector
+    fn kurtosis_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::kurtosis)
+    }
+
+    /// Computes excess kurtosis over rows and returns a column
+    /// vector
+    fn kurtosis_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::kurtosis)
+    }
+}
+
+
+pub trait Means <T: FieldPartial + Float + FromPrimitive> : Shape<T> {
+
+    /// Computes geometric mean over columns and returns a row
+    /// vector
+    fn geometric_mean_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::geometric_mean)
+    }
+
+    /// Computes geometric mean over rows and returns a column
+    /// vector
+    fn geometric_mean_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::geometric_mean)
+    }
+
+    /// Computes harmonic mean over columns and returns a row
+    /// vector
+    fn harmonic_mean_cw(&self) -> Matrix<T> {
+        apply_cw(self, sample::harmonic_mean)
+    }
+
+    /// Computes harmonic mean over rows and returns a column
+    /// vector
+    fn harmonic_mean_rw(&self) -> Matrix<T> {
+        apply_rw(self, sample::harmonic_mean)
+    }
+
+    /// Computes the mean over columns after trimming the given
+    /// proportion from each end and returns a row vector
+    fn trimmed_mean_cw(&self, proportion : T) -> Matrix<T> {
+        apply_cw(self, |x| sample::trimmed_mean(x, proportion))
+    }
+
+    /// Computes the mean over rows after trimming the given
+    /// proportion from each end and returns a column vector
+    fn trimmed_mean_rw(&self, proportion : T) -> Matrix<T> {
+        apply_rw(self, |x| sample::trimmed_mean(x, proportion))
+    }
+}
@@ -0,0 +1,448 @@
+#![doc="Discrete distributions on the non negative integers: binomial,
+Poisson, geometric and negative binomial
+
+The distribution functions are incomplete beta and gamma functions
+
+P(Binomial(n, p) <= k) = I_{1 - p}(n - k, k + 1)
+P(Poisson(lambda) <= k) = Q(k + 1, lambda)
+P(NegativeBinomial(r, p) <= k) = I_p(r, k + 1)
+
+and quantiles are found by searching for the smallest k with
+P(X <= k) >= p. The geometric and negative binomial distributions
+count the failures before the first (or r-th) success, so both
+start at zero.
+"]
+
+// std imports
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::SRResult;
+use srnum::special::gamma::{ln_gamma, gamma_p, gamma_q};
+use srnum::special::beta::beta_inc;
+use distributions::traits::Distribution;
+use distributions::support::{check, is_probability, open_uniform, standard_gamma,
+    poisson, discrete_quantile, discrete_entropy};
+
+
+/// Returns true if x is a non negative integer
+fn is_count(x : f64) -> bool {
+    x >= 0. && x.floor() == x
+}
+
+
+/// Returns ln(n! / (k! (n - k)!)) for real n and k
+fn ln_binomial(n : f64, k : f64) -> f64 {
+    ln_gamma(n + 1.) - ln_gamma(k + 1.) - ln_gamma(n - k + 1.)
+}
+
+
+/// Binomial distribution: the number of successes in n independent
+/// trials of success probability p
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Binomial {
+    n : f64,
+    p : f64
+}
+
+
+impl Binomial {
+
+    /// Creates a binomial distribution with 0 <= p <= 1
+    pub fn new(n : usize, p : f64) -> SRResult<Binomial> {
+        check(is_probability(p))?;
+        Ok(Binomial { n : n as f64, p : p })
+    }
+}
+
+
+impl Distribution for Binomial {
+
+    fn pdf(&self, x : f64) -> f64 {
+        if !is_count(x) || x > self.n {
+            return 0.;
+        }
+        // Degenerate distributions
+        if self.p == 0. || self.p == 1. {
+            let k = if self.p == 0. { 0. } else { self.n };
+            return if x == k { 1. } else { 0. };
+        }
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        if self.p == 0. || self.p == 1. || !is_count(x) || x > self.n {
+            return self.pdf(x).ln();
+        }
+        ln_binomial(self.n, x) + x * self.p.ln() + (self.n - x) * (-self.p).ln_1p()
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        let k = x.floor();
+        if k < 0. {
+            return 0.;
+        }
+        if k >= self.n {
+            return 1.;
+        }
+        beta_inc(self.n - k, k + 1., 1. - self.p)
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        let k = x.floor();
+        if k < 0. {
+            return 1.;
+        }
+        if k >= self.n {
+            return 0.;
+        }
+        beta_inc(k + 1., self.n - k, self.p)
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        if p == 1. {
+            return self.n;
+        }
+        discrete_quantile(|k| self.cdf(k), p, self.mean()).min(self.n)
+    }
+
+    fn mean(&self) -> f64 {
+        self.n * self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.n * self.p * (1. - self.p)
+    }
+
+    fn entropy(&self) -> f64 {
+        let mode = ((self.n + 1.) * self.p).floor().min(self.n);
+        discrete_entropy(|k| self.pdf(k), mode, self.n)
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        if self.n < 50. {
+            let mut k = 0.;
+            for _ in 0..(self.n as usize) {
+                if rng.gen::<f64>() < self.p {
+                    k += 1.;
+                }
+            }
+            return k;
+        }
+        self.quantile(rng.gen::<f64>())
+    }
+}
+
+
+/// Poisson distribution with mean lambda
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Poisson {
+    lambda : f64
+}
+
+
+impl Poisson {
+
+    /// Creates a Poisson distribution with positive mean
+    pub fn new(lambda : f64) -> SRResult<Poisson> {
+        check(lambda > 0. && lambda.is_finite())?;
+        Ok(Poisson { lambda : lambda })
+    }
+}
+
+
+impl Distribution for Poisson {
+
+    fn pdf(&self, x : f64) -> f64 {
+        if is_count(x) { self.ln_pdf(x).exp() } else { 0. }
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        if !is_count(x) {
+            return f64::NEG_INFINITY;
+        }
+        x * self.lambda.ln() - self.lambda - ln_gamma(x + 1.)
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        let k = x.floor();
+        if k < 0. { 0. } else { gamma_q(k + 1., self.lambda) }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        let k = x.floor();
+        if k < 0. { 1. } else { gamma_p(k + 1., self.lambda) }
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        if p == 1. {
+            return f64::INFINITY;
+        }
+        discrete_quantile(|k| self.cdf(k), p, self.lambda)
+    }
+
+    fn mean(&self) -> f64 {
+        self.lambda
+    }
+
+    fn variance(&self) -> f64 {
+        self.lambda
+    }
+
+    fn entropy(&self) -> f64 {
+        discrete_entropy(|k| self.pdf(k), self.lambda.floor(), f64::INFINITY)
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        poisson(rng, self.lambda)
+    }
+}
+
+
+/// Geometric distribution: the number of failures before the first
+/// success in independent trials of success probability p
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geometric {
+    p : f64
+}
+
+
+impl Geometric {
+
+    /// Creates a geometric distribution with 0 < p < 1
+    pub fn new(p : f64) -> SRResult<Geometric> {
+        check(p > 0. && p < 1.)?;
+        Ok(Geometric { p : p })
+    }
+}
+
+
+impl Distribution for Geometric {
+
+    fn pdf(&self, x : f64) -> f64 {
+        if is_count(x) { self.ln_pdf(x).exp() } else { 0. }
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        if !is_count(x) {
+            return f64::NEG_INFINITY;
+        }
+        self.p.ln() + x * (-self.p).ln_1p()
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        let k = x.floor();
+        if k < 0. { 0. } else { -((k + 1.) * (-self.p).ln_1p()).exp_m1() }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        let k = x.floor();
+        if k < 0. { 1. } else { ((k + 1.) * (-self.p).ln_1p()).exp() }
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        if p == 1. {
+            return f64::INFINITY;
+        }
+        // Closed form, corrected for rounding
+        let guess = ((-p).ln_1p() / (-self.p).ln_1p() - 1.).ceil();
+        discrete_quantile(|k| self.cdf(k), p, guess)
+    }
+
+    fn mean(&self) -> f64 {
+        (1. - self.p) / self.p
+    }
+
+    fn variance(&self) -> f64 {
+        (1. - self.p) / (self.p * self.p)
+    }
+
+    fn entropy(&self) -> f64 {
+        let (p, q) = (self.p, 1. - self.p);
+        -(q * q.ln() + p * p.ln()) / p
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        (open_uniform(rng).ln() / (-self.p).ln_1p()).floor()
+    }
+}
+
+
+/// Negative binomial distribution: the number of failures before
+/// the r-th success in independent trials of success probability
+/// p. A real r > 0 gives the gamma mixture of Poisson
+/// distributions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NegativeBinomial {
+    r : f64,
+    p : f64
+}
+
+
+impl NegativeBinomial {
+
+    /// Creates a negative binomial distribution with r > 0 and
+    /// 0 < p < 1
+    pub fn new(r : f64, p : f64) -> SRResult<NegativeBinomial> {
+        check(r > 0. && r.is_finite() && p > 0. && p < 1.)?;
+        Ok(NegativeBinomial { r : r, p : p })
+    }
+}
+
+
+impl Distribution for NegativeBinomial {
+
+    fn pdf(&self, x : f64) -> f64 {
+        if is_count(x) { self.ln_pdf(x).exp() } else { 0. }
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        if !is_count(x) {
+            return f64::NEG_INFINITY;
+        }
+        let r = self.r;
+        ln_gamma(x + r) - ln_gamma(r) - ln_gamma(x + 1.) + r * self.p.ln() + x * (-self.p).ln_1p()
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        let k = x.floor();
+        if k < 0. { 0. } else { beta_inc(self.r, k + 1., self.p) }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        let k = x.floor();
+        if k < 0. { 1. } else { beta_inc(k + 1., self.r, 1. - self.p) }
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        if p == 1. {
+            return f64::INFINITY;
+        }
+        discrete_quantile(|k| self.cdf(k), p, self.mean())
+    }
+
+    fn mean(&self) -> f64 {
+        self.r * (1. - self.p) / self.p
+    }
+
+    fn variance(&self) -> f64 {
+        self.r * (1. - self.p) / (self.p * self.p)
+    }
+
+    fn entropy(&self) -> f64 {
+        let mode = ((self.r - 1.) * (1. - self.p) / self.p).floor().max(0.);
+        discrete_entropy(|k| self.pdf(k), mode, f64::INFINITY)
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        let lambda = standard_gamma(rng, self.r) * (1. - self.p) / self.p;
+        if lambda > 0. { poisson(rng, lambda) } else { 0. }
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use distributions::support::assert_sample_moments;
+    use test_support::assert_near;
+
+    /// Checks that the quantile is the smallest k with cdf(k) >= p
+    fn assert_quantiles<D:Distribution>(d : &D, last : usize) {
+        for k in 0..last {
+            let k = k as f64;
+            let c = d.cdf(k);
+            assert_eq!(d.quantile(c), k);
+            if k > 0. {
+                assert_eq!(d.quantile(0.5 * (d.cdf(k - 1.) + c)), k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_binomial(){
+        let d = Binomial::new(20, 0.3).unwrap();
+        assert_near(d.pdf(6.), 0.191_638_982_753_442_57, 1e-14);
+        assert_near(d.cdf(6.), 0.608_009_812_200_924, 1e-14);
+        assert_near(d.cdf(6.5), 0.608_009_812_200_924, 1e-14);
+        assert_near(d.sf(6.), 1. - 0.608_009_812_200_924, 1e-14);
+        assert_near(d.entropy(), 2.132_538_641_661_49, 1e-14);
+        assert_eq!((d.pdf(6.5), d.pdf(21.), d.cdf(-1.), d.cdf(20.)), (0., 0., 0., 1.));
+        assert_eq!((d.quantile(0.5), d.quantile(0.), d.quantile(1.)), (6., 0., 20.));
+        assert_near(d.mean(), 6., 1e-15);
+        assert_near(d.variance(), 4.2, 1e-15);
+        assert_quantiles(&d, 15);
+        assert_sample_moments(&d, 13);
+        // Sampled by inversion
+        assert_sample_moments(&Binomial::new(1000, 0.05).unwrap(), 14);
+        let one = Binomial::new(5, 1.).unwrap();
+        assert_eq!((one.pdf(5.), one.pdf(4.), one.entropy()), (1., 0., 0.));
+        assert!(Binomial::new(5, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_poisson(){
+        let d = Poisson::new(4.5).unwrap();
+        assert_near(d.pdf(3.), 0.168_717_884_924_555_03, 1e-14);
+        assert_near(d.cdf(3.), 0.342_295_955_834_591_05, 1e-14);
+        assert_near(d.sf(3.), 1. - 0.342_295_955_834_591_05, 1e-14);
+        assert_near(d.entropy(), 2.149_057_657_291_178, 1e-14);
+        assert_eq!((d.quantile(0.9), d.quantile(1.)), (7., f64::INFINITY));
+        assert_eq!((d.mean(), d.variance()), (4.5, 4.5));
+        assert_quantiles(&d, 15);
+        assert_sample_moments(&d, 15);
+        // Sampled by transformed rejection
+        assert_sample_moments(&Poisson::new(250.).unwrap(), 16);
+        assert!(Poisson::new(0.).is_err());
+    }
+
+    #[test]
+    fn test_geometric(){
+        let d = Geometric::new(0.25).unwrap();
+        assert_near(d.pdf(2.), 0.25 * 0.75 * 0.75, 1e-15);
+        assert_near(d.cdf(2.), 1. - 0.75f64.powi(3), 1e-15);
+        assert_near(d.sf(2.), 0.75f64.powi(3), 1e-15);
+        assert_near(d.entropy(), 2.249_340_578_475_233_6, 1e-15);
+        assert_eq!((d.mean(), d.variance()), (3., 12.));
+        assert_quantiles(&d, 30);
+        assert_sample_moments(&d, 17);
+        assert!(Geometric::new(0.).is_err());
+    }
+
+    #[test]
+    fn test_negative_binomial(){
+        let d = NegativeBinomial::new(3.5, 0.4).unwrap();
+        assert_near(d.pdf(4.), 0.123_072_304_782_773_94, 1e-14);
+        assert_near(d.cdf(4.), 0.489_532_218_975_854_5, 1e-14);
+        assert_near(d.sf(4.), 1. - 0.489_532_218_975_854_5, 1e-14);
+        assert_near(d.entropy(), 2.588_013_894_867_419_4, 1e-14);
+        assert_near(d.mean(), 5.25, 1e-15);
+        assert_near(d.variance(), 13.125, 1e-15);
+        assert_quantiles(&d, 30);
+        assert_sample_moments(&d, 18);
+        // r = 1 is the geometric distribution
+        let g = NegativeBinomial::new(1., 0.25).unwrap();
+        assert_near(g.cdf(5.), Geometric::new(0.25).unwrap().cdf(5.), 1e-14);
+        assert!(NegativeBinomial::new(2., 1.).is_err());
+    }
+}
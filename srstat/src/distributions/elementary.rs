@@ -0,0 +1,336 @@
+#![doc="Distributions with closed form distribution functions:
+uniform, exponential, Weibull and Cauchy
+
+These are sampled by inverting the distribution function.
+"]
+
+// std imports
+use std::f64::consts::PI;
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::SRResult;
+use srnum::special::gamma::gamma;
+use distributions::traits::Distribution;
+use distributions::support::{check, is_probability, open_uniform, standard_exponential};
+
+
+/// Euler–Mascheroni constant
+const EULER_GAMMA : f64 = 0.577_215_664_901_532_9;
+
+
+/// Continuous uniform distribution on [a, b]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Uniform {
+    a : f64,
+    b : f64
+}
+
+
+impl Uniform {
+
+    /// Creates a uniform distribution on [a, b] with a < b
+    pub fn new(a : f64, b : f64) -> SRResult<Uniform> {
+        check(a.is_finite() && b.is_finite() && a < b)?;
+        Ok(Uniform { a : a, b : b })
+    }
+}
+
+
+impl Distribution for Uniform {
+
+    fn pdf(&self, x : f64) -> f64 {
+        if x < self.a || x > self.b { 0. } else { 1. / (self.b - self.a) }
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        ((x - self.a) / (self.b - self.a)).clamp(0., 1.)
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        self.a + p * (self.b - self.a)
+    }
+
+    fn mean(&self) -> f64 {
+        0.5 * (self.a + self.b)
+    }
+
+    fn variance(&self) -> f64 {
+        let w = self.b - self.a;
+        w * w / 12.
+    }
+
+    fn entropy(&self) -> f64 {
+        (self.b - self.a).ln()
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        self.a + rng.gen::<f64>() * (self.b - self.a)
+    }
+}
+
+
+/// Exponential distribution with density rate exp(-rate x) on
+/// x >= 0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exponential {
+    rate : f64
+}
+
+
+impl Exponential {
+
+    /// Creates an exponential distribution with positive rate
+    pub fn new(rate : f64) -> SRResult<Exponential> {
+        check(rate > 0. && rate.is_finite())?;
+        Ok(Exponential { rate : rate })
+    }
+}
+
+
+impl Distribution for Exponential {
+
+    fn pdf(&self, x : f64) -> f64 {
+        if x < 0. { 0. } else { self.rate * (-self.rate * x).exp() }
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        if x < 0. { f64::NEG_INFINITY } else { self.rate.ln() - self.rate * x }
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        if x <= 0. { 0. } else { -(-self.rate * x).exp_m1() }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        if x <= 0. { 1. } else { (-self.rate * x).exp() }
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        -(-p).ln_1p() / self.rate
+    }
+
+    fn mean(&self) -> f64 {
+        1. / self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        1. / (self.rate * self.rate)
+    }
+
+    fn entropy(&self) -> f64 {
+        1. - self.rate.ln()
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        standard_exponential(rng) / self.rate
+    }
+}
+
+
+/// Weibull distribution with shape k and scale lambda:
+/// P(X > x) = exp(-(x / lambda)^k) for x >= 0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weibull {
+    shape : f64,
+    scale : f64
+}
+
+
+impl Weibull {
+
+    /// Creates a Weibull distribution with positive shape and scale
+    pub fn new(shape : f64, scale : f64) -> SRResult<Weibull> {
+        check(shape > 0. && shape.is_finite() && scale > 0. && scale.is_finite())?;
+        Ok(Weibull { shape : shape, scale : scale })
+    }
+}
+
+
+impl Distribution for Weibull {
+
+    fn pdf(&self, x : f64) -> f64 {
+        if x < 0. {
+            return 0.;
+        }
+        let y = x / self.scale;
+        self.shape / self.scale * y.powf(self.shape - 1.) * (-y.powf(self.shape)).exp()
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        if x <= 0. { 0. } else { -(-(x / self.scale).powf(self.shape)).exp_m1() }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        if x <= 0. { 1. } else { (-(x / self.scale).powf(self.shape)).exp() }
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        self.scale * (-(-p).ln_1p()).powf(1. / self.shape)
+    }
+
+    fn mean(&self) -> f64 {
+        self.scale * gamma(1. + 1. / self.shape)
+    }
+
+    fn variance(&self) -> f64 {
+        let g1 = gamma(1. + 1. / self.shape);
+        self.scale * self.scale * (gamma(1. + 2. / self.shape) - g1 * g1)
+    }
+
+    fn entropy(&self) -> f64 {
+        EULER_GAMMA * (1. - 1. / self.shape) + (self.scale / self.shape).ln() + 1.
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        self.scale * standard_exponential(rng).powf(1. / self.shape)
+    }
+}
+
+
+/// Cauchy distribution with given location and scale. Its mean
+/// and variance do not exist and are reported as NaN.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cauchy {
+    location : f64,
+    scale : f64
+}
+
+
+impl Cauchy {
+
+    /// Creates a Cauchy distribution with positive scale
+    pub fn new(location : f64, scale : f64) -> SRResult<Cauchy> {
+        check(location.is_finite() && scale > 0. && scale.is_finite())?;
+        Ok(Cauchy { location : location, scale : scale })
+    }
+}
+
+
+impl Distribution for Cauchy {
+
+    fn pdf(&self, x : f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        1. / (PI * self.scale * (1. + z * z))
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        0.5 + ((x - self.location) / self.scale).atan() / PI
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        0.5 - ((x - self.location) / self.scale).atan() / PI
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        if p == 0. {
+            return f64::NEG_INFINITY;
+        }
+        if p == 1. {
+            return f64::INFINITY;
+        }
+        self.location + self.scale * (PI * (p - 0.5)).tan()
+    }
+
+    fn mean(&self) -> f64 {
+        f64::NAN
+    }
+
+    fn variance(&self) -> f64 {
+        f64::NAN
+    }
+
+    fn entropy(&self) -> f64 {
+        (4. * PI * self.scale).ln()
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        self.quantile(open_uniform(rng))
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+    use distributions::support::assert_sample_moments;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_uniform(){
+        let d = Uniform::new(-1., 3.).unwrap();
+        assert_eq!((d.pdf(0.), d.pdf(3.5), d.cdf(0.), d.cdf(5.), d.sf(-2.)), (0.25, 0., 0.25, 1., 1.));
+        assert_eq!((d.quantile(0.75), d.mean(), d.variance()), (2., 1., 4. / 3.));
+        assert_eq!(d.entropy(), 4f64.ln());
+        assert!(d.quantile(-0.1).is_nan());
+        assert_sample_moments(&d, 3);
+        assert!(Uniform::new(1., 1.).is_err());
+    }
+
+    #[test]
+    fn test_exponential(){
+        let d = Exponential::new(2.).unwrap();
+        assert_near(d.pdf(0.5), 2. / 1f64.exp(), 1e-15);
+        assert_near(d.cdf(1e-10), 2e-10, 1e-9);
+        assert_near(d.sf(20.), (-40f64).exp(), 1e-15);
+        assert_near(d.quantile(d.cdf(0.7)), 0.7, 1e-15);
+        assert_near(d.quantile(1e-12), 5e-13, 1e-12);
+        assert_eq!((d.mean(), d.variance(), d.quantile(1.)), (0.5, 0.25, f64::INFINITY));
+        assert_near(d.entropy(), 1. - 2f64.ln(), 1e-15);
+        assert_eq!(d.ln_pdf(1.), 2f64.ln() - 2.);
+        assert_sample_moments(&d, 4);
+        assert!(Exponential::new(-1.).is_err());
+    }
+
+    #[test]
+    fn test_weibull(){
+        let d = Weibull::new(1.5, 2.).unwrap();
+        assert_near(d.pdf(1.), 0.372_391_688_219_422, 1e-15);
+        assert_near(d.cdf(1.), 0.297_811_498_673_440_4, 1e-15);
+        assert_near(d.quantile(0.297_811_498_673_440_4), 1., 1e-15);
+        assert_near(d.mean(), 1.805_490_585_901_867_3, 1e-14);
+        assert_near(d.variance(), 1.502_761_139_255_728, 1e-14);
+        assert_near(d.entropy(), 1.480_087_294_085_625_1, 1e-15);
+        assert_sample_moments(&d, 5);
+        // Shape 1 is the exponential distribution
+        let e = Weibull::new(1., 0.5).unwrap();
+        assert_near(e.cdf(0.7), Exponential::new(2.).unwrap().cdf(0.7), 1e-15);
+    }
+
+    #[test]
+    fn test_cauchy(){
+        let d = Cauchy::new(1., 0.5).unwrap();
+        assert_near(d.pdf(2.), 0.127_323_954_473_516_27, 1e-15);
+        assert_near(d.cdf(2.), 0.852_416_382_349_566_7, 1e-15);
+        assert_near(d.quantile(0.852_416_382_349_566_7), 2., 1e-14);
+        assert_near(d.entropy(), 1.837_877_066_409_345_6, 1e-15);
+        assert_eq!((d.quantile(0.5), d.quantile(0.)), (1., f64::NEG_INFINITY));
+        assert!(d.mean().is_nan() && d.variance().is_nan());
+        // The sample median estimates the location
+        let mut rng : StdRng = SeedableRng::from_seed(&[6][..]);
+        let mut x : Vec<f64> = (0..10001).map(|_| d.sample(&mut rng)).collect();
+        x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((x[5000] - 1.).abs() < 0.05);
+    }
+}
@@ -0,0 +1,292 @@
+#![doc="Gamma, chi-squared and beta distributions
+
+Their distribution functions are the regularized incomplete gamma
+and beta functions, and their quantiles the inverses of these.
+Gamma variates are generated by the method of Marsaglia and Tsang,
+and a beta variate is X / (X + Y) for independent gamma variates X
+and Y.
+"]
+
+// std imports
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::SRResult;
+use srnum::special::gamma::{ln_gamma, digamma, gamma_p, gamma_q, gamma_p_inv, gamma_q_inv};
+use srnum::special::beta::{ln_beta, beta_inc, beta_inc_inv};
+use distributions::traits::Distribution;
+use distributions::support::{check, is_probability, standard_gamma};
+
+
+/// Gamma distribution with shape k and scale theta, of density
+/// x^(k - 1) exp(-x / theta) / (Gamma(k) theta^k) on x >= 0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gamma {
+    shape : f64,
+    scale : f64
+}
+
+
+impl Gamma {
+
+    /// Creates a gamma distribution with positive shape and scale
+    pub fn new(shape : f64, scale : f64) -> SRResult<Gamma> {
+        check(shape > 0. && shape.is_finite() && scale > 0. && scale.is_finite())?;
+        Ok(Gamma { shape : shape, scale : scale })
+    }
+}
+
+
+impl Distribution for Gamma {
+
+    fn pdf(&self, x : f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        if x < 0. {
+            return f64::NEG_INFINITY;
+        }
+        let y = x / self.scale;
+        if y == 0. {
+            return if self.shape < 1. { f64::INFINITY } else if self.shape == 1. { -self.scale.ln() } else { f64::NEG_INFINITY };
+        }
+        (self.shape - 1.) * y.ln() - y - ln_gamma(self.shape) - self.scale.ln()
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        if x <= 0. { 0. } else { gamma_p(self.shape, x / self.scale) }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        if x <= 0. { 1. } else { gamma_q(self.shape, x / self.scale) }
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        // Invert the smaller tail to keep the relative accuracy
+        let y = if p <= 0.5 { gamma_p_inv(self.shape, p) } else { gamma_q_inv(self.shape, 1. - p) };
+        self.scale * y
+    }
+
+    fn mean(&self) -> f64 {
+        self.shape * self.scale
+    }
+
+    fn variance(&self) -> f64 {
+        self.shape * self.scale * self.scale
+    }
+
+    fn entropy(&self) -> f64 {
+        let k = self.shape;
+        k + self.scale.ln() + ln_gamma(k) + (1. - k) * digamma(k)
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        self.scale * standard_gamma(rng, self.shape)
+    }
+}
+
+
+/// Chi-squared distribution with k degrees of freedom: the gamma
+/// distribution of shape k / 2 and scale 2
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    dof : f64,
+    gamma : Gamma
+}
+
+
+impl ChiSquared {
+
+    /// Creates a chi-squared distribution with positive (not
+    /// necessarily integer) degrees of freedom
+    pub fn new(dof : f64) -> SRResult<ChiSquared> {
+        Ok(ChiSquared { dof : dof, gamma : Gamma::new(0.5 * dof, 2.)? })
+    }
+
+    /// Returns the degrees of freedom
+    pub fn dof(&self) -> f64 {
+        self.dof
+    }
+}
+
+
+impl Distribution for ChiSquared {
+
+    fn pdf(&self, x : f64) -> f64 {
+        self.gamma.pdf(x)
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        self.gamma.ln_pdf(x)
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        self.gamma.cdf(x)
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        self.gamma.sf(x)
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        self.gamma.quantile(p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.dof
+    }
+
+    fn variance(&self) -> f64 {
+        2. * self.dof
+    }
+
+    fn entropy(&self) -> f64 {
+        self.gamma.entropy()
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        self.gamma.sample(rng)
+    }
+}
+
+
+/// Beta distribution with shape parameters a and b, of density
+/// x^(a - 1) (1 - x)^(b - 1) / B(a, b) on [0, 1]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Beta {
+    a : f64,
+    b : f64
+}
+
+
+impl Beta {
+
+    /// Creates a beta distribution with positive shape parameters
+    pub fn new(a : f64, b : f64) -> SRResult<Beta> {
+        check(a > 0. && a.is_finite() && b > 0. && b.is_finite())?;
+        Ok(Beta { a : a, b : b })
+    }
+}
+
+
+impl Distribution for Beta {
+
+    fn pdf(&self, x : f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        if !(0. ..=1.).contains(&x) {
+            return f64::NEG_INFINITY;
+        }
+        let left = if self.a == 1. { 0. } else { (self.a - 1.) * x.ln() };
+        let right = if self.b == 1. { 0. } else { (self.b - 1.) * (-x).ln_1p() };
+        left + right - ln_beta(self.a, self.b)
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        if x <= 0. { 0. } else if x >= 1. { 1. } else { beta_inc(self.a, self.b, x) }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        if x <= 0. { 1. } else if x >= 1. { 0. } else { beta_inc(self.b, self.a, 1. - x) }
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        beta_inc_inv(self.a, self.b, p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.a / (self.a + self.b)
+    }
+
+    fn variance(&self) -> f64 {
+        let s = self.a + self.b;
+        self.a * self.b / (s * s * (s + 1.))
+    }
+
+    fn entropy(&self) -> f64 {
+        let (a, b) = (self.a, self.b);
+        ln_beta(a, b) - (a - 1.) * digamma(a) - (b - 1.) * digamma(b) + (a + b - 2.) * digamma(a + b)
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        let x = standard_gamma(rng, self.a);
+        let y = standard_gamma(rng, self.b);
+        x / (x + y)
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use distributions::support::assert_sample_moments;
+    use distributions::elementary::Exponential;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_gamma(){
+        let d = Gamma::new(2.5, 1.5).unwrap();
+        assert_near(d.pdf(3.), 0.191_967_880_935_779_74, 1e-14);
+        assert_near(d.cdf(3.), 0.450_584_048_647_219_8, 1e-14);
+        assert_near(d.sf(3.), 1. - 0.450_584_048_647_219_8, 1e-14);
+        assert_near(d.quantile(0.9), 6.927_267_674_835_839, 1e-13);
+        assert_near(d.quantile(d.cdf(0.1)), 0.1, 1e-12);
+        assert_near(d.entropy(), 2.135_413_017_613_218_7, 1e-14);
+        assert_eq!((d.mean(), d.variance()), (3.75, 5.625));
+        assert_eq!((d.pdf(-1.), d.cdf(0.), d.quantile(0.), d.quantile(1.)), (0., 0., 0., f64::INFINITY));
+        assert_sample_moments(&d, 7);
+        // Shape below one
+        assert_sample_moments(&Gamma::new(0.3, 2.).unwrap(), 8);
+        assert_eq!(Gamma::new(0.5, 1.).unwrap().pdf(0.), f64::INFINITY);
+        // Shape one is the exponential distribution
+        let g = Gamma::new(1., 0.5).unwrap();
+        let e = Exponential::new(2.).unwrap();
+        assert_eq!(g.pdf(0.), 2.);
+        assert_near(g.pdf(0.8), e.pdf(0.8), 1e-14);
+        assert!(Gamma::new(0., 1.).is_err());
+    }
+
+    #[test]
+    fn test_chi_squared(){
+        let d = ChiSquared::new(3.).unwrap();
+        assert_near(d.cdf(2.), 0.427_593_295_529_120_2, 1e-14);
+        assert_near(d.quantile(0.95), 7.814_727_903_251_18, 1e-13);
+        assert_eq!((d.dof(), d.mean(), d.variance()), (3., 3., 6.));
+        assert_sample_moments(&d, 9);
+        assert!(ChiSquared::new(-2.).is_err());
+    }
+
+    #[test]
+    fn test_beta(){
+        let d = Beta::new(2., 5.).unwrap();
+        assert_near(d.pdf(0.3), 2.1609, 1e-14);
+        assert_near(d.cdf(0.3), 0.579_825, 1e-14);
+        assert_near(d.sf(0.3), 0.420_175, 1e-14);
+        assert_near(d.quantile(0.5), 0.264_449_983_295_659_94, 1e-14);
+        assert_near(d.entropy(), -0.484_530_714_995_488_7, 1e-14);
+        assert_near(d.mean(), 2. / 7., 1e-15);
+        assert_near(d.variance(), 10. / 392., 1e-15);
+        assert_eq!((d.pdf(1.5), d.cdf(1.), d.quantile(1.)), (0., 1., 1.));
+        assert_sample_moments(&d, 10);
+        // Beta(1, 1) is uniform on [0, 1]
+        let u = Beta::new(1., 1.).unwrap();
+        for &x in [0., 0.3, 1.].iter() {
+            assert_near(u.pdf(x), 1., 1e-14);
+        }
+        assert!(Beta::new(1., f64::INFINITY).is_err());
+    }
+}
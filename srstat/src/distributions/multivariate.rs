@@ -0,0 +1,226 @@
+#![doc="Multivariate normal distribution
+
+N(mu, Sigma) in p dimensions has density
+
+f(x) = exp(-(x - mu)^T Sigma^-1 (x - mu) / 2) / sqrt((2 pi)^p det(Sigma))
+
+With the Cholesky factorization Sigma = L L^T the quadratic form is
+|y|^2 for the solution y of L y = x - mu, ln det(Sigma) is twice the
+sum of ln L_ii, and mu + L z is a draw from N(mu, Sigma) when z is
+a vector of independent standard normal numbers.
+"]
+
+// std imports
+use std::f64::consts::PI;
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::*;
+use srlinalg::cholesky::CholeskyDecomposition;
+use srlinalg::factorization::Factorization;
+use distributions::support::standard_normal;
+
+
+/// Multivariate normal distribution with given mean vector and
+/// covariance matrix
+#[derive(Debug, Clone)]
+pub struct MultivariateNormal {
+    mean : MatrixF64,
+    cov : MatrixF64,
+    /// Lower triangular Cholesky factor of the covariance
+    factor : MatrixF64,
+    /// ln det(Sigma)
+    ln_det : f64
+}
+
+
+impl MultivariateNormal {
+
+    /// Creates a multivariate normal distribution from a p x 1 mean
+    /// and a symmetric positive definite p x p covariance matrix
+    pub fn new(mean : &MatrixF64, cov : &MatrixF64) -> SRResult<MultivariateNormal> {
+        if !mean.is_col() {
+            return Err(SRError::IsNotAColVector);
+        }
+        if !cov.is_square() {
+            return Err(SRError::IsNotSquareMatrix);
+        }
+        let p = mean.num_rows();
+        if cov.num_rows() != p {
+            return Err(SRError::DimensionsMismatch);
+        }
+        for i in 0..p {
+            for j in 0..i {
+                let (a, b) = (cov.get(i, j).unwrap(), cov.get(j, i).unwrap());
+                if (a - b).abs() > 1e-12 * (a.abs() + b.abs()) {
+                    return Err(SRError::IsNotSymmetric);
+                }
+            }
+        }
+        let chol = CholeskyDecomposition::new(cov)?;
+        let (_, ln_det) = chol.log_det()?;
+        let factor = chol.l();
+        Ok(MultivariateNormal {
+            mean : mean.clone(),
+            cov : cov.clone(),
+            factor : factor,
+            ln_det : ln_det
+        })
+    }
+
+    /// Returns the dimension p
+    pub fn dim(&self) -> usize {
+        self.mean.num_rows()
+    }
+
+    /// Returns the mean vector
+    pub fn mean(&self) -> &MatrixF64 {
+        &self.mean
+    }
+
+    /// Returns the covariance matrix
+    pub fn cov(&self) -> &MatrixF64 {
+        &self.cov
+    }
+
+    /// Returns the lower triangular Cholesky factor L of the
+    /// covariance matrix
+    pub fn cholesky_factor(&self) -> &MatrixF64 {
+        &self.factor
+    }
+
+    /// Returns ln det(Sigma)
+    pub fn ln_det_cov(&self) -> f64 {
+        self.ln_det
+    }
+
+    /// Returns the squared Mahalanobis distance
+    /// (x - mu)^T Sigma^-1 (x - mu)
+    pub fn mahalanobis_sqr(&self, x : &[f64]) -> SRResult<f64> {
+        let p = self.dim();
+        if x.len() != p {
+            return Err(SRError::DimensionsMismatch);
+        }
+        // Forward substitution of L y = x - mu
+        let mut y = vec![0.; p];
+        let mut sum = 0.;
+        for i in 0..p {
+            let mut v = x[i] - self.mean[i];
+            for (k, &yk) in y.iter().enumerate().take(i) {
+                v -= self.factor.get(i, k).unwrap() * yk;
+            }
+            y[i] = v / self.factor.get(i, i).unwrap();
+            sum += y[i] * y[i];
+        }
+        Ok(sum)
+    }
+
+    /// Returns the logarithm of the density at x
+    pub fn ln_pdf(&self, x : &[f64]) -> SRResult<f64> {
+        let q = self.mahalanobis_sqr(x)?;
+        Ok(-0.5 * (self.dim() as f64 * (2. * PI).ln() + self.ln_det + q))
+    }
+
+    /// Returns the density at x
+    pub fn pdf(&self, x : &[f64]) -> SRResult<f64> {
+        Ok(self.ln_pdf(x)?.exp())
+    }
+
+    /// Returns the differential entropy in nats
+    pub fn entropy(&self) -> f64 {
+        0.5 * (self.dim() as f64 * (1. + (2. * PI).ln()) + self.ln_det)
+    }
+
+    /// Draws a random p x 1 vector from the distribution
+    pub fn sample<R:Rng>(&self, rng : &mut R) -> MatrixF64 {
+        let p = self.dim();
+        let z : Vec<f64> = (0..p).map(|_| standard_normal(rng)).collect();
+        let mut x = self.mean.clone();
+        for i in 0..p {
+            let mut v = x[i];
+            for (k, &zk) in z.iter().enumerate().take(i + 1) {
+                v += self.factor.get(i, k).unwrap() * zk;
+            }
+            x.set(i, 0, v);
+        }
+        x
+    }
+
+    /// Returns an n x p matrix whose rows are independent draws
+    /// from the distribution
+    pub fn sample_matrix<R:Rng>(&self, rng : &mut R, n : usize) -> MatrixF64 {
+        let p = self.dim();
+        let mut m : MatrixF64 = Matrix::zeros(n, p);
+        for r in 0..n {
+            let x = self.sample(rng);
+            for c in 0..p {
+                m.set(r, c, x[c]);
+            }
+        }
+        m
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+    use moments::traits::Moments;
+
+    fn example() -> MultivariateNormal {
+        let mean = matrix_cw_f64(2, 1, &[1., -2.]);
+        let cov = matrix_rw_f64(2, 2, &[4., 1.2, 1.2, 1.]);
+        MultivariateNormal::new(&mean, &cov).unwrap()
+    }
+
+    #[test]
+    fn test_density(){
+        let d = example();
+        assert_eq!(d.dim(), 2);
+        assert_eq!(d.cholesky_factor(), &matrix_rw_f64(2, 2, &[2., 0., 0.6, 0.8]));
+        // Bivariate density with sigma_1 = 2, sigma_2 = 1, rho = 0.6
+        let (x, y) = (2., -1.5);
+        let (u, v, rho) : (f64, f64, f64) = ((x - 1.) / 2., y + 2., 0.6);
+        let q = (u * u - 2. * rho * u * v + v * v) / (1. - rho * rho);
+        let expected = (-0.5 * q).exp() / (2. * PI * 2. * 0.8);
+        assert!((d.pdf(&[x, y]).unwrap() - expected).abs() < 1e-15);
+        assert!((d.mahalanobis_sqr(&[x, y]).unwrap() - q).abs() < 1e-15);
+        assert!((d.ln_det_cov() - 2.56f64.ln()).abs() < 1e-15);
+        assert!((d.entropy() - (1. + (2. * PI).ln() + 0.5 * 2.56f64.ln())).abs() < 1e-15);
+        assert!(d.pdf(&[1.]).is_err());
+    }
+
+    #[test]
+    fn test_sample(){
+        let d = example();
+        let mut rng : StdRng = SeedableRng::from_seed(&[19][..]);
+        let x = d.sample_matrix(&mut rng, 20000);
+        assert_eq!(x.size(), (20000, 2));
+        let mean = x.mean_cw();
+        assert!((mean.get(0, 0).unwrap() - 1.).abs() < 0.05);
+        assert!((mean.get(0, 1).unwrap() + 2.).abs() < 0.025);
+        let cov = x.cov();
+        for &(i, j) in [(0, 0), (0, 1), (1, 1)].iter() {
+            let expected = d.cov().get(i, j).unwrap();
+            assert!((cov.get(i, j).unwrap() - expected).abs() < 0.1 * expected, "{} {}", i, j);
+        }
+    }
+
+    #[test]
+    fn test_invalid(){
+        let mean = matrix_cw_f64(2, 1, &[0., 0.]);
+        assert!(MultivariateNormal::new(&mean, &matrix_rw_f64(2, 2, &[1., 2., 2., 1.])).is_err());
+        assert!(MultivariateNormal::new(&mean, &matrix_rw_f64(2, 2, &[1., 0.5, 0., 1.])).is_err());
+        assert!(MultivariateNormal::new(&mean, &Matrix::identity(3, 3)).is_err());
+        assert!(MultivariateNormal::new(&mean.transpose(), &Matrix::identity(2, 2)).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+#![doc="Normal and log-normal distributions
+
+The normal distribution N(mu, sigma^2) has density
+
+f(x) = exp(-(x - mu)^2 / (2 sigma^2)) / (sigma sqrt(2 pi))
+
+and X is log-normal with parameters mu and sigma when ln X is
+N(mu, sigma^2). Tail probabilities are computed from erfc, so the
+survival function stays accurate far beyond the point where
+1 - cdf(x) would round to zero.
+"]
+
+// std imports
+use std::f64::consts::{PI, SQRT_2};
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::SRResult;
+use srnum::special::erf::{erfc, erfc_inv};
+use distributions::traits::Distribution;
+use distributions::support::{check, is_probability, standard_normal};
+
+
+/// Returns ln(sqrt(2 pi))
+fn ln_sqrt_2pi() -> f64 {
+    0.5 * (2. * PI).ln()
+}
+
+
+/// Normal distribution with given mean and standard deviation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    mean : f64,
+    std_dev : f64
+}
+
+
+impl Normal {
+
+    /// Creates a normal distribution. The standard deviation must
+    /// be positive.
+    pub fn new(mean : f64, std_dev : f64) -> SRResult<Normal> {
+        check(mean.is_finite() && std_dev > 0. && std_dev.is_finite())?;
+        Ok(Normal { mean : mean, std_dev : std_dev })
+    }
+
+    /// Creates the standard normal distribution N(0, 1)
+    pub fn standard() -> Normal {
+        Normal { mean : 0., std_dev : 1. }
+    }
+
+    /// Returns the standardized value (x - mu) / sigma
+    fn z(&self, x : f64) -> f64 {
+        (x - self.mean) / self.std_dev
+    }
+}
+
+
+impl Distribution for Normal {
+
+    fn pdf(&self, x : f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        let z = self.z(x);
+        -0.5 * z * z - self.std_dev.ln() - ln_sqrt_2pi()
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        0.5 * erfc(-self.z(x) / SQRT_2)
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        0.5 * erfc(self.z(x) / SQRT_2)
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        self.mean - self.std_dev * SQRT_2 * erfc_inv(2. * p)
+    }
+
+    fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    fn variance(&self) -> f64 {
+        self.std_dev * self.std_dev
+    }
+
+    fn std_dev(&self) -> f64 {
+        self.std_dev
+    }
+
+    fn entropy(&self) -> f64 {
+        0.5 + ln_sqrt_2pi() + self.std_dev.ln()
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        self.mean + self.std_dev * standard_normal(rng)
+    }
+}
+
+
+/// Log-normal distribution: the distribution of exp(Y) for Y normal
+/// with mean mu and standard deviation sigma
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogNormal {
+    normal : Normal
+}
+
+
+impl LogNormal {
+
+    /// Creates a log-normal distribution from the parameters of the
+    /// underlying normal distribution
+    pub fn new(mu : f64, sigma : f64) -> SRResult<LogNormal> {
+        Ok(LogNormal { normal : Normal::new(mu, sigma)? })
+    }
+}
+
+
+impl Distribution for LogNormal {
+
+    fn pdf(&self, x : f64) -> f64 {
+        if x <= 0. { 0. } else { self.ln_pdf(x).exp() }
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        if x <= 0. {
+            return f64::NEG_INFINITY;
+        }
+        let y = x.ln();
+        self.normal.ln_pdf(y) - y
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        if x <= 0. { 0. } else { self.normal.cdf(x.ln()) }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        if x <= 0. { 1. } else { self.normal.sf(x.ln()) }
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        self.normal.quantile(p).exp()
+    }
+
+    fn mean(&self) -> f64 {
+        (self.normal.mean + 0.5 * self.normal.variance()).exp()
+    }
+
+    fn variance(&self) -> f64 {
+        let s2 = self.normal.variance();
+        s2.exp_m1() * (2. * self.normal.mean + s2).exp()
+    }
+
+    fn entropy(&self) -> f64 {
+        self.normal.entropy() + self.normal.mean
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        self.normal.sample(rng).exp()
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use distributions::support::assert_sample_moments;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_normal(){
+        let d = Normal::new(1., 2.).unwrap();
+        assert_near(d.pdf(2.), 0.176_032_663_382_149_73, 1e-15);
+        assert_near(d.cdf(2.), 0.691_462_461_274_013_1, 1e-15);
+        assert_near(d.cdf(-5.), 0.001_349_898_031_630_094_6, 1e-14);
+        assert_near(d.sf(15.), 1.279_812_543_885_835e-12, 1e-14);
+        assert_near(d.quantile(0.975), 4.919_927_969_080_108, 1e-15);
+        assert_near(d.quantile(d.cdf(-3.)), -3., 1e-14);
+        assert_eq!(d.quantile(0.5), 1.);
+        assert_eq!(d.quantile(1.), f64::INFINITY);
+        assert!(d.quantile(1.5).is_nan());
+        assert_near(d.entropy(), 2.112_085_713_764_618, 1e-15);
+        assert_eq!((d.mean(), d.variance(), d.std_dev()), (1., 4., 2.));
+        assert_sample_moments(&d, 1);
+        assert!(Normal::new(0., 0.).is_err());
+        assert!(Normal::new(f64::NAN, 1.).is_err());
+        assert_eq!(Normal::standard().cdf(0.), 0.5);
+    }
+
+    #[test]
+    fn test_log_normal(){
+        let d = LogNormal::new(0.5, 0.8).unwrap();
+        assert_near(d.pdf(2.), 0.242_176_774_884_833_4, 1e-15);
+        assert_near(d.cdf(2.), 0.595_390_608_679_215, 1e-15);
+        assert_near(d.quantile(0.3), 1.083_806_725_740_852_8, 1e-15);
+        assert_near(d.entropy(), 1.695_794_981_890_463, 1e-15);
+        assert_near(d.mean(), 2.270_499_837_532_405_7, 1e-15);
+        assert_near(d.variance(), 4.621_510_897_294_224, 1e-15);
+        assert_eq!((d.pdf(-1.), d.cdf(0.), d.sf(0.)), (0., 0., 1.));
+        assert_eq!(d.quantile(0.), 0.);
+        assert_sample_moments(&d, 2);
+    }
+}
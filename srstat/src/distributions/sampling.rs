@@ -0,0 +1,274 @@
+#![doc="Sampling distributions: Student's t and Fisher's F
+
+With I the regularized incomplete beta function
+
+P(|T| > t) = I_{nu / (nu + t^2)}(nu / 2, 1 / 2)
+P(F <= x) = I_{d1 x / (d1 x + d2)}(d1 / 2, d2 / 2)
+
+Tail probabilities and quantiles are computed from whichever of
+I_x(a, b) and I_{1 - x}(b, a) keeps the relative accuracy.
+"]
+
+// std imports
+use std::f64::consts::PI;
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::SRResult;
+use srnum::special::gamma::{ln_gamma, digamma};
+use srnum::special::beta::{ln_beta, beta_inc, beta_inc_inv};
+use distributions::traits::Distribution;
+use distributions::support::{check, is_probability, standard_normal, standard_gamma};
+
+
+/// Student's t distribution with nu degrees of freedom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StudentT {
+    dof : f64
+}
+
+
+impl StudentT {
+
+    /// Creates a t distribution with positive (not necessarily
+    /// integer) degrees of freedom
+    pub fn new(dof : f64) -> SRResult<StudentT> {
+        check(dof > 0. && dof.is_finite())?;
+        Ok(StudentT { dof : dof })
+    }
+
+    /// Returns the degrees of freedom
+    pub fn dof(&self) -> f64 {
+        self.dof
+    }
+
+    /// Returns P(T > |t|)
+    fn tail(&self, t : f64) -> f64 {
+        let nu = self.dof;
+        let t2 = t * t;
+        if t2 < nu {
+            0.5 - 0.5 * beta_inc(0.5, 0.5 * nu, t2 / (nu + t2))
+        }
+        else {
+            0.5 * beta_inc(0.5 * nu, 0.5, nu / (nu + t2))
+        }
+    }
+}
+
+
+impl Distribution for StudentT {
+
+    fn pdf(&self, x : f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        let nu = self.dof;
+        ln_gamma(0.5 * (nu + 1.)) - ln_gamma(0.5 * nu) - 0.5 * (nu * PI).ln()
+            - 0.5 * (nu + 1.) * (x * x / nu).ln_1p()
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        if x < 0. { self.tail(x) } else { 1. - self.tail(x) }
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        self.cdf(-x)
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        if p == 0.5 {
+            return 0.;
+        }
+        let nu = self.dof;
+        // Two sided tail probability
+        let q = 2. * p.min(1. - p);
+        let t = if q < 0.5 {
+            let y = beta_inc_inv(0.5 * nu, 0.5, q);
+            (nu * (1. - y) / y).sqrt()
+        }
+        else {
+            let y = beta_inc_inv(0.5, 0.5 * nu, 1. - q);
+            (nu * y / (1. - y)).sqrt()
+        };
+        if p < 0.5 { -t } else { t }
+    }
+
+    fn mean(&self) -> f64 {
+        if self.dof > 1. { 0. } else { f64::NAN }
+    }
+
+    fn variance(&self) -> f64 {
+        let nu = self.dof;
+        if nu > 2. { nu / (nu - 2.) } else if nu > 1. { f64::INFINITY } else { f64::NAN }
+    }
+
+    fn entropy(&self) -> f64 {
+        let nu = self.dof;
+        0.5 * (nu + 1.) * (digamma(0.5 * (nu + 1.)) - digamma(0.5 * nu))
+            + 0.5 * nu.ln() + ln_beta(0.5 * nu, 0.5)
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        let chi2 = 2. * standard_gamma(rng, 0.5 * self.dof);
+        standard_normal(rng) / (chi2 / self.dof).sqrt()
+    }
+}
+
+
+/// Fisher's F distribution with d1 and d2 degrees of freedom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FisherF {
+    d1 : f64,
+    d2 : f64
+}
+
+
+impl FisherF {
+
+    /// Creates an F distribution with positive degrees of freedom
+    pub fn new(d1 : f64, d2 : f64) -> SRResult<FisherF> {
+        check(d1 > 0. && d1.is_finite() && d2 > 0. && d2.is_finite())?;
+        Ok(FisherF { d1 : d1, d2 : d2 })
+    }
+
+    /// Returns the degrees of freedom of the numerator and the
+    /// denominator
+    pub fn dof(&self) -> (f64, f64) {
+        (self.d1, self.d2)
+    }
+}
+
+
+impl Distribution for FisherF {
+
+    fn pdf(&self, x : f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x : f64) -> f64 {
+        let (d1, d2) = (self.d1, self.d2);
+        if x < 0. {
+            return f64::NEG_INFINITY;
+        }
+        if x == 0. {
+            return if d1 < 2. { f64::INFINITY } else if d1 == 2. { 0. } else { f64::NEG_INFINITY };
+        }
+        let y = d1 * x;
+        0.5 * (d1 * y.ln() + d2 * d2.ln() - (d1 + d2) * (y + d2).ln()) - x.ln() - ln_beta(0.5 * d1, 0.5 * d2)
+    }
+
+    fn cdf(&self, x : f64) -> f64 {
+        if x <= 0. {
+            return 0.;
+        }
+        let y = self.d1 * x;
+        beta_inc(0.5 * self.d1, 0.5 * self.d2, y / (y + self.d2))
+    }
+
+    fn sf(&self, x : f64) -> f64 {
+        if x <= 0. {
+            return 1.;
+        }
+        beta_inc(0.5 * self.d2, 0.5 * self.d1, self.d2 / (self.d1 * x + self.d2))
+    }
+
+    fn quantile(&self, p : f64) -> f64 {
+        if !is_probability(p) {
+            return f64::NAN;
+        }
+        let (d1, d2) = (self.d1, self.d2);
+        if p <= 0.5 {
+            let y = beta_inc_inv(0.5 * d1, 0.5 * d2, p);
+            d2 * y / (d1 * (1. - y))
+        }
+        else {
+            let z = beta_inc_inv(0.5 * d2, 0.5 * d1, 1. - p);
+            d2 * (1. - z) / (d1 * z)
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        let d2 = self.d2;
+        if d2 > 2. { d2 / (d2 - 2.) } else { f64::NAN }
+    }
+
+    fn variance(&self) -> f64 {
+        let (d1, d2) = (self.d1, self.d2);
+        if d2 > 4. {
+            2. * d2 * d2 * (d1 + d2 - 2.) / (d1 * (d2 - 2.) * (d2 - 2.) * (d2 - 4.))
+        }
+        else if d2 > 2. { f64::INFINITY } else { f64::NAN }
+    }
+
+    fn entropy(&self) -> f64 {
+        let (a, b) = (0.5 * self.d1, 0.5 * self.d2);
+        (self.d2 / self.d1).ln() + ln_beta(a, b) + (1. - a) * digamma(a)
+            - (1. + b) * digamma(b) + (a + b) * digamma(a + b)
+    }
+
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64 {
+        let x = standard_gamma(rng, 0.5 * self.d1) / self.d1;
+        let y = standard_gamma(rng, 0.5 * self.d2) / self.d2;
+        x / y
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use distributions::support::assert_sample_moments;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_student_t(){
+        let d = StudentT::new(5.).unwrap();
+        assert_near(d.pdf(0.), 0.379_606_689_822_494_46, 1e-14);
+        assert_near(d.cdf(2.), 0.949_030_260_585_070_8, 1e-14);
+        assert_near(d.sf(-2.), 0.949_030_260_585_070_8, 1e-14);
+        assert_near(d.cdf(-2.), 1. - 0.949_030_260_585_070_8, 1e-13);
+        assert_near(d.sf(30.), 3.859_324_310_248_026e-7, 1e-12);
+        assert_near(d.quantile(0.975), 2.570_581_835_636_315_5, 1e-13);
+        assert_near(d.quantile(0.025), -2.570_581_835_636_315_5, 1e-13);
+        assert_near(d.quantile(d.cdf(0.1)), 0.1, 1e-12);
+        assert_eq!((d.cdf(0.), d.quantile(0.5), d.quantile(1.)), (0.5, 0., f64::INFINITY));
+        assert_near(d.entropy(), 1.627_502_672_414_396, 1e-14);
+        assert_eq!((d.mean(), d.dof()), (0., 5.));
+        assert_near(d.variance(), 5. / 3., 1e-15);
+        assert_sample_moments(&StudentT::new(10.).unwrap(), 11);
+        let cauchy = StudentT::new(1.).unwrap();
+        assert!(cauchy.mean().is_nan() && cauchy.variance().is_nan());
+        assert_eq!(StudentT::new(1.5).unwrap().variance(), f64::INFINITY);
+        assert!(StudentT::new(0.).is_err());
+    }
+
+    #[test]
+    fn test_fisher_f(){
+        let d = FisherF::new(3., 10.).unwrap();
+        assert_near(d.pdf(1.), 0.404_122_811_488_562_66, 1e-14);
+        assert_near(d.cdf(2.), 0.821_992_592_624_824_6, 1e-14);
+        assert_near(d.sf(2.), 1. - 0.821_992_592_624_824_6, 1e-13);
+        assert_near(d.quantile(0.95), 3.708_264_819_046_844_4, 1e-13);
+        assert_near(d.quantile(d.cdf(0.2)), 0.2, 1e-12);
+        assert_near(d.mean(), 1.25, 1e-15);
+        assert_near(d.variance(), 1.909_722_222_222_222_3, 1e-15);
+        assert_near(d.entropy(), 1.197_654_987_200_967_9, 1e-14);
+        assert_eq!((d.pdf(0.), d.cdf(0.), d.quantile(0.)), (0., 0., 0.));
+        assert_near(FisherF::new(2., 7.).unwrap().pdf(0.), 1., 1e-15);
+        assert_sample_moments(&FisherF::new(5., 20.).unwrap(), 12);
+        assert!(FisherF::new(3., 2.).unwrap().mean().is_nan());
+        assert!(FisherF::new(3., -2.).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+#![doc="Helpers shared by the distributions: parameter checks,
+basic random number generators and the quantile search of discrete
+distributions
+"]
+
+// std imports
+
+// external imports
+use rand::Rng;
+use rand::distributions::normal::StandardNormal;
+
+// local imports
+use srmatrix::api::{SRError, SRResult};
+use srnum::special::gamma::ln_gamma;
+#[cfg(test)]
+use distributions::traits::Distribution;
+
+
+/// Returns InvalidArgument unless the condition on the parameters
+/// holds
+pub fn check(valid : bool) -> SRResult<()> {
+    if valid { Ok(()) } else { Err(SRError::InvalidArgument) }
+}
+
+
+/// Returns true if p is a probability
+pub fn is_probability(p : f64) -> bool {
+    (0. ..=1.).contains(&p)
+}
+
+
+/// Draws a uniform random number in the open interval (0, 1)
+pub fn open_uniform<R:Rng>(rng : &mut R) -> f64 {
+    loop {
+        let u = rng.gen::<f64>();
+        if u > 0. {
+            return u;
+        }
+    }
+}
+
+
+/// Draws a standard normal random number
+pub fn standard_normal<R:Rng>(rng : &mut R) -> f64 {
+    let StandardNormal(z) = rng.gen::<StandardNormal>();
+    z
+}
+
+
+/// Draws a random number of rate 1 exponential distribution
+pub fn standard_exponential<R:Rng>(rng : &mut R) -> f64 {
+    -open_uniform(rng).ln()
+}
+
+
+/// Draws a random number of the gamma distribution of unit scale
+/// (Marsaglia and Tsang, 2000)
+pub fn standard_gamma<R:Rng>(rng : &mut R, shape : f64) -> f64 {
+    if shape < 1. {
+        // X U^(1/k) with X ~ Gamma(k + 1) has the Gamma(k) distribution
+        return standard_gamma(rng, shape + 1.) * open_uniform(rng).powf(1. / shape);
+    }
+    let d = shape - 1. / 3.;
+    let c = 1. / (9. * d).sqrt();
+    loop {
+        let z = standard_normal(rng);
+        let v = 1. + c * z;
+        if v <= 0. {
+            continue;
+        }
+        let v = v * v * v;
+        let u = open_uniform(rng);
+        if u < 1. - 0.0331 * z * z * z * z || u.ln() < 0.5 * z * z + d * (1. - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+
+/// Draws a Poisson random number: by multiplying uniforms for small
+/// means and by transformed rejection (Hörmann's PTRS) otherwise
+pub fn poisson<R:Rng>(rng : &mut R, lambda : f64) -> f64 {
+    if lambda < 10. {
+        let limit = (-lambda).exp();
+        let mut k = 0.;
+        let mut product = rng.gen::<f64>();
+        while product > limit {
+            k += 1.;
+            product *= rng.gen::<f64>();
+        }
+        return k;
+    }
+    let slam = lambda.sqrt();
+    let loglam = lambda.ln();
+    let b = 0.931 + 2.53 * slam;
+    let a = -0.059 + 0.02483 * b;
+    let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+    let vr = 0.9277 - 3.6224 / (b - 2.);
+    loop {
+        let u = rng.gen::<f64>() - 0.5;
+        let v = rng.gen::<f64>();
+        let us = 0.5 - u.abs();
+        let k = ((2. * a / us + b) * u + lambda + 0.43).floor();
+        if us >= 0.07 && v <= vr {
+            return k;
+        }
+        if k < 0. || (us < 0.013 && v > us) {
+            continue;
+        }
+        if v.ln() + inv_alpha.ln() - (a / (us * us) + b).ln() <= -lambda + k * loglam - ln_gamma(k + 1.) {
+            return k;
+        }
+    }
+}
+
+
+/// Returns the smallest integer k >= 0 with cdf(k) >= p, searching
+/// outwards from a guess and then bisecting
+pub fn discrete_quantile<F:Fn(f64) -> f64>(cdf : F, p : f64, guess : f64) -> f64 {
+    let mut hi = if guess.is_finite() { guess.max(0.).floor() } else { 0. };
+    let mut step = 1.;
+    // Invariant: cdf(lo) < p <= cdf(hi), with cdf(-1) = 0
+    let mut lo;
+    if cdf(hi) >= p {
+        loop {
+            lo = hi - step;
+            if lo < 0. {
+                lo = -1.;
+                break;
+            }
+            if cdf(lo) < p {
+                break;
+            }
+            hi = lo;
+            step *= 2.;
+        }
+    }
+    else {
+        loop {
+            lo = hi;
+            hi += step;
+            if cdf(hi) >= p {
+                break;
+            }
+            step *= 2.;
+        }
+    }
+    while hi - lo > 1. {
+        let mid = (0.5 * (lo + hi)).floor();
+        if cdf(mid) >= p { hi = mid } else { lo = mid }
+    }
+    hi
+}
+
+
+/// Returns the Shannon entropy of a unimodal distribution on the
+/// integers 0..=last given its probability mass function and a
+/// mode. The sum runs outwards from the mode until the terms become
+/// negligible.
+pub fn discrete_entropy<F:Fn(f64) -> f64>(pmf : F, mode : f64, last : f64) -> f64 {
+    let term = |k : f64| {
+        let p = pmf(k);
+        if p > 0. { -p * p.ln() } else { 0. }
+    };
+    let mut entropy = term(mode);
+    let mut k = mode + 1.;
+    while k <= last && pmf(k) > 1e-20 {
+        entropy += term(k);
+        k += 1.;
+    }
+    k = mode - 1.;
+    while k >= 0. && pmf(k) > 1e-20 {
+        entropy += term(k);
+        k -= 1.;
+    }
+    entropy
+}
+
+
+/// Checks the mean and variance of a large seeded sample against
+/// those of the distribution, within five standard errors
+#[cfg(test)]
+pub fn assert_sample_moments<D:Distribution>(d : &D, seed : usize) {
+    use rand::{SeedableRng, StdRng};
+    let mut rng : StdRng = SeedableRng::from_seed(&[seed][..]);
+    let n = 20000;
+    let x = d.sample_matrix(&mut rng, n, 1);
+    let nf = n as f64;
+    let mean = (0..n).map(|i| x[i]).sum::<f64>() / nf;
+    let var = (0..n).map(|i| (x[i] - mean) * (x[i] - mean)).sum::<f64>() / (nf - 1.);
+    let sd = d.std_dev();
+    assert!((mean - d.mean()).abs() < 5. * sd / nf.sqrt(), "mean {} {}", mean, d.mean());
+    // Loose check of the variance, whose standard error depends
+    // on the fourth moment
+    assert!((var / d.variance() - 1.).abs() < 0.1, "variance {} {}", var, d.variance());
+}
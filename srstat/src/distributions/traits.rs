@@ -0,0 +1,67 @@
+#![doc="Traits describing probability distributions
+"]
+
+// std imports
+
+// external imports
+use rand::Rng;
+
+// local imports
+use srmatrix::api::{Matrix, MatrixF64, Shape};
+
+
+/// A univariate probability distribution.
+///
+/// For discrete distributions, supported on the integers, ``pdf``
+/// is the probability mass function; it is zero off the support.
+pub trait Distribution {
+
+    /// Probability density (or mass) at x
+    fn pdf(&self, x : f64) -> f64;
+
+    /// Logarithm of the probability density (or mass) at x
+    fn ln_pdf(&self, x : f64) -> f64 {
+        self.pdf(x).ln()
+    }
+
+    /// Cumulative distribution function P(X <= x)
+    fn cdf(&self, x : f64) -> f64;
+
+    /// Survival function P(X > x)
+    fn sf(&self, x : f64) -> f64 {
+        1. - self.cdf(x)
+    }
+
+    /// Quantile function: the smallest x with P(X <= x) >= p.
+    /// NaN for p outside [0, 1].
+    fn quantile(&self, p : f64) -> f64;
+
+    /// Mean, NaN (or infinite) where it does not exist
+    fn mean(&self) -> f64;
+
+    /// Variance, NaN (or infinite) where it does not exist
+    fn variance(&self) -> f64;
+
+    /// Standard deviation
+    fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Differential entropy in nats, or Shannon entropy for
+    /// discrete distributions
+    fn entropy(&self) -> f64;
+
+    /// Draws a random number from the distribution
+    fn sample<R:Rng>(&self, rng : &mut R) -> f64;
+
+    /// Returns a matrix of independent random numbers
+    fn sample_matrix<R:Rng>(&self, rng : &mut R, rows : usize, cols : usize) -> MatrixF64 {
+        let mut m : MatrixF64 = Matrix::zeros(rows, cols);
+        for c in 0..cols {
+            for r in 0..rows {
+                m.set(r, c, self.sample(rng));
+            }
+        }
+        m
+    }
+}
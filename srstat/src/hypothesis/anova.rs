@@ -0,0 +1,126 @@
+#![doc="One-way analysis of variance
+
+For k groups with N observations in total, group means m_i, sizes
+n_i and grand mean m, the sums of squares
+
+SS_between = sum_i n_i (m_i - m)^2
+SS_within = sum_i sum_j (x_ij - m_i)^2
+
+give the statistic
+
+F = (SS_between / (k - 1)) / (SS_within / (N - k))
+
+which follows the F distribution with k - 1 and N - k degrees of
+freedom when the groups are normal with equal means and variances.
+"]
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use correlation::missing::{MissingValues, data_columns};
+use distributions::traits::Distribution;
+use distributions::sampling::FisherF;
+use hypothesis::support::{check_sample, mean_and_ss};
+
+
+/// Result of a one-way analysis of variance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnovaResult {
+    /// The F statistic
+    pub statistic : f64,
+    /// The p-value P(F >= statistic)
+    pub p_value : f64,
+    /// Degrees of freedom between the groups, k - 1
+    pub dof_between : f64,
+    /// Degrees of freedom within the groups, N - k
+    pub dof_within : f64,
+    /// Sum of squares between the groups
+    pub ss_between : f64,
+    /// Sum of squares within the groups
+    pub ss_within : f64
+}
+
+
+/// Tests whether the groups have equal means
+pub fn one_way_anova(groups : &[&[f64]]) -> SRResult<AnovaResult> {
+    let k = groups.len();
+    if k < 2 {
+        return Err(SRError::InvalidArgument);
+    }
+    for g in groups.iter() {
+        check_sample(g, 1)?;
+    }
+    let n : usize = groups.iter().map(|g| g.len()).sum();
+    if n <= k {
+        return Err(SRError::InvalidArgument);
+    }
+    let grand_mean = groups.iter().map(|g| g.iter().sum::<f64>()).sum::<f64>() / n as f64;
+    let (mut ss_between, mut ss_within) = (0., 0.);
+    for g in groups.iter() {
+        let (mean, ss) = mean_and_ss(g);
+        ss_between += g.len() as f64 * (mean - grand_mean) * (mean - grand_mean);
+        ss_within += ss;
+    }
+    if ss_within <= 0. {
+        return Err(SRError::InvalidArgument);
+    }
+    let (dof_between, dof_within) = ((k - 1) as f64, (n - k) as f64);
+    let f = (ss_between / dof_between) / (ss_within / dof_within);
+    Ok(AnovaResult {
+        statistic : f,
+        p_value : FisherF::new(dof_between, dof_within)?.sf(f),
+        dof_between : dof_between,
+        dof_within : dof_within,
+        ss_between : ss_between,
+        ss_within : ss_within
+    })
+}
+
+
+/// Tests whether the columns of x have equal means
+pub fn one_way_anova_cw(x : &MatrixF64) -> SRResult<AnovaResult> {
+    let columns = data_columns(x, MissingValues::Propagate);
+    let groups : Vec<&[f64]> = columns.iter().map(|c| &c[..]).collect();
+    one_way_anova(&groups)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_anova(){
+        let a = [6.1, 5.8, 7.0, 6.5];
+        let b = [7.9, 8.3, 7.2, 8.0, 7.7];
+        let c = [5.5, 6.3, 5.9];
+        let r = one_way_anova(&[&a, &b, &c]).unwrap();
+        assert_eq!((r.dof_between, r.dof_within), (2., 9.));
+        assert_near(r.ss_between, 8.412, 1e-13);
+        assert_near(r.ss_within, 1.798, 1e-13);
+        assert_near(r.statistic, 21.053_392_658_509_454, 1e-13);
+        assert_near(r.p_value, 4.035_875_438_501_701_7e-4, 1e-11);
+        assert!(one_way_anova(&[&a]).is_err());
+        assert!(one_way_anova(&[&a, &[]]).is_err());
+        assert!(one_way_anova(&[&[1.], &[2.]]).is_err());
+    }
+
+    #[test]
+    fn test_anova_cw(){
+        let x = matrix_cw_f64(3, 3, &[1., 2., 3., 2., 3., 4., 4., 5., 6.]);
+        let r = one_way_anova_cw(&x).unwrap();
+        // Group means 2, 3 and 5 about the grand mean 10 / 3
+        assert_near(r.ss_between, 14., 1e-14);
+        assert_near(r.ss_within, 6., 1e-14);
+        assert_near(r.statistic, 7., 1e-14);
+        assert_near(r.p_value, 0.027, 1e-12);
+    }
+}
@@ -0,0 +1,157 @@
+#![doc="Pearson's chi-square tests
+
+The statistic compares observed counts O with their expectations E
+under the null hypothesis
+
+X^2 = sum (O - E)^2 / E
+
+For goodness of fit to the probabilities p_1, ..., p_k the
+expectation of cell i is N p_i and X^2 has k - 1 degrees of
+freedom. For independence in an r x c contingency table the
+expectation of cell (i, j) is the product of its row and column
+totals divided by N, and X^2 has (r - 1)(c - 1) degrees of
+freedom. Yates' continuity correction of 2 x 2 tables reduces each
+|O - E| by min(0.5, min |O - E|).
+"]
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use distributions::traits::Distribution;
+use distributions::gamma::ChiSquared;
+use hypothesis::options::{TestOptions, TestResult};
+
+
+/// Checks that the counts are finite and non-negative with a
+/// positive total
+fn check_counts(counts : &[f64]) -> SRResult<f64> {
+    if counts.iter().any(|&c| !(c >= 0. && c.is_finite())) {
+        return Err(SRError::InvalidArgument);
+    }
+    let total = counts.iter().sum::<f64>();
+    if total <= 0. {
+        return Err(SRError::InvalidArgument);
+    }
+    Ok(total)
+}
+
+
+/// Returns the result of a chi-square test
+fn chi_square_result(statistic : f64, dof : f64) -> SRResult<TestResult> {
+    Ok(TestResult {
+        statistic : statistic,
+        p_value : ChiSquared::new(dof)?.sf(statistic),
+        dof : Some(dof),
+        confidence_interval : None
+    })
+}
+
+
+/// Tests whether the observed counts follow the cell probabilities
+/// proportional to the given weights, or equal probabilities when
+/// the weights are None
+pub fn chi_square_goodness_of_fit(observed : &[f64], weights : Option<&[f64]>) -> SRResult<TestResult> {
+    let k = observed.len();
+    if k < 2 {
+        return Err(SRError::InvalidArgument);
+    }
+    let total = check_counts(observed)?;
+    let probabilities = match weights {
+        Some(w) => {
+            if w.len() != k {
+                return Err(SRError::DimensionsMismatch);
+            }
+            if w.iter().any(|&v| v <= 0.) {
+                return Err(SRError::InvalidArgument);
+            }
+            let sum = check_counts(w)?;
+            w.iter().map(|&v| v / sum).collect()
+        },
+        None => vec![1. / k as f64; k]
+    };
+    let statistic = observed.iter().zip(probabilities.iter()).map(|(&o, &p)| {
+        let e = total * p;
+        (o - e) * (o - e) / e
+    }).sum();
+    chi_square_result(statistic, (k - 1) as f64)
+}
+
+
+/// Tests whether the rows and columns of a contingency table of
+/// counts are independent
+pub fn chi_square_independence(table : &MatrixF64, options : &TestOptions) -> SRResult<TestResult> {
+    let (rows, cols) = table.size();
+    if rows < 2 || cols < 2 {
+        return Err(SRError::InvalidArgument);
+    }
+    let counts : Vec<f64> = (0..rows).flat_map(|r| (0..cols).map(move |c| (r, c)))
+        .map(|(r, c)| table.get(r, c).unwrap()).collect();
+    let total = check_counts(&counts)?;
+    let row_sums : Vec<f64> = (0..rows).map(|r| counts[r * cols..(r + 1) * cols].iter().sum()).collect();
+    let col_sums : Vec<f64> = (0..cols).map(|c| (0..rows).map(|r| counts[r * cols + c]).sum()).collect();
+    // An empty row or column leaves expectations of zero
+    if row_sums.iter().chain(col_sums.iter()).any(|&s| s == 0.) {
+        return Err(SRError::InvalidArgument);
+    }
+    let expected : Vec<f64> = (0..rows * cols).map(|i| row_sums[i / cols] * col_sums[i % cols] / total).collect();
+    let yates = if options.continuity && rows == 2 && cols == 2 {
+        counts.iter().zip(expected.iter()).map(|(&o, &e)| (o - e).abs()).fold(0.5, f64::min)
+    }
+    else {
+        0.
+    };
+    let statistic = counts.iter().zip(expected.iter()).map(|(&o, &e)| {
+        let d = (o - e).abs() - yates;
+        d * d / e
+    }).sum();
+    chi_square_result(statistic, ((rows - 1) * (cols - 1)) as f64)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_goodness_of_fit(){
+        // Fair die
+        let r = chi_square_goodness_of_fit(&[16., 18., 16., 14., 12., 24.], None).unwrap();
+        assert_near(r.statistic, 5.12, 1e-14);
+        assert_eq!(r.dof, Some(5.));
+        assert_near(r.p_value, 0.401_411_593_246_095_3, 1e-12);
+        assert!(r.confidence_interval.is_none());
+        // Mendel's peas, 9 : 3 : 3 : 1
+        let r = chi_square_goodness_of_fit(&[315., 108., 101., 32.], Some(&[9., 3., 3., 1.])).unwrap();
+        assert_near(r.statistic, 0.470_023_980_815_347_7, 1e-13);
+        assert_near(r.p_value, 0.925_425_895_103_616, 1e-12);
+        assert!(chi_square_goodness_of_fit(&[1., 2.], Some(&[1.])).is_err());
+        assert!(chi_square_goodness_of_fit(&[1., -2.], None).is_err());
+        assert!(chi_square_goodness_of_fit(&[1., 2.], Some(&[1., 0.])).is_err());
+    }
+
+    #[test]
+    fn test_independence(){
+        let table = matrix_rw_f64(2, 3, &[20., 15., 25., 30., 35., 15.]);
+        let r = chi_square_independence(&table, &TestOptions::new()).unwrap();
+        assert_near(r.statistic, 9.843_75, 1e-13);
+        assert_eq!(r.dof, Some(2.));
+        assert_near(r.p_value, 0.007_285_457_798_938_988, 1e-12);
+        let table = matrix_rw_f64(2, 2, &[12., 5., 7., 16.]);
+        let yates = chi_square_independence(&table, &TestOptions::new()).unwrap();
+        assert_near(yates.statistic, 4.812_286_470_652_334, 1e-13);
+        assert_near(yates.p_value, 0.028_257_528_526_361_304, 1e-12);
+        let plain = chi_square_independence(&table, &TestOptions::new().continuity(false)).unwrap();
+        assert_near(plain.statistic, 6.319_891_801_114_038, 1e-13);
+        assert_eq!(plain.dof, Some(1.));
+        assert!(chi_square_independence(&matrix_rw_f64(2, 2, &[1., 0., 2., 0.]), &TestOptions::new()).is_err());
+        assert!(chi_square_independence(&matrix_rw_f64(1, 2, &[1., 2.]), &TestOptions::new()).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+#![doc="Corrections of p-values for multiple testing
+
+With n p-values sorted as p_(1) <= ... <= p_(n) the adjusted values
+are
+
+* Bonferroni: min(1, n p_i)
+* Holm: max over j <= i of min(1, (n - j + 1) p_(j))
+* Benjamini–Hochberg: min over j >= i of min(1, n p_(j) / j)
+
+Bonferroni and Holm control the family-wise error rate, and
+Benjamini–Hochberg the false discovery rate of independent or
+positively dependent tests. Rejecting the hypotheses whose adjusted
+p-value is at most alpha applies the procedure at level alpha.
+NaN p-values are left in place and do not count in n.
+"]
+
+// std imports
+use std::cmp::Ordering;
+
+// local imports
+
+
+/// Method of multiple testing correction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Correction {
+    /// Bonferroni's correction
+    Bonferroni,
+    /// Holm's step-down procedure
+    Holm,
+    /// Benjamini and Hochberg's step-up procedure
+    BenjaminiHochberg
+}
+
+
+/// Returns the adjusted p-values in the order of p
+pub fn adjust_p_values(p : &[f64], method : Correction) -> Vec<f64> {
+    let mut order : Vec<usize> = (0..p.len()).filter(|&i| !p[i].is_nan()).collect();
+    order.sort_by(|&i, &j| p[i].partial_cmp(&p[j]).unwrap_or(Ordering::Equal));
+    let n = order.len() as f64;
+    let mut result = p.to_vec();
+    match method {
+        Correction::Bonferroni => {
+            for &i in order.iter() {
+                result[i] = (n * p[i]).min(1.);
+            }
+        },
+        Correction::Holm => {
+            let mut running = 0f64;
+            for (k, &i) in order.iter().enumerate() {
+                running = running.max(((n - k as f64) * p[i]).min(1.));
+                result[i] = running;
+            }
+        },
+        Correction::BenjaminiHochberg => {
+            let mut running = 1f64;
+            for (k, &i) in order.iter().enumerate().rev() {
+                running = running.min(n * p[i] / (k + 1) as f64);
+                result[i] = running;
+            }
+        }
+    }
+    result
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+
+    fn assert_all_near(a : &[f64], b : &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-15, "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_corrections(){
+        let p = [0.01, 0.04, 0.03, 0.005, 0.2];
+        assert_all_near(&adjust_p_values(&p, Correction::Bonferroni), &[0.05, 0.2, 0.15, 0.025, 1.]);
+        assert_all_near(&adjust_p_values(&p, Correction::Holm), &[0.04, 0.09, 0.09, 0.025, 0.2]);
+        assert_all_near(&adjust_p_values(&p, Correction::BenjaminiHochberg), &[0.025, 0.05, 0.05, 0.025, 0.2]);
+        assert!(adjust_p_values(&[], Correction::Holm).is_empty());
+    }
+
+    #[test]
+    fn test_missing(){
+        let p = [0.02, f64::NAN, 0.01];
+        let holm = adjust_p_values(&p, Correction::Holm);
+        assert!(holm[1].is_nan());
+        assert_all_near(&[holm[0], holm[2]], &[0.02, 0.02]);
+        let bh = adjust_p_values(&p, Correction::BenjaminiHochberg);
+        assert_all_near(&[bh[0], bh[2]], &[0.02, 0.02]);
+    }
+}
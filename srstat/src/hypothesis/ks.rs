@@ -0,0 +1,342 @@
+#![doc="Kolmogorov–Smirnov tests
+
+The one sample test compares the empirical distribution function
+F_n of x with a hypothesized continuous distribution function F,
+and the two sample test compares the empirical distribution
+functions of x and y. The statistics are
+
+* two sided: D = sup |F_n - F|
+* greater: D+ = sup (F_n - F)
+* less: D- = sup (F - F_n)
+
+with F replaced by the empirical distribution function of y in the
+two sample test.
+
+The exact distribution of D is computed by the method of Marsaglia,
+Tsang and Wang (2003), and that of D+ and D- by the formula of
+Birnbaum and Tingey (1951). The exact two sample distribution of D
+counts the lattice paths which stay within the band |i / m - j / n|
+< D. Otherwise the p-values are asymptotic: P(K > sqrt(n) D) for
+the Kolmogorov distribution K, and exp(-2 n D^2) for the one sided
+statistics, with n replaced by m n / (m + n) for two samples.
+"]
+
+// std imports
+use std::f64::consts::PI;
+
+// local imports
+use srmatrix::api::*;
+use srnum::special::gamma::ln_gamma;
+use descriptive::sample::sorted;
+use distributions::traits::Distribution;
+use hypothesis::options::{Alternative, TestOptions, TestResult};
+use hypothesis::support::check_sample;
+
+
+/// Returns P(K > lambda) for the Kolmogorov distribution
+fn kolmogorov_sf(lambda : f64) -> f64 {
+    if lambda <= 0. {
+        return 1.;
+    }
+    if lambda < 1.18 {
+        // P(K <= lambda) = sqrt(2 pi) / lambda sum exp(-(2j - 1)^2 pi^2 / (8 lambda^2))
+        let w = PI * PI / (8. * lambda * lambda);
+        let sum : f64 = (1..10).map(|j| {
+            let k = (2 * j - 1) as f64;
+            (-k * k * w).exp()
+        }).sum();
+        1. - (2. * PI).sqrt() / lambda * sum
+    }
+    else {
+        // P(K > lambda) = 2 sum (-1)^(j - 1) exp(-2 j^2 lambda^2)
+        let sum : f64 = (1..10).map(|j| {
+            let k = j as f64;
+            let term = (-2. * k * k * lambda * lambda).exp();
+            if j % 2 == 1 { term } else { -term }
+        }).sum();
+        2. * sum
+    }
+}
+
+
+/// Returns the product of two m x m row major matrices
+fn matrix_product(a : &[f64], b : &[f64], m : usize) -> Vec<f64> {
+    let mut c = vec![0.; m * m];
+    for i in 0..m {
+        for k in 0..m {
+            let aik = a[i * m + k];
+            for j in 0..m {
+                c[i * m + j] += aik * b[k * m + j];
+            }
+        }
+    }
+    c
+}
+
+
+/// Returns the n-th power of an m x m matrix as a matrix and a
+/// decimal exponent, rescaling to avoid overflow
+fn matrix_power(a : &[f64], m : usize, n : usize) -> (Vec<f64>, i32) {
+    if n == 1 {
+        return (a.to_vec(), 0);
+    }
+    let (v, ev) = matrix_power(a, m, n / 2);
+    let mut b = matrix_product(&v, &v, m);
+    let mut eb = 2 * ev;
+    if n % 2 == 1 {
+        b = matrix_product(a, &b, m);
+    }
+    let centre = (m / 2) * m + m / 2;
+    if b[centre] > 1e140 {
+        for v in b.iter_mut() {
+            *v *= 1e-140;
+        }
+        eb += 140;
+    }
+    (b, eb)
+}
+
+
+/// Returns P(D_n < d) for the two sided one sample statistic
+fn kolmogorov_cdf_exact(n : usize, d : f64) -> f64 {
+    let nf = n as f64;
+    let s = d * d * nf;
+    if s > 7.24 || (s > 3.76 && n > 99) {
+        return 1. - 2. * (-(2.000_071 + 0.331 / nf.sqrt() + 1.409 / nf) * s).exp();
+    }
+    let k = (nf * d) as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - nf * d;
+    let mut a = vec![0.; m * m];
+    for i in 0..m {
+        for j in 0..m {
+            if i + 1 >= j {
+                a[i * m + j] = 1.;
+            }
+        }
+    }
+    for i in 0..m {
+        a[i * m] -= h.powi(i as i32 + 1);
+        a[(m - 1) * m + i] -= h.powi((m - i) as i32);
+    }
+    if 2. * h - 1. > 0. {
+        a[(m - 1) * m] += (2. * h - 1.).powi(m as i32);
+    }
+    for i in 0..m {
+        for j in 0..(i + 2).min(m) {
+            for g in 1..(i + 2 - j) {
+                a[i * m + j] /= g as f64;
+            }
+        }
+    }
+    let (q, mut e) = matrix_power(&a, m, n);
+    let mut s = q[(k - 1) * m + k - 1];
+    for i in 1..(n + 1) {
+        s = s * i as f64 / nf;
+        if s < 1e-140 {
+            s *= 1e140;
+            e -= 140;
+        }
+    }
+    s * 10f64.powi(e)
+}
+
+
+/// Returns P(D+_n >= d) for the one sided one sample statistic
+fn smirnov_sf_exact(n : usize, d : f64) -> f64 {
+    if d <= 0. {
+        return 1.;
+    }
+    if d >= 1. {
+        return 0.;
+    }
+    let nf = n as f64;
+    let last = (nf * (1. - d)).floor() as usize;
+    let sum : f64 = (0..(last + 1)).map(|j| {
+        let jf = j as f64;
+        let ln_binomial = ln_gamma(nf + 1.) - ln_gamma(jf + 1.) - ln_gamma(nf - jf + 1.);
+        (ln_binomial + (nf - jf) * (1. - d - jf / nf).ln() + (jf - 1.) * (d + jf / nf).ln()).exp()
+    }).sum();
+    d * sum
+}
+
+
+/// Returns P(D_mn < d) for the two sided two sample statistic
+fn smirnov_cdf_exact(m : usize, n : usize, d : f64) -> f64 {
+    let (m, n) = if m > n { (n, m) } else { (m, n) };
+    let (md, nd) = (m as f64, n as f64);
+    // The largest attainable value of D below d
+    let q = (0.5 + (d * md * nd - 1e-7).floor()) / (md * nd);
+    let mut u : Vec<f64> = (0..(n + 1)).map(|j| if j as f64 / nd > q { 0. } else { 1. }).collect();
+    for i in 1..(m + 1) {
+        let w = i as f64 / (i + n) as f64;
+        let x = i as f64 / md;
+        u[0] = if x > q { 0. } else { w * u[0] };
+        for j in 1..(n + 1) {
+            u[j] = if (x - j as f64 / nd).abs() > q { 0. } else { w * u[j] + u[j - 1] };
+        }
+    }
+    u[n]
+}
+
+
+/// Selects D, D+ or D- according to the alternative
+fn select_statistic(alternative : Alternative, plus : f64, minus : f64) -> f64 {
+    match alternative {
+        Alternative::TwoSided => plus.max(minus),
+        Alternative::Less => minus,
+        Alternative::Greater => plus
+    }
+}
+
+
+/// Tests whether x is a sample of the continuous distribution d.
+/// The exact null distribution is used by default below 100
+/// observations.
+pub fn ks_test<D:Distribution>(x : &[f64], d : &D, options : &TestOptions) -> SRResult<TestResult> {
+    check_sample(x, 1)?;
+    let x = sorted(x).ok_or(SRError::InvalidArgument)?;
+    let n = x.len();
+    let nf = n as f64;
+    let (mut plus, mut minus) = (0f64, 0f64);
+    for (i, &v) in x.iter().enumerate() {
+        let f = d.cdf(v);
+        plus = plus.max((i + 1) as f64 / nf - f);
+        minus = minus.max(f - i as f64 / nf);
+    }
+    let statistic = select_statistic(options.alternative, plus, minus);
+    let exact = options.exact.unwrap_or(n < 100);
+    let p = match (options.alternative, exact) {
+        (Alternative::TwoSided, true) => 1. - kolmogorov_cdf_exact(n, statistic),
+        (Alternative::TwoSided, false) => kolmogorov_sf(nf.sqrt() * statistic),
+        (_, true) => smirnov_sf_exact(n, statistic),
+        (_, false) => (-2. * nf * statistic * statistic).exp()
+    };
+    Ok(TestResult {
+        statistic : statistic,
+        p_value : p.clamp(0., 1.),
+        dof : None,
+        confidence_interval : None
+    })
+}
+
+
+/// Tests whether x and y are samples of the same continuous
+/// distribution. The exact null distribution is used by default for
+/// two sided tests when the product of the sample sizes is below
+/// 10000, unless the pooled sample has ties.
+pub fn ks_test_two_sample(x : &[f64], y : &[f64], options : &TestOptions) -> SRResult<TestResult> {
+    check_sample(x, 1)?;
+    check_sample(y, 1)?;
+    let x = sorted(x).ok_or(SRError::InvalidArgument)?;
+    let y = sorted(y).ok_or(SRError::InvalidArgument)?;
+    let (m, n) = (x.len(), y.len());
+    let (mf, nf) = (m as f64, n as f64);
+    let (mut plus, mut minus) = (0f64, 0f64);
+    let (mut i, mut j) = (0, 0);
+    while i < m && j < n {
+        let v = x[i].min(y[j]);
+        while i < m && x[i] == v {
+            i += 1;
+        }
+        while j < n && y[j] == v {
+            j += 1;
+        }
+        let diff = i as f64 / mf - j as f64 / nf;
+        plus = plus.max(diff);
+        minus = minus.max(-diff);
+    }
+    let mut pooled = [&x[..], &y[..]].concat();
+    pooled.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let ties = pooled.windows(2).any(|w| w[0] == w[1]);
+    let statistic = select_statistic(options.alternative, plus, minus);
+    let exact = options.alternative == Alternative::TwoSided && !ties && options.exact.unwrap_or(m * n < 10000);
+    let ne = mf * nf / (mf + nf);
+    let p = match options.alternative {
+        Alternative::TwoSided if exact => 1. - smirnov_cdf_exact(m, n, statistic),
+        Alternative::TwoSided => kolmogorov_sf(ne.sqrt() * statistic),
+        _ => (-2. * ne * statistic * statistic).exp()
+    };
+    Ok(TestResult {
+        statistic : statistic,
+        p_value : p.clamp(0., 1.),
+        dof : None,
+        confidence_interval : None
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use distributions::normal::Normal;
+    use distributions::elementary::Uniform;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_distributions(){
+        // Example of Marsaglia, Tsang and Wang
+        assert_near(kolmogorov_cdf_exact(10, 0.274), 0.628_479_615_456_504_3, 1e-13);
+        // A single observation has P(D < d) = 2d - 1 above 1 / 2
+        assert_near(kolmogorov_cdf_exact(1, 0.8), 0.6, 1e-14);
+        assert_near(smirnov_sf_exact(1, 0.3), 0.7, 1e-14);
+        // D+ and D- above 1 / 2 cannot both exceed d
+        assert_near(1. - kolmogorov_cdf_exact(20, 0.6), 2. * smirnov_sf_exact(20, 0.6), 1e-8);
+        assert_near(kolmogorov_sf(1.), 0.269_999_671_677_354_5, 1e-12);
+        assert_near(kolmogorov_sf(0.5), 0.963_945_243_664_875_1, 1e-12);
+        assert_near(kolmogorov_sf(2.), 6.709_252_557_796_954e-4, 1e-12);
+        // The exact distribution approaches the limit
+        assert!((1. - kolmogorov_cdf_exact(1000, 0.04) - kolmogorov_sf(1000f64.sqrt() * 0.04)).abs() < 5e-3);
+        assert_near(smirnov_cdf_exact(5, 6, 0.6), 16. / 21., 1e-13);
+        assert_near(smirnov_cdf_exact(6, 5, 0.6), 16. / 21., 1e-13);
+    }
+
+    #[test]
+    fn test_one_sample(){
+        let x = [-1.2, 0.3, 0.8, -0.4, 1.9, 0.1, -0.7, 0.5, 1.1, -0.2];
+        let d = Normal::standard();
+        let r = ks_test(&x, &d, &TestOptions::new()).unwrap();
+        assert_near(r.statistic, 0.144_578_258_389_675_83, 1e-14);
+        assert_near(r.p_value, 0.965_975_706_188_204_5, 1e-12);
+        assert!(r.dof.is_none());
+        let asymptotic = ks_test(&x, &d, &TestOptions::new().exact(false)).unwrap();
+        assert_near(asymptotic.p_value, 0.985_011_647_154_339_8, 1e-12);
+        let greater = ks_test(&x, &d, &TestOptions::new().alternative(Alternative::Greater)).unwrap();
+        assert_near(greater.statistic, 0.035_666_060_946_382_66, 1e-14);
+        assert_near(greater.p_value, 0.951_108_557_812_289_1, 1e-12);
+        let less = ks_test(&x, &d, &TestOptions::new().alternative(Alternative::Less)).unwrap();
+        assert_near(less.statistic, 0.144_578_258_389_675_83, 1e-14);
+        // Uniform samples far from a narrow uniform distribution
+        let u = Uniform::new(0., 0.1).unwrap();
+        let r = ks_test(&[0.5, 0.6, 0.7], &u, &TestOptions::new()).unwrap();
+        assert_eq!(r.statistic, 1.);
+        assert!(r.p_value < 1e-12);
+        assert!(ks_test(&[], &d, &TestOptions::new()).is_err());
+        assert!(ks_test(&[f64::NAN], &d, &TestOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_two_sample(){
+        let x = [0.61, 0.29, 0.06, 0.59, -1.73, -0.74];
+        let y = [0.51, -0.56, 0.39, 1.64, 0.05];
+        let r = ks_test_two_sample(&x, &y, &TestOptions::new()).unwrap();
+        assert_near(r.statistic, 1. / 3., 1e-15);
+        assert_near(r.p_value, 9. / 11., 1e-12);
+        let asymptotic = ks_test_two_sample(&x, &y, &TestOptions::new().exact(false)).unwrap();
+        assert_near(asymptotic.p_value, 0.922_332_007_856_780_6, 1e-12);
+        let greater = ks_test_two_sample(&x, &y, &TestOptions::new().alternative(Alternative::Greater)).unwrap();
+        assert_near(greater.statistic, 1. / 3., 1e-15);
+        assert_near(greater.p_value, 0.545_495_563_820_243_5, 1e-12);
+        // Ties switch to the asymptotic distribution
+        let tied = ks_test_two_sample(&[1., 2., 2., 3.], &[2., 4., 5.], &TestOptions::new()).unwrap();
+        assert_near(tied.statistic, 2. / 3., 1e-15);
+        assert_near(tied.p_value, kolmogorov_sf((12f64 / 7.).sqrt() * 2. / 3.), 1e-15);
+        assert!(ks_test_two_sample(&x, &[], &TestOptions::new()).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+#![doc="Shapiro–Wilk test of normality
+
+With the order statistics x_(1) <= ... <= x_(n) the statistic is
+
+W = (sum_i a_i (x_(n + 1 - i) - x_(i)))^2 / sum (x_i - mean)^2
+
+over i <= n / 2. The coefficients a_i and the p-values follow the
+approximations of Royston (1992, 1995, algorithm AS R94), valid for
+3 <= n <= 5000: the a_i are the normalized expected normal order
+statistics m_i = -Phi^-1((i - 3/8) / (n + 1/4)), with the two most
+extreme corrected by polynomials in 1 / sqrt(n), and a normalizing
+transformation of 1 - W is compared with the normal distribution.
+For n = 3 the distribution of W is exact.
+"]
+
+// std imports
+use std::f64::consts::{PI, FRAC_1_SQRT_2};
+
+// local imports
+use srmatrix::api::*;
+use descriptive::sample::sorted;
+use distributions::traits::Distribution;
+use distributions::normal::Normal;
+use hypothesis::options::TestResult;
+use hypothesis::support::{check_sample, mean_and_ss};
+
+
+/// Corrections of the largest two coefficients, in u = 1 / sqrt(n)
+const C1 : [f64; 6] = [0., 0.221_157, -0.147_981, -2.071_19, 4.434_685, -2.706_056];
+const C2 : [f64; 6] = [0., 0.042_981, -0.293_762, -1.752_461, 5.682_633, -3.582_633];
+/// Mean and log standard deviation of the transformed statistic
+/// for 4 <= n <= 11, in n
+const C3 : [f64; 4] = [0.544, -0.399_78, 0.025_054, -6.714e-4];
+const C4 : [f64; 4] = [1.382_2, -0.778_57, 0.062_767, -0.002_032_2];
+/// Mean and log standard deviation of ln(1 - W) for n >= 12, in
+/// ln(n)
+const C5 : [f64; 4] = [-1.586_1, -0.310_82, -0.083_751, 0.003_891_5];
+const C6 : [f64; 3] = [-0.480_3, -0.082_676, 0.003_030_2];
+/// Bound of ln(1 - W) for n <= 11, in n
+const G : [f64; 2] = [-2.273, 0.459];
+
+
+/// Evaluates the polynomial with coefficients c of increasing
+/// degree at x
+fn poly(c : &[f64], x : f64) -> f64 {
+    c.iter().rev().fold(0., |s, &ci| s * x + ci)
+}
+
+
+/// Returns the coefficients a_1, ..., a_(n/2) of the largest order
+/// statistics
+fn coefficients(n : usize) -> Vec<f64> {
+    if n == 3 {
+        return vec![FRAC_1_SQRT_2];
+    }
+    let half = n / 2;
+    let nf = n as f64;
+    let d = Normal::standard();
+    let m : Vec<f64> = (0..half).map(|i| -d.quantile((i as f64 + 0.625) / (nf + 0.25))).collect();
+    let summ2 = 2. * m.iter().map(|v| v * v).sum::<f64>();
+    let ssumm2 = summ2.sqrt();
+    let u = 1. / nf.sqrt();
+    let mut a = vec![0.; half];
+    a[0] = m[0] / ssumm2 + poly(&C1, u);
+    let (start, fac) = if n > 5 {
+        a[1] = m[1] / ssumm2 + poly(&C2, u);
+        (2, ((summ2 - 2. * m[0] * m[0] - 2. * m[1] * m[1]) / (1. - 2. * a[0] * a[0] - 2. * a[1] * a[1])).sqrt())
+    }
+    else {
+        (1, ((summ2 - 2. * m[0] * m[0]) / (1. - 2. * a[0] * a[0])).sqrt())
+    };
+    for i in start..half {
+        a[i] = m[i] / fac;
+    }
+    a
+}
+
+
+/// Returns the p-value of the statistic w of a sample of size n
+fn p_value(w : f64, n : usize) -> f64 {
+    if n == 3 {
+        let p = 6. / PI * (w.sqrt().asin() - 0.75f64.sqrt().asin());
+        return p.clamp(0., 1.);
+    }
+    let nf = n as f64;
+    let y = (1. - w).ln();
+    let z = if n <= 11 {
+        let gamma = poly(&G, nf);
+        if y >= gamma {
+            return 0.;
+        }
+        (-(gamma - y).ln() - poly(&C3, nf)) / poly(&C4, nf).exp()
+    }
+    else {
+        let l = nf.ln();
+        (y - poly(&C5, l)) / poly(&C6, l).exp()
+    };
+    Normal::standard().sf(z)
+}
+
+
+/// Tests whether x is a sample of a normal distribution. Requires
+/// between 3 and 5000 observations, not all equal.
+pub fn shapiro_wilk(x : &[f64]) -> SRResult<TestResult> {
+    check_sample(x, 3)?;
+    let n = x.len();
+    if n > 5000 {
+        return Err(SRError::InvalidArgument);
+    }
+    let x = sorted(x).ok_or(SRError::InvalidArgument)?;
+    let (_, ss) = mean_and_ss(&x);
+    if !(x[n - 1] > x[0] && ss > 0.) {
+        return Err(SRError::InvalidArgument);
+    }
+    let a = coefficients(n);
+    let b = a.iter().enumerate().map(|(i, &ai)| ai * (x[n - 1 - i] - x[i])).sum::<f64>();
+    let w = (b * b / ss).min(1.);
+    Ok(TestResult {
+        statistic : w,
+        p_value : p_value(w, n),
+        dof : None,
+        confidence_interval : None
+    })
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_coefficients(){
+        // Table of Shapiro and Wilk (1965) for n = 10
+        let expected = [0.5739, 0.3291, 0.2141, 0.1224, 0.0399];
+        for (a, e) in coefficients(10).iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 5e-4, "{} {}", a, e);
+        }
+        assert!((coefficients(20)[0] - 0.4734).abs() < 5e-5);
+    }
+
+    #[test]
+    fn test_shapiro_wilk(){
+        let r = shapiro_wilk(&[148., 154., 158., 160., 161., 162., 166., 170., 182., 195., 236.]).unwrap();
+        assert_near(r.statistic, 0.788_814_694_835_387_4, 1e-12);
+        assert_near(r.p_value, 0.006_703_814_056_503_009, 1e-10);
+        assert!(r.dof.is_none() && r.confidence_interval.is_none());
+        let x = [2.1, 3.4, 1.9, 5.6, 4.4, 3.8, 2.9, 4.1, 3.3, 5.0, 2.6, 3.9, 4.7, 3.1, 2.4, 4.2, 3.6, 5.3, 2.8, 3.5];
+        let r = shapiro_wilk(&x).unwrap();
+        assert_near(r.statistic, 0.982_039_044_458_652_7, 1e-12);
+        assert_near(r.p_value, 0.957_624_877_920_735_9, 1e-10);
+        let r = shapiro_wilk(&[1., 2., 3., 4., 5.]).unwrap();
+        assert_near(r.statistic, 0.986_762_155_447_719_6, 1e-12);
+        assert_near(r.p_value, 0.967_173_935_968_040_6, 1e-10);
+        // Exact distribution for three observations
+        let r = shapiro_wilk(&[1., 2., 4.]).unwrap();
+        assert_near(r.statistic, 27. / 28., 1e-14);
+        assert_near(r.p_value, 0.636_886_845_028_969_8, 1e-12);
+        assert!(shapiro_wilk(&[1., 2.]).is_err());
+        assert!(shapiro_wilk(&[3., 3., 3., 3.]).is_err());
+    }
+}
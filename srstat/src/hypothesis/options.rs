@@ -0,0 +1,182 @@
+#![doc="Alternatives, options and results of hypothesis tests
+"]
+
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use distributions::traits::Distribution;
+
+
+/// The alternative hypothesis of a test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alternative {
+    /// The parameter differs from its null value
+    TwoSided,
+    /// The parameter is below its null value
+    Less,
+    /// The parameter is above its null value
+    Greater
+}
+
+
+impl Alternative {
+
+    /// Returns the p-value of a statistic with continuous null
+    /// distribution d which grows with the parameter
+    pub fn p_value<D:Distribution>(&self, d : &D, statistic : f64) -> f64 {
+        match *self {
+            Alternative::TwoSided => (2. * d.cdf(statistic).min(d.sf(statistic))).min(1.),
+            Alternative::Less => d.cdf(statistic),
+            Alternative::Greater => d.sf(statistic)
+        }
+    }
+
+    /// Returns the confidence interval of the given level for a
+    /// parameter whose estimate minus the parameter, divided by the
+    /// standard error, follows the symmetric distribution d
+    pub fn confidence_interval<D:Distribution>(&self, d : &D, estimate : f64, std_err : f64, level : f64) -> (f64, f64) {
+        match *self {
+            Alternative::TwoSided => {
+                let w = d.quantile(0.5 + 0.5 * level) * std_err;
+                (estimate - w, estimate + w)
+            },
+            Alternative::Less => (f64::NEG_INFINITY, estimate + d.quantile(level) * std_err),
+            Alternative::Greater => (estimate - d.quantile(level) * std_err, f64::INFINITY)
+        }
+    }
+}
+
+
+/// Options of the hypothesis tests
+#[derive(Debug, Clone, Copy)]
+pub struct TestOptions {
+    /// The alternative hypothesis
+    pub alternative : Alternative,
+    /// Level of the confidence intervals
+    pub confidence : f64,
+    /// Whether the rank and Kolmogorov-Smirnov tests use the exact
+    /// null distribution, chosen from the sample sizes when None
+    pub exact : Option<bool>,
+    /// Whether normal approximations of discrete statistics and
+    /// the chi-square test of 2 x 2 tables use a continuity
+    /// correction
+    pub continuity : bool
+}
+
+impl TestOptions {
+
+    /// Default options: two sided tests with 95% confidence
+    /// intervals, automatic choice of exact distributions and
+    /// continuity corrections
+    pub fn new() -> TestOptions {
+        TestOptions {
+            alternative : Alternative::TwoSided,
+            confidence : 0.95,
+            exact : None,
+            continuity : true
+        }
+    }
+
+    /// Sets the alternative hypothesis
+    pub fn alternative(mut self, alternative : Alternative) -> TestOptions {
+        self.alternative = alternative;
+        self
+    }
+
+    /// Sets the confidence level
+    pub fn confidence(mut self, confidence : f64) -> TestOptions {
+        self.confidence = confidence;
+        self
+    }
+
+    /// Forces or forbids exact null distributions
+    pub fn exact(mut self, exact : bool) -> TestOptions {
+        self.exact = Some(exact);
+        self
+    }
+
+    /// Enables or disables continuity corrections
+    pub fn continuity(mut self, continuity : bool) -> TestOptions {
+        self.continuity = continuity;
+        self
+    }
+
+    /// Checks the options. The confidence level must lie in (0, 1).
+    pub fn validate(&self) -> SRResult<()> {
+        if !(self.confidence > 0. && self.confidence < 1.) {
+            return Err(SRError::InvalidArgument);
+        }
+        Ok(())
+    }
+}
+
+impl Default for TestOptions {
+    fn default() -> TestOptions {
+        TestOptions::new()
+    }
+}
+
+
+/// Result of a hypothesis test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestResult {
+    /// The test statistic
+    pub statistic : f64,
+    /// The p-value
+    pub p_value : f64,
+    /// Degrees of freedom of the null distribution, if it has any
+    pub dof : Option<f64>,
+    /// Confidence interval of the tested parameter, for the tests
+    /// which provide one
+    pub confidence_interval : Option<(f64, f64)>
+}
+
+impl TestResult {
+
+    /// Returns whether the null hypothesis is rejected at the
+    /// significance level alpha
+    pub fn rejects(&self, alpha : f64) -> bool {
+        self.p_value <= alpha
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use distributions::normal::Normal;
+
+    #[test]
+    fn test_alternative(){
+        let d = Normal::standard();
+        let p = Alternative::TwoSided.p_value(&d, -1.959_963_984_540_054);
+        assert!((p - 0.05).abs() < 1e-14);
+        assert!((Alternative::Less.p_value(&d, 0.) - 0.5).abs() < 1e-15);
+        assert!((Alternative::Greater.p_value(&d, 1.644_853_626_951_472_7) - 0.05).abs() < 1e-14);
+        let (a, b) = Alternative::TwoSided.confidence_interval(&d, 1., 2., 0.95);
+        assert!((a - (1. - 3.919_927_969_080_108)).abs() < 1e-13);
+        assert!((b - (1. + 3.919_927_969_080_108)).abs() < 1e-13);
+        let (a, b) = Alternative::Less.confidence_interval(&d, 1., 2., 0.95);
+        assert_eq!(a, f64::NEG_INFINITY);
+        assert!((b - (1. + 3.289_707_253_902_945)).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_options(){
+        let options = TestOptions::new().alternative(Alternative::Less).confidence(0.9).exact(false);
+        assert_eq!(options.alternative, Alternative::Less);
+        assert_eq!((options.confidence, options.exact, options.continuity), (0.9, Some(false), true));
+        assert!(options.validate().is_ok());
+        assert!(TestOptions::new().confidence(1.).validate().is_err());
+        let result = TestResult { statistic : 2., p_value : 0.03, dof : None, confidence_interval : None };
+        assert!(result.rejects(0.05) && !result.rejects(0.01));
+    }
+}
@@ -0,0 +1,237 @@
+#![doc="Rank tests: Mann–Whitney U and Wilcoxon signed-rank
+
+The Mann–Whitney statistic of samples x and y of sizes m and n is
+
+U = R_x - m (m + 1) / 2
+
+where R_x is the sum of the ranks of x in the pooled sample, with
+ties given their average rank. It counts the pairs with x_i > y_j,
+ties counting one half, and has mean m n / 2 under the null
+hypothesis that x and y come from the same distribution.
+
+The signed-rank statistic V of a sample d_i = x_i - mu is the sum of
+the ranks of |d_i| over the positive d_i, after zero differences are
+dropped. It has mean n (n + 1) / 4 when the d_i are symmetric about
+zero.
+
+The exact null distributions count the rank subsets with each sum.
+They are used by default below 50 observations per sample when
+there are no ties and, for V, no zeros. Otherwise the statistics are
+standardized with their tie corrected variances
+
+Var U = m n / 12 ((N + 1) - sum (t^3 - t) / (N (N - 1)))
+Var V = n (n + 1) (2n + 1) / 24 - sum (t^3 - t) / 48
+
+for tie groups of sizes t, and compared with the standard normal
+distribution after an optional continuity correction of 1/2.
+"]
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use correlation::rank::ranks;
+use distributions::normal::Normal;
+use hypothesis::options::{TestOptions, TestResult};
+use hypothesis::support::{check_sample, discrete_p_value, tie_sum, continuity_correction};
+
+
+/// Returns the probabilities of the sums 0, 1, ..., of the rank
+/// subsets of size m drawn from 1, ..., n
+fn rank_sum_distribution(n : usize, m : usize) -> Vec<f64> {
+    let max_sum = m * n;
+    // counts[j][s] is the number of subsets of size j with sum s
+    let mut counts = vec![vec![0.; max_sum + 1]; m + 1];
+    counts[0][0] = 1.;
+    for k in 1..(n + 1) {
+        for j in (1..(m.min(k) + 1)).rev() {
+            for s in (k..(max_sum + 1)).rev() {
+                counts[j][s] += counts[j - 1][s - k];
+            }
+        }
+    }
+    let total = counts[m].iter().sum::<f64>();
+    counts[m].iter().map(|c| c / total).collect()
+}
+
+
+/// Returns the probabilities of the sums 0, 1, ..., n (n + 1) / 2 of
+/// the subsets of the ranks 1, ..., n
+fn signed_rank_distribution(n : usize) -> Vec<f64> {
+    let max_sum = n * (n + 1) / 2;
+    let mut counts = vec![0.; max_sum + 1];
+    counts[0] = 1.;
+    for k in 1..(n + 1) {
+        for s in (k..(max_sum + 1)).rev() {
+            counts[s] += counts[s - k];
+        }
+    }
+    let total = counts.iter().sum::<f64>();
+    counts.iter().map(|c| c / total).collect()
+}
+
+
+/// Returns the p-value of the integer statistic s from the
+/// probabilities of the values 0, 1, ...
+fn exact_p_value(distribution : &[f64], s : f64, mean : f64, options : &TestOptions) -> f64 {
+    let lower = |s : f64| distribution[..(s as usize + 1)].iter().sum::<f64>();
+    let upper = |s : f64| distribution[(s as usize)..].iter().sum::<f64>();
+    discrete_p_value(options.alternative, s, mean, lower, upper).min(1.)
+}
+
+
+/// Returns the result of the normal approximation of a statistic
+/// with given mean and variance
+fn normal_result(s : f64, mean : f64, variance : f64, options : &TestOptions) -> SRResult<TestResult> {
+    if variance <= 0. {
+        return Err(SRError::InvalidArgument);
+    }
+    let correction = continuity_correction(options.alternative, s - mean, options.continuity);
+    let z = (s - mean - correction) / variance.sqrt();
+    Ok(TestResult {
+        statistic : s,
+        p_value : options.alternative.p_value(&Normal::standard(), z),
+        dof : None,
+        confidence_interval : None
+    })
+}
+
+
+/// Tests whether x and y come from the same distribution against a
+/// shift of x, by the Mann–Whitney U (Wilcoxon rank-sum) test
+pub fn mann_whitney_u(x : &[f64], y : &[f64], options : &TestOptions) -> SRResult<TestResult> {
+    check_sample(x, 1)?;
+    check_sample(y, 1)?;
+    let (m, n) = (x.len(), y.len());
+    let pooled = [x, y].concat();
+    let rank_sum = ranks(&pooled)[..m].iter().sum::<f64>();
+    let u = rank_sum - (m * (m + 1)) as f64 / 2.;
+    let mean = (m * n) as f64 / 2.;
+    let ties = tie_sum(&pooled);
+    let exact = ties == 0. && options.exact.unwrap_or(m < 50 && n < 50);
+    if exact {
+        let distribution = rank_sum_distribution(m + n, m);
+        // Shift the rank sums to the values of U
+        let shifted = &distribution[(m * (m + 1) / 2)..];
+        return Ok(TestResult {
+            statistic : u,
+            p_value : exact_p_value(shifted, u, mean, options),
+            dof : None,
+            confidence_interval : None
+        });
+    }
+    let nf = (m + n) as f64;
+    let variance = mean / 6. * ((nf + 1.) - ties / (nf * (nf - 1.)));
+    normal_result(u, mean, variance, options)
+}
+
+
+/// Tests whether the distribution of x is symmetric about mu, by the
+/// Wilcoxon signed-rank test
+pub fn wilcoxon_signed_rank(x : &[f64], mu : f64, options : &TestOptions) -> SRResult<TestResult> {
+    check_sample(x, 1)?;
+    let d : Vec<f64> = x.iter().map(|&v| v - mu).filter(|&v| v != 0.).collect();
+    let zeros = d.len() < x.len();
+    let n = d.len();
+    if n == 0 {
+        return Err(SRError::InvalidArgument);
+    }
+    let magnitudes : Vec<f64> = d.iter().map(|v| v.abs()).collect();
+    let v = ranks(&magnitudes).iter().zip(d.iter()).filter(|&(_, &di)| di > 0.).map(|(r, _)| r).sum::<f64>();
+    let nf = n as f64;
+    let mean = nf * (nf + 1.) / 4.;
+    let ties = tie_sum(&magnitudes);
+    let exact = ties == 0. && !zeros && options.exact.unwrap_or(n < 50);
+    if exact {
+        return Ok(TestResult {
+            statistic : v,
+            p_value : exact_p_value(&signed_rank_distribution(n), v, mean, options),
+            dof : None,
+            confidence_interval : None
+        });
+    }
+    let variance = mean * (2. * nf + 1.) / 6. - ties / 48.;
+    normal_result(v, mean, variance, options)
+}
+
+
+/// Tests whether the differences x_i - y_i of paired observations
+/// are symmetric about mu, by the Wilcoxon signed-rank test
+pub fn wilcoxon_signed_rank_paired(x : &[f64], y : &[f64], mu : f64, options : &TestOptions) -> SRResult<TestResult> {
+    if x.len() != y.len() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let d : Vec<f64> = x.iter().zip(y.iter()).map(|(&u, &v)| u - v).collect();
+    wilcoxon_signed_rank(&d, mu, options)
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use hypothesis::options::Alternative;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_distributions(){
+        // Subsets of size 2 of 1..4 have sums 3, 4, 5, 5, 6, 7
+        let d = rank_sum_distribution(4, 2);
+        assert_eq!(d.len(), 9);
+        assert_near(d[5], 2. / 6., 1e-15);
+        assert_eq!(d[2], 0.);
+        // Subsets of 1..3 have sums 0, 1, 2, 3, 3, 4, 5, 6
+        let d = signed_rank_distribution(3);
+        assert_near(d[3], 0.25, 1e-15);
+        assert_near(d.iter().sum::<f64>(), 1., 1e-15);
+    }
+
+    // Hollander & Wolfe depression scores from the wilcox.test examples in R;
+    // 3.14 is a measurement, not an approximation of PI
+    #[allow(clippy::approx_constant)]
+    #[test]
+    fn test_mann_whitney(){
+        let x = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+        let y = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+        // Exact test without ties
+        let y_distinct = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.07, 3.14, 1.28];
+        let r = mann_whitney_u(&x, &y_distinct, &TestOptions::new()).unwrap();
+        assert_eq!(r.statistic, 58.);
+        assert_near(r.p_value, 0.135_911_147_675_853_56, 1e-12);
+        let greater = mann_whitney_u(&x, &y_distinct, &TestOptions::new().alternative(Alternative::Greater)).unwrap();
+        assert_near(greater.p_value, 0.067_955_573_837_926_78, 1e-12);
+        // Ties in y switch to the normal approximation
+        let r = mann_whitney_u(&x, &y, &TestOptions::new()).unwrap();
+        assert_eq!(r.statistic, 58.);
+        assert_near(r.p_value, 0.132_919_458_185_318_83, 1e-12);
+        let r = mann_whitney_u(&x, &y_distinct, &TestOptions::new().exact(false).continuity(false)).unwrap();
+        assert_near(r.p_value, 0.122_276_677_214_713_89, 1e-12);
+        assert!(mann_whitney_u(&x, &[], &TestOptions::new()).is_err());
+    }
+
+    // Same Hollander & Wolfe data as test_mann_whitney
+    #[allow(clippy::approx_constant)]
+    #[test]
+    fn test_signed_rank(){
+        let x = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+        let y = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+        let r = wilcoxon_signed_rank_paired(&x, &y, 0., &TestOptions::new()).unwrap();
+        assert_eq!(r.statistic, 40.);
+        assert_near(r.p_value, 0.039_062_5, 1e-14);
+        let greater = wilcoxon_signed_rank_paired(&x, &y, 0., &TestOptions::new().alternative(Alternative::Greater)).unwrap();
+        assert_near(greater.p_value, 0.019_531_25, 1e-14);
+        let approximate = wilcoxon_signed_rank_paired(&x, &y, 0., &TestOptions::new().exact(false)).unwrap();
+        assert_near(approximate.p_value, 0.044_010_984_012_951_41, 1e-12);
+        // Zeros and ties switch to the normal approximation
+        let r = wilcoxon_signed_rank(&[0., 1., -2., 3., 3., 4., 5., -6.], 0., &TestOptions::new()).unwrap();
+        assert_eq!(r.statistic, 19.);
+        assert_near(r.p_value, 0.446_059_549_370_743_95, 1e-12);
+        assert!(wilcoxon_signed_rank(&[2., 2.], 2., &TestOptions::new()).is_err());
+        assert!(wilcoxon_signed_rank_paired(&x, &y[1..], 0., &TestOptions::new()).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+#![doc="Helpers shared by the hypothesis tests
+"]
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use hypothesis::options::Alternative;
+
+
+/// Checks that a sample has at least min_len observations, all of
+/// them finite
+pub fn check_sample(x : &[f64], min_len : usize) -> SRResult<()> {
+    if x.len() < min_len || x.iter().any(|v| !v.is_finite()) {
+        return Err(SRError::InvalidArgument);
+    }
+    Ok(())
+}
+
+
+/// Returns the mean and the sum of squared deviations of a sample
+pub fn mean_and_ss(x : &[f64]) -> (f64, f64) {
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    let ss = x.iter().map(|&v| (v - mean) * (v - mean)).sum();
+    (mean, ss)
+}
+
+
+/// Returns the p-value of a discrete statistic s from its null
+/// distribution P(S <= k) and P(S >= k). The two sided p-value
+/// doubles the tail on the side of s relative to the null mean.
+pub fn discrete_p_value<F, G>(alternative : Alternative, s : f64, mean : f64, lower : F, upper : G) -> f64
+where F : Fn(f64) -> f64, G : Fn(f64) -> f64 {
+    match alternative {
+        Alternative::TwoSided => {
+            let p = if s > mean { upper(s) } else { lower(s) };
+            (2. * p).min(1.)
+        },
+        Alternative::Less => lower(s),
+        Alternative::Greater => upper(s)
+    }
+}
+
+
+/// Returns the sum of t^3 - t over the groups of t tied values
+pub fn tie_sum(values : &[f64]) -> f64 {
+    let mut v = values.to_vec();
+    v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut sum = 0.;
+    let mut start = 0;
+    while start < v.len() {
+        let mut end = start + 1;
+        while end < v.len() && v[end] == v[start] {
+            end += 1;
+        }
+        let t = (end - start) as f64;
+        sum += t * t * t - t;
+        start = end;
+    }
+    sum
+}
+
+
+/// Returns the continuity correction of a normal approximation of
+/// the statistic s - mean
+pub fn continuity_correction(alternative : Alternative, deviation : f64, continuity : bool) -> f64 {
+    if !continuity {
+        return 0.;
+    }
+    match alternative {
+        Alternative::TwoSided => if deviation > 0. { 0.5 } else if deviation < 0. { -0.5 } else { 0. },
+        Alternative::Less => -0.5,
+        Alternative::Greater => 0.5
+    }
+}
@@ -0,0 +1,209 @@
+#![doc="Student's t-tests
+
+With sample means m_x, m_y, variances s_x^2, s_y^2 and sizes n_x,
+n_y the statistic is t = (estimate - mu) / se where
+
+* one sample: estimate m_x, se = s_x / sqrt(n_x), n_x - 1 degrees
+  of freedom
+* paired: the one sample test of the differences x_i - y_i
+* two sample: estimate m_x - m_y, se = s_p sqrt(1 / n_x + 1 / n_y)
+  with the pooled variance
+  s_p^2 = ((n_x - 1) s_x^2 + (n_y - 1) s_y^2) / (n_x + n_y - 2)
+  and n_x + n_y - 2 degrees of freedom
+* Welch: estimate m_x - m_y, se^2 = s_x^2 / n_x + s_y^2 / n_y and
+  the Welch–Satterthwaite degrees of freedom
+  se^4 / ((s_x^2 / n_x)^2 / (n_x - 1) + (s_y^2 / n_y)^2 / (n_y - 1))
+
+The confidence interval is that of the mean, of the mean
+difference, or of the difference of means respectively.
+"]
+
+// std imports
+
+// local imports
+use srmatrix::api::*;
+use correlation::missing::{MissingValues, data_columns};
+use distributions::sampling::StudentT;
+use hypothesis::options::{TestOptions, TestResult};
+use hypothesis::support::{check_sample, mean_and_ss};
+
+
+/// Returns the result of a t-test of the given estimate
+fn t_result(estimate : f64, mu : f64, std_err : f64, dof : f64, options : &TestOptions) -> SRResult<TestResult> {
+    // Constant samples leave the statistic undefined
+    if !(std_err > 0. && std_err.is_finite()) {
+        return Err(SRError::InvalidArgument);
+    }
+    let d = StudentT::new(dof)?;
+    let t = (estimate - mu) / std_err;
+    let alternative = options.alternative;
+    Ok(TestResult {
+        statistic : t,
+        p_value : alternative.p_value(&d, t),
+        dof : Some(dof),
+        confidence_interval : Some(alternative.confidence_interval(&d, estimate, std_err, options.confidence))
+    })
+}
+
+
+/// Tests whether the mean of x equals mu
+pub fn one_sample_t_test(x : &[f64], mu : f64, options : &TestOptions) -> SRResult<TestResult> {
+    options.validate()?;
+    check_sample(x, 2)?;
+    let n = x.len() as f64;
+    let (mean, ss) = mean_and_ss(x);
+    t_result(mean, mu, (ss / (n - 1.) / n).sqrt(), n - 1., options)
+}
+
+
+/// Tests whether the mean of the differences x_i - y_i of paired
+/// observations equals mu
+pub fn paired_t_test(x : &[f64], y : &[f64], mu : f64, options : &TestOptions) -> SRResult<TestResult> {
+    if x.len() != y.len() {
+        return Err(SRError::DimensionsMismatch);
+    }
+    let d : Vec<f64> = x.iter().zip(y.iter()).map(|(&u, &v)| u - v).collect();
+    one_sample_t_test(&d, mu, options)
+}
+
+
+/// Tests whether the difference of the means of x and y equals mu,
+/// assuming equal variances
+pub fn two_sample_t_test(x : &[f64], y : &[f64], mu : f64, options : &TestOptions) -> SRResult<TestResult> {
+    options.validate()?;
+    check_sample(x, 1)?;
+    check_sample(y, 1)?;
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+    if nx + ny < 3. {
+        return Err(SRError::InvalidArgument);
+    }
+    let (mx, ssx) = mean_and_ss(x);
+    let (my, ssy) = mean_and_ss(y);
+    let dof = nx + ny - 2.;
+    let pooled = (ssx + ssy) / dof;
+    t_result(mx - my, mu, (pooled * (1. / nx + 1. / ny)).sqrt(), dof, options)
+}
+
+
+/// Tests whether the difference of the means of x and y equals mu,
+/// without assuming equal variances
+pub fn welch_t_test(x : &[f64], y : &[f64], mu : f64, options : &TestOptions) -> SRResult<TestResult> {
+    options.validate()?;
+    check_sample(x, 2)?;
+    check_sample(y, 2)?;
+    let (nx, ny) = (x.len() as f64, y.len() as f64);
+    let (mx, ssx) = mean_and_ss(x);
+    let (my, ssy) = mean_and_ss(y);
+    let vx = ssx / (nx - 1.) / nx;
+    let vy = ssy / (ny - 1.) / ny;
+    let v = vx + vy;
+    let dof = v * v / (vx * vx / (nx - 1.) + vy * vy / (ny - 1.));
+    t_result(mx - my, mu, v.sqrt(), dof, options)
+}
+
+
+/// Tests for each column of x whether its mean equals mu
+pub fn one_sample_t_test_cw(x : &MatrixF64, mu : f64, options : &TestOptions) -> SRResult<Vec<TestResult>> {
+    data_columns(x, MissingValues::Propagate).iter()
+        .map(|c| one_sample_t_test(c, mu, options))
+        .collect()
+}
+
+
+/// Tests for each pair of corresponding columns of x and y whether
+/// the difference of their means equals mu, by Welch's test
+pub fn welch_t_test_cw(x : &MatrixF64, y : &MatrixF64, mu : f64, options : &TestOptions) -> SRResult<Vec<TestResult>> {
+    if x.num_cols() != y.num_cols() {
+        return Err(SRError::ColsMismatch);
+    }
+    let xc = data_columns(x, MissingValues::Propagate);
+    let yc = data_columns(y, MissingValues::Propagate);
+    xc.iter().zip(yc.iter())
+        .map(|(a, b)| welch_t_test(a, b, mu, options))
+        .collect()
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use hypothesis::options::Alternative;
+    use test_support::assert_near;
+
+    fn samples() -> (Vec<f64>, Vec<f64>) {
+        (vec![5.1, 4.9, 5.6, 5.8, 6.0, 5.5, 5.3, 4.8], vec![4.4, 4.7, 5.0, 4.2, 4.9, 4.6])
+    }
+
+    fn assert_interval(r : &TestResult, a : f64, b : f64) {
+        let (lo, hi) = r.confidence_interval.unwrap();
+        assert_near(lo, a, 1e-12);
+        assert_near(hi, b, 1e-12);
+    }
+
+    #[test]
+    fn test_one_sample(){
+        let (x, _) = samples();
+        let r = one_sample_t_test(&x, 5., &TestOptions::new()).unwrap();
+        assert_near(r.statistic, 2.485_250_608_738_541_6, 1e-13);
+        assert_near(r.p_value, 0.041_886_813_937_264_23, 1e-12);
+        assert_eq!(r.dof, Some(7.));
+        assert_interval(&r, 5.018_201_337_028_389, 5.731_798_662_971_611);
+        let less = one_sample_t_test(&x, 5., &TestOptions::new().alternative(Alternative::Less)).unwrap();
+        assert_near(less.p_value, 1. - 0.041_886_813_937_264_23 / 2., 1e-12);
+        assert_eq!(less.confidence_interval.unwrap().0, f64::NEG_INFINITY);
+        let greater = one_sample_t_test(&x, 5., &TestOptions::new().alternative(Alternative::Greater)).unwrap();
+        assert_near(greater.p_value, 0.041_886_813_937_264_23 / 2., 1e-12);
+        assert!(one_sample_t_test(&[1.], 0., &TestOptions::new()).is_err());
+        assert!(one_sample_t_test(&[2., 2., 2.], 0., &TestOptions::new()).is_err());
+        assert!(one_sample_t_test(&[1., f64::NAN], 0., &TestOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_two_sample(){
+        let (x, y) = samples();
+        let r = two_sample_t_test(&x, &y, 0., &TestOptions::new()).unwrap();
+        assert_near(r.statistic, 3.618_613_769_815_520_5, 1e-13);
+        assert_near(r.p_value, 0.003_523_512_292_643_914, 1e-11);
+        assert_eq!(r.dof, Some(12.));
+        assert_interval(&r, 0.295_099_845_374_698_9, 1.188_233_487_958_634);
+        let w = welch_t_test(&x, &y, 0., &TestOptions::new()).unwrap();
+        assert_near(w.statistic, 3.810_743_258_483_221, 1e-13);
+        assert_near(w.p_value, 0.002_486_435_696_507_800_7, 1e-11);
+        assert_near(w.dof.unwrap(), 11.984_490_855_819_886, 1e-13);
+        assert_interval(&w, 0.317_553_913_942_254_84, 1.165_779_419_391_078);
+        // Shifting y by mu gives the same test
+        let shifted : Vec<f64> = y.iter().map(|v| v + 0.5).collect();
+        let s = welch_t_test(&x, &shifted, -0.5, &TestOptions::new()).unwrap();
+        assert_near(s.statistic, w.statistic, 1e-12);
+    }
+
+    #[test]
+    fn test_paired(){
+        let x = [12.1, 14.3, 11.8, 13.5, 12.9, 15.0];
+        let y = [11.4, 13.9, 11.9, 12.6, 12.0, 14.1];
+        let r = paired_t_test(&x, &y, 0., &TestOptions::new().confidence(0.9)).unwrap();
+        assert_near(r.statistic, 3.756_780_810_994_390_5, 1e-13);
+        assert_near(r.p_value, 0.013_201_193_982_651_918, 1e-12);
+        assert_eq!(r.dof, Some(5.));
+        assert_interval(&r, 0.285_901_251_788_375, 0.947_432_081_544_958_3);
+        assert!(paired_t_test(&x, &y[1..], 0., &TestOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_columns(){
+        let (x, y) = samples();
+        let m = matrix_cw_f64(6, 2, &[x[0], x[1], x[2], x[3], x[4], x[5], y[0], y[1], y[2], y[3], y[4], y[5]]);
+        let r = one_sample_t_test_cw(&m, 5., &TestOptions::new()).unwrap();
+        assert_eq!(r.len(), 2);
+        assert_eq!(r[1], one_sample_t_test(&y, 5., &TestOptions::new()).unwrap());
+        let w = welch_t_test_cw(&m, &m, 0., &TestOptions::new()).unwrap();
+        assert_eq!(w[0].statistic, 0.);
+        assert!(welch_t_test_cw(&m, &matrix_cw_f64(6, 1, &y), 0., &TestOptions::new()).is_err());
+    }
+}
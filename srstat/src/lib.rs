@@ -1,8 +1,18 @@
 #![doc="Statistics
+
+* ``moments``: sums, means, variances and covariances
+* ``descriptive``: order statistics, quantiles, shape and other means
+* ``correlation``: correlation and cross-covariance matrices
+* ``streaming``: weighted, mergeable online accumulators
+* ``distributions``: probability distributions and random sampling
+* ``hypothesis``: hypothesis tests and multiple testing corrections
 "]
 extern crate num;
+extern crate rand;
 extern crate sralgebra;
 extern crate srmatrix;
+extern crate srlinalg;
+extern crate srnum;
 
 pub mod moments {
     pub mod traits;
@@ -10,4 +20,48 @@ pub mod moments {
     //pub mod view_moments;
 }
 
+pub mod descriptive {
+    pub mod sample;
+    pub mod traits;
+    pub mod matrix_descriptive;
+}
+
+pub mod correlation {
+    pub mod missing;
+    pub mod pearson;
+    pub mod rank;
+    pub mod partial;
+}
+
+pub mod streaming {
+    pub mod univariate;
+    pub mod multivariate;
+}
+
+pub mod distributions {
+    mod support;
+    pub mod traits;
+    pub mod normal;
+    pub mod elementary;
+    pub mod gamma;
+    pub mod sampling;
+    pub mod discrete;
+    pub mod multivariate;
+}
+
+pub mod hypothesis {
+    mod support;
+    pub mod options;
+    pub mod t_test;
+    pub mod anova;
+    pub mod chi_square;
+    pub mod ks;
+    pub mod rank_tests;
+    pub mod normality;
+    pub mod correction;
+}
+
 pub mod api;
+
+#[cfg(test)]
+mod test_support;
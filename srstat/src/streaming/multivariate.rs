@@ -0,0 +1,309 @@
+#![doc="Online means and covariances of several variables
+
+Observations arrive one row at a time. The accumulator keeps the
+total weight W, the weighted mean vector and the matrix of central
+cross products C = sum w_k (x_k - mean) (x_k - mean)^T, updated
+with the weighted form of Welford's recurrence
+
+mean' = mean + w (x - mean) / W'
+C' = C + w (x - mean) (x - mean')^T
+
+and combined across chunks of data with
+
+C = C_a + C_b + (w_a w_b / W) (mean_b - mean_a) (mean_b - mean_a)^T
+
+Memory is O(p^2) whatever the number of observations.
+"]
+
+// std imports
+
+// external imports
+use num::{Float, FromPrimitive};
+
+// local imports
+use sralgebra::FieldPartial;
+use srmatrix::api::*;
+
+
+/// Weighted online accumulator of the mean vector and covariance
+/// matrix of p variables
+#[derive(Debug, Clone)]
+pub struct CovarianceAccumulator<T:FieldPartial + Float> {
+    /// Number of observations with non zero weight
+    count : usize,
+    /// Sum of weights
+    weight : T,
+    /// Weighted means of the variables
+    mean : Vec<T>,
+    /// Central cross products, p x p row major
+    comoments : Vec<T>
+}
+
+
+impl <T:FieldPartial + Float + FromPrimitive> CovarianceAccumulator<T> {
+
+    /// Creates an accumulator of p variables without observations
+    pub fn new(p : usize) -> CovarianceAccumulator<T> {
+        CovarianceAccumulator {
+            count : 0,
+            weight : T::zero(),
+            mean : vec![T::zero(); p],
+            comoments : vec![T::zero(); p * p]
+        }
+    }
+
+    /// Creates an accumulator over the rows of a data matrix
+    pub fn from_matrix(x : &Matrix<T>) -> CovarianceAccumulator<T> {
+        let mut acc = CovarianceAccumulator::new(x.num_cols());
+        acc.push_rows(x).unwrap();
+        acc
+    }
+
+    /// Returns the number of variables
+    pub fn dim(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Returns the number of observations with non zero weight
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the sum of weights
+    pub fn weight(&self) -> T {
+        self.weight
+    }
+
+    /// Adds an observation of unit weight
+    pub fn push(&mut self, row : &[T]) -> SRResult<()> {
+        self.push_weighted(row, T::one())
+    }
+
+    /// Adds a weighted observation. Weights must be finite and
+    /// non negative; observations of zero weight are ignored.
+    pub fn push_weighted(&mut self, row : &[T], weight : T) -> SRResult<()> {
+        if row.len() != self.dim() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if !weight.is_finite() || weight < T::zero() {
+            return Err(SRError::InvalidArgument);
+        }
+        if weight == T::zero() {
+            return Ok(());
+        }
+        let p = self.dim();
+        self.count += 1;
+        self.weight = self.weight + weight;
+        let ratio = weight / self.weight;
+        let delta : Vec<T> = row.iter().zip(self.mean.iter()).map(|(&x, &m)| x - m).collect();
+        for (m, &d) in self.mean.iter_mut().zip(delta.iter()) {
+            *m = *m + d * ratio;
+        }
+        for i in 0..p {
+            let wd = weight * delta[i];
+            for j in 0..p {
+                let c = &mut self.comoments[i * p + j];
+                *c = *c + wd * (row[j] - self.mean[j]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds each row of a data matrix as an observation of unit
+    /// weight
+    pub fn push_rows(&mut self, x : &Matrix<T>) -> SRResult<()> {
+        let weights = vec![T::one(); x.num_rows()];
+        self.push_rows_weighted(x, &weights)
+    }
+
+    /// Adds each row of a data matrix as an observation with the
+    /// corresponding weight
+    pub fn push_rows_weighted(&mut self, x : &Matrix<T>, weights : &[T]) -> SRResult<()> {
+        if x.num_cols() != self.dim() {
+            return Err(SRError::ColsMismatch);
+        }
+        if x.num_rows() != weights.len() {
+            return Err(SRError::RowsMismatch);
+        }
+        let mut row = vec![T::zero(); self.dim()];
+        for (r, &w) in weights.iter().enumerate() {
+            for (c, v) in row.iter_mut().enumerate() {
+                *v = x.get(r, c).unwrap();
+            }
+            self.push_weighted(&row, w)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the statistics of another accumulator, as if its
+    /// observations had been pushed into this one
+    pub fn merge(&mut self, other : &CovarianceAccumulator<T>) -> SRResult<()> {
+        if other.dim() != self.dim() {
+            return Err(SRError::DimensionsMismatch);
+        }
+        if other.count == 0 {
+            return Ok(());
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return Ok(());
+        }
+        let p = self.dim();
+        let w = self.weight + other.weight;
+        let scale = self.weight * other.weight / w;
+        let delta : Vec<T> = other.mean.iter().zip(self.mean.iter()).map(|(&b, &a)| b - a).collect();
+        for i in 0..p {
+            for j in 0..p {
+                let k = i * p + j;
+                self.comoments[k] = self.comoments[k] + other.comoments[k] + scale * delta[i] * delta[j];
+            }
+        }
+        let ratio = other.weight / w;
+        for (m, &d) in self.mean.iter_mut().zip(delta.iter()) {
+            *m = *m + d * ratio;
+        }
+        self.weight = w;
+        self.count += other.count;
+        Ok(())
+    }
+
+    /// Returns the weighted means as a row vector, NaN without
+    /// observations
+    pub fn mean(&self) -> Matrix<T> {
+        let mut result = Matrix::new_uninitialized(1, self.dim());
+        for (c, &m) in self.mean.iter().enumerate() {
+            result.set(0, c, if self.count == 0 { T::nan() } else { m });
+        }
+        result
+    }
+
+    /// Returns the central cross products divided by the given
+    /// denominator, NaN if it is not positive
+    fn scaled_comoments(&self, denom : T) -> Matrix<T> {
+        let p = self.dim();
+        let mut result = Matrix::new_uninitialized(p, p);
+        for i in 0..p {
+            for j in 0..p {
+                let value = if denom > T::zero() { self.comoments[i * p + j] / denom } else { T::nan() };
+                result.set(i, j, value);
+            }
+        }
+        result
+    }
+
+    /// Returns the sample variances as a row vector
+    pub fn variance(&self) -> Matrix<T> {
+        let p = self.dim();
+        let mut result = Matrix::new_uninitialized(1, p);
+        let denom = self.weight - T::one();
+        for i in 0..p {
+            let value = if denom > T::zero() { self.comoments[i * p + i] / denom } else { T::nan() };
+            result.set(0, i, value);
+        }
+        result
+    }
+
+    /// Returns the p x p sample covariance matrix C / (W - 1)
+    pub fn cov(&self) -> Matrix<T> {
+        self.scaled_comoments(self.weight - T::one())
+    }
+
+    /// Returns the p x p population covariance matrix C / W
+    pub fn population_cov(&self) -> Matrix<T> {
+        self.scaled_comoments(self.weight)
+    }
+
+    /// Returns the p x p Pearson correlation matrix
+    pub fn corrcoef(&self) -> Matrix<T> {
+        let p = self.dim();
+        let mut result = Matrix::new_uninitialized(p, p);
+        for i in 0..p {
+            for j in 0..p {
+                let denom = (self.comoments[i * p + i] * self.comoments[j * p + j]).sqrt();
+                let value = if self.count < 2 { T::nan() } else { self.comoments[i * p + j] / denom };
+                result.set(i, j, if value.is_nan() { value } else { value.max(-T::one()).min(T::one()) });
+            }
+        }
+        result
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use moments::traits::Moments;
+    use test_support::assert_close;
+
+    fn data() -> MatrixF64 {
+        matrix_rw_f64(6, 3, &[
+            1., 2., 5.,
+            2., 4., 3.,
+            3., 5., 4.,
+            4., 4., 1.,
+            5., 5., 2.,
+            6., 7., 2.])
+    }
+
+    #[test]
+    fn test_against_batch(){
+        let x = data();
+        let acc = CovarianceAccumulator::from_matrix(&x);
+        assert_eq!((acc.dim(), acc.count()), (3, 6));
+        assert_close(&acc.mean(), &x.mean_cw(), 1e-15);
+        assert_close(&acc.variance(), &x.var_cw(), 1e-14);
+        assert_close(&acc.cov(), &x.cov(), 1e-14);
+        let mut population = x.cov();
+        population.div_scalar(6. / 5.).unwrap();
+        assert_close(&acc.population_cov(), &population, 1e-14);
+        let r = acc.corrcoef();
+        assert_eq!(r.get(1, 1).unwrap(), 1.);
+        let c = x.cov();
+        let expected = c.get(0, 2).unwrap() / (c.get(0, 0).unwrap() * c.get(2, 2).unwrap()).sqrt();
+        assert!((r.get(2, 0).unwrap() - expected).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_merge_and_weights(){
+        let x = data();
+        let mut merged = CovarianceAccumulator::from_matrix(&x.view(0, 0, 4, 3).to_matrix());
+        merged.merge(&CovarianceAccumulator::from_matrix(&x.view(4, 0, 2, 3).to_matrix())).unwrap();
+        assert_eq!(merged.count(), 6);
+        assert_close(&merged.cov(), &x.cov(), 1e-14);
+        assert_close(&merged.mean(), &x.mean_cw(), 1e-15);
+        // Weight 2 on the first row repeats it
+        let mut weighted = CovarianceAccumulator::new(3);
+        weighted.push_rows_weighted(&x, &[2., 1., 1., 1., 1., 0.]).unwrap();
+        let repeated = matrix_rw_f64(6, 3, &[
+            1., 2., 5.,
+            1., 2., 5.,
+            2., 4., 3.,
+            3., 5., 4.,
+            4., 4., 1.,
+            5., 5., 2.]);
+        assert_eq!(weighted.count(), 5);
+        assert_close(&weighted.cov(), &repeated.cov(), 1e-14);
+        assert!(weighted.push(&[1., 2.]).is_err());
+        assert!(weighted.push_weighted(&[1., 2., 3.], -1.).is_err());
+        assert!(weighted.merge(&CovarianceAccumulator::new(2)).is_err());
+        assert!(weighted.push_rows(&matrix_rw_f64(1, 2, &[1., 2.])).is_err());
+    }
+
+    #[test]
+    fn test_stability(){
+        // A large common offset leaves the covariance unchanged
+        let mut shifted = data();
+        shifted.add_scalar(1e8);
+        let acc = CovarianceAccumulator::from_matrix(&shifted);
+        assert_close(&acc.cov(), &data().cov(), 1e-7);
+        let empty : CovarianceAccumulator<f32> = CovarianceAccumulator::new(2);
+        assert!(empty.mean().get(0, 1).unwrap().is_nan());
+        assert!(empty.cov().get(0, 0).unwrap().is_nan());
+    }
+}
@@ -0,0 +1,282 @@
+#![doc="Online moments of a single variable
+
+The accumulator keeps the total weight W, the weighted mean and the
+central sums M_k = sum w_i (x_i - mean)^k for k = 2, 3, 4. Two
+accumulators over disjoint data combine exactly (Chan et al., with
+the higher order terms of Pébay); adding an observation is the
+combination with an accumulator holding that observation only, so
+no pass over the data is repeated and nothing is subtracted from a
+large running sum of squares.
+
+Weights are frequency weights: an observation of weight 2 counts
+as two equal observations. The unweighted statistics follow from
+unit weights.
+"]
+
+// std imports
+
+// external imports
+use num::{Float, FromPrimitive};
+
+// local imports
+use srmatrix::api::{SRError, SRResult};
+
+
+/// Weighted online accumulator of the mean, variance, skewness and
+/// kurtosis of a variable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MomentAccumulator<T:Float> {
+    /// Number of observations with non zero weight
+    count : usize,
+    /// Sum of weights
+    weight : T,
+    /// Sum of squared weights
+    weight_sqr : T,
+    /// Weighted mean
+    mean : T,
+    /// Central sums of order 2, 3 and 4
+    m2 : T,
+    m3 : T,
+    m4 : T
+}
+
+
+impl <T:Float + FromPrimitive> Default for MomentAccumulator<T> {
+    fn default() -> MomentAccumulator<T> {
+        MomentAccumulator::new()
+    }
+}
+
+
+impl <T:Float + FromPrimitive> MomentAccumulator<T> {
+
+    /// Creates an accumulator without observations
+    pub fn new() -> MomentAccumulator<T> {
+        MomentAccumulator {
+            count : 0,
+            weight : T::zero(),
+            weight_sqr : T::zero(),
+            mean : T::zero(),
+            m2 : T::zero(),
+            m3 : T::zero(),
+            m4 : T::zero()
+        }
+    }
+
+    /// Creates an accumulator over a slice of observations
+    pub fn from_slice(values : &[T]) -> MomentAccumulator<T> {
+        let mut acc = MomentAccumulator::new();
+        acc.extend(values);
+        acc
+    }
+
+    /// Adds an observation of unit weight
+    pub fn push(&mut self, value : T) {
+        self.merge_point(value, T::one());
+    }
+
+    /// Adds a weighted observation. Weights must be finite and
+    /// non negative; observations of zero weight are ignored.
+    pub fn push_weighted(&mut self, value : T, weight : T) -> SRResult<()> {
+        if !weight.is_finite() || weight < T::zero() {
+            return Err(SRError::InvalidArgument);
+        }
+        if weight > T::zero() {
+            self.merge_point(value, weight);
+        }
+        Ok(())
+    }
+
+    /// Adds observations of unit weight
+    pub fn extend(&mut self, values : &[T]) {
+        for &v in values {
+            self.push(v);
+        }
+    }
+
+    /// Adds the statistics of another accumulator, as if its
+    /// observations had been pushed into this one
+    pub fn merge(&mut self, other : &MomentAccumulator<T>) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let (wa, wb) = (self.weight, other.weight);
+        let w = wa + wb;
+        let delta = other.mean - self.mean;
+        let delta_w = delta / w;
+        let delta_w2 = delta_w * delta_w;
+        let three = T::from_f64(3.).unwrap();
+        let four = T::from_f64(4.).unwrap();
+        let six = T::from_f64(6.).unwrap();
+        let m2 = self.m2 + other.m2 + delta * delta_w * wa * wb;
+        let m3 = self.m3 + other.m3
+            + delta * delta_w2 * wa * wb * (wa - wb)
+            + three * delta_w * (wa * other.m2 - wb * self.m2);
+        let m4 = self.m4 + other.m4
+            + delta * delta_w2 * delta_w * wa * wb * (wa * wa - wa * wb + wb * wb)
+            + six * delta_w2 * (wa * wa * other.m2 + wb * wb * self.m2)
+            + four * delta_w * (wa * other.m3 - wb * self.m3);
+        self.mean = self.mean + delta_w * wb;
+        self.m2 = m2;
+        self.m3 = m3;
+        self.m4 = m4;
+        self.weight = w;
+        self.weight_sqr = self.weight_sqr + other.weight_sqr;
+        self.count += other.count;
+    }
+
+    /// Adds a single observation with positive weight
+    fn merge_point(&mut self, value : T, weight : T) {
+        let point = MomentAccumulator {
+            count : 1,
+            weight : weight,
+            weight_sqr : weight * weight,
+            mean : value,
+            m2 : T::zero(),
+            m3 : T::zero(),
+            m4 : T::zero()
+        };
+        self.merge(&point);
+    }
+
+    /// Returns the number of observations with non zero weight
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the sum of weights
+    pub fn weight(&self) -> T {
+        self.weight
+    }
+
+    /// Returns the weighted mean, NaN without observations
+    pub fn mean(&self) -> T {
+        if self.count == 0 { T::nan() } else { self.mean }
+    }
+
+    /// Returns the sample variance sum w (x - mean)^2 / (W - 1),
+    /// NaN unless W > 1
+    pub fn variance(&self) -> T {
+        if self.weight > T::one() { self.m2 / (self.weight - T::one()) } else { T::nan() }
+    }
+
+    /// Returns the unbiased variance under reliability weights,
+    /// sum w (x - mean)^2 / (W - sum w^2 / W)
+    pub fn reliability_variance(&self) -> T {
+        let denom = self.weight - self.weight_sqr / self.weight;
+        if self.count > 1 && denom > T::zero() { self.m2 / denom } else { T::nan() }
+    }
+
+    /// Returns the population variance sum w (x - mean)^2 / W
+    pub fn population_variance(&self) -> T {
+        if self.count == 0 { T::nan() } else { self.m2 / self.weight }
+    }
+
+    /// Returns the sample standard deviation
+    pub fn std_dev(&self) -> T {
+        self.variance().sqrt()
+    }
+
+    /// Returns the (biased) skewness sqrt(W) M_3 / M_2^(3/2)
+    pub fn skewness(&self) -> T {
+        if self.count < 2 {
+            return T::nan();
+        }
+        self.weight.sqrt() * self.m3 / self.m2.powf(T::from_f64(1.5).unwrap())
+    }
+
+    /// Returns the excess kurtosis W M_4 / M_2^2 - 3
+    pub fn kurtosis(&self) -> T {
+        if self.count < 2 {
+            return T::nan();
+        }
+        self.weight * self.m4 / (self.m2 * self.m2) - T::from_f64(3.).unwrap()
+    }
+}
+
+
+/******************************************************
+ *
+ *   Unit tests
+ *
+ *******************************************************/
+#[cfg(test)]
+mod test{
+
+    use super::*;
+    use descriptive::sample;
+    use test_support::assert_near;
+
+    #[test]
+    fn test_against_batch(){
+        let x = [2., 8., 0., 4., 1., 9., 9., 0.];
+        let acc = MomentAccumulator::from_slice(&x);
+        assert_eq!(acc.count(), 8);
+        assert_near(acc.mean(), 4.125, 1e-15);
+        // sum (x - mean)^2 = 110.875
+        assert_near(acc.variance(), 110.875 / 7., 1e-15);
+        assert_near(acc.population_variance(), 110.875 / 8., 1e-15);
+        assert_near(acc.skewness(), sample::skewness(&x), 1e-14);
+        assert_near(acc.kurtosis(), sample::kurtosis(&x), 1e-14);
+    }
+
+    #[test]
+    fn test_merge(){
+        let x : Vec<f64> = (0..100).map(|i| ((i * 37) % 101) as f64 / 7.).collect();
+        let all = MomentAccumulator::from_slice(&x);
+        let mut merged = MomentAccumulator::new();
+        for chunk in x.chunks(13) {
+            merged.merge(&MomentAccumulator::from_slice(chunk));
+        }
+        assert_eq!(merged.count(), 100);
+        assert_near(merged.mean(), all.mean(), 1e-14);
+        assert_near(merged.variance(), all.variance(), 1e-14);
+        assert_near(merged.skewness(), all.skewness(), 1e-12);
+        assert_near(merged.kurtosis(), all.kurtosis(), 1e-12);
+        let mut empty = MomentAccumulator::new();
+        empty.merge(&all);
+        assert_eq!(empty, all);
+        empty.merge(&MomentAccumulator::new());
+        assert_eq!(empty, all);
+    }
+
+    #[test]
+    fn test_weights(){
+        // Integer weights repeat observations
+        let mut weighted = MomentAccumulator::new();
+        weighted.push_weighted(1., 2.).unwrap();
+        weighted.push_weighted(4., 1.).unwrap();
+        weighted.push_weighted(5., 3.).unwrap();
+        weighted.push_weighted(7., 0.).unwrap();
+        let repeated = MomentAccumulator::from_slice(&[1., 1., 4., 5., 5., 5.]);
+        assert_eq!(weighted.count(), 3);
+        assert_eq!(weighted.weight(), 6.);
+        assert_near(weighted.mean(), repeated.mean(), 1e-15);
+        assert_near(weighted.variance(), repeated.variance(), 1e-15);
+        assert_near(weighted.skewness(), repeated.skewness(), 1e-14);
+        assert_near(weighted.kurtosis(), repeated.kurtosis(), 1e-14);
+        // M_2 = 19.5 and the squared weights sum to 14
+        assert_near(weighted.reliability_variance(), 19.5 / (6. - 14. / 6.), 1e-15);
+        assert!(weighted.push_weighted(1., -1.).is_err());
+        assert!(weighted.push_weighted(1., f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_stability(){
+        // Small spread around a large offset
+        let mut acc = MomentAccumulator::new();
+        for i in 0..1000 {
+            acc.push(1e9 + (i % 4) as f64);
+        }
+        assert_near(acc.population_variance(), 1.25, 1e-9);
+        let empty : MomentAccumulator<f32> = MomentAccumulator::new();
+        assert!(empty.mean().is_nan() && empty.variance().is_nan());
+        let one = MomentAccumulator::from_slice(&[3f32]);
+        assert_eq!(one.mean(), 3.);
+        assert!(one.variance().is_nan() && one.skewness().is_nan());
+    }
+}
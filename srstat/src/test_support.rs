@@ -0,0 +1,25 @@
+#![doc="Assertions shared by the unit tests
+"]
+
+// srmatrix imports
+use srmatrix::api::*;
+
+
+/// Asserts that a agrees with the expected value b up to the
+/// relative tolerance
+pub fn assert_near(a : f64, b : f64, tolerance : f64) {
+    assert!((a - b).abs() <= tolerance * b.abs(), "{} {}", a, b);
+}
+
+
+/// Asserts that the matrices have the same size and agree entrywise
+/// up to the absolute tolerance
+pub fn assert_close(a : &MatrixF64, b : &MatrixF64, tolerance : f64) {
+    assert_eq!(a.size(), b.size());
+    for r in 0..a.num_rows() {
+        for c in 0..a.num_cols() {
+            let (u, v) = (a.get(r, c).unwrap(), b.get(r, c).unwrap());
+            assert!((u - v).abs() <= tolerance, "{} {} {} {}", r, c, u, v);
+        }
+    }
+}